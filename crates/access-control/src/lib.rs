@@ -0,0 +1,113 @@
+//! # Access Control
+//!
+//! Role-based access control any contract can embed. Roles are plain
+//! `Symbol`s (`symbol_short!("minter")`, `symbol_short!("pauser")`, ...)
+//! rather than a fixed enum, so each contract defines its own vocabulary
+//! and an account may hold any number of roles at once.
+//!
+//! ```ignore
+//! const MINTER: Symbol = symbol_short!("minter");
+//!
+//! pub fn mint(env: Env, caller: Address, to: Address, amount: i128) -> Result<(), Error> {
+//!     caller.require_auth();
+//!     recipes_access_control::require_role(&env, &caller, &MINTER)?; // via From<AccessError>
+//!     // ...
+//! }
+//! ```
+//!
+//! Storage lives in persistent entries under tuple keys namespaced with
+//! `"access"`, so it cannot collide with the host contract's own keys:
+//!
+//! | Key                                | Value          |
+//! |------------------------------------|----------------|
+//! | `("access", role, account)`        | `true`         |
+//! | `("access", "members", role)`      | `Vec<Address>` |
+//!
+//! Like `recipes-pausable`, nothing here calls `require_auth` or publishes
+//! events: deciding who may grant a role, and how to announce it, is the
+//! host contract's job.
+
+#![no_std]
+
+use soroban_sdk::{contracterror, symbol_short, Address, Env, Symbol, Vec};
+
+/// Namespace prefix for every storage key this crate writes.
+pub const NAMESPACE: Symbol = symbol_short!("access");
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AccessError {
+    /// The account does not hold the required role.
+    MissingRole = 1,
+}
+
+fn member_key(account: &Address, role: &Symbol) -> (Symbol, Symbol, Address) {
+    (NAMESPACE, role.clone(), account.clone())
+}
+
+fn members_key(role: &Symbol) -> (Symbol, Symbol, Symbol) {
+    (NAMESPACE, symbol_short!("members"), role.clone())
+}
+
+/// Give `role` to `account`. Returns `false` if it already held the role.
+pub fn grant(env: &Env, account: &Address, role: &Symbol) -> bool {
+    if has(env, account, role) {
+        return false;
+    }
+    env.storage()
+        .persistent()
+        .set(&member_key(account, role), &true);
+    let mut list = members(env, role);
+    list.push_back(account.clone());
+    env.storage().persistent().set(&members_key(role), &list);
+    true
+}
+
+/// Take `role` away from `account`. Returns `false` if it did not hold it.
+pub fn revoke(env: &Env, account: &Address, role: &Symbol) -> bool {
+    if !has(env, account, role) {
+        return false;
+    }
+    env.storage()
+        .persistent()
+        .remove(&member_key(account, role));
+    let mut list = members(env, role);
+    if let Some(index) = list.first_index_of(account) {
+        list.remove(index);
+    }
+    if list.is_empty() {
+        env.storage().persistent().remove(&members_key(role));
+    } else {
+        env.storage().persistent().set(&members_key(role), &list);
+    }
+    true
+}
+
+/// Whether `account` currently holds `role`.
+pub fn has(env: &Env, account: &Address, role: &Symbol) -> bool {
+    env.storage().persistent().has(&member_key(account, role))
+}
+
+/// Fail with [`AccessError::MissingRole`] unless `account` holds `role`.
+pub fn require_role(env: &Env, account: &Address, role: &Symbol) -> Result<(), AccessError> {
+    if !has(env, account, role) {
+        return Err(AccessError::MissingRole);
+    }
+    Ok(())
+}
+
+/// Every holder of `role`, in the order they were granted it.
+pub fn members(env: &Env, role: &Symbol) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&members_key(role))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Number of accounts holding `role`.
+pub fn member_count(env: &Env, role: &Symbol) -> u32 {
+    members(env, role).len()
+}
+
+mod test;
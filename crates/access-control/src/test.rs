@@ -0,0 +1,123 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, testutils::Address as _, vec, Address, Env, Symbol,
+};
+
+const MINTER: Symbol = symbol_short!("minter");
+const PAUSER: Symbol = symbol_short!("pauser");
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum HostError {
+    Forbidden = 10,
+}
+
+impl From<AccessError> for HostError {
+    fn from(err: AccessError) -> Self {
+        match err {
+            AccessError::MissingRole => HostError::Forbidden,
+        }
+    }
+}
+
+#[contract]
+pub struct Host;
+
+#[contractimpl]
+impl Host {
+    pub fn grant(env: Env, account: Address, role: Symbol) -> bool {
+        super::grant(&env, &account, &role)
+    }
+
+    pub fn revoke(env: Env, account: Address, role: Symbol) -> bool {
+        super::revoke(&env, &account, &role)
+    }
+
+    pub fn has(env: Env, account: Address, role: Symbol) -> bool {
+        super::has(&env, &account, &role)
+    }
+
+    pub fn members(env: Env, role: Symbol) -> Vec<Address> {
+        super::members(&env, &role)
+    }
+
+    pub fn member_count(env: Env, role: Symbol) -> u32 {
+        super::member_count(&env, &role)
+    }
+
+    pub fn mint(env: Env, caller: Address) -> Result<u32, HostError> {
+        require_role(&env, &caller, &MINTER)?;
+        Ok(1)
+    }
+}
+
+fn setup() -> (Env, HostClient<'static>) {
+    let env = Env::default();
+    let client = HostClient::new(&env, &env.register_contract(None, Host));
+    (env, client)
+}
+
+#[test]
+fn test_grant_and_revoke() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+
+    assert!(!client.has(&alice, &MINTER));
+    assert!(client.grant(&alice, &MINTER));
+    assert!(!client.grant(&alice, &MINTER), "second grant is a no-op");
+    assert!(client.has(&alice, &MINTER));
+
+    assert!(client.revoke(&alice, &MINTER));
+    assert!(!client.revoke(&alice, &MINTER), "second revoke is a no-op");
+    assert!(!client.has(&alice, &MINTER));
+}
+
+#[test]
+fn test_roles_are_independent() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    client.grant(&alice, &MINTER);
+    client.grant(&alice, &PAUSER);
+
+    client.revoke(&alice, &PAUSER);
+    assert!(client.has(&alice, &MINTER));
+    assert!(!client.has(&alice, &PAUSER));
+}
+
+#[test]
+fn test_require_role_maps_into_host_error() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    assert_eq!(client.try_mint(&alice), Err(Ok(HostError::Forbidden)));
+    client.grant(&alice, &MINTER);
+    assert_eq!(client.mint(&alice), 1);
+}
+
+#[test]
+fn test_member_enumeration() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    client.grant(&alice, &MINTER);
+    client.grant(&bob, &MINTER);
+    client.grant(&carol, &MINTER);
+    client.grant(&bob, &PAUSER);
+
+    assert_eq!(
+        client.members(&MINTER),
+        vec![&env, alice.clone(), bob.clone(), carol.clone()]
+    );
+    assert_eq!(client.members(&PAUSER), vec![&env, bob.clone()]);
+
+    client.revoke(&bob, &MINTER);
+    assert_eq!(client.members(&MINTER), vec![&env, alice, carol]);
+    assert_eq!(client.member_count(&MINTER), 2);
+
+    client.revoke(&bob, &PAUSER);
+    assert_eq!(client.member_count(&PAUSER), 0);
+    assert_eq!(client.members(&PAUSER), vec![&env]);
+}
@@ -0,0 +1,111 @@
+//! # Errors
+//!
+//! A catalogue of error codes shared across the cookbook, so that the same
+//! failure has the same number in every example.
+//!
+//! Codes are split into two ranges:
+//!
+//! - `1..=99` ([`COMMON_MIN`]..=[`COMMON_MAX`]) are reserved for
+//!   [`CommonError`]. A contract that reports one of these conditions uses
+//!   the catalogue's number for it.
+//! - `100..` ([`CONTRACT_MIN`] and up) are free for each contract's own
+//!   variants.
+//!
+//! `#[contracterror]` enums cannot be nested and their discriminants must be
+//! literals, so a contract does not wrap `CommonError`. It declares its own
+//! enum, repeats the common variants it uses with the same numbers, and
+//! converts with `From`:
+//!
+//! ```ignore
+//! #[contracterror]
+//! pub enum Error {
+//!     NotInitialized = 1,    // CommonError::NotInitialized
+//!     DelayOutOfRange = 100, // CONTRACT_MIN
+//! }
+//!
+//! impl From<CommonError> for Error { /* ... */ }
+//! ```
+//!
+//! A test asserting `Error::NotInitialized as u32 ==
+//! CommonError::NotInitialized as u32` keeps the copy honest.
+//!
+//! Once published, a code never changes meaning. New common variants take
+//! the next free number below [`CONTRACT_MIN`].
+
+#![no_std]
+
+use soroban_sdk::contracterror;
+
+/// Lowest code in the shared range.
+pub const COMMON_MIN: u32 = 1;
+/// Highest code in the shared range.
+pub const COMMON_MAX: u32 = 99;
+/// First code a contract may use for its own variants.
+pub const CONTRACT_MIN: u32 = 100;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CommonError {
+    /// The contract has not been initialized yet.
+    NotInitialized = 1,
+    /// `initialize` was called a second time.
+    AlreadyInitialized = 2,
+    /// The caller is not allowed to do this.
+    Unauthorized = 3,
+    /// The operation is blocked because the contract is paused.
+    Paused = 4,
+    /// Arithmetic went out of range.
+    Overflow = 5,
+    /// The requested entry does not exist.
+    NotFound = 6,
+    /// A deadline or time window has passed.
+    Expired = 7,
+    /// An argument was rejected.
+    InvalidInput = 8,
+    /// A divisor was zero.
+    DivisionByZero = 9,
+}
+
+impl CommonError {
+    /// Every variant, in code order.
+    pub const ALL: [CommonError; 9] = [
+        CommonError::NotInitialized,
+        CommonError::AlreadyInitialized,
+        CommonError::Unauthorized,
+        CommonError::Paused,
+        CommonError::Overflow,
+        CommonError::NotFound,
+        CommonError::Expired,
+        CommonError::InvalidInput,
+        CommonError::DivisionByZero,
+    ];
+
+    /// The variant with this code, if the code is a common one.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|err| *err as u32 == code)
+    }
+
+    /// The variant name, as written in Rust.
+    pub fn name(self) -> &'static str {
+        match self {
+            CommonError::NotInitialized => "NotInitialized",
+            CommonError::AlreadyInitialized => "AlreadyInitialized",
+            CommonError::Unauthorized => "Unauthorized",
+            CommonError::Paused => "Paused",
+            CommonError::Overflow => "Overflow",
+            CommonError::NotFound => "NotFound",
+            CommonError::Expired => "Expired",
+            CommonError::InvalidInput => "InvalidInput",
+            CommonError::DivisionByZero => "DivisionByZero",
+        }
+    }
+}
+
+/// The catalogue name for a contract error code, or `"Unknown"` for codes
+/// outside it, including every contract-specific code.
+pub fn code_to_name(code: u32) -> &'static str {
+    CommonError::from_code(code).map_or("Unknown", CommonError::name)
+}
+
+mod test;
@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use super::*;
+
+#[test]
+fn test_every_variant_round_trips() {
+    for err in CommonError::ALL {
+        let code = err as u32;
+        assert_eq!(CommonError::from_code(code), Some(err));
+        assert_eq!(code_to_name(code), err.name());
+        assert!((COMMON_MIN..=COMMON_MAX).contains(&code));
+
+        // The same code survives the trip through the host error type.
+        let host = soroban_sdk::Error::from(err);
+        assert_eq!(host, soroban_sdk::Error::from_contract_error(code));
+        assert_eq!(CommonError::try_from(host), Ok(err));
+    }
+}
+
+#[test]
+fn test_all_is_exhaustive_and_ordered() {
+    // Adding a variant without listing it in ALL fails to compile here.
+    for err in CommonError::ALL {
+        match err {
+            CommonError::NotInitialized
+            | CommonError::AlreadyInitialized
+            | CommonError::Unauthorized
+            | CommonError::Paused
+            | CommonError::Overflow
+            | CommonError::NotFound
+            | CommonError::Expired
+            | CommonError::InvalidInput
+            | CommonError::DivisionByZero => {}
+        }
+    }
+    for (i, err) in CommonError::ALL.into_iter().enumerate() {
+        assert_eq!(err as u32, i as u32 + 1);
+    }
+}
+
+#[test]
+fn test_codes_are_stable() {
+    // Published codes must never change.
+    assert_eq!(CommonError::NotInitialized as u32, 1);
+    assert_eq!(CommonError::AlreadyInitialized as u32, 2);
+    assert_eq!(CommonError::Unauthorized as u32, 3);
+    assert_eq!(CommonError::Paused as u32, 4);
+    assert_eq!(CommonError::Overflow as u32, 5);
+    assert_eq!(CommonError::NotFound as u32, 6);
+    assert_eq!(CommonError::Expired as u32, 7);
+    assert_eq!(CommonError::InvalidInput as u32, 8);
+    assert_eq!(CommonError::DivisionByZero as u32, 9);
+}
+
+#[test]
+fn test_unknown_codes() {
+    let known = (0..=CONTRACT_MIN + 100)
+        .filter(|code| code_to_name(*code) != "Unknown")
+        .count();
+    assert_eq!(known, CommonError::ALL.len());
+
+    assert_eq!(code_to_name(0), "Unknown");
+    assert_eq!(code_to_name(CONTRACT_MIN), "Unknown");
+    assert_eq!(code_to_name(u32::MAX), "Unknown");
+    assert_eq!(CommonError::from_code(0), None);
+}
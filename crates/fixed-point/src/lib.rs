@@ -0,0 +1,281 @@
+//! # Fixed Point
+//!
+//! Decimal fixed-point arithmetic for contracts: an `i128` scaled by 10^7,
+//! the same 7 decimals Stellar assets use. `I128F7::from_raw(15_000_000)`
+//! is 1.5.
+//!
+//! ```ignore
+//! use recipes_fixed_point::{I128F7, Rounding};
+//!
+//! // 5% a period, compounded 12 times.
+//! let rate = I128F7::from_ratio(5, 100)?;
+//! let growth = I128F7::ONE.add(rate)?.pow_int(12)?;
+//! let balance = I128F7::from_amount(principal, 7, Rounding::Floor)?
+//!     .mul(growth)?
+//!     .to_amount(7, Rounding::Floor)?; // via From<FixedError>
+//! ```
+//!
+//! ## Rounding
+//!
+//! `mul`, `div`, `pow_int` and `sqrt` truncate toward zero, so each result
+//! is within one ULP (10^-7) of the exact value; `pow_int` truncates once
+//! per multiplication. Intermediate products are never formed in a way that
+//! can overflow: an operation fails with `Overflow` only when its result
+//! does not fit.
+//!
+//! Converting to and from token amounts takes an explicit [`Rounding`]. As
+//! a rule, round in the contract's favour: `Floor` for what it pays out,
+//! `Ceil` for what it charges.
+
+#![no_std]
+
+use soroban_sdk::contracterror;
+
+/// Number of decimal places.
+pub const DECIMALS: u32 = 7;
+
+/// Raw value of 1.0.
+pub const SCALE: i128 = 10_000_000;
+
+/// Most Newton steps `sqrt` takes before giving up. Starting from
+/// `max(x, 1.0)` it needs at most about 70.
+pub const MAX_SQRT_ITERATIONS: u32 = 100;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FixedError {
+    /// The result does not fit in an `I128F7`.
+    Overflow = 1,
+    DivisionByZero = 2,
+    /// Square root of a negative number.
+    NegativeSqrt = 3,
+    /// `sqrt` did not settle within `MAX_SQRT_ITERATIONS`.
+    NoConvergence = 4,
+}
+
+/// How to round when a value has more precision than its target.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    /// Toward negative infinity.
+    Floor,
+    /// Toward positive infinity.
+    Ceil,
+    /// To the nearest value; ties round away from zero.
+    Nearest,
+}
+
+/// An `i128` scaled by 10^7.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct I128F7(i128);
+
+// The arithmetic methods return `Result`, so they cannot implement the
+// `core::ops` traits whose names they share.
+#[allow(clippy::should_implement_trait)]
+impl I128F7 {
+    pub const ZERO: I128F7 = I128F7(0);
+    pub const ONE: I128F7 = I128F7(SCALE);
+    pub const MAX: I128F7 = I128F7(i128::MAX);
+    pub const MIN: I128F7 = I128F7(i128::MIN);
+
+    /// Wrap a value that is already scaled by 10^7.
+    pub const fn from_raw(raw: i128) -> Self {
+        I128F7(raw)
+    }
+
+    /// The underlying value, scaled by 10^7.
+    pub const fn raw(self) -> i128 {
+        self.0
+    }
+
+    /// The whole number `n`.
+    pub fn from_int(n: i128) -> Result<Self, FixedError> {
+        n.checked_mul(SCALE).map(I128F7).ok_or(FixedError::Overflow)
+    }
+
+    /// `num / den`, truncated toward zero.
+    pub fn from_ratio(num: i128, den: i128) -> Result<Self, FixedError> {
+        I128F7(num).div(I128F7(den))
+    }
+
+    /// A token amount with `decimals` places. Precision beyond 7 decimals
+    /// is rounded with `rounding`.
+    pub fn from_amount(
+        amount: i128,
+        decimals: u32,
+        rounding: Rounding,
+    ) -> Result<Self, FixedError> {
+        rescale(amount, decimals, DECIMALS, rounding).map(I128F7)
+    }
+
+    /// This value as a token amount with `decimals` places, rounded with
+    /// `rounding` when `decimals < 7`.
+    pub fn to_amount(self, decimals: u32, rounding: Rounding) -> Result<i128, FixedError> {
+        rescale(self.0, DECIMALS, decimals, rounding)
+    }
+
+    pub fn add(self, rhs: Self) -> Result<Self, FixedError> {
+        self.0
+            .checked_add(rhs.0)
+            .map(I128F7)
+            .ok_or(FixedError::Overflow)
+    }
+
+    pub fn sub(self, rhs: Self) -> Result<Self, FixedError> {
+        self.0
+            .checked_sub(rhs.0)
+            .map(I128F7)
+            .ok_or(FixedError::Overflow)
+    }
+
+    /// `self * rhs`, truncated toward zero.
+    pub fn mul(self, rhs: Self) -> Result<Self, FixedError> {
+        let magnitude = mul_magnitude(self.0.unsigned_abs(), rhs.0.unsigned_abs())
+            .ok_or(FixedError::Overflow)?;
+        with_sign((self.0 < 0) != (rhs.0 < 0), magnitude).map(I128F7)
+    }
+
+    /// `self / rhs`, truncated toward zero.
+    pub fn div(self, rhs: Self) -> Result<Self, FixedError> {
+        if rhs.0 == 0 {
+            return Err(FixedError::DivisionByZero);
+        }
+        let magnitude = div_magnitude(self.0.unsigned_abs(), rhs.0.unsigned_abs())
+            .ok_or(FixedError::Overflow)?;
+        with_sign((self.0 < 0) != (rhs.0 < 0), magnitude).map(I128F7)
+    }
+
+    /// `self` raised to the whole power `n`, by repeated squaring. `x^0` is
+    /// 1, including `0^0`.
+    pub fn pow_int(self, mut n: u32) -> Result<Self, FixedError> {
+        let mut base = self;
+        let mut acc = I128F7::ONE;
+        while n > 0 {
+            if n & 1 == 1 {
+                acc = acc.mul(base)?;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.mul(base)?;
+            }
+        }
+        Ok(acc)
+    }
+
+    /// The square root, truncated. Fails with `NegativeSqrt` below zero.
+    pub fn sqrt(self) -> Result<Self, FixedError> {
+        if self.0 < 0 {
+            return Err(FixedError::NegativeSqrt);
+        }
+        if self.0 == 0 {
+            return Ok(I128F7::ZERO);
+        }
+        // Integer Newton on y² = x·SCALE. Starting at or above the root,
+        // the iterates decrease until they reach floor(√(x·SCALE)).
+        let x = self.0 as u128;
+        let mut y = x.max(SCALE as u128);
+        for _ in 0..MAX_SQRT_ITERATIONS {
+            // y ≥ √(x·SCALE), so x·SCALE / y ≤ y and the sum fits in a u128.
+            let next = (y + div_magnitude(x, y).ok_or(FixedError::Overflow)?) / 2;
+            if next >= y {
+                return Ok(I128F7(y as i128));
+            }
+            y = next;
+        }
+        Err(FixedError::NoConvergence)
+    }
+}
+
+/// `⌊a · b / SCALE⌋` for magnitudes, without forming `a · b`.
+///
+/// With `a = a1·S + a0` and `b = b1·S + b0`, the product over `S` is
+/// `a1·b1·S + a1·b0 + a0·b1 + ⌊a0·b0 / S⌋`. Every term is non-negative, so
+/// one overflows only if the result would.
+fn mul_magnitude(a: u128, b: u128) -> Option<u128> {
+    let s = SCALE as u128;
+    let (a1, a0) = (a / s, a % s);
+    let (b1, b0) = (b / s, b % s);
+    a1.checked_mul(b1)?
+        .checked_mul(s)?
+        .checked_add(a1.checked_mul(b0)?)?
+        .checked_add(a0.checked_mul(b1)?)?
+        .checked_add(a0 * b0 / s)
+}
+
+/// `⌊a · SCALE / b⌋` for magnitudes, `b > 0`, without forming `a · SCALE`.
+///
+/// The whole part `⌊a / b⌋ · S` is exact; the remainder `r < b` is scaled
+/// by `S` one bit at a time, keeping every intermediate below `2b`, which
+/// fits because `b ≤ 2^127`.
+fn div_magnitude(a: u128, b: u128) -> Option<u128> {
+    let s = SCALE as u128;
+    let whole = (a / b).checked_mul(s)?;
+    let r = a % b;
+    let (mut q, mut rem) = (0u128, 0u128);
+    for bit in (0..u128::BITS - s.leading_zeros()).rev() {
+        q <<= 1;
+        rem <<= 1;
+        if rem >= b {
+            rem -= b;
+            q += 1;
+        }
+        if (s >> bit) & 1 == 1 {
+            rem += r;
+            if rem >= b {
+                rem -= b;
+                q += 1;
+            }
+        }
+    }
+    whole.checked_add(q)
+}
+
+fn with_sign(negative: bool, magnitude: u128) -> Result<i128, FixedError> {
+    if negative {
+        0i128
+            .checked_sub_unsigned(magnitude)
+            .ok_or(FixedError::Overflow)
+    } else {
+        i128::try_from(magnitude).map_err(|_| FixedError::Overflow)
+    }
+}
+
+/// Move `value` from `from` decimal places to `to`.
+fn rescale(value: i128, from: u32, to: u32, rounding: Rounding) -> Result<i128, FixedError> {
+    if to >= from {
+        let factor = 10i128.checked_pow(to - from).ok_or(FixedError::Overflow)?;
+        return value.checked_mul(factor).ok_or(FixedError::Overflow);
+    }
+    match 10i128.checked_pow(from - to) {
+        Some(factor) => Ok(div_round(value, factor, rounding)),
+        // 10^39 and up exceed every i128, so everything rounds to 0 or ±1.
+        None => Ok(match rounding {
+            Rounding::Ceil if value > 0 => 1,
+            Rounding::Floor if value < 0 => -1,
+            _ => 0,
+        }),
+    }
+}
+
+/// `value / divisor` for `divisor > 0`, rounded with `rounding`.
+fn div_round(value: i128, divisor: i128, rounding: Rounding) -> i128 {
+    let floor = value.div_euclid(divisor);
+    let rem = value.rem_euclid(divisor);
+    if rem == 0 {
+        return floor;
+    }
+    match rounding {
+        Rounding::Floor => floor,
+        Rounding::Ceil => floor + 1,
+        // `rem` is measured up from `floor`; a tie goes up for positive
+        // values and down (away from zero) for negative ones.
+        Rounding::Nearest => match (divisor - rem).cmp(&rem) {
+            core::cmp::Ordering::Less => floor + 1,
+            core::cmp::Ordering::Greater => floor,
+            core::cmp::Ordering::Equal if value > 0 => floor + 1,
+            core::cmp::Ordering::Equal => floor,
+        },
+    }
+}
+
+mod test;
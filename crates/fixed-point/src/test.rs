@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use super::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn fx(raw: i128) -> I128F7 {
+    I128F7::from_raw(raw)
+}
+
+fn int(n: i128) -> I128F7 {
+    I128F7::from_int(n).unwrap()
+}
+
+#[test]
+fn test_known_values() {
+    // 1.5 × 2.5 = 3.75
+    assert_eq!(fx(15_000_000).mul(fx(25_000_000)), Ok(fx(37_500_000)));
+    assert_eq!(int(-3).mul(fx(5_000_000)), Ok(fx(-15_000_000)));
+    // 1 / 3 and -1 / 3 truncate toward zero.
+    assert_eq!(I128F7::from_ratio(1, 3), Ok(fx(3_333_333)));
+    assert_eq!(I128F7::from_ratio(-1, 3), Ok(fx(-3_333_333)));
+    assert_eq!(int(7).sub(int(10)), Ok(int(-3)));
+
+    // √2 = 1.41421356…
+    assert_eq!(int(2).sqrt(), Ok(fx(14_142_135)));
+    assert_eq!(int(144).sqrt(), Ok(int(12)));
+    assert_eq!(fx(2_500_000).sqrt(), Ok(fx(5_000_000)));
+    assert_eq!(fx(1).sqrt(), Ok(fx(3_162)));
+    assert_eq!(I128F7::ZERO.sqrt(), Ok(I128F7::ZERO));
+
+    assert_eq!(int(2).pow_int(10), Ok(int(1_024)));
+    assert_eq!(int(-2).pow_int(3), Ok(int(-8)));
+    assert_eq!(I128F7::ZERO.pow_int(0), Ok(I128F7::ONE));
+    // 1.05^10 = 1.62889462677…, truncated once per multiplication.
+    let growth = fx(10_500_000).pow_int(10).unwrap();
+    assert!((16_288_940..=16_288_946).contains(&growth.raw()));
+}
+
+#[test]
+fn test_negative_operands() {
+    assert_eq!(int(-4).sqrt(), Err(FixedError::NegativeSqrt));
+    assert_eq!(fx(-1).sqrt(), Err(FixedError::NegativeSqrt));
+    assert_eq!(int(-6).div(int(-4)), Ok(fx(15_000_000)));
+    assert_eq!(int(6).div(int(-4)), Ok(fx(-15_000_000)));
+    assert_eq!(fx(-15_000_000).mul(fx(-15_000_000)), Ok(fx(22_500_000)));
+    assert_eq!(int(1).div(I128F7::ZERO), Err(FixedError::DivisionByZero));
+    assert_eq!(I128F7::from_ratio(1, 0), Err(FixedError::DivisionByZero));
+}
+
+#[test]
+fn test_overflow_boundaries() {
+    assert_eq!(I128F7::MAX.add(fx(1)), Err(FixedError::Overflow));
+    assert_eq!(I128F7::MIN.sub(fx(1)), Err(FixedError::Overflow));
+    assert_eq!(I128F7::MAX.mul(I128F7::ONE), Ok(I128F7::MAX));
+    assert_eq!(I128F7::MIN.mul(I128F7::ONE), Ok(I128F7::MIN));
+    assert_eq!(I128F7::MAX.mul(fx(SCALE + 1)), Err(FixedError::Overflow));
+    // MIN has no positive counterpart.
+    assert_eq!(I128F7::MIN.mul(fx(-SCALE)), Err(FixedError::Overflow));
+    assert_eq!(I128F7::MIN.div(fx(-SCALE)), Err(FixedError::Overflow));
+    assert_eq!(I128F7::MAX.div(fx(SCALE - 1)), Err(FixedError::Overflow));
+
+    // Products whose intermediate a·b exceeds i128 but whose result fits.
+    let big = int(10_000_000_000_000_000_000);
+    assert_eq!(big.mul(big), Err(FixedError::Overflow));
+    assert_eq!(
+        big.mul(int(1_000_000)),
+        Ok(int(10_000_000_000_000_000_000_000_000))
+    );
+    assert!(I128F7::MAX.sqrt().is_ok());
+
+    let max_int = i128::MAX / SCALE;
+    assert_eq!(I128F7::from_int(max_int), Ok(fx(max_int * SCALE)));
+    assert_eq!(I128F7::from_int(max_int + 1), Err(FixedError::Overflow));
+    assert_eq!(int(10).pow_int(31), Ok(int(10i128.pow(31))));
+    assert_eq!(int(10).pow_int(32), Err(FixedError::Overflow));
+}
+
+#[test]
+fn test_amount_conversion_rounding() {
+    use Rounding::*;
+
+    // 9-decimal token: 1.234567891 has two digits too many.
+    assert_eq!(
+        I128F7::from_amount(1_234_567_891, 9, Floor),
+        Ok(fx(12_345_678))
+    );
+    assert_eq!(
+        I128F7::from_amount(1_234_567_891, 9, Ceil),
+        Ok(fx(12_345_679))
+    );
+    assert_eq!(
+        I128F7::from_amount(1_234_567_891, 9, Nearest),
+        Ok(fx(12_345_679))
+    );
+    assert_eq!(
+        I128F7::from_amount(-1_234_567_891, 9, Floor),
+        Ok(fx(-12_345_679))
+    );
+    assert_eq!(
+        I128F7::from_amount(-1_234_567_891, 9, Ceil),
+        Ok(fx(-12_345_678))
+    );
+    assert_eq!(I128F7::from_amount(42, 2, Floor), Ok(fx(4_200_000)));
+
+    // To 2 decimals: 1.2345678.
+    let x = fx(12_345_678);
+    assert_eq!(x.to_amount(2, Floor), Ok(123));
+    assert_eq!(x.to_amount(2, Ceil), Ok(124));
+    assert_eq!(x.to_amount(2, Nearest), Ok(123));
+    assert_eq!(x.to_amount(7, Floor), Ok(12_345_678));
+    assert_eq!(x.to_amount(9, Floor), Ok(1_234_567_800));
+
+    // Ties round away from zero.
+    assert_eq!(fx(12_350_000).to_amount(2, Nearest), Ok(124));
+    assert_eq!(fx(-12_350_000).to_amount(2, Nearest), Ok(-124));
+    assert_eq!(fx(-12_340_000).to_amount(2, Nearest), Ok(-123));
+
+    assert_eq!(I128F7::MAX.to_amount(8, Floor), Err(FixedError::Overflow));
+    assert_eq!(I128F7::from_amount(1, 60, Ceil), Ok(fx(1)));
+    assert_eq!(I128F7::from_amount(1, 60, Floor), Ok(I128F7::ZERO));
+}
+
+#[test]
+fn test_mul_then_div_round_trips_within_one_ulp() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..10_000 {
+        let a = fx(rng.gen_range(-10i128.pow(25)..=10i128.pow(25)));
+        // |b| ≥ 1.0: dividing back scales the truncation error down.
+        let b = fx(rng.gen_range(SCALE..=10i128.pow(19)) * if rng.gen() { 1 } else { -1 });
+        let back = a.mul(b).unwrap().div(b).unwrap();
+        assert!(
+            (back.raw() - a.raw()).abs() <= 1,
+            "{a:?} × {b:?} / {b:?} = {back:?}"
+        );
+    }
+}
+
+#[test]
+fn test_div_then_mul_round_trips_within_one_ulp() {
+    let mut rng = StdRng::seed_from_u64(11);
+    for _ in 0..10_000 {
+        let a = fx(rng.gen_range(-10i128.pow(30)..=10i128.pow(30)));
+        // 0 < |b| ≤ 1.0: multiplying back scales the truncation error down.
+        let b = fx(rng.gen_range(1..=SCALE) * if rng.gen() { 1 } else { -1 });
+        let back = a.div(b).unwrap().mul(b).unwrap();
+        assert!(
+            (back.raw() - a.raw()).abs() <= 1,
+            "{a:?} / {b:?} × {b:?} = {back:?}"
+        );
+    }
+}
+
+#[test]
+fn test_sqrt_is_floor_of_exact_root() {
+    let mut rng = StdRng::seed_from_u64(13);
+    for _ in 0..2_000 {
+        let x = fx(rng.gen_range(0..=10i128.pow(24)));
+        let root = x.sqrt().unwrap().raw();
+        // root² ≤ x·SCALE < (root + 1)², checked without leaving i128.
+        let n = x.raw() * SCALE;
+        assert!(
+            root * root <= n && n < (root + 1) * (root + 1),
+            "√{x:?} = {root}"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! # Pausable
+//!
+//! A circuit breaker any contract can embed: a single boolean flag in
+//! instance storage, a guard that fails while it is set, and a standard
+//! event for every change.
+//!
+//! ```ignore
+//! pub fn pause(env: Env, admin: Address) -> Result<(), Error> {
+//!     Self::require_admin(&env, &admin)?;
+//!     recipes_pausable::set_paused(&env, true);
+//!     recipes_pausable::emit_pause_changed(&env, &admin, true);
+//!     Ok(())
+//! }
+//!
+//! pub fn transfer(env: Env, /* ... */) -> Result<(), Error> {
+//!     recipes_pausable::require_not_paused(&env)?; // via From<PausableError>
+//!     // ...
+//! }
+//! ```
+//!
+//! The flag lives under the tuple key `("pausable", "paused")` rather than a
+//! `#[contracttype]` enum variant, so it cannot collide with a host
+//! contract's own `DataKey::Paused` or any other key it defines.
+//!
+//! Authorization is left to the host: these functions never call
+//! `require_auth`, because only the host knows who may pause it.
+
+#![no_std]
+
+use soroban_sdk::{contracterror, symbol_short, Address, Env, Symbol};
+
+/// Namespace used for both the storage key and the event topics.
+pub const NAMESPACE: Symbol = symbol_short!("pausable");
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PausableError {
+    /// The operation is blocked because the contract is paused.
+    Paused = 1,
+}
+
+fn key() -> (Symbol, Symbol) {
+    (NAMESPACE, symbol_short!("paused"))
+}
+
+/// Set or clear the pause flag. Clearing removes the entry entirely.
+pub fn set_paused(env: &Env, paused: bool) {
+    if paused {
+        env.storage().instance().set(&key(), &true);
+    } else {
+        env.storage().instance().remove(&key());
+    }
+}
+
+/// Whether the contract is currently paused. `false` if never set.
+pub fn is_paused(env: &Env) -> bool {
+    env.storage().instance().get(&key()).unwrap_or(false)
+}
+
+/// Fail with [`PausableError::Paused`] while the contract is paused.
+pub fn require_not_paused(env: &Env) -> Result<(), PausableError> {
+    if is_paused(env) {
+        return Err(PausableError::Paused);
+    }
+    Ok(())
+}
+
+/// Publish `("pausable", "paused" | "unpaused")` with `by` as data.
+pub fn emit_pause_changed(env: &Env, by: &Address, paused: bool) {
+    let action = if paused {
+        symbol_short!("paused")
+    } else {
+        symbol_short!("unpaused")
+    };
+    env.events().publish((NAMESPACE, action), by.clone());
+}
+
+mod test;
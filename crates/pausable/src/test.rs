@@ -0,0 +1,112 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype,
+    testutils::{Address as _, Events},
+    Address, Env, IntoVal,
+};
+
+/// A host contract with its own `DataKey::Paused` to prove the recipe's key
+/// does not collide with it.
+#[contracttype]
+pub enum DataKey {
+    Paused,
+    Value,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum HostError {
+    Paused = 10,
+}
+
+impl From<PausableError> for HostError {
+    fn from(err: PausableError) -> Self {
+        match err {
+            PausableError::Paused => HostError::Paused,
+        }
+    }
+}
+
+#[contract]
+pub struct Host;
+
+#[contractimpl]
+impl Host {
+    pub fn set_paused(env: Env, by: Address, paused: bool) {
+        by.require_auth();
+        super::set_paused(&env, paused);
+        emit_pause_changed(&env, &by, paused);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        super::is_paused(&env)
+    }
+
+    pub fn set_value(env: Env, value: u32) -> Result<(), HostError> {
+        require_not_paused(&env)?;
+        env.storage().instance().set(&DataKey::Value, &value);
+        Ok(())
+    }
+
+    pub fn set_host_flag(env: Env, flag: bool) {
+        env.storage().instance().set(&DataKey::Paused, &flag);
+    }
+}
+
+fn setup() -> (Env, Address, HostClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = HostClient::new(&env, &env.register_contract(None, Host));
+    let admin = Address::generate(&env);
+    (env, admin, client)
+}
+
+#[test]
+fn test_unpaused_by_default() {
+    let (_, _, client) = setup();
+    assert!(!client.is_paused());
+    client.set_value(&1);
+}
+
+#[test]
+fn test_pause_blocks_guarded_calls() {
+    let (_, admin, client) = setup();
+    client.set_paused(&admin, &true);
+    assert!(client.is_paused());
+    assert_eq!(client.try_set_value(&1), Err(Ok(HostError::Paused)));
+
+    client.set_paused(&admin, &false);
+    assert!(!client.is_paused());
+    client.set_value(&1);
+}
+
+#[test]
+fn test_events_emitted() {
+    let (env, admin, client) = setup();
+    client.set_paused(&admin, &true);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (NAMESPACE, symbol_short!("paused")).into_val(&env));
+    let by: Address = data.into_val(&env);
+    assert_eq!(by, admin);
+
+    client.set_paused(&admin, &false);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("unpaused")).into_val(&env)
+    );
+}
+
+#[test]
+fn test_key_does_not_collide_with_host_keys() {
+    let (_, admin, client) = setup();
+    client.set_host_flag(&true);
+    assert!(!client.is_paused());
+
+    client.set_paused(&admin, &true);
+    client.set_host_flag(&false);
+    assert!(client.is_paused());
+}
@@ -0,0 +1,198 @@
+//! # Rate Limit
+//!
+//! Token-bucket throttling any contract can embed. Each bucket is named by
+//! a `Symbol` key and holds up to `capacity` tokens per account; tokens
+//! drip back in at a fixed rate, and each action spends `cost` of them.
+//!
+//! ```ignore
+//! const POST: Symbol = symbol_short!("post");
+//!
+//! // Bursts of 5, then one post every 12 seconds (5 per minute).
+//! recipes_rate_limit::configure_rate(&env, &POST, 5, 5, 60)?;
+//!
+//! pub fn post(env: Env, author: Address) -> Result<(), Error> {
+//!     author.require_auth();
+//!     recipes_rate_limit::try_consume(&env, &POST, &author, 1)?; // via From<RateLimitError>
+//!     // ...
+//! }
+//! ```
+//!
+//! A cooldown is the special case `capacity = 1`: one action, then wait a
+//! full period. A bucket also allows bursts, which a cooldown cannot.
+//!
+//! ## Lazy refill
+//!
+//! Nothing runs on a timer. A bucket stores its level and the timestamp it
+//! was last updated; every read adds the tokens earned since then. The level
+//! is kept in *units* of `1 / per_secs` token, so a rate such as 5 tokens
+//! per 60 seconds accrues 5 units a second and never loses the fraction of
+//! a token earned between reads.
+//!
+//! | Key                                 | Storage    | Value          |
+//! |-------------------------------------|------------|----------------|
+//! | `("ratelimit", "config", key)`      | instance   | `BucketConfig` |
+//! | `("ratelimit", key, account)`       | persistent | `Bucket`       |
+//!
+//! As with `recipes-pausable`, nothing here calls `require_auth`.
+
+#![no_std]
+
+use soroban_sdk::{contracterror, contracttype, symbol_short, Address, Env, Symbol};
+
+/// Namespace prefix for every storage key this crate writes.
+pub const NAMESPACE: Symbol = symbol_short!("ratelimit");
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RateLimitError {
+    /// No bucket is configured under the key.
+    NotConfigured = 1,
+    /// Not enough tokens right now; retry later.
+    Exhausted = 2,
+    /// The cost is larger than the bucket could ever hold.
+    CostExceedsCapacity = 3,
+    /// `capacity`, `tokens` or `per_secs` is zero.
+    InvalidConfig = 4,
+    Overflow = 5,
+}
+
+/// `tokens` are added every `per_secs` seconds, up to `capacity`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BucketConfig {
+    pub capacity: u32,
+    pub tokens: u32,
+    pub per_secs: u32,
+}
+
+/// One account's bucket. `level` is in units of `1 / per_secs` token.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Bucket {
+    pub level: u64,
+    pub updated_at: u64,
+}
+
+fn config_key(key: &Symbol) -> (Symbol, Symbol, Symbol) {
+    (NAMESPACE, symbol_short!("config"), key.clone())
+}
+
+fn bucket_key(key: &Symbol, account: &Address) -> (Symbol, Symbol, Address) {
+    (NAMESPACE, key.clone(), account.clone())
+}
+
+/// Configure `key` to refill `refill_per_sec` tokens a second, up to
+/// `capacity`.
+pub fn configure(
+    env: &Env,
+    key: &Symbol,
+    capacity: u32,
+    refill_per_sec: u32,
+) -> Result<(), RateLimitError> {
+    configure_rate(env, key, capacity, refill_per_sec, 1)
+}
+
+/// Configure `key` to refill `tokens` every `per_secs` seconds, up to
+/// `capacity`. Use this for rates slower than one token a second, such as
+/// 10 per minute. Existing buckets keep their level.
+pub fn configure_rate(
+    env: &Env,
+    key: &Symbol,
+    capacity: u32,
+    tokens: u32,
+    per_secs: u32,
+) -> Result<(), RateLimitError> {
+    if capacity == 0 || tokens == 0 || per_secs == 0 {
+        return Err(RateLimitError::InvalidConfig);
+    }
+    let config = BucketConfig {
+        capacity,
+        tokens,
+        per_secs,
+    };
+    env.storage().instance().set(&config_key(key), &config);
+    Ok(())
+}
+
+/// The configuration of `key`, if any.
+pub fn config(env: &Env, key: &Symbol) -> Option<BucketConfig> {
+    env.storage().instance().get(&config_key(key))
+}
+
+/// Spend `cost` tokens from `account`'s bucket. Returns the whole tokens
+/// left. A new bucket starts full.
+pub fn try_consume(
+    env: &Env,
+    key: &Symbol,
+    account: &Address,
+    cost: u32,
+) -> Result<u32, RateLimitError> {
+    let config = config(env, key).ok_or(RateLimitError::NotConfigured)?;
+    if cost > config.capacity {
+        return Err(RateLimitError::CostExceedsCapacity);
+    }
+    let mut bucket = refilled(env, &config, key, account)?;
+    let spend = (cost as u64)
+        .checked_mul(config.per_secs as u64)
+        .ok_or(RateLimitError::Overflow)?;
+    bucket.level = bucket
+        .level
+        .checked_sub(spend)
+        .ok_or(RateLimitError::Exhausted)?;
+    env.storage()
+        .persistent()
+        .set(&bucket_key(key, account), &bucket);
+    Ok(whole_tokens(&config, &bucket))
+}
+
+/// Whole tokens `account` could spend right now under `key`. Zero if `key`
+/// is not configured.
+pub fn available(env: &Env, key: &Symbol, account: &Address) -> u32 {
+    let Some(config) = config(env, key) else {
+        return 0;
+    };
+    refilled(env, &config, key, account)
+        .map(|bucket| whole_tokens(&config, &bucket))
+        .unwrap_or(0)
+}
+
+/// The bucket as of now: its stored level plus everything earned since
+/// `updated_at`, capped at capacity.
+fn refilled(
+    env: &Env,
+    config: &BucketConfig,
+    key: &Symbol,
+    account: &Address,
+) -> Result<Bucket, RateLimitError> {
+    let now = env.ledger().timestamp();
+    let full = (config.capacity as u64)
+        .checked_mul(config.per_secs as u64)
+        .ok_or(RateLimitError::Overflow)?;
+    let Some(stored) = env
+        .storage()
+        .persistent()
+        .get::<_, Bucket>(&bucket_key(key, account))
+    else {
+        return Ok(Bucket {
+            level: full,
+            updated_at: now,
+        });
+    };
+    // Earning more than the bucket holds is the same as filling it, so an
+    // overflowing product is treated as full rather than as an error.
+    let earned = now
+        .saturating_sub(stored.updated_at)
+        .checked_mul(config.tokens as u64)
+        .unwrap_or(full);
+    Ok(Bucket {
+        level: stored.level.saturating_add(earned).min(full),
+        updated_at: now,
+    })
+}
+
+fn whole_tokens(config: &BucketConfig, bucket: &Bucket) -> u32 {
+    (bucket.level / config.per_secs as u64) as u32
+}
+
+mod test;
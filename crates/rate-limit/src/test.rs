@@ -0,0 +1,161 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+#[contract]
+pub struct Host;
+
+#[contractimpl]
+impl Host {
+    pub fn configure(
+        env: Env,
+        key: Symbol,
+        capacity: u32,
+        refill_per_sec: u32,
+    ) -> Result<(), RateLimitError> {
+        super::configure(&env, &key, capacity, refill_per_sec)
+    }
+
+    pub fn configure_rate(
+        env: Env,
+        key: Symbol,
+        capacity: u32,
+        tokens: u32,
+        per_secs: u32,
+    ) -> Result<(), RateLimitError> {
+        super::configure_rate(&env, &key, capacity, tokens, per_secs)
+    }
+
+    pub fn act(env: Env, key: Symbol, account: Address, cost: u32) -> Result<u32, RateLimitError> {
+        try_consume(&env, &key, &account, cost)
+    }
+
+    pub fn available(env: Env, key: Symbol, account: Address) -> u32 {
+        super::available(&env, &key, &account)
+    }
+}
+
+const POST: Symbol = symbol_short!("post");
+const LIKE: Symbol = symbol_short!("like");
+
+fn setup() -> (Env, HostClient<'static>) {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let client = HostClient::new(&env, &env.register_contract(None, Host));
+    (env, client)
+}
+
+fn advance(env: &Env, secs: u64) {
+    env.ledger().with_mut(|li| li.timestamp += secs);
+}
+
+#[test]
+fn test_burst_up_to_capacity() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    client.configure(&POST, &5, &1);
+
+    assert_eq!(client.available(&POST, &alice), 5);
+    for remaining in (0..5).rev() {
+        assert_eq!(client.act(&POST, &alice, &1), remaining);
+    }
+    assert_eq!(
+        client.try_act(&POST, &alice, &1),
+        Err(Ok(RateLimitError::Exhausted))
+    );
+}
+
+#[test]
+fn test_refill_after_partial_elapse() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    client.configure(&POST, &10, &2);
+    client.act(&POST, &alice, &10);
+
+    advance(&env, 3);
+    assert_eq!(client.available(&POST, &alice), 6);
+    assert_eq!(client.act(&POST, &alice, &4), 2);
+
+    // Refill stops at capacity, however long the wait.
+    advance(&env, 1_000);
+    assert_eq!(client.available(&POST, &alice), 10);
+}
+
+#[test]
+fn test_fractional_refill_is_carried_over() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    // 10 per minute: one token every 6 seconds.
+    client.configure_rate(&POST, &10, &10, &60);
+    client.act(&POST, &alice, &10);
+
+    advance(&env, 5);
+    assert_eq!(client.available(&POST, &alice), 0);
+    advance(&env, 1);
+    assert_eq!(client.available(&POST, &alice), 1);
+
+    // Spending rounds nothing away: 3 more seconds is half a token, and 3
+    // after that completes it.
+    client.act(&POST, &alice, &1);
+    advance(&env, 3);
+    assert_eq!(client.available(&POST, &alice), 0);
+    assert_eq!(
+        client.try_act(&POST, &alice, &1),
+        Err(Ok(RateLimitError::Exhausted))
+    );
+    advance(&env, 3);
+    assert_eq!(client.act(&POST, &alice, &1), 0);
+}
+
+#[test]
+fn test_keys_and_accounts_are_independent() {
+    let (env, client) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    client.configure(&POST, &2, &1);
+    client.configure(&LIKE, &50, &10);
+
+    client.act(&POST, &alice, &2);
+    assert_eq!(client.available(&POST, &alice), 0);
+    assert_eq!(client.available(&POST, &bob), 2);
+    assert_eq!(client.available(&LIKE, &alice), 50);
+    assert_eq!(client.act(&LIKE, &alice, &5), 45);
+    assert_eq!(client.available(&POST, &alice), 0);
+}
+
+#[test]
+fn test_cost_above_capacity_rejected() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    client.configure(&POST, &5, &1);
+
+    assert_eq!(
+        client.try_act(&POST, &alice, &6),
+        Err(Ok(RateLimitError::CostExceedsCapacity))
+    );
+    // Rejected outright: the bucket is untouched.
+    assert_eq!(client.available(&POST, &alice), 5);
+}
+
+#[test]
+fn test_configuration_errors() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    assert_eq!(
+        client.try_act(&POST, &alice, &1),
+        Err(Ok(RateLimitError::NotConfigured))
+    );
+    assert_eq!(client.available(&POST, &alice), 0);
+    assert_eq!(
+        client.try_configure(&POST, &0, &1),
+        Err(Ok(RateLimitError::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_configure_rate(&POST, &1, &1, &0),
+        Err(Ok(RateLimitError::InvalidConfig))
+    );
+}
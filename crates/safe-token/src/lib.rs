@@ -0,0 +1,169 @@
+//! # Safe Token
+//!
+//! Token transfers that report failure as a typed error instead of trapping
+//! the whole invocation.
+//!
+//! A plain `token::Client::transfer` that fails aborts the caller with an
+//! opaque host error. These helpers call the `try_` client instead and sort
+//! the failure into a small [`TokenXferError`] the host contract can match
+//! on or return:
+//!
+//! ```ignore
+//! pub fn deposit(env: Env, from: Address, amount: i128) -> Result<(), Error> {
+//!     from.require_auth();
+//!     let vault = env.current_contract_address();
+//!     recipes_safe_token::safe_transfer_verified(&env, &token, &from, &vault, amount)?;
+//!     // ...
+//! }
+//! ```
+//!
+//! The `_verified` variants also read both balances before and after the
+//! call and fail with [`TokenXferError::BalanceMismatch`] unless each moved
+//! by exactly `amount`. That catches fee-on-transfer tokens, and tokens
+//! whose `transfer` returns without moving anything.
+//!
+//! Error codes are only standard for the Stellar Asset Contract. Any other
+//! token's contract errors are reported as [`TokenXferError::Unexpected`].
+//! So is a failed `require_auth` inside the token: the host hands the
+//! caller the same generic error for it as for a panic.
+//!
+//! A verified transfer that fails has still moved the tokens. Return the
+//! error from the contract function so the host rolls the transfer back;
+//! swallowing it keeps the transfer.
+
+#![no_std]
+
+use soroban_sdk::{contracterror, token, xdr::ScErrorType, Address, Env, Error, InvokeError};
+
+/// Stellar Asset Contract error codes this module recognises.
+const SAC_ACCOUNT_MISSING: u32 = 6;
+const SAC_ALLOWANCE: u32 = 9;
+const SAC_BALANCE: u32 = 10;
+const SAC_BALANCE_DEAUTHORIZED: u32 = 11;
+const SAC_TRUSTLINE_MISSING: u32 = 13;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TokenXferError {
+    /// `from` holds less than `amount`.
+    InsufficientBalance = 1,
+    /// The spender's allowance is less than `amount`.
+    InsufficientAllowance = 2,
+    /// The issuer has deauthorized a balance, or a classic account is
+    /// missing or has no trustline for the asset.
+    NotAuthorized = 3,
+    /// A verified transfer did not move exactly `amount`.
+    BalanceMismatch = 4,
+    /// Anything else: a panic, a failed `require_auth`, a non-standard error
+    /// code, or a return value of the wrong type.
+    Unexpected = 5,
+}
+
+/// Transfer `amount` of `token` from `from` to `to`.
+pub fn safe_transfer(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), TokenXferError> {
+    settle(token::Client::new(env, token).try_transfer(from, to, &amount))
+}
+
+/// Transfer `amount` of `token` from `from` to `to`, spending `spender`'s
+/// allowance.
+pub fn safe_transfer_from(
+    env: &Env,
+    token: &Address,
+    spender: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), TokenXferError> {
+    settle(token::Client::new(env, token).try_transfer_from(spender, from, to, &amount))
+}
+
+/// [`safe_transfer`], then check that both balances moved by `amount`.
+pub fn safe_transfer_verified(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), TokenXferError> {
+    verify(env, token, from, to, amount, || {
+        safe_transfer(env, token, from, to, amount)
+    })
+}
+
+/// [`safe_transfer_from`], then check that both balances moved by `amount`.
+pub fn safe_transfer_from_verified(
+    env: &Env,
+    token: &Address,
+    spender: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), TokenXferError> {
+    verify(env, token, from, to, amount, || {
+        safe_transfer_from(env, token, spender, from, to, amount)
+    })
+}
+
+fn settle<E>(
+    result: Result<Result<(), E>, Result<Error, InvokeError>>,
+) -> Result<(), TokenXferError> {
+    match result {
+        Ok(Ok(())) => Ok(()),
+        // The call succeeded but returned something other than `()`.
+        Ok(Err(_)) => Err(TokenXferError::Unexpected),
+        Err(Ok(err)) => Err(classify(err)),
+        Err(Err(_)) => Err(TokenXferError::Unexpected),
+    }
+}
+
+fn classify(err: Error) -> TokenXferError {
+    if !err.is_type(ScErrorType::Contract) {
+        return TokenXferError::Unexpected;
+    }
+    match err.get_code() {
+        SAC_BALANCE => TokenXferError::InsufficientBalance,
+        SAC_ALLOWANCE => TokenXferError::InsufficientAllowance,
+        SAC_ACCOUNT_MISSING | SAC_BALANCE_DEAUTHORIZED | SAC_TRUSTLINE_MISSING => {
+            TokenXferError::NotAuthorized
+        }
+        _ => TokenXferError::Unexpected,
+    }
+}
+
+fn verify(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    transfer: impl FnOnce() -> Result<(), TokenXferError>,
+) -> Result<(), TokenXferError> {
+    let client = token::Client::new(env, token);
+    let from_before = balance(&client, from)?;
+    let to_before = balance(&client, to)?;
+    transfer()?;
+    let sent = from_before.checked_sub(balance(&client, from)?);
+    let received = balance(&client, to)?.checked_sub(to_before);
+    // A transfer to oneself leaves the balance where it was.
+    let expected = if from == to { 0 } else { amount };
+    if sent != Some(expected) || received != Some(expected) {
+        return Err(TokenXferError::BalanceMismatch);
+    }
+    Ok(())
+}
+
+fn balance(client: &token::Client, id: &Address) -> Result<i128, TokenXferError> {
+    match client.try_balance(id) {
+        Ok(Ok(balance)) => Ok(balance),
+        _ => Err(TokenXferError::Unexpected),
+    }
+}
+
+mod test;
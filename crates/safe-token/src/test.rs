@@ -0,0 +1,306 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype,
+    testutils::{Address as _, IssuerFlags},
+    token::{StellarAssetClient, TokenClient},
+    xdr::FromXdr,
+    Bytes, IntoVal, Val,
+};
+
+/// A host contract that pays out through the helpers and returns their
+/// error as its own.
+#[contract]
+pub struct Payer;
+
+#[contractimpl]
+impl Payer {
+    pub fn pay(
+        env: Env,
+        token: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+        verified: bool,
+    ) -> Result<(), TokenXferError> {
+        if verified {
+            safe_transfer_verified(&env, &token, &from, &to, amount)
+        } else {
+            safe_transfer(&env, &token, &from, &to, amount)
+        }
+    }
+
+    pub fn pay_from(
+        env: Env,
+        token: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+        verified: bool,
+    ) -> Result<(), TokenXferError> {
+        let spender = env.current_contract_address();
+        if verified {
+            safe_transfer_from_verified(&env, &token, &spender, &from, &to, amount)
+        } else {
+            safe_transfer_from(&env, &token, &spender, &from, &to, amount)
+        }
+    }
+}
+
+/// How [`BadToken`] misbehaves.
+#[contracttype]
+#[derive(Clone, Copy)]
+pub enum Mode {
+    /// Keeps one unit of every transfer as a fee.
+    Fee,
+    /// Reports success without moving anything.
+    Phantom,
+    /// Panics.
+    Trap,
+    /// Fails with a contract error code of its own.
+    CustomError,
+    /// Returns a `u32` where the interface promises `()`.
+    WrongReturn,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BadTokenError {
+    Broken = 42,
+}
+
+/// A token that implements `balance`, `transfer` and `transfer_from`, badly.
+#[contract]
+pub struct BadToken;
+
+#[contractimpl]
+impl BadToken {
+    pub fn set_mode(env: Env, mode: Mode) {
+        env.storage().instance().set(&"mode", &mode);
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<Val, BadTokenError> {
+        let mode: Mode = env.storage().instance().get(&"mode").unwrap();
+        match mode {
+            Mode::Fee => {
+                Self::mint(env.clone(), from, -amount);
+                Self::mint(env.clone(), to, amount - 1);
+            }
+            Mode::Phantom => {}
+            Mode::Trap => panic!("broken token"),
+            Mode::CustomError => return Err(BadTokenError::Broken),
+            Mode::WrongReturn => return Ok(7_u32.into_val(&env)),
+        }
+        Ok(().into_val(&env))
+    }
+
+    pub fn transfer_from(
+        env: Env,
+        _spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<Val, BadTokenError> {
+        Self::transfer(env, from, to, amount)
+    }
+}
+
+struct Setup {
+    env: Env,
+    payer: PayerClient<'static>,
+    token: Address,
+    admin: StellarAssetClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let payer = PayerClient::new(&env, &env.register_contract(None, Payer));
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    // Lets the issuer deauthorize balances.
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = asset.address();
+    let admin = StellarAssetClient::new(&env, &token);
+    admin.mint(&payer.address, &100);
+    Setup {
+        env,
+        payer,
+        token,
+        admin,
+    }
+}
+
+fn bad_token(s: &Setup, mode: Mode) -> BadTokenClient<'static> {
+    let bad = BadTokenClient::new(&s.env, &s.env.register_contract(None, BadToken));
+    bad.set_mode(&mode);
+    bad.mint(&s.payer.address, &100);
+    bad
+}
+
+/// A classic `G...` account that exists only as an address: it has no
+/// trustline for any issued asset.
+fn classic_account(env: &Env) -> Address {
+    let mut xdr = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+    xdr.append(&Bytes::from_array(env, &[7; 32]));
+    Address::from_xdr(env, &xdr).unwrap()
+}
+
+#[test]
+fn test_asset_contract_transfers_succeed() {
+    let s = setup();
+    let to = Address::generate(&s.env);
+    let balances = TokenClient::new(&s.env, &s.token);
+
+    s.payer.pay(&s.token, &s.payer.address, &to, &30, &false);
+    s.payer.pay(&s.token, &s.payer.address, &to, &20, &true);
+    assert_eq!(balances.balance(&s.payer.address), 50);
+    assert_eq!(balances.balance(&to), 50);
+
+    // A verified transfer to oneself expects no change.
+    s.payer
+        .pay(&s.token, &s.payer.address, &s.payer.address, &10, &true);
+    assert_eq!(balances.balance(&s.payer.address), 50);
+
+    let owner = Address::generate(&s.env);
+    s.admin.mint(&owner, &40);
+    balances.approve(&owner, &s.payer.address, &40, &1_000);
+    s.payer.pay_from(&s.token, &owner, &to, &15, &false);
+    s.payer.pay_from(&s.token, &owner, &to, &25, &true);
+    assert_eq!(balances.balance(&owner), 0);
+    assert_eq!(balances.balance(&to), 90);
+    assert_eq!(balances.allowance(&owner, &s.payer.address), 0);
+}
+
+#[test]
+fn test_shortfalls_are_typed() {
+    let s = setup();
+    let to = Address::generate(&s.env);
+    assert_eq!(
+        s.payer
+            .try_pay(&s.token, &s.payer.address, &to, &101, &false),
+        Err(Ok(TokenXferError::InsufficientBalance))
+    );
+    assert_eq!(
+        s.payer
+            .try_pay(&s.token, &s.payer.address, &to, &101, &true),
+        Err(Ok(TokenXferError::InsufficientBalance))
+    );
+
+    let owner = Address::generate(&s.env);
+    s.admin.mint(&owner, &40);
+    TokenClient::new(&s.env, &s.token).approve(&owner, &s.payer.address, &10, &1_000);
+    assert_eq!(
+        s.payer.try_pay_from(&s.token, &owner, &to, &11, &false),
+        Err(Ok(TokenXferError::InsufficientAllowance))
+    );
+}
+
+#[test]
+fn test_authorization_failures_are_typed() {
+    let s = setup();
+    let to = Address::generate(&s.env);
+
+    // The recipient's balance is frozen by the issuer.
+    s.admin.set_authorized(&to, &false);
+    assert_eq!(
+        s.payer.try_pay(&s.token, &s.payer.address, &to, &1, &false),
+        Err(Ok(TokenXferError::NotAuthorized))
+    );
+
+    // A classic account without a trustline cannot hold the asset.
+    let account = classic_account(&s.env);
+    assert_eq!(
+        s.payer
+            .try_pay(&s.token, &s.payer.address, &account, &1, &false),
+        Err(Ok(TokenXferError::NotAuthorized))
+    );
+
+    // `from` never signed. The host reports a failed `require_auth` in
+    // another contract as a generic error, indistinguishable from a panic.
+    let holder = Address::generate(&s.env);
+    s.admin.mint(&holder, &10);
+    s.env.set_auths(&[]);
+    assert_eq!(
+        s.payer.try_pay(&s.token, &holder, &to, &1, &false),
+        Err(Ok(TokenXferError::Unexpected))
+    );
+}
+
+#[test]
+fn test_verification_catches_fee_on_transfer() {
+    let s = setup();
+    let bad = bad_token(&s, Mode::Fee);
+    let to = Address::generate(&s.env);
+
+    // Unverified, the fee goes unnoticed.
+    s.payer
+        .pay(&bad.address, &s.payer.address, &to, &10, &false);
+    assert_eq!(bad.balance(&to), 9);
+
+    assert_eq!(
+        s.payer
+            .try_pay(&bad.address, &s.payer.address, &to, &10, &true),
+        Err(Ok(TokenXferError::BalanceMismatch))
+    );
+    assert_eq!(
+        s.payer
+            .try_pay_from(&bad.address, &s.payer.address, &to, &10, &true),
+        Err(Ok(TokenXferError::BalanceMismatch))
+    );
+    // Returning the error rolled the transfers back.
+    assert_eq!(bad.balance(&s.payer.address), 90);
+    assert_eq!(bad.balance(&to), 9);
+}
+
+#[test]
+fn test_broken_token_failures_are_unexpected() {
+    let s = setup();
+    let to = Address::generate(&s.env);
+
+    let phantom = bad_token(&s, Mode::Phantom);
+    assert_eq!(
+        s.payer
+            .try_pay(&phantom.address, &s.payer.address, &to, &10, &true),
+        Err(Ok(TokenXferError::BalanceMismatch))
+    );
+
+    for mode in [Mode::Trap, Mode::CustomError, Mode::WrongReturn] {
+        let bad = bad_token(&s, mode);
+        assert_eq!(
+            s.payer
+                .try_pay(&bad.address, &s.payer.address, &to, &10, &false),
+            Err(Ok(TokenXferError::Unexpected))
+        );
+        assert_eq!(
+            s.payer
+                .try_pay_from(&bad.address, &s.payer.address, &to, &10, &false),
+            Err(Ok(TokenXferError::Unexpected))
+        );
+    }
+
+    // An address with no contract behind it.
+    let missing = Address::generate(&s.env);
+    assert_eq!(
+        s.payer
+            .try_pay(&missing, &s.payer.address, &to, &10, &false),
+        Err(Ok(TokenXferError::Unexpected))
+    );
+}
@@ -0,0 +1,252 @@
+//! Golden-file snapshots of contract state and events.
+//!
+//! A [`Snapshot`] collects storage reads and the event stream into plain
+//! text, one line per entry, with every value rendered from its XDR form so
+//! the output does not depend on host object handles. [`assert_golden`]
+//! compares that text with a file committed next to the tests.
+//!
+//! To regenerate golden files after an intentional change, run the tests
+//! with `UPDATE_GOLDEN=1` and review the diff in version control.
+
+use std::path::PathBuf;
+
+use soroban_sdk::{testutils::Events as _, xdr::ScVal, Address, Env, IntoVal, TryFromVal, Val};
+
+/// Environment variable that makes [`assert_golden`] rewrite golden files
+/// instead of comparing against them.
+pub const UPDATE_GOLDEN: &str = "UPDATE_GOLDEN";
+
+/// Deterministic text rendering of selected storage entries and events.
+///
+/// Sections and lines appear in the order they were added. Addresses given
+/// a name with [`Snapshot::name`] render as that name everywhere, which
+/// keeps golden files readable and independent of address generation.
+pub struct Snapshot {
+    env: Env,
+    names: Vec<(Address, String)>,
+    lines: Vec<String>,
+}
+
+impl Snapshot {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            env: env.clone(),
+            names: Vec::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Render `address` as `name` in every later line.
+    pub fn name(&mut self, address: &Address, name: &str) -> &mut Self {
+        self.names.push((address.clone(), name.into()));
+        self
+    }
+
+    /// Start a new `[title]` section.
+    pub fn section(&mut self, title: &str) -> &mut Self {
+        if !self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.lines.push(format!("[{title}]"));
+        self
+    }
+
+    /// Record `contract`'s instance entry under `key`, or `<none>`.
+    pub fn instance<K: IntoVal<Env, Val>>(&mut self, contract: &Address, key: K) -> &mut Self {
+        let key = key.into_val(&self.env);
+        let value = self.env.as_contract(contract, || {
+            self.env.storage().instance().get::<Val, Val>(&key)
+        });
+        self.entry(contract, "instance", key, value)
+    }
+
+    /// Record `contract`'s persistent entry under `key`, or `<none>`.
+    pub fn persistent<K: IntoVal<Env, Val>>(&mut self, contract: &Address, key: K) -> &mut Self {
+        let key = key.into_val(&self.env);
+        let value = self.env.as_contract(contract, || {
+            self.env.storage().persistent().get::<Val, Val>(&key)
+        });
+        self.entry(contract, "persistent", key, value)
+    }
+
+    /// Record `contract`'s temporary entry under `key`, or `<none>`.
+    pub fn temporary<K: IntoVal<Env, Val>>(&mut self, contract: &Address, key: K) -> &mut Self {
+        let key = key.into_val(&self.env);
+        let value = self.env.as_contract(contract, || {
+            self.env.storage().temporary().get::<Val, Val>(&key)
+        });
+        self.entry(contract, "temporary", key, value)
+    }
+
+    /// Record every event published so far, in order, as
+    /// `contract: (topics) => data`.
+    pub fn events(&mut self) -> &mut Self {
+        for (contract, topics, data) in self.env.events().all().iter() {
+            let topics: Vec<String> = topics.iter().map(|t| self.val(&t)).collect();
+            let line = format!(
+                "{}: ({}) => {}",
+                self.address(&contract),
+                topics.join(", "),
+                self.val(&data)
+            );
+            self.lines.push(line);
+        }
+        self
+    }
+
+    /// Record a free-form line, such as a view function's result.
+    pub fn line(&mut self, label: &str, value: impl IntoVal<Env, Val>) -> &mut Self {
+        let value = self.val(&value.into_val(&self.env));
+        self.lines.push(format!("{label} = {value}"));
+        self
+    }
+
+    /// The snapshot text, ending with a newline.
+    pub fn render(&self) -> String {
+        let mut out = self.lines.join("\n");
+        out.push('\n');
+        out
+    }
+
+    fn entry(&mut self, contract: &Address, kind: &str, key: Val, value: Option<Val>) -> &mut Self {
+        let value = value.map_or_else(|| "<none>".into(), |v| self.val(&v));
+        let line = format!(
+            "{}.{kind} {} = {value}",
+            self.address(contract),
+            self.val(&key)
+        );
+        self.lines.push(line);
+        self
+    }
+
+    fn val(&self, val: &Val) -> String {
+        let scval = ScVal::try_from_val(&self.env, val).expect("value has no XDR form");
+        self.scval(&scval)
+    }
+
+    fn address(&self, address: &Address) -> String {
+        if let Some((_, name)) = self.names.iter().find(|(a, _)| a == address) {
+            return name.clone();
+        }
+        let strkey = address.to_string();
+        let mut buf = [0u8; 56];
+        strkey.copy_into_slice(&mut buf[..strkey.len() as usize]);
+        String::from_utf8_lossy(&buf[..strkey.len() as usize]).into_owned()
+    }
+
+    fn scval(&self, val: &ScVal) -> String {
+        match val {
+            ScVal::Void => "()".into(),
+            ScVal::Bool(b) => format!("{b}"),
+            ScVal::U32(n) => format!("{n}u32"),
+            ScVal::I32(n) => format!("{n}i32"),
+            ScVal::U64(n) => format!("{n}u64"),
+            ScVal::I64(n) => format!("{n}i64"),
+            ScVal::Timepoint(t) => format!("timepoint({})", t.0),
+            ScVal::Duration(d) => format!("duration({})", d.0),
+            ScVal::U128(parts) => format!("{}u128", (parts.hi as u128) << 64 | parts.lo as u128),
+            ScVal::I128(parts) => format!("{}i128", ((parts.hi as i128) << 64) | parts.lo as i128),
+            ScVal::Bytes(bytes) => hex(bytes.as_slice()),
+            ScVal::String(s) => format!("{:?}", s.to_utf8_string_lossy()),
+            ScVal::Symbol(s) => s.to_utf8_string_lossy(),
+            ScVal::Vec(Some(items)) => {
+                let items: Vec<String> = items.iter().map(|v| self.scval(v)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            ScVal::Map(Some(entries)) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|e| format!("{}: {}", self.scval(&e.key), self.scval(&e.val)))
+                    .collect();
+                format!("{{{}}}", entries.join(", "))
+            }
+            ScVal::Address(_) => {
+                let address = Address::try_from_val(&self.env, val).expect("invalid address");
+                self.address(&address)
+            }
+            ScVal::Error(err) => format!("{err:?}"),
+            other => format!("{other:?}"),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::from("0x");
+    for byte in bytes {
+        out.push_str(&format!("{byte:02x}"));
+    }
+    out
+}
+
+/// Compare `actual` with the golden file at `path`, relative to the crate
+/// under test. Panics with a line diff on mismatch.
+///
+/// With `UPDATE_GOLDEN=1` set, writes `actual` to the file instead.
+#[track_caller]
+pub fn assert_golden(path: &str, actual: &str) {
+    let file = golden_path(path);
+    if std::env::var_os(UPDATE_GOLDEN).is_some() {
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir).expect("cannot create golden directory");
+        }
+        std::fs::write(&file, actual).expect("cannot write golden file");
+        return;
+    }
+    let expected = std::fs::read_to_string(&file).unwrap_or_else(|_| {
+        panic!(
+            "golden file {} is missing; rerun with {UPDATE_GOLDEN}=1 to create it",
+            file.display()
+        )
+    });
+    if expected != actual {
+        panic!(
+            "golden file {} is out of date (- golden, + actual); \
+             rerun with {UPDATE_GOLDEN}=1 if the change is intended\n{}",
+            file.display(),
+            diff_lines(&expected, actual)
+        );
+    }
+}
+
+fn golden_path(path: &str) -> PathBuf {
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => PathBuf::from(dir).join(path),
+        None => PathBuf::from(path),
+    }
+}
+
+/// A unified-style line diff: unchanged lines prefixed with two spaces,
+/// removed lines with `- `, added lines with `+ `.
+pub fn diff_lines(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // Longest common subsequence table, filled from the end.
+    let mut lcs = std::vec![std::vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        }
+    }
+    out
+}
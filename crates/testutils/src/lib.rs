@@ -0,0 +1,188 @@
+//! # Test Utilities
+//!
+//! Helpers shared by the cookbook's test suites, so each `test.rs` can focus
+//! on the contract under test instead of re-implementing the same plumbing.
+//!
+//! ```ignore
+//! use recipes_testutils::{advance_time, assert_topic_symbol, TestEnv};
+//!
+//! let (env, _, client) = TestEnv::with_contract(TimelockContract, TimelockContractClient::new);
+//!
+//! client.queue(&admin, &op, &3_600);
+//! advance_time(&env, 3_600);
+//! client.execute(&admin, &op);
+//!
+//! let (_, topics, _) = env.events().all().last().unwrap();
+//! assert_topic_symbol(&env, &topics, 0, &symbol_short!("timelock"));
+//! ```
+//!
+//! This is a dev-dependency only: it enables the SDK's `testutils` feature
+//! and must never end up in a contract's `[dependencies]`.
+
+use core::ops::Deref;
+
+pub use golden::{assert_golden, diff_lines, Snapshot, UPDATE_GOLDEN};
+#[cfg(feature = "testutils")]
+pub use property::{check_sequences, shrink, Rng};
+
+use soroban_sdk::{
+    testutils::{ContractFunctionSet, Events as _, Ledger as _},
+    xdr::{Limited, Limits, ReadXdr, ScMetaEntry},
+    Address, Env, Symbol, TryFromVal, Val, Vec,
+};
+
+/// A fresh [`Env`] with every `require_auth` call mocked to succeed.
+///
+/// Derefs to [`Env`], so `&env` can be passed anywhere an `&Env` is expected.
+pub struct TestEnv {
+    pub env: Env,
+}
+
+impl TestEnv {
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        Self { env }
+    }
+
+    /// Registers `contract` and builds its client in one step.
+    ///
+    /// Pass the generated client constructor, e.g.
+    /// `env.register(CounterContract, CounterContractClient::new)`.
+    pub fn register<T, C>(
+        &self,
+        contract: T,
+        client: impl FnOnce(&Env, &Address) -> C,
+    ) -> (Address, C)
+    where
+        T: ContractFunctionSet + 'static,
+    {
+        let contract_id = self.env.register_contract(None, contract);
+        let client = client(&self.env, &contract_id);
+        (contract_id, client)
+    }
+
+    /// One-shot setup for the common single-contract test: a fresh mocked
+    /// env, the registered contract's id, and its client.
+    ///
+    /// Returns a plain [`Env`] so values can be decoded with
+    /// `T::try_from_val(&env, ...)` without going through `Deref`.
+    pub fn with_contract<T, C>(
+        contract: T,
+        client: impl FnOnce(&Env, &Address) -> C,
+    ) -> (Env, Address, C)
+    where
+        T: ContractFunctionSet + 'static,
+    {
+        let test_env = Self::new();
+        let (contract_id, client) = test_env.register(contract, client);
+        (test_env.env, contract_id, client)
+    }
+}
+
+impl Default for TestEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for TestEnv {
+    type Target = Env;
+
+    fn deref(&self) -> &Env {
+        &self.env
+    }
+}
+
+/// Moves the ledger clock forward by `secs` seconds.
+pub fn advance_time(env: &Env, secs: u64) {
+    env.ledger().with_mut(|l| l.timestamp += secs);
+}
+
+/// Sets the ledger clock to an absolute `timestamp`.
+pub fn set_timestamp(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|l| l.timestamp = timestamp);
+}
+
+/// Moves the ledger sequence number forward by `n` ledgers.
+pub fn advance_ledgers(env: &Env, n: u32) {
+    env.ledger().with_mut(|l| l.sequence_number += n);
+}
+
+/// Replaces the host's base PRNG seed, from which each contract
+/// invocation's local PRNG is derived.
+///
+/// A fresh test `Env` always starts from an all-zero seed, so PRNG output
+/// already depends only on call order. Pin a seed explicitly when a test
+/// asserts exact random values or compares runs under different seeds.
+pub fn pin_prng_seed(env: &Env, seed: [u8; 32]) {
+    env.host().set_base_prng_seed(seed).unwrap();
+}
+
+/// Every event published by `contract_id`, as `(topics, data)` pairs in
+/// emission order.
+///
+/// Events from other contracts (for example a token contract called during
+/// the same invocation) are dropped.
+pub fn all_events_for(env: &Env, contract_id: &Address) -> std::vec::Vec<(Vec<Val>, Val)> {
+    env.events()
+        .all()
+        .iter()
+        .filter(|(id, _, _)| id == contract_id)
+        .map(|(_, topics, data)| (topics, data))
+        .collect()
+}
+
+/// Asserts that `topics[index]` decodes to the `expected` symbol.
+#[track_caller]
+pub fn assert_topic_symbol(env: &Env, topics: &Vec<Val>, index: u32, expected: &Symbol) {
+    let actual = topic_as::<Symbol>(env, topics, index);
+    assert_eq!(&actual, expected, "topic {index} mismatch");
+}
+
+/// Asserts that `topics[index]` decodes to the `expected` address.
+#[track_caller]
+pub fn assert_topic_address(env: &Env, topics: &Vec<Val>, index: u32, expected: &Address) {
+    let actual = topic_as::<Address>(env, topics, index);
+    assert_eq!(&actual, expected, "topic {index} mismatch");
+}
+
+/// Every `contractmeta!` entry compiled into `wasm`, as `(key, val)`
+/// pairs in declaration order.
+///
+/// The SDK also writes its own entries, such as `rsver` and `rssdkver`, so
+/// look entries up by key rather than by position.
+pub fn contract_meta(wasm: &[u8]) -> std::vec::Vec<(String, String)> {
+    let mut entries = std::vec::Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(wasm) {
+        let payload = payload.expect("wasm does not parse");
+        let wasmparser::Payload::CustomSection(section) = payload else {
+            continue;
+        };
+        if section.name() != "contractmetav0" {
+            continue;
+        }
+        let mut data = Limited::new(section.data(), Limits::none());
+        for entry in ScMetaEntry::read_xdr_iter(&mut data) {
+            let ScMetaEntry::ScMetaV0(meta) = entry.expect("malformed contractmetav0 entry");
+            entries.push((
+                meta.key.to_utf8_string_lossy(),
+                meta.val.to_utf8_string_lossy(),
+            ));
+        }
+    }
+    entries
+}
+
+#[track_caller]
+fn topic_as<T: TryFromVal<Env, Val>>(env: &Env, topics: &Vec<Val>, index: u32) -> T {
+    let raw = topics
+        .get(index)
+        .unwrap_or_else(|| panic!("event has no topic at index {index}"));
+    T::try_from_val(env, &raw).unwrap_or_else(|_| panic!("topic {index} has an unexpected type"))
+}
+
+mod golden;
+#[cfg(feature = "testutils")]
+mod property;
+mod test;
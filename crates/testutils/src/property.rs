@@ -0,0 +1,101 @@
+//! Seeded property runs with shrinking.
+//!
+//! A property test generates random operation sequences from a seed, runs
+//! each against the contract and a model, and shrinks any failing sequence
+//! to a minimal one before reporting it with its seed:
+//!
+//! ```ignore
+//! check_sequences(0..CASES, generate, run, |_| Vec::new());
+//! ```
+//!
+//! This is a few lines of plain Rust rather than proptest, so the examples
+//! build with the workspace's existing lockfile.
+
+use core::{fmt::Debug, ops::Range};
+
+/// splitmix64: enough randomness to drive a generator, and the same
+/// sequence for the same seed on every platform.
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// In `0..bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// In `low..=high`.
+    pub fn between(&mut self, low: i128, high: i128) -> i128 {
+        low + i128::from(self.below((high - low + 1) as u64))
+    }
+}
+
+/// The smallest sequence derived from `ops` that still `fails`.
+///
+/// Drops one operation at a time, then replaces each operation with the
+/// first of its `simpler` variants that still fails, and repeats until
+/// neither makes progress. `ops` must fail to begin with.
+pub fn shrink<T: Clone>(
+    mut ops: Vec<T>,
+    simpler: impl Fn(&T) -> Vec<T>,
+    fails: impl Fn(&[T]) -> bool,
+) -> Vec<T> {
+    loop {
+        let mut progress = false;
+
+        let mut i = 0;
+        while i < ops.len() {
+            let mut candidate = ops.clone();
+            candidate.remove(i);
+            if fails(&candidate) {
+                ops = candidate;
+                progress = true;
+            } else {
+                i += 1;
+            }
+        }
+
+        for i in 0..ops.len() {
+            for op in simpler(&ops[i]) {
+                let mut candidate = ops.clone();
+                candidate[i] = op;
+                if fails(&candidate) {
+                    ops = candidate;
+                    progress = true;
+                    break;
+                }
+            }
+        }
+
+        if !progress {
+            return ops;
+        }
+    }
+}
+
+/// Run the sequence `generate` makes for every seed in `seeds`. On the
+/// first failure, shrink it and panic with the seed, the error and the
+/// minimal sequence.
+#[track_caller]
+pub fn check_sequences<T: Clone + Debug>(
+    seeds: Range<u64>,
+    generate: impl Fn(&mut Rng) -> Vec<T>,
+    run: impl Fn(&[T]) -> Result<(), String>,
+    simpler: impl Fn(&T) -> Vec<T>,
+) {
+    for seed in seeds {
+        let ops = generate(&mut Rng(seed));
+        if run(&ops).is_err() {
+            let minimal = shrink(ops, &simpler, |ops| run(ops).is_err());
+            let error = run(&minimal).unwrap_err();
+            panic!("seed {seed:#x}: {error}\nminimal sequence: {minimal:#?}");
+        }
+    }
+}
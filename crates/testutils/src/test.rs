@@ -0,0 +1,223 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::Address as _,
+    xdr::{ScMetaV0, WriteXdr},
+};
+
+/// A tiny contract that needs auth and publishes one event per call.
+#[contract]
+pub struct Host;
+
+#[contractimpl]
+impl Host {
+    pub fn draw(env: Env) -> u64 {
+        env.prng().gen()
+    }
+
+    pub fn ping(env: Env, from: Address) -> u64 {
+        from.require_auth();
+        env.events()
+            .publish((symbol_short!("host"), symbol_short!("ping"), from), 1u32);
+        env.ledger().timestamp()
+    }
+}
+
+#[test]
+fn test_register_returns_matching_client() {
+    let env = TestEnv::new();
+    let (contract_id, client) = env.register(Host, HostClient::new);
+
+    assert_eq!(client.address, contract_id);
+}
+
+#[test]
+fn test_new_env_mocks_auth() {
+    let env = TestEnv::new();
+    let (_, client) = env.register(Host, HostClient::new);
+
+    // Would fail with an auth error on a plain `Env::default()`.
+    client.ping(&Address::generate(&env));
+    assert_eq!(env.auths().len(), 1);
+}
+
+#[test]
+fn test_time_and_ledger_helpers() {
+    let env = TestEnv::new();
+    set_timestamp(&env, 1_000);
+    advance_time(&env, 50);
+    assert_eq!(env.ledger().timestamp(), 1_050);
+
+    let seq = env.ledger().sequence();
+    advance_ledgers(&env, 10);
+    assert_eq!(env.ledger().sequence(), seq + 10);
+}
+
+#[test]
+fn test_events_filtered_by_contract_and_topics_asserted() {
+    let env = TestEnv::new();
+    let (first, client) = env.register(Host, HostClient::new);
+    let (second, other) = env.register(Host, HostClient::new);
+    let caller = Address::generate(&env);
+
+    client.ping(&caller);
+    let events = all_events_for(&env, &first);
+    assert_eq!(events.len(), 1);
+    assert!(all_events_for(&env, &second).is_empty());
+
+    let (topics, _) = &events[0];
+    assert_topic_symbol(&env, topics, 0, &symbol_short!("host"));
+    assert_topic_symbol(&env, topics, 1, &symbol_short!("ping"));
+    assert_topic_address(&env, topics, 2, &caller);
+
+    other.ping(&caller);
+    assert_eq!(all_events_for(&env, &second).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "topic 1 mismatch")]
+fn test_assert_topic_symbol_reports_index() {
+    let env = TestEnv::new();
+    let (contract_id, client) = env.register(Host, HostClient::new);
+    client.ping(&Address::generate(&env));
+
+    let (topics, _) = &all_events_for(&env, &contract_id)[0];
+    assert_topic_symbol(&env, topics, 1, &symbol_short!("pong"));
+}
+
+#[test]
+fn test_with_contract_returns_plain_env() {
+    let (env, contract_id, client) = TestEnv::with_contract(Host, HostClient::new);
+    client.ping(&Address::generate(&env));
+
+    let (topics, data) = &all_events_for(&env, &contract_id)[0];
+    assert_eq!(topics.len(), 3);
+    assert_eq!(u32::try_from_val(&env, data).unwrap(), 1);
+}
+
+#[test]
+fn test_pin_prng_seed_makes_draws_repeatable() {
+    let draws = |seed: [u8; 32]| {
+        let (env, _, client) = TestEnv::with_contract(Host, HostClient::new);
+        pin_prng_seed(&env, seed);
+        (client.draw(), client.draw())
+    };
+
+    assert_eq!(draws([7; 32]), draws([7; 32]));
+    assert_ne!(draws([7; 32]), draws([8; 32]));
+}
+
+/// A wasm module holding nothing but one custom section.
+fn wasm_with_section(name: &str, data: &[u8]) -> std::vec::Vec<u8> {
+    let mut body = vec![name.len() as u8];
+    body.extend_from_slice(name.as_bytes());
+    body.extend_from_slice(data);
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    wasm.push(0);
+    wasm.push(body.len() as u8);
+    wasm.extend(body);
+    wasm
+}
+
+#[test]
+fn test_contract_meta_reads_entries_in_order() {
+    let mut data = std::vec::Vec::new();
+    for (key, val) in [("name", "demo"), ("version", "1.2.3")] {
+        let entry = ScMetaEntry::ScMetaV0(ScMetaV0 {
+            key: key.try_into().unwrap(),
+            val: val.try_into().unwrap(),
+        });
+        data.extend(entry.to_xdr(Limits::none()).unwrap());
+    }
+
+    assert_eq!(
+        contract_meta(&wasm_with_section("contractmetav0", &data)),
+        [
+            ("name".to_string(), "demo".to_string()),
+            ("version".to_string(), "1.2.3".to_string()),
+        ]
+    );
+    assert!(contract_meta(&wasm_with_section("other", &data)).is_empty());
+}
+
+#[test]
+fn test_snapshot_renders_storage_and_events_by_name() {
+    let env = TestEnv::new();
+    let (contract_id, client) = env.register(Host, HostClient::new);
+    let user = Address::generate(&env);
+    client.ping(&user);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("bal"), user.clone()), &-5i128);
+        env.storage().instance().set(&symbol_short!("note"), &"hi");
+    });
+
+    let mut snapshot = Snapshot::new(&env);
+    snapshot
+        .name(&contract_id, "host")
+        .name(&user, "user")
+        .section("storage")
+        .persistent(&contract_id, (symbol_short!("bal"), user.clone()))
+        .instance(&contract_id, symbol_short!("note"))
+        .temporary(&contract_id, symbol_short!("gone"))
+        .section("events")
+        .events();
+
+    assert_eq!(
+        snapshot.render(),
+        "[storage]\n\
+         host.persistent [bal, user] = -5i128\n\
+         host.instance note = \"hi\"\n\
+         host.temporary gone = <none>\n\
+         \n\
+         [events]\n\
+         host: (host, ping, user) => 1u32\n"
+    );
+}
+
+#[test]
+fn test_diff_lines_marks_changes() {
+    let diff = diff_lines("a\nb\nc\n", "a\nB\nc\nd\n");
+    assert_eq!(diff, "  a\n- b\n+ B\n  c\n+ d\n");
+}
+
+#[test]
+fn test_assert_golden_accepts_matching_file() {
+    let path = std::env::temp_dir().join("recipes_testutils_golden_match.txt");
+    std::fs::write(&path, "x = 1u32\n").unwrap();
+    assert_golden(path.to_str().unwrap(), "x = 1u32\n");
+}
+
+#[test]
+#[should_panic(expected = "- x = 1u32\n+ x = 2u32")]
+fn test_assert_golden_reports_diff() {
+    let path = std::env::temp_dir().join("recipes_testutils_golden_diff.txt");
+    std::fs::write(&path, "[state]\nx = 1u32\n").unwrap();
+    assert_golden(path.to_str().unwrap(), "[state]\nx = 2u32\n");
+}
+
+#[cfg(feature = "testutils")]
+#[test]
+fn test_rng_is_reproducible() {
+    let draw = |seed| {
+        let mut rng = Rng(seed);
+        (0..8)
+            .map(|_| rng.between(-3, 3))
+            .collect::<std::vec::Vec<_>>()
+    };
+    assert_eq!(draw(7), draw(7));
+    assert_ne!(draw(7), draw(8));
+    assert!(draw(7).iter().all(|n| (-3..=3).contains(n)));
+}
+
+#[cfg(feature = "testutils")]
+#[test]
+fn test_shrink_drops_and_simplifies() {
+    // Stands in for a bug: any value above 10 "fails".
+    let fails = |ops: &[i128]| ops.iter().any(|op| *op > 10);
+    let simpler = |op: &i128| std::vec![op / 2, op - 1];
+    assert_eq!(shrink(std::vec![3, 40, 7, 25], simpler, fails), [11]);
+}
@@ -1,16 +1,84 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, Env, Symbol};
+use recipes_errors::CommonError;
+use recipes_pausable::PausableError;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Bytes, Env,
+    Symbol, Val, Vec,
+};
 
 /// Minimum delay (in seconds) that must pass before execution
-const MIN_DELAY: u64 = 60;
+pub const MIN_DELAY: u64 = 60;
 /// Maximum delay (in seconds) allowed when queuing
-const MAX_DELAY: u64 = 86_400; // 24 hours
+pub const MAX_DELAY: u64 = 86_400; // 24 hours
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for the timelock's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TimelockError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// `queue` or `execute` while paused. `CommonError::Paused`.
+    Paused = 4,
+    /// No operation with this id is queued. `CommonError::NotFound`.
+    OperationNotFound = 6,
+    /// The delay is outside `MIN_DELAY..=MAX_DELAY`.
+    DelayOutOfRange = 100,
+    /// An operation with this id is already queued.
+    AlreadyQueued = 101,
+    /// The operation's delay has not passed yet.
+    TooEarly = 102,
+}
+
+impl From<CommonError> for TimelockError {
+    fn from(err: CommonError) -> Self {
+        match err {
+            CommonError::NotInitialized => TimelockError::NotInitialized,
+            CommonError::AlreadyInitialized => TimelockError::AlreadyInitialized,
+            CommonError::Paused => TimelockError::Paused,
+            CommonError::NotFound => TimelockError::OperationNotFound,
+            // Auth failures come from `require_auth`, and the timelock does
+            // no arithmetic that can fail or take arbitrary input.
+            CommonError::Unauthorized
+            | CommonError::Overflow
+            | CommonError::Expired
+            | CommonError::InvalidInput
+            | CommonError::DivisionByZero => unreachable!(),
+        }
+    }
+}
+
+impl From<PausableError> for TimelockError {
+    fn from(err: PausableError) -> Self {
+        match err {
+            PausableError::Paused => CommonError::Paused.into(),
+        }
+    }
+}
+
+/// A contract call made by the timelock when its operation executes.
+///
+/// The timelock is the direct invoker, so a target that checks
+/// `require_auth` on the timelock's address accepts the call without any
+/// signature.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Call {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
 
 #[contracttype]
 pub enum DataKey {
     /// Maps operation_id -> scheduled execution timestamp
     Operation(Bytes),
+    /// Maps operation_id -> calls made on execution, if any
+    Calls(Bytes),
     /// The admin who can queue/cancel/execute
     Admin,
 }
@@ -37,7 +105,7 @@ impl TimelockContract {
     /// Initialize the contract with an admin address.
     pub fn initialize(env: Env, admin: Address) {
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            panic_with_error!(&env, TimelockError::AlreadyInitialized);
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
     }
@@ -47,57 +115,54 @@ impl TimelockContract {
     /// - `operation_id`: unique identifier for this operation (caller-defined bytes)
     /// - `delay`:        seconds from now before the operation can be executed (MIN_DELAY..=MAX_DELAY)
     ///
-    /// Emits a `queued` event on success.
+    /// Emits a `queued` event on success. Blocked while paused.
     pub fn queue(env: Env, operation_id: Bytes, delay: u64) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        admin.require_auth();
-
-        if delay < MIN_DELAY || delay > MAX_DELAY {
-            panic!("Delay out of range");
-        }
-
-        let key = DataKey::Operation(operation_id.clone());
-        if env.storage().persistent().has(&key) {
-            panic!("Operation already queued");
-        }
-
-        let execute_at = env.ledger().timestamp() + delay;
-        env.storage().persistent().set(&key, &execute_at);
+        Self::schedule(&env, operation_id, delay);
+    }
 
-        env.events()
-            .publish((Symbol::new(&env, "queued"),), (operation_id, execute_at));
+    /// Queue an operation that makes `calls`, in order, when executed.
+    ///
+    /// Same rules and event as [`Self::queue`]. If any call fails, the whole
+    /// `execute` fails and the operation stays queued.
+    pub fn queue_calls(env: Env, operation_id: Bytes, calls: Vec<Call>, delay: u64) {
+        Self::schedule(&env, operation_id.clone(), delay);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Calls(operation_id), &calls);
     }
 
     /// Execute a queued operation after its delay has passed.
     ///
     /// Removes the operation from storage (marking it done).
-    /// Emits an `executed` event on success.
+    /// Emits an `executed` event on success. Blocked while paused.
     pub fn execute(env: Env, operation_id: Bytes) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        admin.require_auth();
+        Self::admin(&env).require_auth();
+        Self::require_not_paused(&env);
 
         let key = DataKey::Operation(operation_id.clone());
         let execute_at: u64 = env
             .storage()
             .persistent()
             .get(&key)
-            .expect("Operation not found");
+            .unwrap_or_else(|| panic_with_error!(&env, TimelockError::OperationNotFound));
 
         let now = env.ledger().timestamp();
         if now < execute_at {
-            panic!("Too early");
+            panic_with_error!(&env, TimelockError::TooEarly);
         }
 
         // Remove so it cannot be replayed
         env.storage().persistent().remove(&key);
+        let calls_key = DataKey::Calls(operation_id.clone());
+        let calls: Vec<Call> = env
+            .storage()
+            .persistent()
+            .get(&calls_key)
+            .unwrap_or(Vec::new(&env));
+        env.storage().persistent().remove(&calls_key);
+        for call in calls.iter() {
+            env.invoke_contract::<Val>(&call.contract, &call.function, call.args);
+        }
 
         env.events()
             .publish((Symbol::new(&env, "executed"),), (operation_id, now));
@@ -107,30 +172,53 @@ impl TimelockContract {
     ///
     /// Emits a `cancelled` event on success.
     pub fn cancel(env: Env, operation_id: Bytes) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        admin.require_auth();
+        Self::admin(&env).require_auth();
 
         let key = DataKey::Operation(operation_id.clone());
         if !env.storage().persistent().has(&key) {
-            panic!("Operation not found");
+            panic_with_error!(&env, TimelockError::OperationNotFound);
         }
 
         env.storage().persistent().remove(&key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Calls(operation_id.clone()));
 
         env.events()
             .publish((Symbol::new(&env, "cancelled"),), operation_id);
     }
 
+    /// Pause `queue` and `execute`. `cancel` stays available so pending
+    /// operations can still be withdrawn during an incident.
+    pub fn pause(env: Env) {
+        Self::set_paused(&env, true);
+    }
+
+    /// Lift a previous `pause`.
+    pub fn unpause(env: Env) {
+        Self::set_paused(&env, false);
+    }
+
+    /// Whether `queue` and `execute` are currently blocked.
+    pub fn is_paused(env: Env) -> bool {
+        recipes_pausable::is_paused(&env)
+    }
+
     /// Return the scheduled execution timestamp for an operation, or 0 if not queued.
     pub fn get_execute_at(env: Env, operation_id: Bytes) -> u64 {
         let key = DataKey::Operation(operation_id);
         env.storage().persistent().get(&key).unwrap_or(0)
     }
 
+    /// Return the calls an operation makes on execution. Empty for
+    /// operations queued with [`Self::queue`] or not queued at all.
+    pub fn get_calls(env: Env, operation_id: Bytes) -> Vec<Call> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Calls(operation_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
     /// Return the current state of an operation.
     pub fn get_state(env: Env, operation_id: Bytes) -> OperationState {
         let key = DataKey::Operation(operation_id);
@@ -147,4 +235,47 @@ impl TimelockContract {
     }
 }
 
+impl TimelockContract {
+    fn admin(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, TimelockError::NotInitialized))
+    }
+
+    fn schedule(env: &Env, operation_id: Bytes, delay: u64) {
+        Self::admin(env).require_auth();
+
+        Self::require_not_paused(env);
+
+        if !(MIN_DELAY..=MAX_DELAY).contains(&delay) {
+            panic_with_error!(env, TimelockError::DelayOutOfRange);
+        }
+
+        let key = DataKey::Operation(operation_id.clone());
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(env, TimelockError::AlreadyQueued);
+        }
+
+        let execute_at = env.ledger().timestamp() + delay;
+        env.storage().persistent().set(&key, &execute_at);
+
+        env.events()
+            .publish((Symbol::new(env, "queued"),), (operation_id, execute_at));
+    }
+
+    fn set_paused(env: &Env, paused: bool) {
+        let admin = Self::admin(env);
+        admin.require_auth();
+        recipes_pausable::set_paused(env, paused);
+        recipes_pausable::emit_pause_changed(env, &admin, paused);
+    }
+
+    fn require_not_paused(env: &Env) {
+        if let Err(err) = recipes_pausable::require_not_paused(env) {
+            panic_with_error!(env, TimelockError::from(err));
+        }
+    }
+}
+
 mod test;
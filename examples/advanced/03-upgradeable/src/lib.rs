@@ -0,0 +1,111 @@
+//! # Upgradeable Contract (v1)
+//!
+//! Demonstrates in-place upgrades with `update_current_contract_wasm`:
+//!
+//! - the contract id and all storage survive an upgrade; only the code
+//!   behind the id changes,
+//! - the admin stored at initialization is the only address allowed to
+//!   upgrade,
+//! - a `version` counter in instance storage is bumped on every upgrade so
+//!   callers can tell which code they are talking to.
+//!
+//! `v2/` holds the next version of this contract, which adds `increment`.
+//! Both are compiled into the wasm fixtures under `fixtures/` that the tests
+//! upgrade between.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contractmeta, contracttype, symbol_short, Address,
+    BytesN, Env,
+};
+
+// The `version` this code reports once installed. `initialize` starts the
+// stored counter at 1 to match; each `upgrade` bumps it to the next binver.
+contractmeta!(key = "binver", val = "1");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Version,
+    Value,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum UpgradeError {
+    AlreadyInitialized = 1,
+    NotAdmin = 2,
+}
+
+#[contract]
+pub struct UpgradeableContract;
+
+#[contractimpl]
+impl UpgradeableContract {
+    /// Store the upgrade admin and start at version 1.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), UpgradeError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(UpgradeError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Version, &1u32);
+        Ok(())
+    }
+
+    /// Replace the code of this contract with `new_wasm_hash`. Admin only.
+    ///
+    /// The wasm must already be uploaded to the network. The new code takes
+    /// effect once this invocation returns; storage is left untouched apart
+    /// from the version bump. Publishes `("upgraded",)` with
+    /// `(new_version, new_wasm_hash)`.
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<u32, UpgradeError> {
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+
+        let version = Self::version(env.clone()) + 1;
+        env.storage().instance().set(&DataKey::Version, &version);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events()
+            .publish((symbol_short!("upgraded"),), (version, new_wasm_hash));
+        Ok(version)
+    }
+
+    /// Number of the code version currently installed, starting at 1.
+    pub fn version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Version).unwrap_or(0)
+    }
+
+    /// Store an application value. Admin only.
+    pub fn set_value(env: Env, admin: Address, value: u32) -> Result<(), UpgradeError> {
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Value, &value);
+        Ok(())
+    }
+
+    /// The stored application value, 0 if never set.
+    pub fn get_value(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Value).unwrap_or(0)
+    }
+}
+
+impl UpgradeableContract {
+    fn ensure_admin(env: &Env, caller: &Address) -> Result<(), UpgradeError> {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if admin.as_ref() != Some(caller) {
+            return Err(UpgradeError::NotAdmin);
+        }
+        Ok(())
+    }
+}
+
+mod test;
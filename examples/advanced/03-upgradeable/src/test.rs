@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use super::*;
+use recipes_testutils::contract_meta;
+use soroban_sdk::{
+    testutils::{Address as _, Events as _},
+    BytesN, Env, IntoVal,
+};
+
+mod v1 {
+    soroban_sdk::contractimport!(file = "fixtures/upgradeable_v1.wasm");
+}
+
+mod v2 {
+    soroban_sdk::contractimport!(file = "fixtures/upgradeable_v2.wasm");
+}
+
+/// Deploys the v1 fixture and initializes it with a fresh admin.
+fn setup_v1() -> (Env, Address, Address, v1::Client<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract_wasm(None, v1::WASM);
+    let client = v1::Client::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    (env, contract_id, admin, client)
+}
+
+fn upload_v2(env: &Env) -> BytesN<32> {
+    env.deployer().upload_contract_wasm(v2::WASM)
+}
+
+#[test]
+fn test_initialize_starts_at_version_one() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, UpgradeableContract);
+    let client = UpgradeableContractClient::new(&env, &contract_id);
+    assert_eq!(client.version(), 0);
+
+    client.initialize(&Address::generate(&env));
+    assert_eq!(client.version(), 1);
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env)),
+        Err(Ok(UpgradeError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_upgrade_to_v2_keeps_id_and_storage() {
+    let (env, contract_id, admin, client) = setup_v1();
+    client.set_value(&admin, &41);
+
+    let new_hash = upload_v2(&env);
+    assert_eq!(client.upgrade(&admin, &new_hash), 2);
+
+    // Same contract id, now running v2 code.
+    let upgraded = v2::Client::new(&env, &contract_id);
+    assert_eq!(upgraded.version(), 2);
+    assert_eq!(upgraded.get_value(), 41);
+    assert_eq!(upgraded.increment(&admin, &1), 42);
+    assert_eq!(upgraded.get_value(), 42);
+}
+
+/// The `binver` meta entry compiled into `wasm`.
+fn binver(wasm: &[u8]) -> u32 {
+    contract_meta(wasm)
+        .into_iter()
+        .find(|(key, _)| key == "binver")
+        .map(|(_, val)| val.parse().unwrap())
+        .expect("wasm has no binver entry")
+}
+
+#[test]
+fn test_stored_version_tracks_wasm_binver() {
+    let (env, _contract_id, admin, client) = setup_v1();
+    assert_eq!(client.version(), binver(v1::WASM));
+
+    client.upgrade(&admin, &upload_v2(&env));
+    assert_eq!(client.version(), binver(v2::WASM));
+}
+
+#[test]
+fn test_v2_function_unavailable_before_upgrade() {
+    let (env, contract_id, admin, _client) = setup_v1();
+
+    let not_yet = v2::Client::new(&env, &contract_id);
+    assert!(not_yet.try_increment(&admin, &1).is_err());
+}
+
+#[test]
+fn test_upgrade_emits_event() {
+    let (env, contract_id, admin, client) = setup_v1();
+    let new_hash = upload_v2(&env);
+    client.upgrade(&admin, &new_hash);
+
+    let (id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(id, contract_id);
+    assert_eq!(topics, (symbol_short!("upgraded"),).into_val(&env));
+    let (version, hash): (u32, BytesN<32>) = data.into_val(&env);
+    assert_eq!(version, 2);
+    assert_eq!(hash, new_hash);
+}
+
+#[test]
+fn test_non_admin_cannot_upgrade() {
+    let (env, contract_id, _admin, client) = setup_v1();
+    let new_hash = upload_v2(&env);
+    let attacker = Address::generate(&env);
+
+    assert_eq!(
+        client.try_upgrade(&attacker, &new_hash),
+        Err(Ok(v1::UpgradeError::NotAdmin))
+    );
+    assert_eq!(client.version(), 1);
+    assert!(v2::Client::new(&env, &contract_id)
+        .try_increment(&attacker, &1)
+        .is_err());
+}
@@ -0,0 +1,109 @@
+//! # Upgradeable Contract (v2)
+//!
+//! Second version of `examples/advanced/03-upgradeable`, used as an upgrade
+//! target by that crate's tests. The storage layout is unchanged; the only
+//! addition is `increment`.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contractmeta, contracttype, symbol_short, Address,
+    BytesN, Env,
+};
+
+// The `version` this code reports once installed: upgrading from v1 bumps
+// the stored counter from 1 to 2.
+contractmeta!(key = "binver", val = "2");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Version,
+    Value,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum UpgradeError {
+    AlreadyInitialized = 1,
+    NotAdmin = 2,
+}
+
+#[contract]
+pub struct UpgradeableContract;
+
+#[contractimpl]
+impl UpgradeableContract {
+    /// Store the upgrade admin and start at version 1.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), UpgradeError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(UpgradeError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Version, &1u32);
+        Ok(())
+    }
+
+    /// Replace the code of this contract with `new_wasm_hash`. Admin only.
+    ///
+    /// The wasm must already be uploaded to the network. The new code takes
+    /// effect once this invocation returns; storage is left untouched apart
+    /// from the version bump. Publishes `("upgraded",)` with
+    /// `(new_version, new_wasm_hash)`.
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<u32, UpgradeError> {
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+
+        let version = Self::version(env.clone()) + 1;
+        env.storage().instance().set(&DataKey::Version, &version);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events()
+            .publish((symbol_short!("upgraded"),), (version, new_wasm_hash));
+        Ok(version)
+    }
+
+    /// Number of the code version currently installed, starting at 1.
+    pub fn version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Version).unwrap_or(0)
+    }
+
+    /// Store an application value. Admin only.
+    pub fn set_value(env: Env, admin: Address, value: u32) -> Result<(), UpgradeError> {
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Value, &value);
+        Ok(())
+    }
+
+    /// The stored application value, 0 if never set.
+    pub fn get_value(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Value).unwrap_or(0)
+    }
+
+    /// New in v2: add `by` to the stored value and return the result.
+    pub fn increment(env: Env, admin: Address, by: u32) -> Result<u32, UpgradeError> {
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        let value = Self::get_value(env.clone()) + by;
+        env.storage().instance().set(&DataKey::Value, &value);
+        Ok(value)
+    }
+}
+
+impl UpgradeableContract {
+    fn ensure_admin(env: &Env, caller: &Address) -> Result<(), UpgradeError> {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if admin.as_ref() != Some(caller) {
+            return Err(UpgradeError::NotAdmin);
+        }
+        Ok(())
+    }
+}
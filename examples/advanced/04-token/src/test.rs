@@ -0,0 +1,219 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger},
+    token::{StellarAssetClient, TokenClient as SdkTokenClient},
+    Env, IntoVal, TryFromVal,
+};
+
+fn setup() -> (Env, Address, TokenClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Token);
+    let client = TokenClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &7,
+        &String::from_str(&env, "Cookbook Token"),
+        &String::from_str(&env, "COOK"),
+    );
+    (env, admin, client)
+}
+
+/// Asserts that a `try_` call failed with the contract error `expected`.
+fn assert_error<T, E>(result: Result<T, Result<E, soroban_sdk::InvokeError>>, expected: TokenError)
+where
+    E: Into<soroban_sdk::Error>,
+{
+    match result {
+        Err(Ok(err)) => assert_eq!(err.into(), expected.into()),
+        _ => panic!("expected contract error {:?}", expected),
+    }
+}
+
+#[test]
+fn test_metadata_set_at_initialization() {
+    let (env, admin, client) = setup();
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.name(), String::from_str(&env, "Cookbook Token"));
+    assert_eq!(client.symbol(), String::from_str(&env, "COOK"));
+    assert_eq!(client.admin(), admin);
+}
+
+#[test]
+fn test_initialize_twice_fails() {
+    let (env, admin, client) = setup();
+    let name = String::from_str(&env, "Again");
+    assert_error(
+        client.try_initialize(&admin, &7, &name, &name),
+        TokenError::AlreadyInitialized,
+    );
+}
+
+#[test]
+fn test_mint_and_transfer_direct() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.mint(&alice, &1_000);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("mint"), admin.clone(), alice.clone()).into_val(&env)
+    );
+    assert_eq!(i128::try_from_val(&env, &data).unwrap(), 1_000);
+
+    client.transfer(&alice, &bob, &300);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env)
+    );
+    assert_eq!(i128::try_from_val(&env, &data).unwrap(), 300);
+
+    assert_eq!(client.balance(&alice), 700);
+    assert_eq!(client.balance(&bob), 300);
+}
+
+#[test]
+fn test_transfer_more_than_balance_fails() {
+    let (env, _admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.mint(&alice, &10);
+
+    assert_error(
+        client.try_transfer(&alice, &bob, &11),
+        TokenError::InsufficientBalance,
+    );
+    assert_error(
+        client.try_transfer(&alice, &bob, &-1),
+        TokenError::NegativeAmount,
+    );
+}
+
+#[test]
+fn test_standard_token_client_interface() {
+    let (env, _admin, client) = setup();
+    let token = SdkTokenClient::new(&env, &client.address);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&alice, &1_000);
+
+    assert_eq!(token.decimals(), 7);
+    assert_eq!(token.name(), String::from_str(&env, "Cookbook Token"));
+    assert_eq!(token.symbol(), String::from_str(&env, "COOK"));
+    assert_eq!(token.balance(&alice), 1_000);
+
+    token.transfer(&alice, &bob, &100);
+    let expiration = env.ledger().sequence() + 100;
+    token.approve(&alice, &spender, &200, &expiration);
+    assert_eq!(token.allowance(&alice, &spender), 200);
+
+    token.transfer_from(&spender, &alice, &bob, &150);
+    assert_eq!(token.allowance(&alice, &spender), 50);
+
+    token.burn(&bob, &50);
+    token.burn_from(&spender, &alice, &50);
+    assert_eq!(token.allowance(&alice, &spender), 0);
+
+    assert_eq!(token.balance(&alice), 700);
+    assert_eq!(token.balance(&bob), 200);
+}
+
+#[test]
+fn test_admin_functions_through_asset_client() {
+    let (env, admin, client) = setup();
+    let asset = StellarAssetClient::new(&env, &client.address);
+    let alice = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+
+    asset.mint(&alice, &5);
+    assert_eq!(client.balance(&alice), 5);
+
+    asset.set_admin(&new_admin);
+    assert_eq!(asset.admin(), new_admin);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("set_admin"), admin).into_val(&env));
+    assert_eq!(Address::try_from_val(&env, &data).unwrap(), new_admin);
+}
+
+#[test]
+fn test_approve_event_shape() {
+    let (env, _admin, client) = setup();
+    let alice = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    client.approve(&alice, &spender, &500, &1_000);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("approve"), alice, spender).into_val(&env)
+    );
+    assert_eq!(
+        <(i128, u32)>::try_from_val(&env, &data).unwrap(),
+        (500, 1_000)
+    );
+}
+
+#[test]
+fn test_allowance_expires_at_expiration_ledger() {
+    let (env, _admin, client) = setup();
+    let token = SdkTokenClient::new(&env, &client.address);
+    let alice = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&alice, &1_000);
+
+    let expiration = env.ledger().sequence() + 10;
+    token.approve(&alice, &spender, &300, &expiration);
+
+    env.ledger().with_mut(|li| li.sequence_number = expiration);
+    assert_eq!(token.allowance(&alice, &spender), 300);
+    token.transfer_from(&spender, &alice, &spender, &100);
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number = expiration + 1);
+    assert_eq!(token.allowance(&alice, &spender), 0);
+    assert_error(
+        client.try_transfer_from(&spender, &alice, &spender, &1),
+        TokenError::InsufficientAllowance,
+    );
+    assert_eq!(token.balance(&alice), 900);
+}
+
+#[test]
+fn test_approve_with_past_expiration_rejected() {
+    let (env, _admin, client) = setup();
+    let alice = Address::generate(&env);
+    let spender = Address::generate(&env);
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+
+    assert_error(
+        client.try_approve(&alice, &spender, &1, &99),
+        TokenError::InvalidExpiration,
+    );
+    // Clearing an allowance is allowed with any expiration.
+    client.approve(&alice, &spender, &0, &0);
+    assert_eq!(client.allowance(&alice, &spender), 0);
+}
+
+#[test]
+fn test_transfer_from_requires_allowance() {
+    let (env, _admin, client) = setup();
+    let alice = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&alice, &1_000);
+
+    assert_error(
+        client.try_transfer_from(&spender, &alice, &spender, &1),
+        TokenError::InsufficientAllowance,
+    );
+    assert_error(
+        client.try_burn_from(&spender, &alice, &1),
+        TokenError::InsufficientAllowance,
+    );
+}
@@ -0,0 +1,131 @@
+//! # Factory Contract
+//!
+//! Deploys instances of the basics counter contract
+//! (`examples/basics/07-counter`) at deterministic addresses and keeps an
+//! index of everything it has deployed.
+//!
+//! - The child's wasm must be uploaded first; the factory only stores its
+//!   hash.
+//! - Each instance address is derived from the factory's address and a
+//!   caller-chosen salt, so it can be computed before deployment and a salt
+//!   can never be used twice.
+//! - Protocol 21 has no constructors, so the factory deploys and then calls
+//!   the child's `initialize` in the same invocation. No one can front-run
+//!   the initialization.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env,
+    IntoVal, Symbol, Vec,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    WasmHash,
+    Instances,
+    Deployed(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FactoryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// An instance was already deployed with this salt.
+    AlreadyDeployed = 3,
+}
+
+#[contract]
+pub struct FactoryContract;
+
+#[contractimpl]
+impl FactoryContract {
+    /// Store the admin and the hash of the uploaded child wasm.
+    pub fn initialize(env: Env, admin: Address, wasm_hash: BytesN<32>) -> Result<(), FactoryError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(FactoryError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::WasmHash, &wasm_hash);
+        Ok(())
+    }
+
+    /// Deploy and initialize a new child instance. Admin only.
+    ///
+    /// The child's admin is the factory admin. The new address is appended
+    /// to the index and published as `("deployed", address)` with `salt`.
+    pub fn deploy_instance(env: Env, salt: BytesN<32>) -> Result<Address, FactoryError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(FactoryError::NotInitialized)?;
+        admin.require_auth();
+        let wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::WasmHash)
+            .ok_or(FactoryError::NotInitialized)?;
+
+        let deployer = env.deployer().with_current_contract(salt.clone());
+        let expected = deployer.deployed_address();
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Deployed(expected.clone()))
+        {
+            return Err(FactoryError::AlreadyDeployed);
+        }
+
+        let instance = deployer.deploy(wasm_hash);
+        env.invoke_contract::<()>(
+            &instance,
+            &Symbol::new(&env, "initialize"),
+            vec![&env, admin.into_val(&env)],
+        );
+
+        let mut instances = Self::load_instances(&env);
+        instances.push_back(instance.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Instances, &instances);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Deployed(instance.clone()), &true);
+        env.events()
+            .publish((symbol_short!("deployed"), instance.clone()), salt);
+        Ok(instance)
+    }
+
+    /// Up to `limit` deployed addresses starting at index `start`, in
+    /// deployment order. Returns an empty list past the end.
+    pub fn get_instances(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let instances = Self::load_instances(&env);
+        let end = start.saturating_add(limit).min(instances.len());
+        if start >= end {
+            return Vec::new(&env);
+        }
+        instances.slice(start..end)
+    }
+
+    /// Number of instances deployed so far.
+    pub fn instance_count(env: Env) -> u32 {
+        Self::load_instances(&env).len()
+    }
+}
+
+impl FactoryContract {
+    fn load_instances(env: &Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Instances)
+            .unwrap_or(Vec::new(env))
+    }
+}
+
+mod test;
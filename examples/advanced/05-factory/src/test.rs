@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Env};
+
+mod counter {
+    soroban_sdk::contractimport!(file = "fixtures/counter.wasm");
+}
+
+fn setup() -> (Env, Address, FactoryContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let wasm_hash = env.deployer().upload_contract_wasm(counter::WASM);
+    let factory_id = env.register_contract(None, FactoryContract);
+    let client = FactoryContractClient::new(&env, &factory_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &wasm_hash);
+    (env, admin, client)
+}
+
+fn salt(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+#[test]
+fn test_deploys_instances_at_distinct_addresses() {
+    let (env, _admin, client) = setup();
+
+    let first = client.deploy_instance(&salt(&env, 1));
+    let second = client.deploy_instance(&salt(&env, 2));
+
+    assert_ne!(first, second);
+    assert_eq!(client.instance_count(), 2);
+}
+
+#[test]
+fn test_address_is_deterministic_per_salt() {
+    let (env, _admin, client) = setup();
+    let salt = salt(&env, 7);
+
+    let expected = env.as_contract(&client.address, || {
+        env.deployer()
+            .with_current_contract(salt.clone())
+            .deployed_address()
+    });
+    assert_eq!(client.deploy_instance(&salt), expected);
+}
+
+#[test]
+fn test_redeploy_with_same_salt_fails() {
+    let (env, _admin, client) = setup();
+    client.deploy_instance(&salt(&env, 1));
+
+    let result = client.try_deploy_instance(&salt(&env, 1));
+    assert_eq!(result, Err(Ok(FactoryError::AlreadyDeployed)));
+    assert_eq!(client.instance_count(), 1);
+}
+
+#[test]
+fn test_deployed_child_is_initialized_and_callable() {
+    let (env, admin, client) = setup();
+    let child_id = client.deploy_instance(&salt(&env, 1));
+    let child = counter::Client::new(&env, &child_id);
+
+    assert_eq!(child.increment(), 1);
+    assert_eq!(child.increment(), 2);
+    child.reset(&admin);
+    assert_eq!(child.get(), 0);
+
+    // The factory already initialized the child, so nobody else can.
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        child.try_initialize(&stranger),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            counter::Error::AlreadyInitialized as u32
+        )))
+    );
+}
+
+#[test]
+fn test_children_have_independent_state() {
+    let (env, _admin, client) = setup();
+    let a = counter::Client::new(&env, &client.deploy_instance(&salt(&env, 1)));
+    let b = counter::Client::new(&env, &client.deploy_instance(&salt(&env, 2)));
+
+    a.increment();
+    a.increment();
+    b.increment();
+
+    assert_eq!(a.get(), 2);
+    assert_eq!(b.get(), 1);
+}
+
+#[test]
+fn test_index_pagination() {
+    let (env, _admin, client) = setup();
+    let mut deployed = Vec::new(&env);
+    for i in 0..5u8 {
+        deployed.push_back(client.deploy_instance(&salt(&env, i)));
+    }
+
+    assert_eq!(client.get_instances(&0, &2), deployed.slice(0..2));
+    assert_eq!(client.get_instances(&2, &2), deployed.slice(2..4));
+    assert_eq!(client.get_instances(&4, &2), deployed.slice(4..5));
+    assert_eq!(client.get_instances(&5, &2).len(), 0);
+    assert_eq!(client.get_instances(&0, &u32::MAX), deployed);
+    assert_eq!(client.get_instances(&1, &0).len(), 0);
+}
+
+#[test]
+fn test_deploy_before_initialize_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let factory_id = env.register_contract(None, FactoryContract);
+    let client = FactoryContractClient::new(&env, &factory_id);
+
+    let result = client.try_deploy_instance(&salt(&env, 1));
+    assert_eq!(result, Err(Ok(FactoryError::NotInitialized)));
+}
@@ -0,0 +1,97 @@
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, symbol_short, vec, Address, Env,
+    IntoVal, Symbol,
+};
+
+use crate::math_service::{MathError, MathServiceClient};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CallerError {
+    /// The service rejected the inputs with its own `Overflow` error.
+    ServiceOverflow = 1,
+    /// The call itself failed: missing function, wrong arguments, trap, or
+    /// an error code the service does not declare.
+    ServiceUnavailable = 2,
+    /// The service returned a value that is not a `u64`.
+    InvalidResponse = 3,
+    /// The registry has no entry for the service name.
+    NameNotFound = 4,
+}
+
+/// The one registry function the caller needs. Declaring it here, rather
+/// than depending on the registry crate, is all a client takes.
+#[contractclient(name = "RegistryClient")]
+pub trait Registry {
+    fn resolve(env: Env, name: Symbol) -> Option<Address>;
+}
+
+/// The caller: forwards additions to a `MathService` at a given address.
+#[contract]
+pub struct Caller;
+
+#[contractimpl]
+impl Caller {
+    /// Style 1: the typed client. Any callee error aborts this call too.
+    pub fn add_with_client(env: Env, service: Address, a: u64, b: u64) -> u64 {
+        MathServiceClient::new(&env, &service).add(&a, &b)
+    }
+
+    /// Style 2: untyped `invoke_contract`, naming the function by `Symbol`.
+    /// As with the typed client, any callee error aborts this call.
+    pub fn add_with_invoke(env: Env, service: Address, a: u64, b: u64) -> u64 {
+        env.invoke_contract(
+            &service,
+            &symbol_short!("add"),
+            vec![&env, a.into_val(&env), b.into_val(&env)],
+        )
+    }
+
+    /// Style 3: the `try_` client. Callee errors come back as values and are
+    /// mapped into `CallerError`, so this contract decides what to do.
+    pub fn try_add(env: Env, service: Address, a: u64, b: u64) -> Result<u64, CallerError> {
+        match MathServiceClient::new(&env, &service).try_add(&a, &b) {
+            Ok(Ok(sum)) => Ok(sum),
+            Ok(Err(_)) => Err(CallerError::InvalidResponse),
+            Err(Ok(MathError::Overflow)) => Err(CallerError::ServiceOverflow),
+            Err(Err(_)) => Err(CallerError::ServiceUnavailable),
+        }
+    }
+
+    /// Late binding: look the service up in a `47-contract-registry`
+    /// instance on every call, then add through the `try_` client as in
+    /// `try_add`. Repointing `name` in the registry moves this caller to a
+    /// new service without redeploying it.
+    pub fn add_by_name(
+        env: Env,
+        registry: Address,
+        name: Symbol,
+        a: u64,
+        b: u64,
+    ) -> Result<u64, CallerError> {
+        let service = RegistryClient::new(&env, &registry)
+            .resolve(&name)
+            .ok_or(CallerError::NameNotFound)?;
+        Self::try_add(env, service, a, b)
+    }
+
+    /// `try_invoke_contract` by function name. Shows how a contract error
+    /// declared by the callee (`Err(Ok(_))`) differs from a failed
+    /// invocation such as an unknown function (`Err(Err(_))`).
+    pub fn try_call_by_name(
+        env: Env,
+        service: Address,
+        func: Symbol,
+        a: u64,
+        b: u64,
+    ) -> Result<u64, CallerError> {
+        let args = vec![&env, a.into_val(&env), b.into_val(&env)];
+        match env.try_invoke_contract::<u64, MathError>(&service, &func, args) {
+            Ok(Ok(sum)) => Ok(sum),
+            Ok(Err(_)) => Err(CallerError::InvalidResponse),
+            Err(Ok(MathError::Overflow)) => Err(CallerError::ServiceOverflow),
+            Err(Err(_)) => Err(CallerError::ServiceUnavailable),
+        }
+    }
+}
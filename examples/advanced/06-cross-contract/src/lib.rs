@@ -0,0 +1,29 @@
+//! # Cross-Contract Calls
+//!
+//! A callee (`MathService`) and a caller (`Caller`) showing the three ways
+//! one contract can invoke another:
+//!
+//! 1. the generated typed client (`MathServiceClient::add`), which panics,
+//!    and so aborts the caller, if the callee fails;
+//! 2. `env.invoke_contract` with the function name as a `Symbol`, for when
+//!    no client type is available;
+//! 3. the `try_` client, which hands back the callee's error so the caller
+//!    can map it into its own error enum instead of aborting.
+//!
+//! `add_by_name` adds late binding on top: it resolves the callee's address
+//! from a `47-contract-registry` instance on every call, so repointing the
+//! name moves the caller to a new service.
+//!
+//! Both contracts live in this crate so the tests can register them side by
+//! side. In a real project the callee would be a separate crate and the
+//! caller would build its client with `contractimport!`.
+
+#![no_std]
+
+mod caller;
+mod math_service;
+
+pub use caller::{Caller, CallerClient, CallerError, Registry, RegistryClient};
+pub use math_service::{MathError, MathService, MathServiceClient};
+
+mod test;
@@ -0,0 +1,21 @@
+use soroban_sdk::{contract, contracterror, contractimpl, Env};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MathError {
+    /// `a + b` does not fit in a `u64`.
+    Overflow = 1,
+}
+
+/// The callee: a stateless arithmetic service.
+#[contract]
+pub struct MathService;
+
+#[contractimpl]
+impl MathService {
+    /// Return `a + b`, or `Overflow` if the sum does not fit in a `u64`.
+    pub fn add(_env: Env, a: u64, b: u64) -> Result<u64, MathError> {
+        a.checked_add(b).ok_or(MathError::Overflow)
+    }
+}
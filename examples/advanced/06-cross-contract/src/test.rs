@@ -0,0 +1,110 @@
+#![cfg(test)]
+
+use super::*;
+use contract_registry::{ContractRegistry, ContractRegistryClient};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol};
+
+fn setup() -> (Env, Address, CallerClient<'static>) {
+    let env = Env::default();
+    let service_id = env.register_contract(None, MathService);
+    let caller_id = env.register_contract(None, Caller);
+    let caller = CallerClient::new(&env, &caller_id);
+    (env, service_id, caller)
+}
+
+#[test]
+fn test_add_with_client() {
+    let (_env, service, caller) = setup();
+    assert_eq!(caller.add_with_client(&service, &2, &3), 5);
+}
+
+#[test]
+fn test_add_with_invoke() {
+    let (_env, service, caller) = setup();
+    assert_eq!(caller.add_with_invoke(&service, &40, &2), 42);
+}
+
+#[test]
+fn test_try_add_success() {
+    let (_env, service, caller) = setup();
+    assert_eq!(caller.try_add(&service, &7, &8), 15);
+}
+
+#[test]
+fn test_callee_error_is_mapped_through_try_client() {
+    let (_env, service, caller) = setup();
+    assert_eq!(
+        caller.try_try_add(&service, &u64::MAX, &1),
+        Err(Ok(CallerError::ServiceOverflow))
+    );
+}
+
+#[test]
+fn test_callee_error_aborts_typed_client_call() {
+    let (_env, service, caller) = setup();
+    assert!(caller.try_add_with_client(&service, &u64::MAX, &1).is_err());
+    assert!(caller.try_add_with_invoke(&service, &u64::MAX, &1).is_err());
+}
+
+#[test]
+fn test_callee_contract_error_by_name() {
+    let (env, service, caller) = setup();
+    let add = Symbol::new(&env, "add");
+    assert_eq!(caller.try_call_by_name(&service, &add, &1, &2), 3);
+    assert_eq!(
+        caller.try_try_call_by_name(&service, &add, &u64::MAX, &1),
+        Err(Ok(CallerError::ServiceOverflow))
+    );
+}
+
+#[test]
+fn test_missing_function_is_invocation_failure() {
+    let (env, service, caller) = setup();
+    let missing = Symbol::new(&env, "multiply");
+    assert_eq!(
+        caller.try_try_call_by_name(&service, &missing, &1, &2),
+        Err(Ok(CallerError::ServiceUnavailable))
+    );
+}
+
+#[test]
+fn test_calling_a_non_service_contract_is_invocation_failure() {
+    let (_env, _service, caller) = setup();
+    // The caller itself has no `add` function.
+    assert_eq!(
+        caller.try_try_add(&caller.address, &1, &2),
+        Err(Ok(CallerError::ServiceUnavailable))
+    );
+}
+
+#[test]
+fn test_callee_resolved_through_registry() {
+    let (env, service, caller) = setup();
+    env.mock_all_auths();
+    let registry =
+        ContractRegistryClient::new(&env, &env.register_contract(None, ContractRegistry));
+    let owner = Address::generate(&env);
+    let name = symbol_short!("math");
+
+    assert_eq!(
+        caller.try_add_by_name(&registry.address, &name, &1, &2),
+        Err(Ok(CallerError::NameNotFound))
+    );
+
+    registry.register(&owner, &name, &service);
+    assert_eq!(caller.add_by_name(&registry.address, &name, &1, &2), 3);
+
+    // Pointed at a contract with no `add`, the same call fails...
+    registry.register(&owner, &name, &caller.address);
+    assert_eq!(
+        caller.try_add_by_name(&registry.address, &name, &1, &2),
+        Err(Ok(CallerError::ServiceUnavailable))
+    );
+
+    // ...and repointed at a second service instance it works again, with
+    // no change to the caller.
+    let second = env.register_contract(None, MathService);
+    registry.register(&owner, &name, &second);
+    assert_eq!(registry.resolve(&name), Some(second));
+    assert_eq!(caller.add_by_name(&registry.address, &name, &40, &2), 42);
+}
@@ -0,0 +1,210 @@
+//! # Escrow Contract
+//!
+//! Holds a buyer's payment until the deal is settled, with a neutral
+//! arbiter to break deadlocks. Modelled as a small state machine in the
+//! style of the timelock example:
+//!
+//! ```text
+//! Unknown --create--> Funded --release--> Released
+//!                       |
+//!                       +-----refund----> Refunded
+//! ```
+//!
+//! - `release` pays the seller; the buyer or the arbiter may call it.
+//! - `refund` returns the funds to the buyer; the seller or the arbiter may
+//!   call it at any time, and anyone may call it once the deadline passes so
+//!   funds can never be stuck.
+//!
+//! Each contract instance holds exactly one escrow.
+
+#![no_std]
+
+use recipes_safe_token::{safe_transfer_verified, TokenXferError};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, Symbol,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Escrow,
+    State,
+}
+
+/// Lifecycle of the escrow.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowState {
+    /// `create` has not been called yet.
+    Unknown,
+    /// Funds are held by the contract.
+    Funded,
+    /// Funds were paid to the seller.
+    Released,
+    /// Funds were returned to the buyer.
+    Refunded,
+}
+
+/// Terms fixed at creation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Escrow {
+    pub buyer: Address,
+    pub seller: Address,
+    pub arbiter: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub deadline: u64,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EscrowError {
+    AlreadyCreated = 1,
+    /// The escrow is not in the `Funded` state.
+    NotFunded = 2,
+    /// The caller may not perform this transition.
+    NotAllowed = 3,
+    InvalidAmount = 4,
+    /// The deadline is not in the future.
+    InvalidDeadline = 5,
+    /// The buyer holds less than `amount`.
+    InsufficientFunds = 6,
+    /// The token rejected the deposit, or the escrow did not receive exactly
+    /// `amount`.
+    DepositFailed = 7,
+}
+
+impl From<TokenXferError> for EscrowError {
+    fn from(err: TokenXferError) -> Self {
+        match err {
+            TokenXferError::InsufficientBalance => EscrowError::InsufficientFunds,
+            _ => EscrowError::DepositFailed,
+        }
+    }
+}
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Open the escrow and pull `amount` of `token` from the buyer.
+    ///
+    /// The deposit is verified, so a token that charges a fee on transfer
+    /// cannot open an escrow holding less than its terms say.
+    ///
+    /// Emits `("created",)` with the escrow terms.
+    pub fn create(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        arbiter: Address,
+        token: Address,
+        amount: i128,
+        deadline: u64,
+    ) -> Result<(), EscrowError> {
+        if Self::get_state(env.clone()) != EscrowState::Unknown {
+            return Err(EscrowError::AlreadyCreated);
+        }
+        if amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+        if deadline <= env.ledger().timestamp() {
+            return Err(EscrowError::InvalidDeadline);
+        }
+        buyer.require_auth();
+
+        let vault = env.current_contract_address();
+        safe_transfer_verified(&env, &token, &buyer, &vault, amount)?;
+
+        let escrow = Escrow {
+            buyer,
+            seller,
+            arbiter,
+            token,
+            amount,
+            deadline,
+        };
+        env.storage().instance().set(&DataKey::Escrow, &escrow);
+        env.storage()
+            .instance()
+            .set(&DataKey::State, &EscrowState::Funded);
+        env.events()
+            .publish((Symbol::new(&env, "created"),), escrow);
+        Ok(())
+    }
+
+    /// Pay the held funds to the seller. `caller` must be the buyer or the
+    /// arbiter.
+    ///
+    /// Emits `("released",)` with `(caller, amount)`.
+    pub fn release(env: Env, caller: Address) -> Result<(), EscrowError> {
+        let escrow = Self::funded_escrow(&env)?;
+        if caller != escrow.buyer && caller != escrow.arbiter {
+            return Err(EscrowError::NotAllowed);
+        }
+        caller.require_auth();
+
+        Self::settle(&env, &escrow, &escrow.seller, EscrowState::Released);
+        env.events()
+            .publish((Symbol::new(&env, "released"),), (caller, escrow.amount));
+        Ok(())
+    }
+
+    /// Return the held funds to the buyer.
+    ///
+    /// Before the deadline `caller` must be the seller or the arbiter; from
+    /// the deadline on anyone may trigger the refund.
+    ///
+    /// Emits `("refunded",)` with `(caller, amount)`.
+    pub fn refund(env: Env, caller: Address) -> Result<(), EscrowError> {
+        let escrow = Self::funded_escrow(&env)?;
+        let expired = env.ledger().timestamp() >= escrow.deadline;
+        if !expired && caller != escrow.seller && caller != escrow.arbiter {
+            return Err(EscrowError::NotAllowed);
+        }
+        caller.require_auth();
+
+        Self::settle(&env, &escrow, &escrow.buyer, EscrowState::Refunded);
+        env.events()
+            .publish((Symbol::new(&env, "refunded"),), (caller, escrow.amount));
+        Ok(())
+    }
+
+    /// Current lifecycle state.
+    pub fn get_state(env: Env) -> EscrowState {
+        env.storage()
+            .instance()
+            .get(&DataKey::State)
+            .unwrap_or(EscrowState::Unknown)
+    }
+
+    /// Escrow terms, or `None` before `create`.
+    pub fn get_escrow(env: Env) -> Option<Escrow> {
+        env.storage().instance().get(&DataKey::Escrow)
+    }
+}
+
+impl EscrowContract {
+    fn funded_escrow(env: &Env) -> Result<Escrow, EscrowError> {
+        if Self::get_state(env.clone()) != EscrowState::Funded {
+            return Err(EscrowError::NotFunded);
+        }
+        Ok(Self::get_escrow(env.clone()).unwrap())
+    }
+
+    /// Moves the held funds to `recipient` and records the final state.
+    /// The state is written first so the escrow can never settle twice.
+    fn settle(env: &Env, escrow: &Escrow, recipient: &Address, state: EscrowState) {
+        env.storage().instance().set(&DataKey::State, &state);
+        token::Client::new(env, &escrow.token).transfer(
+            &env.current_contract_address(),
+            recipient,
+            &escrow.amount,
+        );
+    }
+}
+
+mod test;
@@ -0,0 +1,216 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal,
+};
+
+const AMOUNT: i128 = 1_000;
+const DEADLINE: u64 = 10_000;
+
+struct Setup {
+    env: Env,
+    buyer: Address,
+    seller: Address,
+    arbiter: Address,
+    token: TokenClient<'static>,
+    escrow: EscrowContractClient<'static>,
+}
+
+/// Registers a Stellar asset, funds the buyer, and opens the escrow.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let issuer = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(issuer);
+    let token = TokenClient::new(&env, &asset.address());
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &AMOUNT);
+
+    let escrow_id = env.register_contract(None, EscrowContract);
+    let escrow = EscrowContractClient::new(&env, &escrow_id);
+    assert_eq!(escrow.get_state(), EscrowState::Unknown);
+    escrow.create(
+        &buyer,
+        &seller,
+        &arbiter,
+        &asset.address(),
+        &AMOUNT,
+        &DEADLINE,
+    );
+
+    Setup {
+        env,
+        buyer,
+        seller,
+        arbiter,
+        token,
+        escrow,
+    }
+}
+
+#[test]
+fn test_create_pulls_funds_from_buyer() {
+    let s = setup();
+    assert_eq!(s.escrow.get_state(), EscrowState::Funded);
+    assert_eq!(s.token.balance(&s.buyer), 0);
+    assert_eq!(s.token.balance(&s.escrow.address), AMOUNT);
+
+    let terms = s.escrow.get_escrow().unwrap();
+    assert_eq!(terms.seller, s.seller);
+    assert_eq!(terms.deadline, DEADLINE);
+
+    let (_, topics, _) = s.env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&s.env, "created"),).into_val(&s.env));
+}
+
+#[test]
+fn test_happy_release_by_buyer() {
+    let s = setup();
+    s.escrow.release(&s.buyer);
+
+    assert_eq!(s.escrow.get_state(), EscrowState::Released);
+    assert_eq!(s.token.balance(&s.seller), AMOUNT);
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+
+    let (_, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&s.env, "released"),).into_val(&s.env));
+    let (caller, amount): (Address, i128) = data.into_val(&s.env);
+    assert_eq!(caller, s.buyer);
+    assert_eq!(amount, AMOUNT);
+}
+
+#[test]
+fn test_arbiter_can_release() {
+    let s = setup();
+    s.escrow.release(&s.arbiter);
+    assert_eq!(s.token.balance(&s.seller), AMOUNT);
+}
+
+#[test]
+fn test_seller_cannot_release() {
+    let s = setup();
+    assert_eq!(
+        s.escrow.try_release(&s.seller),
+        Err(Ok(EscrowError::NotAllowed))
+    );
+    assert_eq!(s.escrow.get_state(), EscrowState::Funded);
+}
+
+#[test]
+fn test_double_release_rejected() {
+    let s = setup();
+    s.escrow.release(&s.buyer);
+    assert_eq!(
+        s.escrow.try_release(&s.buyer),
+        Err(Ok(EscrowError::NotFunded))
+    );
+    assert_eq!(
+        s.escrow.try_refund(&s.arbiter),
+        Err(Ok(EscrowError::NotFunded))
+    );
+    assert_eq!(s.token.balance(&s.seller), AMOUNT);
+}
+
+#[test]
+fn test_arbiter_forced_refund() {
+    let s = setup();
+    s.escrow.refund(&s.arbiter);
+
+    assert_eq!(s.escrow.get_state(), EscrowState::Refunded);
+    assert_eq!(s.token.balance(&s.buyer), AMOUNT);
+    assert_eq!(s.token.balance(&s.seller), 0);
+}
+
+#[test]
+fn test_stranger_refund_only_after_deadline() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+
+    assert_eq!(
+        s.escrow.try_refund(&stranger),
+        Err(Ok(EscrowError::NotAllowed))
+    );
+    assert_eq!(
+        s.escrow.try_refund(&s.buyer),
+        Err(Ok(EscrowError::NotAllowed))
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = DEADLINE);
+    s.escrow.refund(&stranger);
+    assert_eq!(s.escrow.get_state(), EscrowState::Refunded);
+    assert_eq!(s.token.balance(&s.buyer), AMOUNT);
+}
+
+#[test]
+fn test_create_twice_rejected() {
+    let s = setup();
+    let terms = s.escrow.get_escrow().unwrap();
+    assert_eq!(
+        s.escrow.try_create(
+            &terms.buyer,
+            &terms.seller,
+            &terms.arbiter,
+            &terms.token,
+            &terms.amount,
+            &terms.deadline,
+        ),
+        Err(Ok(EscrowError::AlreadyCreated))
+    );
+}
+
+#[test]
+fn test_create_validates_terms() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    let escrow = EscrowContractClient::new(&env, &env.register_contract(None, EscrowContract));
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let party = Address::generate(&env);
+
+    assert_eq!(
+        escrow.try_create(&party, &party, &party, &token.address(), &0, &1_000),
+        Err(Ok(EscrowError::InvalidAmount))
+    );
+    assert_eq!(
+        escrow.try_create(&party, &party, &party, &token.address(), &1, &500),
+        Err(Ok(EscrowError::InvalidDeadline))
+    );
+    assert_eq!(escrow.get_state(), EscrowState::Unknown);
+}
+
+#[test]
+fn test_create_reports_deposit_failures() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let escrow = EscrowContractClient::new(&env, &env.register_contract(None, EscrowContract));
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let buyer = Address::generate(&env);
+    let party = Address::generate(&env);
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &AMOUNT);
+
+    assert_eq!(
+        escrow.try_create(
+            &buyer,
+            &party,
+            &party,
+            &asset.address(),
+            &(AMOUNT + 1),
+            &DEADLINE
+        ),
+        Err(Ok(EscrowError::InsufficientFunds))
+    );
+    // An address with no token behind it.
+    let not_a_token = Address::generate(&env);
+    assert_eq!(
+        escrow.try_create(&buyer, &party, &party, &not_a_token, &AMOUNT, &DEADLINE),
+        Err(Ok(EscrowError::DepositFailed))
+    );
+    assert_eq!(escrow.get_state(), EscrowState::Unknown);
+}
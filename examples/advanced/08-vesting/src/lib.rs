@@ -0,0 +1,214 @@
+//! # Token Vesting
+//!
+//! Escrows tokens for a beneficiary and releases them linearly over time,
+//! with nothing available before a cliff:
+//!
+//! ```text
+//! vested(t) = 0                                 if t < start + cliff
+//!           = total * (t - start) / duration    if start + cliff <= t < start + duration
+//!           = total                             if t >= start + duration
+//! ```
+//!
+//! `cliff` and `duration` are both measured in seconds from `start`.
+//! Division rounds down, so the beneficiary never receives more than has
+//! vested and any remainder is paid out by the final claim. All arithmetic
+//! is checked i128 math.
+//!
+//! The admin may revoke a schedule: the vested part stays claimable by the
+//! beneficiary and the unvested remainder returns to the admin.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Schedule(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub admin: Address,
+    pub token: Address,
+    pub total: i128,
+    pub claimed: i128,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    /// Set once the admin has revoked the schedule; `total` is then frozen
+    /// at the amount vested at revocation time.
+    pub revoked: bool,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VestingError {
+    AlreadyExists = 1,
+    NotFound = 2,
+    /// Non-positive total, zero duration, or a cliff longer than the duration.
+    InvalidSchedule = 3,
+    NotAdmin = 4,
+    NothingToClaim = 5,
+    AlreadyRevoked = 6,
+    MathOverflow = 7,
+}
+
+#[contract]
+pub struct VestingContract;
+
+#[contractimpl]
+impl VestingContract {
+    /// Create a schedule for `beneficiary` and pull `total` of `token` from
+    /// `admin` into the contract. One schedule per beneficiary.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_vesting(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        token: Address,
+        total: i128,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<(), VestingError> {
+        admin.require_auth();
+        let key = DataKey::Schedule(beneficiary.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(VestingError::AlreadyExists);
+        }
+        if total <= 0 || duration == 0 || cliff > duration {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        token::Client::new(&env, &token).transfer(&admin, &env.current_contract_address(), &total);
+        let schedule = VestingSchedule {
+            admin,
+            token,
+            total,
+            claimed: 0,
+            start,
+            cliff,
+            duration,
+            revoked: false,
+        };
+        env.storage().persistent().set(&key, &schedule);
+        env.events()
+            .publish((symbol_short!("vest_new"), beneficiary), total);
+        Ok(())
+    }
+
+    /// Amount `beneficiary` could claim right now.
+    pub fn claimable(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        let schedule = Self::load(&env, &beneficiary)?;
+        Self::claimable_now(&env, &schedule)
+    }
+
+    /// Transfer everything currently claimable to `beneficiary` and return
+    /// the amount paid.
+    pub fn claim(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        beneficiary.require_auth();
+        let mut schedule = Self::load(&env, &beneficiary)?;
+        let amount = Self::claimable_now(&env, &schedule)?;
+        if amount == 0 {
+            return Err(VestingError::NothingToClaim);
+        }
+
+        schedule.claimed = schedule
+            .claimed
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(beneficiary.clone()), &schedule);
+        token::Client::new(&env, &schedule.token).transfer(
+            &env.current_contract_address(),
+            &beneficiary,
+            &amount,
+        );
+        env.events()
+            .publish((symbol_short!("vest_clm"), beneficiary), amount);
+        Ok(amount)
+    }
+
+    /// Stop vesting for `beneficiary` and return the unvested remainder to
+    /// the admin. Whatever has vested so far stays claimable. Returns the
+    /// amount returned to the admin.
+    pub fn revoke(env: Env, admin: Address, beneficiary: Address) -> Result<i128, VestingError> {
+        admin.require_auth();
+        let mut schedule = Self::load(&env, &beneficiary)?;
+        if schedule.admin != admin {
+            return Err(VestingError::NotAdmin);
+        }
+        if schedule.revoked {
+            return Err(VestingError::AlreadyRevoked);
+        }
+
+        let vested = Self::vested_at(&schedule, env.ledger().timestamp())?;
+        let unvested = schedule
+            .total
+            .checked_sub(vested)
+            .ok_or(VestingError::MathOverflow)?;
+        schedule.total = vested;
+        schedule.revoked = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(beneficiary.clone()), &schedule);
+        if unvested > 0 {
+            token::Client::new(&env, &schedule.token).transfer(
+                &env.current_contract_address(),
+                &admin,
+                &unvested,
+            );
+        }
+        env.events()
+            .publish((symbol_short!("vest_rvk"), beneficiary), unvested);
+        Ok(unvested)
+    }
+
+    /// The stored schedule for `beneficiary`, if any.
+    pub fn get_schedule(env: Env, beneficiary: Address) -> Option<VestingSchedule> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Schedule(beneficiary))
+    }
+}
+
+impl VestingContract {
+    fn load(env: &Env, beneficiary: &Address) -> Result<VestingSchedule, VestingError> {
+        Self::get_schedule(env.clone(), beneficiary.clone()).ok_or(VestingError::NotFound)
+    }
+
+    fn claimable_now(env: &Env, schedule: &VestingSchedule) -> Result<i128, VestingError> {
+        let vested = Self::vested_at(schedule, env.ledger().timestamp())?;
+        vested
+            .checked_sub(schedule.claimed)
+            .ok_or(VestingError::MathOverflow)
+    }
+
+    /// Total vested at `now`, rounded down. A revoked schedule is frozen at
+    /// its (already reduced) total.
+    fn vested_at(schedule: &VestingSchedule, now: u64) -> Result<i128, VestingError> {
+        if schedule.revoked {
+            return Ok(schedule.total);
+        }
+        let elapsed = now.saturating_sub(schedule.start);
+        if elapsed < schedule.cliff {
+            return Ok(0);
+        }
+        if elapsed >= schedule.duration {
+            return Ok(schedule.total);
+        }
+        schedule
+            .total
+            .checked_mul(i128::from(elapsed))
+            .and_then(|scaled| scaled.checked_div(i128::from(schedule.duration)))
+            .ok_or(VestingError::MathOverflow)
+    }
+}
+
+mod test;
@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use super::*;
+use recipes_testutils::set_timestamp;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env,
+};
+
+const START: u64 = 1_000;
+const CLIFF: u64 = 100;
+const DURATION: u64 = 1_000;
+const TOTAL: i128 = 10_000;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    beneficiary: Address,
+    token: TokenClient<'static>,
+    vesting: VestingContractClient<'static>,
+}
+
+fn setup_with(total: i128, cliff: u64, duration: u64) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, START);
+
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let token = TokenClient::new(&env, &asset.address());
+    let admin = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    StellarAssetClient::new(&env, &asset.address()).mint(&admin, &total);
+
+    let vesting = VestingContractClient::new(&env, &env.register_contract(None, VestingContract));
+    vesting.create_vesting(
+        &admin,
+        &beneficiary,
+        &asset.address(),
+        &total,
+        &START,
+        &cliff,
+        &duration,
+    );
+
+    Setup {
+        env,
+        admin,
+        beneficiary,
+        token,
+        vesting,
+    }
+}
+
+fn setup() -> Setup {
+    setup_with(TOTAL, CLIFF, DURATION)
+}
+
+#[test]
+fn test_create_escrows_total() {
+    let s = setup();
+    assert_eq!(s.token.balance(&s.vesting.address), TOTAL);
+    assert_eq!(s.token.balance(&s.admin), 0);
+}
+
+#[test]
+fn test_nothing_claimable_before_cliff() {
+    let s = setup();
+    set_timestamp(&s.env, START + CLIFF - 1);
+    assert_eq!(s.vesting.claimable(&s.beneficiary), 0);
+    assert_eq!(
+        s.vesting.try_claim(&s.beneficiary),
+        Err(Ok(VestingError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_cliff_releases_linear_amount_so_far() {
+    let s = setup();
+    set_timestamp(&s.env, START + CLIFF);
+    assert_eq!(s.vesting.claimable(&s.beneficiary), 1_000);
+}
+
+#[test]
+fn test_partial_linear_claims() {
+    let s = setup();
+
+    set_timestamp(&s.env, START + 250);
+    assert_eq!(s.vesting.claim(&s.beneficiary), 2_500);
+
+    set_timestamp(&s.env, START + 600);
+    assert_eq!(s.vesting.claimable(&s.beneficiary), 3_500);
+    assert_eq!(s.vesting.claim(&s.beneficiary), 3_500);
+
+    set_timestamp(&s.env, START + 900);
+    assert_eq!(s.vesting.claim(&s.beneficiary), 3_000);
+
+    assert_eq!(s.token.balance(&s.beneficiary), 9_000);
+    assert_eq!(
+        s.vesting.get_schedule(&s.beneficiary).unwrap().claimed,
+        9_000
+    );
+}
+
+#[test]
+fn test_full_claim_after_duration() {
+    let s = setup();
+    set_timestamp(&s.env, START + DURATION + 50);
+    assert_eq!(s.vesting.claim(&s.beneficiary), TOTAL);
+    assert_eq!(s.token.balance(&s.beneficiary), TOTAL);
+    assert_eq!(s.token.balance(&s.vesting.address), 0);
+    assert_eq!(
+        s.vesting.try_claim(&s.beneficiary),
+        Err(Ok(VestingError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_revocation_mid_stream_splits_vested_and_unvested() {
+    let s = setup();
+    set_timestamp(&s.env, START + 200);
+    s.vesting.claim(&s.beneficiary);
+
+    set_timestamp(&s.env, START + 400);
+    assert_eq!(s.vesting.revoke(&s.admin, &s.beneficiary), 6_000);
+    assert_eq!(s.token.balance(&s.admin), 6_000);
+
+    // Vested-but-unclaimed stays claimable, and nothing more ever vests.
+    set_timestamp(&s.env, START + DURATION * 2);
+    assert_eq!(s.vesting.claimable(&s.beneficiary), 2_000);
+    s.vesting.claim(&s.beneficiary);
+    assert_eq!(s.token.balance(&s.beneficiary), 4_000);
+    assert_eq!(s.token.balance(&s.vesting.address), 0);
+
+    assert_eq!(
+        s.vesting.try_revoke(&s.admin, &s.beneficiary),
+        Err(Ok(VestingError::AlreadyRevoked))
+    );
+}
+
+#[test]
+fn test_only_schedule_admin_can_revoke() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.vesting.try_revoke(&stranger, &s.beneficiary),
+        Err(Ok(VestingError::NotAdmin))
+    );
+}
+
+#[test]
+fn test_rounding_at_second_boundaries() {
+    let s = setup_with(1_000, 0, 3);
+
+    set_timestamp(&s.env, START + 1);
+    assert_eq!(s.vesting.claimable(&s.beneficiary), 333);
+    set_timestamp(&s.env, START + 2);
+    assert_eq!(s.vesting.claimable(&s.beneficiary), 666);
+    assert_eq!(s.vesting.claim(&s.beneficiary), 666);
+
+    // The final second pays out the rounding remainder.
+    set_timestamp(&s.env, START + 3);
+    assert_eq!(s.vesting.claim(&s.beneficiary), 334);
+    assert_eq!(s.token.balance(&s.beneficiary), 1_000);
+}
+
+#[test]
+fn test_invalid_schedules_rejected() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    let token = s.token.address.clone();
+
+    assert_eq!(
+        s.vesting
+            .try_create_vesting(&s.admin, &other, &token, &0, &START, &0, &10),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(
+        s.vesting
+            .try_create_vesting(&s.admin, &other, &token, &10, &START, &0, &0),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(
+        s.vesting
+            .try_create_vesting(&s.admin, &other, &token, &10, &START, &11, &10),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(
+        s.vesting
+            .try_create_vesting(&s.admin, &s.beneficiary, &token, &10, &START, &0, &10),
+        Err(Ok(VestingError::AlreadyExists))
+    );
+}
@@ -0,0 +1,67 @@
+//! # Atomic Swap
+//!
+//! Two parties trade tokens in a single transaction: either both legs move
+//! or neither does. Neither party has to trust the other or a custodian.
+//!
+//! Each party signs only *their own* terms with `require_auth_for_args`:
+//!
+//! - `a` authorizes `(token_a, token_b, amount_a, min_b)`: "I give
+//!   `amount_a` of `token_a` if I receive at least `min_b` of `token_b`."
+//! - `b` authorizes `(token_b, token_a, amount_b, min_a)`.
+//!
+//! Because the signed payload omits the counterparty's amounts, a
+//! matchmaker can pair any two compatible offers without either party
+//! signing again. The contract then checks that each side receives at least
+//! its minimum.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, token, Address, Env, IntoVal};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SwapError {
+    /// One side would receive less than the minimum it signed for.
+    MinimumNotMet = 1,
+    InvalidAmount = 2,
+}
+
+#[contract]
+pub struct AtomicSwapContract;
+
+#[contractimpl]
+impl AtomicSwapContract {
+    /// Swap `amount_a` of `token_a` from `a` for `amount_b` of `token_b`
+    /// from `b`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap(
+        env: Env,
+        a: Address,
+        b: Address,
+        token_a: Address,
+        token_b: Address,
+        amount_a: i128,
+        min_b: i128,
+        amount_b: i128,
+        min_a: i128,
+    ) -> Result<(), SwapError> {
+        if amount_a <= 0 || amount_b <= 0 {
+            return Err(SwapError::InvalidAmount);
+        }
+        if amount_b < min_b || amount_a < min_a {
+            return Err(SwapError::MinimumNotMet);
+        }
+
+        a.require_auth_for_args((token_a.clone(), token_b.clone(), amount_a, min_b).into_val(&env));
+        b.require_auth_for_args((token_b.clone(), token_a.clone(), amount_b, min_a).into_val(&env));
+
+        // If either transfer fails (e.g. insufficient balance) the whole
+        // invocation aborts and the other leg is rolled back with it.
+        token::Client::new(&env, &token_a).transfer(&a, &b, &amount_a);
+        token::Client::new(&env, &token_b).transfer(&b, &a, &amount_b);
+        Ok(())
+    }
+}
+
+mod test;
@@ -0,0 +1,250 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, MockAuth, MockAuthInvoke},
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal, Symbol,
+};
+
+struct Setup {
+    env: Env,
+    a: Address,
+    b: Address,
+    token_a: TokenClient<'static>,
+    token_b: TokenClient<'static>,
+    swap: AtomicSwapContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+
+    let asset_a = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let asset_b = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    env.mock_all_auths();
+    StellarAssetClient::new(&env, &asset_a.address()).mint(&a, &1_000);
+    StellarAssetClient::new(&env, &asset_b.address()).mint(&b, &5_000);
+
+    let swap =
+        AtomicSwapContractClient::new(&env, &env.register_contract(None, AtomicSwapContract));
+    Setup {
+        token_a: TokenClient::new(&env, &asset_a.address()),
+        token_b: TokenClient::new(&env, &asset_b.address()),
+        env,
+        a,
+        b,
+        swap,
+    }
+}
+
+fn assert_balances(s: &Setup, a: (i128, i128), b: (i128, i128)) {
+    assert_eq!(
+        (s.token_a.balance(&s.a), s.token_b.balance(&s.a)),
+        a,
+        "balances of a"
+    );
+    assert_eq!(
+        (s.token_a.balance(&s.b), s.token_b.balance(&s.b)),
+        b,
+        "balances of b"
+    );
+}
+
+#[test]
+fn test_successful_swap() {
+    let s = setup();
+    s.swap.swap(
+        &s.a,
+        &s.b,
+        &s.token_a.address,
+        &s.token_b.address,
+        &1_000,
+        &4_500,
+        &5_000,
+        &950,
+    );
+
+    // Each party signed exactly its own leg's terms.
+    assert_eq!(
+        s.env.auths(),
+        std::vec![
+            (
+                s.a.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        s.swap.address.clone(),
+                        Symbol::new(&s.env, "swap"),
+                        (
+                            s.token_a.address.clone(),
+                            s.token_b.address.clone(),
+                            1_000_i128,
+                            4_500_i128
+                        )
+                            .into_val(&s.env),
+                    )),
+                    sub_invocations: std::vec![AuthorizedInvocation {
+                        function: AuthorizedFunction::Contract((
+                            s.token_a.address.clone(),
+                            Symbol::new(&s.env, "transfer"),
+                            (s.a.clone(), s.b.clone(), 1_000_i128).into_val(&s.env),
+                        )),
+                        sub_invocations: std::vec![],
+                    }],
+                }
+            ),
+            (
+                s.b.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        s.swap.address.clone(),
+                        Symbol::new(&s.env, "swap"),
+                        (
+                            s.token_b.address.clone(),
+                            s.token_a.address.clone(),
+                            5_000_i128,
+                            950_i128
+                        )
+                            .into_val(&s.env),
+                    )),
+                    sub_invocations: std::vec![AuthorizedInvocation {
+                        function: AuthorizedFunction::Contract((
+                            s.token_b.address.clone(),
+                            Symbol::new(&s.env, "transfer"),
+                            (s.b.clone(), s.a.clone(), 5_000_i128).into_val(&s.env),
+                        )),
+                        sub_invocations: std::vec![],
+                    }],
+                }
+            ),
+        ]
+    );
+    assert_balances(&s, (0, 5_000), (1_000, 0));
+}
+
+#[test]
+fn test_minimum_not_met() {
+    let s = setup();
+    // b only offers 4_000 but a demands at least 4_500.
+    assert_eq!(
+        s.swap.try_swap(
+            &s.a,
+            &s.b,
+            &s.token_a.address,
+            &s.token_b.address,
+            &1_000,
+            &4_500,
+            &4_000,
+            &950,
+        ),
+        Err(Ok(SwapError::MinimumNotMet))
+    );
+    assert_balances(&s, (1_000, 0), (0, 5_000));
+}
+
+/// Authorize only `signer`'s leg of a 1_000 A <-> 5_000 B swap.
+fn mock_single_leg(s: &Setup, signer: &Address, args: soroban_sdk::Vec<soroban_sdk::Val>) {
+    s.env.mock_auths(&[MockAuth {
+        address: signer,
+        invoke: &MockAuthInvoke {
+            contract: &s.swap.address,
+            fn_name: "swap",
+            args,
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+#[test]
+fn test_missing_auth_from_b_rejected() {
+    let s = setup();
+    let a_terms = (
+        s.token_a.address.clone(),
+        s.token_b.address.clone(),
+        1_000_i128,
+        5_000_i128,
+    )
+        .into_val(&s.env);
+    mock_single_leg(&s, &s.a, a_terms);
+
+    let result = s.swap.try_swap(
+        &s.a,
+        &s.b,
+        &s.token_a.address,
+        &s.token_b.address,
+        &1_000,
+        &5_000,
+        &5_000,
+        &1_000,
+    );
+    assert!(matches!(result, Err(Err(_))));
+    s.env.mock_all_auths();
+    assert_balances(&s, (1_000, 0), (0, 5_000));
+}
+
+#[test]
+fn test_missing_auth_from_a_rejected() {
+    let s = setup();
+    let b_terms = (
+        s.token_b.address.clone(),
+        s.token_a.address.clone(),
+        5_000_i128,
+        1_000_i128,
+    )
+        .into_val(&s.env);
+    mock_single_leg(&s, &s.b, b_terms);
+
+    let result = s.swap.try_swap(
+        &s.a,
+        &s.b,
+        &s.token_a.address,
+        &s.token_b.address,
+        &1_000,
+        &5_000,
+        &5_000,
+        &1_000,
+    );
+    assert!(matches!(result, Err(Err(_))));
+    s.env.mock_all_auths();
+    assert_balances(&s, (1_000, 0), (0, 5_000));
+}
+
+#[test]
+fn test_insufficient_balance_leaves_everything_untouched() {
+    let s = setup();
+    // b only holds 5_000 of token_b; a's leg would succeed on its own but
+    // must be rolled back when b's transfer fails.
+    let result = s.swap.try_swap(
+        &s.a,
+        &s.b,
+        &s.token_a.address,
+        &s.token_b.address,
+        &1_000,
+        &6_000,
+        &6_000,
+        &1_000,
+    );
+    assert!(matches!(result, Err(Err(_))));
+    assert_balances(&s, (1_000, 0), (0, 5_000));
+}
+
+#[test]
+fn test_non_positive_amount_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.swap.try_swap(
+            &s.a,
+            &s.b,
+            &s.token_a.address,
+            &s.token_b.address,
+            &0,
+            &0,
+            &5_000,
+            &0,
+        ),
+        Err(Ok(SwapError::InvalidAmount))
+    );
+}
@@ -0,0 +1,356 @@
+//! # Constant-Product AMM
+//!
+//! A single pool holding two tokens. Prices come from the invariant
+//! `reserve_a * reserve_b = k`, and every swap pays a 30 bps fee to liquidity
+//! providers.
+//!
+//! - `deposit` adds liquidity in the current pool ratio and mints share units.
+//! - `swap` sells one token for the other, with a `min_out` slippage guard.
+//! - `withdraw` burns shares for a proportional cut of both reserves.
+//!
+//! All arithmetic goes through checked helpers that return typed errors
+//! instead of panicking, as in the error-handling example. Every rounding
+//! step favours the pool, so `k` never decreases.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+/// Fee taken from every swap input, in basis points.
+pub const FEE_BPS: i128 = 30;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+const NAMESPACE: Symbol = symbol_short!("amm");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    TokenA,
+    TokenB,
+    ReserveA,
+    ReserveB,
+    TotalShares,
+    Shares(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AmmError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// Both sides of the pool are the same token, or `sell_token` is not in
+    /// the pool.
+    InvalidToken = 3,
+    InvalidAmount = 4,
+    /// The swap would pay out less than `min_out`.
+    SlippageExceeded = 5,
+    InsufficientShares = 6,
+    /// The operation would mint zero shares or pay out zero tokens.
+    InsufficientLiquidity = 7,
+    MathOverflow = 8,
+    DivisionByZero = 9,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositEvent {
+    pub amount_a: i128,
+    pub amount_b: i128,
+    pub shares: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapEvent {
+    pub sell_token: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawEvent {
+    pub shares: i128,
+    pub amount_a: i128,
+    pub amount_b: i128,
+}
+
+#[contract]
+pub struct AmmContract;
+
+#[contractimpl]
+impl AmmContract {
+    pub fn initialize(env: Env, token_a: Address, token_b: Address) -> Result<(), AmmError> {
+        if env.storage().instance().has(&DataKey::TokenA) {
+            return Err(AmmError::AlreadyInitialized);
+        }
+        if token_a == token_b {
+            return Err(AmmError::InvalidToken);
+        }
+        let storage = env.storage().instance();
+        storage.set(&DataKey::TokenA, &token_a);
+        storage.set(&DataKey::TokenB, &token_b);
+        storage.set(&DataKey::ReserveA, &0_i128);
+        storage.set(&DataKey::ReserveB, &0_i128);
+        storage.set(&DataKey::TotalShares, &0_i128);
+        Ok(())
+    }
+
+    /// Add liquidity and return the shares minted.
+    ///
+    /// The first deposit sets the price and mints `sqrt(amount_a * amount_b)`
+    /// shares. Later deposits are trimmed to the current reserve ratio, so
+    /// only the matching amount of the over-supplied token is pulled.
+    pub fn deposit(
+        env: Env,
+        provider: Address,
+        amount_a: i128,
+        amount_b: i128,
+    ) -> Result<i128, AmmError> {
+        provider.require_auth();
+        if amount_a <= 0 || amount_b <= 0 {
+            return Err(AmmError::InvalidAmount);
+        }
+        let (reserve_a, reserve_b) = Self::get_reserves(env.clone())?;
+        let total = Self::total_shares(env.clone());
+
+        let (used_a, used_b, shares) = if total == 0 {
+            let product = checked_mul(amount_a, amount_b)?;
+            (amount_a, amount_b, sqrt(product))
+        } else {
+            let optimal_b = mul_div(amount_a, reserve_b, reserve_a)?;
+            let (used_a, used_b) = if optimal_b <= amount_b {
+                (amount_a, optimal_b)
+            } else {
+                (mul_div(amount_b, reserve_a, reserve_b)?, amount_b)
+            };
+            (used_a, used_b, mul_div(used_a, total, reserve_a)?)
+        };
+        if shares <= 0 || used_a <= 0 || used_b <= 0 {
+            return Err(AmmError::InsufficientLiquidity);
+        }
+
+        let contract = env.current_contract_address();
+        Self::token_client(&env, &DataKey::TokenA)?.transfer(&provider, &contract, &used_a);
+        Self::token_client(&env, &DataKey::TokenB)?.transfer(&provider, &contract, &used_b);
+
+        Self::set_reserves(
+            &env,
+            checked_add(reserve_a, used_a)?,
+            checked_add(reserve_b, used_b)?,
+        );
+        Self::set_total_shares(&env, checked_add(total, shares)?);
+        let balance = Self::shares_of(env.clone(), provider.clone());
+        Self::set_shares(&env, &provider, checked_add(balance, shares)?);
+
+        env.events().publish(
+            (NAMESPACE, symbol_short!("deposit"), provider),
+            DepositEvent {
+                amount_a: used_a,
+                amount_b: used_b,
+                shares,
+            },
+        );
+        Ok(shares)
+    }
+
+    /// Sell `amount_in` of `sell_token` for the other pool token and return
+    /// the amount received.
+    ///
+    /// `out = in_after_fee * reserve_out / (reserve_in + in_after_fee)`
+    pub fn swap(
+        env: Env,
+        trader: Address,
+        sell_token: Address,
+        amount_in: i128,
+        min_out: i128,
+    ) -> Result<i128, AmmError> {
+        trader.require_auth();
+        if amount_in <= 0 {
+            return Err(AmmError::InvalidAmount);
+        }
+        let token_a = Self::token(&env, &DataKey::TokenA)?;
+        let token_b = Self::token(&env, &DataKey::TokenB)?;
+        let (reserve_a, reserve_b) = Self::get_reserves(env.clone())?;
+        let selling_a = if sell_token == token_a {
+            true
+        } else if sell_token == token_b {
+            false
+        } else {
+            return Err(AmmError::InvalidToken);
+        };
+        let (reserve_in, reserve_out, buy_token) = if selling_a {
+            (reserve_a, reserve_b, token_b)
+        } else {
+            (reserve_b, reserve_a, token_a)
+        };
+
+        let amount_out = Self::quote(amount_in, reserve_in, reserve_out)?;
+        if amount_out <= 0 {
+            return Err(AmmError::InsufficientLiquidity);
+        }
+        if amount_out < min_out {
+            return Err(AmmError::SlippageExceeded);
+        }
+
+        let contract = env.current_contract_address();
+        token::Client::new(&env, &sell_token).transfer(&trader, &contract, &amount_in);
+        token::Client::new(&env, &buy_token).transfer(&contract, &trader, &amount_out);
+
+        let new_in = checked_add(reserve_in, amount_in)?;
+        let new_out = checked_sub(reserve_out, amount_out)?;
+        if selling_a {
+            Self::set_reserves(&env, new_in, new_out);
+        } else {
+            Self::set_reserves(&env, new_out, new_in);
+        }
+
+        env.events().publish(
+            (NAMESPACE, symbol_short!("swap"), trader),
+            SwapEvent {
+                sell_token,
+                amount_in,
+                amount_out,
+            },
+        );
+        Ok(amount_out)
+    }
+
+    /// Burn `shares` and return the proportional `(amount_a, amount_b)`.
+    pub fn withdraw(env: Env, provider: Address, shares: i128) -> Result<(i128, i128), AmmError> {
+        provider.require_auth();
+        if shares <= 0 {
+            return Err(AmmError::InvalidAmount);
+        }
+        let balance = Self::shares_of(env.clone(), provider.clone());
+        if balance < shares {
+            return Err(AmmError::InsufficientShares);
+        }
+        let (reserve_a, reserve_b) = Self::get_reserves(env.clone())?;
+        let total = Self::total_shares(env.clone());
+
+        let amount_a = mul_div(shares, reserve_a, total)?;
+        let amount_b = mul_div(shares, reserve_b, total)?;
+
+        Self::set_shares(&env, &provider, checked_sub(balance, shares)?);
+        Self::set_total_shares(&env, checked_sub(total, shares)?);
+        Self::set_reserves(
+            &env,
+            checked_sub(reserve_a, amount_a)?,
+            checked_sub(reserve_b, amount_b)?,
+        );
+
+        let contract = env.current_contract_address();
+        Self::token_client(&env, &DataKey::TokenA)?.transfer(&contract, &provider, &amount_a);
+        Self::token_client(&env, &DataKey::TokenB)?.transfer(&contract, &provider, &amount_b);
+
+        env.events().publish(
+            (NAMESPACE, symbol_short!("withdraw"), provider),
+            WithdrawEvent {
+                shares,
+                amount_a,
+                amount_b,
+            },
+        );
+        Ok((amount_a, amount_b))
+    }
+
+    pub fn get_reserves(env: Env) -> Result<(i128, i128), AmmError> {
+        let storage = env.storage().instance();
+        let reserve_a = storage
+            .get(&DataKey::ReserveA)
+            .ok_or(AmmError::NotInitialized)?;
+        let reserve_b = storage
+            .get(&DataKey::ReserveB)
+            .ok_or(AmmError::NotInitialized)?;
+        Ok((reserve_a, reserve_b))
+    }
+
+    pub fn total_shares(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0)
+    }
+
+    pub fn shares_of(env: Env, provider: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Shares(provider))
+            .unwrap_or(0)
+    }
+}
+
+impl AmmContract {
+    fn quote(amount_in: i128, reserve_in: i128, reserve_out: i128) -> Result<i128, AmmError> {
+        let in_after_fee = checked_mul(amount_in, BPS_DENOMINATOR - FEE_BPS)?;
+        let denominator = checked_add(checked_mul(reserve_in, BPS_DENOMINATOR)?, in_after_fee)?;
+        mul_div(in_after_fee, reserve_out, denominator)
+    }
+
+    fn token(env: &Env, key: &DataKey) -> Result<Address, AmmError> {
+        env.storage()
+            .instance()
+            .get(key)
+            .ok_or(AmmError::NotInitialized)
+    }
+
+    fn token_client<'a>(env: &Env, key: &DataKey) -> Result<token::Client<'a>, AmmError> {
+        Ok(token::Client::new(env, &Self::token(env, key)?))
+    }
+
+    fn set_reserves(env: &Env, reserve_a: i128, reserve_b: i128) {
+        env.storage().instance().set(&DataKey::ReserveA, &reserve_a);
+        env.storage().instance().set(&DataKey::ReserveB, &reserve_b);
+    }
+
+    fn set_total_shares(env: &Env, total: i128) {
+        env.storage().instance().set(&DataKey::TotalShares, &total);
+    }
+
+    fn set_shares(env: &Env, provider: &Address, shares: i128) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Shares(provider.clone()), &shares);
+    }
+}
+
+fn checked_add(a: i128, b: i128) -> Result<i128, AmmError> {
+    a.checked_add(b).ok_or(AmmError::MathOverflow)
+}
+
+fn checked_sub(a: i128, b: i128) -> Result<i128, AmmError> {
+    a.checked_sub(b).ok_or(AmmError::MathOverflow)
+}
+
+fn checked_mul(a: i128, b: i128) -> Result<i128, AmmError> {
+    a.checked_mul(b).ok_or(AmmError::MathOverflow)
+}
+
+/// `a * b / c`, rounded down, with overflow and a zero divisor surfaced as
+/// typed errors.
+fn mul_div(a: i128, b: i128, c: i128) -> Result<i128, AmmError> {
+    if c == 0 {
+        return Err(AmmError::DivisionByZero);
+    }
+    Ok(checked_mul(a, b)? / c)
+}
+
+/// Integer square root (floor) by Newton's method.
+fn sqrt(value: i128) -> i128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+mod test;
@@ -0,0 +1,224 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal, TryFromVal,
+};
+
+struct Setup {
+    env: Env,
+    token_a: TokenClient<'static>,
+    token_b: TokenClient<'static>,
+    amm: AmmContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let asset_a = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let asset_b = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let amm = AmmContractClient::new(&env, &env.register_contract(None, AmmContract));
+    amm.initialize(&asset_a.address(), &asset_b.address());
+    Setup {
+        token_a: TokenClient::new(&env, &asset_a.address()),
+        token_b: TokenClient::new(&env, &asset_b.address()),
+        env,
+        amm,
+    }
+}
+
+/// A fresh account holding `amount` of both pool tokens.
+fn funded_user(s: &Setup, amount: i128) -> Address {
+    let user = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token_a.address).mint(&user, &amount);
+    StellarAssetClient::new(&s.env, &s.token_b.address).mint(&user, &amount);
+    user
+}
+
+fn k(s: &Setup) -> i128 {
+    let (a, b) = s.amm.get_reserves();
+    a * b
+}
+
+#[test]
+fn test_first_deposit_mints_geometric_mean() {
+    let s = setup();
+    let provider = funded_user(&s, 10_000);
+
+    assert_eq!(s.amm.deposit(&provider, &1_000, &4_000), 2_000);
+    assert_eq!(s.amm.get_reserves(), (1_000, 4_000));
+    assert_eq!(s.amm.total_shares(), 2_000);
+    assert_eq!(s.amm.shares_of(&provider), 2_000);
+    assert_eq!(s.token_a.balance(&s.amm.address), 1_000);
+    assert_eq!(s.token_b.balance(&s.amm.address), 4_000);
+}
+
+#[test]
+fn test_subsequent_deposit_is_proportional() {
+    let s = setup();
+    let first = funded_user(&s, 10_000);
+    let second = funded_user(&s, 10_000);
+    s.amm.deposit(&first, &1_000, &4_000);
+
+    // Offers too much B: only the matching 2_000 B is pulled.
+    assert_eq!(s.amm.deposit(&second, &500, &3_000), 1_000);
+    assert_eq!(s.token_a.balance(&second), 9_500);
+    assert_eq!(s.token_b.balance(&second), 8_000);
+    assert_eq!(s.amm.get_reserves(), (1_500, 6_000));
+    assert_eq!(s.amm.total_shares(), 3_000);
+
+    let event = s.env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (NAMESPACE, symbol_short!("deposit"), second.clone()).into_val(&s.env)
+    );
+    assert_eq!(
+        DepositEvent::try_from_val(&s.env, &event.2).unwrap(),
+        DepositEvent {
+            amount_a: 500,
+            amount_b: 2_000,
+            shares: 1_000,
+        }
+    );
+}
+
+#[test]
+fn test_swap_matches_hand_computed_output() {
+    let s = setup();
+    let provider = funded_user(&s, 10_000);
+    let trader = funded_user(&s, 1_000);
+    s.amm.deposit(&provider, &10_000, &10_000);
+
+    // in_after_fee = 1_000 * 9_970            = 9_970_000
+    // out = 9_970_000 * 10_000 / (10_000 * 10_000 + 9_970_000)
+    //     = 99_700_000_000 / 109_970_000      = 906 (floor of 906.61)
+    assert_eq!(s.amm.swap(&trader, &s.token_a.address, &1_000, &900), 906);
+    assert_eq!(s.token_a.balance(&trader), 0);
+    assert_eq!(s.token_b.balance(&trader), 1_906);
+    assert_eq!(s.amm.get_reserves(), (11_000, 9_094));
+
+    let event = s.env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (NAMESPACE, symbol_short!("swap"), trader.clone()).into_val(&s.env)
+    );
+    assert_eq!(
+        SwapEvent::try_from_val(&s.env, &event.2).unwrap(),
+        SwapEvent {
+            sell_token: s.token_a.address.clone(),
+            amount_in: 1_000,
+            amount_out: 906,
+        }
+    );
+}
+
+#[test]
+fn test_slippage_protection() {
+    let s = setup();
+    let provider = funded_user(&s, 10_000);
+    let trader = funded_user(&s, 1_000);
+    s.amm.deposit(&provider, &10_000, &10_000);
+
+    assert_eq!(
+        s.amm.try_swap(&trader, &s.token_a.address, &1_000, &907),
+        Err(Ok(AmmError::SlippageExceeded))
+    );
+    assert_eq!(s.amm.get_reserves(), (10_000, 10_000));
+    assert_eq!(s.token_a.balance(&trader), 1_000);
+}
+
+#[test]
+fn test_swap_rejects_foreign_token() {
+    let s = setup();
+    let provider = funded_user(&s, 10_000);
+    s.amm.deposit(&provider, &10_000, &10_000);
+    let foreign = Address::generate(&s.env);
+    assert_eq!(
+        s.amm.try_swap(&provider, &foreign, &100, &0),
+        Err(Ok(AmmError::InvalidToken))
+    );
+}
+
+#[test]
+fn test_proportional_withdrawal() {
+    let s = setup();
+    let first = funded_user(&s, 10_000);
+    let second = funded_user(&s, 10_000);
+    s.amm.deposit(&first, &1_000, &4_000);
+    s.amm.deposit(&second, &500, &2_000);
+
+    assert_eq!(s.amm.withdraw(&second, &1_000), (500, 2_000));
+    assert_eq!(s.token_a.balance(&second), 10_000);
+    assert_eq!(s.token_b.balance(&second), 10_000);
+    assert_eq!(s.amm.shares_of(&second), 0);
+    assert_eq!(s.amm.get_reserves(), (1_000, 4_000));
+
+    assert_eq!(
+        s.amm.try_withdraw(&second, &1),
+        Err(Ok(AmmError::InsufficientShares))
+    );
+}
+
+#[test]
+fn test_fees_accrue_to_liquidity_providers() {
+    let s = setup();
+    let provider = funded_user(&s, 10_000);
+    let trader = funded_user(&s, 10_000);
+    s.amm.deposit(&provider, &10_000, &10_000);
+
+    let out = s.amm.swap(&trader, &s.token_a.address, &2_000, &0);
+    s.amm.swap(&trader, &s.token_b.address, &out, &0);
+
+    // After a round trip the pool holds more A than it started with.
+    let (a, b) = s.amm.withdraw(&provider, &10_000);
+    assert!(a > 10_000);
+    assert_eq!(b, 10_000);
+}
+
+#[test]
+fn test_k_never_decreases() {
+    let s = setup();
+    let provider = funded_user(&s, 1_000_000);
+    let trader = funded_user(&s, 1_000_000);
+    s.amm.deposit(&provider, &100_000, &250_000);
+
+    let mut last_k = k(&s);
+    for i in 1..=40_i128 {
+        // Vary direction and size, including tiny trades where rounding
+        // dominates.
+        let amount = (i * 7_919) % 20_000 + 1;
+        let sell = if i % 3 == 0 {
+            &s.token_b.address
+        } else {
+            &s.token_a.address
+        };
+        match s.amm.try_swap(&trader, sell, &amount, &0) {
+            Ok(_) | Err(Ok(AmmError::InsufficientLiquidity)) => {}
+            other => panic!("unexpected swap result: {:?}", other),
+        }
+        let current = k(&s);
+        assert!(current >= last_k, "k decreased on iteration {i}");
+        last_k = current;
+    }
+}
+
+#[test]
+fn test_invalid_inputs() {
+    let s = setup();
+    let provider = funded_user(&s, 10_000);
+    assert_eq!(
+        s.amm.try_initialize(&s.token_a.address, &s.token_b.address),
+        Err(Ok(AmmError::AlreadyInitialized))
+    );
+    assert_eq!(
+        s.amm.try_deposit(&provider, &0, &100),
+        Err(Ok(AmmError::InvalidAmount))
+    );
+    // An empty pool has nothing to pay out.
+    assert_eq!(
+        s.amm.try_swap(&provider, &s.token_a.address, &100, &0),
+        Err(Ok(AmmError::InsufficientLiquidity))
+    );
+}
@@ -0,0 +1,174 @@
+//! # Commit-Reveal
+//!
+//! Players first publish a hash of their choice and later reveal the choice
+//! itself. Nobody can see (or copy) a choice before the reveal, and nobody
+//! can change it after committing.
+//!
+//! ```text
+//! commitment = sha256(value.to_be_bytes() || salt)
+//!              └ 8 bytes, big-endian ┘  └ 32 bytes ┘
+//! ```
+//!
+//! The random `salt` stops anyone brute-forcing a small `value` space from
+//! the public commitment.
+//!
+//! Play is organised in rounds. Each player may commit once per round and
+//! must reveal before the admin calls `close_round`; reveals after that are
+//! rejected as late.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Round,
+    /// The player's most recent commitment.
+    Commitment(Address),
+    /// A revealed value, keyed by round and player.
+    Revealed(u32, Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Commitment {
+    pub round: u32,
+    pub hash: BytesN<32>,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CommitRevealError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAdmin = 3,
+    /// The player already committed in the current round.
+    AlreadyCommitted = 4,
+    /// The player has no commitment to reveal.
+    NotCommitted = 5,
+    /// `sha256(value || salt)` does not match the stored commitment.
+    HashMismatch = 6,
+    AlreadyRevealed = 7,
+    /// The commitment belongs to a round that has been closed.
+    LateReveal = 8,
+}
+
+#[contract]
+pub struct CommitRevealContract;
+
+#[contractimpl]
+impl CommitRevealContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CommitRevealError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(CommitRevealError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Round, &1_u32);
+        Ok(())
+    }
+
+    /// Store `player`'s commitment for the current round.
+    pub fn commit(
+        env: Env,
+        player: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), CommitRevealError> {
+        player.require_auth();
+        let round = Self::round(env.clone())?;
+        let key = DataKey::Commitment(player.clone());
+        if let Some(existing) = env.storage().persistent().get::<_, Commitment>(&key) {
+            if existing.round == round {
+                return Err(CommitRevealError::AlreadyCommitted);
+            }
+        }
+        env.storage().persistent().set(
+            &key,
+            &Commitment {
+                round,
+                hash: commitment.clone(),
+            },
+        );
+        env.events()
+            .publish((symbol_short!("commit"), round, player), commitment);
+        Ok(())
+    }
+
+    /// Reveal `value` and `salt`; they must hash to the stored commitment.
+    pub fn reveal(
+        env: Env,
+        player: Address,
+        value: u64,
+        salt: BytesN<32>,
+    ) -> Result<(), CommitRevealError> {
+        player.require_auth();
+        let round = Self::round(env.clone())?;
+        let commitment: Commitment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Commitment(player.clone()))
+            .ok_or(CommitRevealError::NotCommitted)?;
+        if commitment.round != round {
+            return Err(CommitRevealError::LateReveal);
+        }
+        let revealed_key = DataKey::Revealed(round, player.clone());
+        if env.storage().persistent().has(&revealed_key) {
+            return Err(CommitRevealError::AlreadyRevealed);
+        }
+        if Self::compute_commitment(env.clone(), value, salt) != commitment.hash {
+            return Err(CommitRevealError::HashMismatch);
+        }
+
+        env.storage().persistent().set(&revealed_key, &value);
+        env.events()
+            .publish((symbol_short!("reveal"), round, player), value);
+        Ok(())
+    }
+
+    /// End the current round and return the new round number.
+    pub fn close_round(env: Env, admin: Address) -> Result<u32, CommitRevealError> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CommitRevealError::NotInitialized)?;
+        if stored != admin {
+            return Err(CommitRevealError::NotAdmin);
+        }
+        let closed = Self::round(env.clone())?;
+        let next = closed + 1;
+        env.storage().instance().set(&DataKey::Round, &next);
+        env.events().publish((symbol_short!("closed"),), closed);
+        Ok(next)
+    }
+
+    pub fn round(env: Env) -> Result<u32, CommitRevealError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Round)
+            .ok_or(CommitRevealError::NotInitialized)
+    }
+
+    /// The value `player` revealed in `round`, if any.
+    pub fn get_reveal(env: Env, round: u32, player: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Revealed(round, player))
+    }
+
+    /// `sha256(value.to_be_bytes() || salt)`, the commitment format this
+    /// contract expects. Exposed so clients can build commitments the same
+    /// way.
+    pub fn compute_commitment(env: Env, value: u64, salt: BytesN<32>) -> BytesN<32> {
+        let mut preimage = Bytes::from_array(&env, &value.to_be_bytes());
+        preimage.append(&salt.into());
+        env.crypto().sha256(&preimage).to_bytes()
+    }
+}
+
+mod test;
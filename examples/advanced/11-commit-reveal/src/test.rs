@@ -0,0 +1,139 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Bytes, BytesN, Env};
+
+fn setup() -> (Env, Address, CommitRevealContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let client =
+        CommitRevealContractClient::new(&env, &env.register_contract(None, CommitRevealContract));
+    client.initialize(&admin);
+    (env, admin, client)
+}
+
+fn salt(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+/// Build the commitment independently of the contract.
+fn commitment_for(env: &Env, value: u64, salt: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    preimage.extend_from_array(&value.to_be_bytes());
+    preimage.extend_from_array(&salt.to_array());
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+#[test]
+fn test_two_players_reveal_in_one_round() {
+    let (env, _, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let alice_salt = salt(&env, 1);
+    let bob_salt = salt(&env, 2);
+
+    client.commit(&alice, &commitment_for(&env, 42, &alice_salt));
+    client.commit(&bob, &commitment_for(&env, 7, &bob_salt));
+
+    client.reveal(&bob, &7, &bob_salt);
+    client.reveal(&alice, &42, &alice_salt);
+
+    assert_eq!(client.get_reveal(&1, &alice), Some(42));
+    assert_eq!(client.get_reveal(&1, &bob), Some(7));
+}
+
+#[test]
+fn test_client_and_contract_agree_on_encoding() {
+    let (env, _, client) = setup();
+    let s = salt(&env, 9);
+    assert_eq!(
+        client.compute_commitment(&123_456, &s),
+        commitment_for(&env, 123_456, &s)
+    );
+}
+
+#[test]
+fn test_wrong_value_or_salt_rejected() {
+    let (env, _, client) = setup();
+    let player = Address::generate(&env);
+    let s = salt(&env, 3);
+    client.commit(&player, &commitment_for(&env, 5, &s));
+
+    assert_eq!(
+        client.try_reveal(&player, &6, &s),
+        Err(Ok(CommitRevealError::HashMismatch))
+    );
+    assert_eq!(
+        client.try_reveal(&player, &5, &salt(&env, 4)),
+        Err(Ok(CommitRevealError::HashMismatch))
+    );
+    assert_eq!(client.get_reveal(&1, &player), None);
+}
+
+#[test]
+fn test_double_commit_rejected() {
+    let (env, _, client) = setup();
+    let player = Address::generate(&env);
+    let s = salt(&env, 3);
+    client.commit(&player, &commitment_for(&env, 5, &s));
+    assert_eq!(
+        client.try_commit(&player, &commitment_for(&env, 6, &s)),
+        Err(Ok(CommitRevealError::AlreadyCommitted))
+    );
+}
+
+#[test]
+fn test_reveal_without_commit_rejected() {
+    let (env, _, client) = setup();
+    let player = Address::generate(&env);
+    assert_eq!(
+        client.try_reveal(&player, &5, &salt(&env, 3)),
+        Err(Ok(CommitRevealError::NotCommitted))
+    );
+}
+
+#[test]
+fn test_double_reveal_rejected() {
+    let (env, _, client) = setup();
+    let player = Address::generate(&env);
+    let s = salt(&env, 3);
+    client.commit(&player, &commitment_for(&env, 5, &s));
+    client.reveal(&player, &5, &s);
+    assert_eq!(
+        client.try_reveal(&player, &5, &s),
+        Err(Ok(CommitRevealError::AlreadyRevealed))
+    );
+}
+
+#[test]
+fn test_late_reveal_rejected_and_new_round_accepts_commit() {
+    let (env, admin, client) = setup();
+    let player = Address::generate(&env);
+    let s = salt(&env, 3);
+    client.commit(&player, &commitment_for(&env, 5, &s));
+
+    assert_eq!(client.close_round(&admin), 2);
+    assert_eq!(
+        client.try_reveal(&player, &5, &s),
+        Err(Ok(CommitRevealError::LateReveal))
+    );
+
+    // A fresh commitment in round 2 is fine.
+    let s2 = salt(&env, 8);
+    client.commit(&player, &commitment_for(&env, 11, &s2));
+    client.reveal(&player, &11, &s2);
+    assert_eq!(client.get_reveal(&2, &player), Some(11));
+    assert_eq!(client.get_reveal(&1, &player), None);
+}
+
+#[test]
+fn test_only_admin_closes_round() {
+    let (env, _, client) = setup();
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_close_round(&stranger),
+        Err(Ok(CommitRevealError::NotAdmin))
+    );
+    assert_eq!(client.round(), 1);
+}
@@ -0,0 +1,145 @@
+//! # Merkle Airdrop
+//!
+//! Storing every eligible `(address, amount)` pair on-chain is expensive.
+//! Instead the admin publishes a single 32-byte Merkle root, and each
+//! claimant supplies a proof that their leaf is in the tree.
+//!
+//! ## Encoding
+//!
+//! ```text
+//! leaf   = sha256(claimant.to_xdr() || amount.to_be_bytes())   // amount: i128, 16 bytes
+//! parent = sha256(min(left, right) || max(left, right))
+//! ```
+//!
+//! Sibling pairs are hashed in sorted byte order. A proof therefore only
+//! needs the sibling hashes from leaf to root, with no left/right flags, and
+//! the tree builder must sort each pair the same way.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, xdr::ToXdr, Address,
+    Bytes, BytesN, Env, Vec,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Token,
+    Root,
+    Claimed(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AirdropError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAdmin = 3,
+    RootNotSet = 4,
+    /// The proof does not lead from the claimant's leaf to the stored root.
+    InvalidProof = 5,
+    AlreadyClaimed = 6,
+    InvalidAmount = 7,
+}
+
+#[contract]
+pub struct MerkleAirdropContract;
+
+#[contractimpl]
+impl MerkleAirdropContract {
+    /// Set the admin and the token to distribute. The contract must hold
+    /// enough `token` to cover every leaf.
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), AirdropError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(AirdropError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        Ok(())
+    }
+
+    pub fn set_root(env: Env, admin: Address, root: BytesN<32>) -> Result<(), AirdropError> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AirdropError::NotInitialized)?;
+        if stored != admin {
+            return Err(AirdropError::NotAdmin);
+        }
+        env.storage().instance().set(&DataKey::Root, &root);
+        env.events().publish((symbol_short!("root"),), root);
+        Ok(())
+    }
+
+    /// Verify `proof` for `(claimant, amount)` and pay out `amount`.
+    pub fn claim(
+        env: Env,
+        claimant: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), AirdropError> {
+        claimant.require_auth();
+        if amount <= 0 {
+            return Err(AirdropError::InvalidAmount);
+        }
+        let root: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Root)
+            .ok_or(AirdropError::RootNotSet)?;
+        let claimed_key = DataKey::Claimed(claimant.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(AirdropError::AlreadyClaimed);
+        }
+
+        let mut node = Self::leaf_hash(env.clone(), claimant.clone(), amount);
+        for sibling in proof.iter() {
+            node = hash_pair(&env, &node, &sibling);
+        }
+        if node != root {
+            return Err(AirdropError::InvalidProof);
+        }
+
+        env.storage().persistent().set(&claimed_key, &true);
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(AirdropError::NotInitialized)?;
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &claimant,
+            &amount,
+        );
+        env.events()
+            .publish((symbol_short!("claimed"), claimant), amount);
+        Ok(())
+    }
+
+    pub fn is_claimed(env: Env, claimant: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Claimed(claimant))
+    }
+
+    /// `sha256(claimant.to_xdr() || amount.to_be_bytes())`, the leaf encoding
+    /// the tree must be built with.
+    pub fn leaf_hash(env: Env, claimant: Address, amount: i128) -> BytesN<32> {
+        let mut preimage = claimant.to_xdr(&env);
+        preimage.extend_from_array(&amount.to_be_bytes());
+        env.crypto().sha256(&preimage).to_bytes()
+    }
+}
+
+/// Hash two sibling nodes in sorted order.
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut preimage = Bytes::from_array(env, &first.to_array());
+    preimage.extend_from_array(&second.to_array());
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+mod test;
@@ -0,0 +1,194 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Bytes, Env,
+};
+
+const AMOUNTS: [i128; 4] = [100, 250, 75, 575];
+
+struct Tree {
+    leaves: [BytesN<32>; 4],
+    left: BytesN<32>,
+    right: BytesN<32>,
+    root: BytesN<32>,
+}
+
+struct Setup {
+    env: Env,
+    recipients: [Address; 4],
+    tree: Tree,
+    token: TokenClient<'static>,
+    airdrop: MerkleAirdropContractClient<'static>,
+}
+
+fn leaf(env: &Env, claimant: &Address, amount: i128) -> BytesN<32> {
+    let mut preimage = claimant.to_xdr(env);
+    preimage.extend_from_array(&amount.to_be_bytes());
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+fn parent(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut preimage = Bytes::new(env);
+    preimage.extend_from_array(&first.to_array());
+    preimage.extend_from_array(&second.to_array());
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+/// ```text
+///            root
+///          /      \
+///       left      right
+///      /    \    /     \
+///    L0     L1  L2     L3
+/// ```
+fn build_tree(env: &Env, recipients: &[Address; 4]) -> Tree {
+    let leaves = [
+        leaf(env, &recipients[0], AMOUNTS[0]),
+        leaf(env, &recipients[1], AMOUNTS[1]),
+        leaf(env, &recipients[2], AMOUNTS[2]),
+        leaf(env, &recipients[3], AMOUNTS[3]),
+    ];
+    let left = parent(env, &leaves[0], &leaves[1]);
+    let right = parent(env, &leaves[2], &leaves[3]);
+    let root = parent(env, &left, &right);
+    Tree {
+        leaves,
+        left,
+        right,
+        root,
+    }
+}
+
+fn proof(s: &Setup, index: usize) -> Vec<BytesN<32>> {
+    let t = &s.tree;
+    match index {
+        0 => vec![&s.env, t.leaves[1].clone(), t.right.clone()],
+        1 => vec![&s.env, t.leaves[0].clone(), t.right.clone()],
+        2 => vec![&s.env, t.leaves[3].clone(), t.left.clone()],
+        3 => vec![&s.env, t.leaves[2].clone(), t.left.clone()],
+        _ => unreachable!(),
+    }
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let recipients = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let tree = build_tree(&env, &recipients);
+
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let airdrop =
+        MerkleAirdropContractClient::new(&env, &env.register_contract(None, MerkleAirdropContract));
+    StellarAssetClient::new(&env, &asset.address())
+        .mint(&airdrop.address, &AMOUNTS.iter().sum::<i128>());
+    airdrop.initialize(&admin, &asset.address());
+    airdrop.set_root(&admin, &tree.root);
+
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        recipients,
+        tree,
+        airdrop,
+    }
+}
+
+#[test]
+fn test_leaf_encoding_matches_contract() {
+    let s = setup();
+    assert_eq!(
+        s.airdrop.leaf_hash(&s.recipients[2], &AMOUNTS[2]),
+        s.tree.leaves[2]
+    );
+}
+
+#[test]
+fn test_every_leaf_can_claim() {
+    let s = setup();
+    for (i, amount) in AMOUNTS.iter().enumerate() {
+        s.airdrop.claim(&s.recipients[i], amount, &proof(&s, i));
+        assert_eq!(s.token.balance(&s.recipients[i]), *amount);
+    }
+    assert_eq!(s.token.balance(&s.airdrop.address), 0);
+}
+
+#[test]
+fn test_claimed_flags_are_per_leaf() {
+    let s = setup();
+    s.airdrop
+        .claim(&s.recipients[1], &AMOUNTS[1], &proof(&s, 1));
+
+    assert!(!s.airdrop.is_claimed(&s.recipients[0]));
+    assert!(s.airdrop.is_claimed(&s.recipients[1]));
+    assert!(!s.airdrop.is_claimed(&s.recipients[2]));
+    assert!(!s.airdrop.is_claimed(&s.recipients[3]));
+}
+
+#[test]
+fn test_double_claim_rejected() {
+    let s = setup();
+    s.airdrop
+        .claim(&s.recipients[0], &AMOUNTS[0], &proof(&s, 0));
+    assert_eq!(
+        s.airdrop
+            .try_claim(&s.recipients[0], &AMOUNTS[0], &proof(&s, 0)),
+        Err(Ok(AirdropError::AlreadyClaimed))
+    );
+    assert_eq!(s.token.balance(&s.recipients[0]), AMOUNTS[0]);
+}
+
+#[test]
+fn test_wrong_amount_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.airdrop.try_claim(&s.recipients[0], &1_000, &proof(&s, 0)),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+    assert!(!s.airdrop.is_claimed(&s.recipients[0]));
+}
+
+#[test]
+fn test_someone_elses_proof_rejected() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    assert_eq!(
+        s.airdrop.try_claim(&outsider, &AMOUNTS[0], &proof(&s, 0)),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+    // A valid leaf with the wrong path.
+    assert_eq!(
+        s.airdrop
+            .try_claim(&s.recipients[0], &AMOUNTS[0], &proof(&s, 2)),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+}
+
+#[test]
+fn test_truncated_proof_rejected() {
+    let s = setup();
+    let short = vec![&s.env, s.tree.leaves[1].clone()];
+    assert_eq!(
+        s.airdrop.try_claim(&s.recipients[0], &AMOUNTS[0], &short),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+}
+
+#[test]
+fn test_only_admin_sets_root() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.airdrop.try_set_root(&stranger, &s.tree.left),
+        Err(Ok(AirdropError::NotAdmin))
+    );
+}
@@ -0,0 +1,252 @@
+//! # Asynchronous Multisig Wallet
+//!
+//! A wallet that holds tokens and only moves them once `threshold` of its
+//! `owners` have approved. Unlike `multi_sig_action` in the authentication
+//! example, where every signer authorizes one transaction together, approvals
+//! here pile up in storage over many separate transactions:
+//!
+//! ```text
+//! submit ──> confirm ... confirm ──(confirmations >= threshold)──> execute
+//!               ^    revoke_confirmation     |
+//!               └────────────────────────────┘
+//! ```
+//!
+//! Changing the owner set is itself a proposal and needs the current
+//! threshold. At execution, only confirmations from *current* owners count,
+//! so a removed owner's old approvals stop counting.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+    Vec,
+};
+
+const NAMESPACE: Symbol = symbol_short!("msig");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Owners,
+    Threshold,
+    NextTxId,
+    Tx(u64),
+}
+
+/// What a proposal does once executed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// `(token, to, amount)`: pay out of the wallet's balance.
+    Transfer(Address, Address, i128),
+    /// `(owners, threshold)`: replace the owner set.
+    SetOwners(Vec<Address>, u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transaction {
+    pub action: Action,
+    pub confirmations: Vec<Address>,
+    pub executed: bool,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum WalletError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// Zero, or more than the number of owners.
+    InvalidThreshold = 3,
+    DuplicateOwner = 4,
+    NotOwner = 5,
+    TxNotFound = 6,
+    AlreadyExecuted = 7,
+    AlreadyConfirmed = 8,
+    NotConfirmed = 9,
+    NotEnoughConfirmations = 10,
+    InvalidAmount = 11,
+}
+
+#[contract]
+pub struct MultisigWallet;
+
+#[contractimpl]
+impl MultisigWallet {
+    pub fn init(env: Env, owners: Vec<Address>, threshold: u32) -> Result<(), WalletError> {
+        if env.storage().instance().has(&DataKey::Owners) {
+            return Err(WalletError::AlreadyInitialized);
+        }
+        validate_owners(&owners, threshold)?;
+        env.storage().instance().set(&DataKey::Owners, &owners);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+        env.storage().instance().set(&DataKey::NextTxId, &0_u64);
+        Ok(())
+    }
+
+    /// Propose paying `amount` of `token` to `to`. The proposer's own
+    /// confirmation is recorded immediately. Returns the transaction id.
+    pub fn submit(
+        env: Env,
+        proposer: Address,
+        to: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<u64, WalletError> {
+        if amount <= 0 {
+            return Err(WalletError::InvalidAmount);
+        }
+        Self::propose(&env, proposer, Action::Transfer(token, to, amount))
+    }
+
+    /// Propose replacing the owner set and threshold.
+    pub fn submit_owner_change(
+        env: Env,
+        proposer: Address,
+        owners: Vec<Address>,
+        threshold: u32,
+    ) -> Result<u64, WalletError> {
+        validate_owners(&owners, threshold)?;
+        Self::propose(&env, proposer, Action::SetOwners(owners, threshold))
+    }
+
+    pub fn confirm(env: Env, owner: Address, tx_id: u64) -> Result<(), WalletError> {
+        Self::require_owner(&env, &owner)?;
+        let mut tx = Self::pending(&env, tx_id)?;
+        if tx.confirmations.contains(&owner) {
+            return Err(WalletError::AlreadyConfirmed);
+        }
+        tx.confirmations.push_back(owner.clone());
+        env.storage().persistent().set(&DataKey::Tx(tx_id), &tx);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("confirm"), tx_id), owner);
+        Ok(())
+    }
+
+    pub fn revoke_confirmation(env: Env, owner: Address, tx_id: u64) -> Result<(), WalletError> {
+        Self::require_owner(&env, &owner)?;
+        let mut tx = Self::pending(&env, tx_id)?;
+        let index = tx
+            .confirmations
+            .first_index_of(&owner)
+            .ok_or(WalletError::NotConfirmed)?;
+        tx.confirmations.remove(index);
+        env.storage().persistent().set(&DataKey::Tx(tx_id), &tx);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("revoke"), tx_id), owner);
+        Ok(())
+    }
+
+    /// Run `tx_id` once enough current owners have confirmed it. Anyone may
+    /// call this; the confirmations are the authorization.
+    pub fn execute(env: Env, tx_id: u64) -> Result<(), WalletError> {
+        let mut tx = Self::pending(&env, tx_id)?;
+        let owners = Self::get_owners(env.clone())?;
+        let confirmed = tx
+            .confirmations
+            .iter()
+            .filter(|c| owners.contains(c))
+            .count() as u32;
+        if confirmed < Self::get_threshold(env.clone())? {
+            return Err(WalletError::NotEnoughConfirmations);
+        }
+
+        // Mark executed before any external call so the transaction cannot
+        // be replayed.
+        tx.executed = true;
+        env.storage().persistent().set(&DataKey::Tx(tx_id), &tx);
+
+        match tx.action {
+            Action::Transfer(token, to, amount) => {
+                token::Client::new(&env, &token).transfer(
+                    &env.current_contract_address(),
+                    &to,
+                    &amount,
+                );
+            }
+            Action::SetOwners(new_owners, threshold) => {
+                env.storage().instance().set(&DataKey::Owners, &new_owners);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::Threshold, &threshold);
+            }
+        }
+        env.events()
+            .publish((NAMESPACE, symbol_short!("execute"), tx_id), ());
+        Ok(())
+    }
+
+    pub fn get_owners(env: Env) -> Result<Vec<Address>, WalletError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Owners)
+            .ok_or(WalletError::NotInitialized)
+    }
+
+    pub fn get_threshold(env: Env) -> Result<u32, WalletError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .ok_or(WalletError::NotInitialized)
+    }
+
+    pub fn get_transaction(env: Env, tx_id: u64) -> Option<Transaction> {
+        env.storage().persistent().get(&DataKey::Tx(tx_id))
+    }
+}
+
+impl MultisigWallet {
+    fn propose(env: &Env, proposer: Address, action: Action) -> Result<u64, WalletError> {
+        Self::require_owner(env, &proposer)?;
+        let tx_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTxId)
+            .ok_or(WalletError::NotInitialized)?;
+        let tx = Transaction {
+            action,
+            confirmations: Vec::from_array(env, [proposer.clone()]),
+            executed: false,
+        };
+        env.storage().persistent().set(&DataKey::Tx(tx_id), &tx);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTxId, &(tx_id + 1));
+        env.events()
+            .publish((NAMESPACE, symbol_short!("submit"), tx_id), proposer);
+        Ok(tx_id)
+    }
+
+    fn require_owner(env: &Env, owner: &Address) -> Result<(), WalletError> {
+        owner.require_auth();
+        if !Self::get_owners(env.clone())?.contains(owner) {
+            return Err(WalletError::NotOwner);
+        }
+        Ok(())
+    }
+
+    fn pending(env: &Env, tx_id: u64) -> Result<Transaction, WalletError> {
+        let tx = Self::get_transaction(env.clone(), tx_id).ok_or(WalletError::TxNotFound)?;
+        if tx.executed {
+            return Err(WalletError::AlreadyExecuted);
+        }
+        Ok(tx)
+    }
+}
+
+fn validate_owners(owners: &Vec<Address>, threshold: u32) -> Result<(), WalletError> {
+    if threshold == 0 || threshold > owners.len() {
+        return Err(WalletError::InvalidThreshold);
+    }
+    for (i, owner) in owners.iter().enumerate() {
+        if owners.first_index_of(&owner) != Some(i as u32) {
+            return Err(WalletError::DuplicateOwner);
+        }
+    }
+    Ok(())
+}
+
+mod test;
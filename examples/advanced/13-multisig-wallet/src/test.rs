@@ -0,0 +1,191 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Env,
+};
+
+struct Setup {
+    env: Env,
+    owners: [Address; 3],
+    token: TokenClient<'static>,
+    wallet: MultisigWalletClient<'static>,
+}
+
+/// A 2-of-3 wallet holding 1_000 tokens.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let owners = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let wallet = MultisigWalletClient::new(&env, &env.register_contract(None, MultisigWallet));
+    wallet.init(
+        &vec![
+            &env,
+            owners[0].clone(),
+            owners[1].clone(),
+            owners[2].clone(),
+        ],
+        &2,
+    );
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &asset.address()).mint(&wallet.address, &1_000);
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        owners,
+        wallet,
+    }
+}
+
+#[test]
+fn test_two_of_three_flow() {
+    let s = setup();
+    let recipient = Address::generate(&s.env);
+
+    let tx_id = s
+        .wallet
+        .submit(&s.owners[0], &recipient, &s.token.address, &400);
+    assert_eq!(
+        s.wallet.try_execute(&tx_id),
+        Err(Ok(WalletError::NotEnoughConfirmations))
+    );
+
+    s.wallet.confirm(&s.owners[2], &tx_id);
+    s.wallet.execute(&tx_id);
+
+    assert_eq!(s.token.balance(&recipient), 400);
+    assert_eq!(s.token.balance(&s.wallet.address), 600);
+    assert!(s.wallet.get_transaction(&tx_id).unwrap().executed);
+}
+
+#[test]
+fn test_revocation_drops_below_threshold() {
+    let s = setup();
+    let recipient = Address::generate(&s.env);
+    let tx_id = s
+        .wallet
+        .submit(&s.owners[0], &recipient, &s.token.address, &400);
+    s.wallet.confirm(&s.owners[1], &tx_id);
+    s.wallet.revoke_confirmation(&s.owners[1], &tx_id);
+
+    assert_eq!(
+        s.wallet.try_execute(&tx_id),
+        Err(Ok(WalletError::NotEnoughConfirmations))
+    );
+    assert_eq!(
+        s.wallet.try_revoke_confirmation(&s.owners[1], &tx_id),
+        Err(Ok(WalletError::NotConfirmed))
+    );
+    assert_eq!(s.token.balance(&recipient), 0);
+}
+
+#[test]
+fn test_non_owner_rejected() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    let tx_id = s
+        .wallet
+        .submit(&s.owners[0], &outsider, &s.token.address, &400);
+
+    assert_eq!(
+        s.wallet.try_confirm(&outsider, &tx_id),
+        Err(Ok(WalletError::NotOwner))
+    );
+    assert_eq!(
+        s.wallet
+            .try_submit(&outsider, &outsider, &s.token.address, &1),
+        Err(Ok(WalletError::NotOwner))
+    );
+}
+
+#[test]
+fn test_double_confirmation_rejected() {
+    let s = setup();
+    let tx_id = s
+        .wallet
+        .submit(&s.owners[0], &s.owners[0], &s.token.address, &1);
+    assert_eq!(
+        s.wallet.try_confirm(&s.owners[0], &tx_id),
+        Err(Ok(WalletError::AlreadyConfirmed))
+    );
+}
+
+#[test]
+fn test_executed_tx_cannot_be_replayed() {
+    let s = setup();
+    let recipient = Address::generate(&s.env);
+    let tx_id = s
+        .wallet
+        .submit(&s.owners[0], &recipient, &s.token.address, &400);
+    s.wallet.confirm(&s.owners[1], &tx_id);
+    s.wallet.execute(&tx_id);
+
+    assert_eq!(
+        s.wallet.try_execute(&tx_id),
+        Err(Ok(WalletError::AlreadyExecuted))
+    );
+    assert_eq!(
+        s.wallet.try_confirm(&s.owners[2], &tx_id),
+        Err(Ok(WalletError::AlreadyExecuted))
+    );
+    assert_eq!(s.token.balance(&recipient), 400);
+}
+
+#[test]
+fn test_owner_set_rotation() {
+    let s = setup();
+    let newcomer = Address::generate(&s.env);
+    let new_owners = vec![&s.env, s.owners[1].clone(), newcomer.clone()];
+
+    // A pending payment confirmed by owner 0, who is about to be removed.
+    let recipient = Address::generate(&s.env);
+    let payment = s
+        .wallet
+        .submit(&s.owners[0], &recipient, &s.token.address, &100);
+
+    let rotation = s.wallet.submit_owner_change(&s.owners[0], &new_owners, &2);
+    s.wallet.confirm(&s.owners[1], &rotation);
+    s.wallet.execute(&rotation);
+
+    assert_eq!(s.wallet.get_owners(), new_owners);
+    assert_eq!(s.wallet.get_threshold(), 2);
+    assert_eq!(
+        s.wallet.try_confirm(&s.owners[0], &payment),
+        Err(Ok(WalletError::NotOwner))
+    );
+
+    // Owner 0's earlier confirmation no longer counts.
+    s.wallet.confirm(&newcomer, &payment);
+    assert_eq!(
+        s.wallet.try_execute(&payment),
+        Err(Ok(WalletError::NotEnoughConfirmations))
+    );
+    s.wallet.confirm(&s.owners[1], &payment);
+    s.wallet.execute(&payment);
+    assert_eq!(s.token.balance(&recipient), 100);
+}
+
+#[test]
+fn test_invalid_owner_sets_rejected() {
+    let s = setup();
+    let dup = vec![&s.env, s.owners[0].clone(), s.owners[0].clone()];
+    assert_eq!(
+        s.wallet.try_submit_owner_change(&s.owners[0], &dup, &1),
+        Err(Ok(WalletError::DuplicateOwner))
+    );
+    let one = vec![&s.env, s.owners[0].clone()];
+    assert_eq!(
+        s.wallet.try_submit_owner_change(&s.owners[0], &one, &2),
+        Err(Ok(WalletError::InvalidThreshold))
+    );
+    assert_eq!(
+        s.wallet.try_submit_owner_change(&s.owners[0], &one, &0),
+        Err(Ok(WalletError::InvalidThreshold))
+    );
+}
@@ -0,0 +1,174 @@
+//! # Oracle Consumer
+//!
+//! Authorized reporters push prices for trading pairs. Consumers read them
+//! through `safe_price`, which refuses to return a value that is too old or
+//! that jumped too far from the previous report. Those are the two most
+//! common ways a bad oracle value reaches a DeFi contract.
+//!
+//! ```text
+//! age       = now - latest.timestamp                          > max_age            -> Stale
+//! deviation = |latest - previous| * 10_000 / previous          > max_deviation_bps  -> Deviation
+//! ```
+//!
+//! The first price for a pair has nothing to compare against, so only the
+//! staleness check applies to it (bootstrap case).
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol,
+};
+
+const NAMESPACE: Symbol = symbol_short!("oracle");
+const BPS: i128 = 10_000;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Reporter(Address),
+    Latest(Symbol),
+    Previous(Symbol),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAdmin = 3,
+    UnauthorizedReporter = 4,
+    InvalidPrice = 5,
+    /// The report is not newer than the stored one.
+    OutdatedReport = 6,
+    /// The report is timestamped after the current ledger time.
+    FutureTimestamp = 7,
+    NoPrice = 8,
+    /// The latest price is older than `max_age`.
+    Stale = 9,
+    /// The latest price moved more than `max_deviation_bps` from the
+    /// previous one.
+    Deviation = 10,
+    MathOverflow = 11,
+}
+
+#[contract]
+pub struct OracleConsumer;
+
+#[contractimpl]
+impl OracleConsumer {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn set_reporter(
+        env: Env,
+        admin: Address,
+        reporter: Address,
+        authorized: bool,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored != admin {
+            return Err(Error::NotAdmin);
+        }
+        let key = DataKey::Reporter(reporter);
+        if authorized {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Record a new price. The previous latest value is kept for the
+    /// deviation check.
+    pub fn push_price(
+        env: Env,
+        reporter: Address,
+        pair: Symbol,
+        price: i128,
+        timestamp: u64,
+    ) -> Result<(), Error> {
+        reporter.require_auth();
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Reporter(reporter.clone()))
+        {
+            return Err(Error::UnauthorizedReporter);
+        }
+        if price <= 0 {
+            return Err(Error::InvalidPrice);
+        }
+        if timestamp > env.ledger().timestamp() {
+            return Err(Error::FutureTimestamp);
+        }
+
+        let latest_key = DataKey::Latest(pair.clone());
+        if let Some(latest) = env.storage().persistent().get::<_, PriceData>(&latest_key) {
+            if timestamp <= latest.timestamp {
+                return Err(Error::OutdatedReport);
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::Previous(pair.clone()), &latest);
+        }
+        let data = PriceData { price, timestamp };
+        env.storage().persistent().set(&latest_key, &data);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("price"), pair), data);
+        Ok(())
+    }
+
+    /// The latest price for `pair`, if it is at most `max_age` seconds old
+    /// and within `max_deviation_bps` of the previous report.
+    pub fn safe_price(
+        env: Env,
+        pair: Symbol,
+        max_age: u64,
+        max_deviation_bps: u32,
+    ) -> Result<i128, Error> {
+        let latest = Self::latest(env.clone(), pair.clone()).ok_or(Error::NoPrice)?;
+        let age = env.ledger().timestamp().saturating_sub(latest.timestamp);
+        if age > max_age {
+            return Err(Error::Stale);
+        }
+
+        let previous: Option<PriceData> = env.storage().persistent().get(&DataKey::Previous(pair));
+        if let Some(previous) = previous {
+            let deviation_bps = (latest.price - previous.price)
+                .abs()
+                .checked_mul(BPS)
+                .ok_or(Error::MathOverflow)?
+                / previous.price;
+            if deviation_bps > i128::from(max_deviation_bps) {
+                return Err(Error::Deviation);
+            }
+        }
+        Ok(latest.price)
+    }
+
+    /// The raw latest report, without any safety checks.
+    pub fn latest(env: Env, pair: Symbol) -> Option<PriceData> {
+        env.storage().persistent().get(&DataKey::Latest(pair))
+    }
+}
+
+mod test;
@@ -0,0 +1,127 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Env, IntoVal, TryFromVal,
+};
+
+const NOW: u64 = 10_000;
+
+fn setup() -> (Env, Address, OracleConsumerClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = NOW);
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let client = OracleConsumerClient::new(&env, &env.register_contract(None, OracleConsumer));
+    client.initialize(&admin);
+    client.set_reporter(&admin, &reporter, &true);
+    (env, reporter, client)
+}
+
+fn xlm_usd() -> Symbol {
+    symbol_short!("XLM_USD")
+}
+
+#[test]
+fn test_bootstrap_first_price_accepted() {
+    let (env, reporter, client) = setup();
+    assert_eq!(
+        client.try_safe_price(&xlm_usd(), &60, &1_000),
+        Err(Ok(Error::NoPrice))
+    );
+
+    client.push_price(&reporter, &xlm_usd(), &1_000_000, &NOW);
+    // Nothing to compare against yet, so even a 0 bps bound passes.
+    assert_eq!(client.safe_price(&xlm_usd(), &60, &0), 1_000_000);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (NAMESPACE, symbol_short!("price"), xlm_usd()).into_val(&env)
+    );
+    assert_eq!(
+        PriceData::try_from_val(&env, &event.2).unwrap(),
+        PriceData {
+            price: 1_000_000,
+            timestamp: NOW,
+        }
+    );
+}
+
+#[test]
+fn test_fresh_price_within_bound_accepted() {
+    let (_, reporter, client) = setup();
+    client.push_price(&reporter, &xlm_usd(), &1_000_000, &(NOW - 30));
+    client.push_price(&reporter, &xlm_usd(), &1_050_000, &NOW);
+    // 5% move = 500 bps.
+    assert_eq!(client.safe_price(&xlm_usd(), &60, &500), 1_050_000);
+}
+
+#[test]
+fn test_stale_price_rejected_after_time_travel() {
+    let (env, reporter, client) = setup();
+    client.push_price(&reporter, &xlm_usd(), &1_000_000, &NOW);
+    env.ledger().with_mut(|li| li.timestamp = NOW + 60);
+    assert_eq!(client.safe_price(&xlm_usd(), &60, &1_000), 1_000_000);
+
+    env.ledger().with_mut(|li| li.timestamp = NOW + 61);
+    assert_eq!(
+        client.try_safe_price(&xlm_usd(), &60, &1_000),
+        Err(Ok(Error::Stale))
+    );
+}
+
+#[test]
+fn test_large_jump_rejected_by_deviation_bound() {
+    let (_, reporter, client) = setup();
+    client.push_price(&reporter, &xlm_usd(), &1_000_000, &(NOW - 10));
+    client.push_price(&reporter, &xlm_usd(), &1_500_000, &NOW);
+
+    assert_eq!(
+        client.try_safe_price(&xlm_usd(), &60, &1_000),
+        Err(Ok(Error::Deviation))
+    );
+    // A 50% jump passes a 5_000 bps bound exactly.
+    assert_eq!(client.safe_price(&xlm_usd(), &60, &5_000), 1_500_000);
+}
+
+#[test]
+fn test_unauthorized_reporter_rejected() {
+    let (env, _, client) = setup();
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_push_price(&stranger, &xlm_usd(), &1_000_000, &NOW),
+        Err(Ok(Error::UnauthorizedReporter))
+    );
+    assert_eq!(client.latest(&xlm_usd()), None);
+}
+
+#[test]
+fn test_only_admin_manages_reporters() {
+    let (env, reporter, client) = setup();
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_reporter(&stranger, &reporter, &false),
+        Err(Ok(Error::NotAdmin))
+    );
+}
+
+#[test]
+fn test_out_of_order_and_future_reports_rejected() {
+    let (_, reporter, client) = setup();
+    client.push_price(&reporter, &xlm_usd(), &1_000_000, &NOW);
+    assert_eq!(
+        client.try_push_price(&reporter, &xlm_usd(), &1_000_000, &NOW),
+        Err(Ok(Error::OutdatedReport))
+    );
+    assert_eq!(
+        client.try_push_price(&reporter, &xlm_usd(), &1_000_000, &(NOW + 1)),
+        Err(Ok(Error::FutureTimestamp))
+    );
+    assert_eq!(
+        client.try_push_price(&reporter, &symbol_short!("BTC_USD"), &0, &NOW),
+        Err(Ok(Error::InvalidPrice))
+    );
+}
@@ -0,0 +1,187 @@
+//! # English Auction
+//!
+//! An ascending-price auction paid in any SEP-41 token.
+//!
+//! - Every bid is escrowed in the contract, and the previous highest bidder
+//!   is refunded in the same call. Losing bidders never have to claim
+//!   anything.
+//! - A bid landing in the last `EXTENSION_WINDOW` seconds pushes the deadline
+//!   out to `now + EXTENSION_WINDOW` (anti-sniping), so there is always
+//!   time to respond to a late bid.
+//! - After the deadline anyone may `finalize`: the seller receives the
+//!   winning bid, or, if no bid met the reserve, nothing changes hands.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+/// Bids within this many seconds of the deadline extend it.
+pub const EXTENSION_WINDOW: u64 = 300;
+
+const NAMESPACE: Symbol = symbol_short!("auction");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Auction,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub seller: Address,
+    pub token: Address,
+    pub reserve: i128,
+    pub end_time: u64,
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: i128,
+    pub settled: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BidEvent {
+    pub amount: i128,
+    pub end_time: u64,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AuctionError {
+    AlreadyStarted = 1,
+    NotStarted = 2,
+    InvalidReserve = 3,
+    InvalidDuration = 4,
+    /// The deadline has passed; no more bids.
+    AuctionEnded = 5,
+    /// The bid does not beat the current highest bid.
+    BidTooLow = 6,
+    BelowReserve = 7,
+    /// `finalize` was called before the deadline.
+    AuctionNotEnded = 8,
+    AlreadySettled = 9,
+}
+
+#[contract]
+pub struct EnglishAuction;
+
+#[contractimpl]
+impl EnglishAuction {
+    /// Open the auction for `duration` seconds from now.
+    pub fn start(
+        env: Env,
+        seller: Address,
+        token: Address,
+        reserve: i128,
+        duration: u64,
+    ) -> Result<(), AuctionError> {
+        seller.require_auth();
+        if env.storage().instance().has(&DataKey::Auction) {
+            return Err(AuctionError::AlreadyStarted);
+        }
+        if reserve <= 0 {
+            return Err(AuctionError::InvalidReserve);
+        }
+        if duration == 0 {
+            return Err(AuctionError::InvalidDuration);
+        }
+        let auction = Auction {
+            seller: seller.clone(),
+            token,
+            reserve,
+            end_time: env.ledger().timestamp() + duration,
+            highest_bidder: None,
+            highest_bid: 0,
+            settled: false,
+        };
+        env.storage().instance().set(&DataKey::Auction, &auction);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("started"), seller), reserve);
+        Ok(())
+    }
+
+    /// Escrow `amount` from `bidder` and refund the previous highest bidder.
+    pub fn bid(env: Env, bidder: Address, amount: i128) -> Result<(), AuctionError> {
+        bidder.require_auth();
+        let mut auction = Self::load(&env)?;
+        let now = env.ledger().timestamp();
+        if auction.settled || now >= auction.end_time {
+            return Err(AuctionError::AuctionEnded);
+        }
+        if amount < auction.reserve {
+            return Err(AuctionError::BelowReserve);
+        }
+        if amount <= auction.highest_bid {
+            return Err(AuctionError::BidTooLow);
+        }
+
+        let token = token::Client::new(&env, &auction.token);
+        let contract = env.current_contract_address();
+        token.transfer(&bidder, &contract, &amount);
+        if let Some(previous) = auction.highest_bidder.take() {
+            token.transfer(&contract, &previous, &auction.highest_bid);
+        }
+
+        if auction.end_time - now < EXTENSION_WINDOW {
+            auction.end_time = now + EXTENSION_WINDOW;
+        }
+        auction.highest_bidder = Some(bidder.clone());
+        auction.highest_bid = amount;
+        env.storage().instance().set(&DataKey::Auction, &auction);
+        env.events().publish(
+            (NAMESPACE, symbol_short!("bid"), bidder),
+            BidEvent {
+                amount,
+                end_time: auction.end_time,
+            },
+        );
+        Ok(())
+    }
+
+    /// Settle after the deadline. Returns the winner, if any.
+    pub fn finalize(env: Env) -> Result<Option<Address>, AuctionError> {
+        let mut auction = Self::load(&env)?;
+        if auction.settled {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if env.ledger().timestamp() < auction.end_time {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+
+        auction.settled = true;
+        env.storage().instance().set(&DataKey::Auction, &auction);
+        // Bids below the reserve are rejected up front, so "no bids" and
+        // "reserve not met" are the same case: nothing is escrowed and the
+        // seller keeps the item.
+        if auction.highest_bidder.is_some() {
+            token::Client::new(&env, &auction.token).transfer(
+                &env.current_contract_address(),
+                &auction.seller,
+                &auction.highest_bid,
+            );
+        }
+        env.events().publish(
+            (NAMESPACE, symbol_short!("settled")),
+            (auction.highest_bidder.clone(), auction.highest_bid),
+        );
+        Ok(auction.highest_bidder)
+    }
+
+    pub fn get_auction(env: Env) -> Option<Auction> {
+        env.storage().instance().get(&DataKey::Auction)
+    }
+}
+
+impl EnglishAuction {
+    fn load(env: &Env) -> Result<Auction, AuctionError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Auction)
+            .ok_or(AuctionError::NotStarted)
+    }
+}
+
+mod test;
@@ -0,0 +1,176 @@
+#![cfg(test)]
+
+use super::*;
+use recipes_testutils::set_timestamp;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal, TryFromVal,
+};
+
+const START: u64 = 1_000;
+const DURATION: u64 = 3_600;
+const RESERVE: i128 = 100;
+
+struct Setup {
+    env: Env,
+    seller: Address,
+    token: TokenClient<'static>,
+    minter: StellarAssetClient<'static>,
+    auction: EnglishAuctionClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, START);
+    let seller = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let auction = EnglishAuctionClient::new(&env, &env.register_contract(None, EnglishAuction));
+    auction.start(&seller, &asset.address(), &RESERVE, &DURATION);
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        minter: StellarAssetClient::new(&env, &asset.address()),
+        env,
+        seller,
+        auction,
+    }
+}
+
+fn bidder(s: &Setup, balance: i128) -> Address {
+    let bidder = Address::generate(&s.env);
+    s.minter.mint(&bidder, &balance);
+    bidder
+}
+
+#[test]
+fn test_outbidding_refunds_previous_bidder() {
+    let s = setup();
+    let alice = bidder(&s, 1_000);
+    let bob = bidder(&s, 1_000);
+
+    s.auction.bid(&alice, &150);
+    assert_eq!(s.token.balance(&alice), 850);
+    assert_eq!(s.token.balance(&s.auction.address), 150);
+
+    s.auction.bid(&bob, &200);
+    assert_eq!(s.token.balance(&alice), 1_000);
+    assert_eq!(s.token.balance(&bob), 800);
+    assert_eq!(s.token.balance(&s.auction.address), 200);
+
+    let event = s.env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (NAMESPACE, symbol_short!("bid"), bob.clone()).into_val(&s.env)
+    );
+    assert_eq!(
+        BidEvent::try_from_val(&s.env, &event.2).unwrap(),
+        BidEvent {
+            amount: 200,
+            end_time: START + DURATION,
+        }
+    );
+}
+
+#[test]
+fn test_low_bids_rejected() {
+    let s = setup();
+    let alice = bidder(&s, 1_000);
+    let bob = bidder(&s, 1_000);
+
+    assert_eq!(
+        s.auction.try_bid(&alice, &(RESERVE - 1)),
+        Err(Ok(AuctionError::BelowReserve))
+    );
+    s.auction.bid(&alice, &150);
+    assert_eq!(
+        s.auction.try_bid(&bob, &150),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    assert_eq!(s.token.balance(&bob), 1_000);
+}
+
+#[test]
+fn test_finalize_before_deadline_rejected() {
+    let s = setup();
+    let alice = bidder(&s, 1_000);
+    s.auction.bid(&alice, &150);
+    set_timestamp(&s.env, START + DURATION - 1);
+    assert_eq!(
+        s.auction.try_finalize(),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+}
+
+#[test]
+fn test_finalize_pays_seller_once() {
+    let s = setup();
+    let alice = bidder(&s, 1_000);
+    s.auction.bid(&alice, &150);
+
+    set_timestamp(&s.env, START + DURATION);
+    assert_eq!(
+        s.auction.try_bid(&alice, &300),
+        Err(Ok(AuctionError::AuctionEnded))
+    );
+    assert_eq!(s.auction.finalize(), Some(alice));
+    assert_eq!(s.token.balance(&s.seller), 150);
+    assert_eq!(s.token.balance(&s.auction.address), 0);
+    assert_eq!(
+        s.auction.try_finalize(),
+        Err(Ok(AuctionError::AlreadySettled))
+    );
+}
+
+#[test]
+fn test_late_bid_extends_deadline() {
+    let s = setup();
+    let alice = bidder(&s, 1_000);
+    let bob = bidder(&s, 1_000);
+
+    // Outside the window: no extension.
+    set_timestamp(&s.env, START + DURATION - EXTENSION_WINDOW);
+    s.auction.bid(&alice, &150);
+    assert_eq!(s.auction.get_auction().unwrap().end_time, START + DURATION);
+
+    // Ten seconds before the end: pushed out to now + window.
+    let late = START + DURATION - 10;
+    set_timestamp(&s.env, late);
+    s.auction.bid(&bob, &200);
+    assert_eq!(
+        s.auction.get_auction().unwrap().end_time,
+        late + EXTENSION_WINDOW
+    );
+
+    // The original deadline no longer ends the auction.
+    set_timestamp(&s.env, START + DURATION);
+    assert_eq!(
+        s.auction.try_finalize(),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    s.auction.bid(&alice, &250);
+
+    set_timestamp(&s.env, START + DURATION + EXTENSION_WINDOW);
+    assert_eq!(s.auction.finalize(), Some(alice));
+    assert_eq!(s.token.balance(&bob), 1_000);
+    assert_eq!(s.token.balance(&s.seller), 250);
+}
+
+#[test]
+fn test_no_bids_settles_without_payment() {
+    let s = setup();
+    set_timestamp(&s.env, START + DURATION);
+    assert_eq!(s.auction.finalize(), None);
+    assert_eq!(s.token.balance(&s.seller), 0);
+    assert!(s.auction.get_auction().unwrap().settled);
+}
+
+#[test]
+fn test_cannot_start_twice() {
+    let s = setup();
+    assert_eq!(
+        s.auction
+            .try_start(&s.seller, &s.token.address, &RESERVE, &DURATION),
+        Err(Ok(AuctionError::AlreadyStarted))
+    );
+}
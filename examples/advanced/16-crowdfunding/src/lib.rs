@@ -0,0 +1,229 @@
+//! # Crowdfunding
+//!
+//! An all-or-nothing campaign: backers pledge tokens until the deadline. If
+//! the total reaches the goal, the creator withdraws everything; otherwise
+//! every backer takes back exactly what they pledged.
+//!
+//! ```text
+//!            pledge ...
+//! Running ───────────────(deadline)──┬── total >= goal ──> Successful ──withdraw──> Withdrawn
+//!                                    └── total <  goal ──> Failed     ──refund (per backer)
+//! ```
+//!
+//! The state is derived from the ledger time and the stored totals, so no
+//! call is needed to "close" the campaign.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Campaign,
+    Total,
+    Withdrawn,
+    Pledge(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Campaign {
+    pub creator: Address,
+    pub token: Address,
+    pub goal: i128,
+    pub deadline: u64,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CampaignState {
+    Unknown = 0,
+    Running = 1,
+    Successful = 2,
+    Failed = 3,
+    Withdrawn = 4,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CrowdfundError {
+    AlreadyCreated = 1,
+    NotCreated = 2,
+    InvalidAmount = 3,
+    InvalidDeadline = 4,
+    /// The campaign has passed its deadline.
+    CampaignEnded = 5,
+    /// The campaign is still accepting pledges.
+    CampaignRunning = 6,
+    GoalNotReached = 7,
+    GoalReached = 8,
+    AlreadyWithdrawn = 9,
+    NotCreator = 10,
+    NothingToRefund = 11,
+}
+
+#[contract]
+pub struct CrowdfundContract;
+
+#[contractimpl]
+impl CrowdfundContract {
+    pub fn create(
+        env: Env,
+        creator: Address,
+        token: Address,
+        goal: i128,
+        deadline: u64,
+    ) -> Result<(), CrowdfundError> {
+        creator.require_auth();
+        if env.storage().instance().has(&DataKey::Campaign) {
+            return Err(CrowdfundError::AlreadyCreated);
+        }
+        if goal <= 0 {
+            return Err(CrowdfundError::InvalidAmount);
+        }
+        if deadline <= env.ledger().timestamp() {
+            return Err(CrowdfundError::InvalidDeadline);
+        }
+        let campaign = Campaign {
+            creator,
+            token,
+            goal,
+            deadline,
+        };
+        env.storage().instance().set(&DataKey::Campaign, &campaign);
+        env.storage().instance().set(&DataKey::Total, &0_i128);
+        env.events().publish((symbol_short!("created"),), campaign);
+        Ok(())
+    }
+
+    /// Escrow `amount` from `backer` while the campaign is running.
+    pub fn pledge(env: Env, backer: Address, amount: i128) -> Result<(), CrowdfundError> {
+        backer.require_auth();
+        let campaign = Self::load(&env)?;
+        if amount <= 0 {
+            return Err(CrowdfundError::InvalidAmount);
+        }
+        if env.ledger().timestamp() >= campaign.deadline {
+            return Err(CrowdfundError::CampaignEnded);
+        }
+
+        token::Client::new(&env, &campaign.token).transfer(
+            &backer,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let key = DataKey::Pledge(backer.clone());
+        let pledged = Self::pledged(env.clone(), backer.clone());
+        env.storage().persistent().set(&key, &(pledged + amount));
+        let total = Self::total(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Total, &(total + amount));
+        env.events()
+            .publish((symbol_short!("pledged"), backer), amount);
+        Ok(())
+    }
+
+    /// Pay the whole raise to the creator. Only after the deadline, only if
+    /// the goal was met, and only once.
+    pub fn withdraw(env: Env, creator: Address) -> Result<i128, CrowdfundError> {
+        creator.require_auth();
+        let campaign = Self::load(&env)?;
+        if creator != campaign.creator {
+            return Err(CrowdfundError::NotCreator);
+        }
+        match Self::get_state(env.clone()) {
+            CampaignState::Running => return Err(CrowdfundError::CampaignRunning),
+            CampaignState::Failed => return Err(CrowdfundError::GoalNotReached),
+            CampaignState::Withdrawn => return Err(CrowdfundError::AlreadyWithdrawn),
+            CampaignState::Unknown | CampaignState::Successful => {}
+        }
+
+        let total = Self::total(env.clone());
+        env.storage().instance().set(&DataKey::Withdrawn, &true);
+        token::Client::new(&env, &campaign.token).transfer(
+            &env.current_contract_address(),
+            &creator,
+            &total,
+        );
+        env.events()
+            .publish((symbol_short!("funded"), creator), total);
+        Ok(total)
+    }
+
+    /// Return `backer`'s full pledge after a failed campaign.
+    pub fn refund(env: Env, backer: Address) -> Result<i128, CrowdfundError> {
+        backer.require_auth();
+        let campaign = Self::load(&env)?;
+        match Self::get_state(env.clone()) {
+            CampaignState::Running => return Err(CrowdfundError::CampaignRunning),
+            CampaignState::Successful | CampaignState::Withdrawn => {
+                return Err(CrowdfundError::GoalReached)
+            }
+            CampaignState::Unknown | CampaignState::Failed => {}
+        }
+
+        let amount = Self::pledged(env.clone(), backer.clone());
+        if amount == 0 {
+            return Err(CrowdfundError::NothingToRefund);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Pledge(backer.clone()));
+        token::Client::new(&env, &campaign.token).transfer(
+            &env.current_contract_address(),
+            &backer,
+            &amount,
+        );
+        env.events()
+            .publish((symbol_short!("refunded"), backer), amount);
+        Ok(amount)
+    }
+
+    pub fn get_state(env: Env) -> CampaignState {
+        let Some(campaign) = env
+            .storage()
+            .instance()
+            .get::<_, Campaign>(&DataKey::Campaign)
+        else {
+            return CampaignState::Unknown;
+        };
+        if env.ledger().timestamp() < campaign.deadline {
+            CampaignState::Running
+        } else if env.storage().instance().has(&DataKey::Withdrawn) {
+            CampaignState::Withdrawn
+        } else if Self::total(env) >= campaign.goal {
+            CampaignState::Successful
+        } else {
+            CampaignState::Failed
+        }
+    }
+
+    pub fn total(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Total).unwrap_or(0)
+    }
+
+    /// Amount `backer` currently has pledged (zero after a refund).
+    pub fn pledged(env: Env, backer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Pledge(backer))
+            .unwrap_or(0)
+    }
+}
+
+impl CrowdfundContract {
+    fn load(env: &Env) -> Result<Campaign, CrowdfundError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Campaign)
+            .ok_or(CrowdfundError::NotCreated)
+    }
+}
+
+mod test;
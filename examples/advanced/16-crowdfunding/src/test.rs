@@ -0,0 +1,152 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Env,
+};
+
+const DEADLINE: u64 = 10_000;
+const GOAL: i128 = 1_000;
+
+struct Setup {
+    env: Env,
+    creator: Address,
+    token: TokenClient<'static>,
+    minter: StellarAssetClient<'static>,
+    campaign: CrowdfundContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let creator = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let campaign =
+        CrowdfundContractClient::new(&env, &env.register_contract(None, CrowdfundContract));
+    campaign.create(&creator, &asset.address(), &GOAL, &DEADLINE);
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        minter: StellarAssetClient::new(&env, &asset.address()),
+        env,
+        creator,
+        campaign,
+    }
+}
+
+fn backer(s: &Setup, pledge: i128) -> Address {
+    let backer = Address::generate(&s.env);
+    s.minter.mint(&backer, &pledge);
+    s.campaign.pledge(&backer, &pledge);
+    backer
+}
+
+fn pass_deadline(env: &Env) {
+    env.ledger().with_mut(|li| li.timestamp = DEADLINE);
+}
+
+#[test]
+fn test_funded_campaign_pays_creator_once() {
+    let s = setup();
+    backer(&s, 600);
+    backer(&s, 400);
+    assert_eq!(s.campaign.get_state(), CampaignState::Running);
+    assert_eq!(
+        s.campaign.try_withdraw(&s.creator),
+        Err(Ok(CrowdfundError::CampaignRunning))
+    );
+
+    pass_deadline(&s.env);
+    assert_eq!(s.campaign.get_state(), CampaignState::Successful);
+    assert_eq!(s.campaign.withdraw(&s.creator), 1_000);
+    assert_eq!(s.token.balance(&s.creator), 1_000);
+    assert_eq!(s.campaign.get_state(), CampaignState::Withdrawn);
+
+    assert_eq!(
+        s.campaign.try_withdraw(&s.creator),
+        Err(Ok(CrowdfundError::AlreadyWithdrawn))
+    );
+    assert_eq!(s.token.balance(&s.creator), 1_000);
+}
+
+#[test]
+fn test_unfunded_campaign_refunds_exact_pledges() {
+    let s = setup();
+    let alice = backer(&s, 300);
+    let bob = backer(&s, 450);
+    // Alice tops up; her refund covers both pledges.
+    s.minter.mint(&alice, &50);
+    s.campaign.pledge(&alice, &50);
+
+    pass_deadline(&s.env);
+    assert_eq!(s.campaign.get_state(), CampaignState::Failed);
+    assert_eq!(
+        s.campaign.try_withdraw(&s.creator),
+        Err(Ok(CrowdfundError::GoalNotReached))
+    );
+
+    assert_eq!(s.campaign.refund(&alice), 350);
+    assert_eq!(s.campaign.refund(&bob), 450);
+    assert_eq!(s.token.balance(&alice), 350);
+    assert_eq!(s.token.balance(&bob), 450);
+    assert_eq!(s.token.balance(&s.campaign.address), 0);
+}
+
+#[test]
+fn test_double_refund_rejected() {
+    let s = setup();
+    let alice = backer(&s, 300);
+    pass_deadline(&s.env);
+    s.campaign.refund(&alice);
+    assert_eq!(
+        s.campaign.try_refund(&alice),
+        Err(Ok(CrowdfundError::NothingToRefund))
+    );
+    assert_eq!(s.token.balance(&alice), 300);
+}
+
+#[test]
+fn test_pledge_after_deadline_rejected() {
+    let s = setup();
+    let late = Address::generate(&s.env);
+    s.minter.mint(&late, &100);
+    pass_deadline(&s.env);
+    assert_eq!(
+        s.campaign.try_pledge(&late, &100),
+        Err(Ok(CrowdfundError::CampaignEnded))
+    );
+    assert_eq!(s.token.balance(&late), 100);
+}
+
+#[test]
+fn test_refund_on_successful_campaign_rejected() {
+    let s = setup();
+    let alice = backer(&s, GOAL);
+    assert_eq!(
+        s.campaign.try_refund(&alice),
+        Err(Ok(CrowdfundError::CampaignRunning))
+    );
+    pass_deadline(&s.env);
+    assert_eq!(
+        s.campaign.try_refund(&alice),
+        Err(Ok(CrowdfundError::GoalReached))
+    );
+    s.campaign.withdraw(&s.creator);
+    assert_eq!(
+        s.campaign.try_refund(&alice),
+        Err(Ok(CrowdfundError::GoalReached))
+    );
+}
+
+#[test]
+fn test_only_creator_withdraws() {
+    let s = setup();
+    let alice = backer(&s, GOAL);
+    pass_deadline(&s.env);
+    assert_eq!(
+        s.campaign.try_withdraw(&alice),
+        Err(Ok(CrowdfundError::NotCreator))
+    );
+}
@@ -0,0 +1,220 @@
+//! # Streaming Payments
+//!
+//! A sender escrows `total` tokens that flow to a recipient second by second
+//! between `start` and `end`:
+//!
+//! ```text
+//! streamed(t)   = total * (t - start) / (end - start)   clamped to [0, total]
+//! withdrawable  = streamed(t) - withdrawn
+//! refundable    = total - streamed(t)
+//! ```
+//!
+//! The recipient can withdraw any part of what has streamed so far.
+//! Either party can cancel: the recipient gets what has streamed and not
+//! been withdrawn, the sender gets the rest, and the stream is closed.
+//!
+//! `streamed` rounds down, so the recipient can never withdraw ahead of the
+//! schedule; the rounding dust is released at `end`, when `streamed`
+//! becomes exactly `total`.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextId,
+    Stream(u64),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stream {
+    pub sender: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub total: i128,
+    pub start: u64,
+    pub end: u64,
+    pub withdrawn: i128,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum StreamError {
+    InvalidAmount = 1,
+    /// `end` is not after `start`.
+    InvalidSchedule = 2,
+    StreamNotFound = 3,
+    NotRecipient = 4,
+    /// The caller is neither the sender nor the recipient.
+    NotParty = 5,
+    /// The withdrawal is larger than what has streamed so far.
+    ExceedsWithdrawable = 6,
+    MathOverflow = 7,
+}
+
+#[contract]
+pub struct StreamingContract;
+
+#[contractimpl]
+impl StreamingContract {
+    /// Escrow `total` from `sender` and return the new stream id.
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        total: i128,
+        start: u64,
+        end: u64,
+    ) -> Result<u64, StreamError> {
+        sender.require_auth();
+        if total <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if end <= start {
+            return Err(StreamError::InvalidSchedule);
+        }
+
+        token::Client::new(&env, &token).transfer(&sender, &env.current_contract_address(), &total);
+        let id: u64 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        let stream = Stream {
+            sender,
+            recipient,
+            token,
+            total,
+            start,
+            end,
+            withdrawn: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(id), &stream);
+        env.events().publish(
+            (symbol_short!("stream"), symbol_short!("created"), id),
+            stream,
+        );
+        Ok(id)
+    }
+
+    /// `(withdrawable by recipient, refundable to sender)` right now.
+    pub fn balance_of_stream(env: Env, stream_id: u64) -> Result<(i128, i128), StreamError> {
+        let stream = Self::load(&env, stream_id)?;
+        Self::split(&env, &stream)
+    }
+
+    pub fn withdraw(
+        env: Env,
+        recipient: Address,
+        stream_id: u64,
+        amount: i128,
+    ) -> Result<(), StreamError> {
+        recipient.require_auth();
+        let mut stream = Self::load(&env, stream_id)?;
+        if recipient != stream.recipient {
+            return Err(StreamError::NotRecipient);
+        }
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        let (withdrawable, _) = Self::split(&env, &stream)?;
+        if amount > withdrawable {
+            return Err(StreamError::ExceedsWithdrawable);
+        }
+
+        stream.withdrawn += amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(stream_id), &stream);
+        token::Client::new(&env, &stream.token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+        env.events().publish(
+            (
+                symbol_short!("stream"),
+                symbol_short!("withdrawn"),
+                stream_id,
+            ),
+            amount,
+        );
+        Ok(())
+    }
+
+    /// Close the stream, paying each side its share as of now. Returns
+    /// `(paid to recipient, refunded to sender)`.
+    pub fn cancel(env: Env, caller: Address, stream_id: u64) -> Result<(i128, i128), StreamError> {
+        caller.require_auth();
+        let stream = Self::load(&env, stream_id)?;
+        if caller != stream.sender && caller != stream.recipient {
+            return Err(StreamError::NotParty);
+        }
+
+        let (to_recipient, to_sender) = Self::split(&env, &stream)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Stream(stream_id));
+        let token = token::Client::new(&env, &stream.token);
+        let contract = env.current_contract_address();
+        if to_recipient > 0 {
+            token.transfer(&contract, &stream.recipient, &to_recipient);
+        }
+        if to_sender > 0 {
+            token.transfer(&contract, &stream.sender, &to_sender);
+        }
+        env.events().publish(
+            (
+                symbol_short!("stream"),
+                symbol_short!("cancelled"),
+                stream_id,
+            ),
+            (to_recipient, to_sender),
+        );
+        Ok((to_recipient, to_sender))
+    }
+
+    pub fn get_stream(env: Env, stream_id: u64) -> Option<Stream> {
+        env.storage().persistent().get(&DataKey::Stream(stream_id))
+    }
+}
+
+impl StreamingContract {
+    fn load(env: &Env, stream_id: u64) -> Result<Stream, StreamError> {
+        Self::get_stream(env.clone(), stream_id).ok_or(StreamError::StreamNotFound)
+    }
+
+    fn split(env: &Env, stream: &Stream) -> Result<(i128, i128), StreamError> {
+        let streamed = Self::streamed_at(stream, env.ledger().timestamp())?;
+        Ok((streamed - stream.withdrawn, stream.total - streamed))
+    }
+
+    fn streamed_at(stream: &Stream, now: u64) -> Result<i128, StreamError> {
+        if now <= stream.start {
+            return Ok(0);
+        }
+        if now >= stream.end {
+            return Ok(stream.total);
+        }
+        mul_div(
+            stream.total,
+            i128::from(now - stream.start),
+            i128::from(stream.end - stream.start),
+        )
+    }
+}
+
+/// `a * b / c`, rounded down, with overflow surfaced as a typed error.
+fn mul_div(a: i128, b: i128, c: i128) -> Result<i128, StreamError> {
+    a.checked_mul(b)
+        .and_then(|product| product.checked_div(c))
+        .ok_or(StreamError::MathOverflow)
+}
+
+mod test;
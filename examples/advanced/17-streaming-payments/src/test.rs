@@ -0,0 +1,157 @@
+#![cfg(test)]
+
+use super::*;
+use recipes_testutils::set_timestamp;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env,
+};
+
+const START: u64 = 1_000;
+
+struct Setup {
+    env: Env,
+    sender: Address,
+    recipient: Address,
+    token: TokenClient<'static>,
+    streams: StreamingContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, START);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &asset.address()).mint(&sender, &1_000_000);
+    let streams =
+        StreamingContractClient::new(&env, &env.register_contract(None, StreamingContract));
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        sender,
+        recipient,
+        streams,
+    }
+}
+
+fn open(s: &Setup, total: i128, duration: u64) -> u64 {
+    s.streams.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token.address,
+        &total,
+        &START,
+        &(START + duration),
+    )
+}
+
+#[test]
+fn test_partial_withdrawals_sum_to_streamed_amount() {
+    let s = setup();
+    let id = open(&s, 10_000, 100);
+
+    set_timestamp(&s.env, START + 10);
+    assert_eq!(s.streams.balance_of_stream(&id), (1_000, 9_000));
+    s.streams.withdraw(&s.recipient, &id, &600);
+
+    set_timestamp(&s.env, START + 45);
+    assert_eq!(s.streams.balance_of_stream(&id), (3_900, 5_500));
+    s.streams.withdraw(&s.recipient, &id, &3_900);
+
+    set_timestamp(&s.env, START + 80);
+    s.streams.withdraw(&s.recipient, &id, &3_500);
+
+    assert_eq!(s.token.balance(&s.recipient), 8_000);
+    assert_eq!(s.streams.get_stream(&id).unwrap().withdrawn, 8_000);
+    assert_eq!(s.streams.balance_of_stream(&id), (0, 2_000));
+}
+
+#[test]
+fn test_cancel_mid_stream_splits_remainder() {
+    let s = setup();
+    let id = open(&s, 10_000, 100);
+
+    set_timestamp(&s.env, START + 30);
+    s.streams.withdraw(&s.recipient, &id, &1_000);
+
+    set_timestamp(&s.env, START + 40);
+    assert_eq!(s.streams.cancel(&s.recipient, &id), (3_000, 6_000));
+    assert_eq!(s.token.balance(&s.recipient), 4_000);
+    assert_eq!(s.token.balance(&s.sender), 1_000_000 - 4_000);
+    assert_eq!(s.token.balance(&s.streams.address), 0);
+    assert_eq!(s.streams.get_stream(&id), None);
+    assert_eq!(
+        s.streams.try_withdraw(&s.recipient, &id, &1),
+        Err(Ok(StreamError::StreamNotFound))
+    );
+}
+
+#[test]
+fn test_only_parties_can_cancel() {
+    let s = setup();
+    let id = open(&s, 10_000, 100);
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.streams.try_cancel(&stranger, &id),
+        Err(Ok(StreamError::NotParty))
+    );
+    // The sender can cancel before anything streams and gets everything back.
+    assert_eq!(s.streams.cancel(&s.sender, &id), (0, 10_000));
+    assert_eq!(s.token.balance(&s.sender), 1_000_000);
+}
+
+#[test]
+fn test_withdrawal_exceeding_accrued_rejected() {
+    let s = setup();
+    let id = open(&s, 10_000, 100);
+    set_timestamp(&s.env, START + 10);
+    assert_eq!(
+        s.streams.try_withdraw(&s.recipient, &id, &1_001),
+        Err(Ok(StreamError::ExceedsWithdrawable))
+    );
+    assert_eq!(
+        s.streams.try_withdraw(&s.sender, &id, &1),
+        Err(Ok(StreamError::NotRecipient))
+    );
+}
+
+#[test]
+fn test_zero_duration_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.streams.try_create_stream(
+            &s.sender,
+            &s.recipient,
+            &s.token.address,
+            &1_000,
+            &START,
+            &START,
+        ),
+        Err(Ok(StreamError::InvalidSchedule))
+    );
+    assert_eq!(s.token.balance(&s.sender), 1_000_000);
+}
+
+#[test]
+fn test_rounding_dust_released_at_end() {
+    let s = setup();
+    let id = open(&s, 1_000, 3);
+
+    set_timestamp(&s.env, START + 1);
+    assert_eq!(s.streams.balance_of_stream(&id), (333, 667));
+    s.streams.withdraw(&s.recipient, &id, &333);
+
+    set_timestamp(&s.env, START + 2);
+    assert_eq!(s.streams.balance_of_stream(&id), (333, 334));
+    s.streams.withdraw(&s.recipient, &id, &333);
+
+    set_timestamp(&s.env, START + 3);
+    assert_eq!(s.streams.balance_of_stream(&id), (334, 0));
+    s.streams.withdraw(&s.recipient, &id, &334);
+
+    assert_eq!(s.token.balance(&s.recipient), 1_000);
+    assert_eq!(s.token.balance(&s.streams.address), 0);
+}
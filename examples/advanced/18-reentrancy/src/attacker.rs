@@ -0,0 +1,93 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+use crate::vault::{Receiver, VaultClient};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum AttackerKey {
+    Plan,
+    Outcome,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Plan {
+    /// Whose ledger balance to withdraw a second time.
+    pub owner: Address,
+    /// The vault the hook calls into.
+    pub target: Address,
+    /// Where the second payout goes. It cannot be this contract: the host
+    /// would refuse to call this contract's hook while the hook is running.
+    pub sink: Address,
+    /// Attack `withdraw` (`true`) or `withdraw_naive` (`false`).
+    pub guarded: bool,
+}
+
+/// What happened when the hook tried to withdraw a second time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReentryOutcome {
+    NotAttempted,
+    /// The nested withdrawal paid out.
+    Succeeded,
+    /// The vault refused with this `VaultError` code.
+    Rejected(u32),
+    /// The host refused the call, e.g. because it re-entered a contract
+    /// that was already on the call stack.
+    Aborted,
+}
+
+/// A payout recipient whose `on_receive` hook calls `withdraw` again
+/// before the first withdrawal has finished.
+#[contract]
+pub struct Attacker;
+
+#[contractimpl]
+impl Attacker {
+    pub fn arm(env: Env, owner: Address, target: Address, sink: Address, guarded: bool) {
+        let plan = Plan {
+            owner,
+            target,
+            sink,
+            guarded,
+        };
+        env.storage().instance().set(&AttackerKey::Plan, &plan);
+        set_outcome(&env, ReentryOutcome::NotAttempted);
+    }
+
+    pub fn outcome(env: Env) -> ReentryOutcome {
+        env.storage()
+            .instance()
+            .get(&AttackerKey::Outcome)
+            .unwrap_or(ReentryOutcome::NotAttempted)
+    }
+}
+
+#[contractimpl]
+impl Receiver for Attacker {
+    /// Called by the vault mid-withdrawal. Tries once to withdraw the same
+    /// amount again and records the result instead of failing, so the
+    /// outer withdrawal can finish either way.
+    fn on_receive(env: Env, _from: Address, amount: i128) {
+        let plan: Plan = env.storage().instance().get(&AttackerKey::Plan).unwrap();
+        let client = VaultClient::new(&env, &plan.target);
+        let result = if plan.guarded {
+            client.try_withdraw(&plan.owner, &plan.sink, &amount)
+        } else {
+            client.try_withdraw_naive(&plan.owner, &plan.sink, &amount)
+        };
+
+        let outcome = match result {
+            Ok(_) => ReentryOutcome::Succeeded,
+            Err(Ok(err)) => ReentryOutcome::Rejected(err as u32),
+            Err(Err(_)) => ReentryOutcome::Aborted,
+        };
+        set_outcome(&env, outcome);
+    }
+}
+
+fn set_outcome(env: &Env, outcome: ReentryOutcome) {
+    env.storage()
+        .instance()
+        .set(&AttackerKey::Outcome, &outcome);
+}
@@ -0,0 +1,106 @@
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum LedgerKey {
+    Admin,
+    Vault(Address),
+    Balance(Address),
+    Lock(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LedgerError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The caller is not a registered vault.
+    NotVault = 3,
+}
+
+/// The balance book shared by every vault front-end.
+///
+/// Only registered vaults may write to it. It stores what it is told and
+/// enforces nothing else: keeping balances consistent is the vaults' job.
+#[contract]
+pub struct Ledger;
+
+#[contractimpl]
+impl Ledger {
+    pub fn init_ledger(env: Env, admin: Address) -> Result<(), LedgerError> {
+        if env.storage().instance().has(&LedgerKey::Admin) {
+            return Err(LedgerError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&LedgerKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Register a vault that may move balances.
+    pub fn add_vault(env: Env, vault: Address) -> Result<(), LedgerError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&LedgerKey::Admin)
+            .ok_or(LedgerError::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .persistent()
+            .set(&LedgerKey::Vault(vault), &true);
+        Ok(())
+    }
+
+    pub fn balance_of(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&LedgerKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    pub fn set_balance(
+        env: Env,
+        vault: Address,
+        user: Address,
+        amount: i128,
+    ) -> Result<(), LedgerError> {
+        require_vault(&env, &vault)?;
+        env.storage()
+            .persistent()
+            .set(&LedgerKey::Balance(user), &amount);
+        Ok(())
+    }
+
+    pub fn is_locked(env: Env, user: Address) -> bool {
+        env.storage().temporary().has(&LedgerKey::Lock(user))
+    }
+
+    /// Set or clear the in-flight flag for `user`. It lives here, not in a
+    /// vault, so that every front-end sees the same flag.
+    pub fn set_lock(
+        env: Env,
+        vault: Address,
+        user: Address,
+        locked: bool,
+    ) -> Result<(), LedgerError> {
+        require_vault(&env, &vault)?;
+        let key = LedgerKey::Lock(user);
+        if locked {
+            env.storage().temporary().set(&key, &true);
+        } else {
+            env.storage().temporary().remove(&key);
+        }
+        Ok(())
+    }
+}
+
+fn require_vault(env: &Env, vault: &Address) -> Result<(), LedgerError> {
+    vault.require_auth();
+    if !env
+        .storage()
+        .persistent()
+        .has(&LedgerKey::Vault(vault.clone()))
+    {
+        return Err(LedgerError::NotVault);
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! # Reentrancy and Callback Safety
+//!
+//! A vault that notifies recipients through an `on_receive` hook, an
+//! attacker whose hook calls back into a vault, and two versions of
+//! `withdraw`:
+//!
+//! - `withdraw_naive` (**vulnerable**) reads the owner's balance, pays out,
+//!   runs the hook, and only then writes the reduced balance back;
+//! - `withdraw` follows checks-effects-interactions: it takes an in-flight
+//!   lock and debits the balance *before* any external call.
+//!
+//! ## Why there is a separate `Ledger`
+//!
+//! The Soroban host refuses to call a contract that is already on the call
+//! stack ("Contract re-entry is not allowed"), so the textbook attack of
+//! re-entering the *same* vault fails. The tests show this, but it does not
+//! make the naive ordering safe. Any state that another contract can change
+//! mid-call is exposed in the same way.
+//!
+//! Here the balances live in a shared `Ledger` used by two vault
+//! front-ends. The thief withdraws from vault A to the attacker contract.
+//! Inside the hook, the attacker withdraws the same balance again from
+//! vault B. No contract is re-entered, so the host allows it, and the naive
+//! vaults pay out twice for one balance.
+//!
+//! Every contract lives in this crate so the tests can register them side
+//! by side. Function names are unique across the three contracts so the
+//! crate still links to a single Wasm module.
+
+#![no_std]
+
+mod attacker;
+mod ledger;
+mod vault;
+
+pub use attacker::{Attacker, AttackerClient, Plan, ReentryOutcome};
+pub use ledger::{Ledger, LedgerClient, LedgerError};
+pub use vault::{Receiver, ReceiverClient, Vault, VaultClient, VaultError};
+
+mod test;
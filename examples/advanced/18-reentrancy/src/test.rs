@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use super::*;
+use recipes_testutils::TestEnv;
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, token, Address, Env};
+
+/// A well-behaved recipient whose hook does nothing.
+#[contract]
+pub struct Wallet;
+
+#[contractimpl]
+impl Receiver for Wallet {
+    fn on_receive(_env: Env, _from: Address, _amount: i128) {}
+}
+
+struct Setup<'a> {
+    env: TestEnv,
+    token: token::Client<'a>,
+    ledger: LedgerClient<'a>,
+    vault_a: VaultClient<'a>,
+    vault_b: VaultClient<'a>,
+    /// The account whose balance is withdrawn twice.
+    thief: Address,
+    attacker: AttackerClient<'a>,
+    /// The thief's second wallet, which receives the nested payout.
+    sink: Address,
+    /// An honest depositor in vault B.
+    wallet: Address,
+}
+
+/// Two vault front-ends over one ledger. The thief has 100 deposited
+/// through vault A; an honest wallet has 100 deposited through vault B.
+fn setup() -> Setup<'static> {
+    let env = TestEnv::new();
+    let admin = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let token = token::Client::new(&env, &sac.address());
+    let mint = token::StellarAssetClient::new(&env, &sac.address());
+
+    let (ledger_id, ledger) = env.register(Ledger, LedgerClient::new);
+    ledger.init_ledger(&admin);
+
+    let (a_id, vault_a) = env.register(Vault, VaultClient::new);
+    let (b_id, vault_b) = env.register(Vault, VaultClient::new);
+    for (id, vault) in [(&a_id, &vault_a), (&b_id, &vault_b)] {
+        vault.initialize(&ledger_id, &sac.address());
+        ledger.add_vault(id);
+    }
+
+    let thief = Address::generate(&env);
+    let (_, attacker) = env.register(Attacker, AttackerClient::new);
+    let (sink, _) = env.register(Wallet, WalletClient::new);
+    let (wallet, _) = env.register(Wallet, WalletClient::new);
+    mint.mint(&thief, &100);
+    mint.mint(&wallet, &100);
+    vault_a.deposit(&thief, &100);
+    vault_b.deposit(&wallet, &100);
+
+    Setup {
+        env,
+        token,
+        ledger,
+        vault_a,
+        vault_b,
+        thief,
+        attacker,
+        sink,
+        wallet,
+    }
+}
+
+#[test]
+fn test_guarded_withdraw_debits_and_pays() {
+    let s = setup();
+    s.vault_b.withdraw(&s.wallet, &s.wallet, &40);
+
+    assert_eq!(s.vault_b.balance(&s.wallet), 60);
+    assert_eq!(s.token.balance(&s.wallet), 40);
+    assert_eq!(s.token.balance(&s.vault_b.address), 60);
+    assert!(!s.ledger.is_locked(&s.wallet));
+}
+
+#[test]
+fn test_attacker_drains_naive_vault() {
+    let s = setup();
+    s.attacker
+        .arm(&s.thief, &s.vault_b.address, &s.sink, &false);
+
+    s.vault_a
+        .withdraw_naive(&s.thief, &s.attacker.address, &100);
+
+    // Vault B paid out against a balance vault A had already spent, and
+    // vault A's late write then zeroed the ledger entry once, not twice.
+    assert_eq!(s.attacker.outcome(), ReentryOutcome::Succeeded);
+    assert_eq!(s.token.balance(&s.attacker.address), 100);
+    assert_eq!(s.token.balance(&s.sink), 100);
+    assert_eq!(s.ledger.balance_of(&s.thief), 0);
+    assert_eq!(s.token.balance(&s.vault_a.address), 0);
+    assert_eq!(s.token.balance(&s.vault_b.address), 0);
+
+    // The honest wallet's 100 is still on the books but no longer backed.
+    assert_eq!(s.ledger.balance_of(&s.wallet), 100);
+    assert!(s.vault_b.try_withdraw(&s.wallet, &s.wallet, &100).is_err());
+}
+
+#[test]
+fn test_guarded_vault_rejects_nested_withdraw() {
+    let s = setup();
+    s.attacker.arm(&s.thief, &s.vault_b.address, &s.sink, &true);
+
+    s.vault_a.withdraw(&s.thief, &s.attacker.address, &100);
+
+    assert_eq!(
+        s.attacker.outcome(),
+        ReentryOutcome::Rejected(VaultError::Reentrant as u32)
+    );
+    assert_eq!(s.token.balance(&s.attacker.address), 100);
+    assert_eq!(s.token.balance(&s.sink), 0);
+    assert_eq!(s.ledger.balance_of(&s.thief), 0);
+    assert_eq!(s.token.balance(&s.vault_a.address), 0);
+    assert_eq!(s.token.balance(&s.vault_b.address), 100);
+    assert!(!s.ledger.is_locked(&s.thief));
+
+    s.vault_b.withdraw(&s.wallet, &s.wallet, &100);
+    assert_eq!(s.token.balance(&s.wallet), 100);
+}
+
+#[test]
+fn test_host_blocks_reentry_into_same_vault() {
+    let s = setup();
+    // Even the naive vault is safe from the textbook attack: the host
+    // refuses to call vault A while vault A is still on the stack.
+    s.attacker
+        .arm(&s.thief, &s.vault_a.address, &s.sink, &false);
+
+    s.vault_a
+        .withdraw_naive(&s.thief, &s.attacker.address, &100);
+
+    assert_eq!(s.attacker.outcome(), ReentryOutcome::Aborted);
+    assert_eq!(s.token.balance(&s.attacker.address), 100);
+    assert_eq!(s.token.balance(&s.sink), 0);
+    assert_eq!(s.ledger.balance_of(&s.thief), 0);
+    assert_eq!(s.token.balance(&s.vault_b.address), 100);
+}
+
+#[test]
+fn test_withdraw_more_than_balance_fails() {
+    let s = setup();
+    assert_eq!(
+        s.vault_b.try_withdraw(&s.wallet, &s.wallet, &101),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    assert_eq!(
+        s.vault_b.try_withdraw_naive(&s.wallet, &s.wallet, &101),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    assert_eq!(
+        s.vault_b.try_withdraw(&s.wallet, &s.wallet, &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_only_registered_vaults_write_ledger() {
+    let s = setup();
+    let (rogue, _) = s.env.register(Vault, VaultClient::new);
+    assert_eq!(
+        s.ledger.try_set_balance(&rogue, &s.wallet, &1_000),
+        Err(Ok(LedgerError::NotVault))
+    );
+    assert_eq!(s.ledger.balance_of(&s.wallet), 100);
+}
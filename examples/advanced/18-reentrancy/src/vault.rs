@@ -0,0 +1,149 @@
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, token, Address, Env,
+};
+
+use crate::ledger::LedgerClient;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum VaultKey {
+    Ledger,
+    Token,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VaultError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    /// A withdrawal for this user is already in flight.
+    Reentrant = 5,
+}
+
+/// The hook a recipient contract implements to be told about a payout.
+/// Every withdrawal calls it on `to`, so `to` must be a contract.
+#[contractclient(name = "ReceiverClient")]
+pub trait Receiver {
+    fn on_receive(env: Env, from: Address, amount: i128);
+}
+
+/// Holds tokens and pays them out, keeping balances in a shared `Ledger`.
+///
+/// Withdrawals debit `from` and pay `to`, so an owner can send funds
+/// straight to a contract that reacts to them.
+#[contract]
+pub struct Vault;
+
+#[contractimpl]
+impl Vault {
+    pub fn initialize(env: Env, ledger: Address, token: Address) -> Result<(), VaultError> {
+        if env.storage().instance().has(&VaultKey::Ledger) {
+            return Err(VaultError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&VaultKey::Ledger, &ledger);
+        env.storage().instance().set(&VaultKey::Token, &token);
+        Ok(())
+    }
+
+    pub fn deposit(env: Env, from: Address, amount: i128) -> Result<(), VaultError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let (ledger, token) = load(&env)?;
+        let this = env.current_contract_address();
+
+        token.transfer(&from, &this, &amount);
+        let balance = ledger.balance_of(&from);
+        ledger.set_balance(&this, &from, &(balance + amount));
+        Ok(())
+    }
+
+    pub fn balance(env: Env, user: Address) -> Result<i128, VaultError> {
+        let (ledger, _) = load(&env)?;
+        Ok(ledger.balance_of(&user))
+    }
+
+    /// **VULNERABLE — do not copy.** Interactions happen before effects:
+    /// the balance read at the top is written back only after the token
+    /// transfer and the recipient's hook have run. Anything the hook does
+    /// to the same balance in the meantime is overwritten, and a second
+    /// withdrawal made from inside the hook still sees the old balance.
+    pub fn withdraw_naive(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), VaultError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let (ledger, token) = load(&env)?;
+        let this = env.current_contract_address();
+
+        // Checks
+        let balance = ledger.balance_of(&from);
+        if balance < amount {
+            return Err(VaultError::InsufficientBalance);
+        }
+
+        // Interactions (too early)
+        token.transfer(&this, &to, &amount);
+        ReceiverClient::new(&env, &to).on_receive(&this, &amount);
+
+        // Effects (too late, and from a stale read)
+        ledger.set_balance(&this, &from, &(balance - amount));
+        Ok(())
+    }
+
+    /// The fixed version: checks, then effects, then interactions, with an
+    /// explicit in-flight lock held in the shared ledger for the duration.
+    ///
+    /// Debiting first is already enough to defeat the attack in this
+    /// recipe. The lock also rejects a nested withdrawal outright, which
+    /// keeps the rule simple to audit when more entry points are added.
+    pub fn withdraw(env: Env, from: Address, to: Address, amount: i128) -> Result<(), VaultError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let (ledger, token) = load(&env)?;
+        let this = env.current_contract_address();
+
+        // Checks
+        if ledger.is_locked(&from) {
+            return Err(VaultError::Reentrant);
+        }
+        let balance = ledger.balance_of(&from);
+        if balance < amount {
+            return Err(VaultError::InsufficientBalance);
+        }
+
+        // Effects
+        ledger.set_lock(&this, &from, &true);
+        ledger.set_balance(&this, &from, &(balance - amount));
+
+        // Interactions
+        token.transfer(&this, &to, &amount);
+        ReceiverClient::new(&env, &to).on_receive(&this, &amount);
+
+        ledger.set_lock(&this, &from, &false);
+        Ok(())
+    }
+}
+
+fn load(env: &Env) -> Result<(LedgerClient<'_>, token::Client<'_>), VaultError> {
+    let storage = env.storage().instance();
+    let ledger: Address = storage
+        .get(&VaultKey::Ledger)
+        .ok_or(VaultError::NotInitialized)?;
+    let token: Address = storage.get(&VaultKey::Token).unwrap();
+    Ok((
+        LedgerClient::new(env, &ledger),
+        token::Client::new(env, &token),
+    ))
+}
@@ -0,0 +1,156 @@
+//! # TTL and Archival Management
+//!
+//! Every ledger entry has a *live-until* ledger. Once the ledger sequence
+//! passes it:
+//!
+//! - a **temporary** entry is deleted for good;
+//! - a **persistent** entry is archived and must be restored (with a
+//!   `RestoreFootprint` operation) before it can be read or written again;
+//! - the **instance** entry archives the contract itself, along with every
+//!   instance key.
+//!
+//! Nothing extends a TTL automatically. This recipe shows the explicit
+//! policy a production contract needs:
+//!
+//! - writes extend the entry they touch with a `threshold` / `extend_to`
+//!   pair, so active entries stay alive without paying for an extension
+//!   on every call;
+//! - `keepalive` lets anyone (a bot, a front-end, a user about to go on
+//!   holiday) bump a batch of persistent entries and the instance;
+//! - `bump` exposes the raw `extend_ttl` parameters for one entry.
+//!
+//! A contract cannot read a TTL. Off-chain, the RPC `getLedgerEntries`
+//! response carries `liveUntilLedgerSeq`. In tests, `get_ttl` from the
+//! SDK's `testutils` reports the remaining ledgers (see `test.rs`).
+//!
+//! ## Instance vs persistent rent
+//!
+//! Instance storage is a single ledger entry. It is loaded, and counted
+//! against the transaction's read budget, on **every** invocation, whatever
+//! the function touches. Rent is charged on its whole size each time it is
+//! extended. Persistent entries are loaded only when read and rented one by
+//! one. Keep instance storage to a few small config values and put anything
+//! that grows with usage in persistent entries. `test_instance_size_taxes_every_call`
+//! measures the difference with the host budget.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, Env, Symbol, Vec};
+
+/// Ledgers per day at a ~5 second close time.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Persistent entries are extended once fewer than this many ledgers remain.
+pub const PERSISTENT_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
+/// ...and are then extended to this many ledgers.
+pub const PERSISTENT_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+
+/// The same policy for the contract instance.
+pub const INSTANCE_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
+pub const INSTANCE_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TtlError {
+    /// No live entry exists under the key.
+    NotFound = 1,
+    /// `extend_to` is below `threshold` or above the network's maximum TTL.
+    InvalidTtl = 2,
+}
+
+#[contract]
+pub struct TtlContract;
+
+#[contractimpl]
+impl TtlContract {
+    // ==================== PERSISTENT ====================
+
+    /// Store `value` under `key` and apply the persistent TTL policy.
+    pub fn put_persistent(env: Env, key: Symbol, value: i128) {
+        let storage = env.storage().persistent();
+        storage.set(&key, &value);
+        storage.extend_ttl(&key, PERSISTENT_THRESHOLD, PERSISTENT_EXTEND_TO);
+        extend_instance(&env);
+    }
+
+    pub fn get_persistent(env: Env, key: Symbol) -> Option<i128> {
+        env.storage().persistent().get(&key)
+    }
+
+    /// Extend one persistent entry to `extend_to` ledgers if fewer than
+    /// `threshold` remain.
+    pub fn bump(env: Env, key: Symbol, threshold: u32, extend_to: u32) -> Result<(), TtlError> {
+        if extend_to < threshold || extend_to > env.storage().max_ttl() {
+            return Err(TtlError::InvalidTtl);
+        }
+        let storage = env.storage().persistent();
+        if !storage.has(&key) {
+            return Err(TtlError::NotFound);
+        }
+        storage.extend_ttl(&key, threshold, extend_to);
+        extend_instance(&env);
+        Ok(())
+    }
+
+    /// Apply the persistent policy to every key in `keys` that exists, and
+    /// the instance policy to the contract. Needs no authorization: anyone
+    /// may pay to keep the contract's data alive. Returns how many entries
+    /// were found.
+    pub fn keepalive(env: Env, keys: Vec<Symbol>) -> u32 {
+        let storage = env.storage().persistent();
+        let mut found = 0;
+        for key in keys.iter() {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, PERSISTENT_THRESHOLD, PERSISTENT_EXTEND_TO);
+                found += 1;
+            }
+        }
+        extend_instance(&env);
+        found
+    }
+
+    // ==================== TEMPORARY ====================
+
+    /// Store `value` under `key` for exactly `ttl` more ledgers. Temporary
+    /// entries are for data that is safe to lose, such as quotes or
+    /// short-lived nonces.
+    pub fn put_temporary(env: Env, key: Symbol, value: i128, ttl: u32) -> Result<(), TtlError> {
+        if ttl > env.storage().max_ttl() {
+            return Err(TtlError::InvalidTtl);
+        }
+        let storage = env.storage().temporary();
+        storage.set(&key, &value);
+        storage.extend_ttl(&key, ttl, ttl);
+        extend_instance(&env);
+        Ok(())
+    }
+
+    /// Read a temporary entry. Returns `None` once it has expired.
+    pub fn get_temporary(env: Env, key: Symbol) -> Option<i128> {
+        env.storage().temporary().get(&key)
+    }
+
+    // ==================== INSTANCE ====================
+
+    /// Store a small config value in instance storage. Every key added
+    /// here makes every later call to this contract more expensive.
+    pub fn put_instance(env: Env, key: Symbol, value: i128) {
+        env.storage().instance().set(&key, &value);
+        extend_instance(&env);
+    }
+
+    pub fn get_instance(env: Env, key: Symbol) -> Option<i128> {
+        env.storage().instance().get(&key)
+    }
+}
+
+/// Every state-changing entry point also applies the instance policy: if
+/// the instance is archived, the contract cannot be called at all.
+fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_THRESHOLD, INSTANCE_EXTEND_TO);
+}
+
+mod test;
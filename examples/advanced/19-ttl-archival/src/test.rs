@@ -0,0 +1,197 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::{advance_ledgers, TestEnv};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{
+        storage::{Instance as _, Persistent as _, Temporary as _},
+        Ledger,
+    },
+    vec, Address, Env,
+};
+
+const START: u32 = 1_000;
+const MAX_TTL: u32 = 365 * DAY_IN_LEDGERS;
+
+fn setup() -> (Env, Address, TtlContractClient<'static>) {
+    let (env, contract_id, client) = TestEnv::with_contract(TtlContract, TtlContractClient::new);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = START;
+        li.min_temp_entry_ttl = 16;
+        li.min_persistent_entry_ttl = 4_096;
+        li.max_entry_ttl = MAX_TTL;
+    });
+    (env, contract_id, client)
+}
+
+// Remaining-TTL getters. A contract cannot read its own TTLs, so these use
+// the SDK's test-only `get_ttl`. Live-until = current sequence + TTL.
+
+fn persistent_ttl(env: &Env, contract_id: &Address, key: &Symbol) -> u32 {
+    env.as_contract(contract_id, || env.storage().persistent().get_ttl(key))
+}
+
+fn temporary_ttl(env: &Env, contract_id: &Address, key: &Symbol) -> u32 {
+    env.as_contract(contract_id, || env.storage().temporary().get_ttl(key))
+}
+
+fn instance_ttl(env: &Env, contract_id: &Address) -> u32 {
+    env.as_contract(contract_id, || env.storage().instance().get_ttl())
+}
+
+fn live_until(env: &Env, ttl: u32) -> u32 {
+    env.ledger().sequence() + ttl
+}
+
+#[test]
+fn test_put_persistent_applies_policy() {
+    let (env, id, client) = setup();
+    let key = symbol_short!("bal");
+    client.put_persistent(&key, &10);
+
+    let ttl = persistent_ttl(&env, &id, &key);
+    assert_eq!(ttl, PERSISTENT_EXTEND_TO);
+    assert_eq!(live_until(&env, ttl), START + PERSISTENT_EXTEND_TO);
+    assert_eq!(instance_ttl(&env, &id), INSTANCE_EXTEND_TO);
+}
+
+#[test]
+fn test_policy_only_extends_below_threshold() {
+    let (env, id, client) = setup();
+    let key = symbol_short!("bal");
+    client.put_persistent(&key, &10);
+    let first_live_until = START + PERSISTENT_EXTEND_TO;
+
+    // 20 days left is above the 7-day threshold: the write does not extend.
+    advance_ledgers(&env, 10 * DAY_IN_LEDGERS);
+    client.put_persistent(&key, &11);
+    let ttl = persistent_ttl(&env, &id, &key);
+    assert_eq!(ttl, 20 * DAY_IN_LEDGERS);
+    assert_eq!(live_until(&env, ttl), first_live_until);
+
+    // 6 days left is below it: the write extends back to 30 days.
+    advance_ledgers(&env, 14 * DAY_IN_LEDGERS);
+    client.put_persistent(&key, &12);
+    let ttl = persistent_ttl(&env, &id, &key);
+    assert_eq!(ttl, PERSISTENT_EXTEND_TO);
+    assert_eq!(
+        live_until(&env, ttl),
+        START + 24 * DAY_IN_LEDGERS + PERSISTENT_EXTEND_TO
+    );
+}
+
+#[test]
+fn test_bump_with_explicit_parameters() {
+    let (env, id, client) = setup();
+    let key = symbol_short!("bal");
+    client.put_persistent(&key, &10);
+
+    client.bump(&key, &(60 * DAY_IN_LEDGERS), &(90 * DAY_IN_LEDGERS));
+    assert_eq!(persistent_ttl(&env, &id, &key), 90 * DAY_IN_LEDGERS);
+
+    // Already above the threshold: no change.
+    client.bump(&key, &DAY_IN_LEDGERS, &(180 * DAY_IN_LEDGERS));
+    assert_eq!(persistent_ttl(&env, &id, &key), 90 * DAY_IN_LEDGERS);
+
+    assert_eq!(
+        client.try_bump(&key, &100, &99),
+        Err(Ok(TtlError::InvalidTtl))
+    );
+    assert_eq!(
+        client.try_bump(&key, &100, &(MAX_TTL + 1)),
+        Err(Ok(TtlError::InvalidTtl))
+    );
+    assert_eq!(
+        client.try_bump(&symbol_short!("nope"), &100, &200),
+        Err(Ok(TtlError::NotFound))
+    );
+}
+
+#[test]
+fn test_keepalive_bumps_existing_keys_and_instance() {
+    let (env, id, client) = setup();
+    let (a, b) = (symbol_short!("a"), symbol_short!("b"));
+    client.put_persistent(&a, &1);
+    client.put_persistent(&b, &2);
+
+    advance_ledgers(&env, 25 * DAY_IN_LEDGERS);
+    assert_eq!(persistent_ttl(&env, &id, &a), 5 * DAY_IN_LEDGERS);
+    assert_eq!(instance_ttl(&env, &id), 5 * DAY_IN_LEDGERS);
+
+    let found = client.keepalive(&vec![&env, a.clone(), b.clone(), symbol_short!("none")]);
+    assert_eq!(found, 2);
+
+    let expected = live_until(&env, PERSISTENT_EXTEND_TO);
+    assert_eq!(live_until(&env, persistent_ttl(&env, &id, &a)), expected);
+    assert_eq!(live_until(&env, persistent_ttl(&env, &id, &b)), expected);
+    assert_eq!(instance_ttl(&env, &id), INSTANCE_EXTEND_TO);
+}
+
+#[test]
+fn test_temporary_entry_expires_after_ttl() {
+    let (env, id, client) = setup();
+    let key = symbol_short!("quote");
+    client.put_temporary(&key, &42, &100);
+    assert_eq!(temporary_ttl(&env, &id, &key), 100);
+
+    advance_ledgers(&env, 100);
+    assert_eq!(client.get_temporary(&key), Some(42));
+
+    advance_ledgers(&env, 1);
+    assert_eq!(client.get_temporary(&key), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Storage, InternalError)")]
+fn test_persistent_entry_archived_when_left_out_of_keepalive() {
+    let (env, _id, client) = setup();
+    let (kept, dropped) = (symbol_short!("kept"), symbol_short!("dropped"));
+    client.put_persistent(&kept, &1);
+    client.put_persistent(&dropped, &2);
+
+    advance_ledgers(&env, 25 * DAY_IN_LEDGERS);
+    client.keepalive(&vec![&env, kept.clone()]);
+
+    advance_ledgers(&env, 5 * DAY_IN_LEDGERS + 1);
+    assert_eq!(client.get_persistent(&kept), Some(1));
+    // Archived, not deleted: the read aborts instead of returning `None`.
+    // The test host reports this as a storage error; on the network the
+    // transaction is rejected before the contract runs, until the entry
+    // is restored.
+    client.get_persistent(&dropped);
+}
+
+#[test]
+fn test_instance_size_taxes_every_call() {
+    let (env, _, small) = setup();
+    let large_instance = TtlContractClient::new(&env, &env.register_contract(None, TtlContract));
+    let large_persistent = TtlContractClient::new(&env, &env.register_contract(None, TtlContract));
+
+    let key = symbol_short!("bal");
+    for client in [&small, &large_instance, &large_persistent] {
+        client.put_persistent(&key, &1);
+    }
+    for i in 0..40u32 {
+        let filler = Symbol::new(&env, &std::format!("cfg_{i}"));
+        large_instance.put_instance(&filler, &(i as i128));
+        large_persistent.put_persistent(&filler, &(i as i128));
+    }
+
+    // Read the same single entry from each contract and compare the cost.
+    let cost_of = |client: &TtlContractClient| {
+        env.budget().reset_default();
+        client.get_persistent(&key);
+        env.budget().memory_bytes_cost()
+    };
+    let small_cost = cost_of(&small);
+    let instance_cost = cost_of(&large_instance);
+    let persistent_cost = cost_of(&large_persistent);
+
+    // 40 unrelated instance keys are loaded on every call...
+    assert!(instance_cost > small_cost);
+    // ...while 40 unrelated persistent keys are not.
+    assert!(persistent_cost < instance_cost);
+}
@@ -0,0 +1,153 @@
+//! # PRNG Randomness
+//!
+//! `env.prng()` gives every contract invocation its own ChaCha20 generator,
+//! seeded by the network. This recipe uses it three ways:
+//!
+//! - `roll`: a bounded die roll straight from the PRNG;
+//! - `shuffle`: an in-place Fisher–Yates shuffle of a `Vec`;
+//! - `commit_seed` + `fair_roll`: a roll that the caller cannot retry into a
+//!   better result.
+//!
+//! ## Why `roll` is manipulable
+//!
+//! The network seed is hard for a user to *choose*, but every transaction
+//! can *observe* its own outcome before it commits. A caller can wrap
+//! `roll` in a contract that panics unless it likes the result; the failed
+//! attempts roll back and cost only a fee, so the caller retries until it
+//! wins (see `Cheater` in the tests). Validators, who build the transaction
+//! set the seed is derived from, have even more influence.
+//!
+//! ## How `fair_roll` mitigates it
+//!
+//! This builds on the commit-reveal recipe (`11-commit-reveal`):
+//!
+//! 1. `commit_seed` stores `sha256(seed)` for a secret seed chosen by the
+//!    player, and draws the contract's own 32 bytes of PRNG entropy *now*,
+//!    while the player's seed is still hidden. The entropy is stored but not
+//!    exposed by any getter, so another contract cannot read it in the same
+//!    transaction and abort the commit.
+//! 2. `fair_roll` checks the revealed seed against the commitment, reseeds
+//!    the PRNG with `sha256(seed ‖ entropy)` via `prng().seed`, and rolls.
+//!
+//! By the time the result can be computed, both inputs are fixed: retrying
+//! `fair_roll` gives the same number, and whoever biased the entropy at
+//! commit time did not know the seed it would be mixed with. A player can
+//! still refuse to reveal a losing roll. Real games pair this with a stake
+//! that is forfeited if the reveal does not arrive in time.
+//!
+//! The PRNG seed is public once a ledger closes. Never use it for secrets.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env,
+    Vec,
+};
+
+/// Faces on the die; rolls are in `1..=DIE_SIDES`.
+pub const DIE_SIDES: u64 = 6;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Commit(Address),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Commitment {
+    pub hash: BytesN<32>,
+    pub entropy: BytesN<32>,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RandomError {
+    /// The player already has an unrevealed commitment.
+    AlreadyCommitted = 1,
+    /// `fair_roll` was called without a commitment.
+    NoCommitment = 2,
+    /// `sha256(seed)` does not match the stored commitment.
+    SeedMismatch = 3,
+}
+
+#[contract]
+pub struct PrngContract;
+
+#[contractimpl]
+impl PrngContract {
+    /// Roll a die for `player` using the invocation's PRNG directly.
+    ///
+    /// **Manipulable:** the caller sees the result before the transaction
+    /// commits and can abort it. Fine for cosmetics, not for stakes.
+    pub fn roll(env: Env, player: Address) -> u64 {
+        player.require_auth();
+        let value = env.prng().gen_range(1..=DIE_SIDES);
+        env.events().publish(
+            (symbol_short!("prng"), symbol_short!("roll"), player),
+            value,
+        );
+        value
+    }
+
+    /// Return `items` in a random order.
+    pub fn shuffle(env: Env, items: Vec<u32>) -> Vec<u32> {
+        let mut items = items;
+        env.prng().shuffle(&mut items);
+        items
+    }
+
+    /// Commit to `sha256(seed)` for a secret 32-byte seed, and lock in the
+    /// contract's share of the entropy.
+    pub fn commit_seed(env: Env, player: Address, hash: BytesN<32>) -> Result<(), RandomError> {
+        player.require_auth();
+        let key = DataKey::Commit(player);
+        if env.storage().persistent().has(&key) {
+            return Err(RandomError::AlreadyCommitted);
+        }
+        let commitment = Commitment {
+            hash,
+            entropy: env.prng().gen(),
+        };
+        env.storage().persistent().set(&key, &commitment);
+        Ok(())
+    }
+
+    /// Reveal the committed seed and roll with a PRNG reseeded from
+    /// `sha256(seed ‖ entropy)`. Consumes the commitment.
+    pub fn fair_roll(env: Env, player: Address, seed: BytesN<32>) -> Result<u64, RandomError> {
+        player.require_auth();
+        let key = DataKey::Commit(player.clone());
+        let commitment: Commitment = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RandomError::NoCommitment)?;
+
+        let seed = Bytes::from(seed);
+        if env.crypto().sha256(&seed).to_bytes() != commitment.hash {
+            return Err(RandomError::SeedMismatch);
+        }
+        env.storage().persistent().remove(&key);
+
+        let mut mixed = seed;
+        mixed.append(&commitment.entropy.into());
+        env.prng()
+            .seed(env.crypto().sha256(&mixed).to_bytes().into());
+
+        let value = env.prng().gen_range(1..=DIE_SIDES);
+        env.events().publish(
+            (symbol_short!("prng"), symbol_short!("fair"), player),
+            value,
+        );
+        Ok(value)
+    }
+
+    /// The commitment hash for `seed`, for clients that cannot hash locally.
+    pub fn seed_hash(env: Env, seed: BytesN<32>) -> BytesN<32> {
+        env.crypto().sha256(&seed.into()).to_bytes()
+    }
+}
+
+mod test;
@@ -0,0 +1,174 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::{pin_prng_seed, TestEnv};
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig},
+    vec, Address, Env,
+};
+
+/// Calls `roll` and aborts unless it gets a six. Every failed attempt is
+/// rolled back, so the caller only ever pays for retries.
+#[contract]
+pub struct Cheater;
+
+#[contractimpl]
+impl Cheater {
+    pub fn roll_six(env: Env, game: Address) -> u64 {
+        let me = env.current_contract_address();
+        let value = PrngContractClient::new(&env, &game).roll(&me);
+        if value != DIE_SIDES {
+            panic!("not a six");
+        }
+        value
+    }
+
+    /// The same trick against a committed `fair_roll`.
+    pub fn fair_roll_six(env: Env, game: Address, seed: BytesN<32>) -> u64 {
+        let me = env.current_contract_address();
+        let value = PrngContractClient::new(&env, &game).fair_roll(&me, &seed);
+        if value != DIE_SIDES {
+            panic!("not a six");
+        }
+        value
+    }
+}
+
+fn setup() -> (Env, PrngContractClient<'static>) {
+    let (env, _, client) = TestEnv::with_contract(PrngContract, PrngContractClient::new);
+    (env, client)
+}
+
+fn rolls(client: &PrngContractClient, player: &Address, n: usize) -> std::vec::Vec<u64> {
+    (0..n).map(|_| client.roll(player)).collect()
+}
+
+fn secret(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+#[test]
+fn test_pinned_seed_is_deterministic() {
+    let run = |seed: [u8; 32]| {
+        let (env, client) = setup();
+        pin_prng_seed(&env, seed);
+        let player = Address::generate(&env);
+        rolls(&client, &player, 20)
+    };
+    assert_eq!(run([7; 32]), run([7; 32]));
+}
+
+#[test]
+fn test_different_seeds_produce_different_sequences() {
+    let run = |seed: [u8; 32]| {
+        let (env, client) = setup();
+        pin_prng_seed(&env, seed);
+        let player = Address::generate(&env);
+        rolls(&client, &player, 20)
+    };
+    assert_ne!(run([1; 32]), run([2; 32]));
+}
+
+#[test]
+fn test_rolls_respect_bounds() {
+    let (mut env, client) = setup();
+    // A thousand roll events would make a multi-megabyte snapshot.
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    pin_prng_seed(&env, [3; 32]);
+    let player = Address::generate(&env);
+    // The test budget covers one transaction; this is a thousand.
+    env.budget().reset_unlimited();
+
+    let mut seen = [0u32; DIE_SIDES as usize];
+    for value in rolls(&client, &player, 1_000) {
+        assert!((1..=DIE_SIDES).contains(&value), "out of range: {value}");
+        seen[(value - 1) as usize] += 1;
+    }
+    // Every face turns up; 1000 rolls of a fair die give ~167 each.
+    assert!(seen.iter().all(|&count| count > 100), "{seen:?}");
+}
+
+#[test]
+fn test_shuffle_is_a_permutation() {
+    let (env, client) = setup();
+    pin_prng_seed(&env, [4; 32]);
+    let items = vec![&env, 1u32, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+    let shuffled = client.shuffle(&items);
+    assert_ne!(shuffled, items);
+
+    let mut sorted: std::vec::Vec<u32> = shuffled.iter().collect();
+    sorted.sort();
+    assert_eq!(sorted, items.iter().collect::<std::vec::Vec<_>>());
+}
+
+#[test]
+fn test_retrying_naive_roll_always_wins() {
+    let (env, client) = setup();
+    pin_prng_seed(&env, [5; 32]);
+    let cheater = CheaterClient::new(&env, &env.register_contract(None, Cheater));
+
+    // Each aborted attempt leaves no trace; keep going until a six lands.
+    let mut attempts = 0;
+    let value = loop {
+        attempts += 1;
+        if let Ok(Ok(value)) = cheater.try_roll_six(&client.address) {
+            break value;
+        }
+    };
+    assert_eq!(value, DIE_SIDES);
+    assert!(attempts <= 100);
+}
+
+#[test]
+fn test_fair_roll_is_fixed_at_commit_time() {
+    let (env, client) = setup();
+    pin_prng_seed(&env, [5; 32]);
+    let cheater_id = env.register_contract(None, Cheater);
+    let cheater = CheaterClient::new(&env, &cheater_id);
+
+    let seed = secret(&env, 9);
+    client.commit_seed(&cheater_id, &client.seed_hash(&seed));
+
+    // The trick that beat `roll` gets nowhere: the commitment survives each
+    // abort, and every retry lands on the same result.
+    for _ in 0..20 {
+        assert!(cheater.try_fair_roll_six(&client.address, &seed).is_err());
+    }
+    let value = client.fair_roll(&cheater_id, &seed);
+    assert!((1..DIE_SIDES).contains(&value));
+}
+
+#[test]
+fn test_fair_roll_consumes_commitment() {
+    let (env, client) = setup();
+    let player = Address::generate(&env);
+    let seed = secret(&env, 9);
+    client.commit_seed(&player, &client.seed_hash(&seed));
+
+    assert_eq!(
+        client.try_commit_seed(&player, &client.seed_hash(&seed)),
+        Err(Ok(RandomError::AlreadyCommitted))
+    );
+    client.fair_roll(&player, &seed);
+    assert_eq!(
+        client.try_fair_roll(&player, &seed),
+        Err(Ok(RandomError::NoCommitment))
+    );
+}
+
+#[test]
+fn test_fair_roll_rejects_wrong_seed() {
+    let (env, client) = setup();
+    let player = Address::generate(&env);
+    client.commit_seed(&player, &client.seed_hash(&secret(&env, 9)));
+
+    assert_eq!(
+        client.try_fair_roll(&player, &secret(&env, 8)),
+        Err(Ok(RandomError::SeedMismatch))
+    );
+}
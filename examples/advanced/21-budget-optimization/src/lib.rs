@@ -0,0 +1,117 @@
+//! # Budget Measurement and Optimization
+//!
+//! Every invocation runs against a CPU-instruction and memory budget. This
+//! recipe implements the same logical operation two ways and lets the
+//! tests measure the difference instead of asserting it:
+//!
+//! - **Sum**: `sum_loop` reads every element on each call; `sum_cached`
+//!   reads a running total that every append keeps up to date, moving an
+//!   O(n) cost from reads to an O(1) cost on writes.
+//! - **Read all**: `get_all_blob` loads one `Vec<i128>` stored under a
+//!   single key; `get_all_entries` loads one persistent entry per element.
+//! - **Append many**: `push_entries` appends a batch in one call, reading
+//!   and writing the counter and total once; calling `push_entry` per value
+//!   pays for those entries, and for an invocation, every time.
+//!
+//! The blob layout is not free: every append re-encodes the whole list,
+//! and write fees grow with the bytes written. Those fees are charged per
+//! ledger entry size, outside the CPU and memory budget, so they do not
+//! show up in `env.budget()`. Use `simulateTransaction` to compare them.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Env, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The whole list as one value.
+    Blob,
+    /// Number of elements stored as entries.
+    Len,
+    /// One element, by index.
+    Item(u32),
+    /// Running total of every value appended to the entries list.
+    Total,
+}
+
+#[contract]
+pub struct BudgetContract;
+
+#[contractimpl]
+impl BudgetContract {
+    // ==================== BLOB ====================
+
+    /// Append to the blob list. Cost grows with the list.
+    pub fn push_blob(env: Env, value: i128) {
+        let storage = env.storage().persistent();
+        let mut list: Vec<i128> = storage.get(&DataKey::Blob).unwrap_or(Vec::new(&env));
+        list.push_back(value);
+        storage.set(&DataKey::Blob, &list);
+    }
+
+    pub fn get_all_blob(env: Env) -> Vec<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Blob)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ==================== ENTRIES ====================
+
+    /// Append to the entries list and update the running total. Cost is
+    /// constant in the list size.
+    pub fn push_entry(env: Env, value: i128) {
+        let storage = env.storage().persistent();
+        let len: u32 = storage.get(&DataKey::Len).unwrap_or(0);
+        let total: i128 = storage.get(&DataKey::Total).unwrap_or(0);
+        storage.set(&DataKey::Item(len), &value);
+        storage.set(&DataKey::Len, &(len + 1));
+        storage.set(&DataKey::Total, &(total + value));
+    }
+
+    /// Append many values in one call: the counter and total are read and
+    /// written once, not once per value.
+    pub fn push_entries(env: Env, values: Vec<i128>) {
+        let storage = env.storage().persistent();
+        let mut len: u32 = storage.get(&DataKey::Len).unwrap_or(0);
+        let mut total: i128 = storage.get(&DataKey::Total).unwrap_or(0);
+        for value in values.iter() {
+            storage.set(&DataKey::Item(len), &value);
+            len += 1;
+            total += value;
+        }
+        storage.set(&DataKey::Len, &len);
+        storage.set(&DataKey::Total, &total);
+    }
+
+    pub fn get_all_entries(env: Env) -> Vec<i128> {
+        let storage = env.storage().persistent();
+        let len: u32 = storage.get(&DataKey::Len).unwrap_or(0);
+        let mut list = Vec::new(&env);
+        for i in 0..len {
+            list.push_back(storage.get(&DataKey::Item(i)).unwrap());
+        }
+        list
+    }
+
+    // ==================== SUM ====================
+
+    /// Sum the entries list by reading every element.
+    pub fn sum_loop(env: Env) -> i128 {
+        let storage = env.storage().persistent();
+        let len: u32 = storage.get(&DataKey::Len).unwrap_or(0);
+        let mut sum = 0;
+        for i in 0..len {
+            sum += storage.get::<_, i128>(&DataKey::Item(i)).unwrap();
+        }
+        sum
+    }
+
+    /// Read the total maintained by `push_entry`.
+    pub fn sum_cached(env: Env) -> i128 {
+        env.storage().persistent().get(&DataKey::Total).unwrap_or(0)
+    }
+}
+
+mod test;
@@ -0,0 +1,131 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::TestEnv;
+use soroban_sdk::{Env, Vec};
+
+// Recorded costs, from `cargo test -p budget-optimization -- --nocapture`.
+//
+// Each benchmark asserts that both variants stay within `TOLERANCE_PCT` of
+// their recorded CPU cost, and that the expensive one costs at least
+// `MARGIN`x the cheap one. After an SDK upgrade or a deliberate change to
+// the contract, rerun with `--nocapture` and copy the printed numbers here.
+//
+// These are native (non-Wasm) measurements: absolute numbers understate
+// on-chain cost, but the ratios are what the recipe is about.
+
+/// Elements in the list before each measurement.
+const LIST_LEN: u32 = 50;
+/// The expensive variant must cost at least this many times the cheap one.
+const MARGIN: u64 = 2;
+/// How far a measurement may drift from its recorded number.
+const TOLERANCE_PCT: u64 = 10;
+
+const SUM_CACHED_CPU: u64 = 22_923;
+const SUM_LOOP_CPU: u64 = 533_596;
+const READ_ALL_BLOB_CPU: u64 = 24_607;
+const READ_ALL_ENTRIES_CPU: u64 = 595_429;
+const APPEND_BATCH_CPU: u64 = 1_155_034;
+const APPEND_SINGLE_CPU: u64 = 2_546_630;
+
+/// Values appended in the batching comparison.
+const BATCH_LEN: u32 = 20;
+
+#[derive(Debug, Clone, Copy)]
+struct Cost {
+    cpu: u64,
+    mem: u64,
+}
+
+/// Run `f` against a freshly reset budget and return what it consumed.
+fn reset_and_measure<R>(env: &Env, f: impl FnOnce() -> R) -> Cost {
+    env.budget().reset_default();
+    f();
+    Cost {
+        cpu: env.budget().cpu_instruction_cost(),
+        mem: env.budget().memory_bytes_cost(),
+    }
+}
+
+/// A contract holding `LIST_LEN` elements in both layouts.
+fn setup() -> (Env, BudgetContractClient<'static>) {
+    let (env, _, client) = TestEnv::with_contract(BudgetContract, BudgetContractClient::new);
+    env.budget().reset_unlimited();
+    for i in 0..LIST_LEN {
+        client.push_blob(&(i as i128));
+        client.push_entry(&(i as i128));
+    }
+    (env, client)
+}
+
+#[track_caller]
+fn assert_recorded(name: &str, measured: Cost, recorded: u64) {
+    let low = recorded * (100 - TOLERANCE_PCT) / 100;
+    let high = recorded * (100 + TOLERANCE_PCT) / 100;
+    assert!(
+        (low..=high).contains(&measured.cpu),
+        "{name}: {} cpu is more than {TOLERANCE_PCT}% from recorded {recorded}",
+        measured.cpu
+    );
+}
+
+#[track_caller]
+fn assert_cheaper(name: &str, cheap: (Cost, u64), expensive: (Cost, u64)) {
+    std::println!("{name}: cheap {:?}, expensive {:?}", cheap.0, expensive.0);
+    assert_recorded(name, cheap.0, cheap.1);
+    assert_recorded(name, expensive.0, expensive.1);
+    assert!(
+        expensive.0.cpu >= cheap.0.cpu * MARGIN,
+        "{name}: expected at least {MARGIN}x, got {} vs {}",
+        expensive.0.cpu,
+        cheap.0.cpu
+    );
+    assert!(expensive.0.mem > cheap.0.mem, "{name}: memory did not drop");
+}
+
+#[test]
+fn test_sum_cached_beats_sum_loop() {
+    let (env, client) = setup();
+    let cached = reset_and_measure(&env, || client.sum_cached());
+    let looped = reset_and_measure(&env, || client.sum_loop());
+    assert_eq!(client.sum_cached(), client.sum_loop());
+    assert_cheaper("sum", (cached, SUM_CACHED_CPU), (looped, SUM_LOOP_CPU));
+}
+
+#[test]
+fn test_read_all_blob_beats_read_all_entries() {
+    let (env, client) = setup();
+    let blob = reset_and_measure(&env, || client.get_all_blob());
+    let entries = reset_and_measure(&env, || client.get_all_entries());
+    assert_eq!(client.get_all_blob(), client.get_all_entries());
+    assert_cheaper(
+        "read all",
+        (blob, READ_ALL_BLOB_CPU),
+        (entries, READ_ALL_ENTRIES_CPU),
+    );
+}
+
+#[test]
+fn test_batch_append_beats_single_appends() {
+    let (env, batched) = setup();
+    let (single_env, single) = setup();
+    let values: std::vec::Vec<i128> = (0..BATCH_LEN as i128).collect();
+
+    let batch = reset_and_measure(&env, || {
+        batched.push_entries(&Vec::from_slice(&env, &values))
+    });
+    let one_by_one = reset_and_measure(&single_env, || {
+        for value in &values {
+            single.push_entry(value);
+        }
+    });
+    assert_eq!(batched.sum_cached(), single.sum_cached());
+    assert_eq!(batched.sum_loop(), single.sum_loop());
+    assert_cheaper(
+        "append batch",
+        (batch, APPEND_BATCH_CPU),
+        (one_by_one, APPEND_SINGLE_CPU),
+    );
+}
@@ -0,0 +1,224 @@
+//! # Custom Account (Smart Wallet)
+//!
+//! A contract becomes an *account* by implementing `__check_auth`. Whenever
+//! another contract calls `require_auth()` on the wallet's address, the host
+//! calls the wallet's `__check_auth` with:
+//!
+//! - `signature_payload`: the hash the signers signed, which commits to the
+//!   network, a nonce, an expiration ledger and the whole invocation tree;
+//! - `signatures`: whatever the wallet defines, here a list of ed25519
+//!   signatures;
+//! - `auth_contexts`: the calls being authorized, for per-call policies.
+//!
+//! This wallet holds weighted ed25519 signers and a threshold. A payload is
+//! authorized when the weights of its valid signatures reach the threshold.
+//!
+//! The signer set administers itself: `add_signer`, `remove_signer` and
+//! `set_threshold` call `require_auth()` on the wallet's own address, so
+//! changing the signers needs a threshold of the current signers.
+//!
+//! ## Replay
+//!
+//! On the network, the host rejects a reused nonce before `__check_auth`
+//! runs. The wallet also records each payload it accepts in temporary
+//! storage for `REPLAY_TTL` ledgers and rejects it if seen again, so the
+//! check holds even if `__check_auth` is reached some other way.
+
+#![no_std]
+
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl, contracttype,
+    crypto::Hash,
+    BytesN, Env, Vec,
+};
+
+/// How long an accepted payload is remembered, in ledgers (about a day).
+pub const REPLAY_TTL: u32 = 17_280;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Weight of a signer, by ed25519 public key.
+    Signer(BytesN<32>),
+    /// Sum of all signer weights.
+    TotalWeight,
+    Threshold,
+    /// A payload already accepted by `__check_auth`.
+    Used(BytesN<32>),
+}
+
+/// One ed25519 signature over the signature payload.
+#[contracttype]
+#[derive(Clone)]
+pub struct Signature {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AccError {
+    AlreadyInitialized = 1,
+    /// The threshold is zero or above the total signer weight.
+    InvalidThreshold = 2,
+    /// A signature is from a key that is not a signer.
+    UnknownSigner = 3,
+    /// Signatures must be sorted by public key, with no duplicates.
+    UnsortedSignatures = 4,
+    /// The valid signatures do not reach the threshold.
+    ThresholdNotMet = 5,
+    /// The payload was already accepted once.
+    Replayed = 6,
+    SignerExists = 7,
+}
+
+#[contract]
+pub struct SmartWallet;
+
+#[contractimpl]
+impl SmartWallet {
+    /// Set the initial signers and threshold. Deploy and initialize in the
+    /// same transaction, or anyone can claim the wallet first.
+    pub fn init(env: Env, signers: Vec<(BytesN<32>, u32)>, threshold: u32) -> Result<(), AccError> {
+        if env.storage().instance().has(&DataKey::Threshold) {
+            return Err(AccError::AlreadyInitialized);
+        }
+        let mut total = 0;
+        for (public_key, weight) in signers.iter() {
+            let key = DataKey::Signer(public_key);
+            if env.storage().persistent().has(&key) {
+                return Err(AccError::SignerExists);
+            }
+            env.storage().persistent().set(&key, &weight);
+            total += weight;
+        }
+        if threshold == 0 || threshold > total {
+            return Err(AccError::InvalidThreshold);
+        }
+        env.storage().instance().set(&DataKey::TotalWeight, &total);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+        Ok(())
+    }
+
+    /// Add a signer. Requires the wallet's own authorization.
+    pub fn add_signer(env: Env, public_key: BytesN<32>, weight: u32) -> Result<(), AccError> {
+        env.current_contract_address().require_auth();
+        let key = DataKey::Signer(public_key);
+        if env.storage().persistent().has(&key) {
+            return Err(AccError::SignerExists);
+        }
+        env.storage().persistent().set(&key, &weight);
+        let total = Self::total_weight(&env) + weight;
+        env.storage().instance().set(&DataKey::TotalWeight, &total);
+        Ok(())
+    }
+
+    /// Remove a signer. Requires the wallet's own authorization, and fails
+    /// if the remaining signers could no longer reach the threshold.
+    pub fn remove_signer(env: Env, public_key: BytesN<32>) -> Result<(), AccError> {
+        env.current_contract_address().require_auth();
+        let key = DataKey::Signer(public_key);
+        let weight: u32 = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(AccError::UnknownSigner)?;
+        let total = Self::total_weight(&env) - weight;
+        if total < Self::threshold(env.clone()) {
+            return Err(AccError::InvalidThreshold);
+        }
+        env.storage().persistent().remove(&key);
+        env.storage().instance().set(&DataKey::TotalWeight, &total);
+        Ok(())
+    }
+
+    /// Change the threshold. Requires the wallet's own authorization.
+    pub fn set_threshold(env: Env, threshold: u32) -> Result<(), AccError> {
+        env.current_contract_address().require_auth();
+        if threshold == 0 || threshold > Self::total_weight(&env) {
+            return Err(AccError::InvalidThreshold);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+        Ok(())
+    }
+
+    pub fn threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .unwrap_or(0)
+    }
+
+    pub fn weight_of(env: Env, public_key: BytesN<32>) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::Signer(public_key))
+    }
+}
+
+impl SmartWallet {
+    fn total_weight(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalWeight)
+            .unwrap_or(0)
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for SmartWallet {
+    type Signature = Vec<Signature>;
+    type Error = AccError;
+
+    /// Authorize `signature_payload` if the signatures' weights reach the
+    /// threshold. An invalid ed25519 signature aborts the call.
+    ///
+    /// Every call is held to the same threshold. A wallet with spending
+    /// limits or per-contract rules would inspect `auth_contexts` here.
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signatures: Vec<Signature>,
+        _auth_contexts: Vec<Context>,
+    ) -> Result<(), AccError> {
+        let payload: BytesN<32> = signature_payload.to_bytes();
+        let used = DataKey::Used(payload.clone());
+        if env.storage().temporary().has(&used) {
+            return Err(AccError::Replayed);
+        }
+
+        let mut weight = 0;
+        let mut previous: Option<BytesN<32>> = None;
+        for sig in signatures.iter() {
+            if let Some(previous) = previous {
+                if previous >= sig.public_key {
+                    return Err(AccError::UnsortedSignatures);
+                }
+            }
+            let signer_weight: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Signer(sig.public_key.clone()))
+                .ok_or(AccError::UnknownSigner)?;
+            env.crypto()
+                .ed25519_verify(&sig.public_key, &payload.clone().into(), &sig.signature);
+            weight += signer_weight;
+            previous = Some(sig.public_key);
+        }
+        if weight < Self::threshold(env.clone()) {
+            return Err(AccError::ThresholdNotMet);
+        }
+
+        env.storage().temporary().set(&used, &());
+        env.storage()
+            .temporary()
+            .extend_ttl(&used, REPLAY_TTL, REPLAY_TTL);
+        Ok(())
+    }
+}
+
+mod test;
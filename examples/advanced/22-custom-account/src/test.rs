@@ -0,0 +1,310 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use authentication::{AuthContract, AuthContractClient};
+use ed25519_dalek::{Signer as _, SigningKey};
+use soroban_sdk::{
+    auth::ContractContext,
+    symbol_short,
+    testutils::Address as _,
+    vec,
+    xdr::{
+        self, HashIdPreimage, HashIdPreimageSorobanAuthorization, InvokeContractArgs, Limits,
+        ScSymbol, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry,
+        SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials, WriteXdr,
+    },
+    Address, Bytes, IntoVal, TryFromVal, Val,
+};
+
+struct Fixture {
+    env: Env,
+    wallet: Address,
+    client: SmartWalletClient<'static>,
+    /// Weight 1.
+    alice: SigningKey,
+    /// Weight 1.
+    bob: SigningKey,
+    /// Weight 2.
+    carol: SigningKey,
+}
+
+/// A wallet with signers of weight 1, 1 and 2, and a threshold of 2.
+fn setup() -> Fixture {
+    let env = Env::default();
+    let wallet = env.register_contract(None, SmartWallet);
+    let client = SmartWalletClient::new(&env, &wallet);
+    let (alice, bob, carol) = (key(1), key(2), key(3));
+    client.init(
+        &vec![
+            &env,
+            (public_key(&env, &alice), 1),
+            (public_key(&env, &bob), 1),
+            (public_key(&env, &carol), 2),
+        ],
+        &2,
+    );
+    Fixture {
+        env,
+        wallet,
+        client,
+        alice,
+        bob,
+        carol,
+    }
+}
+
+fn key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+/// Sign `payload` with each key, sorted by public key as the wallet requires.
+fn sign(env: &Env, payload: &BytesN<32>, keys: &[&SigningKey]) -> Vec<Signature> {
+    let mut keys = keys.to_vec();
+    keys.sort_by_key(|k| k.verifying_key().to_bytes());
+    let mut signatures = Vec::new(env);
+    for k in keys {
+        signatures.push_back(Signature {
+            public_key: public_key(env, k),
+            signature: BytesN::from_array(env, &k.sign(&payload.to_array()).to_bytes()),
+        });
+    }
+    signatures
+}
+
+/// Call `__check_auth` directly, as the host would for `contexts`.
+fn check(
+    f: &Fixture,
+    payload: &BytesN<32>,
+    keys: &[&SigningKey],
+) -> Result<(), Result<AccError, soroban_sdk::InvokeError>> {
+    let contexts = vec![
+        &f.env,
+        Context::Contract(ContractContext {
+            contract: Address::generate(&f.env),
+            fn_name: symbol_short!("transfer"),
+            args: ().into_val(&f.env),
+        }),
+    ];
+    f.env.try_invoke_contract_check_auth::<AccError>(
+        &f.wallet,
+        payload,
+        sign(&f.env, payload, keys).into_val(&f.env),
+        &contexts,
+    )
+}
+
+fn payload(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+/// Build an authorization entry for `wallet` calling `contract.fn_name(args)`,
+/// signed with `keys` over the same payload the host will compute, and
+/// install it with `set_auths`. `__check_auth` runs for real.
+fn authorize(
+    f: &Fixture,
+    keys: &[&SigningKey],
+    nonce: i64,
+    contract: &Address,
+    fn_name: &str,
+    args: Vec<Val>,
+) {
+    let env = &f.env;
+    let expiration = env.ledger().sequence() + 100;
+    let invocation = SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address: contract.into(),
+            function_name: ScSymbol(fn_name.try_into().unwrap()),
+            args: args
+                .iter()
+                .map(|v| ScVal::try_from_val(env, &v).unwrap())
+                .collect::<std::vec::Vec<_>>()
+                .try_into()
+                .unwrap(),
+        }),
+        sub_invocations: Default::default(),
+    };
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: xdr::Hash(env.ledger().network_id().to_array()),
+        nonce,
+        signature_expiration_ledger: expiration,
+        invocation: invocation.clone(),
+    });
+    let xdr = preimage.to_xdr(Limits::none()).unwrap();
+    let payload = env
+        .crypto()
+        .sha256(&Bytes::from_slice(env, &xdr))
+        .to_bytes();
+    let signature = ScVal::try_from_val(env, &sign(env, &payload, keys).to_val()).unwrap();
+
+    env.set_auths(&[SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: (&f.wallet).into(),
+            nonce,
+            signature_expiration_ledger: expiration,
+            signature,
+        }),
+        root_invocation: invocation,
+    }]);
+}
+
+#[test]
+fn test_threshold_met() {
+    let f = setup();
+    assert_eq!(check(&f, &payload(&f.env, 1), &[&f.alice, &f.bob]), Ok(()));
+    // One heavier signer is enough on its own.
+    assert_eq!(check(&f, &payload(&f.env, 2), &[&f.carol]), Ok(()));
+}
+
+#[test]
+fn test_threshold_not_met() {
+    let f = setup();
+    assert_eq!(
+        check(&f, &payload(&f.env, 1), &[&f.alice]),
+        Err(Ok(AccError::ThresholdNotMet))
+    );
+    assert_eq!(
+        check(&f, &payload(&f.env, 1), &[]),
+        Err(Ok(AccError::ThresholdNotMet))
+    );
+}
+
+#[test]
+fn test_unknown_signer_rejected() {
+    let f = setup();
+    let mallory = key(9);
+    assert_eq!(
+        check(&f, &payload(&f.env, 1), &[&f.alice, &mallory]),
+        Err(Ok(AccError::UnknownSigner))
+    );
+}
+
+#[test]
+fn test_duplicate_signature_rejected() {
+    let f = setup();
+    let p = payload(&f.env, 1);
+    let mut signatures = sign(&f.env, &p, &[&f.alice]);
+    signatures.push_back(signatures.get(0).unwrap());
+    assert_eq!(
+        f.env.try_invoke_contract_check_auth::<AccError>(
+            &f.wallet,
+            &p,
+            signatures.into_val(&f.env),
+            &Vec::new(&f.env),
+        ),
+        Err(Ok(AccError::UnsortedSignatures))
+    );
+}
+
+#[test]
+fn test_forged_signature_aborts() {
+    let f = setup();
+    let p = payload(&f.env, 1);
+    // Carol's signature over a different payload.
+    let mut signatures = sign(&f.env, &payload(&f.env, 2), &[&f.carol]);
+    signatures.set(
+        0,
+        Signature {
+            public_key: public_key(&f.env, &f.carol),
+            signature: signatures.get(0).unwrap().signature,
+        },
+    );
+    let result = f.env.try_invoke_contract_check_auth::<AccError>(
+        &f.wallet,
+        &p,
+        signatures.into_val(&f.env),
+        &Vec::new(&f.env),
+    );
+    assert!(matches!(result, Err(Err(_))));
+}
+
+#[test]
+fn test_replayed_payload_rejected() {
+    let f = setup();
+    let p = payload(&f.env, 1);
+    assert_eq!(check(&f, &p, &[&f.carol]), Ok(()));
+    assert_eq!(check(&f, &p, &[&f.carol]), Err(Ok(AccError::Replayed)));
+}
+
+#[test]
+fn test_wallet_as_transfer_sender() {
+    let f = setup();
+    let auth_id = f.env.register_contract(None, AuthContract);
+    let auth = AuthContractClient::new(&f.env, &auth_id);
+    let admin = Address::generate(&f.env);
+    let recipient = Address::generate(&f.env);
+
+    f.env.mock_all_auths();
+    auth.initialize(&admin);
+    auth.set_balance(&admin, &f.wallet, &100);
+
+    // Alice alone cannot move funds...
+    let args: Vec<Val> = (&f.wallet, &recipient, 40_i128).into_val(&f.env);
+    authorize(&f, &[&f.alice], 1, &auth_id, "transfer", args.clone());
+    assert!(auth.try_transfer(&f.wallet, &recipient, &40).is_err());
+
+    // ...but Alice and Bob together can.
+    authorize(&f, &[&f.alice, &f.bob], 2, &auth_id, "transfer", args);
+    auth.transfer(&f.wallet, &recipient, &40);
+    assert_eq!(auth.get_balance(&f.wallet), 60);
+    assert_eq!(auth.get_balance(&recipient), 40);
+
+    // The same signed entry cannot be submitted twice.
+    assert!(auth.try_transfer(&f.wallet, &recipient, &40).is_err());
+    assert_eq!(auth.get_balance(&f.wallet), 60);
+}
+
+#[test]
+fn test_signer_rotation_requires_wallet_auth() {
+    let f = setup();
+    let dave = key(4);
+    let dave_pk = public_key(&f.env, &dave);
+    let alice_pk = public_key(&f.env, &f.alice);
+
+    // Without the wallet's own authorization, nothing changes.
+    assert!(f.client.try_add_signer(&dave_pk, &2).is_err());
+    // Below the threshold does not count as the wallet's authorization.
+    let args: Vec<Val> = (dave_pk.clone(), 2_u32).into_val(&f.env);
+    authorize(&f, &[&f.alice], 1, &f.wallet, "add_signer", args.clone());
+    assert!(f.client.try_add_signer(&dave_pk, &2).is_err());
+    assert_eq!(f.client.weight_of(&dave_pk), None);
+
+    authorize(&f, &[&f.alice, &f.bob], 2, &f.wallet, "add_signer", args);
+    f.client.add_signer(&dave_pk, &2);
+    assert_eq!(f.client.weight_of(&dave_pk), Some(2));
+
+    // The new signer can now act alone; rotate Alice out.
+    let args: Vec<Val> = (alice_pk.clone(),).into_val(&f.env);
+    authorize(&f, &[&dave], 3, &f.wallet, "remove_signer", args);
+    f.client.remove_signer(&alice_pk);
+
+    assert_eq!(check(&f, &payload(&f.env, 1), &[&dave]), Ok(()));
+    assert_eq!(
+        check(&f, &payload(&f.env, 2), &[&f.alice, &f.bob]),
+        Err(Ok(AccError::UnknownSigner))
+    );
+}
+
+#[test]
+fn test_threshold_must_stay_reachable() {
+    let f = setup();
+    f.env.mock_all_auths();
+    assert_eq!(
+        f.client.try_set_threshold(&5),
+        Err(Ok(AccError::InvalidThreshold))
+    );
+    f.client.set_threshold(&4);
+    assert_eq!(
+        f.client.try_remove_signer(&public_key(&f.env, &f.alice)),
+        Err(Ok(AccError::InvalidThreshold))
+    );
+    assert_eq!(
+        f.client.try_init(&Vec::new(&f.env), &1),
+        Err(Ok(AccError::AlreadyInitialized))
+    );
+}
@@ -0,0 +1,300 @@
+//! # Payment Splitter
+//!
+//! Anyone deposits a token into the splitter; each payee pulls their share
+//! whenever they like. The admin fixes the payees and integer share weights.
+//!
+//! ## Accounting
+//!
+//! The contract never tracks per-payee balances. It tracks two totals and
+//! what each payee has already taken:
+//!
+//! ```text
+//! entitled(p)   = floor(total_received * shares(p) / total_shares)
+//! releasable(p) = entitled(p) - released(p)
+//! ```
+//!
+//! Because `entitled` is computed from the cumulative total, the remainder
+//! lost to `floor` on one deposit is not lost: it stays in the contract and
+//! becomes claimable once later deposits push the product over the next
+//! whole unit. Nobody is ever paid twice for the same unit, and the sum of
+//! every `entitled(p)` never exceeds `total_received`.
+//!
+//! ## Reconfiguration
+//!
+//! `set_payees` replaces the payees only when every current payee has
+//! released everything they are entitled to. Any remaining dust carries
+//! over to the new payees as their opening balance.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Vec,
+};
+
+/// Upper bound on payees, so `set_payees` and the drained check stay cheap.
+pub const MAX_PAYEES: u32 = 20;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Token,
+    Payees,
+    TotalShares,
+    TotalReceived,
+    TotalReleased,
+    Shares(Address),
+    Released(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SplitError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAdmin = 3,
+    /// `payees` and `shares` differ in length.
+    LengthMismatch = 4,
+    /// No payees, or more than `MAX_PAYEES`.
+    InvalidPayees = 5,
+    ZeroShares = 6,
+    DuplicatePayee = 7,
+    NotPayee = 8,
+    NothingToRelease = 9,
+    InvalidAmount = 10,
+    /// The deposit is not in the splitter's token.
+    WrongToken = 11,
+    /// A current payee still has an unreleased balance.
+    NotDrained = 12,
+    Overflow = 13,
+}
+
+#[contract]
+pub struct SplitterContract;
+
+#[contractimpl]
+impl SplitterContract {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), SplitError> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(SplitError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&DataKey::Payees, &Vec::<Address>::new(&env));
+        Ok(())
+    }
+
+    /// Replace the payees and their share weights. Fails with `NotDrained`
+    /// while any current payee has something left to release.
+    pub fn set_payees(
+        env: Env,
+        admin: Address,
+        payees: Vec<Address>,
+        shares: Vec<u32>,
+    ) -> Result<(), SplitError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        if payees.len() != shares.len() {
+            return Err(SplitError::LengthMismatch);
+        }
+        if payees.is_empty() || payees.len() > MAX_PAYEES {
+            return Err(SplitError::InvalidPayees);
+        }
+
+        let old = Self::payees(env.clone());
+        for payee in old.iter() {
+            if Self::releasable(env.clone(), payee)? > 0 {
+                return Err(SplitError::NotDrained);
+            }
+        }
+        // Unclaimed dust becomes the new payees' opening balance.
+        let carry = Self::total_received(env.clone()) - Self::total_released(env.clone());
+        for payee in old.iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Shares(payee.clone()));
+            env.storage().persistent().remove(&DataKey::Released(payee));
+        }
+
+        let mut total_shares: u32 = 0;
+        for (payee, share) in payees.iter().zip(shares.iter()) {
+            if share == 0 {
+                return Err(SplitError::ZeroShares);
+            }
+            let key = DataKey::Shares(payee);
+            if env.storage().persistent().has(&key) {
+                return Err(SplitError::DuplicatePayee);
+            }
+            env.storage().persistent().set(&key, &share);
+            total_shares = total_shares
+                .checked_add(share)
+                .ok_or(SplitError::Overflow)?;
+        }
+
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Payees, &payees);
+        storage.set(&DataKey::TotalShares, &total_shares);
+        storage.set(&DataKey::TotalReceived, &carry);
+        storage.set(&DataKey::TotalReleased, &0_i128);
+        env.events()
+            .publish((symbol_short!("payees"),), (payees, shares));
+        Ok(())
+    }
+
+    /// Move `amount` of the splitter's token from `payer` into the splitter.
+    pub fn deposit(
+        env: Env,
+        payer: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), SplitError> {
+        payer.require_auth();
+        if token != Self::token(&env)? {
+            return Err(SplitError::WrongToken);
+        }
+        if amount <= 0 {
+            return Err(SplitError::InvalidAmount);
+        }
+        let total = Self::total_received(env.clone())
+            .checked_add(amount)
+            .ok_or(SplitError::Overflow)?;
+
+        token::Client::new(&env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalReceived, &total);
+        env.events()
+            .publish((symbol_short!("deposit"), payer), amount);
+        Ok(())
+    }
+
+    /// Pay `payee` everything they are entitled to and have not yet taken.
+    pub fn release(env: Env, payee: Address) -> Result<i128, SplitError> {
+        payee.require_auth();
+        let amount = Self::releasable(env.clone(), payee.clone())?;
+        if amount == 0 {
+            return Err(SplitError::NothingToRelease);
+        }
+
+        let released = Self::released(env.clone(), payee.clone())
+            .checked_add(amount)
+            .ok_or(SplitError::Overflow)?;
+        let total_released = Self::total_released(env.clone())
+            .checked_add(amount)
+            .ok_or(SplitError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Released(payee.clone()), &released);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalReleased, &total_released);
+
+        token::Client::new(&env, &Self::token(&env)?).transfer(
+            &env.current_contract_address(),
+            &payee,
+            &amount,
+        );
+        env.events()
+            .publish((symbol_short!("release"), payee), amount);
+        Ok(amount)
+    }
+
+    /// What `payee` could release right now.
+    pub fn releasable(env: Env, payee: Address) -> Result<i128, SplitError> {
+        let shares: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Shares(payee.clone()))
+            .ok_or(SplitError::NotPayee)?;
+        let total_shares: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0);
+        let entitled = mul_div_floor(Self::total_received(env.clone()), shares, total_shares)?;
+        Ok(entitled - Self::released(env, payee))
+    }
+
+    pub fn released(env: Env, payee: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Released(payee))
+            .unwrap_or(0)
+    }
+
+    pub fn shares(env: Env, payee: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Shares(payee))
+            .unwrap_or(0)
+    }
+
+    pub fn payees(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Payees)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Everything deposited since the payees were last set, plus carried
+    /// dust.
+    pub fn total_received(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalReceived)
+            .unwrap_or(0)
+    }
+
+    pub fn total_released(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalReleased)
+            .unwrap_or(0)
+    }
+}
+
+impl SplitterContract {
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), SplitError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(SplitError::NotInitialized)?;
+        if *caller != admin {
+            return Err(SplitError::NotAdmin);
+        }
+        Ok(())
+    }
+
+    fn token(env: &Env) -> Result<Address, SplitError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(SplitError::NotInitialized)
+    }
+}
+
+/// `floor(amount * share / total)` for `0 <= share <= total`, without
+/// forming `amount * share`.
+///
+/// With `amount = q * total + r` and `r < total`, the result is
+/// `q * share + floor(r * share / total)`. `q * share <= amount` because
+/// `share <= total`, and `r * share < total²` fits in an `i128` because
+/// both are `u32`s.
+fn mul_div_floor(amount: i128, share: u32, total: u32) -> Result<i128, SplitError> {
+    if total == 0 {
+        return Ok(0);
+    }
+    let (share, total) = (share as i128, total as i128);
+    let q = amount / total;
+    let r = amount % total;
+    q.checked_mul(share)
+        .and_then(|whole| whole.checked_add(r * share / total))
+        .ok_or(SplitError::Overflow)
+}
+
+mod test;
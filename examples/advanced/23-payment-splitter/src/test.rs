@@ -0,0 +1,237 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Env,
+};
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    token: TokenClient<'static>,
+    minter: StellarAssetClient<'static>,
+    splitter: SplitterContractClient<'static>,
+    /// 50, 30 and 20 shares.
+    payees: [Address; 3],
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let splitter =
+        SplitterContractClient::new(&env, &env.register_contract(None, SplitterContract));
+    splitter.initialize(&admin, &asset.address());
+
+    let payees = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    splitter.set_payees(
+        &admin,
+        &vec![
+            &env,
+            payees[0].clone(),
+            payees[1].clone(),
+            payees[2].clone(),
+        ],
+        &vec![&env, 50, 30, 20],
+    );
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        minter: StellarAssetClient::new(&env, &asset.address()),
+        env,
+        admin,
+        splitter,
+        payees,
+    }
+}
+
+fn deposit(s: &Setup, amount: i128) {
+    let payer = Address::generate(&s.env);
+    s.minter.mint(&payer, &amount);
+    s.splitter.deposit(&payer, &s.token.address, &amount);
+}
+
+fn release_all(s: &Setup) {
+    for payee in &s.payees {
+        if s.splitter.releasable(payee) > 0 {
+            s.splitter.release(payee);
+        }
+    }
+}
+
+#[test]
+fn test_split_over_two_deposits() {
+    let s = setup();
+    let [a, b, c] = &s.payees;
+
+    deposit(&s, 1_000);
+    assert_eq!(s.splitter.release(a), 500);
+    assert_eq!(s.splitter.releasable(b), 300);
+
+    deposit(&s, 2_000);
+    // `a` already took 500 of its 1500.
+    assert_eq!(s.splitter.release(a), 1_000);
+    assert_eq!(s.splitter.release(b), 900);
+    assert_eq!(s.splitter.release(c), 600);
+
+    assert_eq!(s.token.balance(a), 1_500);
+    assert_eq!(s.token.balance(b), 900);
+    assert_eq!(s.token.balance(c), 600);
+    assert_eq!(s.token.balance(&s.splitter.address), 0);
+    assert_eq!(
+        s.splitter.try_release(a),
+        Err(Ok(SplitError::NothingToRelease))
+    );
+}
+
+#[test]
+fn test_dust_stays_claimable() {
+    let s = setup();
+    let [a, b, c] = &s.payees;
+
+    // 99 does not split evenly: 49.5 / 29.7 / 19.8.
+    deposit(&s, 99);
+    release_all(&s);
+    assert_eq!(s.splitter.released(a), 49);
+    assert_eq!(s.splitter.released(b), 29);
+    assert_eq!(s.splitter.released(c), 19);
+    assert_eq!(s.token.balance(&s.splitter.address), 2);
+
+    // One more unit makes the total 100: the held dust is paid out exactly
+    // once, to the payees whose floors it completes.
+    deposit(&s, 1);
+    assert_eq!(s.splitter.releasable(a), 1);
+    assert_eq!(s.splitter.releasable(b), 1);
+    assert_eq!(s.splitter.releasable(c), 1);
+    release_all(&s);
+    assert_eq!(s.token.balance(&s.splitter.address), 0);
+}
+
+#[test]
+fn test_non_payee_cannot_release() {
+    let s = setup();
+    deposit(&s, 1_000);
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.splitter.try_release(&stranger),
+        Err(Ok(SplitError::NotPayee))
+    );
+    assert_eq!(
+        s.splitter.try_releasable(&stranger),
+        Err(Ok(SplitError::NotPayee))
+    );
+}
+
+#[test]
+fn test_reconfigure_only_when_drained() {
+    let s = setup();
+    let [a, b, c] = &s.payees;
+    let d = Address::generate(&s.env);
+    let new_payees = vec![&s.env, c.clone(), d.clone()];
+    let new_shares = vec![&s.env, 1, 1];
+
+    deposit(&s, 99);
+    s.splitter.release(a);
+    s.splitter.release(b);
+    assert_eq!(
+        s.splitter
+            .try_set_payees(&s.admin, &new_payees, &new_shares),
+        Err(Ok(SplitError::NotDrained))
+    );
+
+    s.splitter.release(c);
+    s.splitter.set_payees(&s.admin, &new_payees, &new_shares);
+
+    // The 2 units of dust carry over and split between the new payees.
+    assert_eq!(s.splitter.total_received(), 2);
+    assert_eq!(s.splitter.releasable(c), 1);
+    assert_eq!(s.splitter.releasable(&d), 1);
+    assert_eq!(s.splitter.try_releasable(a), Err(Ok(SplitError::NotPayee)));
+}
+
+#[test]
+fn test_set_payees_validation() {
+    let s = setup();
+    let [a, b, _] = &s.payees;
+    let env = &s.env;
+    assert_eq!(
+        s.splitter.try_set_payees(
+            &Address::generate(env),
+            &vec![env, a.clone()],
+            &vec![env, 1]
+        ),
+        Err(Ok(SplitError::NotAdmin))
+    );
+    assert_eq!(
+        s.splitter
+            .try_set_payees(&s.admin, &vec![env, a.clone()], &vec![env, 1, 2]),
+        Err(Ok(SplitError::LengthMismatch))
+    );
+    assert_eq!(
+        s.splitter
+            .try_set_payees(&s.admin, &vec![env, a.clone(), b.clone()], &vec![env, 1, 0]),
+        Err(Ok(SplitError::ZeroShares))
+    );
+    assert_eq!(
+        s.splitter
+            .try_set_payees(&s.admin, &vec![env, a.clone(), a.clone()], &vec![env, 1, 1]),
+        Err(Ok(SplitError::DuplicatePayee))
+    );
+    assert_eq!(
+        s.splitter
+            .try_set_payees(&s.admin, &Vec::new(env), &Vec::new(env)),
+        Err(Ok(SplitError::InvalidPayees))
+    );
+}
+
+#[test]
+fn test_deposit_rejects_other_tokens() {
+    let s = setup();
+    let other = s
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&s.env));
+    let payer = Address::generate(&s.env);
+    assert_eq!(
+        s.splitter.try_deposit(&payer, &other.address(), &10),
+        Err(Ok(SplitError::WrongToken))
+    );
+    assert_eq!(
+        s.splitter.try_deposit(&payer, &s.token.address, &0),
+        Err(Ok(SplitError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_total_released_equals_total_deposited() {
+    let s = setup();
+    let mut deposited = 0;
+    for amount in [7, 333, 1_001, 59] {
+        deposit(&s, amount);
+        deposited += amount;
+        release_all(&s);
+        // Floors never overpay.
+        assert!(s.splitter.total_released() <= deposited);
+    }
+    assert_eq!(deposited, 1_400);
+    assert_eq!(s.splitter.total_released(), deposited);
+    let paid: i128 = s.payees.iter().map(|p| s.token.balance(p)).sum();
+    assert_eq!(paid, deposited);
+    assert_eq!(s.token.balance(&s.splitter.address), 0);
+}
+
+#[test]
+fn test_mul_div_floor_does_not_overflow() {
+    // `amount * share` would overflow an i128.
+    assert_eq!(
+        mul_div_floor(i128::MAX, u32::MAX - 1, u32::MAX),
+        Ok(i128::MAX - i128::MAX / u32::MAX as i128 - 1)
+    );
+    assert_eq!(mul_div_floor(i128::MAX, 1, 1), Ok(i128::MAX));
+    assert_eq!(mul_div_floor(99, 50, 100), Ok(49));
+}
@@ -0,0 +1,251 @@
+//! # Allowlist Sale
+//!
+//! A fixed-supply sale open only to allowlisted buyers, each capped at a
+//! personal maximum. Buyers pay in a token; the sale credits them units.
+//!
+//! A purchase goes through three checks before any money moves:
+//!
+//! 1. **Membership**: the buyer holds the `allowlist` role, managed with
+//!    `recipes-access-control`.
+//! 2. **Personal cap**: what the buyer already bought plus `amount` is at
+//!    most `max_per_address`.
+//! 3. **Supply**: `amount` fits in what is left. A purchase that does not
+//!    fit is rejected whole, not partially filled.
+//!
+//! Payment of `amount * price` goes straight to the treasury. The admin can
+//! change the price and the cap between purchases; each purchase uses the
+//! values in force when it runs.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+    Vec,
+};
+
+/// Role held by every allowlisted buyer.
+pub const ALLOWLIST: Symbol = symbol_short!("allowlist");
+
+/// Most addresses `add_to_allowlist` accepts in one call.
+pub const MAX_BATCH: u32 = 50;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Config,
+    Sold,
+    Purchased(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleConfig {
+    /// Token buyers pay in.
+    pub token: Address,
+    /// Receives every payment.
+    pub treasury: Address,
+    /// Payment per unit, in `token`'s smallest denomination.
+    pub price: i128,
+    pub max_per_address: i128,
+    pub supply: i128,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SaleError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAdmin = 3,
+    InvalidAmount = 4,
+    InvalidPrice = 5,
+    /// More than `MAX_BATCH` addresses in one `add_to_allowlist` call.
+    BatchTooLarge = 6,
+    NotAllowlisted = 7,
+    /// The purchase would take the buyer past `max_per_address`.
+    ExceedsCap = 8,
+    /// The purchase is larger than the remaining supply.
+    ExceedsSupply = 9,
+    Overflow = 10,
+}
+
+impl From<recipes_access_control::AccessError> for SaleError {
+    fn from(_: recipes_access_control::AccessError) -> Self {
+        SaleError::NotAllowlisted
+    }
+}
+
+#[contract]
+pub struct SaleContract;
+
+#[contractimpl]
+impl SaleContract {
+    pub fn initialize(env: Env, admin: Address, config: SaleConfig) -> Result<(), SaleError> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(SaleError::AlreadyInitialized);
+        }
+        if config.price <= 0 {
+            return Err(SaleError::InvalidPrice);
+        }
+        if config.max_per_address <= 0 || config.supply <= 0 {
+            return Err(SaleError::InvalidAmount);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::Sold, &0_i128);
+        Ok(())
+    }
+
+    // ==================== ADMIN ====================
+
+    /// Allowlist up to `MAX_BATCH` buyers. Returns how many were new.
+    pub fn add_to_allowlist(
+        env: Env,
+        admin: Address,
+        buyers: Vec<Address>,
+    ) -> Result<u32, SaleError> {
+        Self::require_admin(&env, &admin)?;
+        if buyers.len() > MAX_BATCH {
+            return Err(SaleError::BatchTooLarge);
+        }
+        let mut added = 0;
+        for buyer in buyers.iter() {
+            if recipes_access_control::grant(&env, &buyer, &ALLOWLIST) {
+                added += 1;
+            }
+        }
+        env.events()
+            .publish((symbol_short!("allow"), symbol_short!("add")), added);
+        Ok(added)
+    }
+
+    /// Remove `buyer` from the allowlist. Units already bought stay bought.
+    pub fn remove_from_allowlist(
+        env: Env,
+        admin: Address,
+        buyer: Address,
+    ) -> Result<bool, SaleError> {
+        Self::require_admin(&env, &admin)?;
+        let removed = recipes_access_control::revoke(&env, &buyer, &ALLOWLIST);
+        if removed {
+            env.events()
+                .publish((symbol_short!("allow"), symbol_short!("remove")), buyer);
+        }
+        Ok(removed)
+    }
+
+    pub fn set_price(env: Env, admin: Address, price: i128) -> Result<(), SaleError> {
+        Self::require_admin(&env, &admin)?;
+        if price <= 0 {
+            return Err(SaleError::InvalidPrice);
+        }
+        let mut config = Self::config(env.clone())?;
+        config.price = price;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn set_max_per_address(env: Env, admin: Address, max: i128) -> Result<(), SaleError> {
+        Self::require_admin(&env, &admin)?;
+        if max <= 0 {
+            return Err(SaleError::InvalidAmount);
+        }
+        let mut config = Self::config(env.clone())?;
+        config.max_per_address = max;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    // ==================== SALE ====================
+
+    /// Buy `amount` units at the current price. Returns what was paid.
+    pub fn purchase(env: Env, buyer: Address, amount: i128) -> Result<i128, SaleError> {
+        buyer.require_auth();
+        let config = Self::config(env.clone())?;
+        if amount <= 0 {
+            return Err(SaleError::InvalidAmount);
+        }
+        recipes_access_control::require_role(&env, &buyer, &ALLOWLIST)?;
+
+        let purchased = Self::purchased(env.clone(), buyer.clone())
+            .checked_add(amount)
+            .ok_or(SaleError::Overflow)?;
+        if purchased > config.max_per_address {
+            return Err(SaleError::ExceedsCap);
+        }
+        let sold = Self::sold(env.clone())
+            .checked_add(amount)
+            .ok_or(SaleError::Overflow)?;
+        if sold > config.supply {
+            return Err(SaleError::ExceedsSupply);
+        }
+        let cost = amount
+            .checked_mul(config.price)
+            .ok_or(SaleError::Overflow)?;
+
+        token::Client::new(&env, &config.token).transfer(&buyer, &config.treasury, &cost);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Purchased(buyer.clone()), &purchased);
+        env.storage().instance().set(&DataKey::Sold, &sold);
+        env.events()
+            .publish((symbol_short!("purchase"), buyer), (amount, cost));
+        Ok(cost)
+    }
+
+    /// How many more units `buyer` could buy right now: the smaller of
+    /// their remaining cap and the remaining supply, or zero if they are
+    /// not allowlisted.
+    pub fn remaining_for(env: Env, buyer: Address) -> i128 {
+        let Ok(config) = Self::config(env.clone()) else {
+            return 0;
+        };
+        if !recipes_access_control::has(&env, &buyer, &ALLOWLIST) {
+            return 0;
+        }
+        let personal = config.max_per_address - Self::purchased(env.clone(), buyer);
+        let supply = config.supply - Self::sold(env);
+        personal.min(supply).max(0)
+    }
+
+    pub fn is_allowlisted(env: Env, buyer: Address) -> bool {
+        recipes_access_control::has(&env, &buyer, &ALLOWLIST)
+    }
+
+    pub fn purchased(env: Env, buyer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Purchased(buyer))
+            .unwrap_or(0)
+    }
+
+    pub fn sold(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Sold).unwrap_or(0)
+    }
+
+    pub fn config(env: Env) -> Result<SaleConfig, SaleError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(SaleError::NotInitialized)
+    }
+}
+
+impl SaleContract {
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), SaleError> {
+        caller.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(SaleError::NotInitialized)?;
+        if *caller != admin {
+            return Err(SaleError::NotAdmin);
+        }
+        Ok(())
+    }
+}
+
+mod test;
@@ -0,0 +1,192 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    vec, Env, IntoVal,
+};
+
+const PRICE: i128 = 10;
+const MAX_PER_ADDRESS: i128 = 50;
+const SUPPLY: i128 = 100;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    treasury: Address,
+    token: TokenClient<'static>,
+    minter: StellarAssetClient<'static>,
+    sale: SaleContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let sale = SaleContractClient::new(&env, &env.register_contract(None, SaleContract));
+    sale.initialize(
+        &admin,
+        &SaleConfig {
+            token: asset.address(),
+            treasury: treasury.clone(),
+            price: PRICE,
+            max_per_address: MAX_PER_ADDRESS,
+            supply: SUPPLY,
+        },
+    );
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        minter: StellarAssetClient::new(&env, &asset.address()),
+        env,
+        admin,
+        treasury,
+        sale,
+    }
+}
+
+/// An allowlisted buyer holding `funds` of the payment token.
+fn buyer(s: &Setup, funds: i128) -> Address {
+    let buyer = Address::generate(&s.env);
+    s.minter.mint(&buyer, &funds);
+    s.sale
+        .add_to_allowlist(&s.admin, &vec![&s.env, buyer.clone()]);
+    buyer
+}
+
+#[test]
+fn test_buyer_hits_personal_cap() {
+    let s = setup();
+    let alice = buyer(&s, 1_000);
+
+    assert_eq!(s.sale.purchase(&alice, &30), 300);
+    assert_eq!(s.sale.remaining_for(&alice), 20);
+    assert_eq!(
+        s.sale.try_purchase(&alice, &21),
+        Err(Ok(SaleError::ExceedsCap))
+    );
+    assert_eq!(s.sale.purchase(&alice, &20), 200);
+
+    assert_eq!(s.sale.purchased(&alice), MAX_PER_ADDRESS);
+    assert_eq!(s.sale.remaining_for(&alice), 0);
+    assert_eq!(
+        s.sale.try_purchase(&alice, &1),
+        Err(Ok(SaleError::ExceedsCap))
+    );
+    assert_eq!(s.token.balance(&alice), 500);
+    assert_eq!(s.token.balance(&s.treasury), 500);
+}
+
+#[test]
+fn test_non_allowlisted_buyer_rejected() {
+    let s = setup();
+    let mallory = Address::generate(&s.env);
+    s.minter.mint(&mallory, &1_000);
+
+    assert_eq!(
+        s.sale.try_purchase(&mallory, &1),
+        Err(Ok(SaleError::NotAllowlisted))
+    );
+    assert_eq!(s.sale.remaining_for(&mallory), 0);
+    assert_eq!(s.token.balance(&mallory), 1_000);
+}
+
+#[test]
+fn test_removed_buyer_keeps_units_but_cannot_buy() {
+    let s = setup();
+    let alice = buyer(&s, 1_000);
+    s.sale.purchase(&alice, &5);
+
+    assert!(s.sale.remove_from_allowlist(&s.admin, &alice));
+    assert!(!s.sale.remove_from_allowlist(&s.admin, &alice));
+    assert_eq!(
+        s.sale.try_purchase(&alice, &1),
+        Err(Ok(SaleError::NotAllowlisted))
+    );
+    assert_eq!(s.sale.purchased(&alice), 5);
+}
+
+#[test]
+fn test_supply_exhausted_mid_purchase() {
+    let s = setup();
+    s.sale.set_max_per_address(&s.admin, &80);
+    let (alice, bob) = (buyer(&s, 10_000), buyer(&s, 10_000));
+
+    s.sale.purchase(&alice, &80);
+    assert_eq!(s.sale.remaining_for(&bob), 20);
+
+    // 30 does not fit in the 20 left: rejected whole, nothing paid.
+    assert_eq!(
+        s.sale.try_purchase(&bob, &30),
+        Err(Ok(SaleError::ExceedsSupply))
+    );
+    assert_eq!(s.token.balance(&bob), 10_000);
+
+    s.sale.purchase(&bob, &20);
+    assert_eq!(s.sale.sold(), SUPPLY);
+    assert_eq!(
+        s.sale.try_purchase(&bob, &1),
+        Err(Ok(SaleError::ExceedsSupply))
+    );
+    assert_eq!(s.token.balance(&s.treasury), SUPPLY * PRICE);
+}
+
+#[test]
+fn test_price_change_between_purchases() {
+    let s = setup();
+    let alice = buyer(&s, 1_000);
+
+    assert_eq!(s.sale.purchase(&alice, &10), 100);
+    s.sale.set_price(&s.admin, &25);
+    assert_eq!(s.sale.purchase(&alice, &10), 250);
+
+    assert_eq!(s.token.balance(&alice), 650);
+    assert_eq!(s.token.balance(&s.treasury), 350);
+    assert_eq!(s.sale.purchased(&alice), 20);
+    assert_eq!(
+        s.sale.try_set_price(&s.admin, &0),
+        Err(Ok(SaleError::InvalidPrice))
+    );
+}
+
+#[test]
+fn test_purchase_event() {
+    let s = setup();
+    let alice = buyer(&s, 1_000);
+    s.sale.purchase(&alice, &3);
+
+    let (contract, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(contract, s.sale.address);
+    assert_eq!(
+        topics,
+        (symbol_short!("purchase"), alice.clone()).into_val(&s.env)
+    );
+    let data: (i128, i128) = data.into_val(&s.env);
+    assert_eq!(data, (3, 3 * PRICE));
+}
+
+#[test]
+fn test_allowlist_batch_and_admin_checks() {
+    let s = setup();
+    let env = &s.env;
+    let mut batch = Vec::new(env);
+    for _ in 0..MAX_BATCH {
+        batch.push_back(Address::generate(env));
+    }
+    assert_eq!(s.sale.add_to_allowlist(&s.admin, &batch), MAX_BATCH);
+    // Re-adding is a no-op.
+    assert_eq!(s.sale.add_to_allowlist(&s.admin, &batch), 0);
+
+    batch.push_back(Address::generate(env));
+    assert_eq!(
+        s.sale.try_add_to_allowlist(&s.admin, &batch),
+        Err(Ok(SaleError::BatchTooLarge))
+    );
+    assert_eq!(
+        s.sale
+            .try_add_to_allowlist(&Address::generate(env), &vec![env, Address::generate(env)]),
+        Err(Ok(SaleError::NotAdmin))
+    );
+}
@@ -0,0 +1,150 @@
+//! # Contract Metadata
+//!
+//! Two ways for a contract to describe itself:
+//!
+//! - **`contractmeta!`** writes key/value strings into the `contractmetav0`
+//!   custom section of the wasm. They describe the *code*: anyone holding
+//!   the wasm (an explorer, a wallet, an auditor) can read them without
+//!   running anything.
+//! - **`about()`** is an ordinary view that describes the *deployed
+//!   instance*: the version recorded in instance storage, the build that
+//!   produced the running code, and the current admin.
+//!
+//! The two agree when the contract is first deployed. `upgrade` records the
+//! new code's version in storage at the same time it installs the code, so
+//! `about()` keeps matching the `version` entry of whatever wasm is running.
+//!
+//! Metadata entries are part of the wasm, so they count toward its size.
+//! Keep them short.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contractmeta, contracttype, symbol_short, Address,
+    BytesN, Env, String,
+};
+
+contractmeta!(
+    key = "Description",
+    val = "Contract metadata recipe: contractmeta! entries and an about() view"
+);
+contractmeta!(key = "name", val = "contract-metadata");
+contractmeta!(key = "version", val = "1.0.0");
+contractmeta!(
+    key = "source",
+    val = "https://github.com/Soroban-Cookbook/Soroban-Cookbook/tree/main/examples/advanced/25-contract-metadata"
+);
+
+/// Version recorded by `initialize`. Keep in step with the `version` meta
+/// entry above.
+pub const INITIAL_VERSION: SemVer = SemVer {
+    major: 1,
+    minor: 0,
+    patch: 0,
+};
+
+/// Identifies the build that produced this code, taken from the
+/// `BUILD_ID` environment variable at compile time (for example a CI run or
+/// git commit). Local builds report `dev`.
+pub const BUILD: &str = match option_env!("BUILD_ID") {
+    Some(id) => id,
+    None => "dev",
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Version,
+}
+
+/// A semantic version. Ordering is by `major`, then `minor`, then `patch`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SemVer {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractInfo {
+    pub version: SemVer,
+    pub build: String,
+    pub admin: Address,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MetaError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAdmin = 3,
+    /// `upgrade` was given a version not greater than the current one.
+    VersionNotIncreasing = 4,
+}
+
+#[contract]
+pub struct MetadataContract;
+
+#[contractimpl]
+impl MetadataContract {
+    /// Store the admin and record `INITIAL_VERSION`.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), MetaError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(MetaError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Version, &INITIAL_VERSION);
+        Ok(())
+    }
+
+    /// Describe this deployment.
+    pub fn about(env: Env) -> Result<ContractInfo, MetaError> {
+        let storage = env.storage().instance();
+        Ok(ContractInfo {
+            version: storage
+                .get(&DataKey::Version)
+                .ok_or(MetaError::NotInitialized)?,
+            build: String::from_str(&env, BUILD),
+            admin: storage
+                .get(&DataKey::Admin)
+                .ok_or(MetaError::NotInitialized)?,
+        })
+    }
+
+    /// Install `new_wasm_hash` and record it as `version`. Admin only.
+    ///
+    /// `version` should be the `version` meta entry of the new wasm, and
+    /// must be greater than the current one. Publishes `("upgraded",)` with
+    /// `(version, new_wasm_hash)`.
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+        version: SemVer,
+    ) -> Result<(), MetaError> {
+        admin.require_auth();
+        let info = Self::about(env.clone())?;
+        if admin != info.admin {
+            return Err(MetaError::NotAdmin);
+        }
+        if version <= info.version {
+            return Err(MetaError::VersionNotIncreasing);
+        }
+
+        env.storage().instance().set(&DataKey::Version, &version);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events()
+            .publish((symbol_short!("upgraded"),), (version, new_wasm_hash));
+        Ok(())
+    }
+}
+
+mod test;
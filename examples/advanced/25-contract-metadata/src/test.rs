@@ -0,0 +1,147 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::{contract_meta, TestEnv};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _},
+    IntoVal,
+};
+
+mod wasm {
+    soroban_sdk::contractimport!(file = "fixtures/contract_metadata.wasm");
+}
+
+fn meta(key: &str) -> Option<std::string::String> {
+    contract_meta(wasm::WASM)
+        .into_iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v)
+}
+
+fn semver(v: &str) -> SemVer {
+    let mut parts = v.split('.').map(|p| p.parse::<u32>().unwrap());
+    let version = SemVer {
+        major: parts.next().unwrap(),
+        minor: parts.next().unwrap(),
+        patch: parts.next().unwrap(),
+    };
+    assert!(parts.next().is_none(), "not a semantic version: {v}");
+    version
+}
+
+/// Deploys the wasm fixture and initializes it with a fresh admin.
+fn setup_wasm() -> (TestEnv, Address, wasm::Client<'static>) {
+    let env = TestEnv::new();
+    let contract_id = env.register_contract_wasm(None, wasm::WASM);
+    let client = wasm::Client::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    (env, admin, client)
+}
+
+#[test]
+fn test_meta_entries_in_wasm() {
+    assert_eq!(
+        meta("Description").as_deref(),
+        Some("Contract metadata recipe: contractmeta! entries and an about() view")
+    );
+    assert_eq!(meta("name").as_deref(), Some("contract-metadata"));
+    assert_eq!(meta("version").as_deref(), Some("1.0.0"));
+    assert!(meta("source").unwrap().ends_with("25-contract-metadata"));
+    // Written by the SDK itself.
+    assert!(meta("rssdkver").is_some());
+}
+
+#[test]
+fn test_about_matches_wasm_meta() {
+    let (env, admin, client) = setup_wasm();
+    let info = client.about();
+
+    let version = semver(&meta("version").unwrap());
+    assert_eq!(
+        (info.version.major, info.version.minor, info.version.patch),
+        (version.major, version.minor, version.patch)
+    );
+    assert_eq!(info.admin, admin);
+    assert_eq!(info.build, String::from_str(&env, "dev"));
+}
+
+#[test]
+fn test_initial_version_matches_meta() {
+    let (env, _, client) = TestEnv::with_contract(MetadataContract, MetadataContractClient::new);
+    assert_eq!(client.try_about(), Err(Ok(MetaError::NotInitialized)));
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    assert_eq!(
+        client.about(),
+        ContractInfo {
+            version: INITIAL_VERSION,
+            build: String::from_str(&env, BUILD),
+            admin,
+        }
+    );
+    assert_eq!(INITIAL_VERSION, semver(&meta("version").unwrap()));
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env)),
+        Err(Ok(MetaError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_upgrade_records_new_version() {
+    let (env, admin, client) = setup_wasm();
+    let hash = env.deployer().upload_contract_wasm(wasm::WASM);
+    let next = wasm::SemVer {
+        major: 1,
+        minor: 1,
+        patch: 0,
+    };
+
+    client.upgrade(&admin, &hash, &next);
+    assert_eq!(client.about().version, next);
+
+    let (id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(id, client.address);
+    assert_eq!(topics, (symbol_short!("upgraded"),).into_val(&*env));
+    let (version, new_hash): (wasm::SemVer, BytesN<32>) = data.into_val(&*env);
+    assert_eq!(version, next);
+    assert_eq!(new_hash, hash);
+}
+
+#[test]
+fn test_upgrade_rejects_stale_version_and_non_admin() {
+    let (env, admin, client) = setup_wasm();
+    let hash = env.deployer().upload_contract_wasm(wasm::WASM);
+    let same = wasm::SemVer {
+        major: 1,
+        minor: 0,
+        patch: 0,
+    };
+    let older = wasm::SemVer {
+        major: 0,
+        minor: 9,
+        patch: 9,
+    };
+    let next = wasm::SemVer {
+        major: 2,
+        minor: 0,
+        patch: 0,
+    };
+
+    assert_eq!(
+        client.try_upgrade(&admin, &hash, &same),
+        Err(Ok(wasm::MetaError::VersionNotIncreasing))
+    );
+    assert_eq!(
+        client.try_upgrade(&admin, &hash, &older),
+        Err(Ok(wasm::MetaError::VersionNotIncreasing))
+    );
+    assert_eq!(
+        client.try_upgrade(&Address::generate(&env), &hash, &next),
+        Err(Ok(wasm::MetaError::NotAdmin))
+    );
+    assert_eq!(client.about().version, same);
+}
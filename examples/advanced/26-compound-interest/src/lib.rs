@@ -0,0 +1,55 @@
+//! # Compound Interest
+//!
+//! A thin contract over `recipes-fixed-point`: it grows a token amount by a
+//! per-period rate, compounded a whole number of times.
+//!
+//! ```text
+//! balance = principal × (1 + rate_bps / 10_000) ^ periods
+//! ```
+//!
+//! The amount is converted into 7-decimal fixed point, grown, and converted
+//! back to the token's decimals rounding down, so the contract never quotes
+//! more than the exact balance. `FixedError` is itself a `#[contracterror]`,
+//! so the functions return it directly.
+
+#![no_std]
+
+use recipes_fixed_point::{FixedError, Rounding, I128F7};
+use soroban_sdk::{contract, contractimpl, Env};
+
+/// Basis points in 100%.
+pub const BPS: i128 = 10_000;
+
+#[contract]
+pub struct InterestContract;
+
+#[contractimpl]
+impl InterestContract {
+    /// `(1 + rate_bps / 10_000) ^ periods` as a raw 7-decimal value.
+    pub fn growth_factor(_env: Env, rate_bps: u32, periods: u32) -> Result<i128, FixedError> {
+        Ok(growth(rate_bps, periods)?.raw())
+    }
+
+    /// `principal`, an amount with `decimals` places, after `periods` of
+    /// interest at `rate_bps` per period. Rounded down.
+    pub fn compound(
+        _env: Env,
+        principal: i128,
+        decimals: u32,
+        rate_bps: u32,
+        periods: u32,
+    ) -> Result<i128, FixedError> {
+        // Round the principal down too: any precision lost here is the
+        // holder's, never the contract's.
+        I128F7::from_amount(principal, decimals, Rounding::Floor)?
+            .mul(growth(rate_bps, periods)?)?
+            .to_amount(decimals, Rounding::Floor)
+    }
+}
+
+fn growth(rate_bps: u32, periods: u32) -> Result<I128F7, FixedError> {
+    let rate = I128F7::from_ratio(rate_bps as i128, BPS)?;
+    I128F7::ONE.add(rate)?.pow_int(periods)
+}
+
+mod test;
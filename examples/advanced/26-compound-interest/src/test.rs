@@ -0,0 +1,56 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::Env;
+
+fn setup() -> InterestContractClient<'static> {
+    let env = Env::default();
+    InterestContractClient::new(&env, &env.register_contract(None, InterestContract))
+}
+
+#[test]
+fn test_compound_known_value() {
+    let client = setup();
+    // 1.05^10 = 1.62889462677…
+    let factor = client.growth_factor(&500, &10);
+    assert!((16_288_940..=16_288_946).contains(&factor));
+
+    // 1,000 units of a 7-decimal token at 5% for 10 periods: 1,628.89…
+    let balance = client.compound(&(1_000 * 10_000_000), &7, &500, &10);
+    assert_eq!(balance / 100_000, 162_889);
+    assert!(balance <= 16_288_946_267);
+}
+
+#[test]
+fn test_zero_rate_or_periods_keeps_principal() {
+    let client = setup();
+    assert_eq!(client.compound(&123_456_789, &7, &0, &50), 123_456_789);
+    assert_eq!(client.compound(&123_456_789, &7, &750, &0), 123_456_789);
+}
+
+#[test]
+fn test_other_decimals_round_down() {
+    let client = setup();
+    // 6 decimals (USDC-style): 100.000000 at 1% for 12 periods = 112.682503…
+    // The factor is a few ULP below exact, and the principal scales that.
+    let balance = client.compound(&100_000_000, &6, &100, &12);
+    assert!((112_682_400..=112_682_503).contains(&balance));
+    // 2 decimals: 10.00 at 10% for 3 periods = 13.31 exactly.
+    assert_eq!(client.compound(&1_000, &2, &1_000, &3), 1_331);
+    // 9 decimals: precision beyond 7 places is dropped, not rounded up.
+    assert_eq!(client.compound(&1_999_999_999, &9, &0, &1), 1_999_999_900);
+}
+
+#[test]
+fn test_overflow_is_an_error() {
+    let client = setup();
+    // Doubling 200 times exceeds i128.
+    assert_eq!(
+        client.try_growth_factor(&10_000, &200),
+        Err(Ok(FixedError::Overflow))
+    );
+    assert_eq!(
+        client.try_compound(&i128::MAX, &7, &1, &1),
+        Err(Ok(FixedError::Overflow))
+    );
+}
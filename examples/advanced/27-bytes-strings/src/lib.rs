@@ -0,0 +1,112 @@
+//! # Bytes and Strings
+//!
+//! Small helpers for `Bytes` and `String` that contracts keep rewriting.
+//!
+//! Both types live in the host, not in contract memory, so the usual `std`
+//! tools do not apply:
+//!
+//! - `Bytes` supports indexing, slicing and appending directly through host
+//!   calls, so `concat`, `slice` and `starts_with` never copy into the guest.
+//! - `String` is opaque: the only way to look at its characters is
+//!   `copy_into_slice` into a fixed-size buffer, and the only way to build
+//!   one is `String::from_bytes` from a slice. Hex conversion therefore
+//!   goes through a stack buffer of `MAX_HEX_LEN` bytes, which also bounds
+//!   the input size.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, panic_with_error, Bytes, Env, String};
+
+/// Longest hex string `to_hex` produces and `from_hex` accepts, in
+/// characters. 128 bytes of data.
+pub const MAX_HEX_LEN: u32 = 256;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BytesError {
+    /// The requested range runs past the end of the data.
+    OutOfRange = 1,
+    /// A hex string must have two characters per byte.
+    OddLength = 2,
+    /// A character other than `0-9`, `a-f` or `A-F`.
+    InvalidHexChar = 3,
+    /// More than `MAX_HEX_LEN` hex characters.
+    TooLong = 4,
+}
+
+#[contract]
+pub struct BytesContract;
+
+#[contractimpl]
+impl BytesContract {
+    /// `a` followed by `b`.
+    pub fn concat(_env: Env, a: Bytes, b: Bytes) -> Bytes {
+        let mut out = a;
+        out.append(&b);
+        out
+    }
+
+    /// The `len` bytes of `data` starting at `start`.
+    pub fn slice(_env: Env, data: Bytes, start: u32, len: u32) -> Result<Bytes, BytesError> {
+        let end = start.checked_add(len).ok_or(BytesError::OutOfRange)?;
+        if end > data.len() {
+            return Err(BytesError::OutOfRange);
+        }
+        Ok(data.slice(start..end))
+    }
+
+    /// Whether `data` begins with `prefix`. Every value starts with the
+    /// empty prefix.
+    pub fn starts_with(_env: Env, data: Bytes, prefix: Bytes) -> bool {
+        prefix.len() <= data.len() && data.slice(..prefix.len()) == prefix
+    }
+
+    /// Lowercase hex encoding of `data`, without a `0x` prefix. Panics with
+    /// `TooLong` beyond `MAX_HEX_LEN / 2` bytes.
+    pub fn to_hex(env: Env, data: Bytes) -> String {
+        if data.len() > MAX_HEX_LEN / 2 {
+            panic_with_error!(&env, BytesError::TooLong);
+        }
+        let mut buf = [0u8; MAX_HEX_LEN as usize];
+        for (i, byte) in data.iter().enumerate() {
+            buf[2 * i] = HEX_DIGITS[(byte >> 4) as usize];
+            buf[2 * i + 1] = HEX_DIGITS[(byte & 0x0f) as usize];
+        }
+        String::from_bytes(&env, &buf[..2 * data.len() as usize])
+    }
+
+    /// Decode a hex string. Upper- and lowercase digits are both accepted;
+    /// a `0x` prefix is not.
+    pub fn from_hex(env: Env, s: String) -> Result<Bytes, BytesError> {
+        let len = s.len();
+        if len > MAX_HEX_LEN {
+            return Err(BytesError::TooLong);
+        }
+        if !len.is_multiple_of(2) {
+            return Err(BytesError::OddLength);
+        }
+        let mut buf = [0u8; MAX_HEX_LEN as usize];
+        let chars = &mut buf[..len as usize];
+        s.copy_into_slice(chars);
+
+        let mut out = Bytes::new(&env);
+        for i in (0..chars.len()).step_by(2) {
+            out.push_back(hex_value(chars[i])? << 4 | hex_value(chars[i + 1])?);
+        }
+        Ok(out)
+    }
+}
+
+fn hex_value(c: u8) -> Result<u8, BytesError> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(BytesError::InvalidHexChar),
+    }
+}
+
+mod test;
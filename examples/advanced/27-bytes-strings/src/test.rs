@@ -0,0 +1,140 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{bytes, BytesN, Env};
+
+fn setup() -> (Env, BytesContractClient<'static>) {
+    let env = Env::default();
+    let client = BytesContractClient::new(&env, &env.register_contract(None, BytesContract));
+    (env, client)
+}
+
+fn s(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+#[test]
+fn test_empty_inputs() {
+    let (env, client) = setup();
+    let empty = Bytes::new(&env);
+    let abc = Bytes::from_slice(&env, b"abc");
+
+    assert_eq!(client.concat(&empty, &empty), empty);
+    assert_eq!(client.concat(&abc, &empty), abc);
+    assert_eq!(client.concat(&empty, &abc), abc);
+    assert_eq!(client.slice(&empty, &0, &0), empty);
+    assert_eq!(client.slice(&abc, &3, &0), empty);
+    assert!(client.starts_with(&abc, &empty));
+    assert!(client.starts_with(&empty, &empty));
+    assert!(!client.starts_with(&empty, &abc));
+    assert_eq!(client.to_hex(&empty), s(&env, ""));
+    assert_eq!(client.from_hex(&s(&env, "")), empty);
+}
+
+#[test]
+fn test_concat_slice_and_prefix() {
+    let (env, client) = setup();
+    let hello = Bytes::from_slice(&env, b"hello, ");
+    let world = Bytes::from_slice(&env, b"world");
+    let joined = client.concat(&hello, &world);
+
+    assert_eq!(joined, Bytes::from_slice(&env, b"hello, world"));
+    assert_eq!(client.slice(&joined, &7, &5), world);
+    assert!(client.starts_with(&joined, &hello));
+    assert!(!client.starts_with(&joined, &world));
+    assert!(!client.starts_with(&hello, &joined));
+}
+
+#[test]
+fn test_out_of_range_slices() {
+    let (env, client) = setup();
+    let data = Bytes::from_slice(&env, b"12345");
+
+    assert_eq!(client.slice(&data, &0, &5), data);
+    assert_eq!(
+        client.try_slice(&data, &0, &6),
+        Err(Ok(BytesError::OutOfRange))
+    );
+    assert_eq!(
+        client.try_slice(&data, &4, &2),
+        Err(Ok(BytesError::OutOfRange))
+    );
+    assert_eq!(
+        client.try_slice(&data, &6, &0),
+        Err(Ok(BytesError::OutOfRange))
+    );
+    // start + len wraps a u32.
+    assert_eq!(
+        client.try_slice(&data, &2, &u32::MAX),
+        Err(Ok(BytesError::OutOfRange))
+    );
+}
+
+#[test]
+fn test_hex_case_and_errors() {
+    let (env, client) = setup();
+    let expected = bytes!(&env, 0xdeadbeef00ff);
+
+    assert_eq!(client.to_hex(&expected), s(&env, "deadbeef00ff"));
+    assert_eq!(client.from_hex(&s(&env, "deadbeef00ff")), expected);
+    assert_eq!(client.from_hex(&s(&env, "DEADBEEF00FF")), expected);
+    assert_eq!(client.from_hex(&s(&env, "DeAdBeEf00Ff")), expected);
+
+    assert_eq!(
+        client.try_from_hex(&s(&env, "abc")),
+        Err(Ok(BytesError::OddLength))
+    );
+    assert_eq!(
+        client.try_from_hex(&s(&env, "0g")),
+        Err(Ok(BytesError::InvalidHexChar))
+    );
+    assert_eq!(
+        client.try_from_hex(&s(&env, "0xab")),
+        Err(Ok(BytesError::InvalidHexChar))
+    );
+    assert_eq!(
+        client.try_from_hex(&s(&env, "ab ")),
+        Err(Ok(BytesError::OddLength))
+    );
+}
+
+#[test]
+fn test_32_byte_values_round_trip() {
+    let (env, client) = setup();
+    let mut values = [[0u8; 32], [0xff; 32], [0u8; 32]];
+    for (i, byte) in values[2].iter_mut().enumerate() {
+        *byte = i as u8 * 8 + 1;
+    }
+    for seed in 0u8..8 {
+        let hash = env.crypto().sha256(&Bytes::from_array(&env, &[seed]));
+        let value: BytesN<32> = hash.into();
+        let data = Bytes::from(value);
+        let hex = client.to_hex(&data);
+        assert_eq!(hex.len(), 64);
+        assert_eq!(client.from_hex(&hex), data);
+    }
+    for value in values {
+        let data = Bytes::from_array(&env, &value);
+        assert_eq!(client.from_hex(&client.to_hex(&data)), data);
+    }
+}
+
+#[test]
+fn test_hex_length_limit() {
+    let (env, client) = setup();
+    let max = Bytes::from_slice(&env, &[0xab; (MAX_HEX_LEN / 2) as usize]);
+    let hex = client.to_hex(&max);
+    assert_eq!(hex.len(), MAX_HEX_LEN);
+    assert_eq!(client.from_hex(&hex), max);
+
+    let mut over = max.clone();
+    over.push_back(0);
+    assert_eq!(
+        client.try_to_hex(&over),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            BytesError::TooLong as u32
+        )))
+    );
+    let long = String::from_bytes(&env, &[b'a'; MAX_HEX_LEN as usize + 2]);
+    assert_eq!(client.try_from_hex(&long), Err(Ok(BytesError::TooLong)));
+}
@@ -0,0 +1,101 @@
+//! # Hashing and Signatures
+//!
+//! Thin wrappers over the host's crypto functions, and the patterns needed
+//! to use them safely.
+//!
+//! ## Verification traps
+//!
+//! `env.crypto().ed25519_verify` returns `()`. An invalid signature does not
+//! produce `false`: it fails the whole invocation, and a contract cannot
+//! catch a failure of its own host calls. `verify_ed25519` therefore only
+//! ever returns `true`.
+//!
+//! To get a real boolean, make the check in a *separate* invocation and call
+//! it with a `try_` client, which turns the failure into an `Err`.
+//! `check_ed25519` does exactly that against a verifier contract. The
+//! verifier must be a different contract: the host rejects a contract
+//! calling back into itself.
+//!
+//! ## Recovery needs a real hash
+//!
+//! `recover_secp256k1` accepts any 32 bytes as the digest, which is only
+//! safe if the caller hashed the message. Without a hash, an attacker can
+//! pick a digest that recovers to a key of their choosing. The SDK puts the
+//! raw-digest entry point behind the `hazmat` feature for that reason; in
+//! your own contracts, prefer hashing the message yourself and passing the
+//! resulting `Hash<32>` to `env.crypto().secp256k1_recover`.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, Address, Bytes, BytesN, Env};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CryptoError {
+    /// The last signature byte is not 0, 1, 27 or 28.
+    InvalidRecoveryId = 1,
+}
+
+#[contract]
+pub struct CryptoContract;
+
+#[contractimpl]
+impl CryptoContract {
+    pub fn sha256(env: Env, data: Bytes) -> BytesN<32> {
+        env.crypto().sha256(&data).into()
+    }
+
+    pub fn keccak256(env: Env, data: Bytes) -> BytesN<32> {
+        env.crypto().keccak256(&data).into()
+    }
+
+    /// Returns `true` if `sig` is `pubkey`'s signature over `msg`. Otherwise
+    /// the invocation traps; it never returns `false`.
+    pub fn verify_ed25519(env: Env, pubkey: BytesN<32>, msg: Bytes, sig: BytesN<64>) -> bool {
+        env.crypto().ed25519_verify(&pubkey, &msg, &sig);
+        true
+    }
+
+    /// `verify_ed25519` as a real boolean: asks `verifier`, another
+    /// deployment of this contract, and treats any failure as `false`.
+    pub fn check_ed25519(
+        env: Env,
+        verifier: Address,
+        pubkey: BytesN<32>,
+        msg: Bytes,
+        sig: BytesN<64>,
+    ) -> bool {
+        CryptoContractClient::new(&env, &verifier)
+            .try_verify_ed25519(&pubkey, &msg, &sig)
+            .is_ok()
+    }
+
+    /// The SEC-1 uncompressed public key (`0x04 ‖ x ‖ y`) that produced
+    /// `signature` over `digest`.
+    ///
+    /// `signature` is `r ‖ s ‖ v`, with the recovery id `v` as 0/1 or in
+    /// Ethereum's 27/28 form. `s` must be in the lower half of the curve
+    /// order, as most signers (and Ethereum) produce.
+    pub fn recover_secp256k1(
+        env: Env,
+        digest: BytesN<32>,
+        signature: BytesN<65>,
+    ) -> Result<BytesN<65>, CryptoError> {
+        let bytes = signature.to_array();
+        let recovery_id = match bytes[64] {
+            v @ (0 | 1) => v,
+            v @ (27 | 28) => v - 27,
+            _ => return Err(CryptoError::InvalidRecoveryId),
+        };
+        let mut rs = [0u8; 64];
+        rs.copy_from_slice(&bytes[..64]);
+        Ok(env.crypto_hazmat().secp256k1_recover(
+            &digest,
+            &BytesN::from_array(&env, &rs),
+            recovery_id as u32,
+        ))
+    }
+}
+
+mod test;
@@ -0,0 +1,140 @@
+#![cfg(test)]
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{bytesn, Env};
+
+fn setup() -> (Env, CryptoContractClient<'static>) {
+    let env = Env::default();
+    let client = CryptoContractClient::new(&env, &env.register_contract(None, CryptoContract));
+    (env, client)
+}
+
+fn ed25519_signed(env: &Env, msg: &[u8]) -> (BytesN<32>, Bytes, BytesN<64>) {
+    let key = SigningKey::from_bytes(&[7; 32]);
+    (
+        BytesN::from_array(env, &key.verifying_key().to_bytes()),
+        Bytes::from_slice(env, msg),
+        BytesN::from_array(env, &key.sign(msg).to_bytes()),
+    )
+}
+
+/// A secp256k1 signature over `digest` as `r ‖ s ‖ v` with `v` in 0/1, and
+/// the signer's uncompressed public key.
+fn secp256k1_signed(env: &Env, digest: &BytesN<32>) -> ([u8; 65], BytesN<65>) {
+    let key = k256::ecdsa::SigningKey::from_bytes(&[9u8; 32].into()).unwrap();
+    let (sig, recovery_id) = key.sign_prehash_recoverable(&digest.to_array()).unwrap();
+    let mut out = [0u8; 65];
+    out[..64].copy_from_slice(&sig.to_bytes());
+    out[64] = recovery_id.to_byte();
+    let public = key.verifying_key().to_encoded_point(false);
+    (
+        out,
+        BytesN::from_array(env, public.as_bytes().try_into().unwrap()),
+    )
+}
+
+#[test]
+fn test_hash_known_answers() {
+    let (env, client) = setup();
+    let abc = Bytes::from_slice(&env, b"abc");
+    let empty = Bytes::new(&env);
+
+    assert_eq!(
+        client.sha256(&abc),
+        bytesn!(
+            &env,
+            0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+        )
+    );
+    assert_eq!(
+        client.sha256(&empty),
+        bytesn!(
+            &env,
+            0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855
+        )
+    );
+    // Keccak-256 as Ethereum uses it, not the padded NIST SHA3-256.
+    assert_eq!(
+        client.keccak256(&abc),
+        bytesn!(
+            &env,
+            0x4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45
+        )
+    );
+    assert_eq!(
+        client.keccak256(&empty),
+        bytesn!(
+            &env,
+            0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
+        )
+    );
+}
+
+#[test]
+fn test_ed25519_valid_and_tampered() {
+    let (env, client) = setup();
+    let (pubkey, msg, sig) = ed25519_signed(&env, b"transfer 100 to bob");
+    assert!(client.verify_ed25519(&pubkey, &msg, &sig));
+
+    // A bad signature traps instead of returning false.
+    let tampered = Bytes::from_slice(&env, b"transfer 900 to bob");
+    assert!(client.try_verify_ed25519(&pubkey, &tampered, &sig).is_err());
+    let mut bad_sig = sig.to_array();
+    bad_sig[0] ^= 1;
+    assert!(client
+        .try_verify_ed25519(&pubkey, &msg, &BytesN::from_array(&env, &bad_sig))
+        .is_err());
+}
+
+#[test]
+fn test_check_ed25519_turns_trap_into_false() {
+    let (env, client) = setup();
+    let verifier = env.register_contract(None, CryptoContract);
+    let (pubkey, msg, sig) = ed25519_signed(&env, b"hello");
+
+    assert!(client.check_ed25519(&verifier, &pubkey, &msg, &sig));
+    let other = Bytes::from_slice(&env, b"hellp");
+    assert!(!client.check_ed25519(&verifier, &pubkey, &other, &sig));
+}
+
+#[test]
+fn test_secp256k1_recover_valid_and_tampered() {
+    let (env, client) = setup();
+    let digest = client.keccak256(&Bytes::from_slice(&env, b"transfer 100 to bob"));
+    let (sig, public) = secp256k1_signed(&env, &digest);
+
+    assert_eq!(
+        client.recover_secp256k1(&digest, &BytesN::from_array(&env, &sig)),
+        public
+    );
+    // Ethereum-style recovery id.
+    let mut eth_sig = sig;
+    eth_sig[64] += 27;
+    assert_eq!(
+        client.recover_secp256k1(&digest, &BytesN::from_array(&env, &eth_sig)),
+        public
+    );
+
+    // Another digest recovers some other key, or none at all.
+    let mut other = digest.to_array();
+    other[0] ^= 1;
+    let recovered = client.try_recover_secp256k1(
+        &BytesN::from_array(&env, &other),
+        &BytesN::from_array(&env, &sig),
+    );
+    assert_ne!(recovered, Ok(Ok(public)));
+}
+
+#[test]
+fn test_secp256k1_rejects_bad_recovery_id() {
+    let (env, client) = setup();
+    let digest = client.sha256(&Bytes::from_slice(&env, b"x"));
+    let (mut sig, _) = secp256k1_signed(&env, &digest);
+    sig[64] = 2;
+
+    assert_eq!(
+        client.try_recover_secp256k1(&digest, &BytesN::from_array(&env, &sig)),
+        Err(Ok(CryptoError::InvalidRecoveryId))
+    );
+}
@@ -0,0 +1,116 @@
+//! # Storage Schema Migration (v1)
+//!
+//! The starting point of an upgrade that changes a storage layout. v1 keeps
+//! one `u64` balance per user under `DataKey::User(address)`. `v2/` stores a
+//! `UserData` struct under the same key instead.
+//!
+//! An upgrade swaps the code, not the data, so right after
+//! `upgrade_and_migrate` every user entry is still in the v1 layout. v2
+//! handles that in two ways:
+//!
+//! - its reads accept both layouts, so the contract keeps working while
+//!   entries are converted,
+//! - `migrate(users)` rewrites a batch of entries in the new layout. Batches
+//!   keep each call well inside the resource limits, and entries already
+//!   converted are skipped, so a batch can be retried safely.
+//!
+//! Both versions are compiled into the wasm fixtures under `fixtures/` that
+//! the tests upgrade between.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Schema of the user entries this code writes. 1 in v1.
+    Schema,
+    /// v1 layout: the user's balance as a bare `u64`.
+    User(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MigrationError {
+    AlreadyInitialized = 1,
+    NotAdmin = 2,
+}
+
+#[contract]
+pub struct MigrationContract;
+
+#[contractimpl]
+impl MigrationContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), MigrationError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(MigrationError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Schema, &1u32);
+        Ok(())
+    }
+
+    /// Set `user`'s balance. Admin only.
+    pub fn set_user(
+        env: Env,
+        admin: Address,
+        user: Address,
+        balance: u64,
+    ) -> Result<(), MigrationError> {
+        Self::ensure_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::User(user), &balance);
+        Ok(())
+    }
+
+    /// `user`'s balance, 0 if never set.
+    pub fn get_user(env: Env, user: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::User(user))
+            .unwrap_or(0)
+    }
+
+    /// The schema of the entries this code writes.
+    pub fn schema(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Schema).unwrap_or(0)
+    }
+
+    /// Install `new_wasm_hash`. Admin only.
+    ///
+    /// The new code runs from the next invocation, and user entries are left
+    /// as they are: the new version is responsible for reading the old layout
+    /// and converting it. Publishes `("upgraded",)` with the hash.
+    pub fn upgrade_and_migrate(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), MigrationError> {
+        Self::ensure_admin(&env, &admin)?;
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+        Ok(())
+    }
+}
+
+impl MigrationContract {
+    fn ensure_admin(env: &Env, caller: &Address) -> Result<(), MigrationError> {
+        caller.require_auth();
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if admin.as_ref() != Some(caller) {
+            return Err(MigrationError::NotAdmin);
+        }
+        Ok(())
+    }
+}
+
+mod test;
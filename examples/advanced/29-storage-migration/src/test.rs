@@ -0,0 +1,183 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger as _},
+    vec, Address, Env, IntoVal, Vec,
+};
+
+mod v1 {
+    soroban_sdk::contractimport!(file = "fixtures/storage_migration_v1.wasm");
+}
+
+mod v2 {
+    soroban_sdk::contractimport!(file = "fixtures/storage_migration_v2.wasm");
+}
+
+struct Setup {
+    env: Env,
+    contract_id: Address,
+    admin: Address,
+    users: Vec<Address>,
+}
+
+/// Deploys the v1 fixture and gives five users balances 100, 200, ... 500.
+fn setup_v1() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract_wasm(None, v1::WASM);
+    let client = v1::Client::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let mut users = Vec::new(&env);
+    for i in 1..=5u64 {
+        let user = Address::generate(&env);
+        client.set_user(&admin, &user, &(i * 100));
+        users.push_back(user);
+    }
+    Setup {
+        env,
+        contract_id,
+        admin,
+        users,
+    }
+}
+
+/// Upgrades the v1 deployment in `s` to v2 and returns a v2 client for it.
+fn upgrade(s: &Setup) -> v2::Client<'static> {
+    let hash = s.env.deployer().upload_contract_wasm(v2::WASM);
+    v1::Client::new(&s.env, &s.contract_id).upgrade_and_migrate(&s.admin, &hash);
+    v2::Client::new(&s.env, &s.contract_id)
+}
+
+fn carried_over(balance: u64) -> v2::UserData {
+    v2::UserData {
+        balance,
+        updated_at: 0,
+    }
+}
+
+#[test]
+fn test_v1_admin_only_writes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client =
+        MigrationContractClient::new(&env, &env.register_contract(None, MigrationContract));
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin);
+
+    assert_eq!(client.schema(), 1);
+    assert_eq!(client.get_user(&user), 0);
+    client.set_user(&admin, &user, &7);
+    assert_eq!(client.get_user(&user), 7);
+    assert_eq!(
+        client.try_set_user(&user, &user, &8),
+        Err(Ok(MigrationError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_initialize(&user),
+        Err(Ok(MigrationError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_v2_reads_unmigrated_entries() {
+    let s = setup_v1();
+    let client = upgrade(&s);
+
+    // Nothing has been converted yet, but every read already works.
+    assert_eq!(client.schema(), 1);
+    for (i, user) in s.users.iter().enumerate() {
+        assert!(!client.is_migrated(&user));
+        assert_eq!(client.get_user(&user), carried_over((i as u64 + 1) * 100));
+    }
+    let stranger = Address::generate(&s.env);
+    assert_eq!(client.get_user(&stranger), carried_over(0));
+    assert!(!client.is_migrated(&stranger));
+}
+
+#[test]
+fn test_batch_migration_preserves_values() {
+    let s = setup_v1();
+    let client = upgrade(&s);
+
+    let mut counts = std::vec::Vec::new();
+    for start in (0..s.users.len()).step_by(2) {
+        let end = (start + 2).min(s.users.len());
+        counts.push(client.migrate(&s.users.slice(start..end)));
+        // Events are per invocation here: each batch reports its own count.
+        let (_, topics, data) = s.env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("migrated"),).into_val(&s.env));
+        let reported: u32 = data.into_val(&s.env);
+        assert_eq!(reported, *counts.last().unwrap());
+    }
+    assert_eq!(counts, [2, 2, 1]);
+
+    assert_eq!(client.schema(), 2);
+    for (i, user) in s.users.iter().enumerate() {
+        assert!(client.is_migrated(&user));
+        assert_eq!(client.get_user(&user), carried_over((i as u64 + 1) * 100));
+    }
+}
+
+#[test]
+fn test_double_migration_is_idempotent() {
+    let s = setup_v1();
+    let client = upgrade(&s);
+
+    assert_eq!(client.migrate(&s.users), 5);
+    let before: std::vec::Vec<_> = s.users.iter().map(|u| client.get_user(&u)).collect();
+    assert_eq!(client.migrate(&s.users), 0);
+    let after: std::vec::Vec<_> = s.users.iter().map(|u| client.get_user(&u)).collect();
+    assert_eq!(before, after);
+
+    // Overlapping and unknown entries in a batch are skipped too.
+    let stranger = Address::generate(&s.env);
+    let batch = vec![&s.env, s.users.get(0).unwrap(), stranger.clone()];
+    assert_eq!(client.migrate(&batch), 0);
+    assert!(!client.is_migrated(&stranger));
+}
+
+#[test]
+fn test_v2_write_converts_single_entry() {
+    let s = setup_v1();
+    let client = upgrade(&s);
+    let user = s.users.get(2).unwrap();
+    s.env.ledger().with_mut(|l| l.sequence_number = 1_000);
+
+    client.set_user(&s.admin, &user, &350);
+    assert!(client.is_migrated(&user));
+    assert_eq!(
+        client.get_user(&user),
+        v2::UserData {
+            balance: 350,
+            updated_at: 1_000,
+        }
+    );
+
+    // The batch only converts the four entries still in the v1 layout and
+    // leaves the fresh write alone.
+    assert_eq!(client.migrate(&s.users), 4);
+    assert_eq!(client.get_user(&user).updated_at, 1_000);
+}
+
+#[test]
+fn test_non_admin_cannot_upgrade() {
+    let s = setup_v1();
+    let hash = s.env.deployer().upload_contract_wasm(v2::WASM);
+    let attacker = Address::generate(&s.env);
+
+    let client = v1::Client::new(&s.env, &s.contract_id);
+    assert_eq!(
+        client.try_upgrade_and_migrate(&attacker, &hash),
+        Err(Ok(v1::MigrationError::NotAdmin))
+    );
+    // Still v1: the v2-only entry point does not exist.
+    let not_yet = v2::Client::new(&s.env, &s.contract_id);
+    assert!(not_yet.try_migrate(&s.users).is_err());
+    assert_eq!(client.get_user(&s.users.get(0).unwrap()), 100);
+}
@@ -0,0 +1,181 @@
+//! # Storage Schema Migration (v2)
+//!
+//! Second version of `examples/advanced/29-storage-migration`, used as an
+//! upgrade target by that crate's tests. `DataKey::User(address)` now holds a
+//! `UserData` struct instead of a bare `u64`.
+//!
+//! Until `migrate` has converted an entry it is still a `u64`, so every read
+//! goes through `load`, which accepts either layout. Entries are converted
+//! in batches by `migrate` and individually by any write.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    TryFromVal, Val, Vec,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Schema of the user entries. Set to 2 by `initialize` or the first
+    /// `migrate`, so it still reads 1 right after an upgrade from v1.
+    Schema,
+    /// v1 layout: `u64`. v2 layout: `UserData`.
+    User(Address),
+}
+
+/// A user entry in the v2 layout.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserData {
+    pub balance: u64,
+    /// Ledger sequence of the last write through v2. 0 for entries that
+    /// were carried over from v1 and not written since.
+    pub updated_at: u32,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MigrationError {
+    AlreadyInitialized = 1,
+    NotAdmin = 2,
+    /// A stored entry is in neither the v1 nor the v2 layout.
+    CorruptEntry = 3,
+}
+
+const SCHEMA: u32 = 2;
+
+/// A stored user entry, in whichever layout it was found.
+enum Stored {
+    V1(u64),
+    V2(UserData),
+}
+
+#[contract]
+pub struct MigrationContract;
+
+#[contractimpl]
+impl MigrationContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), MigrationError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(MigrationError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Schema, &SCHEMA);
+        Ok(())
+    }
+
+    /// Set `user`'s balance, writing the entry in the v2 layout. Admin only.
+    pub fn set_user(
+        env: Env,
+        admin: Address,
+        user: Address,
+        balance: u64,
+    ) -> Result<(), MigrationError> {
+        Self::ensure_admin(&env, &admin)?;
+        let data = UserData {
+            balance,
+            updated_at: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&DataKey::User(user), &data);
+        Ok(())
+    }
+
+    /// `user`'s entry in the v2 shape, whichever layout it is stored in.
+    /// A zero balance if never set.
+    pub fn get_user(env: Env, user: Address) -> Result<UserData, MigrationError> {
+        Ok(match Self::load(&env, &user)? {
+            None => UserData {
+                balance: 0,
+                updated_at: 0,
+            },
+            Some(Stored::V1(balance)) => UserData {
+                balance,
+                updated_at: 0,
+            },
+            Some(Stored::V2(data)) => data,
+        })
+    }
+
+    /// Whether `user`'s entry is stored in the v2 layout. `false` for users
+    /// without an entry.
+    pub fn is_migrated(env: Env, user: Address) -> Result<bool, MigrationError> {
+        Ok(matches!(Self::load(&env, &user)?, Some(Stored::V2(_))))
+    }
+
+    /// Rewrite the entries of `users` in the v2 layout and return how many
+    /// were converted.
+    ///
+    /// Users without an entry, and entries already in the v2 layout, are
+    /// skipped, so calling this twice with the same batch converts nothing
+    /// the second time. Anyone may call it: it never changes a balance.
+    /// Publishes `("migrated",)` with the count.
+    pub fn migrate(env: Env, users: Vec<Address>) -> Result<u32, MigrationError> {
+        let mut converted: u32 = 0;
+        for user in users.iter() {
+            if let Some(Stored::V1(balance)) = Self::load(&env, &user)? {
+                let data = UserData {
+                    balance,
+                    updated_at: 0,
+                };
+                env.storage().persistent().set(&DataKey::User(user), &data);
+                converted = converted.saturating_add(1);
+            }
+        }
+        env.storage().instance().set(&DataKey::Schema, &SCHEMA);
+        env.events()
+            .publish((symbol_short!("migrated"),), converted);
+        Ok(converted)
+    }
+
+    /// 1 until the first `migrate` after an upgrade from v1, 2 afterwards.
+    pub fn schema(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Schema).unwrap_or(0)
+    }
+
+    /// Install `new_wasm_hash`. Admin only. Same contract as in v1, so the
+    /// next version can follow the same path.
+    pub fn upgrade_and_migrate(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), MigrationError> {
+        Self::ensure_admin(&env, &admin)?;
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+        Ok(())
+    }
+}
+
+impl MigrationContract {
+    fn ensure_admin(env: &Env, caller: &Address) -> Result<(), MigrationError> {
+        caller.require_auth();
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if admin.as_ref() != Some(caller) {
+            return Err(MigrationError::NotAdmin);
+        }
+        Ok(())
+    }
+
+    /// Read `user`'s entry as a raw value and decide its layout from the
+    /// value's type: a `u64` is v1, a map with the `UserData` fields is v2.
+    fn load(env: &Env, user: &Address) -> Result<Option<Stored>, MigrationError> {
+        let key = DataKey::User(user.clone());
+        let Some(raw) = env.storage().persistent().get::<_, Val>(&key) else {
+            return Ok(None);
+        };
+        if let Ok(balance) = u64::try_from_val(env, &raw) {
+            return Ok(Some(Stored::V1(balance)));
+        }
+        match UserData::try_from_val(env, &raw) {
+            Ok(data) => Ok(Some(Stored::V2(data))),
+            Err(_) => Err(MigrationError::CorruptEntry),
+        }
+    }
+}
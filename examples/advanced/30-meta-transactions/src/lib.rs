@@ -0,0 +1,198 @@
+//! # Meta-Transactions
+//!
+//! A user signs an `Intent` off-chain with an ed25519 key, and anyone (a
+//! relayer) submits it with `execute_signed`. The relayer pays the fees; the
+//! action is attributed to the user's key, which never has to hold an
+//! account or sign a transaction.
+//!
+//! ## What the signature covers
+//!
+//! The signed message is `payload(intent)`: the XDR encoding of this
+//! contract's address and the whole intent. Binding the contract address
+//! keeps an intent from being replayed against another deployment, and
+//! signing every field means a relayer cannot change the action, the
+//! parameters, the nonce or the expiry.
+//!
+//! ## Replay protection
+//!
+//! Each key has a nonce that starts at 0. An intent must carry exactly the
+//! current nonce, which is bumped when it executes, so every signed intent
+//! runs at most once and in the order the user signed them. `expiry` bounds
+//! how long a relayer can hold on to an intent before submitting it.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
+    BytesN, Env,
+};
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MetaTxError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// The ledger timestamp is past the intent's `expiry`.
+    /// `CommonError::Expired`.
+    Expired = 7,
+    /// A zero or negative amount. `CommonError::InvalidInput`.
+    InvalidAmount = 8,
+    /// The intent's nonce is not the signer's current nonce.
+    BadNonce = 100,
+    InsufficientBalance = 101,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Balance of an ed25519 public key.
+    Balance(BytesN<32>),
+    /// The nonce the key's next intent must carry.
+    Nonce(BytesN<32>),
+}
+
+/// Parameters of `Action::Transfer`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferParams {
+    pub to: BytesN<32>,
+    pub amount: i128,
+}
+
+/// What the signer wants done.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Move `amount` from the signer's balance to `to`.
+    Transfer(TransferParams),
+}
+
+/// A signed request to perform `action` on behalf of the signing key.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Intent {
+    pub action: Action,
+    /// Must equal the signer's current nonce.
+    pub nonce: u64,
+    /// Last ledger timestamp at which the intent may execute.
+    pub expiry: u64,
+}
+
+#[contract]
+pub struct MetaTxContract;
+
+#[contractimpl]
+impl MetaTxContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), MetaTxError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(MetaTxError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Credit `amount` to the key `to`. Admin only.
+    pub fn mint(env: Env, to: BytesN<32>, amount: i128) -> Result<(), MetaTxError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(MetaTxError::NotInitialized)?;
+        admin.require_auth();
+        if amount <= 0 {
+            return Err(MetaTxError::InvalidAmount);
+        }
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to), &(balance + amount));
+        Ok(())
+    }
+
+    /// The bytes a user signs to authorize `intent` on this contract.
+    pub fn payload(env: Env, intent: Intent) -> Bytes {
+        (env.current_contract_address(), intent).to_xdr(&env)
+    }
+
+    /// Execute `intent` on behalf of `user`, the key that signed it.
+    ///
+    /// Anyone may submit it. A signature that does not match `user` and
+    /// `payload(intent)` fails the invocation. The intent must carry
+    /// `user`'s current nonce and not be expired; the nonce is bumped before
+    /// the action runs.
+    pub fn execute_signed(
+        env: Env,
+        user: BytesN<32>,
+        intent: Intent,
+        signature: BytesN<64>,
+    ) -> Result<(), MetaTxError> {
+        let payload = Self::payload(env.clone(), intent.clone());
+        env.crypto().ed25519_verify(&user, &payload, &signature);
+
+        let nonce = Self::nonce(env.clone(), user.clone());
+        if intent.nonce != nonce {
+            return Err(MetaTxError::BadNonce);
+        }
+        if env.ledger().timestamp() > intent.expiry {
+            return Err(MetaTxError::Expired);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Nonce(user.clone()), &(nonce + 1));
+
+        match intent.action {
+            Action::Transfer(params) => Self::transfer(&env, user, params),
+        }
+    }
+
+    pub fn balance(env: Env, user: BytesN<32>) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    /// The nonce `user`'s next intent must carry.
+    pub fn nonce(env: Env, user: BytesN<32>) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Nonce(user))
+            .unwrap_or(0)
+    }
+}
+
+impl MetaTxContract {
+    /// Publishes `("transfer", from, to)` with the amount. The topics name
+    /// the signer, not whoever submitted the transaction.
+    fn transfer(env: &Env, from: BytesN<32>, params: TransferParams) -> Result<(), MetaTxError> {
+        if params.amount <= 0 {
+            return Err(MetaTxError::InvalidAmount);
+        }
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < params.amount {
+            return Err(MetaTxError::InsufficientBalance);
+        }
+        let storage = env.storage().persistent();
+        storage.set(
+            &DataKey::Balance(from.clone()),
+            &(from_balance - params.amount),
+        );
+        // Read after the debit so a transfer to oneself is a no-op.
+        let to_balance = Self::balance(env.clone(), params.to.clone());
+        storage.set(
+            &DataKey::Balance(params.to.clone()),
+            &(to_balance + params.amount),
+        );
+        env.events()
+            .publish((symbol_short!("transfer"), from, params.to), params.amount);
+        Ok(())
+    }
+}
+
+mod test;
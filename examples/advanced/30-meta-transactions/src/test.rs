@@ -0,0 +1,288 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use recipes_errors::CommonError;
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger as _},
+    vec, IntoVal,
+};
+
+struct Setup {
+    env: Env,
+    client: MetaTxContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = MetaTxContractClient::new(&env, &env.register_contract(None, MetaTxContract));
+    client.initialize(&Address::generate(&env));
+    Setup { env, client }
+}
+
+/// An off-chain user: an ed25519 key and its public key as the contract
+/// sees it.
+struct User {
+    key: SigningKey,
+    public: BytesN<32>,
+}
+
+fn user(env: &Env, seed: u8) -> User {
+    let key = SigningKey::from_bytes(&[seed; 32]);
+    let public = BytesN::from_array(env, &key.verifying_key().to_bytes());
+    User { key, public }
+}
+
+fn transfer(to: &User, amount: i128, nonce: u64, expiry: u64) -> Intent {
+    Intent {
+        action: Action::Transfer(TransferParams {
+            to: to.public.clone(),
+            amount,
+        }),
+        nonce,
+        expiry,
+    }
+}
+
+/// Sign `intent` for the contract at `contract` the way a wallet would,
+/// building the payload without asking the contract.
+fn sign(env: &Env, contract: &Address, signer: &User, intent: &Intent) -> BytesN<64> {
+    let payload = (contract.clone(), intent.clone()).to_xdr(env);
+    let mut msg = std::vec![0u8; payload.len() as usize];
+    payload.copy_into_slice(&mut msg);
+    BytesN::from_array(env, &signer.key.sign(&msg).to_bytes())
+}
+
+impl Setup {
+    fn sign(&self, signer: &User, intent: &Intent) -> BytesN<64> {
+        sign(&self.env, &self.client.address, signer, intent)
+    }
+
+    fn relay(&self, signer: &User, intent: &Intent) {
+        let sig = self.sign(signer, intent);
+        self.client.execute_signed(&signer.public, intent, &sig);
+    }
+}
+
+#[test]
+fn test_relayed_transfer_attributed_to_user() {
+    let s = setup();
+    let (alice, bob) = (user(&s.env, 1), user(&s.env, 2));
+    s.client.mint(&alice.public, &100);
+
+    let intent = transfer(&bob, 30, 0, 1_000);
+    assert_eq!(
+        s.client.payload(&intent),
+        (s.client.address.clone(), intent.clone()).to_xdr(&s.env)
+    );
+    let sig = s.sign(&alice, &intent);
+    s.client.execute_signed(&alice.public, &intent, &sig);
+
+    // No address authorized anything: the signature alone carried the call.
+    assert_eq!(s.env.auths(), std::vec![]);
+    assert_eq!(s.client.balance(&alice.public), 70);
+    assert_eq!(s.client.balance(&bob.public), 30);
+    assert_eq!(s.client.nonce(&alice.public), 1);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            (
+                s.client.address.clone(),
+                (symbol_short!("transfer"), alice.public, bob.public).into_val(&s.env),
+                30i128.into_val(&s.env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_replayed_intent_rejected() {
+    let s = setup();
+    let (alice, bob) = (user(&s.env, 1), user(&s.env, 2));
+    s.client.mint(&alice.public, &100);
+
+    let intent = transfer(&bob, 30, 0, 1_000);
+    let sig = s.sign(&alice, &intent);
+    s.client.execute_signed(&alice.public, &intent, &sig);
+
+    // The same signed intent a second time.
+    assert_eq!(
+        s.client.try_execute_signed(&alice.public, &intent, &sig),
+        Err(Ok(MetaTxError::BadNonce))
+    );
+    // Nonces cannot be skipped either.
+    let ahead = transfer(&bob, 30, 2, 1_000);
+    assert_eq!(
+        s.client
+            .try_execute_signed(&alice.public, &ahead, &s.sign(&alice, &ahead)),
+        Err(Ok(MetaTxError::BadNonce))
+    );
+    assert_eq!(s.client.balance(&alice.public), 70);
+    assert_eq!(s.client.nonce(&alice.public), 1);
+}
+
+#[test]
+fn test_expired_intent_rejected() {
+    let s = setup();
+    let (alice, bob) = (user(&s.env, 1), user(&s.env, 2));
+    s.client.mint(&alice.public, &100);
+    let intent = transfer(&bob, 30, 0, 500);
+    let sig = s.sign(&alice, &intent);
+
+    s.env.ledger().with_mut(|l| l.timestamp = 501);
+    assert_eq!(
+        s.client.try_execute_signed(&alice.public, &intent, &sig),
+        Err(Ok(MetaTxError::Expired))
+    );
+    assert_eq!(s.client.nonce(&alice.public), 0);
+
+    // The user signs a fresh intent with the same nonce.
+    s.relay(&alice, &transfer(&bob, 30, 0, 600));
+    assert_eq!(s.client.balance(&bob.public), 30);
+}
+
+#[test]
+fn test_tampered_intent_rejected() {
+    let s = setup();
+    let (alice, bob, mallory) = (user(&s.env, 1), user(&s.env, 2), user(&s.env, 3));
+    s.client.mint(&alice.public, &100);
+    let intent = transfer(&bob, 10, 0, 1_000);
+    let sig = s.sign(&alice, &intent);
+
+    // A relayer raising the amount or redirecting the funds.
+    let raised = transfer(&bob, 90, 0, 1_000);
+    assert!(s
+        .client
+        .try_execute_signed(&alice.public, &raised, &sig)
+        .is_err());
+    let redirected = transfer(&mallory, 10, 0, 1_000);
+    assert!(s
+        .client
+        .try_execute_signed(&alice.public, &redirected, &sig)
+        .is_err());
+    // Mallory's own signature does not speak for Alice.
+    let forged = s.sign(&mallory, &redirected);
+    assert!(s
+        .client
+        .try_execute_signed(&alice.public, &redirected, &forged)
+        .is_err());
+    // Nor does a signature meant for another deployment.
+    let elsewhere = sign(&s.env, &Address::generate(&s.env), &alice, &intent);
+    assert!(s
+        .client
+        .try_execute_signed(&alice.public, &intent, &elsewhere)
+        .is_err());
+
+    assert_eq!(s.client.balance(&alice.public), 100);
+    assert_eq!(s.client.balance(&mallory.public), 0);
+    assert_eq!(s.client.nonce(&alice.public), 0);
+
+    // The untouched intent still goes through.
+    s.client.execute_signed(&alice.public, &intent, &sig);
+    assert_eq!(s.client.balance(&bob.public), 10);
+}
+
+#[test]
+fn test_relayer_submits_for_two_users() {
+    let s = setup();
+    let (alice, bob, carol) = (user(&s.env, 1), user(&s.env, 2), user(&s.env, 3));
+    s.client.mint(&alice.public, &100);
+    s.client.mint(&bob.public, &50);
+
+    // Both users start at nonce 0; the relayer interleaves their intents.
+    s.relay(&alice, &transfer(&carol, 10, 0, 1_000));
+    s.relay(&bob, &transfer(&carol, 5, 0, 1_000));
+    s.relay(&alice, &transfer(&bob, 20, 1, 1_000));
+
+    assert_eq!(s.client.nonce(&alice.public), 2);
+    assert_eq!(s.client.nonce(&bob.public), 1);
+    assert_eq!(s.client.balance(&alice.public), 70);
+    assert_eq!(s.client.balance(&bob.public), 65);
+    assert_eq!(s.client.balance(&carol.public), 15);
+
+    let event = |from: &User, to: &User, amount: i128| {
+        (
+            s.client.address.clone(),
+            (
+                symbol_short!("transfer"),
+                from.public.clone(),
+                to.public.clone(),
+            )
+                .into_val(&s.env),
+            amount.into_val(&s.env),
+        )
+    };
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&alice, &carol, 10),
+            event(&bob, &carol, 5),
+            event(&alice, &bob, 20),
+        ]
+    );
+
+    // Bob's signature over Alice's next intent does not move Alice's funds.
+    let intent = transfer(&bob, 70, 2, 1_000);
+    assert!(s
+        .client
+        .try_execute_signed(&alice.public, &intent, &s.sign(&bob, &intent))
+        .is_err());
+    assert_eq!(
+        s.client
+            .try_execute_signed(&bob.public, &intent, &s.sign(&bob, &intent)),
+        Err(Ok(MetaTxError::BadNonce))
+    );
+}
+
+#[test]
+fn test_action_errors() {
+    let s = setup();
+    let (alice, bob) = (user(&s.env, 1), user(&s.env, 2));
+    s.client.mint(&alice.public, &100);
+
+    let overdraw = transfer(&bob, 101, 0, 1_000);
+    assert_eq!(
+        s.client
+            .try_execute_signed(&alice.public, &overdraw, &s.sign(&alice, &overdraw)),
+        Err(Ok(MetaTxError::InsufficientBalance))
+    );
+    let zero = transfer(&bob, 0, 0, 1_000);
+    assert_eq!(
+        s.client
+            .try_execute_signed(&alice.public, &zero, &s.sign(&alice, &zero)),
+        Err(Ok(MetaTxError::InvalidAmount))
+    );
+    // A failed action does not use up the nonce.
+    assert_eq!(s.client.nonce(&alice.public), 0);
+
+    // Sending to oneself leaves the balance as it was.
+    s.relay(&alice, &transfer(&alice, 40, 0, 1_000));
+    assert_eq!(s.client.balance(&alice.public), 100);
+
+    assert_eq!(
+        s.client.try_initialize(&Address::generate(&s.env)),
+        Err(Ok(MetaTxError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (MetaTxError::NotInitialized, CommonError::NotInitialized),
+        (
+            MetaTxError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (MetaTxError::Expired, CommonError::Expired),
+        (MetaTxError::InvalidAmount, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(MetaTxError::BadNonce as u32 >= recipes_errors::CONTRACT_MIN);
+}
@@ -0,0 +1,313 @@
+//! # Subscription Billing
+//!
+//! Merchants publish plans that charge a fixed `amount` of a token every
+//! `period` seconds. A user subscribes by approving this contract as a
+//! spender on the plan's token and calling `subscribe`, which pulls the
+//! first payment. After that, anyone (usually the merchant's billing job)
+//! may call `charge` once per period to pull the next one.
+//!
+//! A failed pull does not trap. The subscription moves through:
+//!
+//! ```text
+//! Active --pull fails--> Grace --pull fails after grace window--> Cancelled
+//!   ^                      |
+//!   +------pull succeeds---+
+//! ```
+//!
+//! While in `Grace`, `charge` retries at any time. The plan's grace window
+//! (`DEFAULT_GRACE_SECS` unless the merchant changes it) is how long the
+//! user has to top up their balance or allowance before the subscription is
+//! cancelled. Users can also cancel at any time.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+    IntoVal, Symbol, Val,
+};
+
+/// Grace window of a new plan: three days.
+pub const DEFAULT_GRACE_SECS: u64 = 3 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextPlanId,
+    Plan(u32),
+    /// `(plan_id, user)`.
+    Subscription(u32, Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Plan {
+    pub merchant: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub period: u64,
+    /// How long a subscription stays in `Grace` before a failed charge
+    /// cancels it.
+    pub grace: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    Active,
+    /// The last charge failed; payment is retried until `grace_until`.
+    Grace,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub status: Status,
+    /// Timestamp of the last successful charge.
+    pub last_charged: u64,
+    /// End of the grace window. Only meaningful in `Grace`.
+    pub grace_until: u64,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SubscriptionError {
+    InvalidAmount = 1,
+    /// A zero period.
+    InvalidPeriod = 2,
+    PlanNotFound = 3,
+    NotMerchant = 4,
+    /// The user already has an active or grace subscription to the plan.
+    AlreadySubscribed = 5,
+    NotSubscribed = 6,
+    /// The subscription is cancelled.
+    NotActive = 7,
+    /// A full period has not passed since the last charge.
+    TooEarly = 8,
+    /// The first payment in `subscribe` could not be pulled.
+    PaymentFailed = 9,
+}
+
+#[contract]
+pub struct SubscriptionContract;
+
+#[contractimpl]
+impl SubscriptionContract {
+    /// Register a plan charging `amount` of `token` every `period_secs` and
+    /// return its id.
+    pub fn create_plan(
+        env: Env,
+        merchant: Address,
+        token: Address,
+        amount: i128,
+        period_secs: u64,
+    ) -> Result<u32, SubscriptionError> {
+        merchant.require_auth();
+        if amount <= 0 {
+            return Err(SubscriptionError::InvalidAmount);
+        }
+        if period_secs == 0 {
+            return Err(SubscriptionError::InvalidPeriod);
+        }
+        let id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextPlanId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextPlanId, &(id + 1));
+        let plan = Plan {
+            merchant,
+            token,
+            amount,
+            period: period_secs,
+            grace: DEFAULT_GRACE_SECS,
+        };
+        env.storage().persistent().set(&DataKey::Plan(id), &plan);
+        env.events()
+            .publish((symbol_short!("plan"), symbol_short!("created"), id), plan);
+        Ok(id)
+    }
+
+    /// Change the grace window of `plan_id`. Merchant only; applies to
+    /// subscriptions entering `Grace` from now on.
+    pub fn set_grace(
+        env: Env,
+        merchant: Address,
+        plan_id: u32,
+        grace_secs: u64,
+    ) -> Result<(), SubscriptionError> {
+        merchant.require_auth();
+        let mut plan = Self::load_plan(&env, plan_id)?;
+        if merchant != plan.merchant {
+            return Err(SubscriptionError::NotMerchant);
+        }
+        plan.grace = grace_secs;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Plan(plan_id), &plan);
+        env.events().publish(
+            (symbol_short!("plan"), symbol_short!("grace"), plan_id),
+            grace_secs,
+        );
+        Ok(())
+    }
+
+    /// Subscribe `user` to `plan_id` and pull the first payment.
+    ///
+    /// The user must have approved this contract to spend the plan's token
+    /// beforehand; the allowance bounds how many periods can be charged.
+    pub fn subscribe(env: Env, user: Address, plan_id: u32) -> Result<(), SubscriptionError> {
+        user.require_auth();
+        let plan = Self::load_plan(&env, plan_id)?;
+        if let Some(sub) = Self::get_subscription(env.clone(), plan_id, user.clone()) {
+            if sub.status != Status::Cancelled {
+                return Err(SubscriptionError::AlreadySubscribed);
+            }
+        }
+        if !Self::pull(&env, &plan, &user) {
+            return Err(SubscriptionError::PaymentFailed);
+        }
+        let sub = Subscription {
+            status: Status::Active,
+            last_charged: env.ledger().timestamp(),
+            grace_until: 0,
+        };
+        Self::save(&env, plan_id, &user, &sub);
+        Self::publish(&env, symbol_short!("started"), plan_id, &user, plan.amount);
+        Ok(())
+    }
+
+    /// Pull the next payment for `user`'s subscription and return its
+    /// status afterwards.
+    ///
+    /// An active subscription can be charged once a full period has passed
+    /// since the last charge; a subscription in `Grace` at any time. A
+    /// failed pull moves an active subscription to `Grace`, and cancels one
+    /// whose grace window has ended. Neither is an error, so the state
+    /// change is kept.
+    pub fn charge(env: Env, plan_id: u32, user: Address) -> Result<Status, SubscriptionError> {
+        let plan = Self::load_plan(&env, plan_id)?;
+        let mut sub = Self::load_subscription(&env, plan_id, &user)?;
+        let now = env.ledger().timestamp();
+        match sub.status {
+            Status::Cancelled => return Err(SubscriptionError::NotActive),
+            Status::Active if now < sub.last_charged.saturating_add(plan.period) => {
+                return Err(SubscriptionError::TooEarly)
+            }
+            _ => {}
+        }
+
+        if Self::pull(&env, &plan, &user) {
+            let resumed = sub.status == Status::Grace;
+            sub.status = Status::Active;
+            sub.last_charged = now;
+            Self::publish(&env, symbol_short!("charged"), plan_id, &user, plan.amount);
+            if resumed {
+                Self::publish(&env, symbol_short!("resumed"), plan_id, &user, now);
+            }
+        } else if sub.status == Status::Active {
+            sub.status = Status::Grace;
+            sub.grace_until = now.saturating_add(plan.grace);
+            Self::publish(
+                &env,
+                symbol_short!("grace"),
+                plan_id,
+                &user,
+                sub.grace_until,
+            );
+        } else if now >= sub.grace_until {
+            sub.status = Status::Cancelled;
+            Self::publish(
+                &env,
+                symbol_short!("cancelled"),
+                plan_id,
+                &user,
+                symbol_short!("lapsed"),
+            );
+        }
+        Self::save(&env, plan_id, &user, &sub);
+        Ok(sub.status)
+    }
+
+    /// Cancel `user`'s subscription. No further charges are made; the
+    /// user may also want to revoke the token allowance.
+    pub fn cancel(env: Env, user: Address, plan_id: u32) -> Result<(), SubscriptionError> {
+        user.require_auth();
+        let mut sub = Self::load_subscription(&env, plan_id, &user)?;
+        if sub.status == Status::Cancelled {
+            return Err(SubscriptionError::NotActive);
+        }
+        sub.status = Status::Cancelled;
+        Self::save(&env, plan_id, &user, &sub);
+        Self::publish(
+            &env,
+            symbol_short!("cancelled"),
+            plan_id,
+            &user,
+            symbol_short!("user"),
+        );
+        Ok(())
+    }
+
+    pub fn get_plan(env: Env, plan_id: u32) -> Option<Plan> {
+        env.storage().persistent().get(&DataKey::Plan(plan_id))
+    }
+
+    pub fn get_subscription(env: Env, plan_id: u32, user: Address) -> Option<Subscription> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Subscription(plan_id, user))
+    }
+}
+
+impl SubscriptionContract {
+    fn load_plan(env: &Env, plan_id: u32) -> Result<Plan, SubscriptionError> {
+        Self::get_plan(env.clone(), plan_id).ok_or(SubscriptionError::PlanNotFound)
+    }
+
+    fn load_subscription(
+        env: &Env,
+        plan_id: u32,
+        user: &Address,
+    ) -> Result<Subscription, SubscriptionError> {
+        Self::get_subscription(env.clone(), plan_id, user.clone())
+            .ok_or(SubscriptionError::NotSubscribed)
+    }
+
+    fn save(env: &Env, plan_id: u32, user: &Address, sub: &Subscription) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(plan_id, user.clone()), sub);
+    }
+
+    /// Move one period's payment from `user` to the merchant using this
+    /// contract's allowance. `false` if the token rejects the transfer, for
+    /// example for a low balance or allowance; the failed call's effects are
+    /// rolled back.
+    fn pull(env: &Env, plan: &Plan, user: &Address) -> bool {
+        let result = token::Client::new(env, &plan.token).try_transfer_from(
+            &env.current_contract_address(),
+            user,
+            &plan.merchant,
+            &plan.amount,
+        );
+        matches!(result, Ok(Ok(())))
+    }
+
+    /// Publishes `("sub", action, plan_id, user)` with `data`.
+    fn publish(
+        env: &Env,
+        action: Symbol,
+        plan_id: u32,
+        user: &Address,
+        data: impl IntoVal<Env, Val>,
+    ) {
+        env.events()
+            .publish((symbol_short!("sub"), action, plan_id, user.clone()), data);
+    }
+}
+
+mod test;
@@ -0,0 +1,280 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::{all_events_for, set_timestamp};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env, Vec,
+};
+
+const START: u64 = 1_000;
+const PERIOD: u64 = 30 * 24 * 60 * 60;
+const PRICE: i128 = 100;
+
+struct Setup {
+    env: Env,
+    merchant: Address,
+    user: Address,
+    token: TokenClient<'static>,
+    subs: SubscriptionContractClient<'static>,
+    plan: u32,
+}
+
+/// A plan for `PRICE` every `PERIOD`, and a user holding `balance` who has
+/// approved the contract for ten periods.
+fn setup(balance: i128) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, START);
+    let merchant = Address::generate(&env);
+    let user = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let token = TokenClient::new(&env, &asset.address());
+    let subs =
+        SubscriptionContractClient::new(&env, &env.register_contract(None, SubscriptionContract));
+    let plan = subs.create_plan(&merchant, &token.address, &PRICE, &PERIOD);
+
+    StellarAssetClient::new(&env, &asset.address()).mint(&user, &balance);
+    approve(&env, &token, &user, &subs.address, 10 * PRICE);
+    Setup {
+        env,
+        merchant,
+        user,
+        token,
+        subs,
+        plan,
+    }
+}
+
+fn approve(env: &Env, token: &TokenClient, user: &Address, spender: &Address, amount: i128) {
+    let expiration = env.ledger().sequence() + 1_000;
+    token.approve(user, spender, &amount, &expiration);
+}
+
+impl Setup {
+    fn at(&self, offset: u64) {
+        set_timestamp(&self.env, START + offset);
+    }
+
+    fn status(&self) -> Status {
+        self.subs
+            .get_subscription(&self.plan, &self.user)
+            .unwrap()
+            .status
+    }
+
+    /// Assert the `("sub", action, plan, user)` events published so far,
+    /// given as `(action, data)`.
+    fn assert_sub_events(&self, expected: &[(Symbol, Val)]) {
+        let mut actual = Vec::<(Vec<Val>, Val)>::new(&self.env);
+        for (topics, data) in all_events_for(&self.env, &self.subs.address) {
+            if topics.len() == 4 {
+                actual.push_back((topics, data));
+            }
+        }
+        let mut want = Vec::<(Vec<Val>, Val)>::new(&self.env);
+        for (action, data) in expected {
+            let topics = (
+                symbol_short!("sub"),
+                action.clone(),
+                self.plan,
+                self.user.clone(),
+            );
+            want.push_back((topics.into_val(&self.env), *data));
+        }
+        assert_eq!(actual, want);
+    }
+
+    fn event(&self, action: Symbol, data: impl IntoVal<Env, Val>) -> (Symbol, Val) {
+        (action, data.into_val(&self.env))
+    }
+}
+
+#[test]
+fn test_on_time_charges_across_three_periods() {
+    let s = setup(1_000);
+    s.subs.subscribe(&s.user, &s.plan);
+    assert_eq!(s.token.balance(&s.merchant), PRICE);
+
+    for n in 1..=3 {
+        s.at(n * PERIOD);
+        assert_eq!(s.subs.charge(&s.plan, &s.user), Status::Active);
+    }
+
+    assert_eq!(s.token.balance(&s.merchant), 4 * PRICE);
+    assert_eq!(s.token.balance(&s.user), 1_000 - 4 * PRICE);
+    assert_eq!(
+        s.subs.get_subscription(&s.plan, &s.user),
+        Some(Subscription {
+            status: Status::Active,
+            last_charged: START + 3 * PERIOD,
+            grace_until: 0,
+        })
+    );
+    s.assert_sub_events(&[
+        s.event(symbol_short!("started"), PRICE),
+        s.event(symbol_short!("charged"), PRICE),
+        s.event(symbol_short!("charged"), PRICE),
+        s.event(symbol_short!("charged"), PRICE),
+    ]);
+}
+
+#[test]
+fn test_early_charge_rejected() {
+    let s = setup(1_000);
+    s.subs.subscribe(&s.user, &s.plan);
+
+    s.at(PERIOD - 1);
+    assert_eq!(
+        s.subs.try_charge(&s.plan, &s.user),
+        Err(Ok(SubscriptionError::TooEarly))
+    );
+    s.at(PERIOD);
+    s.subs.charge(&s.plan, &s.user);
+    // The next period counts from the charge just made.
+    assert_eq!(
+        s.subs.try_charge(&s.plan, &s.user),
+        Err(Ok(SubscriptionError::TooEarly))
+    );
+    assert_eq!(s.token.balance(&s.merchant), 2 * PRICE);
+}
+
+#[test]
+fn test_insufficient_funds_moves_to_grace() {
+    // Enough for the first payment only.
+    let s = setup(PRICE + 40);
+    s.subs.subscribe(&s.user, &s.plan);
+
+    s.at(PERIOD);
+    // The failed pull is reported as a status, not a trapped invocation,
+    // and no tokens move.
+    assert_eq!(s.subs.charge(&s.plan, &s.user), Status::Grace);
+    assert_eq!(s.token.balance(&s.user), 40);
+    assert_eq!(s.token.balance(&s.merchant), PRICE);
+    let sub = s.subs.get_subscription(&s.plan, &s.user).unwrap();
+    assert_eq!(sub.last_charged, START);
+    assert_eq!(sub.grace_until, START + PERIOD + DEFAULT_GRACE_SECS);
+
+    // Topping up within the window restores the subscription.
+    s.at(PERIOD + 60);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.user, &PRICE);
+    assert_eq!(s.subs.charge(&s.plan, &s.user), Status::Active);
+    assert_eq!(s.token.balance(&s.merchant), 2 * PRICE);
+    assert_eq!(
+        s.subs
+            .get_subscription(&s.plan, &s.user)
+            .unwrap()
+            .last_charged,
+        START + PERIOD + 60
+    );
+    s.assert_sub_events(&[
+        s.event(symbol_short!("started"), PRICE),
+        s.event(symbol_short!("grace"), START + PERIOD + DEFAULT_GRACE_SECS),
+        s.event(symbol_short!("charged"), PRICE),
+        s.event(symbol_short!("resumed"), START + PERIOD + 60),
+    ]);
+}
+
+#[test]
+fn test_grace_then_auto_cancel() {
+    let s = setup(1_000);
+    let day = 24 * 60 * 60;
+    s.subs.set_grace(&s.merchant, &s.plan, &day);
+    s.subs.subscribe(&s.user, &s.plan);
+    // The user withdraws the allowance; the balance alone is not enough.
+    approve(&s.env, &s.token, &s.user, &s.subs.address, 0);
+
+    s.at(PERIOD);
+    assert_eq!(s.subs.charge(&s.plan, &s.user), Status::Grace);
+    // Retries inside the window keep it in grace without new events.
+    s.at(PERIOD + day - 1);
+    assert_eq!(s.subs.charge(&s.plan, &s.user), Status::Grace);
+    s.at(PERIOD + day);
+    assert_eq!(s.subs.charge(&s.plan, &s.user), Status::Cancelled);
+    assert_eq!(s.status(), Status::Cancelled);
+
+    assert_eq!(
+        s.subs.try_charge(&s.plan, &s.user),
+        Err(Ok(SubscriptionError::NotActive))
+    );
+    assert_eq!(s.token.balance(&s.merchant), PRICE);
+    s.assert_sub_events(&[
+        s.event(symbol_short!("started"), PRICE),
+        s.event(symbol_short!("grace"), START + PERIOD + day),
+        s.event(symbol_short!("cancelled"), symbol_short!("lapsed")),
+    ]);
+}
+
+#[test]
+fn test_user_cancellation() {
+    let s = setup(1_000);
+    s.subs.subscribe(&s.user, &s.plan);
+    s.subs.cancel(&s.user, &s.plan);
+    assert_eq!(s.status(), Status::Cancelled);
+
+    s.at(PERIOD);
+    assert_eq!(
+        s.subs.try_charge(&s.plan, &s.user),
+        Err(Ok(SubscriptionError::NotActive))
+    );
+    assert_eq!(
+        s.subs.try_cancel(&s.user, &s.plan),
+        Err(Ok(SubscriptionError::NotActive))
+    );
+    assert_eq!(s.token.balance(&s.merchant), PRICE);
+
+    // A cancelled user can subscribe again, starting a new period.
+    s.subs.subscribe(&s.user, &s.plan);
+    assert_eq!(s.status(), Status::Active);
+    assert_eq!(s.token.balance(&s.merchant), 2 * PRICE);
+    s.assert_sub_events(&[
+        s.event(symbol_short!("started"), PRICE),
+        s.event(symbol_short!("cancelled"), symbol_short!("user")),
+        s.event(symbol_short!("started"), PRICE),
+    ]);
+}
+
+#[test]
+fn test_plan_and_subscribe_validation() {
+    let s = setup(1_000);
+    assert_eq!(
+        s.subs
+            .try_create_plan(&s.merchant, &s.token.address, &0, &PERIOD),
+        Err(Ok(SubscriptionError::InvalidAmount))
+    );
+    assert_eq!(
+        s.subs
+            .try_create_plan(&s.merchant, &s.token.address, &PRICE, &0),
+        Err(Ok(SubscriptionError::InvalidPeriod))
+    );
+    assert_eq!(
+        s.subs.try_set_grace(&s.user, &s.plan, &0),
+        Err(Ok(SubscriptionError::NotMerchant))
+    );
+    assert_eq!(
+        s.subs.try_subscribe(&s.user, &7),
+        Err(Ok(SubscriptionError::PlanNotFound))
+    );
+    assert_eq!(
+        s.subs.try_charge(&s.plan, &s.user),
+        Err(Ok(SubscriptionError::NotSubscribed))
+    );
+
+    s.subs.subscribe(&s.user, &s.plan);
+    assert_eq!(
+        s.subs.try_subscribe(&s.user, &s.plan),
+        Err(Ok(SubscriptionError::AlreadySubscribed))
+    );
+
+    // Without an allowance the first payment fails and nothing is stored.
+    let stranger = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&stranger, &1_000);
+    assert_eq!(
+        s.subs.try_subscribe(&stranger, &s.plan),
+        Err(Ok(SubscriptionError::PaymentFailed))
+    );
+    assert_eq!(s.subs.get_subscription(&s.plan, &stranger), None);
+}
@@ -0,0 +1,374 @@
+//! # Collateralized Lending
+//!
+//! A single market: users lock a collateral token and borrow a debt token
+//! against it, priced by an admin-pushed oracle value.
+//!
+//! ```text
+//! value      = collateral * price / PRICE_SCALE
+//! max debt   = value * max_ltv_bps / 10_000                    borrow and withdraw
+//! liquidable = debt > value * liquidation_threshold_bps / 10_000
+//! seized     = debt * PRICE_SCALE / price * (10_000 + liquidation_bonus_bps) / 10_000
+//! ```
+//!
+//! `price` is the value of one collateral unit in debt units, scaled by
+//! `PRICE_SCALE`. A liquidator repays the whole debt of an unhealthy
+//! position and receives its value in collateral plus the bonus, capped at
+//! the collateral the position holds. The rest stays with the borrower.
+//!
+//! All ratio math goes through the checked `mul_div` helper. Every rounding
+//! step favours the market: collateral value and the borrowing limit round
+//! down, and so does the collateral paid to a liquidator.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+/// `price` is scaled by 10^7, the same 7 decimals Stellar assets use.
+pub const PRICE_SCALE: i128 = 10_000_000;
+pub const BPS: i128 = 10_000;
+
+const NAMESPACE: Symbol = symbol_short!("lend");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Config,
+    Price,
+    Position(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub collateral_token: Address,
+    pub debt_token: Address,
+    /// Highest loan-to-value a borrow or withdrawal may leave behind.
+    pub max_ltv_bps: u32,
+    /// Loan-to-value above which a position can be liquidated.
+    pub liquidation_threshold_bps: u32,
+    /// Extra collateral a liquidator receives on top of the debt's value.
+    pub liquidation_bonus_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Position {
+    pub collateral: i128,
+    pub debt: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationEvent {
+    pub liquidator: Address,
+    pub repaid: i128,
+    pub seized: i128,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LendingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// `max_ltv_bps` is not below `liquidation_threshold_bps`, or the
+    /// threshold is above 100%.
+    InvalidConfig = 3,
+    InvalidAmount = 4,
+    InvalidPrice = 5,
+    NoPrice = 6,
+    /// The borrow or withdrawal would leave the position above `max_ltv_bps`.
+    ExceedsLtv = 7,
+    InsufficientCollateral = 8,
+    /// The position is not above the liquidation threshold.
+    PositionHealthy = 9,
+    MathOverflow = 10,
+    DivisionByZero = 11,
+}
+
+#[contract]
+pub struct LendingContract;
+
+#[contractimpl]
+impl LendingContract {
+    pub fn initialize(env: Env, admin: Address, config: Config) -> Result<(), LendingError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(LendingError::AlreadyInitialized);
+        }
+        if config.max_ltv_bps >= config.liquidation_threshold_bps
+            || i128::from(config.liquidation_threshold_bps) > BPS
+        {
+            return Err(LendingError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Push the price of one collateral unit in debt units, scaled by
+    /// `PRICE_SCALE`. Admin only.
+    pub fn set_price(env: Env, price: i128) -> Result<(), LendingError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(LendingError::NotInitialized)?;
+        admin.require_auth();
+        if price <= 0 {
+            return Err(LendingError::InvalidPrice);
+        }
+        env.storage().instance().set(&DataKey::Price, &price);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("price")), price);
+        Ok(())
+    }
+
+    /// Add debt tokens for borrowers to draw from.
+    pub fn fund(env: Env, lender: Address, amount: i128) -> Result<(), LendingError> {
+        lender.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        let config = Self::config(&env)?;
+        token::Client::new(&env, &config.debt_token).transfer(
+            &lender,
+            &env.current_contract_address(),
+            &amount,
+        );
+        Ok(())
+    }
+
+    pub fn deposit_collateral(env: Env, user: Address, amount: i128) -> Result<(), LendingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        let config = Self::config(&env)?;
+        token::Client::new(&env, &config.collateral_token).transfer(
+            &user,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let mut position = Self::get_position(env.clone(), user.clone());
+        position.collateral = checked_add(position.collateral, amount)?;
+        Self::save(&env, &user, &position);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("deposit"), user), amount);
+        Ok(())
+    }
+
+    /// Take collateral back, as long as the position stays within
+    /// `max_ltv_bps`.
+    pub fn withdraw_collateral(env: Env, user: Address, amount: i128) -> Result<(), LendingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        let config = Self::config(&env)?;
+        let mut position = Self::get_position(env.clone(), user.clone());
+        if amount > position.collateral {
+            return Err(LendingError::InsufficientCollateral);
+        }
+        position.collateral -= amount;
+        if position.debt > 0 && position.debt > Self::max_debt(&env, &config, &position)? {
+            return Err(LendingError::ExceedsLtv);
+        }
+        Self::save(&env, &user, &position);
+        token::Client::new(&env, &config.collateral_token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+        env.events()
+            .publish((NAMESPACE, symbol_short!("withdraw"), user), amount);
+        Ok(())
+    }
+
+    /// Borrow `amount` of the debt token. The new debt may be at most
+    /// `max_ltv_bps` of the collateral's value.
+    pub fn borrow(env: Env, user: Address, amount: i128) -> Result<(), LendingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        let config = Self::config(&env)?;
+        let mut position = Self::get_position(env.clone(), user.clone());
+        position.debt = checked_add(position.debt, amount)?;
+        if position.debt > Self::max_debt(&env, &config, &position)? {
+            return Err(LendingError::ExceedsLtv);
+        }
+        Self::save(&env, &user, &position);
+        token::Client::new(&env, &config.debt_token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+        env.events()
+            .publish((NAMESPACE, symbol_short!("borrow"), user), amount);
+        Ok(())
+    }
+
+    /// Repay up to `amount` of `user`'s debt and return how much was
+    /// repaid. Anything above the outstanding debt is not taken.
+    pub fn repay(env: Env, user: Address, amount: i128) -> Result<i128, LendingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        let config = Self::config(&env)?;
+        let mut position = Self::get_position(env.clone(), user.clone());
+        let repaid = amount.min(position.debt);
+        if repaid == 0 {
+            return Ok(0);
+        }
+        token::Client::new(&env, &config.debt_token).transfer(
+            &user,
+            &env.current_contract_address(),
+            &repaid,
+        );
+        position.debt -= repaid;
+        Self::save(&env, &user, &position);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("repay"), user), repaid);
+        Ok(repaid)
+    }
+
+    /// Repay all of an unhealthy position's debt on `user`'s behalf and
+    /// seize the equivalent collateral plus the liquidation bonus. Returns
+    /// the collateral seized.
+    pub fn liquidate(env: Env, liquidator: Address, user: Address) -> Result<i128, LendingError> {
+        liquidator.require_auth();
+        let config = Self::config(&env)?;
+        let mut position = Self::get_position(env.clone(), user.clone());
+        if !Self::is_liquidatable(&env, &config, &position)? {
+            return Err(LendingError::PositionHealthy);
+        }
+
+        let price = Self::price(&env)?;
+        let debt_in_collateral = mul_div(position.debt, PRICE_SCALE, price)?;
+        let with_bonus = mul_div(
+            debt_in_collateral,
+            checked_add(BPS, i128::from(config.liquidation_bonus_bps))?,
+            BPS,
+        )?;
+        let seized = with_bonus.min(position.collateral);
+        let repaid = position.debt;
+
+        let contract = env.current_contract_address();
+        token::Client::new(&env, &config.debt_token).transfer(&liquidator, &contract, &repaid);
+        token::Client::new(&env, &config.collateral_token).transfer(
+            &contract,
+            &liquidator,
+            &seized,
+        );
+        position.debt = 0;
+        position.collateral -= seized;
+        Self::save(&env, &user, &position);
+        env.events().publish(
+            (NAMESPACE, symbol_short!("liquidate"), user),
+            LiquidationEvent {
+                liquidator,
+                repaid,
+                seized,
+            },
+        );
+        Ok(seized)
+    }
+
+    pub fn get_position(env: Env, user: Address) -> Position {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Position(user))
+            .unwrap_or_default()
+    }
+
+    /// `user`'s loan-to-value in basis points, rounded up. 0 without debt.
+    pub fn ltv_bps(env: Env, user: Address) -> Result<i128, LendingError> {
+        let position = Self::get_position(env.clone(), user);
+        if position.debt == 0 {
+            return Ok(0);
+        }
+        let value = Self::collateral_value(&env, &position)?;
+        mul_div_ceil(position.debt, BPS, value)
+    }
+
+    pub fn can_liquidate(env: Env, user: Address) -> Result<bool, LendingError> {
+        let config = Self::config(&env)?;
+        Self::is_liquidatable(&env, &config, &Self::get_position(env.clone(), user))
+    }
+}
+
+impl LendingContract {
+    fn config(env: &Env) -> Result<Config, LendingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(LendingError::NotInitialized)
+    }
+
+    fn price(env: &Env) -> Result<i128, LendingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Price)
+            .ok_or(LendingError::NoPrice)
+    }
+
+    fn save(env: &Env, user: &Address, position: &Position) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Position(user.clone()), position);
+    }
+
+    /// The collateral's value in debt units, rounded down.
+    fn collateral_value(env: &Env, position: &Position) -> Result<i128, LendingError> {
+        mul_div(position.collateral, Self::price(env)?, PRICE_SCALE)
+    }
+
+    /// The most debt `position` may carry after a borrow or withdrawal.
+    fn max_debt(env: &Env, config: &Config, position: &Position) -> Result<i128, LendingError> {
+        let value = Self::collateral_value(env, position)?;
+        mul_div(value, i128::from(config.max_ltv_bps), BPS)
+    }
+
+    fn is_liquidatable(
+        env: &Env,
+        config: &Config,
+        position: &Position,
+    ) -> Result<bool, LendingError> {
+        if position.debt == 0 {
+            return Ok(false);
+        }
+        let value = Self::collateral_value(env, position)?;
+        let limit = mul_div(value, i128::from(config.liquidation_threshold_bps), BPS)?;
+        Ok(position.debt > limit)
+    }
+}
+
+fn checked_add(a: i128, b: i128) -> Result<i128, LendingError> {
+    a.checked_add(b).ok_or(LendingError::MathOverflow)
+}
+
+/// `a * b / c`, rounded down, with overflow and a zero divisor surfaced as
+/// typed errors.
+fn mul_div(a: i128, b: i128, c: i128) -> Result<i128, LendingError> {
+    if c == 0 {
+        return Err(LendingError::DivisionByZero);
+    }
+    a.checked_mul(b)
+        .map(|product| product / c)
+        .ok_or(LendingError::MathOverflow)
+}
+
+/// `a * b / c` for non-negative operands, rounded up.
+fn mul_div_ceil(a: i128, b: i128, c: i128) -> Result<i128, LendingError> {
+    if c == 0 {
+        return Err(LendingError::DivisionByZero);
+    }
+    let product = a.checked_mul(b).ok_or(LendingError::MathOverflow)?;
+    Ok(product / c + i128::from(product % c != 0))
+}
+
+mod test;
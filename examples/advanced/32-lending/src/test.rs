@@ -0,0 +1,272 @@
+#![cfg(test)]
+
+use super::*;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal,
+};
+
+/// 2.0 debt units per collateral unit.
+const PRICE: i128 = 2 * PRICE_SCALE;
+
+struct Setup {
+    env: Env,
+    user: Address,
+    liquidator: Address,
+    collateral: TokenClient<'static>,
+    debt: TokenClient<'static>,
+    market: LendingContractClient<'static>,
+}
+
+/// A market at 75% max LTV, 80% liquidation threshold and a 5% bonus,
+/// funded with 10 000 debt tokens. The user holds 1 000 collateral and the
+/// liquidator 5 000 debt tokens.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    let lender = Address::generate(&env);
+    let collateral = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let debt = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &collateral).mint(&user, &1_000);
+    StellarAssetClient::new(&env, &debt).mint(&lender, &10_000);
+    StellarAssetClient::new(&env, &debt).mint(&liquidator, &5_000);
+
+    let market = LendingContractClient::new(&env, &env.register_contract(None, LendingContract));
+    market.initialize(
+        &admin,
+        &Config {
+            collateral_token: collateral.clone(),
+            debt_token: debt.clone(),
+            max_ltv_bps: 7_500,
+            liquidation_threshold_bps: 8_000,
+            liquidation_bonus_bps: 500,
+        },
+    );
+    market.set_price(&PRICE);
+    market.fund(&lender, &10_000);
+    Setup {
+        user,
+        liquidator,
+        collateral: TokenClient::new(&env, &collateral),
+        debt: TokenClient::new(&env, &debt),
+        market,
+        env,
+    }
+}
+
+/// `setup` with the user's 1 000 collateral deposited and 1 500 borrowed,
+/// exactly the 75% limit at a price of 2.0.
+fn borrowed_at_limit() -> Setup {
+    let s = setup();
+    s.market.deposit_collateral(&s.user, &1_000);
+    s.market.borrow(&s.user, &1_500);
+    s
+}
+
+#[test]
+fn test_borrow_at_ltv_edge() {
+    let s = setup();
+    s.market.deposit_collateral(&s.user, &1_000);
+
+    // Value 2 000, so 75% allows exactly 1 500.
+    assert_eq!(
+        s.market.try_borrow(&s.user, &1_501),
+        Err(Ok(LendingError::ExceedsLtv))
+    );
+    s.market.borrow(&s.user, &1_500);
+    assert_eq!(s.market.ltv_bps(&s.user), 7_500);
+    assert_eq!(
+        s.market.try_borrow(&s.user, &1),
+        Err(Ok(LendingError::ExceedsLtv))
+    );
+
+    assert_eq!(s.debt.balance(&s.user), 1_500);
+    assert_eq!(s.collateral.balance(&s.market.address), 1_000);
+    assert_eq!(
+        s.market.get_position(&s.user),
+        Position {
+            collateral: 1_000,
+            debt: 1_500,
+        }
+    );
+    // At the borrowing limit, but below the liquidation threshold.
+    assert!(!s.market.can_liquidate(&s.user));
+}
+
+#[test]
+fn test_over_borrow_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.market.try_borrow(&s.user, &1),
+        Err(Ok(LendingError::ExceedsLtv))
+    );
+
+    s.market.deposit_collateral(&s.user, &1_000);
+    assert_eq!(
+        s.market.try_borrow(&s.user, &2_000),
+        Err(Ok(LendingError::ExceedsLtv))
+    );
+    s.market.borrow(&s.user, &1_000);
+
+    // Withdrawing is held to the same limit: 1 000 debt needs 666.67
+    // collateral at 2.0, so 667 must stay.
+    assert_eq!(
+        s.market.try_withdraw_collateral(&s.user, &334),
+        Err(Ok(LendingError::ExceedsLtv))
+    );
+    s.market.withdraw_collateral(&s.user, &333);
+    assert_eq!(s.market.get_position(&s.user).collateral, 667);
+    assert_eq!(s.collateral.balance(&s.user), 333);
+
+    assert_eq!(
+        s.market.try_borrow(&s.user, &0),
+        Err(Ok(LendingError::InvalidAmount))
+    );
+    assert_eq!(s.debt.balance(&s.user), 1_000);
+}
+
+#[test]
+fn test_price_drop_enables_liquidation() {
+    let s = borrowed_at_limit();
+    assert_eq!(
+        s.market.try_liquidate(&s.liquidator, &s.user),
+        Err(Ok(LendingError::PositionHealthy))
+    );
+
+    // At 1.875 the value is 1 875 and the threshold exactly 1 500: still
+    // healthy, because liquidation needs the debt to be above it.
+    s.market.set_price(&18_750_000);
+    assert!(!s.market.can_liquidate(&s.user));
+    // One unit of price lower, the value rounds down to 1 874 and the
+    // threshold to 1 499.
+    s.market.set_price(&18_749_999);
+    assert!(s.market.can_liquidate(&s.user));
+
+    s.market.set_price(&18_000_000);
+    assert!(s.market.can_liquidate(&s.user));
+    // 1 500 / 1 800 = 83.33%, rounded up.
+    assert_eq!(s.market.ltv_bps(&s.user), 8_334);
+}
+
+#[test]
+fn test_partial_repay_restores_health() {
+    let s = borrowed_at_limit();
+    s.market.set_price(&18_000_000);
+    assert!(s.market.can_liquidate(&s.user));
+
+    // The threshold at 1.8 is 1 440.
+    assert_eq!(s.market.repay(&s.user, &59), 59);
+    assert!(s.market.can_liquidate(&s.user));
+    assert_eq!(s.market.repay(&s.user, &1), 1);
+    assert!(!s.market.can_liquidate(&s.user));
+    assert_eq!(s.market.get_position(&s.user).debt, 1_440);
+    assert_eq!(
+        s.market.try_liquidate(&s.liquidator, &s.user),
+        Err(Ok(LendingError::PositionHealthy))
+    );
+
+    // Repaying more than is owed only takes the debt.
+    StellarAssetClient::new(&s.env, &s.debt.address).mint(&s.user, &1_000);
+    assert_eq!(s.market.repay(&s.user, &5_000), 1_440);
+    assert_eq!(s.debt.balance(&s.user), 1_500 - 60 + 1_000 - 1_440);
+    assert_eq!(s.market.ltv_bps(&s.user), 0);
+}
+
+#[test]
+fn test_liquidation_math() {
+    let s = borrowed_at_limit();
+    s.market.set_price(&18_000_000);
+
+    // 1 500 debt / 1.8 = 833.33 -> 833 collateral, plus 5% = 874.65 -> 874.
+    assert_eq!(s.market.liquidate(&s.liquidator, &s.user), 874);
+    assert_eq!(
+        s.market.get_position(&s.user),
+        Position {
+            collateral: 126,
+            debt: 0,
+        }
+    );
+    assert_eq!(s.debt.balance(&s.liquidator), 5_000 - 1_500);
+    assert_eq!(s.collateral.balance(&s.liquidator), 874);
+    assert_eq!(s.collateral.balance(&s.market.address), 126);
+
+    let (topics, data) = all_events_for(&s.env, &s.market.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("liquidate"), s.user.clone()).into_val(&s.env)
+    );
+    let event: LiquidationEvent = data.into_val(&s.env);
+    assert_eq!(
+        event,
+        LiquidationEvent {
+            liquidator: s.liquidator.clone(),
+            repaid: 1_500,
+            seized: 874,
+        }
+    );
+
+    // The borrower keeps the 1 500 borrowed tokens and the rest of the
+    // collateral, which they can now withdraw.
+    assert_eq!(s.debt.balance(&s.user), 1_500);
+    s.market.withdraw_collateral(&s.user, &126);
+    assert_eq!(s.collateral.balance(&s.user), 126);
+}
+
+#[test]
+fn test_liquidation_capped_at_collateral() {
+    let s = borrowed_at_limit();
+    // At 1.5 the debt is worth exactly the 1 000 collateral; the 5% bonus
+    // would need 1 050, so the liquidator gets everything there is.
+    s.market.set_price(&15_000_000);
+    assert_eq!(s.market.liquidate(&s.liquidator, &s.user), 1_000);
+    assert_eq!(s.market.get_position(&s.user), Position::default());
+    assert_eq!(s.collateral.balance(&s.liquidator), 1_000);
+    assert_eq!(s.collateral.balance(&s.market.address), 0);
+}
+
+#[test]
+fn test_configuration_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let market = LendingContractClient::new(&env, &env.register_contract(None, LendingContract));
+    let admin = Address::generate(&env);
+    let config = |max_ltv_bps, liquidation_threshold_bps| Config {
+        collateral_token: Address::generate(&env),
+        debt_token: Address::generate(&env),
+        max_ltv_bps,
+        liquidation_threshold_bps,
+        liquidation_bonus_bps: 500,
+    };
+
+    assert_eq!(
+        market.try_initialize(&admin, &config(8_000, 8_000)),
+        Err(Ok(LendingError::InvalidConfig))
+    );
+    assert_eq!(
+        market.try_initialize(&admin, &config(7_500, 10_001)),
+        Err(Ok(LendingError::InvalidConfig))
+    );
+    assert_eq!(
+        market.try_set_price(&1),
+        Err(Ok(LendingError::NotInitialized))
+    );
+
+    market.initialize(&admin, &config(7_500, 8_000));
+    assert_eq!(
+        market.try_initialize(&admin, &config(7_500, 8_000)),
+        Err(Ok(LendingError::AlreadyInitialized))
+    );
+    assert_eq!(
+        market.try_set_price(&0),
+        Err(Ok(LendingError::InvalidPrice))
+    );
+}
@@ -0,0 +1,304 @@
+//! # Order Book
+//!
+//! A limit order book for one pair: a base token traded against a quote
+//! token. `price` is a whole number of quote units per base unit, so every
+//! fill settles exactly, with no rounding.
+//!
+//! - `place_order` escrows what the order could spend: `amount` of base
+//!   for a sell, `amount * price` of quote for a buy.
+//! - `match_orders` crosses the best bid and ask while their prices
+//!   overlap. Anyone can call it, so matching is bounded by `max_matches`
+//!   rather than done inside `place_order`.
+//! - `cancel_order` refunds whatever the order has not spent.
+//!
+//! ## Priority and execution price
+//!
+//! Each side is a `Vec<Order>` kept sorted best price first, and by id
+//! (arrival) among equal prices. A fill executes at the price of the older
+//! of the two orders, the one that was resting when the other arrived. A
+//! buyer who bid above that price gets the difference back from escrow.
+//!
+//! ## Depth
+//!
+//! A whole side is one storage entry that every call reads and rewrites, so
+//! its cost grows with the number of resting orders. `MAX_DEPTH` caps each
+//! side; a full side rejects new orders until some fill or are cancelled.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+    Vec,
+};
+
+/// Most resting orders per side.
+pub const MAX_DEPTH: u32 = 32;
+
+const NAMESPACE: Symbol = symbol_short!("book");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Base,
+    Quote,
+    NextId,
+    /// Buy orders, highest price first.
+    Bids,
+    /// Sell orders, lowest price first.
+    Asks,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Order {
+    pub id: u64,
+    pub trader: Address,
+    pub side: Side,
+    /// Quote units per base unit.
+    pub price: i128,
+    /// Base units not yet filled.
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fill {
+    pub price: i128,
+    pub amount: i128,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BookError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InvalidPrice = 4,
+    /// The order's side already holds `MAX_DEPTH` orders.
+    BookFull = 5,
+    OrderNotFound = 6,
+    NotOwner = 7,
+    MathOverflow = 8,
+}
+
+#[contract]
+pub struct OrderBookContract;
+
+#[contractimpl]
+impl OrderBookContract {
+    pub fn initialize(env: Env, base: Address, quote: Address) -> Result<(), BookError> {
+        if env.storage().instance().has(&DataKey::Base) {
+            return Err(BookError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Base, &base);
+        env.storage().instance().set(&DataKey::Quote, &quote);
+        Ok(())
+    }
+
+    /// Escrow the order's funds, add it to the book behind every order at
+    /// the same or a better price, and return its id.
+    pub fn place_order(
+        env: Env,
+        trader: Address,
+        side: Side,
+        price: i128,
+        amount: i128,
+    ) -> Result<u64, BookError> {
+        trader.require_auth();
+        if amount <= 0 {
+            return Err(BookError::InvalidAmount);
+        }
+        if price <= 0 {
+            return Err(BookError::InvalidPrice);
+        }
+        let mut book = Self::side(&env, side);
+        if book.len() >= MAX_DEPTH {
+            return Err(BookError::BookFull);
+        }
+
+        let (escrow_token, escrow) = match side {
+            Side::Buy => (DataKey::Quote, checked_mul(amount, price)?),
+            Side::Sell => (DataKey::Base, amount),
+        };
+        Self::token_client(&env, &escrow_token)?.transfer(
+            &trader,
+            &env.current_contract_address(),
+            &escrow,
+        );
+
+        let id: u64 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        let order = Order {
+            id,
+            trader,
+            side,
+            price,
+            amount,
+        };
+        // First position holding a strictly worse price; equal prices keep
+        // arrival order.
+        let index = book
+            .iter()
+            .position(|resting| match side {
+                Side::Buy => resting.price < price,
+                Side::Sell => resting.price > price,
+            })
+            .map_or(book.len(), |i| i as u32);
+        book.insert(index, order.clone());
+        Self::save_side(&env, side, &book);
+
+        env.events()
+            .publish((NAMESPACE, symbol_short!("placed"), id), order);
+        Ok(id)
+    }
+
+    /// Remove `trader`'s order and refund its unfilled escrow. Returns the
+    /// amount refunded, in quote units for a buy and base units for a sell.
+    pub fn cancel_order(env: Env, trader: Address, order_id: u64) -> Result<i128, BookError> {
+        trader.require_auth();
+        for side in [Side::Buy, Side::Sell] {
+            let mut book = Self::side(&env, side);
+            let Some(index) = book.iter().position(|order| order.id == order_id) else {
+                continue;
+            };
+            let order = book.get_unchecked(index as u32);
+            if order.trader != trader {
+                return Err(BookError::NotOwner);
+            }
+            book.remove(index as u32);
+            Self::save_side(&env, side, &book);
+
+            let (refund_token, refund) = match side {
+                Side::Buy => (DataKey::Quote, checked_mul(order.amount, order.price)?),
+                Side::Sell => (DataKey::Base, order.amount),
+            };
+            Self::token_client(&env, &refund_token)?.transfer(
+                &env.current_contract_address(),
+                &trader,
+                &refund,
+            );
+            env.events()
+                .publish((NAMESPACE, symbol_short!("cancelled"), order_id), refund);
+            return Ok(refund);
+        }
+        Err(BookError::OrderNotFound)
+    }
+
+    /// Cross the best bid and ask while the bid's price is at least the
+    /// ask's, at most `max_matches` times. Returns the number of fills.
+    ///
+    /// Each fill publishes `("book", "fill", bid_id, ask_id)` with a `Fill`.
+    pub fn match_orders(env: Env, max_matches: u32) -> Result<u32, BookError> {
+        let base = Self::token_client(&env, &DataKey::Base)?;
+        let quote = Self::token_client(&env, &DataKey::Quote)?;
+        let contract = env.current_contract_address();
+        let mut bids = Self::side(&env, Side::Buy);
+        let mut asks = Self::side(&env, Side::Sell);
+
+        let mut fills = 0;
+        while fills < max_matches {
+            let (Some(mut bid), Some(mut ask)) = (bids.first(), asks.first()) else {
+                break;
+            };
+            if bid.price < ask.price {
+                break;
+            }
+            let amount = bid.amount.min(ask.amount);
+            let price = if bid.id < ask.id {
+                bid.price
+            } else {
+                ask.price
+            };
+
+            let paid = checked_mul(amount, price)?;
+            let escrowed = checked_mul(amount, bid.price)?;
+            base.transfer(&contract, &bid.trader, &amount);
+            quote.transfer(&contract, &ask.trader, &paid);
+            if escrowed > paid {
+                quote.transfer(&contract, &bid.trader, &(escrowed - paid));
+            }
+
+            bid.amount -= amount;
+            ask.amount -= amount;
+            if bid.amount == 0 {
+                bids.pop_front();
+            } else {
+                bids.set(0, bid.clone());
+            }
+            if ask.amount == 0 {
+                asks.pop_front();
+            } else {
+                asks.set(0, ask.clone());
+            }
+            env.events().publish(
+                (NAMESPACE, symbol_short!("fill"), bid.id, ask.id),
+                Fill { price, amount },
+            );
+            fills += 1;
+        }
+
+        Self::save_side(&env, Side::Buy, &bids);
+        Self::save_side(&env, Side::Sell, &asks);
+        Ok(fills)
+    }
+
+    /// Resting buy orders, best first.
+    pub fn bids(env: Env) -> Vec<Order> {
+        Self::side(&env, Side::Buy)
+    }
+
+    /// Resting sell orders, best first.
+    pub fn asks(env: Env) -> Vec<Order> {
+        Self::side(&env, Side::Sell)
+    }
+
+    pub fn get_order(env: Env, order_id: u64) -> Option<Order> {
+        Self::bids(env.clone())
+            .iter()
+            .chain(Self::asks(env).iter())
+            .find(|order| order.id == order_id)
+    }
+}
+
+impl OrderBookContract {
+    fn key(side: Side) -> DataKey {
+        match side {
+            Side::Buy => DataKey::Bids,
+            Side::Sell => DataKey::Asks,
+        }
+    }
+
+    fn side(env: &Env, side: Side) -> Vec<Order> {
+        env.storage()
+            .persistent()
+            .get(&Self::key(side))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn save_side(env: &Env, side: Side, book: &Vec<Order>) {
+        env.storage().persistent().set(&Self::key(side), book);
+    }
+
+    fn token_client<'a>(env: &Env, key: &DataKey) -> Result<token::Client<'a>, BookError> {
+        let address: Address = env
+            .storage()
+            .instance()
+            .get(key)
+            .ok_or(BookError::NotInitialized)?;
+        Ok(token::Client::new(env, &address))
+    }
+}
+
+fn checked_mul(a: i128, b: i128) -> Result<i128, BookError> {
+    a.checked_mul(b).ok_or(BookError::MathOverflow)
+}
+
+mod test;
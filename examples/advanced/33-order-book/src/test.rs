@@ -0,0 +1,248 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal, Val,
+};
+
+struct Setup {
+    env: Env,
+    base: TokenClient<'static>,
+    quote: TokenClient<'static>,
+    book: OrderBookContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let issuer = Address::generate(&env);
+    let base = env
+        .register_stellar_asset_contract_v2(issuer.clone())
+        .address();
+    let quote = env.register_stellar_asset_contract_v2(issuer).address();
+    let book = OrderBookContractClient::new(&env, &env.register_contract(None, OrderBookContract));
+    book.initialize(&base, &quote);
+    Setup {
+        base: TokenClient::new(&env, &base),
+        quote: TokenClient::new(&env, &quote),
+        book,
+        env,
+    }
+}
+
+impl Setup {
+    /// A trader holding 1 000 of each token.
+    fn trader(&self) -> Address {
+        let trader = Address::generate(&self.env);
+        StellarAssetClient::new(&self.env, &self.base.address).mint(&trader, &1_000);
+        StellarAssetClient::new(&self.env, &self.quote.address).mint(&trader, &1_000);
+        trader
+    }
+
+    /// `(bid_id, ask_id, fill)` for every fill so far.
+    fn fills(&self) -> std::vec::Vec<(u64, u64, Fill)> {
+        all_events_for(&self.env, &self.book.address)
+            .into_iter()
+            .filter(|(topics, _)| topics.len() == 4)
+            .map(|(topics, data)| {
+                let bid_id: u64 = topics.get_unchecked(2).into_val(&self.env);
+                let ask_id: u64 = topics.get_unchecked(3).into_val(&self.env);
+                (bid_id, ask_id, data.into_val(&self.env))
+            })
+            .collect()
+    }
+
+    fn remaining(&self, order_id: u64) -> Option<i128> {
+        self.book.get_order(&order_id).map(|order| order.amount)
+    }
+}
+
+#[test]
+fn test_full_fill() {
+    let s = setup();
+    let (seller, buyer) = (s.trader(), s.trader());
+    let ask = s.book.place_order(&seller, &Side::Sell, &5, &10);
+    let bid = s.book.place_order(&buyer, &Side::Buy, &5, &10);
+    assert_eq!(s.base.balance(&s.book.address), 10);
+    assert_eq!(s.quote.balance(&s.book.address), 50);
+
+    assert_eq!(s.book.match_orders(&10), 1);
+    assert_eq!(s.book.bids().len(), 0);
+    assert_eq!(s.book.asks().len(), 0);
+    assert_eq!(s.base.balance(&buyer), 1_010);
+    assert_eq!(s.quote.balance(&buyer), 950);
+    assert_eq!(s.base.balance(&seller), 990);
+    assert_eq!(s.quote.balance(&seller), 1_050);
+    assert_eq!(s.base.balance(&s.book.address), 0);
+    assert_eq!(s.quote.balance(&s.book.address), 0);
+    assert_eq!(
+        s.fills(),
+        std::vec![(
+            bid,
+            ask,
+            Fill {
+                price: 5,
+                amount: 10,
+            }
+        )]
+    );
+}
+
+#[test]
+fn test_partial_fill_leaves_resting_remainder() {
+    let s = setup();
+    let (seller, buyer) = (s.trader(), s.trader());
+    let ask = s.book.place_order(&seller, &Side::Sell, &5, &10);
+    // The bid arrives later and above the ask, so it fills at the resting
+    // ask's price and gets the difference back.
+    let bid = s.book.place_order(&buyer, &Side::Buy, &6, &4);
+    assert_eq!(s.quote.balance(&buyer), 1_000 - 24);
+
+    assert_eq!(s.book.match_orders(&10), 1);
+    assert_eq!(s.remaining(ask), Some(6));
+    assert_eq!(s.remaining(bid), None);
+    assert_eq!(s.base.balance(&buyer), 1_004);
+    assert_eq!(s.quote.balance(&buyer), 1_000 - 20);
+    assert_eq!(s.quote.balance(&seller), 1_020);
+    assert_eq!(
+        s.fills(),
+        std::vec![(
+            bid,
+            ask,
+            Fill {
+                price: 5,
+                amount: 4
+            }
+        )]
+    );
+
+    // Nothing left to cross; the remainder rests at the top of the book.
+    assert_eq!(s.book.match_orders(&10), 0);
+    assert_eq!(s.book.asks().get_unchecked(0).amount, 6);
+    assert_eq!(s.base.balance(&s.book.address), 6);
+    assert_eq!(s.quote.balance(&s.book.address), 0);
+}
+
+#[test]
+fn test_price_time_priority() {
+    let s = setup();
+    let (a, b, c, buyer) = (s.trader(), s.trader(), s.trader(), s.trader());
+    let first_at_5 = s.book.place_order(&a, &Side::Sell, &5, &5);
+    let second_at_5 = s.book.place_order(&b, &Side::Sell, &5, &5);
+    let better = s.book.place_order(&c, &Side::Sell, &4, &5);
+    let asks: std::vec::Vec<u64> = s.book.asks().iter().map(|o| o.id).collect();
+    assert_eq!(asks, [better, first_at_5, second_at_5]);
+
+    let bid = s.book.place_order(&buyer, &Side::Buy, &5, &12);
+    // `max_matches` bounds the work per call.
+    assert_eq!(s.book.match_orders(&2), 2);
+    assert_eq!(s.book.match_orders(&2), 1);
+    assert_eq!(
+        s.fills(),
+        std::vec![
+            (
+                bid,
+                better,
+                Fill {
+                    price: 4,
+                    amount: 5
+                }
+            ),
+            (
+                bid,
+                first_at_5,
+                Fill {
+                    price: 5,
+                    amount: 5
+                }
+            ),
+            (
+                bid,
+                second_at_5,
+                Fill {
+                    price: 5,
+                    amount: 2
+                }
+            ),
+        ]
+    );
+    assert_eq!(s.remaining(second_at_5), Some(3));
+    assert_eq!(s.quote.balance(&c), 1_020);
+    assert_eq!(s.quote.balance(&a), 1_025);
+    assert_eq!(s.quote.balance(&b), 1_010);
+    // Escrowed 60, paid 20 + 25 + 10 = 55, refunded 5.
+    assert_eq!(s.quote.balance(&buyer), 1_000 - 55);
+}
+
+#[test]
+fn test_cancel_partially_filled_order() {
+    let s = setup();
+    let (seller, buyer, stranger) = (s.trader(), s.trader(), s.trader());
+    let ask = s.book.place_order(&seller, &Side::Sell, &5, &10);
+    s.book.place_order(&buyer, &Side::Buy, &5, &4);
+    s.book.match_orders(&1);
+
+    assert_eq!(
+        s.book.try_cancel_order(&stranger, &ask),
+        Err(Ok(BookError::NotOwner))
+    );
+    assert_eq!(s.book.cancel_order(&seller, &ask), 6);
+    assert_eq!(s.base.balance(&seller), 1_000 - 4);
+    assert_eq!(s.quote.balance(&seller), 1_020);
+    assert_eq!(s.book.asks().len(), 0);
+    assert_eq!(s.base.balance(&s.book.address), 0);
+    assert_eq!(
+        s.book.try_cancel_order(&seller, &ask),
+        Err(Ok(BookError::OrderNotFound))
+    );
+
+    // A buy refunds its unspent quote.
+    let bid = s.book.place_order(&buyer, &Side::Buy, &7, &3);
+    assert_eq!(s.book.cancel_order(&buyer, &bid), 21);
+    assert_eq!(s.quote.balance(&buyer), 1_000 - 20);
+
+    let (topics, data): (soroban_sdk::Vec<Val>, Val) =
+        all_events_for(&s.env, &s.book.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("cancelled"), bid).into_val(&s.env)
+    );
+    let refund: i128 = data.into_val(&s.env);
+    assert_eq!(refund, 21);
+}
+
+#[test]
+fn test_depth_cap_rejects_orders() {
+    let s = setup();
+    let trader = s.trader();
+    for i in 0..MAX_DEPTH {
+        s.book
+            .place_order(&trader, &Side::Buy, &1, &(i128::from(i) + 1));
+    }
+    assert_eq!(
+        s.book.try_place_order(&trader, &Side::Buy, &1, &1),
+        Err(Ok(BookError::BookFull))
+    );
+    // The cap is per side.
+    s.book.place_order(&trader, &Side::Sell, &2, &1);
+
+    // A cancellation frees a slot.
+    let oldest = s.book.bids().get_unchecked(0).id;
+    s.book.cancel_order(&trader, &oldest);
+    s.book.place_order(&trader, &Side::Buy, &1, &1);
+    assert_eq!(s.book.bids().len(), MAX_DEPTH);
+
+    assert_eq!(
+        s.book.try_place_order(&trader, &Side::Sell, &0, &1),
+        Err(Ok(BookError::InvalidPrice))
+    );
+    assert_eq!(
+        s.book.try_place_order(&trader, &Side::Sell, &1, &0),
+        Err(Ok(BookError::InvalidAmount))
+    );
+}
@@ -0,0 +1,240 @@
+//! # Dead Man's Switch
+//!
+//! An owner escrows tokens for a beneficiary and proves they are still
+//! around by calling `heartbeat`. If the heartbeats stop for longer than
+//! `interval + grace`, the beneficiary can `claim` the whole balance.
+//!
+//! This is the timelock pattern with a moving deadline: instead of queuing
+//! an operation once, every heartbeat pushes the earliest claim time out to
+//! `now + interval + grace`.
+//!
+//! ## While the owner is alive
+//!
+//! The owner can top up the escrow, change the beneficiary, or withdraw
+//! everything. Only `heartbeat` resets the clock. A heartbeat that arrives
+//! after the deadline still counts as long as nobody has claimed yet: the
+//! switch fires on the claim, not on the deadline.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+const NAMESPACE: Symbol = symbol_short!("switch");
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SwitchError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// The address passed in is not the owner, or not the beneficiary.
+    /// `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// `last_heartbeat + interval + grace` does not fit in a `u64`.
+    /// `CommonError::Overflow`.
+    Overflow = 5,
+    /// A zero interval or a non-positive amount. `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// The claim deadline has not passed.
+    StillAlive = 100,
+    /// The beneficiary has already claimed; the switch is spent.
+    AlreadyClaimed = 101,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Switch,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Switch {
+    pub owner: Address,
+    pub beneficiary: Address,
+    pub token: Address,
+    /// Expected seconds between heartbeats.
+    pub interval: u64,
+    /// Extra seconds after a missed heartbeat before a claim is allowed.
+    pub grace: u64,
+    pub last_heartbeat: u64,
+    pub claimed: bool,
+}
+
+#[contract]
+pub struct DeadMansSwitchContract;
+
+#[contractimpl]
+impl DeadMansSwitchContract {
+    /// Set up the switch. The clock starts now, as if the owner had just
+    /// sent a heartbeat.
+    pub fn initialize(
+        env: Env,
+        owner: Address,
+        beneficiary: Address,
+        token: Address,
+        interval: u64,
+        grace: u64,
+    ) -> Result<(), SwitchError> {
+        owner.require_auth();
+        if env.storage().instance().has(&DataKey::Switch) {
+            return Err(SwitchError::AlreadyInitialized);
+        }
+        if interval == 0 {
+            return Err(SwitchError::InvalidInput);
+        }
+        let switch = Switch {
+            owner,
+            beneficiary,
+            token,
+            interval,
+            grace,
+            last_heartbeat: env.ledger().timestamp(),
+            claimed: false,
+        };
+        deadline(&switch)?;
+        env.storage().instance().set(&DataKey::Switch, &switch);
+        Ok(())
+    }
+
+    /// Reset the clock. Returns the new claim deadline.
+    pub fn heartbeat(env: Env, owner: Address) -> Result<u64, SwitchError> {
+        let mut switch = Self::owner_switch(&env, &owner)?;
+        switch.last_heartbeat = env.ledger().timestamp();
+        let deadline = deadline(&switch)?;
+        Self::save(&env, &switch);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("heartbeat")), deadline);
+        Ok(deadline)
+    }
+
+    /// Add `amount` of the token to the escrow. Does not reset the clock.
+    pub fn deposit(env: Env, owner: Address, amount: i128) -> Result<(), SwitchError> {
+        let switch = Self::owner_switch(&env, &owner)?;
+        if amount <= 0 {
+            return Err(SwitchError::InvalidInput);
+        }
+        token::Client::new(&env, &switch.token).transfer(
+            &owner,
+            &env.current_contract_address(),
+            &amount,
+        );
+        env.events()
+            .publish((NAMESPACE, symbol_short!("deposit")), amount);
+        Ok(())
+    }
+
+    pub fn set_beneficiary(
+        env: Env,
+        owner: Address,
+        beneficiary: Address,
+    ) -> Result<(), SwitchError> {
+        let mut switch = Self::owner_switch(&env, &owner)?;
+        switch.beneficiary = beneficiary.clone();
+        Self::save(&env, &switch);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("nominated")), beneficiary);
+        Ok(())
+    }
+
+    /// Return the whole escrow to the owner. The switch stays armed, so the
+    /// owner can deposit again later. Returns the amount withdrawn.
+    pub fn withdraw(env: Env, owner: Address) -> Result<i128, SwitchError> {
+        let switch = Self::owner_switch(&env, &owner)?;
+        let amount = Self::pay_out(&env, &switch, &owner);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("withdraw")), amount);
+        Ok(amount)
+    }
+
+    /// Pay the whole escrow to the beneficiary once the deadline has
+    /// passed. Returns the amount claimed.
+    pub fn claim(env: Env, beneficiary: Address) -> Result<i128, SwitchError> {
+        let mut switch = Self::load(&env)?;
+        if beneficiary != switch.beneficiary {
+            return Err(SwitchError::Unauthorized);
+        }
+        beneficiary.require_auth();
+        if switch.claimed {
+            return Err(SwitchError::AlreadyClaimed);
+        }
+        if env.ledger().timestamp() <= deadline(&switch)? {
+            return Err(SwitchError::StillAlive);
+        }
+
+        switch.claimed = true;
+        Self::save(&env, &switch);
+        let amount = Self::pay_out(&env, &switch, &beneficiary);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("claimed"), beneficiary), amount);
+        Ok(amount)
+    }
+
+    /// Last timestamp at which the beneficiary still cannot claim.
+    pub fn deadline(env: Env) -> Result<u64, SwitchError> {
+        deadline(&Self::load(&env)?)
+    }
+
+    pub fn get_switch(env: Env) -> Result<Switch, SwitchError> {
+        Self::load(&env)
+    }
+
+    /// Tokens currently held in escrow.
+    pub fn balance(env: Env) -> Result<i128, SwitchError> {
+        let switch = Self::load(&env)?;
+        Ok(token::Client::new(&env, &switch.token).balance(&env.current_contract_address()))
+    }
+}
+
+impl DeadMansSwitchContract {
+    fn load(env: &Env) -> Result<Switch, SwitchError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Switch)
+            .ok_or(SwitchError::NotInitialized)
+    }
+
+    fn save(env: &Env, switch: &Switch) {
+        env.storage().instance().set(&DataKey::Switch, switch);
+    }
+
+    /// Load the switch for an owner-only call: `owner` must be the owner
+    /// and must authorize, and the switch must not have fired.
+    fn owner_switch(env: &Env, owner: &Address) -> Result<Switch, SwitchError> {
+        let switch = Self::load(env)?;
+        if *owner != switch.owner {
+            return Err(SwitchError::Unauthorized);
+        }
+        owner.require_auth();
+        if switch.claimed {
+            return Err(SwitchError::AlreadyClaimed);
+        }
+        Ok(switch)
+    }
+
+    fn pay_out(env: &Env, switch: &Switch, to: &Address) -> i128 {
+        let token = token::Client::new(env, &switch.token);
+        let contract = env.current_contract_address();
+        let amount = token.balance(&contract);
+        if amount > 0 {
+            token.transfer(&contract, to, &amount);
+        }
+        amount
+    }
+}
+
+fn deadline(switch: &Switch) -> Result<u64, SwitchError> {
+    switch
+        .last_heartbeat
+        .checked_add(switch.interval)
+        .and_then(|t| t.checked_add(switch.grace))
+        .ok_or(SwitchError::Overflow)
+}
+
+mod test;
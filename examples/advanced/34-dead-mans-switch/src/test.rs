@@ -0,0 +1,257 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::{all_events_for, set_timestamp};
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal,
+};
+
+const START: u64 = 1_000;
+const DAY: u64 = 24 * 60 * 60;
+const INTERVAL: u64 = 30 * DAY;
+const GRACE: u64 = 7 * DAY;
+/// The first claim deadline, with no heartbeat after `initialize`.
+const DEADLINE: u64 = START + INTERVAL + GRACE;
+
+struct Setup {
+    env: Env,
+    owner: Address,
+    beneficiary: Address,
+    token: TokenClient<'static>,
+    switch: DeadMansSwitchContractClient<'static>,
+}
+
+/// A switch set up at `START` with 1 000 tokens escrowed.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, START);
+    let owner = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &asset.address()).mint(&owner, &1_000);
+
+    let switch = DeadMansSwitchContractClient::new(
+        &env,
+        &env.register_contract(None, DeadMansSwitchContract),
+    );
+    switch.initialize(&owner, &beneficiary, &asset.address(), &INTERVAL, &GRACE);
+    switch.deposit(&owner, &1_000);
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        owner,
+        beneficiary,
+        switch,
+    }
+}
+
+#[test]
+fn test_claim_blocked_while_heartbeats_are_fresh() {
+    let s = setup();
+    assert_eq!(s.switch.deadline(), DEADLINE);
+    assert_eq!(
+        s.switch.try_claim(&s.beneficiary),
+        Err(Ok(SwitchError::StillAlive))
+    );
+
+    // A heartbeat every interval keeps the claim out of reach indefinitely.
+    for n in 1..=3 {
+        set_timestamp(&s.env, START + n * INTERVAL);
+        assert_eq!(
+            s.switch.heartbeat(&s.owner),
+            START + n * INTERVAL + INTERVAL + GRACE
+        );
+        set_timestamp(&s.env, START + n * INTERVAL + INTERVAL);
+        assert_eq!(
+            s.switch.try_claim(&s.beneficiary),
+            Err(Ok(SwitchError::StillAlive))
+        );
+    }
+    assert_eq!(s.switch.balance(), 1_000);
+}
+
+#[test]
+fn test_claim_after_window() {
+    let s = setup();
+    // The deadline itself is still inside the window.
+    set_timestamp(&s.env, DEADLINE);
+    assert_eq!(
+        s.switch.try_claim(&s.beneficiary),
+        Err(Ok(SwitchError::StillAlive))
+    );
+
+    set_timestamp(&s.env, DEADLINE + 1);
+    assert_eq!(s.switch.claim(&s.beneficiary), 1_000);
+    assert_eq!(s.token.balance(&s.beneficiary), 1_000);
+    assert_eq!(s.switch.balance(), 0);
+    assert!(s.switch.get_switch().claimed);
+
+    let (topics, data) = all_events_for(&s.env, &s.switch.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("claimed"), s.beneficiary.clone()).into_val(&s.env)
+    );
+    let amount: i128 = data.into_val(&s.env);
+    assert_eq!(amount, 1_000);
+}
+
+#[test]
+fn test_heartbeat_just_in_time() {
+    let s = setup();
+    // The owner misses the interval but checks in on the last second of
+    // the grace period.
+    set_timestamp(&s.env, DEADLINE);
+    let new_deadline = s.switch.heartbeat(&s.owner);
+    assert_eq!(new_deadline, DEADLINE + INTERVAL + GRACE);
+
+    set_timestamp(&s.env, DEADLINE + 1);
+    assert_eq!(
+        s.switch.try_claim(&s.beneficiary),
+        Err(Ok(SwitchError::StillAlive))
+    );
+    set_timestamp(&s.env, new_deadline);
+    assert_eq!(
+        s.switch.try_claim(&s.beneficiary),
+        Err(Ok(SwitchError::StillAlive))
+    );
+    set_timestamp(&s.env, new_deadline + 1);
+    assert_eq!(s.switch.claim(&s.beneficiary), 1_000);
+}
+
+#[test]
+fn test_beneficiary_change_requires_owner() {
+    let s = setup();
+    let heir = Address::generate(&s.env);
+    assert_eq!(
+        s.switch.try_set_beneficiary(&s.beneficiary, &heir),
+        Err(Ok(SwitchError::Unauthorized))
+    );
+
+    s.switch.set_beneficiary(&s.owner, &heir);
+    assert_eq!(
+        s.env.auths(),
+        std::vec![(
+            s.owner.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    s.switch.address.clone(),
+                    Symbol::new(&s.env, "set_beneficiary"),
+                    (s.owner.clone(), heir.clone()).into_val(&s.env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+
+    // Only the current beneficiary can claim.
+    set_timestamp(&s.env, DEADLINE + 1);
+    assert_eq!(
+        s.switch.try_claim(&s.beneficiary),
+        Err(Ok(SwitchError::Unauthorized))
+    );
+    assert_eq!(s.switch.claim(&heir), 1_000);
+    assert_eq!(s.token.balance(&heir), 1_000);
+}
+
+#[test]
+fn test_double_claim_rejected() {
+    let s = setup();
+    set_timestamp(&s.env, DEADLINE + 1);
+    s.switch.claim(&s.beneficiary);
+    assert_eq!(
+        s.switch.try_claim(&s.beneficiary),
+        Err(Ok(SwitchError::AlreadyClaimed))
+    );
+
+    // The switch is spent: the owner can no longer revive or refill it.
+    assert_eq!(
+        s.switch.try_heartbeat(&s.owner),
+        Err(Ok(SwitchError::AlreadyClaimed))
+    );
+    assert_eq!(
+        s.switch.try_deposit(&s.owner, &1),
+        Err(Ok(SwitchError::AlreadyClaimed))
+    );
+    assert_eq!(s.token.balance(&s.beneficiary), 1_000);
+}
+
+#[test]
+fn test_owner_top_up_and_withdraw() {
+    let s = setup();
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.owner, &500);
+    s.switch.deposit(&s.owner, &500);
+    assert_eq!(s.switch.balance(), 1_500);
+    // Topping up is not a heartbeat.
+    assert_eq!(s.switch.deadline(), DEADLINE);
+
+    assert_eq!(s.switch.withdraw(&s.owner), 1_500);
+    assert_eq!(s.token.balance(&s.owner), 1_500);
+    assert_eq!(
+        s.switch.try_withdraw(&s.beneficiary),
+        Err(Ok(SwitchError::Unauthorized))
+    );
+
+    // A claim on an empty escrow succeeds with nothing to pay.
+    set_timestamp(&s.env, DEADLINE + 1);
+    assert_eq!(s.switch.claim(&s.beneficiary), 0);
+}
+
+#[test]
+fn test_initialize_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let switch = DeadMansSwitchContractClient::new(
+        &env,
+        &env.register_contract(None, DeadMansSwitchContract),
+    );
+    let owner = Address::generate(&env);
+    let token = Address::generate(&env);
+    assert_eq!(
+        switch.try_heartbeat(&owner),
+        Err(Ok(SwitchError::NotInitialized))
+    );
+    assert_eq!(
+        switch.try_initialize(&owner, &owner, &token, &0, &GRACE),
+        Err(Ok(SwitchError::InvalidInput))
+    );
+    set_timestamp(&env, u64::MAX - INTERVAL);
+    assert_eq!(
+        switch.try_initialize(&owner, &owner, &token, &INTERVAL, &1),
+        Err(Ok(SwitchError::Overflow))
+    );
+
+    set_timestamp(&env, START);
+    switch.initialize(&owner, &owner, &token, &INTERVAL, &GRACE);
+    assert_eq!(
+        switch.try_initialize(&owner, &owner, &token, &INTERVAL, &GRACE),
+        Err(Ok(SwitchError::AlreadyInitialized))
+    );
+    assert_eq!(
+        switch.try_deposit(&owner, &0),
+        Err(Ok(SwitchError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (SwitchError::NotInitialized, CommonError::NotInitialized),
+        (
+            SwitchError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (SwitchError::Unauthorized, CommonError::Unauthorized),
+        (SwitchError::Overflow, CommonError::Overflow),
+        (SwitchError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(SwitchError::StillAlive as u32 >= recipes_errors::CONTRACT_MIN);
+}
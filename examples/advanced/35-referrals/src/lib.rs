@@ -0,0 +1,236 @@
+//! # Referral Rewards
+//!
+//! Users register once, optionally naming the registered user who referred
+//! them. When a registered user makes a purchase, the merchant (the admin)
+//! records it and the user's direct referrer earns `reward_bps` of the
+//! amount. Rewards accrue on-chain and the referrer claims them in one
+//! transfer.
+//!
+//! ```text
+//! reward = amount * reward_bps / 10_000    rounded down
+//! ```
+//!
+//! ## Attribution
+//!
+//! Only the direct referrer is paid; there are no multi-level payouts. A
+//! referral is fixed at registration and can never change. The referrer
+//! must already be registered, so a chain of referrals always points to
+//! earlier registrations and can never loop back.
+//!
+//! Every registration and purchase publishes an event with both the user
+//! and their referrer in the topics, so attribution can be rebuilt from
+//! events alone.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+pub const BPS: u32 = 10_000;
+
+const NAMESPACE: Symbol = symbol_short!("referral");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Token,
+    RewardBps,
+    /// The referrer a user registered with, `None` if nobody referred them.
+    /// Present for every registered user.
+    Referrer(Address),
+    /// Rewards accrued and not yet claimed.
+    Rewards(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ReferralError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    AlreadyRegistered = 3,
+    SelfReferral = 4,
+    /// The named referrer has not registered, so the referral could form
+    /// a loop.
+    ReferrerNotRegistered = 5,
+    NotRegistered = 6,
+    InvalidAmount = 7,
+    /// `reward_bps` above 10 000.
+    InvalidBps = 8,
+    MathOverflow = 9,
+    NothingToClaim = 10,
+}
+
+#[contract]
+pub struct ReferralContract;
+
+#[contractimpl]
+impl ReferralContract {
+    /// Rewards are paid in `token`, which the admin pays in as purchases
+    /// are recorded.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        reward_bps: u32,
+    ) -> Result<(), ReferralError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ReferralError::AlreadyInitialized);
+        }
+        if reward_bps > BPS {
+            return Err(ReferralError::InvalidBps);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardBps, &reward_bps);
+        Ok(())
+    }
+
+    /// Change the reward rate. Rewards already accrued keep the rate they
+    /// were recorded at. Admin only.
+    pub fn set_reward_bps(env: Env, reward_bps: u32) -> Result<(), ReferralError> {
+        Self::admin(&env)?.require_auth();
+        if reward_bps > BPS {
+            return Err(ReferralError::InvalidBps);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardBps, &reward_bps);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("rate")), reward_bps);
+        Ok(())
+    }
+
+    /// Register `user`, optionally attributed to `referrer`.
+    ///
+    /// Publishes `("referral", "joined", user, referrer)`, with `referrer`
+    /// as `None` for an unreferred user.
+    pub fn register(
+        env: Env,
+        user: Address,
+        referrer: Option<Address>,
+    ) -> Result<(), ReferralError> {
+        user.require_auth();
+        if Self::is_registered(env.clone(), user.clone()) {
+            return Err(ReferralError::AlreadyRegistered);
+        }
+        if let Some(referrer) = &referrer {
+            if *referrer == user {
+                return Err(ReferralError::SelfReferral);
+            }
+            if !Self::is_registered(env.clone(), referrer.clone()) {
+                return Err(ReferralError::ReferrerNotRegistered);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Referrer(user.clone()), &referrer);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("joined"), user, referrer), ());
+        Ok(())
+    }
+
+    /// Record a purchase of `amount` by `user` and credit their referrer.
+    /// The admin pays the reward into the contract, so accrued rewards are
+    /// always backed. Returns the reward, 0 for an unreferred user.
+    ///
+    /// Publishes `("referral", "purchase", user, referrer)` with
+    /// `(amount, reward)`.
+    pub fn record_purchase(env: Env, user: Address, amount: i128) -> Result<i128, ReferralError> {
+        let admin = Self::admin(&env)?;
+        admin.require_auth();
+        if amount <= 0 {
+            return Err(ReferralError::InvalidAmount);
+        }
+        let referrer: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Referrer(user.clone()))
+            .ok_or(ReferralError::NotRegistered)?;
+
+        let mut reward = 0;
+        if let Some(referrer) = &referrer {
+            let bps: u32 = env.storage().instance().get(&DataKey::RewardBps).unwrap();
+            reward = amount
+                .checked_mul(i128::from(bps))
+                .ok_or(ReferralError::MathOverflow)?
+                / i128::from(BPS);
+            if reward > 0 {
+                let key = DataKey::Rewards(referrer.clone());
+                let accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+                let accrued = accrued
+                    .checked_add(reward)
+                    .ok_or(ReferralError::MathOverflow)?;
+                Self::token(&env).transfer(&admin, &env.current_contract_address(), &reward);
+                env.storage().persistent().set(&key, &accrued);
+            }
+        }
+        env.events().publish(
+            (NAMESPACE, symbol_short!("purchase"), user, referrer),
+            (amount, reward),
+        );
+        Ok(reward)
+    }
+
+    /// Pay out everything `referrer` has accrued. Returns the amount.
+    pub fn claim_rewards(env: Env, referrer: Address) -> Result<i128, ReferralError> {
+        referrer.require_auth();
+        let key = DataKey::Rewards(referrer.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return Err(ReferralError::NothingToClaim);
+        }
+        env.storage().persistent().remove(&key);
+        Self::token(&env).transfer(&env.current_contract_address(), &referrer, &amount);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("claimed"), referrer), amount);
+        Ok(amount)
+    }
+
+    pub fn is_registered(env: Env, user: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Referrer(user))
+    }
+
+    /// Who referred `user`. `None` if nobody did or `user` is not
+    /// registered.
+    pub fn referrer_of(env: Env, user: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get::<_, Option<Address>>(&DataKey::Referrer(user))
+            .flatten()
+    }
+
+    pub fn rewards(env: Env, referrer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Rewards(referrer))
+            .unwrap_or(0)
+    }
+
+    pub fn reward_bps(env: Env) -> Result<u32, ReferralError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardBps)
+            .ok_or(ReferralError::NotInitialized)
+    }
+}
+
+impl ReferralContract {
+    fn admin(env: &Env) -> Result<Address, ReferralError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ReferralError::NotInitialized)
+    }
+
+    fn token(env: &Env) -> token::Client<'_> {
+        let address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(env, &address)
+    }
+}
+
+mod test;
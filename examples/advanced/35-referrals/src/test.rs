@@ -0,0 +1,209 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal, Val, Vec,
+};
+
+/// 5% of every referred purchase.
+const REWARD_BPS: u32 = 500;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    token: TokenClient<'static>,
+    referrals: ReferralContractClient<'static>,
+}
+
+/// The admin holds 10 000 reward tokens.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &asset.address()).mint(&admin, &10_000);
+    let referrals =
+        ReferralContractClient::new(&env, &env.register_contract(None, ReferralContract));
+    referrals.initialize(&admin, &asset.address(), &REWARD_BPS);
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        admin,
+        referrals,
+    }
+}
+
+impl Setup {
+    fn user(&self, referrer: Option<&Address>) -> Address {
+        let user = Address::generate(&self.env);
+        self.referrals.register(&user, &referrer.cloned());
+        user
+    }
+
+    /// `(topics, data)` of every event with `action` as its second topic.
+    fn events(&self, action: Symbol) -> Vec<(Vec<Val>, Val)> {
+        let action: Val = action.into_val(&self.env);
+        let mut events = Vec::new(&self.env);
+        for (topics, data) in all_events_for(&self.env, &self.referrals.address) {
+            if topics.get_unchecked(1).get_payload() == action.get_payload() {
+                events.push_back((topics, data));
+            }
+        }
+        events
+    }
+}
+
+#[test]
+fn test_two_level_chain_pays_direct_referrer_only() {
+    let s = setup();
+    let alice = s.user(None);
+    let bob = s.user(Some(&alice));
+    let carol = s.user(Some(&bob));
+    assert_eq!(s.referrals.referrer_of(&carol), Some(bob.clone()));
+    assert_eq!(s.referrals.referrer_of(&alice), None);
+
+    assert_eq!(s.referrals.record_purchase(&carol, &1_000), 50);
+    assert_eq!(s.referrals.rewards(&bob), 50);
+    assert_eq!(s.referrals.rewards(&alice), 0);
+    // Alice referred nobody who bought, and has no referrer to pay.
+    assert_eq!(s.referrals.record_purchase(&alice, &1_000), 0);
+    assert_eq!(s.token.balance(&s.referrals.address), 50);
+    assert_eq!(s.token.balance(&s.admin), 10_000 - 50);
+
+    // The attribution is in the topics: who bought, and who referred them.
+    let none: Option<Address> = None;
+    let expected = Vec::from_array(
+        &s.env,
+        [
+            (
+                (NAMESPACE, symbol_short!("purchase"), carol, Some(bob)).into_val(&s.env),
+                (1_000_i128, 50_i128).into_val(&s.env),
+            ),
+            (
+                (NAMESPACE, symbol_short!("purchase"), alice, none).into_val(&s.env),
+                (1_000_i128, 0_i128).into_val(&s.env),
+            ),
+        ],
+    );
+    assert_eq!(s.events(symbol_short!("purchase")), expected);
+}
+
+#[test]
+fn test_self_and_circular_referral_rejected() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    assert_eq!(
+        s.referrals.try_register(&alice, &Some(alice.clone())),
+        Err(Ok(ReferralError::SelfReferral))
+    );
+
+    // Naming someone who has not registered yet is what would let two
+    // users refer each other, so it is refused.
+    let bob = Address::generate(&s.env);
+    assert_eq!(
+        s.referrals.try_register(&alice, &Some(bob.clone())),
+        Err(Ok(ReferralError::ReferrerNotRegistered))
+    );
+    assert!(!s.referrals.is_registered(&alice));
+
+    s.referrals.register(&alice, &None);
+    s.referrals.register(&bob, &Some(alice.clone()));
+    // Alice cannot close the loop: her registration is fixed.
+    assert_eq!(
+        s.referrals.try_register(&alice, &Some(bob.clone())),
+        Err(Ok(ReferralError::AlreadyRegistered))
+    );
+    assert_eq!(s.referrals.referrer_of(&alice), None);
+
+    let joined = s.events(symbol_short!("joined"));
+    assert_eq!(joined.len(), 2);
+    assert_eq!(
+        joined.get_unchecked(1).0,
+        (NAMESPACE, symbol_short!("joined"), bob, Some(alice)).into_val(&s.env)
+    );
+}
+
+#[test]
+fn test_rewards_accrue_over_purchases() {
+    let s = setup();
+    let alice = s.user(None);
+    let bob = s.user(Some(&alice));
+    let carol = s.user(Some(&alice));
+
+    s.referrals.record_purchase(&bob, &1_000);
+    s.referrals.record_purchase(&carol, &400);
+    // 5% of 19 is 0.95, rounded down to nothing.
+    assert_eq!(s.referrals.record_purchase(&bob, &19), 0);
+    s.referrals.record_purchase(&bob, &20);
+    assert_eq!(s.referrals.rewards(&alice), 50 + 20 + 1);
+    assert_eq!(s.token.balance(&s.referrals.address), 71);
+
+    assert_eq!(
+        s.referrals.try_record_purchase(&bob, &0),
+        Err(Ok(ReferralError::InvalidAmount))
+    );
+    assert_eq!(
+        s.referrals
+            .try_record_purchase(&Address::generate(&s.env), &100),
+        Err(Ok(ReferralError::NotRegistered))
+    );
+    assert_eq!(
+        s.referrals.try_record_purchase(&bob, &i128::MAX),
+        Err(Ok(ReferralError::MathOverflow))
+    );
+}
+
+#[test]
+fn test_claim_zeroes_accrual() {
+    let s = setup();
+    let alice = s.user(None);
+    let bob = s.user(Some(&alice));
+    s.referrals.record_purchase(&bob, &2_000);
+
+    assert_eq!(s.referrals.claim_rewards(&alice), 100);
+    assert_eq!(s.token.balance(&alice), 100);
+    assert_eq!(s.referrals.rewards(&alice), 0);
+    assert_eq!(s.token.balance(&s.referrals.address), 0);
+    assert_eq!(
+        s.referrals.try_claim_rewards(&alice),
+        Err(Ok(ReferralError::NothingToClaim))
+    );
+
+    // Accrual starts again from zero.
+    s.referrals.record_purchase(&bob, &200);
+    assert_eq!(s.referrals.claim_rewards(&alice), 10);
+    assert_eq!(s.token.balance(&alice), 110);
+}
+
+#[test]
+fn test_rate_change_applies_to_future_purchases() {
+    let s = setup();
+    let alice = s.user(None);
+    let bob = s.user(Some(&alice));
+    s.referrals.record_purchase(&bob, &1_000);
+
+    s.referrals.set_reward_bps(&1_000);
+    assert_eq!(s.referrals.reward_bps(), 1_000);
+    assert_eq!(s.referrals.rewards(&alice), 50);
+    assert_eq!(s.referrals.record_purchase(&bob, &1_000), 100);
+    assert_eq!(s.referrals.rewards(&alice), 150);
+
+    s.referrals.set_reward_bps(&0);
+    assert_eq!(s.referrals.record_purchase(&bob, &1_000), 0);
+    assert_eq!(s.referrals.claim_rewards(&alice), 150);
+
+    assert_eq!(
+        s.referrals.try_set_reward_bps(&(BPS + 1)),
+        Err(Ok(ReferralError::InvalidBps))
+    );
+    assert_eq!(
+        s.referrals
+            .try_initialize(&s.admin, &s.token.address, &REWARD_BPS),
+        Err(Ok(ReferralError::AlreadyInitialized))
+    );
+}
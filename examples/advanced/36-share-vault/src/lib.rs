@@ -0,0 +1,265 @@
+//! # Share Vault
+//!
+//! An ERC-4626-style vault for one asset token. Depositors receive shares,
+//! and each share is a claim on a proportional slice of everything the
+//! vault holds. Yield paid into the vault (`harvest`) raises the assets
+//! behind every share, so the exchange rate only moves up.
+//!
+//! ```text
+//! shares = assets * (total_shares + V) / (total_assets + 1)    deposit, rounded down
+//! assets = shares * (total_assets + 1) / (total_shares + V)    withdraw, rounded down
+//! ```
+//!
+//! `total_assets` is the vault's token balance, so tokens sent straight to
+//! the vault count too. Both conversions round down, in the vault's favour:
+//! a depositor never gets a share the assets did not pay for, and a
+//! withdrawal never pays out an asset the shares did not own. What rounding
+//! keeps stays in the vault, with the remaining holders.
+//!
+//! ## Virtual shares
+//!
+//! `V = 10^virtual_offset` virtual shares and one virtual asset sit in the
+//! vault from the start. They defeat the inflation attack, where the first
+//! depositor mints a single share and then donates assets to push the
+//! price of a share so high that the next deposit rounds down to almost
+//! nothing. With virtual shares the donation is mostly captured by the
+//! virtual shares, and the attack costs more than it steals.
+//!
+//! Passing `None` as the offset turns the mitigation off: the first deposit
+//! mints shares 1:1 and later ones use `total_shares / total_assets`. That
+//! mode exists to show the attack; do not deploy it.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+/// Largest `virtual_offset`, keeping `10^offset` far from overflow.
+pub const MAX_VIRTUAL_OFFSET: u32 = 18;
+
+const NAMESPACE: Symbol = symbol_short!("vault");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Asset,
+    /// `Option<u32>`: the virtual share offset, `None` when disabled.
+    VirtualOffset,
+    TotalShares,
+    Shares(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VaultError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientShares = 4,
+    /// The deposit is worth less than one share.
+    ZeroShares = 5,
+    /// The shares are worth less than one asset unit.
+    ZeroAssets = 6,
+    MathOverflow = 7,
+    DivisionByZero = 8,
+    /// `virtual_offset` above `MAX_VIRTUAL_OFFSET`.
+    InvalidOffset = 9,
+}
+
+#[contract]
+pub struct ShareVaultContract;
+
+#[contractimpl]
+impl ShareVaultContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        virtual_offset: Option<u32>,
+    ) -> Result<(), VaultError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(VaultError::AlreadyInitialized);
+        }
+        if virtual_offset.is_some_and(|offset| offset > MAX_VIRTUAL_OFFSET) {
+            return Err(VaultError::InvalidOffset);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Asset, &asset);
+        env.storage()
+            .instance()
+            .set(&DataKey::VirtualOffset, &virtual_offset);
+        env.storage().instance().set(&DataKey::TotalShares, &0_i128);
+        Ok(())
+    }
+
+    /// Deposit `assets` and mint shares at the current rate, rounded down.
+    /// Returns the shares minted.
+    pub fn deposit(env: Env, user: Address, assets: i128) -> Result<i128, VaultError> {
+        user.require_auth();
+        if assets <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let shares = Self::convert_to_shares(env.clone(), assets)?;
+        if shares == 0 {
+            return Err(VaultError::ZeroShares);
+        }
+
+        Self::asset(&env)?.transfer(&user, &env.current_contract_address(), &assets);
+        Self::mint(&env, &user, shares)?;
+        env.events().publish(
+            (NAMESPACE, symbol_short!("deposit"), user),
+            (assets, shares),
+        );
+        Ok(shares)
+    }
+
+    /// Burn `shares` and pay out the assets behind them, rounded down.
+    /// Returns the assets paid.
+    pub fn withdraw(env: Env, user: Address, shares: i128) -> Result<i128, VaultError> {
+        user.require_auth();
+        if shares <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let balance = Self::shares_of(env.clone(), user.clone());
+        if shares > balance {
+            return Err(VaultError::InsufficientShares);
+        }
+        let assets = Self::convert_to_assets(env.clone(), shares)?;
+        if assets == 0 {
+            return Err(VaultError::ZeroAssets);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Shares(user.clone()), &(balance - shares));
+        let total = Self::total_shares(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalShares, &(total - shares));
+        Self::asset(&env)?.transfer(&env.current_contract_address(), &user, &assets);
+        env.events().publish(
+            (NAMESPACE, symbol_short!("withdraw"), user),
+            (shares, assets),
+        );
+        Ok(assets)
+    }
+
+    /// Pay `profit` into the vault from the admin, standing in for yield
+    /// earned by a strategy. Every share becomes worth more. Admin only.
+    pub fn harvest(env: Env, profit: i128) -> Result<(), VaultError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VaultError::NotInitialized)?;
+        admin.require_auth();
+        if profit <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        Self::asset(&env)?.transfer(&admin, &env.current_contract_address(), &profit);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("harvest")), profit);
+        Ok(())
+    }
+
+    /// The vault's asset balance.
+    pub fn total_assets(env: Env) -> Result<i128, VaultError> {
+        Ok(Self::asset(&env)?.balance(&env.current_contract_address()))
+    }
+
+    pub fn total_shares(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0)
+    }
+
+    pub fn shares_of(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Shares(user))
+            .unwrap_or(0)
+    }
+
+    /// Shares a deposit of `assets` would mint now, rounded down.
+    pub fn convert_to_shares(env: Env, assets: i128) -> Result<i128, VaultError> {
+        let total_assets = Self::total_assets(env.clone())?;
+        let total_shares = Self::total_shares(env.clone());
+        match Self::virtual_offset(&env) {
+            Some(offset) => mul_div(
+                assets,
+                checked_add(total_shares, pow10(offset))?,
+                checked_add(total_assets, 1)?,
+            ),
+            None if total_shares == 0 => Ok(assets),
+            None => mul_div(assets, total_shares, total_assets),
+        }
+    }
+
+    /// Assets `shares` would withdraw now, rounded down.
+    pub fn convert_to_assets(env: Env, shares: i128) -> Result<i128, VaultError> {
+        let total_assets = Self::total_assets(env.clone())?;
+        let total_shares = Self::total_shares(env.clone());
+        match Self::virtual_offset(&env) {
+            Some(offset) => mul_div(
+                shares,
+                checked_add(total_assets, 1)?,
+                checked_add(total_shares, pow10(offset))?,
+            ),
+            None if total_shares == 0 => Ok(shares),
+            None => mul_div(shares, total_assets, total_shares),
+        }
+    }
+}
+
+impl ShareVaultContract {
+    fn asset(env: &Env) -> Result<token::Client<'_>, VaultError> {
+        let address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .ok_or(VaultError::NotInitialized)?;
+        Ok(token::Client::new(env, &address))
+    }
+
+    fn virtual_offset(env: &Env) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::VirtualOffset)
+            .flatten()
+    }
+
+    fn mint(env: &Env, user: &Address, shares: i128) -> Result<(), VaultError> {
+        let key = DataKey::Shares(user.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &checked_add(balance, shares)?);
+        let total = checked_add(Self::total_shares(env.clone()), shares)?;
+        env.storage().instance().set(&DataKey::TotalShares, &total);
+        Ok(())
+    }
+}
+
+/// `a * b / c` for non-negative operands, rounded down.
+fn mul_div(a: i128, b: i128, c: i128) -> Result<i128, VaultError> {
+    if c == 0 {
+        return Err(VaultError::DivisionByZero);
+    }
+    a.checked_mul(b)
+        .map(|product| product / c)
+        .ok_or(VaultError::MathOverflow)
+}
+
+fn checked_add(a: i128, b: i128) -> Result<i128, VaultError> {
+    a.checked_add(b).ok_or(VaultError::MathOverflow)
+}
+
+fn pow10(exp: u32) -> i128 {
+    10_i128.pow(exp)
+}
+
+mod test;
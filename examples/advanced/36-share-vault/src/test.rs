@@ -0,0 +1,196 @@
+#![cfg(test)]
+
+use super::*;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal,
+};
+
+struct Setup {
+    env: Env,
+    alice: Address,
+    bob: Address,
+    asset: TokenClient<'static>,
+    vault: ShareVaultContractClient<'static>,
+}
+
+/// A vault with the given offset. The admin holds 10 000 assets for
+/// harvests, and Alice and Bob 100 000 each.
+fn setup(virtual_offset: Option<u32>) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let minter = StellarAssetClient::new(&env, &asset.address());
+    minter.mint(&admin, &10_000);
+    minter.mint(&alice, &100_000);
+    minter.mint(&bob, &100_000);
+
+    let vault =
+        ShareVaultContractClient::new(&env, &env.register_contract(None, ShareVaultContract));
+    vault.initialize(&admin, &asset.address(), &virtual_offset);
+    Setup {
+        asset: TokenClient::new(&env, &asset.address()),
+        env,
+        alice,
+        bob,
+        vault,
+    }
+}
+
+#[test]
+fn test_first_deposit_bootstrap() {
+    // One virtual share and one virtual asset: an empty vault is 1:1.
+    let s = setup(Some(0));
+    assert_eq!(s.vault.convert_to_shares(&1_000), 1_000);
+    assert_eq!(s.vault.deposit(&s.alice, &1_000), 1_000);
+    assert_eq!(s.vault.total_assets(), 1_000);
+    assert_eq!(s.vault.total_shares(), 1_000);
+    assert_eq!(s.vault.shares_of(&s.alice), 1_000);
+
+    // Without yield the rate stays 1:1 for later depositors.
+    assert_eq!(s.vault.deposit(&s.bob, &500), 500);
+    assert_eq!(s.vault.convert_to_assets(&500), 500);
+
+    let (topics, data) = all_events_for(&s.env, &s.vault.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("deposit"), s.bob.clone()).into_val(&s.env)
+    );
+    let amounts: (i128, i128) = data.into_val(&s.env);
+    assert_eq!(amounts, (500, 500));
+}
+
+#[test]
+fn test_harvest_raises_exchange_rate() {
+    let s = setup(Some(0));
+    s.vault.deposit(&s.alice, &1_000);
+    s.vault.harvest(&1_000);
+    assert_eq!(s.vault.total_assets(), 2_000);
+    assert_eq!(s.vault.total_shares(), 1_000);
+
+    // 1 000 * 2 001 / 1 001 = 1 999.000999: the virtual share keeps a
+    // sliver of the yield.
+    assert_eq!(s.vault.convert_to_assets(&1_000), 1_999);
+    // Bob pays about 2 assets per share now.
+    assert_eq!(s.vault.deposit(&s.bob, &2_000), 1_000);
+    assert_eq!(s.vault.shares_of(&s.bob), 1_000);
+    assert_eq!(s.vault.total_assets(), 4_000);
+
+    assert_eq!(s.vault.try_harvest(&0), Err(Ok(VaultError::InvalidAmount)));
+}
+
+#[test]
+fn test_rounding_favours_the_vault() {
+    let s = setup(Some(0));
+    s.vault.deposit(&s.alice, &1_000);
+    s.vault.harvest(&1_000);
+
+    // 1 * 1 001 / 2 001 rounds down to nothing, so the deposit is refused
+    // rather than taken for zero shares.
+    assert_eq!(s.vault.convert_to_shares(&1), 0);
+    assert_eq!(
+        s.vault.try_deposit(&s.bob, &1),
+        Err(Ok(VaultError::ZeroShares))
+    );
+
+    // 3 * 1 001 / 2 001 = 1.5: Bob pays 3 and gets 1 share.
+    assert_eq!(s.vault.deposit(&s.bob, &3), 1);
+    // 1 * 2 004 / 1 002 = 2: Bob's share pays out less than it cost,
+    // and the remainder stays in the vault.
+    assert_eq!(s.vault.withdraw(&s.bob, &1), 2);
+    assert_eq!(s.asset.balance(&s.bob), 100_000 - 1);
+    // Alice's 1 000 shares picked up Bob's lost unit.
+    assert_eq!(s.vault.convert_to_assets(&1_000), 2_000);
+}
+
+#[test]
+fn test_inflation_attack_without_virtual_shares() {
+    let s = setup(None);
+    let (attacker, victim) = (&s.alice, &s.bob);
+
+    // The attacker mints one share, then donates straight to the vault so
+    // that a share costs over 10 000 assets.
+    assert_eq!(s.vault.deposit(attacker, &1), 1);
+    s.asset.transfer(attacker, &s.vault.address, &10_000);
+    assert_eq!(s.vault.total_assets(), 10_001);
+
+    // 20 000 * 1 / 10 001 = 1.99: the victim gets 1 share for 20 000.
+    assert_eq!(s.vault.deposit(victim, &20_000), 1);
+    assert_eq!(s.vault.withdraw(attacker, &1), 15_000);
+    assert_eq!(s.asset.balance(attacker), 100_000 - 10_001 + 15_000);
+    assert_eq!(s.vault.withdraw(victim, &1), 15_001);
+    assert_eq!(s.asset.balance(victim), 100_000 - 4_999);
+}
+
+#[test]
+fn test_inflation_attack_with_virtual_shares() {
+    // 1 000 virtual shares.
+    let s = setup(Some(3));
+    let (attacker, victim) = (&s.alice, &s.bob);
+
+    assert_eq!(s.vault.deposit(attacker, &1), 1_000);
+    s.asset.transfer(attacker, &s.vault.address, &10_000);
+
+    // 20 000 * 2 000 / 10 002 = 3 999.2. The donation mostly went to the
+    // virtual shares, so the victim's deposit is priced almost fairly.
+    assert_eq!(s.vault.deposit(victim, &20_000), 3_999);
+    // 1 000 * 30 002 / 5 999 = 5 001.2: the attack loses about half the
+    // donation.
+    assert_eq!(s.vault.withdraw(attacker, &1_000), 5_001);
+    assert_eq!(s.asset.balance(attacker), 100_000 - 10_001 + 5_001);
+    // 3 999 * 25 001 / 4 999 = 19 999.8.
+    assert_eq!(s.vault.withdraw(victim, &3_999), 19_999);
+    assert_eq!(s.asset.balance(victim), 100_000 - 1);
+}
+
+#[test]
+fn test_full_exit_accounting() {
+    let s = setup(Some(0));
+    let alice_shares = s.vault.deposit(&s.alice, &1_000);
+    s.vault.harvest(&1_000);
+    let bob_shares = s.vault.deposit(&s.bob, &2_000);
+
+    assert_eq!(
+        s.vault.try_withdraw(&s.alice, &(alice_shares + 1)),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+    let alice_out = s.vault.withdraw(&s.alice, &alice_shares);
+    let bob_out = s.vault.withdraw(&s.bob, &bob_shares);
+    assert_eq!((alice_out, bob_out), (1_999, 2_000));
+
+    // Every share is gone. What is left belongs to the virtual share, and
+    // nothing was created or lost: 3 000 deposited plus 1 000 of yield.
+    assert_eq!(s.vault.total_shares(), 0);
+    assert_eq!(s.vault.shares_of(&s.alice), 0);
+    assert_eq!(s.vault.total_assets(), 1);
+    assert_eq!(alice_out + bob_out + s.vault.total_assets(), 3_000 + 1_000);
+    assert_eq!(
+        s.vault.try_withdraw(&s.bob, &1),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+}
+
+#[test]
+fn test_initialize_errors() {
+    let s = setup(Some(0));
+    assert_eq!(
+        s.vault.try_initialize(&s.alice, &s.asset.address, &Some(0)),
+        Err(Ok(VaultError::AlreadyInitialized))
+    );
+
+    let vault =
+        ShareVaultContractClient::new(&s.env, &s.env.register_contract(None, ShareVaultContract));
+    assert_eq!(
+        vault.try_deposit(&s.alice, &1),
+        Err(Ok(VaultError::NotInitialized))
+    );
+    assert_eq!(
+        vault.try_initialize(&s.alice, &s.asset.address, &Some(MAX_VIRTUAL_OFFSET + 1)),
+        Err(Ok(VaultError::InvalidOffset))
+    );
+}
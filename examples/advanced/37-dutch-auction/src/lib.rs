@@ -0,0 +1,236 @@
+//! # Dutch Auction
+//!
+//! A descending-price sale of a fixed number of units, paid in any SEP-41
+//! token. The unit price starts high and falls to a floor; buyers wait for
+//! a price they like and buy at it, first come first served.
+//!
+//! ```text
+//! elapsed = min(now - start_time, duration), rounded down to a multiple of step
+//! price   = start_price - (start_price - floor_price) * elapsed / duration
+//! ```
+//!
+//! - The price falls linearly when `step` is 1 second and in even steps
+//!   for a larger `step`. It reaches `floor_price` at `duration` and stays
+//!   there. The division rounds the discount down, so the price rounds up.
+//! - `buy` takes at most `quantity` units at the current price, and fails
+//!   if that price is above the buyer's `max_price`. If fewer units remain
+//!   than asked for, the buyer gets what is left and pays only for that.
+//! - Payment goes straight into the contract. The seller collects it with
+//!   `withdraw_proceeds` at any time.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+const NAMESPACE: Symbol = symbol_short!("dutch");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Sale,
+    /// Units bought by an address.
+    Units(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sale {
+    pub seller: Address,
+    pub token: Address,
+    pub start_price: i128,
+    pub floor_price: i128,
+    pub start_time: u64,
+    /// Seconds from `start_price` to `floor_price`.
+    pub duration: u64,
+    /// Seconds between price changes. Divides `duration`.
+    pub step: u64,
+    /// Units not yet sold.
+    pub remaining: u32,
+    /// Everything buyers have paid.
+    pub proceeds: i128,
+    /// The part of `proceeds` already paid to the seller.
+    pub withdrawn: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Purchase {
+    pub units: u32,
+    pub price: i128,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AuctionError {
+    AlreadyStarted = 1,
+    NotStarted = 2,
+    /// The floor is not positive, or above the start price.
+    InvalidPrice = 3,
+    /// A zero duration, or a step that does not divide the duration.
+    InvalidDuration = 4,
+    InvalidQuantity = 5,
+    /// The current price is above the buyer's `max_price`.
+    PriceAboveMax = 6,
+    SoldOut = 7,
+    MathOverflow = 8,
+    NothingToWithdraw = 9,
+}
+
+#[contract]
+pub struct DutchAuction;
+
+#[contractimpl]
+impl DutchAuction {
+    /// Put `supply` units on sale, starting now at `start_price`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn start(
+        env: Env,
+        seller: Address,
+        token: Address,
+        start_price: i128,
+        floor_price: i128,
+        duration: u64,
+        step: u64,
+        supply: u32,
+    ) -> Result<(), AuctionError> {
+        seller.require_auth();
+        if env.storage().instance().has(&DataKey::Sale) {
+            return Err(AuctionError::AlreadyStarted);
+        }
+        if floor_price <= 0 || floor_price > start_price {
+            return Err(AuctionError::InvalidPrice);
+        }
+        if duration == 0 || step == 0 || !duration.is_multiple_of(step) {
+            return Err(AuctionError::InvalidDuration);
+        }
+        if supply == 0 {
+            return Err(AuctionError::InvalidQuantity);
+        }
+        let sale = Sale {
+            seller: seller.clone(),
+            token,
+            start_price,
+            floor_price,
+            start_time: env.ledger().timestamp(),
+            duration,
+            step,
+            remaining: supply,
+            proceeds: 0,
+            withdrawn: 0,
+        };
+        env.storage().instance().set(&DataKey::Sale, &sale);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("started"), seller), supply);
+        Ok(())
+    }
+
+    /// The unit price at the current ledger timestamp.
+    pub fn current_price(env: Env) -> Result<i128, AuctionError> {
+        price_at(&Self::load(&env)?, env.ledger().timestamp())
+    }
+
+    /// Buy up to `quantity` units at the current price, if it is at most
+    /// `max_price`. Returns the units bought and the unit price paid.
+    pub fn buy(
+        env: Env,
+        buyer: Address,
+        max_price: i128,
+        quantity: u32,
+    ) -> Result<Purchase, AuctionError> {
+        buyer.require_auth();
+        let mut sale = Self::load(&env)?;
+        if quantity == 0 {
+            return Err(AuctionError::InvalidQuantity);
+        }
+        if sale.remaining == 0 {
+            return Err(AuctionError::SoldOut);
+        }
+        let price = price_at(&sale, env.ledger().timestamp())?;
+        if price > max_price {
+            return Err(AuctionError::PriceAboveMax);
+        }
+
+        let units = quantity.min(sale.remaining);
+        let cost = price
+            .checked_mul(i128::from(units))
+            .ok_or(AuctionError::MathOverflow)?;
+        sale.remaining -= units;
+        sale.proceeds = sale
+            .proceeds
+            .checked_add(cost)
+            .ok_or(AuctionError::MathOverflow)?;
+        env.storage().instance().set(&DataKey::Sale, &sale);
+
+        let key = DataKey::Units(buyer.clone());
+        let owned: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(owned + units));
+
+        token::Client::new(&env, &sale.token).transfer(
+            &buyer,
+            &env.current_contract_address(),
+            &cost,
+        );
+        let purchase = Purchase { units, price };
+        env.events().publish(
+            (NAMESPACE, symbol_short!("bought"), buyer),
+            purchase.clone(),
+        );
+        Ok(purchase)
+    }
+
+    /// Pay the seller everything collected since the last withdrawal.
+    /// Returns the amount.
+    pub fn withdraw_proceeds(env: Env) -> Result<i128, AuctionError> {
+        let mut sale = Self::load(&env)?;
+        sale.seller.require_auth();
+        let amount = sale.proceeds - sale.withdrawn;
+        if amount == 0 {
+            return Err(AuctionError::NothingToWithdraw);
+        }
+        sale.withdrawn = sale.proceeds;
+        env.storage().instance().set(&DataKey::Sale, &sale);
+        token::Client::new(&env, &sale.token).transfer(
+            &env.current_contract_address(),
+            &sale.seller,
+            &amount,
+        );
+        env.events()
+            .publish((NAMESPACE, symbol_short!("withdrawn")), amount);
+        Ok(amount)
+    }
+
+    pub fn units_of(env: Env, buyer: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Units(buyer))
+            .unwrap_or(0)
+    }
+
+    pub fn get_sale(env: Env) -> Option<Sale> {
+        env.storage().instance().get(&DataKey::Sale)
+    }
+}
+
+impl DutchAuction {
+    fn load(env: &Env) -> Result<Sale, AuctionError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Sale)
+            .ok_or(AuctionError::NotStarted)
+    }
+}
+
+fn price_at(sale: &Sale, now: u64) -> Result<i128, AuctionError> {
+    let elapsed = now.saturating_sub(sale.start_time).min(sale.duration);
+    let elapsed = elapsed - elapsed % sale.step;
+    let discount = (sale.start_price - sale.floor_price)
+        .checked_mul(i128::from(elapsed))
+        .ok_or(AuctionError::MathOverflow)?
+        / i128::from(sale.duration);
+    Ok(sale.start_price - discount)
+}
+
+mod test;
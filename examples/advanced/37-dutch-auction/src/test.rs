@@ -0,0 +1,232 @@
+#![cfg(test)]
+
+use super::*;
+use recipes_testutils::{all_events_for, set_timestamp};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal,
+};
+
+const START: u64 = 1_000;
+const DURATION: u64 = 900;
+const START_PRICE: i128 = 1_000;
+const FLOOR_PRICE: i128 = 100;
+const SUPPLY: u32 = 10;
+
+struct Setup {
+    env: Env,
+    seller: Address,
+    token: TokenClient<'static>,
+    auction: DutchAuctionClient<'static>,
+}
+
+/// A sale of `SUPPLY` units from 1 000 down to 100 over 900 seconds, in
+/// steps of `step` seconds.
+fn setup(step: u64) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, START);
+    let seller = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let auction = DutchAuctionClient::new(&env, &env.register_contract(None, DutchAuction));
+    auction.start(
+        &seller,
+        &asset.address(),
+        &START_PRICE,
+        &FLOOR_PRICE,
+        &DURATION,
+        &step,
+        &SUPPLY,
+    );
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        seller,
+        auction,
+    }
+}
+
+impl Setup {
+    fn at(&self, offset: u64) {
+        set_timestamp(&self.env, START + offset);
+    }
+
+    /// A buyer holding 100 000 tokens.
+    fn buyer(&self) -> Address {
+        let buyer = Address::generate(&self.env);
+        StellarAssetClient::new(&self.env, &self.token.address).mint(&buyer, &100_000);
+        buyer
+    }
+}
+
+#[test]
+fn test_price_at_start_midpoint_and_floor() {
+    let s = setup(1);
+    assert_eq!(s.auction.current_price(), START_PRICE);
+    // 900 * 1 / 900 = 1 off per second.
+    s.at(1);
+    assert_eq!(s.auction.current_price(), 999);
+    s.at(DURATION / 2);
+    assert_eq!(s.auction.current_price(), 550);
+    s.at(DURATION);
+    assert_eq!(s.auction.current_price(), FLOOR_PRICE);
+}
+
+#[test]
+fn test_stepwise_decay() {
+    let s = setup(300);
+    s.at(299);
+    assert_eq!(s.auction.current_price(), START_PRICE);
+    s.at(300);
+    assert_eq!(s.auction.current_price(), 700);
+    s.at(899);
+    assert_eq!(s.auction.current_price(), 400);
+    s.at(DURATION);
+    assert_eq!(s.auction.current_price(), FLOOR_PRICE);
+}
+
+#[test]
+fn test_price_never_below_floor() {
+    let s = setup(1);
+    for offset in [DURATION + 1, 10 * DURATION, u64::MAX - START] {
+        s.at(offset);
+        assert_eq!(s.auction.current_price(), FLOOR_PRICE);
+    }
+    let buyer = s.buyer();
+    assert_eq!(
+        s.auction.buy(&buyer, &FLOOR_PRICE, &2),
+        Purchase {
+            units: 2,
+            price: FLOOR_PRICE,
+        }
+    );
+}
+
+#[test]
+fn test_buy_rejected_above_max_price() {
+    let s = setup(1);
+    let buyer = s.buyer();
+    s.at(450);
+    assert_eq!(
+        s.auction.try_buy(&buyer, &549, &1),
+        Err(Ok(AuctionError::PriceAboveMax))
+    );
+    assert_eq!(s.token.balance(&buyer), 100_000);
+    assert_eq!(s.auction.get_sale().unwrap().remaining, SUPPLY);
+
+    // One second later the price has come down to the buyer's limit.
+    s.at(451);
+    assert_eq!(s.auction.buy(&buyer, &549, &1).price, 549);
+    assert_eq!(s.token.balance(&buyer), 100_000 - 549);
+}
+
+#[test]
+fn test_supply_exhausted_mid_purchase() {
+    let s = setup(1);
+    let (first, second, third) = (s.buyer(), s.buyer(), s.buyer());
+    s.auction.buy(&first, &START_PRICE, &7);
+
+    // Three units are left: the second buyer gets them and pays for three.
+    s.at(100);
+    assert_eq!(
+        s.auction.buy(&second, &START_PRICE, &5),
+        Purchase {
+            units: 3,
+            price: 900,
+        }
+    );
+    assert_eq!(s.token.balance(&second), 100_000 - 3 * 900);
+    assert_eq!(s.auction.units_of(&second), 3);
+    assert_eq!(s.auction.get_sale().unwrap().remaining, 0);
+
+    assert_eq!(
+        s.auction.try_buy(&third, &START_PRICE, &1),
+        Err(Ok(AuctionError::SoldOut))
+    );
+
+    let (topics, data) = all_events_for(&s.env, &s.auction.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("bought"), second.clone()).into_val(&s.env)
+    );
+    let purchase: Purchase = data.into_val(&s.env);
+    assert_eq!(purchase.units, 3);
+}
+
+#[test]
+fn test_proceeds_match_executed_prices() {
+    let s = setup(1);
+    let (a, b, c) = (s.buyer(), s.buyer(), s.buyer());
+    let mut expected = 0;
+    for (buyer, offset, quantity) in [(&a, 0, 2), (&b, 333, 3), (&a, 600, 1), (&c, 2_000, 4)] {
+        s.at(offset);
+        let purchase = s.auction.buy(buyer, &START_PRICE, &quantity);
+        expected += purchase.price * i128::from(purchase.units);
+    }
+    // 2 * 1 000 + 3 * 667 + 1 * 400 + 4 * 100.
+    assert_eq!(expected, 2_000 + 2_001 + 400 + 400);
+    assert_eq!(s.auction.units_of(&a), 3);
+    assert_eq!(s.token.balance(&s.auction.address), expected);
+
+    assert_eq!(s.auction.withdraw_proceeds(), expected);
+    assert_eq!(s.token.balance(&s.seller), expected);
+    assert_eq!(s.token.balance(&s.auction.address), 0);
+    let sale = s.auction.get_sale().unwrap();
+    assert_eq!((sale.proceeds, sale.withdrawn), (expected, expected));
+    assert_eq!(
+        s.auction.try_withdraw_proceeds(),
+        Err(Ok(AuctionError::NothingToWithdraw))
+    );
+}
+
+#[test]
+fn test_start_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let auction = DutchAuctionClient::new(&env, &env.register_contract(None, DutchAuction));
+    let seller = Address::generate(&env);
+    let token = Address::generate(&env);
+    let start = |start_price, floor_price, duration, step, supply| {
+        auction.try_start(
+            &seller,
+            &token,
+            &start_price,
+            &floor_price,
+            &duration,
+            &step,
+            &supply,
+        )
+    };
+
+    assert_eq!(
+        auction.try_current_price(),
+        Err(Ok(AuctionError::NotStarted))
+    );
+    assert_eq!(
+        start(100, 101, 900, 1, 1),
+        Err(Ok(AuctionError::InvalidPrice))
+    );
+    assert_eq!(
+        start(100, 0, 900, 1, 1),
+        Err(Ok(AuctionError::InvalidPrice))
+    );
+    assert_eq!(
+        start(100, 50, 900, 400, 1),
+        Err(Ok(AuctionError::InvalidDuration))
+    );
+    assert_eq!(
+        start(100, 50, 0, 1, 1),
+        Err(Ok(AuctionError::InvalidDuration))
+    );
+    assert_eq!(
+        start(100, 50, 900, 1, 0),
+        Err(Ok(AuctionError::InvalidQuantity))
+    );
+    // A flat price is allowed.
+    assert_eq!(start(100, 100, 900, 900, 1), Ok(Ok(())));
+    assert_eq!(
+        start(100, 100, 900, 900, 1),
+        Err(Ok(AuctionError::AlreadyStarted))
+    );
+}
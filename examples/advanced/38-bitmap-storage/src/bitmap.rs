@@ -0,0 +1,98 @@
+//! Flags packed 128 to a storage entry.
+//!
+//! Flag `index` lives in bit `index % 128` of the `u128` word stored under
+//! `DataKey::Word(index / 128)`. A word that was never written reads as
+//! all zeros, so unset flags cost nothing.
+
+use soroban_sdk::Env;
+
+use crate::{BitmapError, DataKey};
+
+/// Flags per storage entry.
+pub const WORD_BITS: u32 = u128::BITS;
+
+/// Widest range `count_set` will scan: 32 words.
+pub const MAX_COUNT_RANGE: u32 = 32 * WORD_BITS;
+
+pub fn get_bit(env: &Env, index: u32) -> bool {
+    word(env, index / WORD_BITS) & (1 << (index % WORD_BITS)) != 0
+}
+
+/// Set flag `index`. Returns `false` if it was already set, in which case
+/// nothing is written.
+pub fn set_bit(env: &Env, index: u32) -> bool {
+    let word_index = index / WORD_BITS;
+    let bit = 1 << (index % WORD_BITS);
+    let current = word(env, word_index);
+    if current & bit != 0 {
+        return false;
+    }
+    save_word(env, word_index, current | bit);
+    true
+}
+
+/// Set every flag in `from..to`, one write per word touched.
+pub fn set_range(env: &Env, from: u32, to: u32) -> Result<(), BitmapError> {
+    check_range(from, to)?;
+    for (word_index, mask) in words(from, to) {
+        let current = word(env, word_index);
+        if current & mask != mask {
+            save_word(env, word_index, current | mask);
+        }
+    }
+    Ok(())
+}
+
+/// Number of set flags in `from..to`. The range is capped at
+/// `MAX_COUNT_RANGE` so a call reads a bounded number of entries.
+pub fn count_set(env: &Env, from: u32, to: u32) -> Result<u32, BitmapError> {
+    check_range(from, to)?;
+    if to - from > MAX_COUNT_RANGE {
+        return Err(BitmapError::RangeTooLarge);
+    }
+    Ok(words(from, to)
+        .map(|(word_index, mask)| (word(env, word_index) & mask).count_ones())
+        .sum())
+}
+
+fn check_range(from: u32, to: u32) -> Result<(), BitmapError> {
+    if from > to {
+        return Err(BitmapError::InvalidRange);
+    }
+    Ok(())
+}
+
+/// The words covering `from..to`, each with a mask of the bits in range.
+fn words(from: u32, to: u32) -> impl Iterator<Item = (u32, u128)> {
+    let first = from / WORD_BITS;
+    let last = to.div_ceil(WORD_BITS);
+    (first..last).map(move |word_index| {
+        let start = word_index * WORD_BITS;
+        let lo = from.max(start) - start;
+        let hi = to.min(start.saturating_add(WORD_BITS)) - start;
+        (word_index, mask(lo, hi))
+    })
+}
+
+/// Bits `lo..hi` of a word, with `hi <= WORD_BITS`.
+fn mask(lo: u32, hi: u32) -> u128 {
+    let width = hi - lo;
+    if width == WORD_BITS {
+        u128::MAX
+    } else {
+        ((1 << width) - 1) << lo
+    }
+}
+
+fn word(env: &Env, word_index: u32) -> u128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Word(word_index))
+        .unwrap_or(0)
+}
+
+fn save_word(env: &Env, word_index: u32, value: u128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Word(word_index), &value);
+}
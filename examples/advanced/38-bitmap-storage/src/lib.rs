@@ -0,0 +1,122 @@
+//! # Bitmap Storage
+//!
+//! Contracts often need one yes/no flag per index: airdrop slots already
+//! claimed, nonces already used. Storing each flag under its own key costs
+//! a ledger entry per flag, and every entry pays rent and adds to the
+//! transaction footprint.
+//!
+//! The `bitmap` module packs 128 flags into one `u128` word per entry, so
+//! 200 claims touch 2 entries instead of 200. Reads and writes still cost
+//! one entry each; the saving is in how many entries exist.
+//!
+//! `claim` uses the bitmap to stop double claims. `claim_unpacked` does
+//! the same with one key per flag, for comparison.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol,
+};
+
+pub mod bitmap;
+
+const NAMESPACE: Symbol = symbol_short!("bitmap");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// 128 packed flags: bit `i` is flag `word * 128 + i`.
+    Word(u32),
+    /// One flag, used only by `claim_unpacked`.
+    Flag(u32),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BitmapError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    AlreadyClaimed = 3,
+    /// `from` is greater than `to`.
+    InvalidRange = 4,
+    /// The range is wider than `bitmap::MAX_COUNT_RANGE`.
+    RangeTooLarge = 5,
+}
+
+#[contract]
+pub struct BitmapContract;
+
+#[contractimpl]
+impl BitmapContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), BitmapError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(BitmapError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Set one flag. Setting a flag that is already set changes nothing.
+    /// Admin only.
+    pub fn set_bit(env: Env, index: u32) -> Result<(), BitmapError> {
+        Self::require_admin(&env)?;
+        bitmap::set_bit(&env, index);
+        Ok(())
+    }
+
+    /// Set every flag in `from..to`, for example to close a batch of slots
+    /// at once. Admin only.
+    pub fn set_range(env: Env, from: u32, to: u32) -> Result<(), BitmapError> {
+        Self::require_admin(&env)?;
+        bitmap::set_range(&env, from, to)
+    }
+
+    pub fn get_bit(env: Env, index: u32) -> bool {
+        bitmap::get_bit(&env, index)
+    }
+
+    /// Number of set flags in `from..to`, at most `MAX_COUNT_RANGE` wide.
+    pub fn count_set(env: Env, from: u32, to: u32) -> Result<u32, BitmapError> {
+        bitmap::count_set(&env, from, to)
+    }
+
+    /// Claim slot `index` once.
+    pub fn claim(env: Env, user: Address, index: u32) -> Result<(), BitmapError> {
+        user.require_auth();
+        if !bitmap::set_bit(&env, index) {
+            return Err(BitmapError::AlreadyClaimed);
+        }
+        env.events()
+            .publish((NAMESPACE, symbol_short!("claimed"), user), index);
+        Ok(())
+    }
+
+    /// `claim` with one storage entry per slot.
+    pub fn claim_unpacked(env: Env, user: Address, index: u32) -> Result<(), BitmapError> {
+        user.require_auth();
+        let key = DataKey::Flag(index);
+        if env.storage().persistent().has(&key) {
+            return Err(BitmapError::AlreadyClaimed);
+        }
+        env.storage().persistent().set(&key, &true);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("claimed"), user), index);
+        Ok(())
+    }
+}
+
+impl BitmapContract {
+    fn require_admin(env: &Env) -> Result<(), BitmapError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(BitmapError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+}
+
+mod test;
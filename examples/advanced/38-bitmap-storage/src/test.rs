@@ -0,0 +1,161 @@
+#![cfg(test)]
+
+use super::bitmap::{MAX_COUNT_RANGE, WORD_BITS};
+use super::*;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{
+    testutils::Address as _,
+    xdr::{ContractDataDurability, LedgerKey, ScVal},
+    Env, IntoVal,
+};
+
+/// Claims made in the storage comparison.
+const CLAIMS: u32 = 200;
+
+fn setup() -> (Env, BitmapContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = BitmapContractClient::new(&env, &env.register_contract(None, BitmapContract));
+    client.initialize(&Address::generate(&env));
+    (env, client)
+}
+
+/// Persistent data entries in the ledger, not counting contract instances.
+fn persistent_entries(env: &Env) -> usize {
+    env.to_snapshot()
+        .ledger
+        .entries()
+        .into_iter()
+        .filter(|(key, _)| match key.as_ref() {
+            LedgerKey::ContractData(data) => {
+                data.durability == ContractDataDurability::Persistent
+                    && data.key != ScVal::LedgerKeyContractInstance
+            }
+            _ => false,
+        })
+        .count()
+}
+
+#[test]
+fn test_packed_claims_use_fewer_entries() {
+    let (packed_env, packed) = setup();
+    let (unpacked_env, unpacked) = setup();
+    let user = Address::generate(&packed_env);
+    let other = Address::generate(&unpacked_env);
+    for index in 0..CLAIMS {
+        packed.claim(&user, &index);
+        unpacked.claim_unpacked(&other, &index);
+    }
+
+    // 200 flags fill one word and part of a second.
+    assert_eq!(persistent_entries(&packed_env), 2);
+    assert_eq!(persistent_entries(&unpacked_env), CLAIMS as usize);
+    assert_eq!(packed.count_set(&0, &CLAIMS), CLAIMS);
+}
+
+#[test]
+fn test_double_claim_rejected() {
+    let (env, client) = setup();
+    let user = Address::generate(&env);
+    client.claim(&user, &7);
+    assert_eq!(
+        client.try_claim(&user, &7),
+        Err(Ok(BitmapError::AlreadyClaimed))
+    );
+    // Another user cannot take the same slot either.
+    assert_eq!(
+        client.try_claim(&Address::generate(&env), &7),
+        Err(Ok(BitmapError::AlreadyClaimed))
+    );
+    client.claim(&user, &8);
+    assert_eq!(client.count_set(&0, &WORD_BITS), 2);
+
+    let (topics, data) = all_events_for(&env, &client.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("claimed"), user).into_val(&env)
+    );
+    let index: u32 = data.into_val(&env);
+    assert_eq!(index, 8);
+
+    client.claim_unpacked(&Address::generate(&env), &7);
+    assert_eq!(
+        client.try_claim_unpacked(&Address::generate(&env), &7),
+        Err(Ok(BitmapError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_word_boundary_indices() {
+    let (env, client) = setup();
+    client.set_bit(&127);
+    assert!(client.get_bit(&127));
+    assert!(!client.get_bit(&128));
+    assert!(!client.get_bit(&126));
+    assert_eq!(persistent_entries(&env), 1);
+
+    // The next index starts a new word.
+    client.set_bit(&128);
+    assert!(client.get_bit(&128));
+    assert_eq!(persistent_entries(&env), 2);
+    assert_eq!(client.count_set(&127, &129), 2);
+    assert_eq!(client.count_set(&0, &127), 0);
+    assert_eq!(client.count_set(&128, &128), 0);
+
+    // The top bits of the index space are reachable.
+    client.set_bit(&u32::MAX);
+    assert!(client.get_bit(&u32::MAX));
+    assert_eq!(client.count_set(&(u32::MAX - 200), &u32::MAX), 0);
+}
+
+#[test]
+fn test_set_is_idempotent() {
+    let (env, client) = setup();
+    client.set_bit(&3);
+    client.set_bit(&3);
+    assert_eq!(client.count_set(&0, &WORD_BITS), 1);
+
+    // A range over flags that are partly set only adds the missing ones.
+    client.set_range(&0, &10);
+    client.set_range(&5, &10);
+    assert_eq!(client.count_set(&0, &WORD_BITS), 10);
+    assert_eq!(persistent_entries(&env), 1);
+    assert_eq!(
+        client.try_claim(&Address::generate(&env), &3),
+        Err(Ok(BitmapError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_set_range_across_words() {
+    let (env, client) = setup();
+    client.set_range(&100, &300);
+    assert!(!client.get_bit(&99));
+    assert!(client.get_bit(&100));
+    assert!(client.get_bit(&299));
+    assert!(!client.get_bit(&300));
+    assert_eq!(client.count_set(&0, &400), 200);
+    // Words 0, 1 and 2: partial, full, partial.
+    assert_eq!(persistent_entries(&env), 3);
+    assert_eq!(client.count_set(&128, &256), WORD_BITS);
+    assert_eq!(client.count_set(&250, &260), 10);
+}
+
+#[test]
+fn test_count_range_is_bounded() {
+    let (_, client) = setup();
+    client.set_range(&0, &MAX_COUNT_RANGE);
+    assert_eq!(client.count_set(&0, &MAX_COUNT_RANGE), MAX_COUNT_RANGE);
+    assert_eq!(
+        client.try_count_set(&0, &(MAX_COUNT_RANGE + 1)),
+        Err(Ok(BitmapError::RangeTooLarge))
+    );
+    assert_eq!(
+        client.try_count_set(&10, &9),
+        Err(Ok(BitmapError::InvalidRange))
+    );
+    assert_eq!(
+        client.try_set_range(&10, &9),
+        Err(Ok(BitmapError::InvalidRange))
+    );
+}
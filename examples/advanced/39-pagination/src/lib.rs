@@ -0,0 +1,161 @@
+//! # Paginated Storage
+//!
+//! A list that can keep growing, stored as fixed-size pages: element `i`
+//! lives at position `i % PAGE_SIZE` of the `Vec` under
+//! `DataKey::Page(i / PAGE_SIZE)`.
+//!
+//! ## Why not one `Vec`?
+//!
+//! Storing the whole list under one key works until it doesn't:
+//!
+//! - A ledger entry has a maximum size set by the network (64 KiB at the
+//!   time of writing). A `Vec<Address>` passes it at around 1 600
+//!   elements, and then every append fails. Nothing can be added or
+//!   removed until the data is migrated.
+//! - Every read and every append loads and re-encodes the entire entry,
+//!   so their cost grows with the list. Long before the size limit, a call
+//!   that touches the list can run out of its CPU and read-byte budget.
+//!
+//! With pages, every operation touches at most two entries of at most
+//! `PAGE_SIZE` elements, whatever the list's length. Readers fetch the
+//! list a page at a time with `get_page`.
+//!
+//! ## Removal
+//!
+//! `remove_at` swaps the last element into the removed slot and shrinks
+//! the last page, deleting it once empty. That keeps every page but the
+//! last full, at the cost of element order.
+//!
+//! Access control is left out: `append` and `remove_at` are open to
+//! anyone, which a real contract would not allow.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Vec};
+
+/// Elements per page.
+pub const PAGE_SIZE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Number of elements, as a `u64`.
+    Len,
+    /// Up to `PAGE_SIZE` elements. Only the last page may be partial.
+    Page(u32),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PaginationError {
+    IndexOutOfBounds = 1,
+}
+
+#[contract]
+pub struct PaginatedList;
+
+#[contractimpl]
+impl PaginatedList {
+    /// Add `value` at the end. Returns its index.
+    pub fn append(env: Env, value: Address) -> u64 {
+        let len = Self::len(env.clone());
+        let (page, _) = locate(len);
+        let mut items = Self::get_page(env.clone(), page);
+        items.push_back(value);
+        Self::save_page(&env, page, &items);
+        env.storage().instance().set(&DataKey::Len, &(len + 1));
+        len
+    }
+
+    pub fn get(env: Env, index: u64) -> Result<Address, PaginationError> {
+        if index >= Self::len(env.clone()) {
+            return Err(PaginationError::IndexOutOfBounds);
+        }
+        let (page, slot) = locate(index);
+        Ok(Self::get_page(env, page).get_unchecked(slot))
+    }
+
+    /// The elements of one page. Empty past the end of the list.
+    pub fn get_page(env: Env, page: u32) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Page(page))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn len(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::Len).unwrap_or(0)
+    }
+
+    pub fn is_empty(env: Env) -> bool {
+        Self::len(env) == 0
+    }
+
+    pub fn page_count(env: Env) -> u32 {
+        let len = Self::len(env);
+        len.div_ceil(u64::from(PAGE_SIZE)) as u32
+    }
+
+    /// Remove the element at `index` by moving the last element into its
+    /// place. Returns the removed element.
+    pub fn remove_at(env: Env, index: u64) -> Result<Address, PaginationError> {
+        let len = Self::len(env.clone());
+        if index >= len {
+            return Err(PaginationError::IndexOutOfBounds);
+        }
+        let (last_page, _) = locate(len - 1);
+        let mut tail = Self::get_page(env.clone(), last_page);
+        let last = tail.pop_back_unchecked();
+
+        let (page, slot) = locate(index);
+        let removed = if page == last_page {
+            if slot == tail.len() {
+                // The last element itself: popping it was the removal.
+                last
+            } else {
+                let removed = tail.get_unchecked(slot);
+                tail.set(slot, last);
+                removed
+            }
+        } else {
+            let mut items = Self::get_page(env.clone(), page);
+            let removed = items.get_unchecked(slot);
+            items.set(slot, last);
+            Self::save_page(&env, page, &items);
+            removed
+        };
+
+        if tail.is_empty() {
+            env.storage().persistent().remove(&DataKey::Page(last_page));
+        } else {
+            Self::save_page(&env, last_page, &tail);
+        }
+        env.storage().instance().set(&DataKey::Len, &(len - 1));
+        Ok(removed)
+    }
+
+    /// Every element, page by page. This is the read that grows with the
+    /// list; it is here for the budget comparison.
+    pub fn get_all(env: Env) -> Vec<Address> {
+        let mut all = Vec::new(&env);
+        for page in 0..Self::page_count(env.clone()) {
+            all.append(&Self::get_page(env.clone(), page));
+        }
+        all
+    }
+}
+
+impl PaginatedList {
+    fn save_page(env: &Env, page: u32, items: &Vec<Address>) {
+        env.storage().persistent().set(&DataKey::Page(page), items);
+    }
+}
+
+/// The page and position within it of element `index`.
+fn locate(index: u64) -> (u32, u32) {
+    let page_size = u64::from(PAGE_SIZE);
+    ((index / page_size) as u32, (index % page_size) as u32)
+}
+
+mod test;
@@ -0,0 +1,131 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::TestEnv;
+use soroban_sdk::testutils::Address as _;
+
+/// The full collection must cost at least this many times one page to read.
+const MARGIN: u64 = 2;
+
+fn setup() -> (Env, PaginatedListClient<'static>) {
+    let (env, _, client) = TestEnv::with_contract(PaginatedList, PaginatedListClient::new);
+    env.budget().reset_unlimited();
+    (env, client)
+}
+
+/// Append `n` fresh addresses and return them in order.
+fn fill(env: &Env, client: &PaginatedListClient, n: u64) -> std::vec::Vec<Address> {
+    (0..n)
+        .map(|i| {
+            let value = Address::generate(env);
+            assert_eq!(client.append(&value), i);
+            value
+        })
+        .collect()
+}
+
+#[test]
+fn test_append_spans_pages() {
+    let (env, client) = setup();
+    let values = fill(&env, &client, 120);
+    assert_eq!(client.len(), 120);
+    assert_eq!(client.page_count(), 3);
+    assert_eq!(client.get_page(&0).len(), PAGE_SIZE);
+    assert_eq!(client.get_page(&1).len(), PAGE_SIZE);
+    assert_eq!(client.get_page(&2).len(), 20);
+    assert_eq!(client.get_page(&3).len(), 0);
+    assert_eq!(client.get_all(), Vec::from_slice(&env, &values));
+}
+
+#[test]
+fn test_cross_page_indexing() {
+    let (env, client) = setup();
+    let values = fill(&env, &client, 120);
+    for index in [0, 49, 50, 99, 100, 119] {
+        assert_eq!(client.get(&index), values[index as usize]);
+    }
+    assert_eq!(client.get_page(&1).get_unchecked(0), values[50]);
+    assert_eq!(
+        client.try_get(&120),
+        Err(Ok(PaginationError::IndexOutOfBounds))
+    );
+}
+
+#[test]
+fn test_swap_remove_from_middle_page() {
+    let (env, client) = setup();
+    let values = fill(&env, &client, 101);
+
+    // The last element, alone on page 2, moves into slot 10 of page 1 and
+    // the emptied page is deleted.
+    assert_eq!(client.remove_at(&60), values[60]);
+    assert_eq!(client.len(), 100);
+    assert_eq!(client.page_count(), 2);
+    assert_eq!(client.get(&60), values[100]);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::Page(2)));
+    });
+
+    // Removing from inside the last page.
+    assert_eq!(client.remove_at(&55), values[55]);
+    assert_eq!(client.get(&55), values[99]);
+    assert_eq!(client.get_page(&1).len(), PAGE_SIZE - 1);
+
+    // Removing the last element moves nothing.
+    assert_eq!(client.remove_at(&98), values[98]);
+    assert_eq!(client.len(), 98);
+    let mut expected = values[..98].to_vec();
+    expected[60] = values[100].clone();
+    expected[55] = values[99].clone();
+    assert_eq!(client.get_all(), Vec::from_slice(&env, &expected));
+}
+
+#[test]
+fn test_empty_collection() {
+    let (env, client) = setup();
+    assert!(client.is_empty());
+    assert_eq!(client.len(), 0);
+    assert_eq!(client.page_count(), 0);
+    assert_eq!(client.get_page(&0), Vec::new(&env));
+    assert_eq!(client.get_all(), Vec::new(&env));
+    assert_eq!(
+        client.try_get(&0),
+        Err(Ok(PaginationError::IndexOutOfBounds))
+    );
+    assert_eq!(
+        client.try_remove_at(&0),
+        Err(Ok(PaginationError::IndexOutOfBounds))
+    );
+
+    // Removing the only element empties the collection again.
+    let value = Address::generate(&env);
+    client.append(&value);
+    assert!(!client.is_empty());
+    assert_eq!(client.remove_at(&0), value);
+    assert!(client.is_empty());
+    assert_eq!(client.page_count(), 0);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::Page(0)));
+    });
+}
+
+#[test]
+fn test_reading_one_page_beats_reading_everything() {
+    let (env, client) = setup();
+    fill(&env, &client, 6 * u64::from(PAGE_SIZE));
+
+    env.budget().reset_default();
+    client.get_page(&3);
+    let page = env.budget().cpu_instruction_cost();
+    env.budget().reset_default();
+    client.get_all();
+    let all = env.budget().cpu_instruction_cost();
+
+    std::println!("one page {page} cpu, six pages {all} cpu");
+    assert!(
+        all >= page * MARGIN,
+        "expected at least {MARGIN}x, got {all} vs {page}"
+    );
+}
@@ -0,0 +1,278 @@
+//! # Role-Gated Vault
+//!
+//! A token vault whose withdrawal rule depends on the caller's role:
+//!
+//! - **Admins** may withdraw any amount the vault holds.
+//! - **Moderators** may withdraw from the vault up to a per-transaction
+//!   cap that admins configure.
+//! - **Users**, meaning anyone without a role, may only withdraw what they
+//!   deposited themselves.
+//!
+//! Roles are stored with `recipes-access-control`; an account holding both
+//! roles is treated as an admin. Errors follow the `recipes-errors`
+//! catalogue, and the two ways a withdrawal can be too large have their own
+//! codes: `ExceedsRoleLimit` when a moderator asks for more than the cap,
+//! `InsufficientBalance` when the funds are not there.
+//!
+//! Admin and moderator withdrawals draw on the same pool that backs user
+//! deposits. The recipe treats them as trusted treasury operators; a user
+//! withdrawal fails with `InsufficientBalance` if they have drained it.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+const NAMESPACE: Symbol = symbol_short!("vault");
+
+/// May withdraw any amount.
+pub const ADMIN: Symbol = symbol_short!("admin");
+/// May withdraw up to the moderator cap per transaction.
+pub const MODERATOR: Symbol = symbol_short!("moderator");
+/// The role label for callers holding neither role, as used in events.
+pub const USER: Symbol = symbol_short!("user");
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VaultError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// The caller is not an admin. `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// A balance no longer fits in an `i128`. `CommonError::Overflow`.
+    Overflow = 5,
+    /// A non-positive amount, a negative cap, or an unknown role.
+    /// `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// A moderator asked for more than the per-transaction cap.
+    ExceedsRoleLimit = 100,
+    /// A user asked for more than they deposited, or anyone asked for more
+    /// than the vault holds.
+    InsufficientBalance = 101,
+}
+
+impl From<recipes_access_control::AccessError> for VaultError {
+    fn from(_: recipes_access_control::AccessError) -> Self {
+        VaultError::Unauthorized
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Token,
+    ModeratorCap,
+    /// Tokens held by the vault, as tracked by deposits and withdrawals.
+    Total,
+    /// What a user has deposited and not yet withdrawn.
+    Balance(Address),
+}
+
+#[contract]
+pub struct RoleVaultContract;
+
+#[contractimpl]
+impl RoleVaultContract {
+    /// Set the vault's token and moderator cap, and make `admin` the first
+    /// admin.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        moderator_cap: i128,
+    ) -> Result<(), VaultError> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Token) {
+            return Err(VaultError::AlreadyInitialized);
+        }
+        if moderator_cap < 0 {
+            return Err(VaultError::InvalidInput);
+        }
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&DataKey::ModeratorCap, &moderator_cap);
+        recipes_access_control::grant(&env, &admin, &ADMIN);
+        Ok(())
+    }
+
+    /// Give `role` (`ADMIN` or `MODERATOR`) to `account`. Admin only.
+    pub fn grant_role(
+        env: Env,
+        admin: Address,
+        account: Address,
+        role: Symbol,
+    ) -> Result<(), VaultError> {
+        Self::require_admin(&env, &admin)?;
+        check_role(&role)?;
+        if recipes_access_control::grant(&env, &account, &role) {
+            env.events()
+                .publish((NAMESPACE, symbol_short!("granted"), account), role);
+        }
+        Ok(())
+    }
+
+    /// Take `role` away from `account`. Admin only. Nothing stops an admin
+    /// from revoking the last admin, which leaves the cap fixed for good.
+    pub fn revoke_role(
+        env: Env,
+        admin: Address,
+        account: Address,
+        role: Symbol,
+    ) -> Result<(), VaultError> {
+        Self::require_admin(&env, &admin)?;
+        check_role(&role)?;
+        if recipes_access_control::revoke(&env, &account, &role) {
+            env.events()
+                .publish((NAMESPACE, symbol_short!("revoked"), account), role);
+        }
+        Ok(())
+    }
+
+    /// Change the moderators' per-transaction cap. Admin only.
+    pub fn set_moderator_cap(env: Env, admin: Address, cap: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env, &admin)?;
+        if cap < 0 {
+            return Err(VaultError::InvalidInput);
+        }
+        let old = Self::moderator_cap(env.clone())?;
+        env.storage().instance().set(&DataKey::ModeratorCap, &cap);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("cap")), (old, cap));
+        Ok(())
+    }
+
+    /// Move `amount` of the token from `user` into the vault and credit it
+    /// to their balance. Anyone may deposit, whatever their role.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<(), VaultError> {
+        user.require_auth();
+        let token = Self::token(&env)?;
+        if amount <= 0 {
+            return Err(VaultError::InvalidInput);
+        }
+        let balance = Self::balance(env.clone(), user.clone())
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        let total = Self::total(env.clone())
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+
+        token.transfer(&user, &env.current_contract_address(), &amount);
+        Self::set_balance(&env, &user, balance);
+        env.storage().instance().set(&DataKey::Total, &total);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("deposit"), user), amount);
+        Ok(())
+    }
+
+    /// Pay `amount` out of the vault to `caller`, under the rule for the
+    /// caller's role. Returns the role the withdrawal was made under.
+    pub fn withdraw(env: Env, caller: Address, amount: i128) -> Result<Symbol, VaultError> {
+        caller.require_auth();
+        let token = Self::token(&env)?;
+        if amount <= 0 {
+            return Err(VaultError::InvalidInput);
+        }
+        let role = Self::role_of(env.clone(), caller.clone());
+        if role == MODERATOR && amount > Self::moderator_cap(env.clone())? {
+            return Err(VaultError::ExceedsRoleLimit);
+        }
+        let balance = Self::balance(env.clone(), caller.clone());
+        if role == USER && amount > balance {
+            return Err(VaultError::InsufficientBalance);
+        }
+        let total = Self::total(env.clone());
+        if amount > total {
+            return Err(VaultError::InsufficientBalance);
+        }
+
+        if role == USER {
+            Self::set_balance(&env, &caller, balance - amount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Total, &(total - amount));
+        token.transfer(&env.current_contract_address(), &caller, &amount);
+        env.events().publish(
+            (NAMESPACE, symbol_short!("withdraw"), caller, role.clone()),
+            amount,
+        );
+        Ok(role)
+    }
+
+    /// `ADMIN`, `MODERATOR` or `USER`: the rule `withdraw` applies to
+    /// `account`.
+    pub fn role_of(env: Env, account: Address) -> Symbol {
+        if recipes_access_control::has(&env, &account, &ADMIN) {
+            ADMIN
+        } else if recipes_access_control::has(&env, &account, &MODERATOR) {
+            MODERATOR
+        } else {
+            USER
+        }
+    }
+
+    /// What `user` has deposited and not withdrawn.
+    pub fn balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    /// Tokens held by the vault.
+    pub fn total(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Total).unwrap_or(0)
+    }
+
+    pub fn moderator_cap(env: Env) -> Result<i128, VaultError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ModeratorCap)
+            .ok_or(VaultError::NotInitialized)
+    }
+}
+
+impl RoleVaultContract {
+    fn token(env: &Env) -> Result<token::Client<'_>, VaultError> {
+        let address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VaultError::NotInitialized)?;
+        Ok(token::Client::new(env, &address))
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), VaultError> {
+        if !env.storage().instance().has(&DataKey::Token) {
+            return Err(VaultError::NotInitialized);
+        }
+        admin.require_auth();
+        recipes_access_control::require_role(env, admin, &ADMIN)?;
+        Ok(())
+    }
+
+    fn set_balance(env: &Env, user: &Address, balance: i128) {
+        let key = DataKey::Balance(user.clone());
+        if balance == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &balance);
+        }
+    }
+}
+
+fn check_role(role: &Symbol) -> Result<(), VaultError> {
+    if *role != ADMIN && *role != MODERATOR {
+        return Err(VaultError::InvalidInput);
+    }
+    Ok(())
+}
+
+mod test;
@@ -0,0 +1,291 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal,
+};
+
+const CAP: i128 = 100;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    moderator: Address,
+    alice: Address,
+    bob: Address,
+    token: TokenClient<'static>,
+    vault: RoleVaultContractClient<'static>,
+}
+
+/// A vault with a moderator capped at `CAP`, and 500 tokens deposited by
+/// each of alice and bob.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let moderator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let minter = StellarAssetClient::new(&env, &asset.address());
+    minter.mint(&alice, &1_000);
+    minter.mint(&bob, &1_000);
+
+    let vault = RoleVaultContractClient::new(&env, &env.register_contract(None, RoleVaultContract));
+    vault.initialize(&admin, &asset.address(), &CAP);
+    vault.grant_role(&admin, &moderator, &MODERATOR);
+    vault.deposit(&alice, &500);
+    vault.deposit(&bob, &500);
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        admin,
+        moderator,
+        alice,
+        bob,
+        vault,
+    }
+}
+
+/// The tracked total matches the vault's actual token holdings.
+fn assert_conserved(s: &Setup) {
+    assert_eq!(s.vault.total(), s.token.balance(&s.vault.address));
+}
+
+#[test]
+fn test_user_withdraws_own_balance_only() {
+    let s = setup();
+    assert_eq!(s.vault.role_of(&s.alice), USER);
+    assert_eq!(
+        s.vault.try_withdraw(&s.alice, &501),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    assert_eq!(s.vault.withdraw(&s.alice, &500), USER);
+    assert_eq!(s.vault.balance(&s.alice), 0);
+    assert_eq!(s.token.balance(&s.alice), 1_000);
+
+    let (topics, data) = all_events_for(&s.env, &s.vault.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("withdraw"), s.alice.clone(), USER).into_val(&s.env)
+    );
+    let amount: i128 = data.into_val(&s.env);
+    assert_eq!(amount, 500);
+    assert_conserved(&s);
+}
+
+#[test]
+fn test_user_cannot_take_another_users_funds() {
+    let s = setup();
+    let carol = Address::generate(&s.env);
+    // Bob's deposit sits in the vault, but carol has deposited nothing.
+    assert_eq!(
+        s.vault.try_withdraw(&carol, &1),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    // Alice can take only the 500 deposited, not bob's as well.
+    assert_eq!(
+        s.vault.try_withdraw(&s.alice, &1_000),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    assert_eq!(s.vault.balance(&s.bob), 500);
+    assert_eq!(s.vault.total(), 1_000);
+    assert_conserved(&s);
+}
+
+#[test]
+fn test_moderator_limited_per_transaction() {
+    let s = setup();
+    assert_eq!(s.vault.role_of(&s.moderator), MODERATOR);
+    assert_eq!(
+        s.vault.try_withdraw(&s.moderator, &(CAP + 1)),
+        Err(Ok(VaultError::ExceedsRoleLimit))
+    );
+    // The cap is per transaction, not cumulative.
+    assert_eq!(s.vault.withdraw(&s.moderator, &CAP), MODERATOR);
+    assert_eq!(s.vault.withdraw(&s.moderator, &CAP), MODERATOR);
+    assert_eq!(s.token.balance(&s.moderator), 2 * CAP);
+    assert_eq!(s.vault.total(), 1_000 - 2 * CAP);
+
+    let (topics, _) = all_events_for(&s.env, &s.vault.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (
+            NAMESPACE,
+            symbol_short!("withdraw"),
+            s.moderator.clone(),
+            MODERATOR
+        )
+            .into_val(&s.env)
+    );
+    assert_conserved(&s);
+}
+
+#[test]
+fn test_admin_withdraws_up_to_vault_holdings() {
+    let s = setup();
+    assert_eq!(
+        s.vault.try_withdraw(&s.admin, &1_001),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    assert_eq!(s.vault.withdraw(&s.admin, &1_000), ADMIN);
+    assert_eq!(s.vault.total(), 0);
+    assert_eq!(s.token.balance(&s.admin), 1_000);
+
+    // Deposits are still recorded, but the pool behind them is empty.
+    assert_eq!(s.vault.balance(&s.alice), 500);
+    assert_eq!(
+        s.vault.try_withdraw(&s.alice, &1),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    assert_conserved(&s);
+}
+
+#[test]
+fn test_cap_reconfiguration() {
+    let s = setup();
+    s.vault.set_moderator_cap(&s.admin, &300);
+    assert_eq!(s.vault.moderator_cap(), 300);
+    let (topics, data) = all_events_for(&s.env, &s.vault.address).pop().unwrap();
+    assert_eq!(topics, (NAMESPACE, symbol_short!("cap")).into_val(&s.env));
+    let change: (i128, i128) = data.into_val(&s.env);
+    assert_eq!(change, (CAP, 300));
+    assert_eq!(s.vault.withdraw(&s.moderator, &300), MODERATOR);
+
+    s.vault.set_moderator_cap(&s.admin, &0);
+    assert_eq!(
+        s.vault.try_withdraw(&s.moderator, &1),
+        Err(Ok(VaultError::ExceedsRoleLimit))
+    );
+
+    // Only admins configure the cap, and it cannot go negative.
+    assert_eq!(
+        s.vault.try_set_moderator_cap(&s.moderator, &1_000),
+        Err(Ok(VaultError::Unauthorized))
+    );
+    assert_eq!(
+        s.vault.try_set_moderator_cap(&s.admin, &-1),
+        Err(Ok(VaultError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_role_changes_switch_the_withdrawal_rule() {
+    let s = setup();
+    // A moderator who also deposited withdraws under the cap, not from the
+    // deposit: the role decides the rule.
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.moderator, &1_000);
+    s.vault.deposit(&s.moderator, &800);
+    assert_eq!(
+        s.vault.try_withdraw(&s.moderator, &800),
+        Err(Ok(VaultError::ExceedsRoleLimit))
+    );
+
+    s.vault.revoke_role(&s.admin, &s.moderator, &MODERATOR);
+    assert_eq!(s.vault.role_of(&s.moderator), USER);
+    assert_eq!(s.vault.withdraw(&s.moderator, &800), USER);
+
+    s.vault.grant_role(&s.admin, &s.alice, &ADMIN);
+    assert_eq!(s.vault.withdraw(&s.alice, &900), ADMIN);
+
+    assert_eq!(
+        s.vault.try_grant_role(&s.bob, &s.bob, &ADMIN),
+        Err(Ok(VaultError::Unauthorized))
+    );
+    assert_eq!(
+        s.vault
+            .try_grant_role(&s.admin, &s.bob, &symbol_short!("owner")),
+        Err(Ok(VaultError::InvalidInput))
+    );
+    assert_conserved(&s);
+}
+
+#[test]
+fn test_scripted_sequence_conserves_balance() {
+    let s = setup();
+    let holders = [
+        s.admin.clone(),
+        s.moderator.clone(),
+        s.alice.clone(),
+        s.bob.clone(),
+        s.vault.address.clone(),
+    ];
+    let supply = || -> i128 { holders.iter().map(|h| s.token.balance(h)).sum() };
+    let initial = supply();
+
+    s.vault.withdraw(&s.alice, &200);
+    s.vault.deposit(&s.bob, &300);
+    assert!(s.vault.try_withdraw(&s.bob, &801).is_err());
+    s.vault.withdraw(&s.bob, &800);
+    assert!(s.vault.try_withdraw(&s.moderator, &(CAP + 1)).is_err());
+    s.vault.withdraw(&s.moderator, &CAP);
+    s.vault.set_moderator_cap(&s.admin, &250);
+    s.vault.deposit(&s.alice, &700);
+    s.vault.withdraw(&s.moderator, &250);
+    s.vault.withdraw(&s.admin, &150);
+    assert_conserved(&s);
+
+    // 1 000 + 300 + 700 in, 200 + 800 + 100 + 250 + 150 out.
+    assert_eq!(s.vault.total(), 500);
+    assert_eq!(s.vault.balance(&s.alice), 1_000);
+    assert_eq!(s.vault.balance(&s.bob), 0);
+    assert_eq!(supply(), initial);
+    assert_conserved(&s);
+}
+
+#[test]
+fn test_initialization_and_input_checks() {
+    let s = setup();
+    assert_eq!(
+        s.vault.try_initialize(&s.admin, &s.token.address, &CAP),
+        Err(Ok(VaultError::AlreadyInitialized))
+    );
+    assert_eq!(
+        s.vault.try_deposit(&s.alice, &0),
+        Err(Ok(VaultError::InvalidInput))
+    );
+    assert_eq!(
+        s.vault.try_withdraw(&s.admin, &-5),
+        Err(Ok(VaultError::InvalidInput))
+    );
+
+    let fresh =
+        RoleVaultContractClient::new(&s.env, &s.env.register_contract(None, RoleVaultContract));
+    assert_eq!(
+        fresh.try_deposit(&s.alice, &10),
+        Err(Ok(VaultError::NotInitialized))
+    );
+    assert_eq!(
+        fresh.try_initialize(&s.admin, &s.token.address, &-1),
+        Err(Ok(VaultError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (VaultError::NotInitialized, CommonError::NotInitialized),
+        (
+            VaultError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (VaultError::Unauthorized, CommonError::Unauthorized),
+        (VaultError::Overflow, CommonError::Overflow),
+        (VaultError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    for error in [
+        VaultError::ExceedsRoleLimit,
+        VaultError::InsufficientBalance,
+    ] {
+        assert!(error as u32 >= recipes_errors::CONTRACT_MIN);
+    }
+}
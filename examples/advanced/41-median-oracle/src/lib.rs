@@ -0,0 +1,298 @@
+//! # Median Price Aggregator
+//!
+//! Up to `MAX_REPORTERS` registered reporters each submit one price per
+//! pair per round. `aggregate` takes the median of the round's reports,
+//! publishes it, and starts the next round.
+//!
+//! A median needs a majority of reporters to move it: one reporter quoting
+//! an absurd price shifts the result by at most one position in the sorted
+//! list, where a mean would follow the outlier. The quorum stops a round
+//! from being settled by too few voices.
+//!
+//! ```text
+//! sorted = [p0, p1, ..., p(n-1)]
+//! n odd:  median = p(n/2)
+//! n even: median = floor((p(n/2 - 1) + p(n/2)) / 2)
+//! ```
+//!
+//! Even counts round down. Prices are positive, so the average is computed
+//! as `low + (high - low) / 2`, which cannot overflow.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol,
+    Vec,
+};
+
+const NAMESPACE: Symbol = symbol_short!("median");
+
+/// Most reporters the admin may register.
+pub const MAX_REPORTERS: u32 = 10;
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum OracleError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// The caller is not a registered reporter. `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// `remove_reporter` on an address that is not a reporter.
+    /// `CommonError::NotFound`.
+    NotFound = 6,
+    /// A non-positive price, or a quorum of zero or above `MAX_REPORTERS`.
+    /// `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// Registering another reporter would pass `MAX_REPORTERS`.
+    TooManyReporters = 100,
+    /// The address is already a reporter.
+    AlreadyReporter = 101,
+    /// The reporter has already submitted a price for this pair this round.
+    AlreadySubmitted = 102,
+    /// Fewer reports than the quorum.
+    InsufficientReports = 103,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Reports needed before a round can be aggregated.
+    Quorum,
+    /// `Vec<Address>` of registered reporters.
+    Reporters,
+    /// Current round number of a pair, from 0.
+    Round(Symbol),
+    /// Reports for a pair's current round, by reporter.
+    Reports(Symbol),
+    /// Last aggregated price of a pair.
+    Latest(Symbol),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundPrice {
+    pub price: i128,
+    /// The round this price settled.
+    pub round: u32,
+    /// Number of reports the median was taken over.
+    pub reports: u32,
+    pub timestamp: u64,
+}
+
+#[contract]
+pub struct MedianOracleContract;
+
+#[contractimpl]
+impl MedianOracleContract {
+    pub fn initialize(env: Env, admin: Address, quorum: u32) -> Result<(), OracleError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(OracleError::AlreadyInitialized);
+        }
+        check_quorum(quorum)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Quorum, &quorum);
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &Vec::<Address>::new(&env));
+        Ok(())
+    }
+
+    /// Register `reporter`. Admin only.
+    pub fn add_reporter(env: Env, reporter: Address) -> Result<(), OracleError> {
+        Self::require_admin(&env)?;
+        let mut reporters = Self::reporters(env.clone());
+        if reporters.contains(&reporter) {
+            return Err(OracleError::AlreadyReporter);
+        }
+        if reporters.len() >= MAX_REPORTERS {
+            return Err(OracleError::TooManyReporters);
+        }
+        reporters.push_back(reporter.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &reporters);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("added")), reporter);
+        Ok(())
+    }
+
+    /// Deregister `reporter`. Admin only. Reports it already made in open
+    /// rounds still count.
+    pub fn remove_reporter(env: Env, reporter: Address) -> Result<(), OracleError> {
+        Self::require_admin(&env)?;
+        let mut reporters = Self::reporters(env.clone());
+        let index = reporters
+            .first_index_of(&reporter)
+            .ok_or(OracleError::NotFound)?;
+        reporters.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &reporters);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("removed")), reporter);
+        Ok(())
+    }
+
+    /// Change the quorum. Admin only.
+    pub fn set_quorum(env: Env, quorum: u32) -> Result<(), OracleError> {
+        Self::require_admin(&env)?;
+        check_quorum(quorum)?;
+        env.storage().instance().set(&DataKey::Quorum, &quorum);
+        Ok(())
+    }
+
+    /// Record `reporter`'s price for `pair` in the current round. Returns
+    /// the round number.
+    pub fn submit(
+        env: Env,
+        reporter: Address,
+        pair: Symbol,
+        price: i128,
+    ) -> Result<u32, OracleError> {
+        reporter.require_auth();
+        if !Self::reporters(env.clone()).contains(&reporter) {
+            return Err(OracleError::Unauthorized);
+        }
+        if price <= 0 {
+            return Err(OracleError::InvalidInput);
+        }
+        let mut reports = Self::reports(&env, &pair);
+        if reports.contains_key(reporter.clone()) {
+            return Err(OracleError::AlreadySubmitted);
+        }
+        reports.set(reporter.clone(), price);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reports(pair.clone()), &reports);
+
+        let round = Self::round(env.clone(), pair.clone());
+        env.events().publish(
+            (NAMESPACE, symbol_short!("submit"), pair, reporter),
+            (round, price),
+        );
+        Ok(round)
+    }
+
+    /// Settle the current round of `pair`: take the median of its reports,
+    /// store it as the latest price and open the next round. Anyone may
+    /// call this once the quorum is met.
+    pub fn aggregate(env: Env, pair: Symbol) -> Result<i128, OracleError> {
+        let quorum: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Quorum)
+            .ok_or(OracleError::NotInitialized)?;
+        let prices = Self::reports(&env, &pair).values();
+        if prices.len() < quorum {
+            return Err(OracleError::InsufficientReports);
+        }
+
+        let price = median(&sorted(&env, &prices));
+        let round = Self::round(env.clone(), pair.clone());
+        let settled = RoundPrice {
+            price,
+            round,
+            reports: prices.len(),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Latest(pair.clone()), &settled);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Reports(pair.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Round(pair.clone()), &(round + 1));
+        env.events().publish(
+            (NAMESPACE, symbol_short!("price"), pair),
+            (round, price, prices.len()),
+        );
+        Ok(price)
+    }
+
+    /// The last settled price of `pair`, if any round has been aggregated.
+    pub fn latest(env: Env, pair: Symbol) -> Option<RoundPrice> {
+        env.storage().persistent().get(&DataKey::Latest(pair))
+    }
+
+    /// The open round of `pair`.
+    pub fn round(env: Env, pair: Symbol) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Round(pair))
+            .unwrap_or(0)
+    }
+
+    /// Reports submitted for `pair` in the open round.
+    pub fn report_count(env: Env, pair: Symbol) -> u32 {
+        Self::reports(&env, &pair).len()
+    }
+
+    pub fn reporters(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Reporters)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+}
+
+impl MedianOracleContract {
+    fn require_admin(env: &Env) -> Result<(), OracleError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(OracleError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn reports(env: &Env, pair: &Symbol) -> Map<Address, i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Reports(pair.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
+}
+
+fn check_quorum(quorum: u32) -> Result<(), OracleError> {
+    if quorum == 0 || quorum > MAX_REPORTERS {
+        return Err(OracleError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// `prices` in ascending order. Insertion sort: there are at most
+/// `MAX_REPORTERS` of them.
+fn sorted(env: &Env, prices: &Vec<i128>) -> Vec<i128> {
+    let mut out = Vec::new(env);
+    for price in prices.iter() {
+        let index = out
+            .iter()
+            .position(|p| p > price)
+            .unwrap_or(out.len() as usize);
+        out.insert(index as u32, price);
+    }
+    out
+}
+
+/// Median of a non-empty ascending list of positive prices, rounding down
+/// for even counts.
+fn median(sorted: &Vec<i128>) -> i128 {
+    let mid = sorted.len() / 2;
+    let high = sorted.get_unchecked(mid);
+    if !sorted.len().is_multiple_of(2) {
+        return high;
+    }
+    let low = sorted.get_unchecked(mid - 1);
+    low + (high - low) / 2
+}
+
+mod test;
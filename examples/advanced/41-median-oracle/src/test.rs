@@ -0,0 +1,227 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::{all_events_for, set_timestamp};
+use soroban_sdk::{testutils::Address as _, Env, IntoVal};
+
+const PAIR: Symbol = symbol_short!("XLM_USD");
+const QUORUM: u32 = 3;
+
+struct Setup {
+    env: Env,
+    reporters: std::vec::Vec<Address>,
+    oracle: MedianOracleContractClient<'static>,
+}
+
+/// An oracle with a quorum of `QUORUM` and `n` registered reporters.
+fn setup(n: usize) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, 1_000);
+    let oracle =
+        MedianOracleContractClient::new(&env, &env.register_contract(None, MedianOracleContract));
+    oracle.initialize(&Address::generate(&env), &QUORUM);
+    let reporters = (0..n)
+        .map(|_| {
+            let reporter = Address::generate(&env);
+            oracle.add_reporter(&reporter);
+            reporter
+        })
+        .collect();
+    Setup {
+        env,
+        reporters,
+        oracle,
+    }
+}
+
+impl Setup {
+    /// Reporter `i` submits `price` for `PAIR`.
+    fn submit(&self, i: usize, price: i128) {
+        self.oracle.submit(&self.reporters[i], &PAIR, &price);
+    }
+}
+
+#[test]
+fn test_median_of_odd_count() {
+    let s = setup(5);
+    for (i, price) in [105, 101, 110, 99, 103].into_iter().enumerate() {
+        s.submit(i, price);
+    }
+    assert_eq!(s.oracle.aggregate(&PAIR), 103);
+    assert_eq!(
+        s.oracle.latest(&PAIR),
+        Some(RoundPrice {
+            price: 103,
+            round: 0,
+            reports: 5,
+            timestamp: 1_000,
+        })
+    );
+
+    let (topics, data) = all_events_for(&s.env, &s.oracle.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("price"), PAIR).into_val(&s.env)
+    );
+    let settled: (u32, i128, u32) = data.into_val(&s.env);
+    assert_eq!(settled, (0, 103, 5));
+}
+
+#[test]
+fn test_median_of_even_count_rounds_down() {
+    let s = setup(4);
+    // Middle two are 100 and 103: the average 101.5 rounds down.
+    for (i, price) in [103, 90, 100, 120].into_iter().enumerate() {
+        s.submit(i, price);
+    }
+    assert_eq!(s.oracle.aggregate(&PAIR), 101);
+
+    // An exact average needs no rounding.
+    for (i, price) in [100, 104, 90, 120].into_iter().enumerate() {
+        s.submit(i, price);
+    }
+    assert_eq!(s.oracle.aggregate(&PAIR), 102);
+
+    // Prices near i128::MAX do not overflow the average.
+    let s = setup(4);
+    let big = i128::MAX - 1;
+    for (i, price) in [big, big - 2, 1, i128::MAX].into_iter().enumerate() {
+        s.submit(i, price);
+    }
+    assert_eq!(s.oracle.aggregate(&PAIR), big - 1);
+}
+
+#[test]
+fn test_quorum_not_met() {
+    let s = setup(5);
+    s.submit(0, 100);
+    s.submit(1, 101);
+    assert_eq!(
+        s.oracle.try_aggregate(&PAIR),
+        Err(Ok(OracleError::InsufficientReports))
+    );
+    // The failed call leaves the round open; one more report settles it.
+    assert_eq!(s.oracle.round(&PAIR), 0);
+    assert_eq!(s.oracle.report_count(&PAIR), 2);
+    s.submit(2, 102);
+    assert_eq!(s.oracle.aggregate(&PAIR), 101);
+    assert_eq!(s.oracle.latest(&symbol_short!("BTC_USD")), None);
+}
+
+#[test]
+fn test_second_submission_in_round_rejected() {
+    let s = setup(3);
+    s.submit(0, 100);
+    assert_eq!(
+        s.oracle.try_submit(&s.reporters[0], &PAIR, &150),
+        Err(Ok(OracleError::AlreadySubmitted))
+    );
+    // Other pairs have their own rounds.
+    s.oracle
+        .submit(&s.reporters[0], &symbol_short!("BTC_USD"), &60_000);
+
+    let outsider = Address::generate(&s.env);
+    assert_eq!(
+        s.oracle.try_submit(&outsider, &PAIR, &100),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert_eq!(
+        s.oracle.try_submit(&s.reporters[1], &PAIR, &0),
+        Err(Ok(OracleError::InvalidInput))
+    );
+    assert_eq!(s.oracle.report_count(&PAIR), 1);
+}
+
+#[test]
+fn test_outlier_does_not_skew_median() {
+    let s = setup(5);
+    // One reporter is wildly off; the mean would be about 200 020.
+    for (i, price) in [100, 101, 1_000_000, 99, 100].into_iter().enumerate() {
+        s.submit(i, price);
+    }
+    assert_eq!(s.oracle.aggregate(&PAIR), 100);
+
+    // Two colluding outliers out of five still cannot move it far.
+    for (i, price) in [1, 1, 100, 101, 102].into_iter().enumerate() {
+        s.submit(i, price);
+    }
+    assert_eq!(s.oracle.aggregate(&PAIR), 100);
+}
+
+#[test]
+fn test_round_rollover_clears_submissions() {
+    let s = setup(3);
+    for i in 0..3 {
+        assert_eq!(s.oracle.submit(&s.reporters[i], &PAIR, &100), 0);
+    }
+    s.oracle.aggregate(&PAIR);
+    assert_eq!(s.oracle.round(&PAIR), 1);
+    assert_eq!(s.oracle.report_count(&PAIR), 0);
+    assert_eq!(
+        s.oracle.try_aggregate(&PAIR),
+        Err(Ok(OracleError::InsufficientReports))
+    );
+
+    // Every reporter may submit again in the new round.
+    for i in 0..3 {
+        assert_eq!(s.oracle.submit(&s.reporters[i], &PAIR, &200), 1);
+    }
+    assert_eq!(s.oracle.aggregate(&PAIR), 200);
+    assert_eq!(s.oracle.latest(&PAIR).unwrap().round, 1);
+}
+
+#[test]
+fn test_reporter_registry() {
+    let s = setup(MAX_REPORTERS as usize);
+    assert_eq!(
+        s.oracle.try_add_reporter(&Address::generate(&s.env)),
+        Err(Ok(OracleError::TooManyReporters))
+    );
+    assert_eq!(
+        s.oracle.try_add_reporter(&s.reporters[0]),
+        Err(Ok(OracleError::AlreadyReporter))
+    );
+
+    s.oracle.remove_reporter(&s.reporters[0]);
+    assert_eq!(
+        s.oracle.try_submit(&s.reporters[0], &PAIR, &100),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert_eq!(
+        s.oracle.try_remove_reporter(&s.reporters[0]),
+        Err(Ok(OracleError::NotFound))
+    );
+    s.oracle.add_reporter(&Address::generate(&s.env));
+    assert_eq!(s.oracle.reporters().len(), MAX_REPORTERS);
+
+    assert_eq!(
+        s.oracle.try_set_quorum(&0),
+        Err(Ok(OracleError::InvalidInput))
+    );
+    assert_eq!(
+        s.oracle.try_set_quorum(&(MAX_REPORTERS + 1)),
+        Err(Ok(OracleError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (OracleError::NotInitialized, CommonError::NotInitialized),
+        (
+            OracleError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (OracleError::Unauthorized, CommonError::Unauthorized),
+        (OracleError::NotFound, CommonError::NotFound),
+        (OracleError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(OracleError::TooManyReporters as u32 >= recipes_errors::CONTRACT_MIN);
+}
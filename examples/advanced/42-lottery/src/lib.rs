@@ -0,0 +1,305 @@
+//! # Lottery
+//!
+//! Players buy tickets with a token while sales are open. The operator
+//! then closes sales, reveals a secret seed after a delay, and the contract
+//! draws a winning ticket. The winner claims the pot minus the operator's
+//! fee.
+//!
+//! ```text
+//! Open --close--> Closed --draw--> Drawn --claim--> Claimed
+//!                       \--draw, no tickets--------> Claimed
+//! ```
+//!
+//! The draw combines the commit-reveal (`11-commit-reveal`) and PRNG
+//! (`20-prng-randomness`) recipes the way `fair_roll` does there:
+//!
+//! 1. `close` stores the operator's `sha256(seed)` and draws 32 bytes of
+//!    the contract's own PRNG entropy, while the seed is still secret.
+//! 2. `draw`, at least `reveal_delay` seconds later, checks the seed
+//!    against the commitment, reseeds the PRNG with
+//!    `sha256(seed ‖ entropy)` and picks a ticket index.
+//!
+//! Neither side can steer the result: the operator fixed the seed before
+//! seeing the entropy, and the entropy was drawn before anyone but the
+//! operator knew the seed. The operator can still refuse to reveal, which
+//! leaves the pot locked; a production lottery adds a refund path for a
+//! draw that does not arrive in time.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Bytes,
+    BytesN, Env, Symbol, Vec,
+};
+
+const NAMESPACE: Symbol = symbol_short!("lottery");
+const BPS: i128 = 10_000;
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LotteryError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// The pot no longer fits in an `i128`. `CommonError::Overflow`.
+    Overflow = 5,
+    /// A non-positive ticket price or a fee above 100%.
+    /// `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// `buy_ticket` or `close` after sales closed.
+    NotOpen = 100,
+    /// `draw` while sales are open, or after the draw.
+    NotClosed = 101,
+    /// `claim` before the draw, or after the pot was claimed.
+    NotDrawn = 102,
+    /// `draw` before `reveal_delay` has passed since `close`.
+    TooEarly = 103,
+    /// `sha256(seed)` does not match the commitment made at `close`.
+    SeedMismatch = 104,
+    /// `claim` by someone other than the winner.
+    NotWinner = 105,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LotteryState {
+    Open,
+    Closed,
+    Drawn,
+    Claimed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub operator: Address,
+    pub token: Address,
+    pub ticket_price: i128,
+    /// Operator's share of the pot, in basis points.
+    pub fee_bps: u32,
+    /// Seconds between `close` and the earliest `draw`.
+    pub reveal_delay: u64,
+}
+
+/// What `close` locks in for the draw.
+#[contracttype]
+#[derive(Clone)]
+pub struct Commitment {
+    pub hash: BytesN<32>,
+    pub entropy: BytesN<32>,
+    pub closed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    State,
+    /// One entry per ticket sold, in order; a player may appear many times.
+    Tickets,
+    Commitment,
+    Winner,
+}
+
+#[contract]
+pub struct LotteryContract;
+
+#[contractimpl]
+impl LotteryContract {
+    /// Open ticket sales.
+    pub fn initialize(env: Env, config: Config) -> Result<(), LotteryError> {
+        config.operator.require_auth();
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(LotteryError::AlreadyInitialized);
+        }
+        if config.ticket_price <= 0 || i128::from(config.fee_bps) > BPS {
+            return Err(LotteryError::InvalidInput);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        Self::set_state(&env, LotteryState::Open);
+        Ok(())
+    }
+
+    /// Buy one ticket for `ticket_price`. Returns the ticket's index.
+    pub fn buy_ticket(env: Env, player: Address) -> Result<u32, LotteryError> {
+        player.require_auth();
+        let config = Self::config(env.clone())?;
+        Self::require_state(&env, LotteryState::Open, LotteryError::NotOpen)?;
+        let mut tickets = Self::tickets(env.clone());
+        Self::pot_of(&config, tickets.len() + 1)?;
+
+        token::Client::new(&env, &config.token).transfer(
+            &player,
+            &env.current_contract_address(),
+            &config.ticket_price,
+        );
+        let index = tickets.len();
+        tickets.push_back(player.clone());
+        env.storage().persistent().set(&DataKey::Tickets, &tickets);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("ticket"), player), index);
+        Ok(index)
+    }
+
+    /// Close sales and commit to `sha256(seed)` for a secret 32-byte seed.
+    /// Operator only.
+    pub fn close(env: Env, seed_hash: BytesN<32>) -> Result<(), LotteryError> {
+        let config = Self::config(env.clone())?;
+        config.operator.require_auth();
+        Self::require_state(&env, LotteryState::Open, LotteryError::NotOpen)?;
+        let commitment = Commitment {
+            hash: seed_hash,
+            entropy: env.prng().gen(),
+            closed_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::Commitment, &commitment);
+        Self::set_state(&env, LotteryState::Closed);
+        env.events().publish(
+            (NAMESPACE, symbol_short!("closed")),
+            Self::tickets(env.clone()).len(),
+        );
+        Ok(())
+    }
+
+    /// Reveal the seed and draw the winning ticket. Operator only. With no
+    /// tickets sold there is nothing to draw: the lottery ends with no
+    /// winner and no fee.
+    pub fn draw(env: Env, seed: BytesN<32>) -> Result<Option<Address>, LotteryError> {
+        let config = Self::config(env.clone())?;
+        config.operator.require_auth();
+        Self::require_state(&env, LotteryState::Closed, LotteryError::NotClosed)?;
+        let commitment: Commitment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Commitment)
+            .ok_or(LotteryError::NotClosed)?;
+        let earliest = commitment
+            .closed_at
+            .checked_add(config.reveal_delay)
+            .ok_or(LotteryError::Overflow)?;
+        if env.ledger().timestamp() < earliest {
+            return Err(LotteryError::TooEarly);
+        }
+        let seed = Bytes::from(seed);
+        if env.crypto().sha256(&seed).to_bytes() != commitment.hash {
+            return Err(LotteryError::SeedMismatch);
+        }
+
+        let tickets = Self::tickets(env.clone());
+        if tickets.is_empty() {
+            Self::set_state(&env, LotteryState::Claimed);
+            env.events()
+                .publish((NAMESPACE, symbol_short!("no_draw")), ());
+            return Ok(None);
+        }
+
+        let mut mixed = seed;
+        mixed.append(&commitment.entropy.into());
+        env.prng()
+            .seed(env.crypto().sha256(&mixed).to_bytes().into());
+        let index = env.prng().gen_range::<u64>(0..u64::from(tickets.len())) as u32;
+        let winner = tickets.get_unchecked(index);
+
+        env.storage().instance().set(&DataKey::Winner, &winner);
+        Self::set_state(&env, LotteryState::Drawn);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("drawn"), winner.clone()), index);
+        Ok(Some(winner))
+    }
+
+    /// Pay the pot minus the fee to the winner, and the fee to the
+    /// operator. Returns the winner's payout.
+    pub fn claim(env: Env, winner: Address) -> Result<i128, LotteryError> {
+        winner.require_auth();
+        let config = Self::config(env.clone())?;
+        Self::require_state(&env, LotteryState::Drawn, LotteryError::NotDrawn)?;
+        if Self::winner(env.clone()) != Some(winner.clone()) {
+            return Err(LotteryError::NotWinner);
+        }
+
+        let pot = Self::pot_of(&config, Self::tickets(env.clone()).len())?;
+        let fee = pot * i128::from(config.fee_bps) / BPS;
+        let payout = pot - fee;
+        let token = token::Client::new(&env, &config.token);
+        let contract = env.current_contract_address();
+        token.transfer(&contract, &winner, &payout);
+        if fee > 0 {
+            token.transfer(&contract, &config.operator, &fee);
+        }
+        Self::set_state(&env, LotteryState::Claimed);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("claimed"), winner), (payout, fee));
+        Ok(payout)
+    }
+
+    pub fn state(env: Env) -> Result<LotteryState, LotteryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::State)
+            .ok_or(LotteryError::NotInitialized)
+    }
+
+    pub fn config(env: Env) -> Result<Config, LotteryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(LotteryError::NotInitialized)
+    }
+
+    /// Ticket holders by ticket index.
+    pub fn tickets(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tickets)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Total paid for tickets so far.
+    pub fn pot(env: Env) -> Result<i128, LotteryError> {
+        let config = Self::config(env.clone())?;
+        Self::pot_of(&config, Self::tickets(env).len())
+    }
+
+    /// The drawn winner, once `draw` has run.
+    pub fn winner(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Winner)
+    }
+
+    /// The commitment hash for `seed`, for operators that cannot hash
+    /// locally.
+    pub fn seed_hash(env: Env, seed: BytesN<32>) -> BytesN<32> {
+        env.crypto().sha256(&seed.into()).to_bytes()
+    }
+}
+
+impl LotteryContract {
+    fn require_state(
+        env: &Env,
+        expected: LotteryState,
+        error: LotteryError,
+    ) -> Result<(), LotteryError> {
+        if Self::state(env.clone())? != expected {
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    fn set_state(env: &Env, state: LotteryState) {
+        env.storage().instance().set(&DataKey::State, &state);
+    }
+
+    fn pot_of(config: &Config, tickets: u32) -> Result<i128, LotteryError> {
+        config
+            .ticket_price
+            .checked_mul(i128::from(tickets))
+            .ok_or(LotteryError::Overflow)
+    }
+}
+
+mod test;
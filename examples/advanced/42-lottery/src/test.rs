@@ -0,0 +1,264 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::{advance_time, all_events_for, pin_prng_seed, set_timestamp};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Env, IntoVal,
+};
+
+const PRICE: i128 = 100;
+/// 5%.
+const FEE_BPS: u32 = 500;
+const DELAY: u64 = 3_600;
+
+struct Setup {
+    env: Env,
+    operator: Address,
+    players: [Address; 3],
+    token: TokenClient<'static>,
+    lottery: LotteryContractClient<'static>,
+}
+
+/// An open lottery with three funded players who have not bought yet.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, 10_000);
+    pin_prng_seed(&env, [7; 32]);
+    let operator = Address::generate(&env);
+    let players = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    for player in &players {
+        StellarAssetClient::new(&env, &asset.address()).mint(player, &1_000);
+    }
+
+    let lottery = LotteryContractClient::new(&env, &env.register_contract(None, LotteryContract));
+    lottery.initialize(&Config {
+        operator: operator.clone(),
+        token: asset.address(),
+        ticket_price: PRICE,
+        fee_bps: FEE_BPS,
+        reveal_delay: DELAY,
+    });
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        operator,
+        players,
+        lottery,
+    }
+}
+
+fn seed(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+impl Setup {
+    /// Each player buys one ticket; the operator closes with `seed(9)`.
+    fn sell_and_close(&self) {
+        for player in &self.players {
+            self.lottery.buy_ticket(player);
+        }
+        self.lottery
+            .close(&self.lottery.seed_hash(&seed(&self.env, 9)));
+    }
+}
+
+/// Run a full round from a fresh setup and return the winner's index.
+fn full_round() -> usize {
+    let s = setup();
+    s.sell_and_close();
+    advance_time(&s.env, DELAY);
+    let winner = s.lottery.draw(&seed(&s.env, 9)).unwrap();
+    s.players.iter().position(|p| *p == winner).unwrap()
+}
+
+#[test]
+fn test_full_round_with_three_players() {
+    let s = setup();
+    for (i, player) in s.players.iter().enumerate() {
+        assert_eq!(s.lottery.buy_ticket(player), i as u32);
+    }
+    assert_eq!(s.lottery.pot(), 3 * PRICE);
+    assert_eq!(s.token.balance(&s.lottery.address), 3 * PRICE);
+
+    s.lottery.close(&s.lottery.seed_hash(&seed(&s.env, 9)));
+    assert_eq!(s.lottery.state(), LotteryState::Closed);
+    advance_time(&s.env, DELAY);
+    let winner = s.lottery.draw(&seed(&s.env, 9)).unwrap();
+    assert_eq!(s.lottery.state(), LotteryState::Drawn);
+    assert_eq!(s.lottery.winner(), Some(winner.clone()));
+
+    // The pinned network seed and the revealed seed fix the result.
+    assert_eq!(winner, s.players[2]);
+    assert_eq!(full_round(), 2);
+
+    // 5% of 300 is 15.
+    assert_eq!(s.lottery.claim(&winner), 285);
+    assert_eq!(s.token.balance(&winner), 1_000 - PRICE + 285);
+    assert_eq!(s.token.balance(&s.operator), 15);
+    assert_eq!(s.token.balance(&s.lottery.address), 0);
+    assert_eq!(s.lottery.state(), LotteryState::Claimed);
+
+    let (topics, data) = all_events_for(&s.env, &s.lottery.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("claimed"), winner.clone()).into_val(&s.env)
+    );
+    let paid: (i128, i128) = data.into_val(&s.env);
+    assert_eq!(paid, (285, 15));
+    assert_eq!(
+        s.lottery.try_claim(&winner),
+        Err(Ok(LotteryError::NotDrawn))
+    );
+}
+
+#[test]
+fn test_reveal_must_match_commitment() {
+    let s = setup();
+    s.sell_and_close();
+    advance_time(&s.env, DELAY);
+    assert_eq!(
+        s.lottery.try_draw(&seed(&s.env, 8)),
+        Err(Ok(LotteryError::SeedMismatch))
+    );
+    assert_eq!(s.lottery.state(), LotteryState::Closed);
+    assert!(s.lottery.draw(&seed(&s.env, 9)).is_some());
+}
+
+#[test]
+fn test_draw_before_delay_rejected() {
+    let s = setup();
+    s.sell_and_close();
+    advance_time(&s.env, DELAY - 1);
+    assert_eq!(
+        s.lottery.try_draw(&seed(&s.env, 9)),
+        Err(Ok(LotteryError::TooEarly))
+    );
+    advance_time(&s.env, 1);
+    assert!(s.lottery.draw(&seed(&s.env, 9)).is_some());
+}
+
+#[test]
+fn test_claim_by_non_winner_rejected() {
+    let s = setup();
+    s.sell_and_close();
+    advance_time(&s.env, DELAY);
+    let winner = s.lottery.draw(&seed(&s.env, 9)).unwrap();
+    for player in s.players.iter().filter(|p| **p != winner) {
+        assert_eq!(
+            s.lottery.try_claim(player),
+            Err(Ok(LotteryError::NotWinner))
+        );
+    }
+    assert_eq!(
+        s.lottery.try_claim(&s.operator),
+        Err(Ok(LotteryError::NotWinner))
+    );
+    assert_eq!(s.token.balance(&s.lottery.address), 3 * PRICE);
+}
+
+#[test]
+fn test_zero_players_ends_without_fee() {
+    let s = setup();
+    s.lottery.close(&s.lottery.seed_hash(&seed(&s.env, 9)));
+    advance_time(&s.env, DELAY);
+    // The seed is still checked, so the operator cannot skip the reveal.
+    assert_eq!(
+        s.lottery.try_draw(&seed(&s.env, 8)),
+        Err(Ok(LotteryError::SeedMismatch))
+    );
+    assert_eq!(s.lottery.draw(&seed(&s.env, 9)), None);
+    assert_eq!(s.lottery.state(), LotteryState::Claimed);
+    assert_eq!(s.lottery.pot(), 0);
+    assert_eq!(s.token.balance(&s.operator), 0);
+    assert_eq!(
+        s.lottery.try_claim(&s.operator),
+        Err(Ok(LotteryError::NotDrawn))
+    );
+}
+
+#[test]
+fn test_state_transitions_are_enforced() {
+    let s = setup();
+    assert_eq!(
+        s.lottery.try_draw(&seed(&s.env, 9)),
+        Err(Ok(LotteryError::NotClosed))
+    );
+    assert_eq!(
+        s.lottery.try_claim(&s.players[0]),
+        Err(Ok(LotteryError::NotDrawn))
+    );
+    s.sell_and_close();
+    assert_eq!(
+        s.lottery.try_buy_ticket(&s.players[0]),
+        Err(Ok(LotteryError::NotOpen))
+    );
+    assert_eq!(
+        s.lottery.try_close(&seed(&s.env, 1)),
+        Err(Ok(LotteryError::NotOpen))
+    );
+    advance_time(&s.env, DELAY);
+    s.lottery.draw(&seed(&s.env, 9));
+    assert_eq!(
+        s.lottery.try_draw(&seed(&s.env, 9)),
+        Err(Ok(LotteryError::NotClosed))
+    );
+}
+
+#[test]
+fn test_initialization_checks() {
+    let s = setup();
+    let config = s.lottery.config();
+    assert_eq!(
+        s.lottery.try_initialize(&config),
+        Err(Ok(LotteryError::AlreadyInitialized))
+    );
+
+    let fresh = LotteryContractClient::new(&s.env, &s.env.register_contract(None, LotteryContract));
+    assert_eq!(
+        fresh.try_buy_ticket(&s.players[0]),
+        Err(Ok(LotteryError::NotInitialized))
+    );
+    for bad in [
+        Config {
+            ticket_price: 0,
+            ..config.clone()
+        },
+        Config {
+            fee_bps: 10_001,
+            ..config.clone()
+        },
+    ] {
+        assert_eq!(
+            fresh.try_initialize(&bad),
+            Err(Ok(LotteryError::InvalidInput))
+        );
+    }
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (LotteryError::NotInitialized, CommonError::NotInitialized),
+        (
+            LotteryError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (LotteryError::Overflow, CommonError::Overflow),
+        (LotteryError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(LotteryError::NotOpen as u32 >= recipes_errors::CONTRACT_MIN);
+}
@@ -0,0 +1,125 @@
+//! # Native Asset
+//!
+//! Holding and moving XLM, or any classic Stellar asset, from a contract.
+//! Classic assets are reached through their Stellar Asset Contract (SAC),
+//! which implements the standard token interface, so the contract talks to
+//! it with an ordinary `token::Client`. Nothing here is specific to XLM:
+//! pass the SAC address of another asset to `initialize` and the same code
+//! moves that asset instead.
+//!
+//! The contract's own balance lives in the SAC, keyed by the contract's
+//! address. The contract keeps no ledger of its own; `contract_balance`
+//! asks the SAC.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+const NAMESPACE: Symbol = symbol_short!("native");
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum NativeError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// `pay_out` by someone other than the admin. `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// A non-positive amount. `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// `pay_out` of more than the contract holds.
+    InsufficientBalance = 100,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Address of the asset's Stellar Asset Contract.
+    Asset,
+}
+
+#[contract]
+pub struct NativeAssetContract;
+
+#[contractimpl]
+impl NativeAssetContract {
+    /// Store the admin and the SAC address of the asset to hold. On a real
+    /// network, `stellar contract id asset --asset native` prints the XLM
+    /// SAC address.
+    pub fn initialize(env: Env, admin: Address, asset: Address) -> Result<(), NativeError> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(NativeError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Asset, &asset);
+        Ok(())
+    }
+
+    /// Move `amount` from `from` into the contract. `from` authorizes both
+    /// this call and the SAC transfer it makes.
+    pub fn deposit_native(env: Env, from: Address, amount: i128) -> Result<(), NativeError> {
+        from.require_auth();
+        let asset = Self::client(&env)?;
+        if amount <= 0 {
+            return Err(NativeError::InvalidInput);
+        }
+        asset.transfer(&from, &env.current_contract_address(), &amount);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("deposit"), from), amount);
+        Ok(())
+    }
+
+    /// Send `amount` from the contract to `to`. Admin only. The contract
+    /// authorizes its own SAC transfer simply by being the caller.
+    pub fn pay_out(env: Env, admin: Address, to: Address, amount: i128) -> Result<(), NativeError> {
+        admin.require_auth();
+        if admin != Self::admin(env.clone())? {
+            return Err(NativeError::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(NativeError::InvalidInput);
+        }
+        if amount > Self::contract_balance(env.clone())? {
+            return Err(NativeError::InsufficientBalance);
+        }
+        Self::client(&env)?.transfer(&env.current_contract_address(), &to, &amount);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("pay_out"), to), amount);
+        Ok(())
+    }
+
+    /// What the contract holds, as reported by the SAC.
+    pub fn contract_balance(env: Env) -> Result<i128, NativeError> {
+        Ok(Self::client(&env)?.balance(&env.current_contract_address()))
+    }
+
+    pub fn admin(env: Env) -> Result<Address, NativeError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(NativeError::NotInitialized)
+    }
+
+    pub fn asset(env: Env) -> Result<Address, NativeError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .ok_or(NativeError::NotInitialized)
+    }
+}
+
+impl NativeAssetContract {
+    fn client(env: &Env) -> Result<token::Client<'_>, NativeError> {
+        Ok(token::Client::new(env, &Self::asset(env.clone())?))
+    }
+}
+
+mod test;
@@ -0,0 +1,186 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    token::{StellarAssetClient, TokenClient},
+    IntoVal,
+};
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+    asset: TokenClient<'static>,
+    contract: NativeAssetContractClient<'static>,
+}
+
+/// A SAC standing in for XLM, with 1 000 minted to each of alice and bob.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let asset_admin = StellarAssetClient::new(&env, &sac.address());
+    asset_admin.mint(&alice, &1_000);
+    asset_admin.mint(&bob, &1_000);
+
+    let contract =
+        NativeAssetContractClient::new(&env, &env.register_contract(None, NativeAssetContract));
+    contract.initialize(&admin, &sac.address());
+    Setup {
+        asset: TokenClient::new(&env, &sac.address()),
+        env,
+        admin,
+        alice,
+        bob,
+        contract,
+    }
+}
+
+#[test]
+fn test_deposits_and_payouts_move_the_balance() {
+    let s = setup();
+    assert_eq!(s.contract.contract_balance(), 0);
+
+    s.contract.deposit_native(&s.alice, &400);
+    s.contract.deposit_native(&s.bob, &250);
+    assert_eq!(s.contract.contract_balance(), 650);
+    assert_eq!(s.asset.balance(&s.contract.address), 650);
+    assert_eq!(s.asset.balance(&s.alice), 600);
+    assert_eq!(s.asset.balance(&s.bob), 750);
+
+    let carol = Address::generate(&s.env);
+    s.contract.pay_out(&s.admin, &carol, &500);
+    assert_eq!(s.contract.contract_balance(), 150);
+    assert_eq!(s.asset.balance(&carol), 500);
+
+    let (topics, data) = all_events_for(&s.env, &s.contract.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("pay_out"), carol.clone()).into_val(&s.env)
+    );
+    let amount: i128 = data.into_val(&s.env);
+    assert_eq!(amount, 500);
+}
+
+#[test]
+fn test_deposit_requires_the_depositors_auth() {
+    let s = setup();
+    s.contract.deposit_native(&s.alice, &100);
+
+    // Alice signs the deposit, and the SAC transfer it makes on alice's behalf
+    // is covered by the same signature.
+    assert_eq!(
+        s.env.auths(),
+        std::vec![(
+            s.alice.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    s.contract.address.clone(),
+                    Symbol::new(&s.env, "deposit_native"),
+                    (s.alice.clone(), 100_i128).into_val(&s.env),
+                )),
+                sub_invocations: std::vec![AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        s.asset.address.clone(),
+                        Symbol::new(&s.env, "transfer"),
+                        (s.alice.clone(), s.contract.address.clone(), 100_i128).into_val(&s.env),
+                    )),
+                    sub_invocations: std::vec![],
+                }],
+            }
+        )]
+    );
+
+    // Without it, nothing moves.
+    s.env.set_auths(&[]);
+    assert!(s.contract.try_deposit_native(&s.alice, &100).is_err());
+    assert_eq!(s.contract.contract_balance(), 100);
+    assert_eq!(s.asset.balance(&s.alice), 900);
+}
+
+#[test]
+fn test_payout_beyond_balance_rejected() {
+    let s = setup();
+    s.contract.deposit_native(&s.alice, &300);
+    assert_eq!(
+        s.contract.try_pay_out(&s.admin, &s.bob, &301),
+        Err(Ok(NativeError::InsufficientBalance))
+    );
+    assert_eq!(s.contract.contract_balance(), 300);
+    assert_eq!(s.asset.balance(&s.bob), 1_000);
+
+    // Exactly the balance is fine, and leaves the contract empty.
+    s.contract.pay_out(&s.admin, &s.bob, &300);
+    assert_eq!(s.contract.contract_balance(), 0);
+    assert_eq!(
+        s.contract.try_pay_out(&s.admin, &s.bob, &1),
+        Err(Ok(NativeError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_only_admin_pays_out() {
+    let s = setup();
+    s.contract.deposit_native(&s.alice, &300);
+    assert_eq!(
+        s.contract.try_pay_out(&s.alice, &s.alice, &300),
+        Err(Ok(NativeError::Unauthorized))
+    );
+    assert_eq!(s.contract.contract_balance(), 300);
+}
+
+#[test]
+fn test_initialization_and_input_checks() {
+    let s = setup();
+    assert_eq!(s.contract.admin(), s.admin);
+    assert_eq!(s.contract.asset(), s.asset.address);
+    assert_eq!(
+        s.contract.try_initialize(&s.alice, &s.asset.address),
+        Err(Ok(NativeError::AlreadyInitialized))
+    );
+    assert_eq!(
+        s.contract.try_deposit_native(&s.alice, &0),
+        Err(Ok(NativeError::InvalidInput))
+    );
+    assert_eq!(
+        s.contract.try_pay_out(&s.admin, &s.bob, &-1),
+        Err(Ok(NativeError::InvalidInput))
+    );
+
+    let fresh =
+        NativeAssetContractClient::new(&s.env, &s.env.register_contract(None, NativeAssetContract));
+    assert_eq!(
+        fresh.try_deposit_native(&s.alice, &10),
+        Err(Ok(NativeError::NotInitialized))
+    );
+    assert_eq!(
+        fresh.try_contract_balance(),
+        Err(Ok(NativeError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (NativeError::NotInitialized, CommonError::NotInitialized),
+        (
+            NativeError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (NativeError::Unauthorized, CommonError::Unauthorized),
+        (NativeError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(NativeError::InsufficientBalance as u32 >= recipes_errors::CONTRACT_MIN);
+}
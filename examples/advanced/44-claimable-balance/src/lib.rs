@@ -0,0 +1,202 @@
+//! # Claimable Balances
+//!
+//! A Soroban take on Stellar's claimable balances. A sponsor locks tokens
+//! for a list of claimants, each with its own time window. The first
+//! claimant to call `claim` inside their window takes the whole amount;
+//! once every window has closed unclaimed, the sponsor can take it back.
+//!
+//! ```text
+//! claimant A          [not_before ........ not_after]
+//! claimant B     [not_before ... not_after]
+//! sponsor                                            reclaim ->
+//! time ------------------------------------------------------->
+//! ```
+//!
+//! Windows are inclusive at both ends. A window that has not opened yet
+//! still counts as open for `reclaim`: the sponsor cannot pull funds a
+//! claimant is still due a chance at.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+    Vec,
+};
+
+const NAMESPACE: Symbol = symbol_short!("claimable");
+
+/// Most claimants one balance may list, as on Stellar.
+pub const MAX_CLAIMANTS: u32 = 10;
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ClaimError {
+    /// `reclaim` by someone other than the sponsor.
+    /// `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// No such balance, or it was already claimed or reclaimed.
+    /// `CommonError::NotFound`.
+    NotFound = 6,
+    /// The claimant's window has closed. `CommonError::Expired`.
+    Expired = 7,
+    /// A non-positive amount, no claimants or more than `MAX_CLAIMANTS`, or
+    /// a window that ends before it starts. `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// The caller is not listed on the balance.
+    NotClaimant = 100,
+    /// The claimant's window has not opened yet.
+    TooEarly = 101,
+    /// `reclaim` while some claimant's window has not closed.
+    WindowOpen = 102,
+}
+
+/// Who may claim, and when: from `not_before` to `not_after`, inclusive,
+/// in ledger seconds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claimant {
+    pub addr: Address,
+    pub not_before: u64,
+    pub not_after: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimableBalance {
+    pub sponsor: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub claimants: Vec<Claimant>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextId,
+    /// An unclaimed balance. Removed when it is claimed or reclaimed.
+    Balance(u64),
+}
+
+#[contract]
+pub struct ClaimableBalanceContract;
+
+#[contractimpl]
+impl ClaimableBalanceContract {
+    /// Lock `amount` of `token` from `sponsor` for `claimants`. Returns the
+    /// balance id.
+    pub fn create(
+        env: Env,
+        sponsor: Address,
+        token: Address,
+        amount: i128,
+        claimants: Vec<Claimant>,
+    ) -> Result<u64, ClaimError> {
+        sponsor.require_auth();
+        if amount <= 0 || claimants.is_empty() || claimants.len() > MAX_CLAIMANTS {
+            return Err(ClaimError::InvalidInput);
+        }
+        if claimants.iter().any(|c| c.not_before > c.not_after) {
+            return Err(ClaimError::InvalidInput);
+        }
+
+        token::Client::new(&env, &token).transfer(
+            &sponsor,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let id: u64 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        let balance = ClaimableBalance {
+            sponsor: sponsor.clone(),
+            token,
+            amount,
+            claimants,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(id), &balance);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("created"), sponsor), (id, amount));
+        Ok(id)
+    }
+
+    /// Pay the whole balance to `claimant`, if the ledger time is inside
+    /// their window. The balance is gone afterwards, so only the first
+    /// successful claim pays.
+    pub fn claim(env: Env, claimant: Address, balance_id: u64) -> Result<i128, ClaimError> {
+        claimant.require_auth();
+        let balance = Self::load(&env, balance_id)?;
+        let window = balance
+            .claimants
+            .iter()
+            .find(|c| c.addr == claimant)
+            .ok_or(ClaimError::NotClaimant)?;
+        let now = env.ledger().timestamp();
+        if now < window.not_before {
+            return Err(ClaimError::TooEarly);
+        }
+        if now > window.not_after {
+            return Err(ClaimError::Expired);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Balance(balance_id));
+        token::Client::new(&env, &balance.token).transfer(
+            &env.current_contract_address(),
+            &claimant,
+            &balance.amount,
+        );
+        env.events().publish(
+            (NAMESPACE, symbol_short!("claimed"), claimant),
+            (balance_id, balance.amount),
+        );
+        Ok(balance.amount)
+    }
+
+    /// Return an unclaimed balance to its sponsor once every claimant's
+    /// window has closed.
+    pub fn reclaim(env: Env, sponsor: Address, balance_id: u64) -> Result<i128, ClaimError> {
+        sponsor.require_auth();
+        let balance = Self::load(&env, balance_id)?;
+        if balance.sponsor != sponsor {
+            return Err(ClaimError::Unauthorized);
+        }
+        let now = env.ledger().timestamp();
+        if balance.claimants.iter().any(|c| now <= c.not_after) {
+            return Err(ClaimError::WindowOpen);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Balance(balance_id));
+        token::Client::new(&env, &balance.token).transfer(
+            &env.current_contract_address(),
+            &sponsor,
+            &balance.amount,
+        );
+        env.events().publish(
+            (NAMESPACE, symbol_short!("reclaimed"), sponsor),
+            (balance_id, balance.amount),
+        );
+        Ok(balance.amount)
+    }
+
+    /// The balance, while it is unclaimed.
+    pub fn get_balance(env: Env, balance_id: u64) -> Option<ClaimableBalance> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(balance_id))
+    }
+}
+
+impl ClaimableBalanceContract {
+    fn load(env: &Env, balance_id: u64) -> Result<ClaimableBalance, ClaimError> {
+        Self::get_balance(env.clone(), balance_id).ok_or(ClaimError::NotFound)
+    }
+}
+
+mod test;
@@ -0,0 +1,249 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::{all_events_for, set_timestamp};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, IntoVal,
+};
+
+const AMOUNT: i128 = 500;
+
+struct Setup {
+    env: Env,
+    sponsor: Address,
+    alice: Address,
+    bob: Address,
+    token: TokenClient<'static>,
+    contract: ClaimableBalanceContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let sponsor = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &asset.address()).mint(&sponsor, &10_000);
+    let contract = ClaimableBalanceContractClient::new(
+        &env,
+        &env.register_contract(None, ClaimableBalanceContract),
+    );
+    Setup {
+        alice: Address::generate(&env),
+        bob: Address::generate(&env),
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        sponsor,
+        contract,
+    }
+}
+
+fn window(addr: &Address, not_before: u64, not_after: u64) -> Claimant {
+    Claimant {
+        addr: addr.clone(),
+        not_before,
+        not_after,
+    }
+}
+
+impl Setup {
+    fn create(&self, claimants: Vec<Claimant>) -> u64 {
+        self.contract
+            .create(&self.sponsor, &self.token.address, &AMOUNT, &claimants)
+    }
+}
+
+#[test]
+fn test_claim_before_inside_and_after_window() {
+    let s = setup();
+    let id = s.create(vec![&s.env, window(&s.alice, 100, 200)]);
+    assert_eq!(s.token.balance(&s.contract.address), AMOUNT);
+
+    set_timestamp(&s.env, 99);
+    assert_eq!(
+        s.contract.try_claim(&s.alice, &id),
+        Err(Ok(ClaimError::TooEarly))
+    );
+    set_timestamp(&s.env, 201);
+    assert_eq!(
+        s.contract.try_claim(&s.alice, &id),
+        Err(Ok(ClaimError::Expired))
+    );
+
+    // Both ends of the window are inclusive.
+    set_timestamp(&s.env, 200);
+    assert_eq!(s.contract.claim(&s.alice, &id), AMOUNT);
+    assert_eq!(s.token.balance(&s.alice), AMOUNT);
+    assert_eq!(s.token.balance(&s.contract.address), 0);
+    assert_eq!(s.contract.get_balance(&id), None);
+
+    let (topics, data) = all_events_for(&s.env, &s.contract.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("claimed"), s.alice.clone()).into_val(&s.env)
+    );
+    let claimed: (u64, i128) = data.into_val(&s.env);
+    assert_eq!(claimed, (id, AMOUNT));
+
+    // Paid exactly once.
+    assert_eq!(
+        s.contract.try_claim(&s.alice, &id),
+        Err(Ok(ClaimError::NotFound))
+    );
+}
+
+#[test]
+fn test_first_claim_wins() {
+    let s = setup();
+    let id = s.create(vec![
+        &s.env,
+        window(&s.alice, 0, 1_000),
+        window(&s.bob, 0, 1_000),
+    ]);
+    set_timestamp(&s.env, 500);
+    s.contract.claim(&s.bob, &id);
+    assert_eq!(
+        s.contract.try_claim(&s.alice, &id),
+        Err(Ok(ClaimError::NotFound))
+    );
+    assert_eq!(s.token.balance(&s.bob), AMOUNT);
+    assert_eq!(s.token.balance(&s.alice), 0);
+}
+
+#[test]
+fn test_windows_are_per_claimant() {
+    let s = setup();
+    let claimants = vec![&s.env, window(&s.alice, 100, 200), window(&s.bob, 300, 400)];
+    let early = s.create(claimants.clone());
+    let late = s.create(claimants);
+
+    // In alice's window, bob is too early.
+    set_timestamp(&s.env, 150);
+    assert_eq!(
+        s.contract.try_claim(&s.bob, &early),
+        Err(Ok(ClaimError::TooEarly))
+    );
+    s.contract.claim(&s.alice, &early);
+
+    // In bob's window, alice's has closed.
+    set_timestamp(&s.env, 350);
+    assert_eq!(
+        s.contract.try_claim(&s.alice, &late),
+        Err(Ok(ClaimError::Expired))
+    );
+    s.contract.claim(&s.bob, &late);
+    assert_eq!(s.token.balance(&s.alice), AMOUNT);
+    assert_eq!(s.token.balance(&s.bob), AMOUNT);
+
+    let carol = Address::generate(&s.env);
+    let id = s.create(vec![&s.env, window(&s.alice, 0, 1_000)]);
+    assert_eq!(
+        s.contract.try_claim(&carol, &id),
+        Err(Ok(ClaimError::NotClaimant))
+    );
+}
+
+#[test]
+fn test_reclaim_waits_for_every_window() {
+    let s = setup();
+    let id = s.create(vec![
+        &s.env,
+        window(&s.alice, 100, 200),
+        window(&s.bob, 300, 400),
+    ]);
+
+    // Before any window opens, inside one, and between the two.
+    for now in [0, 150, 250, 400] {
+        set_timestamp(&s.env, now);
+        assert_eq!(
+            s.contract.try_reclaim(&s.sponsor, &id),
+            Err(Ok(ClaimError::WindowOpen))
+        );
+    }
+
+    set_timestamp(&s.env, 401);
+    assert_eq!(
+        s.contract.try_reclaim(&s.alice, &id),
+        Err(Ok(ClaimError::Unauthorized))
+    );
+    assert_eq!(s.contract.reclaim(&s.sponsor, &id), AMOUNT);
+    assert_eq!(s.token.balance(&s.sponsor), 10_000);
+    assert_eq!(
+        s.contract.try_claim(&s.bob, &id),
+        Err(Ok(ClaimError::NotFound))
+    );
+    assert_eq!(
+        s.contract.try_reclaim(&s.sponsor, &id),
+        Err(Ok(ClaimError::NotFound))
+    );
+}
+
+#[test]
+fn test_create_checks() {
+    let s = setup();
+    let ok = vec![&s.env, window(&s.alice, 0, 10)];
+    assert_eq!(
+        s.contract.try_create(&s.sponsor, &s.token.address, &0, &ok),
+        Err(Ok(ClaimError::InvalidInput))
+    );
+    assert_eq!(
+        s.contract
+            .try_create(&s.sponsor, &s.token.address, &AMOUNT, &Vec::new(&s.env)),
+        Err(Ok(ClaimError::InvalidInput))
+    );
+    assert_eq!(
+        s.contract.try_create(
+            &s.sponsor,
+            &s.token.address,
+            &AMOUNT,
+            &vec![&s.env, window(&s.alice, 10, 9)]
+        ),
+        Err(Ok(ClaimError::InvalidInput))
+    );
+    let mut crowd = Vec::new(&s.env);
+    for _ in 0..=MAX_CLAIMANTS {
+        crowd.push_back(window(&Address::generate(&s.env), 0, 10));
+    }
+    assert_eq!(
+        s.contract
+            .try_create(&s.sponsor, &s.token.address, &AMOUNT, &crowd),
+        Err(Ok(ClaimError::InvalidInput))
+    );
+
+    // Ids count up from 0, and the stored balance matches the request.
+    assert_eq!(s.create(ok.clone()), 0);
+    assert_eq!(s.create(ok.clone()), 1);
+    assert_eq!(
+        s.contract.get_balance(&1),
+        Some(ClaimableBalance {
+            sponsor: s.sponsor.clone(),
+            token: s.token.address.clone(),
+            amount: AMOUNT,
+            claimants: ok,
+        })
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (ClaimError::Unauthorized, CommonError::Unauthorized),
+        (ClaimError::NotFound, CommonError::NotFound),
+        (ClaimError::Expired, CommonError::Expired),
+        (ClaimError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    for error in [
+        ClaimError::NotClaimant,
+        ClaimError::TooEarly,
+        ClaimError::WindowOpen,
+    ] {
+        assert!(error as u32 >= recipes_errors::CONTRACT_MIN);
+    }
+}
@@ -0,0 +1,242 @@
+//! # Soulbound Badges
+//!
+//! Non-transferable credentials. An account holding the issuer role awards
+//! a badge, a plain `Symbol` such as `symbol_short!("kyc")`, to an address,
+//! optionally until an expiry time. The badge stays with that address
+//! until it expires or an issuer revokes it.
+//!
+//! **There is no transfer.** No function takes a badge from one holder and
+//! gives it to another, and none accepts the holder's authorization for
+//! anything but reading. Only an issuer can create a badge, and only for
+//! the address named in the call. `test_api_has_no_way_to_move_a_badge`
+//! pins the exported function list so a transfer cannot be added by
+//! accident.
+//!
+//! Roles come from `recipes-access-control`: the account passed to
+//! `initialize` holds `ADMIN` and manages who holds `ISSUER`.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec,
+};
+
+const NAMESPACE: Symbol = symbol_short!("badges");
+
+/// Adds and removes issuers.
+pub const ADMIN: Symbol = symbol_short!("admin");
+/// Issues and revokes badges.
+pub const ISSUER: Symbol = symbol_short!("issuer");
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BadgeError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// The caller lacks the role the call needs. `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// `revoke` of a badge the address does not hold.
+    /// `CommonError::NotFound`.
+    NotFound = 6,
+    /// An expiry that is not in the future. `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// The address already holds this badge and it has not expired.
+    AlreadyIssued = 100,
+}
+
+impl From<recipes_access_control::AccessError> for BadgeError {
+    fn from(_: recipes_access_control::AccessError) -> Self {
+        BadgeError::Unauthorized
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeRecord {
+    pub issuer: Address,
+    pub issued_at: u64,
+    /// Ledger time from which the badge no longer counts, if any.
+    pub expires_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Initialized,
+    Badge(Address, Symbol),
+    /// Badge names an address has records for, in issue order.
+    Badges(Address),
+}
+
+#[contract]
+pub struct SoulboundBadgesContract;
+
+#[contractimpl]
+impl SoulboundBadgesContract {
+    /// Make `admin` the first admin.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), BadgeError> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Initialized) {
+            return Err(BadgeError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        recipes_access_control::grant(&env, &admin, &ADMIN);
+        Ok(())
+    }
+
+    /// Give `issuer` the issuer role. Admin only.
+    pub fn add_issuer(env: Env, admin: Address, issuer: Address) -> Result<(), BadgeError> {
+        Self::require_role(&env, &admin, &ADMIN)?;
+        recipes_access_control::grant(&env, &issuer, &ISSUER);
+        Ok(())
+    }
+
+    /// Take the issuer role from `issuer`. Badges it issued stay valid.
+    /// Admin only.
+    pub fn remove_issuer(env: Env, admin: Address, issuer: Address) -> Result<(), BadgeError> {
+        Self::require_role(&env, &admin, &ADMIN)?;
+        recipes_access_control::revoke(&env, &issuer, &ISSUER);
+        Ok(())
+    }
+
+    /// Award `badge` to `to` with no expiry. Issuer only.
+    pub fn issue(env: Env, issuer: Address, to: Address, badge: Symbol) -> Result<(), BadgeError> {
+        Self::issue_badge(&env, issuer, to, badge, None)
+    }
+
+    /// Award `badge` to `to` until `expires_at`, in ledger seconds.
+    /// Issuer only.
+    pub fn issue_until(
+        env: Env,
+        issuer: Address,
+        to: Address,
+        badge: Symbol,
+        expires_at: u64,
+    ) -> Result<(), BadgeError> {
+        if expires_at <= env.ledger().timestamp() {
+            return Err(BadgeError::InvalidInput);
+        }
+        Self::issue_badge(&env, issuer, to, badge, Some(expires_at))
+    }
+
+    /// Remove `badge` from `from`, expired or not. Any issuer may revoke
+    /// any badge, not only the ones it issued.
+    pub fn revoke(
+        env: Env,
+        issuer: Address,
+        from: Address,
+        badge: Symbol,
+    ) -> Result<(), BadgeError> {
+        Self::require_role(&env, &issuer, &ISSUER)?;
+        let key = DataKey::Badge(from.clone(), badge.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(BadgeError::NotFound);
+        }
+        env.storage().persistent().remove(&key);
+        let mut names = Self::names(&env, &from);
+        if let Some(index) = names.first_index_of(&badge) {
+            names.remove(index);
+        }
+        let list_key = DataKey::Badges(from.clone());
+        if names.is_empty() {
+            env.storage().persistent().remove(&list_key);
+        } else {
+            env.storage().persistent().set(&list_key, &names);
+        }
+        env.events()
+            .publish((NAMESPACE, symbol_short!("revoked"), from), badge);
+        Ok(())
+    }
+
+    /// Whether `who` holds `badge` and it has not expired.
+    pub fn has_badge(env: Env, who: Address, badge: Symbol) -> bool {
+        Self::badge(env.clone(), who, badge).is_some_and(|record| is_live(&env, &record))
+    }
+
+    /// The badges `who` currently holds, leaving out expired ones.
+    pub fn badges_of(env: Env, who: Address) -> Vec<Symbol> {
+        let mut live = Vec::new(&env);
+        for name in Self::names(&env, &who).iter() {
+            if Self::has_badge(env.clone(), who.clone(), name.clone()) {
+                live.push_back(name);
+            }
+        }
+        live
+    }
+
+    /// The stored record for `badge`, including an expired one.
+    pub fn badge(env: Env, who: Address, badge: Symbol) -> Option<BadgeRecord> {
+        env.storage().persistent().get(&DataKey::Badge(who, badge))
+    }
+
+    pub fn is_issuer(env: Env, account: Address) -> bool {
+        recipes_access_control::has(&env, &account, &ISSUER)
+    }
+}
+
+impl SoulboundBadgesContract {
+    fn require_role(env: &Env, account: &Address, role: &Symbol) -> Result<(), BadgeError> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(BadgeError::NotInitialized);
+        }
+        account.require_auth();
+        recipes_access_control::require_role(env, account, role)?;
+        Ok(())
+    }
+
+    /// Store the record. An expired badge may be issued again, which
+    /// renews it; a live one may not.
+    fn issue_badge(
+        env: &Env,
+        issuer: Address,
+        to: Address,
+        badge: Symbol,
+        expires_at: Option<u64>,
+    ) -> Result<(), BadgeError> {
+        Self::require_role(env, &issuer, &ISSUER)?;
+        let existing = Self::badge(env.clone(), to.clone(), badge.clone());
+        if existing.as_ref().is_some_and(|record| is_live(env, record)) {
+            return Err(BadgeError::AlreadyIssued);
+        }
+        if existing.is_none() {
+            let mut names = Self::names(env, &to);
+            names.push_back(badge.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Badges(to.clone()), &names);
+        }
+        let record = BadgeRecord {
+            issuer,
+            issued_at: env.ledger().timestamp(),
+            expires_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Badge(to.clone(), badge.clone()), &record);
+        env.events().publish(
+            (NAMESPACE, symbol_short!("issued"), to),
+            (badge, expires_at),
+        );
+        Ok(())
+    }
+
+    fn names(env: &Env, who: &Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Badges(who.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+}
+
+fn is_live(env: &Env, record: &BadgeRecord) -> bool {
+    record
+        .expires_at
+        .is_none_or(|expires_at| env.ledger().timestamp() < expires_at)
+}
+
+mod test;
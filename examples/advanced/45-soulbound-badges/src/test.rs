@@ -0,0 +1,261 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::{advance_time, all_events_for, set_timestamp};
+use soroban_sdk::{testutils::Address as _, vec, IntoVal};
+
+const KYC: Symbol = symbol_short!("kyc");
+const SPEAKER: Symbol = symbol_short!("speaker");
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    issuer: Address,
+    alice: Address,
+    bob: Address,
+    badges: SoulboundBadgesContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, 1_000);
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let badges = SoulboundBadgesContractClient::new(
+        &env,
+        &env.register_contract(None, SoulboundBadgesContract),
+    );
+    badges.initialize(&admin);
+    badges.add_issuer(&admin, &issuer);
+    Setup {
+        alice: Address::generate(&env),
+        bob: Address::generate(&env),
+        env,
+        admin,
+        issuer,
+        badges,
+    }
+}
+
+#[test]
+fn test_issue_and_enumerate() {
+    let s = setup();
+    s.badges.issue(&s.issuer, &s.alice, &KYC);
+    s.badges.issue(&s.issuer, &s.alice, &SPEAKER);
+    s.badges.issue(&s.issuer, &s.bob, &SPEAKER);
+
+    assert!(s.badges.has_badge(&s.alice, &KYC));
+    assert!(!s.badges.has_badge(&s.bob, &KYC));
+    assert_eq!(s.badges.badges_of(&s.alice), vec![&s.env, KYC, SPEAKER]);
+    assert_eq!(s.badges.badges_of(&s.bob), vec![&s.env, SPEAKER]);
+    assert_eq!(
+        s.badges.badge(&s.alice, &KYC),
+        Some(BadgeRecord {
+            issuer: s.issuer.clone(),
+            issued_at: 1_000,
+            expires_at: None,
+        })
+    );
+
+    let (topics, data) = all_events_for(&s.env, &s.badges.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("issued"), s.bob.clone()).into_val(&s.env)
+    );
+    let issued: (Symbol, Option<u64>) = data.into_val(&s.env);
+    assert_eq!(issued, (SPEAKER, None));
+}
+
+#[test]
+fn test_badge_expires() {
+    let s = setup();
+    s.badges.issue_until(&s.issuer, &s.alice, &KYC, &1_500);
+    s.badges.issue(&s.issuer, &s.alice, &SPEAKER);
+
+    advance_time(&s.env, 499);
+    assert!(s.badges.has_badge(&s.alice, &KYC));
+    advance_time(&s.env, 1);
+    assert!(!s.badges.has_badge(&s.alice, &KYC));
+    assert_eq!(s.badges.badges_of(&s.alice), vec![&s.env, SPEAKER]);
+    // The record is kept, so the expiry is still visible.
+    assert_eq!(
+        s.badges.badge(&s.alice, &KYC).unwrap().expires_at,
+        Some(1_500)
+    );
+
+    // An expired badge may be issued again, which renews it.
+    s.badges.issue_until(&s.issuer, &s.alice, &KYC, &3_000);
+    assert!(s.badges.has_badge(&s.alice, &KYC));
+    assert_eq!(s.badges.badges_of(&s.alice), vec![&s.env, KYC, SPEAKER]);
+
+    assert_eq!(
+        s.badges.try_issue_until(&s.issuer, &s.bob, &KYC, &1_500),
+        Err(Ok(BadgeError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_revoke() {
+    let s = setup();
+    s.badges.issue(&s.issuer, &s.alice, &KYC);
+    s.badges.issue(&s.issuer, &s.alice, &SPEAKER);
+
+    // Another issuer may revoke a badge it did not issue.
+    let second = Address::generate(&s.env);
+    s.badges.add_issuer(&s.admin, &second);
+    s.badges.revoke(&second, &s.alice, &KYC);
+    assert!(!s.badges.has_badge(&s.alice, &KYC));
+    assert_eq!(s.badges.badge(&s.alice, &KYC), None);
+    assert_eq!(s.badges.badges_of(&s.alice), vec![&s.env, SPEAKER]);
+
+    let (topics, data) = all_events_for(&s.env, &s.badges.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("revoked"), s.alice.clone()).into_val(&s.env)
+    );
+    let badge: Symbol = data.into_val(&s.env);
+    assert_eq!(badge, KYC);
+
+    assert_eq!(
+        s.badges.try_revoke(&s.issuer, &s.alice, &KYC),
+        Err(Ok(BadgeError::NotFound))
+    );
+    s.badges.revoke(&s.issuer, &s.alice, &SPEAKER);
+    assert_eq!(s.badges.badges_of(&s.alice), Vec::new(&s.env));
+}
+
+#[test]
+fn test_duplicate_issue_rejected() {
+    let s = setup();
+    s.badges.issue(&s.issuer, &s.alice, &KYC);
+    assert_eq!(
+        s.badges.try_issue(&s.issuer, &s.alice, &KYC),
+        Err(Ok(BadgeError::AlreadyIssued))
+    );
+    assert_eq!(
+        s.badges.try_issue_until(&s.issuer, &s.alice, &KYC, &5_000),
+        Err(Ok(BadgeError::AlreadyIssued))
+    );
+    assert_eq!(s.badges.badges_of(&s.alice), vec![&s.env, KYC]);
+}
+
+#[test]
+fn test_only_issuers_issue_and_revoke() {
+    let s = setup();
+    s.badges.issue(&s.issuer, &s.alice, &KYC);
+
+    // Neither a holder nor the admin holds the issuer role.
+    for caller in [&s.alice, &s.bob, &s.admin] {
+        assert_eq!(
+            s.badges.try_issue(caller, &s.bob, &KYC),
+            Err(Ok(BadgeError::Unauthorized))
+        );
+        assert_eq!(
+            s.badges.try_revoke(caller, &s.alice, &KYC),
+            Err(Ok(BadgeError::Unauthorized))
+        );
+    }
+    assert_eq!(
+        s.badges.try_add_issuer(&s.issuer, &s.bob),
+        Err(Ok(BadgeError::Unauthorized))
+    );
+
+    s.badges.remove_issuer(&s.admin, &s.issuer);
+    assert!(!s.badges.is_issuer(&s.issuer));
+    assert_eq!(
+        s.badges.try_issue(&s.issuer, &s.bob, &KYC),
+        Err(Ok(BadgeError::Unauthorized))
+    );
+    // Badges it issued stay valid.
+    assert!(s.badges.has_badge(&s.alice, &KYC));
+}
+
+/// The exported functions, read from this crate's source. Anything added
+/// to the `#[contractimpl]` block shows up here and fails the test below
+/// until it is reviewed.
+fn exported_functions() -> std::vec::Vec<std::string::String> {
+    let source = include_str!("lib.rs");
+    let start = source.find("#[contractimpl]").unwrap();
+    let end = source.rfind("impl SoulboundBadgesContract {").unwrap();
+    source[start..end]
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("pub fn "))
+        .map(|rest| rest[..rest.find('(').unwrap()].into())
+        .collect()
+}
+
+#[test]
+fn test_api_has_no_way_to_move_a_badge() {
+    let s = setup();
+    s.badges.issue(&s.issuer, &s.alice, &KYC);
+
+    // Every state-changing function needs the admin or issuer role; none
+    // takes a holder and a recipient. A holder therefore has no call that
+    // moves a badge.
+    assert_eq!(
+        exported_functions(),
+        [
+            "initialize",
+            "add_issuer",
+            "remove_issuer",
+            "issue",
+            "issue_until",
+            "revoke",
+            "has_badge",
+            "badges_of",
+            "badge",
+            "is_issuer",
+        ]
+    );
+    for name in exported_functions() {
+        for word in ["transfer", "move", "send", "give"] {
+            assert!(!name.starts_with(word), "{name} looks like a transfer");
+        }
+    }
+
+    // The nearest thing to a transfer is revoke plus issue, and a holder
+    // can do neither.
+    assert!(s.badges.try_revoke(&s.alice, &s.alice, &KYC).is_err());
+    assert!(s.badges.try_issue(&s.alice, &s.bob, &KYC).is_err());
+    assert!(s.badges.has_badge(&s.alice, &KYC));
+    assert!(!s.badges.has_badge(&s.bob, &KYC));
+}
+
+#[test]
+fn test_initialization_checks() {
+    let s = setup();
+    assert_eq!(
+        s.badges.try_initialize(&s.bob),
+        Err(Ok(BadgeError::AlreadyInitialized))
+    );
+    let fresh = SoulboundBadgesContractClient::new(
+        &s.env,
+        &s.env.register_contract(None, SoulboundBadgesContract),
+    );
+    assert_eq!(
+        fresh.try_issue(&s.issuer, &s.alice, &KYC),
+        Err(Ok(BadgeError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (BadgeError::NotInitialized, CommonError::NotInitialized),
+        (
+            BadgeError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (BadgeError::Unauthorized, CommonError::Unauthorized),
+        (BadgeError::NotFound, CommonError::NotFound),
+        (BadgeError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(BadgeError::AlreadyIssued as u32 >= recipes_errors::CONTRACT_MIN);
+}
@@ -0,0 +1,308 @@
+//! # Circuit Breaker
+//!
+//! A token vault that pauses itself when withdrawals spike. A breaker adds
+//! up the amount withdrawn in the current window. When a withdrawal takes
+//! that volume past the configured limit, the vault sets the
+//! `recipes-pausable` flag and every later withdrawal fails with `Paused`
+//! until the admin calls `reset`.
+//!
+//! The limit is either an absolute amount or a share of the vault, in
+//! basis points of the total held when the window opened. Measuring
+//! against the opening total keeps the threshold from shrinking as the
+//! drain it is meant to catch empties the vault.
+//!
+//! **The breaching withdrawal completes, then the vault pauses.** A
+//! Soroban call that returns an error rolls back every write it made, the
+//! pause flag included, so a breaker that rejected the breaching
+//! withdrawal could never stay tripped. The cost is that one withdrawal
+//! gets through past the limit; it is still bounded by the caller's own
+//! balance.
+//!
+//! ```text
+//! window_start            window_start + window
+//! |-- volume adds up -------|-- next withdrawal opens a new window
+//! ```
+//!
+//! A window opens with the first withdrawal after the previous one ended,
+//! so volume from an earlier window is forgotten as a whole.
+
+#![no_std]
+
+use recipes_pausable::PausableError;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+const NAMESPACE: Symbol = symbol_short!("breaker");
+const BPS: i128 = 10_000;
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BreakerError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// The caller is not the admin. `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// `withdraw` while the breaker is tripped. `CommonError::Paused`.
+    Paused = 4,
+    /// A balance or the window volume no longer fits in an `i128`.
+    /// `CommonError::Overflow`.
+    Overflow = 5,
+    /// A non-positive amount, a zero window, a negative absolute limit or a
+    /// basis-point limit above 10 000. `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// A withdrawal of more than the caller deposited.
+    InsufficientBalance = 100,
+}
+
+impl From<PausableError> for BreakerError {
+    fn from(err: PausableError) -> Self {
+        match err {
+            PausableError::Paused => BreakerError::Paused,
+        }
+    }
+}
+
+/// How much may leave the vault in one window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Limit {
+    /// A fixed amount of the token.
+    Absolute(i128),
+    /// Basis points of the total held when the window opened.
+    Bps(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub token: Address,
+    /// Window length in seconds.
+    pub window: u64,
+    pub limit: Limit,
+}
+
+/// The breaker's accounting for the current window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Window {
+    pub start: u64,
+    /// Total withdrawn since `start`.
+    pub volume: i128,
+    /// Vault total when the window opened, the base for `Limit::Bps`.
+    pub opening_total: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Window,
+    /// Tokens held by the vault.
+    Total,
+    Balance(Address),
+}
+
+#[contract]
+pub struct CircuitBreakerContract;
+
+#[contractimpl]
+impl CircuitBreakerContract {
+    pub fn initialize(env: Env, config: Config) -> Result<(), BreakerError> {
+        config.admin.require_auth();
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(BreakerError::AlreadyInitialized);
+        }
+        if config.window == 0 {
+            return Err(BreakerError::InvalidInput);
+        }
+        check_limit(&config.limit)?;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Move `amount` from `user` into the vault. Deposits stay open while
+    /// the breaker is tripped: they add funds, they cannot drain them.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<(), BreakerError> {
+        user.require_auth();
+        let config = Self::config(env.clone())?;
+        if amount <= 0 {
+            return Err(BreakerError::InvalidInput);
+        }
+        let balance = Self::balance(env.clone(), user.clone())
+            .checked_add(amount)
+            .ok_or(BreakerError::Overflow)?;
+        let total = Self::total(env.clone())
+            .checked_add(amount)
+            .ok_or(BreakerError::Overflow)?;
+
+        token::Client::new(&env, &config.token).transfer(
+            &user,
+            &env.current_contract_address(),
+            &amount,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user.clone()), &balance);
+        env.storage().instance().set(&DataKey::Total, &total);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("deposit"), user), amount);
+        Ok(())
+    }
+
+    /// Pay `amount` of `user`'s balance back to them, and count it towards
+    /// the window's volume. Returns `true` if this withdrawal tripped the
+    /// breaker; it still completes.
+    pub fn withdraw(env: Env, user: Address, amount: i128) -> Result<bool, BreakerError> {
+        user.require_auth();
+        let config = Self::config(env.clone())?;
+        recipes_pausable::require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(BreakerError::InvalidInput);
+        }
+        let balance = Self::balance(env.clone(), user.clone());
+        if amount > balance {
+            return Err(BreakerError::InsufficientBalance);
+        }
+
+        let mut window = Self::current_window(&env, &config);
+        window.volume = window
+            .volume
+            .checked_add(amount)
+            .ok_or(BreakerError::Overflow)?;
+        let threshold = threshold_of(&config.limit, &window)?;
+        let tripped = window.volume > threshold;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user.clone()), &(balance - amount));
+        env.storage()
+            .instance()
+            .set(&DataKey::Total, &(Self::total(env.clone()) - amount));
+        env.storage().instance().set(&DataKey::Window, &window);
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+        env.events()
+            .publish((NAMESPACE, symbol_short!("withdraw"), user), amount);
+
+        if tripped {
+            recipes_pausable::set_paused(&env, true);
+            recipes_pausable::emit_pause_changed(&env, &env.current_contract_address(), true);
+            env.events().publish(
+                (NAMESPACE, symbol_short!("tripped")),
+                (window.volume, threshold),
+            );
+        }
+        Ok(tripped)
+    }
+
+    /// Clear the pause and start a fresh window. Admin only.
+    pub fn reset(env: Env, admin: Address) -> Result<(), BreakerError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::Window);
+        recipes_pausable::set_paused(&env, false);
+        recipes_pausable::emit_pause_changed(&env, &admin, false);
+        Ok(())
+    }
+
+    /// Replace the limit. Applies from the next withdrawal, against the
+    /// current window's volume. Admin only.
+    pub fn set_limit(env: Env, admin: Address, limit: Limit) -> Result<(), BreakerError> {
+        let mut config = Self::require_admin(&env, &admin)?;
+        check_limit(&limit)?;
+        config.limit = limit;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        recipes_pausable::is_paused(&env)
+    }
+
+    /// The current window, or `None` before the first withdrawal and after
+    /// a `reset`. A window that has ended is still returned until the next
+    /// withdrawal replaces it.
+    pub fn window(env: Env) -> Option<Window> {
+        env.storage().instance().get(&DataKey::Window)
+    }
+
+    /// The volume at which a withdrawal opening a window now would trip the
+    /// breaker, or the current window's threshold while it is open.
+    pub fn threshold(env: Env) -> Result<i128, BreakerError> {
+        let config = Self::config(env.clone())?;
+        threshold_of(&config.limit, &Self::current_window(&env, &config))
+    }
+
+    pub fn balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    pub fn total(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Total).unwrap_or(0)
+    }
+
+    pub fn config(env: Env) -> Result<Config, BreakerError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(BreakerError::NotInitialized)
+    }
+}
+
+impl CircuitBreakerContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<Config, BreakerError> {
+        let config = Self::config(env.clone())?;
+        admin.require_auth();
+        if *admin != config.admin {
+            return Err(BreakerError::Unauthorized);
+        }
+        Ok(config)
+    }
+
+    /// The stored window if it is still open, otherwise a new one opening
+    /// now with no volume.
+    fn current_window(env: &Env, config: &Config) -> Window {
+        let now = env.ledger().timestamp();
+        match Self::window(env.clone()) {
+            Some(window) if now < window.start.saturating_add(config.window) => window,
+            _ => Window {
+                start: now,
+                volume: 0,
+                opening_total: Self::total(env.clone()),
+            },
+        }
+    }
+}
+
+fn check_limit(limit: &Limit) -> Result<(), BreakerError> {
+    match limit {
+        Limit::Absolute(amount) if *amount < 0 => Err(BreakerError::InvalidInput),
+        Limit::Bps(bps) if i128::from(*bps) > BPS => Err(BreakerError::InvalidInput),
+        _ => Ok(()),
+    }
+}
+
+fn threshold_of(limit: &Limit, window: &Window) -> Result<i128, BreakerError> {
+    match limit {
+        Limit::Absolute(amount) => Ok(*amount),
+        Limit::Bps(bps) => Ok(window
+            .opening_total
+            .checked_mul(i128::from(*bps))
+            .ok_or(BreakerError::Overflow)?
+            / BPS),
+    }
+}
+
+mod test;
@@ -0,0 +1,271 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::{advance_time, all_events_for, set_timestamp};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    IntoVal,
+};
+
+const WINDOW: u64 = 3_600;
+const LIMIT: i128 = 500;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+    token: TokenClient<'static>,
+    vault: CircuitBreakerContractClient<'static>,
+}
+
+/// A vault limited to `LIMIT` per `WINDOW`, holding 1 000 from each of
+/// alice and bob.
+fn setup_with(limit: Limit) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, 10_000);
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let minter = StellarAssetClient::new(&env, &asset.address());
+    minter.mint(&alice, &1_000);
+    minter.mint(&bob, &1_000);
+
+    let vault = CircuitBreakerContractClient::new(
+        &env,
+        &env.register_contract(None, CircuitBreakerContract),
+    );
+    vault.initialize(&Config {
+        admin: admin.clone(),
+        token: asset.address(),
+        window: WINDOW,
+        limit,
+    });
+    vault.deposit(&alice, &1_000);
+    vault.deposit(&bob, &1_000);
+    Setup {
+        token: TokenClient::new(&env, &asset.address()),
+        env,
+        admin,
+        alice,
+        bob,
+        vault,
+    }
+}
+
+fn setup() -> Setup {
+    setup_with(Limit::Absolute(LIMIT))
+}
+
+/// Withdraw until the breaker trips.
+fn trip(s: &Setup) {
+    s.vault.withdraw(&s.alice, &LIMIT);
+    assert!(s.vault.withdraw(&s.bob, &1));
+}
+
+#[test]
+fn test_withdrawals_under_threshold_proceed() {
+    let s = setup();
+    assert!(!s.vault.withdraw(&s.alice, &200));
+    advance_time(&s.env, 60);
+    // Reaching the limit exactly does not trip it.
+    assert!(!s.vault.withdraw(&s.bob, &300));
+    assert!(!s.vault.is_paused());
+    assert_eq!(
+        s.vault.window(),
+        Some(Window {
+            start: 10_000,
+            volume: 500,
+            opening_total: 2_000,
+        })
+    );
+    assert_eq!(s.token.balance(&s.alice), 200);
+    assert_eq!(s.token.balance(&s.bob), 300);
+    assert_eq!(s.vault.total(), 1_500);
+}
+
+#[test]
+fn test_breaching_withdrawal_completes_then_pauses() {
+    let s = setup();
+    s.vault.withdraw(&s.alice, &400);
+    assert!(s.vault.withdraw(&s.bob, &200));
+
+    // The breaching withdrawal went through.
+    assert_eq!(s.token.balance(&s.bob), 200);
+    assert_eq!(s.vault.balance(&s.bob), 800);
+    assert_eq!(s.vault.total(), 1_400);
+    assert!(s.vault.is_paused());
+
+    let events = all_events_for(&s.env, &s.vault.address);
+    let (topics, data) = &events[events.len() - 2];
+    assert_eq!(
+        *topics,
+        (recipes_pausable::NAMESPACE, symbol_short!("paused")).into_val(&s.env)
+    );
+    let by: Address = data.into_val(&s.env);
+    assert_eq!(by, s.vault.address);
+    let (topics, data) = events.last().unwrap();
+    assert_eq!(
+        *topics,
+        (NAMESPACE, symbol_short!("tripped")).into_val(&s.env)
+    );
+    let tripped: (i128, i128) = data.into_val(&s.env);
+    assert_eq!(tripped, (600, LIMIT));
+}
+
+#[test]
+fn test_withdrawals_blocked_while_tripped() {
+    let s = setup();
+    trip(&s);
+    assert_eq!(
+        s.vault.try_withdraw(&s.alice, &1),
+        Err(Ok(BreakerError::Paused))
+    );
+    // Waiting out the window does not clear the pause.
+    advance_time(&s.env, 10 * WINDOW);
+    assert_eq!(
+        s.vault.try_withdraw(&s.bob, &1),
+        Err(Ok(BreakerError::Paused))
+    );
+    // Deposits still work.
+    s.vault.deposit(&s.alice, &100);
+    assert_eq!(s.vault.balance(&s.alice), 600);
+}
+
+#[test]
+fn test_admin_reset() {
+    let s = setup();
+    trip(&s);
+    assert_eq!(
+        s.vault.try_reset(&s.alice),
+        Err(Ok(BreakerError::Unauthorized))
+    );
+    s.vault.reset(&s.admin);
+    assert!(!s.vault.is_paused());
+    assert_eq!(s.vault.window(), None);
+
+    let (topics, _) = all_events_for(&s.env, &s.vault.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (recipes_pausable::NAMESPACE, symbol_short!("unpaused")).into_val(&s.env)
+    );
+
+    // The reset also forgot the volume, so a full limit is available again.
+    assert!(!s.vault.withdraw(&s.alice, &LIMIT));
+    assert!(s.vault.withdraw(&s.bob, &1));
+}
+
+#[test]
+fn test_window_rolls_over() {
+    let s = setup();
+    s.vault.withdraw(&s.alice, &450);
+    advance_time(&s.env, WINDOW - 1);
+    assert!(s.vault.withdraw(&s.alice, &51));
+    s.vault.reset(&s.admin);
+
+    s.vault.withdraw(&s.bob, &450);
+    advance_time(&s.env, WINDOW);
+    // The window has ended, so its 450 no longer count.
+    assert!(!s.vault.withdraw(&s.bob, &450));
+    let window = s.vault.window().unwrap();
+    assert_eq!(window.start, s.env.ledger().timestamp());
+    assert_eq!(window.volume, 450);
+}
+
+#[test]
+fn test_bps_limit_uses_the_opening_total() {
+    // 25% of the 2 000 held when the window opens.
+    let s = setup_with(Limit::Bps(2_500));
+    assert_eq!(s.vault.threshold(), 500);
+    assert!(!s.vault.withdraw(&s.alice, &500));
+    // The vault now holds 1 500, but the threshold stays at 500.
+    assert_eq!(s.vault.threshold(), 500);
+    assert!(s.vault.withdraw(&s.bob, &1));
+
+    // A new window measures against the total at that time.
+    s.vault.reset(&s.admin);
+    assert_eq!(s.vault.threshold(), 1_499 * 2_500 / 10_000);
+}
+
+#[test]
+fn test_set_limit_and_input_checks() {
+    let s = setup();
+    s.vault.withdraw(&s.alice, &400);
+    s.vault.set_limit(&s.admin, &Limit::Absolute(300));
+    // The new limit applies to the volume already in the window.
+    assert!(s.vault.withdraw(&s.alice, &1));
+    assert_eq!(s.vault.config().limit, Limit::Absolute(300));
+
+    assert_eq!(
+        s.vault.try_set_limit(&s.bob, &Limit::Absolute(1_000)),
+        Err(Ok(BreakerError::Unauthorized))
+    );
+    assert_eq!(
+        s.vault.try_set_limit(&s.admin, &Limit::Absolute(-1)),
+        Err(Ok(BreakerError::InvalidInput))
+    );
+    assert_eq!(
+        s.vault.try_set_limit(&s.admin, &Limit::Bps(10_001)),
+        Err(Ok(BreakerError::InvalidInput))
+    );
+    s.vault.reset(&s.admin);
+    assert_eq!(
+        s.vault.try_withdraw(&s.bob, &0),
+        Err(Ok(BreakerError::InvalidInput))
+    );
+    assert_eq!(
+        s.vault.try_withdraw(&s.bob, &1_001),
+        Err(Ok(BreakerError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_initialization_checks() {
+    let s = setup();
+    let config = s.vault.config();
+    assert_eq!(
+        s.vault.try_initialize(&config),
+        Err(Ok(BreakerError::AlreadyInitialized))
+    );
+
+    let fresh = CircuitBreakerContractClient::new(
+        &s.env,
+        &s.env.register_contract(None, CircuitBreakerContract),
+    );
+    assert_eq!(
+        fresh.try_withdraw(&s.alice, &1),
+        Err(Ok(BreakerError::NotInitialized))
+    );
+    assert_eq!(
+        fresh.try_initialize(&Config {
+            window: 0,
+            ..config
+        }),
+        Err(Ok(BreakerError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (BreakerError::NotInitialized, CommonError::NotInitialized),
+        (
+            BreakerError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (BreakerError::Unauthorized, CommonError::Unauthorized),
+        (BreakerError::Paused, CommonError::Paused),
+        (BreakerError::Overflow, CommonError::Overflow),
+        (BreakerError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(BreakerError::InsufficientBalance as u32 >= recipes_errors::CONTRACT_MIN);
+}
@@ -0,0 +1,143 @@
+//! # Contract Registry
+//!
+//! Resolves names to contract addresses, so callers can find a service by
+//! a stable name instead of a hard-coded address. Pointing a name at a new
+//! address moves every caller that resolves it, with no change to the
+//! callers: late binding. `06-cross-contract`'s `add_by_name` resolves its
+//! callee here.
+//!
+//! Names are first come, first served. The first `register` of a name
+//! makes the caller its owner; after that only the owner can repoint it or
+//! hand it over with `transfer_name`. Names are never released.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec,
+};
+
+const NAMESPACE: Symbol = symbol_short!("registry");
+
+/// Most names `list_names` returns in one page.
+pub const MAX_PAGE: u32 = 20;
+
+/// Failures, using the `recipes-errors` catalogue codes.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RegistryError {
+    /// The name belongs to someone else. `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// `transfer_name` of a name nobody registered. `CommonError::NotFound`.
+    NotFound = 6,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    pub owner: Address,
+    pub target: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Record(Symbol),
+    /// Every registered name, in registration order.
+    Names,
+}
+
+#[contract]
+pub struct ContractRegistry;
+
+#[contractimpl]
+impl ContractRegistry {
+    /// Claim `name` for `owner` and point it at `target`, or repoint a name
+    /// `owner` already holds.
+    pub fn register(
+        env: Env,
+        owner: Address,
+        name: Symbol,
+        target: Address,
+    ) -> Result<(), RegistryError> {
+        owner.require_auth();
+        let key = DataKey::Record(name.clone());
+        match Self::record(env.clone(), name.clone()) {
+            Some(record) if record.owner != owner => return Err(RegistryError::Unauthorized),
+            Some(_) => {
+                env.events()
+                    .publish((NAMESPACE, symbol_short!("update"), name), target.clone());
+            }
+            None => {
+                let mut names = Self::names(&env);
+                names.push_back(name.clone());
+                env.storage().persistent().set(&DataKey::Names, &names);
+                env.events().publish(
+                    (NAMESPACE, symbol_short!("register"), name),
+                    (owner.clone(), target.clone()),
+                );
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&key, &Record { owner, target });
+        Ok(())
+    }
+
+    /// The address `name` points at, if it is registered.
+    pub fn resolve(env: Env, name: Symbol) -> Option<Address> {
+        Self::record(env, name).map(|record| record.target)
+    }
+
+    /// Hand `name` to `new_owner`. The target is unchanged.
+    pub fn transfer_name(
+        env: Env,
+        owner: Address,
+        name: Symbol,
+        new_owner: Address,
+    ) -> Result<(), RegistryError> {
+        owner.require_auth();
+        let mut record = Self::record(env.clone(), name.clone()).ok_or(RegistryError::NotFound)?;
+        if record.owner != owner {
+            return Err(RegistryError::Unauthorized);
+        }
+        record.owner = new_owner.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Record(name.clone()), &record);
+        env.events().publish(
+            (NAMESPACE, symbol_short!("transfer"), name),
+            (owner, new_owner),
+        );
+        Ok(())
+    }
+
+    /// Up to `limit` names, capped at `MAX_PAGE`, starting at position
+    /// `start` in registration order. Past the end the page is empty.
+    pub fn list_names(env: Env, start: u32, limit: u32) -> Vec<Symbol> {
+        let names = Self::names(&env);
+        let start = start.min(names.len());
+        let end = start.saturating_add(limit.min(MAX_PAGE)).min(names.len());
+        names.slice(start..end)
+    }
+
+    /// Number of registered names.
+    pub fn name_count(env: Env) -> u32 {
+        Self::names(&env).len()
+    }
+
+    pub fn record(env: Env, name: Symbol) -> Option<Record> {
+        env.storage().persistent().get(&DataKey::Record(name))
+    }
+}
+
+impl ContractRegistry {
+    fn names(env: &Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Names)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+}
+
+mod test;
@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{testutils::Address as _, IntoVal};
+
+const MATH: Symbol = symbol_short!("math");
+
+struct Setup {
+    env: Env,
+    alice: Address,
+    bob: Address,
+    registry: ContractRegistryClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let registry =
+        ContractRegistryClient::new(&env, &env.register_contract(None, ContractRegistry));
+    Setup {
+        alice: Address::generate(&env),
+        bob: Address::generate(&env),
+        env,
+        registry,
+    }
+}
+
+#[test]
+fn test_register_and_resolve() {
+    let s = setup();
+    let target = Address::generate(&s.env);
+    s.registry.register(&s.alice, &MATH, &target);
+    assert_eq!(s.registry.resolve(&MATH), Some(target.clone()));
+    assert_eq!(
+        s.registry.record(&MATH),
+        Some(Record {
+            owner: s.alice.clone(),
+            target: target.clone(),
+        })
+    );
+
+    let (topics, data) = all_events_for(&s.env, &s.registry.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("register"), MATH).into_val(&s.env)
+    );
+    let registered: (Address, Address) = data.into_val(&s.env);
+    assert_eq!(registered, (s.alice.clone(), target));
+}
+
+#[test]
+fn test_unknown_name_resolves_to_none() {
+    let s = setup();
+    assert_eq!(s.registry.resolve(&MATH), None);
+    assert_eq!(s.registry.record(&MATH), None);
+}
+
+#[test]
+fn test_only_owner_updates() {
+    let s = setup();
+    let target = Address::generate(&s.env);
+    s.registry.register(&s.alice, &MATH, &target);
+
+    // Bob cannot take or repoint alice's name.
+    let squatter = Address::generate(&s.env);
+    assert_eq!(
+        s.registry.try_register(&s.bob, &MATH, &squatter),
+        Err(Ok(RegistryError::Unauthorized))
+    );
+    assert_eq!(s.registry.resolve(&MATH), Some(target));
+
+    let upgraded = Address::generate(&s.env);
+    s.registry.register(&s.alice, &MATH, &upgraded);
+    assert_eq!(s.registry.resolve(&MATH), Some(upgraded.clone()));
+    assert_eq!(s.registry.name_count(), 1);
+
+    let (topics, data) = all_events_for(&s.env, &s.registry.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("update"), MATH).into_val(&s.env)
+    );
+    let target: Address = data.into_val(&s.env);
+    assert_eq!(target, upgraded);
+}
+
+#[test]
+fn test_transfer_name() {
+    let s = setup();
+    let target = Address::generate(&s.env);
+    s.registry.register(&s.alice, &MATH, &target);
+    assert_eq!(
+        s.registry.try_transfer_name(&s.bob, &MATH, &s.bob),
+        Err(Ok(RegistryError::Unauthorized))
+    );
+
+    s.registry.transfer_name(&s.alice, &MATH, &s.bob);
+    assert_eq!(s.registry.record(&MATH).unwrap().owner, s.bob);
+    // The target is unchanged, and the old owner has lost control.
+    assert_eq!(s.registry.resolve(&MATH), Some(target.clone()));
+    assert_eq!(
+        s.registry.try_register(&s.alice, &MATH, &s.alice),
+        Err(Ok(RegistryError::Unauthorized))
+    );
+    s.registry.register(&s.bob, &MATH, &s.bob);
+
+    let (topics, _) = all_events_for(&s.env, &s.registry.address)
+        .into_iter()
+        .rev()
+        .nth(1)
+        .unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("transfer"), MATH).into_val(&s.env)
+    );
+
+    assert_eq!(
+        s.registry
+            .try_transfer_name(&s.alice, &symbol_short!("nope"), &s.bob),
+        Err(Ok(RegistryError::NotFound))
+    );
+}
+
+#[test]
+fn test_list_names_pages() {
+    let s = setup();
+    let target = Address::generate(&s.env);
+    let names: std::vec::Vec<Symbol> = (0..45)
+        .map(|i| Symbol::new(&s.env, &std::format!("svc{i}")))
+        .collect();
+    for name in &names {
+        s.registry.register(&s.alice, name, &target);
+    }
+    assert_eq!(s.registry.name_count(), 45);
+
+    let mut listed = std::vec::Vec::new();
+    let mut start = 0;
+    loop {
+        let page = s.registry.list_names(&start, &MAX_PAGE);
+        if page.is_empty() {
+            break;
+        }
+        listed.extend(page.iter());
+        start += page.len();
+    }
+    assert_eq!(listed, names);
+
+    // Limits are capped, and a start past the end gives an empty page.
+    assert_eq!(s.registry.list_names(&0, &100).len(), MAX_PAGE);
+    assert_eq!(s.registry.list_names(&40, &20).len(), 5);
+    assert_eq!(s.registry.list_names(&45, &20).len(), 0);
+    assert_eq!(s.registry.list_names(&u32::MAX, &u32::MAX).len(), 0);
+    assert_eq!(s.registry.list_names(&3, &0).len(), 0);
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    assert_eq!(
+        RegistryError::Unauthorized as u32,
+        CommonError::Unauthorized as u32
+    );
+    assert_eq!(RegistryError::NotFound as u32, CommonError::NotFound as u32);
+}
@@ -0,0 +1,194 @@
+//! # Ring Buffer
+//!
+//! A fixed-capacity history of `i128` values that keeps the newest ones
+//! and overwrites the oldest: the storage behind a TWAP, a recent-activity
+//! feed or a bounded audit log.
+//!
+//! Each value sits in its own persistent entry, `DataKey::Slot(i)` for
+//! `i` in `0..capacity`. Two counters in instance storage track the
+//! layout:
+//!
+//! ```text
+//! capacity 5, after pushing 1..=7:
+//!
+//! slot   0   1   2   3   4
+//! value  6   7   3   4   5
+//!                ^head
+//! len 5 (full), the oldest value is at head, the newest just before it
+//! ```
+//!
+//! `push` writes one slot and the two counters, however many values have
+//! ever been pushed, so its cost stays flat. Once the buffer is full, the
+//! number of entries stops growing too.
+//!
+//! `resize` is the one operation whose cost depends on the contents: it
+//! rewrites every kept value into its new slot. Shrinking **discards the
+//! oldest values** that no longer fit, for good.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Vec};
+
+/// Largest capacity `initialize` and `resize` accept. `resize` rewrites
+/// up to this many entries in one call.
+pub const MAX_CAPACITY: u32 = 256;
+
+/// Failures, using the `recipes-errors` catalogue codes.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RingError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// `resize` by someone other than the admin. `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// A capacity of zero or above `MAX_CAPACITY`.
+    /// `CommonError::InvalidInput`.
+    InvalidInput = 8,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Capacity,
+    /// The slot the next `push` writes. Once the buffer is full, also the
+    /// slot of the oldest value.
+    Head,
+    /// Number of values held, at most the capacity.
+    Len,
+    Slot(u32),
+}
+
+#[contract]
+pub struct RingBuffer;
+
+#[contractimpl]
+impl RingBuffer {
+    pub fn initialize(env: Env, admin: Address, capacity: u32) -> Result<(), RingError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(RingError::AlreadyInitialized);
+        }
+        check_capacity(capacity)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Capacity, &capacity);
+        Ok(())
+    }
+
+    /// Append `value`, overwriting the oldest value once the buffer is
+    /// full. Needs the admin's authorization.
+    pub fn push(env: Env, value: i128) -> Result<(), RingError> {
+        Self::admin(&env)?.require_auth();
+        let capacity = Self::capacity(env.clone())?;
+        let head = Self::head(&env);
+        let len = Self::len(env.clone());
+
+        env.storage().persistent().set(&DataKey::Slot(head), &value);
+        env.storage()
+            .instance()
+            .set(&DataKey::Head, &((head + 1) % capacity));
+        if len < capacity {
+            env.storage().instance().set(&DataKey::Len, &(len + 1));
+        }
+        Ok(())
+    }
+
+    /// The newest `n` values, newest first. Returns all of them if `n` is
+    /// larger than `len`.
+    pub fn latest(env: Env, n: u32) -> Result<Vec<i128>, RingError> {
+        let capacity = Self::capacity(env.clone())?;
+        let head = Self::head(&env);
+        let count = n.min(Self::len(env.clone()));
+        let mut values = Vec::new(&env);
+        for back in 1..=count {
+            let slot = (head + capacity - back) % capacity;
+            values.push_back(Self::slot(&env, slot));
+        }
+        Ok(values)
+    }
+
+    /// Change the capacity. Admin only.
+    ///
+    /// The newest `min(len, new_capacity)` values are kept, in order, and
+    /// the rest are deleted: shrinking below `len` loses the oldest values
+    /// permanently. Growing keeps everything. Either way the kept values
+    /// are rewritten from slot 0, so the call costs O(capacity), not O(1).
+    pub fn resize(env: Env, admin: Address, new_capacity: u32) -> Result<u32, RingError> {
+        admin.require_auth();
+        if admin != Self::admin(&env)? {
+            return Err(RingError::Unauthorized);
+        }
+        check_capacity(new_capacity)?;
+        let old_capacity = Self::capacity(env.clone())?;
+
+        // `kept` is newest first; the oldest kept value goes to slot 0.
+        let kept = Self::latest(env.clone(), new_capacity)?;
+        let len = kept.len();
+        for slot in 0..old_capacity.max(new_capacity) {
+            let key = DataKey::Slot(slot);
+            if slot < len {
+                let value = kept.get_unchecked(len - 1 - slot);
+                env.storage().persistent().set(&key, &value);
+            } else {
+                env.storage().persistent().remove(&key);
+            }
+        }
+        let discarded = Self::len(env.clone()) - len;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Capacity, &new_capacity);
+        env.storage().instance().set(&DataKey::Len, &len);
+        env.storage()
+            .instance()
+            .set(&DataKey::Head, &(len % new_capacity));
+        Ok(discarded)
+    }
+
+    pub fn capacity(env: Env) -> Result<u32, RingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Capacity)
+            .ok_or(RingError::NotInitialized)
+    }
+
+    /// Number of values held, at most the capacity.
+    pub fn len(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Len).unwrap_or(0)
+    }
+
+    pub fn is_empty(env: Env) -> bool {
+        Self::len(env) == 0
+    }
+}
+
+impl RingBuffer {
+    fn admin(env: &Env) -> Result<Address, RingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(RingError::NotInitialized)
+    }
+
+    fn head(env: &Env) -> u32 {
+        env.storage().instance().get(&DataKey::Head).unwrap_or(0)
+    }
+
+    fn slot(env: &Env, slot: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Slot(slot))
+            .unwrap_or(0)
+    }
+}
+
+fn check_capacity(capacity: u32) -> Result<(), RingError> {
+    if capacity == 0 || capacity > MAX_CAPACITY {
+        return Err(RingError::InvalidInput);
+    }
+    Ok(())
+}
+
+mod test;
@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, EnvTestConfig},
+    vec,
+};
+
+/// Largest allowed gap, in percent, between the cost of a push early in
+/// the buffer's history and one after many wrap-arounds.
+const TOLERANCE_PCT: u64 = 10;
+
+fn setup(capacity: u32) -> (Env, Address, RingBufferClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let client = RingBufferClient::new(&env, &env.register_contract(None, RingBuffer));
+    client.initialize(&admin, &capacity);
+    (env, admin, client)
+}
+
+fn push_range(client: &RingBufferClient, values: core::ops::RangeInclusive<i128>) {
+    for value in values {
+        client.push(&value);
+    }
+}
+
+#[test]
+fn test_wraps_after_capacity() {
+    let (env, _, client) = setup(5);
+    assert!(client.is_empty());
+    push_range(&client, 1..=3);
+    assert_eq!(client.len(), 3);
+    assert_eq!(client.latest(&5), vec![&env, 3, 2, 1]);
+
+    push_range(&client, 4..=7);
+    assert_eq!(client.len(), 5);
+    assert_eq!(client.capacity(), 5);
+    assert_eq!(client.latest(&5), vec![&env, 7, 6, 5, 4, 3]);
+
+    // The slots match the layout in the crate docs.
+    env.as_contract(&client.address, || {
+        let slots: std::vec::Vec<i128> = (0..5)
+            .map(|i| env.storage().persistent().get(&DataKey::Slot(i)).unwrap())
+            .collect();
+        assert_eq!(slots, [6, 7, 3, 4, 5]);
+        assert!(!env.storage().persistent().has(&DataKey::Slot(5)));
+    });
+}
+
+#[test]
+fn test_latest_ordering_and_bounds() {
+    let (env, _, client) = setup(8);
+    assert_eq!(client.latest(&3), Vec::new(&env));
+    push_range(&client, 1..=20);
+    assert_eq!(client.latest(&1), vec![&env, 20]);
+    assert_eq!(client.latest(&3), vec![&env, 20, 19, 18]);
+    assert_eq!(client.latest(&0), Vec::new(&env));
+    // More than len gives everything held.
+    assert_eq!(
+        client.latest(&100),
+        vec![&env, 20, 19, 18, 17, 16, 15, 14, 13]
+    );
+}
+
+#[test]
+fn test_resize_smaller_discards_oldest() {
+    let (env, admin, client) = setup(5);
+    push_range(&client, 1..=7);
+    assert_eq!(client.resize(&admin, &3), 2);
+    assert_eq!(client.capacity(), 3);
+    assert_eq!(client.len(), 3);
+    assert_eq!(client.latest(&10), vec![&env, 7, 6, 5]);
+
+    // Nothing is left behind in the dropped slots, and pushing carries on.
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::Slot(3)));
+        assert!(!env.storage().persistent().has(&DataKey::Slot(4)));
+    });
+    client.push(&8);
+    assert_eq!(client.latest(&10), vec![&env, 8, 7, 6]);
+}
+
+#[test]
+fn test_resize_larger_keeps_everything() {
+    let (env, admin, client) = setup(3);
+    push_range(&client, 1..=5);
+    assert_eq!(client.resize(&admin, &6), 0);
+    assert_eq!(client.latest(&10), vec![&env, 5, 4, 3]);
+    push_range(&client, 6..=9);
+    assert_eq!(client.len(), 6);
+    assert_eq!(client.latest(&10), vec![&env, 9, 8, 7, 6, 5, 4]);
+}
+
+#[test]
+fn test_admin_and_input_checks() {
+    let (env, admin, client) = setup(4);
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_resize(&other, &8),
+        Err(Ok(RingError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_resize(&admin, &0),
+        Err(Ok(RingError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_resize(&admin, &(MAX_CAPACITY + 1)),
+        Err(Ok(RingError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_initialize(&admin, &4),
+        Err(Ok(RingError::AlreadyInitialized))
+    );
+
+    let fresh = RingBufferClient::new(&env, &env.register_contract(None, RingBuffer));
+    assert_eq!(fresh.try_push(&1), Err(Ok(RingError::NotInitialized)));
+    assert_eq!(
+        fresh.try_initialize(&admin, &0),
+        Err(Ok(RingError::InvalidInput))
+    );
+}
+
+/// Pushes into a ring buffer it administers, as a TWAP or feed contract
+/// would. Being the direct caller, it authorizes `push` without a
+/// signature, so the benchmark measures the buffer rather than the test
+/// host's mocked signatures, whose nonces pile up with every call.
+#[contract]
+struct Feeder;
+
+#[contractimpl]
+impl Feeder {
+    pub fn feed(env: Env, ring: Address, value: i128) {
+        RingBufferClient::new(&env, &ring).push(&value);
+    }
+}
+
+#[test]
+fn bench_push_cost_is_flat() {
+    const CAPACITY: u32 = 16;
+    let mut env = Env::default();
+    // A snapshot of 500 pushes would only bloat `test_snapshots/`.
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.budget().reset_unlimited();
+    let feeder = FeederClient::new(&env, &env.register_contract(None, Feeder));
+    let ring = RingBufferClient::new(&env, &env.register_contract(None, RingBuffer));
+    ring.initialize(&feeder.address, &CAPACITY);
+
+    // CPU instructions for one push, after `history` more values.
+    let cost_after = |history: u32| {
+        for value in 0..history {
+            feeder.feed(&ring.address, &i128::from(value));
+        }
+        env.budget().reset_default();
+        feeder.feed(&ring.address, &0);
+        let cost = env.budget().cpu_instruction_cost();
+        env.budget().reset_unlimited();
+        cost
+    };
+
+    // Once full, the buffer holds the same entries however long its
+    // history, so a push after 30 wrap-arounds costs what one after the
+    // first did.
+    let early = cost_after(CAPACITY);
+    let late = cost_after(30 * CAPACITY);
+    std::println!("push after {CAPACITY} values: {early} cpu, after 30 more laps: {late} cpu");
+    assert!(
+        late.abs_diff(early) * 100 <= early * TOLERANCE_PCT,
+        "push cost moved from {early} to {late}"
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (RingError::NotInitialized, CommonError::NotInitialized),
+        (
+            RingError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (RingError::Unauthorized, CommonError::Unauthorized),
+        (RingError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+}
@@ -0,0 +1,103 @@
+//! # Leaderboard
+//!
+//! The top `BOARD_SIZE` scores, kept sorted in one instance-storage `Vec`.
+//! The board never grows past its size, so every read and write touches a
+//! bounded amount of data however many scores are submitted.
+//!
+//! - Each player has at most one entry: their best score.
+//! - Higher scores rank first. Equal scores rank in the order they reached
+//!   that score, so an earlier submission stays ahead.
+//! - A score that would rank below a full board is ignored; one that
+//!   makes the board pushes out the lowest entry.
+//!
+//! When the top entry changes, the contract publishes
+//! `("board", "newhigh", player)`, following the `(namespace, action,
+//! index)` topic layout of the `04-events` recipe.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+const NAMESPACE: Symbol = symbol_short!("board");
+
+/// Entries kept on the board.
+pub const BOARD_SIZE: u32 = 10;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// `Vec<(Address, u64)>`, best score first.
+    Board,
+}
+
+#[contract]
+pub struct Leaderboard;
+
+#[contractimpl]
+impl Leaderboard {
+    /// Record `score` for `player`. Returns the player's 1-based rank
+    /// afterwards, or `None` if they are not on the board.
+    ///
+    /// A score no better than the player's current entry changes nothing.
+    pub fn submit_score(env: Env, player: Address, score: u64) -> Option<u32> {
+        player.require_auth();
+        let mut board = Self::leaderboard(env.clone());
+        let top_before = board.first();
+
+        let mut current = None;
+        for (index, (holder, held)) in board.iter().enumerate() {
+            if holder == player {
+                current = Some((index as u32, held));
+                break;
+            }
+        }
+        if let Some((index, held)) = current {
+            if score <= held {
+                return Some(index + 1);
+            }
+            board.remove(index);
+        }
+
+        // After every entry with an equal or higher score.
+        let position = board
+            .iter()
+            .position(|(_, held)| held < score)
+            .map_or(board.len(), |position| position as u32);
+        if position >= BOARD_SIZE {
+            return None;
+        }
+        board.insert(position, (player.clone(), score));
+        if board.len() > BOARD_SIZE {
+            board.pop_back();
+        }
+        env.storage().instance().set(&DataKey::Board, &board);
+
+        let rank = position + 1;
+        env.events()
+            .publish((NAMESPACE, symbol_short!("score"), player), (score, rank));
+        if board.first() != top_before {
+            let (leader, best) = board.get_unchecked(0);
+            env.events()
+                .publish((NAMESPACE, symbol_short!("newhigh"), leader), best);
+        }
+        Some(rank)
+    }
+
+    /// The board, best score first.
+    pub fn leaderboard(env: Env) -> Vec<(Address, u64)> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Board)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// `player`'s 1-based rank, or `None` if they are not on the board.
+    pub fn rank_of(env: Env, player: Address) -> Option<u32> {
+        Self::leaderboard(env)
+            .iter()
+            .position(|(holder, _)| holder == player)
+            .map(|index| index as u32 + 1)
+    }
+}
+
+mod test;
@@ -0,0 +1,185 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{testutils::Address as _, vec, IntoVal};
+
+struct Setup {
+    env: Env,
+    board: LeaderboardClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let board = LeaderboardClient::new(&env, &env.register_contract(None, Leaderboard));
+    Setup { env, board }
+}
+
+fn players(env: &Env, n: usize) -> std::vec::Vec<Address> {
+    (0..n).map(|_| Address::generate(env)).collect()
+}
+
+#[test]
+fn test_first_score_on_empty_board() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    assert_eq!(s.board.leaderboard(), Vec::new(&s.env));
+
+    assert_eq!(s.board.submit_score(&alice, &50), Some(1));
+    assert_eq!(s.board.leaderboard(), vec![&s.env, (alice.clone(), 50)]);
+
+    // The first entry is also a new top spot.
+    let events = all_events_for(&s.env, &s.board.address);
+    assert_eq!(events.len(), 2);
+    let (topics, data) = events[1].clone();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("newhigh"), alice).into_val(&s.env)
+    );
+    let best: u64 = data.into_val(&s.env);
+    assert_eq!(best, 50);
+}
+
+#[test]
+fn test_mid_board_insertion_keeps_order() {
+    let s = setup();
+    let p = players(&s.env, 4);
+    s.board.submit_score(&p[0], &90);
+    s.board.submit_score(&p[1], &30);
+    s.board.submit_score(&p[2], &60);
+
+    assert_eq!(s.board.submit_score(&p[3], &45), Some(3));
+    assert_eq!(
+        s.board.leaderboard(),
+        vec![
+            &s.env,
+            (p[0].clone(), 90),
+            (p[2].clone(), 60),
+            (p[3].clone(), 45),
+            (p[1].clone(), 30),
+        ]
+    );
+
+    // The top spot did not change, so only the score event fired.
+    let (topics, data) = all_events_for(&s.env, &s.board.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("score"), p[3].clone()).into_val(&s.env)
+    );
+    let scored: (u64, u32) = data.into_val(&s.env);
+    assert_eq!(scored, (45, 3));
+}
+
+#[test]
+fn test_full_board_evicts_lowest() {
+    let s = setup();
+    let p = players(&s.env, BOARD_SIZE as usize + 2);
+    for (i, player) in p.iter().take(BOARD_SIZE as usize).enumerate() {
+        s.board.submit_score(player, &(10 * (i as u64 + 1)));
+    }
+    assert_eq!(s.board.leaderboard().len(), BOARD_SIZE);
+
+    // Too low to make the board: nothing changes.
+    let late = &p[BOARD_SIZE as usize];
+    assert_eq!(s.board.submit_score(late, &5), None);
+    assert_eq!(s.board.rank_of(late), None);
+
+    // Beating the lowest score pushes it out.
+    let board_before = s.board.leaderboard();
+    assert_eq!(s.board.submit_score(late, &15), Some(BOARD_SIZE));
+    let board = s.board.leaderboard();
+    assert_eq!(board.len(), BOARD_SIZE);
+    assert_eq!(board.last(), Some((late.clone(), 15)));
+    assert_eq!(s.board.rank_of(&p[0]), None);
+    assert_eq!(
+        board.slice(0..BOARD_SIZE - 1),
+        board_before.slice(0..BOARD_SIZE - 1)
+    );
+
+    // A new best takes the top spot and announces it.
+    let champion = &p[BOARD_SIZE as usize + 1];
+    assert_eq!(s.board.submit_score(champion, &1_000), Some(1));
+    let (topics, _) = all_events_for(&s.env, &s.board.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("newhigh"), champion.clone()).into_val(&s.env)
+    );
+}
+
+#[test]
+fn test_improvement_replaces_entry() {
+    let s = setup();
+    let p = players(&s.env, 3);
+    s.board.submit_score(&p[0], &80);
+    s.board.submit_score(&p[1], &60);
+    s.board.submit_score(&p[2], &40);
+
+    assert_eq!(s.board.submit_score(&p[2], &70), Some(2));
+    assert_eq!(
+        s.board.leaderboard(),
+        vec![
+            &s.env,
+            (p[0].clone(), 80),
+            (p[2].clone(), 70),
+            (p[1].clone(), 60),
+        ]
+    );
+
+    // A worse score keeps the best one and publishes nothing.
+    let published = all_events_for(&s.env, &s.board.address).len();
+    assert_eq!(s.board.submit_score(&p[2], &10), Some(2));
+    assert_eq!(all_events_for(&s.env, &s.board.address).len(), published);
+    assert_eq!(s.board.leaderboard().len(), 3);
+
+    // Overtaking the leader moves the top spot to them.
+    assert_eq!(s.board.submit_score(&p[2], &99), Some(1));
+    let (topics, data) = all_events_for(&s.env, &s.board.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (NAMESPACE, symbol_short!("newhigh"), p[2].clone()).into_val(&s.env)
+    );
+    let best: u64 = data.into_val(&s.env);
+    assert_eq!(best, 99);
+}
+
+#[test]
+fn test_ties_go_to_earlier_submission() {
+    let s = setup();
+    let p = players(&s.env, 3);
+    s.board.submit_score(&p[0], &50);
+    assert_eq!(s.board.submit_score(&p[1], &50), Some(2));
+
+    // Reaching 50 later, even by improving, ranks behind both.
+    s.board.submit_score(&p[2], &20);
+    assert_eq!(s.board.submit_score(&p[2], &50), Some(3));
+    assert_eq!(
+        s.board.leaderboard(),
+        vec![
+            &s.env,
+            (p[0].clone(), 50),
+            (p[1].clone(), 50),
+            (p[2].clone(), 50),
+        ]
+    );
+
+    // A tie at the top does not take the top spot.
+    assert!(all_events_for(&s.env, &s.board.address)
+        .iter()
+        .all(|(topics, _)| *topics
+            != (NAMESPACE, symbol_short!("newhigh"), p[2].clone()).into_val(&s.env)));
+}
+
+#[test]
+fn test_rank_of() {
+    let s = setup();
+    let p = players(&s.env, 3);
+    s.board.submit_score(&p[0], &10);
+    s.board.submit_score(&p[1], &30);
+
+    assert_eq!(s.board.rank_of(&p[1]), Some(1));
+    assert_eq!(s.board.rank_of(&p[0]), Some(2));
+    assert_eq!(s.board.rank_of(&p[2]), None);
+}
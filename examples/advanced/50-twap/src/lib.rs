@@ -0,0 +1,269 @@
+//! # TWAP Accumulator
+//!
+//! A time-weighted average price over any recent window, built from the
+//! reporter's `update_price` calls. Observations are kept in a ring buffer
+//! laid out as in the `48-ring-buffer` recipe, so the newest `capacity`
+//! observations are held and older ones are overwritten.
+//!
+//! Each observation carries a running total, `cumulative`: the sum of
+//! `price * seconds` from the first observation ever recorded up to its
+//! own timestamp. A price holds from its observation until the next one,
+//! so the total at any later time `t` is
+//!
+//! ```text
+//! cumulative(t) = obs.cumulative + obs.price * (t - obs.timestamp)
+//! ```
+//!
+//! where `obs` is the last observation at or before `t`. The average over
+//! `[now - window, now]` is then two lookups and a division:
+//!
+//! ```text
+//! twap = (cumulative(now) - cumulative(now - window)) / window
+//! ```
+//!
+//! A price spike that lasts a few seconds adds only `spike * seconds` to
+//! the total, so it barely moves an hour-long average.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol,
+};
+
+const NAMESPACE: Symbol = symbol_short!("twap");
+
+/// Largest capacity `initialize` accepts.
+pub const MAX_CAPACITY: u32 = 256;
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TwapError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// `update_price` from someone other than the reporter.
+    /// `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// The running total no longer fits in an `i128`.
+    /// `CommonError::Overflow`.
+    Overflow = 5,
+    /// A non-positive price, a zero window, or a capacity of zero or above
+    /// `MAX_CAPACITY`. `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// The window starts before the oldest observation held.
+    WindowTooLong = 100,
+    /// Fewer than two observations are held.
+    NotEnoughObservations = 101,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// The one address allowed to call `update_price`.
+    Reporter,
+    Capacity,
+    /// The slot the next new observation goes to. Once the buffer is full,
+    /// also the slot of the oldest observation.
+    Head,
+    /// Number of observations held, at most the capacity.
+    Len,
+    Slot(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Observation {
+    pub timestamp: u64,
+    pub price: i128,
+    /// Sum of `price * seconds` from the first observation up to
+    /// `timestamp`.
+    pub cumulative: i128,
+}
+
+#[contract]
+pub struct TwapContract;
+
+#[contractimpl]
+impl TwapContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        reporter: Address,
+        capacity: u32,
+    ) -> Result<(), TwapError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(TwapError::AlreadyInitialized);
+        }
+        if capacity == 0 || capacity > MAX_CAPACITY {
+            return Err(TwapError::InvalidInput);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Reporter, &reporter);
+        env.storage().instance().set(&DataKey::Capacity, &capacity);
+        Ok(())
+    }
+
+    /// Replace the reporter. Admin only.
+    pub fn set_reporter(env: Env, reporter: Address) -> Result<(), TwapError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(TwapError::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Reporter, &reporter);
+        Ok(())
+    }
+
+    /// Record `price` at the current ledger timestamp.
+    ///
+    /// A second update with the same timestamp replaces the price of the
+    /// first instead of adding a zero-length observation.
+    pub fn update_price(env: Env, reporter: Address, price: i128) -> Result<(), TwapError> {
+        reporter.require_auth();
+        let expected: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Reporter)
+            .ok_or(TwapError::NotInitialized)?;
+        if reporter != expected {
+            return Err(TwapError::Unauthorized);
+        }
+        if price <= 0 {
+            return Err(TwapError::InvalidInput);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        let capacity = Self::capacity(&env)?;
+        let head = Self::head(&env);
+        let len = Self::observation_count(env.clone());
+        let newest = (len > 0).then(|| Self::at(&env, capacity, head, 1));
+        match newest {
+            Some(newest) if newest.timestamp == timestamp => {
+                let slot = (head + capacity - 1) % capacity;
+                let replaced = Observation { price, ..newest };
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Slot(slot), &replaced);
+            }
+            _ => {
+                let cumulative = match newest {
+                    Some(newest) => cumulative_at(&newest, timestamp)?,
+                    None => 0,
+                };
+                let observation = Observation {
+                    timestamp,
+                    price,
+                    cumulative,
+                };
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Slot(head), &observation);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::Head, &((head + 1) % capacity));
+                if len < capacity {
+                    env.storage().instance().set(&DataKey::Len, &(len + 1));
+                }
+            }
+        }
+
+        env.events()
+            .publish((NAMESPACE, symbol_short!("price")), (timestamp, price));
+        Ok(())
+    }
+
+    /// The time-weighted average price over the last `window_secs`
+    /// seconds, rounded toward zero.
+    pub fn twap(env: Env, window_secs: u64) -> Result<i128, TwapError> {
+        if window_secs == 0 {
+            return Err(TwapError::InvalidInput);
+        }
+        let capacity = Self::capacity(&env)?;
+        let head = Self::head(&env);
+        let len = Self::observation_count(env.clone());
+        if len < 2 {
+            return Err(TwapError::NotEnoughObservations);
+        }
+
+        let now = env.ledger().timestamp();
+        let oldest = Self::at(&env, capacity, head, len);
+        let start = now
+            .checked_sub(window_secs)
+            .filter(|start| *start >= oldest.timestamp)
+            .ok_or(TwapError::WindowTooLong)?;
+
+        // Binary search, counting back from the newest, for the last
+        // observation at or before `start`. `back = len` (the oldest)
+        // always qualifies.
+        let (mut newer, mut older) = (1, len);
+        while newer < older {
+            let mid = newer + (older - newer) / 2;
+            if Self::at(&env, capacity, head, mid).timestamp <= start {
+                older = mid;
+            } else {
+                newer = mid + 1;
+            }
+        }
+        let at_start = Self::at(&env, capacity, head, older);
+
+        let total_now = cumulative_at(&Self::at(&env, capacity, head, 1), now)?;
+        let total_start = cumulative_at(&at_start, start)?;
+        let sum = total_now
+            .checked_sub(total_start)
+            .ok_or(TwapError::Overflow)?;
+        Ok(sum / i128::from(window_secs))
+    }
+
+    /// The newest observation, if any.
+    pub fn latest(env: Env) -> Result<Option<Observation>, TwapError> {
+        let capacity = Self::capacity(&env)?;
+        let len = Self::observation_count(env.clone());
+        Ok((len > 0).then(|| Self::at(&env, capacity, Self::head(&env), 1)))
+    }
+
+    /// Number of observations held, at most the capacity.
+    pub fn observation_count(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Len).unwrap_or(0)
+    }
+}
+
+impl TwapContract {
+    fn capacity(env: &Env) -> Result<u32, TwapError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Capacity)
+            .ok_or(TwapError::NotInitialized)
+    }
+
+    fn head(env: &Env) -> u32 {
+        env.storage().instance().get(&DataKey::Head).unwrap_or(0)
+    }
+
+    /// The observation `back` places before `head`: 1 is the newest.
+    fn at(env: &Env, capacity: u32, head: u32, back: u32) -> Observation {
+        let slot = (head + capacity - back) % capacity;
+        env.storage()
+            .persistent()
+            .get(&DataKey::Slot(slot))
+            .unwrap()
+    }
+}
+
+/// The running total at `timestamp`, with `observation`'s price holding
+/// since it was recorded.
+fn cumulative_at(observation: &Observation, timestamp: u64) -> Result<i128, TwapError> {
+    let elapsed = i128::from(timestamp - observation.timestamp);
+    observation
+        .price
+        .checked_mul(elapsed)
+        .and_then(|area| observation.cumulative.checked_add(area))
+        .ok_or(TwapError::Overflow)
+}
+
+mod test;
@@ -0,0 +1,185 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::{advance_time, set_timestamp};
+use soroban_sdk::testutils::Address as _;
+
+const HOUR: u64 = 3_600;
+const START: u64 = 1_000_000;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    reporter: Address,
+    twap: TwapContractClient<'static>,
+}
+
+fn setup(capacity: u32) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, START);
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let twap = TwapContractClient::new(&env, &env.register_contract(None, TwapContract));
+    twap.initialize(&admin, &reporter, &capacity);
+    Setup {
+        env,
+        admin,
+        reporter,
+        twap,
+    }
+}
+
+impl Setup {
+    /// Report `price`, then let `secs` pass.
+    fn report(&self, price: i128, secs: u64) {
+        self.twap.update_price(&self.reporter, &price);
+        advance_time(&self.env, secs);
+    }
+}
+
+#[test]
+fn test_constant_price_averages_to_itself() {
+    let s = setup(16);
+    for _ in 0..6 {
+        s.report(250, 600);
+    }
+    assert_eq!(s.twap.observation_count(), 6);
+    assert_eq!(s.twap.twap(&HOUR), 250);
+    assert_eq!(s.twap.twap(&60), 250);
+}
+
+#[test]
+fn test_step_change_is_weighted_by_duration() {
+    let s = setup(16);
+    s.report(100, 2_700);
+    s.report(200, 900);
+
+    // (100 * 2700 + 200 * 900) / 3600
+    assert_eq!(s.twap.twap(&HOUR), 125);
+    // Only the last 1800s: (100 * 900 + 200 * 900) / 1800
+    assert_eq!(s.twap.twap(&1_800), 150);
+    // Entirely after the step.
+    assert_eq!(s.twap.twap(&900), 200);
+
+    // A second update in the same ledger replaces the first.
+    s.twap.update_price(&s.reporter, &300);
+    s.twap.update_price(&s.reporter, &400);
+    assert_eq!(s.twap.observation_count(), 3);
+    assert_eq!(s.twap.latest().unwrap().price, 400);
+}
+
+#[test]
+fn test_window_beyond_history() {
+    let s = setup(16);
+    assert_eq!(
+        s.twap.try_twap(&60),
+        Err(Ok(TwapError::NotEnoughObservations))
+    );
+    s.report(100, 600);
+    assert_eq!(
+        s.twap.try_twap(&60),
+        Err(Ok(TwapError::NotEnoughObservations))
+    );
+
+    s.report(100, 600);
+    assert_eq!(s.twap.twap(&1_200), 100);
+    assert_eq!(s.twap.try_twap(&1_201), Err(Ok(TwapError::WindowTooLong)));
+    // Longer than the ledger's whole history.
+    assert_eq!(
+        s.twap.try_twap(&(START + 2 * HOUR)),
+        Err(Ok(TwapError::WindowTooLong))
+    );
+    assert_eq!(s.twap.try_twap(&0), Err(Ok(TwapError::InvalidInput)));
+}
+
+#[test]
+fn test_oldest_observations_are_pruned() {
+    let s = setup(4);
+    for price in [10, 20, 30, 40, 50, 60] {
+        s.report(price, 100);
+    }
+    assert_eq!(s.twap.observation_count(), 4);
+
+    // Only the observations of 30..=60 are left, the oldest from 400s ago.
+    assert_eq!(s.twap.twap(&400), 45);
+    assert_eq!(s.twap.try_twap(&401), Err(Ok(TwapError::WindowTooLong)));
+
+    // The overwritten slots still hold just four entries.
+    s.env.as_contract(&s.twap.address, || {
+        assert!(!s.env.storage().persistent().has(&DataKey::Slot(4)));
+    });
+}
+
+#[test]
+fn test_short_spike_barely_moves_hourly_twap() {
+    let s = setup(128);
+    // An hour of steady updates at 100, one a minute.
+    for _ in 0..60 {
+        s.report(100, 60);
+    }
+    // A tenfold spike held for one 5-second ledger, then back to 100.
+    s.report(1_000, 5);
+    s.report(100, 55);
+
+    assert_eq!(s.twap.latest().unwrap().price, 100);
+    // (100 * 3595 + 1000 * 5) / 3600 = 101.25
+    let twap = s.twap.twap(&HOUR);
+    assert_eq!(twap, 101);
+    // The average of the last minute moves more, which is why consumers
+    // should ask for a window much longer than a manipulator can hold.
+    assert_eq!(s.twap.twap(&60), 175);
+}
+
+#[test]
+fn test_reporter_and_input_checks() {
+    let s = setup(4);
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.twap.try_update_price(&other, &100),
+        Err(Ok(TwapError::Unauthorized))
+    );
+    assert_eq!(
+        s.twap.try_update_price(&s.reporter, &0),
+        Err(Ok(TwapError::InvalidInput))
+    );
+    assert_eq!(
+        s.twap.try_initialize(&s.admin, &s.reporter, &4),
+        Err(Ok(TwapError::AlreadyInitialized))
+    );
+
+    s.twap.set_reporter(&other);
+    s.twap.update_price(&other, &100);
+    assert_eq!(
+        s.twap.try_update_price(&s.reporter, &100),
+        Err(Ok(TwapError::Unauthorized))
+    );
+
+    let fresh = TwapContractClient::new(&s.env, &s.env.register_contract(None, TwapContract));
+    assert_eq!(fresh.try_twap(&60), Err(Ok(TwapError::NotInitialized)));
+    assert_eq!(
+        fresh.try_initialize(&s.admin, &s.reporter, &(MAX_CAPACITY + 1)),
+        Err(Ok(TwapError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (TwapError::NotInitialized, CommonError::NotInitialized),
+        (
+            TwapError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (TwapError::Unauthorized, CommonError::Unauthorized),
+        (TwapError::Overflow, CommonError::Overflow),
+        (TwapError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(TwapError::WindowTooLong as u32 >= recipes_errors::CONTRACT_MIN);
+}
@@ -0,0 +1,281 @@
+//! # Permit: Signed Approvals
+//!
+//! A token where an owner can grant an allowance by signing a `Permit`
+//! off-chain. Anyone (usually the spender) submits it with `permit`, and
+//! from then on the spender uses the ordinary `transfer_from`. The owner
+//! never sends a transaction or pays a fee.
+//!
+//! ## Owners are accounts
+//!
+//! The owner is named by an ed25519 public key, the same key behind a
+//! Stellar `G...` account. `account_of` turns the key into that account's
+//! `Address` by decoding the XDR of an account address, so balances and
+//! allowances stay keyed by `Address` like in any other token. An owner
+//! can still call `approve` or `transfer` directly from the account.
+//!
+//! ## What the signature covers
+//!
+//! The signed message is `payload(details)`: the XDR of this contract's
+//! address and the whole permit. Binding the contract address keeps a
+//! permit from being replayed against another token, and signing every
+//! field means the submitter cannot change the spender, the amount, the
+//! nonce or the expiry.
+//!
+//! Each owner has a permit nonce that starts at 0. A permit must carry
+//! the current nonce, which is bumped when it is used, so every permit
+//! works at most once. Like `approve`, a permit replaces the allowance
+//! rather than adding to it.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short,
+    xdr::{FromXdr, ToXdr},
+    Address, Bytes, BytesN, Env,
+};
+
+/// XDR of an `ScVal::Address(ScAddress::Account(..))` up to the ed25519
+/// key: the `ScVal` type (18, address), the address type (0, account) and
+/// the public key type (0, ed25519), each a big-endian `u32`.
+const ACCOUNT_XDR_PREFIX: [u8; 12] = [0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PermitError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// The ledger timestamp is past the permit's `expiry`.
+    /// `CommonError::Expired`.
+    Expired = 7,
+    /// A negative amount. `CommonError::InvalidInput`.
+    InvalidAmount = 8,
+    /// The permit's nonce is not the owner's current nonce.
+    BadNonce = 100,
+    InsufficientBalance = 101,
+    InsufficientAllowance = 102,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Balance(Address),
+    /// Amount `spender` may still move from `from`.
+    Allowance(AllowanceKey),
+    /// The nonce the owner's next permit must carry.
+    Nonce(Address),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AllowanceKey {
+    pub from: Address,
+    pub spender: Address,
+}
+
+/// An allowance the owner signs off-chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Permit {
+    pub spender: Address,
+    /// The allowance to set, replacing any current one.
+    pub amount: i128,
+    /// Must equal the owner's current nonce.
+    pub nonce: u64,
+    /// Last ledger timestamp at which the permit may be submitted.
+    pub expiry: u64,
+}
+
+#[contract]
+pub struct PermitToken;
+
+#[contractimpl]
+impl PermitToken {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PermitError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PermitError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Credit `amount` to `to`. Admin only.
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), PermitError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PermitError::NotInitialized)?;
+        admin.require_auth();
+        check_amount(amount)?;
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to), &(balance + amount));
+        Ok(())
+    }
+
+    /// The account address of the ed25519 key `owner_pubkey`.
+    pub fn account_of(env: Env, owner_pubkey: BytesN<32>) -> Address {
+        let mut xdr = Bytes::from_array(&env, &ACCOUNT_XDR_PREFIX);
+        xdr.append(&owner_pubkey.into());
+        Address::from_xdr(&env, &xdr).unwrap()
+    }
+
+    /// The bytes an owner signs to grant `details` on this token.
+    pub fn payload(env: Env, details: Permit) -> Bytes {
+        (env.current_contract_address(), details).to_xdr(&env)
+    }
+
+    /// Set the allowance `details` grants from the account of
+    /// `owner_pubkey`, the key that signed it.
+    ///
+    /// Anyone may submit it. A signature that does not match the key and
+    /// `payload(details)` fails the invocation. The permit must carry the
+    /// owner's current nonce and not be expired; the nonce is bumped.
+    pub fn permit(
+        env: Env,
+        owner_pubkey: BytesN<32>,
+        details: Permit,
+        signature: BytesN<64>,
+    ) -> Result<(), PermitError> {
+        let payload = Self::payload(env.clone(), details.clone());
+        env.crypto()
+            .ed25519_verify(&owner_pubkey, &payload, &signature);
+
+        let owner = Self::account_of(env.clone(), owner_pubkey);
+        let nonce = Self::nonce(env.clone(), owner.clone());
+        if details.nonce != nonce {
+            return Err(PermitError::BadNonce);
+        }
+        if env.ledger().timestamp() > details.expiry {
+            return Err(PermitError::Expired);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Nonce(owner.clone()), &(nonce + 1));
+        Self::set_allowance(&env, owner, details.spender, details.amount)
+    }
+
+    /// Let `spender` move up to `amount` of `from`'s balance, replacing
+    /// any current allowance. The on-chain path to the same result as
+    /// `permit`.
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+    ) -> Result<(), PermitError> {
+        from.require_auth();
+        Self::set_allowance(&env, from, spender, amount)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), PermitError> {
+        from.require_auth();
+        Self::move_balance(&env, from, to, amount)
+    }
+
+    /// Move `amount` from `from` to `to` out of `spender`'s allowance.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), PermitError> {
+        spender.require_auth();
+        check_amount(amount)?;
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            return Err(PermitError::InsufficientAllowance);
+        }
+        env.storage().persistent().set(
+            &DataKey::Allowance(AllowanceKey {
+                from: from.clone(),
+                spender,
+            }),
+            &(allowance - amount),
+        );
+        Self::move_balance(&env, from, to, amount)
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allowance(AllowanceKey { from, spender }))
+            .unwrap_or(0)
+    }
+
+    /// The nonce `owner`'s next permit must carry.
+    pub fn nonce(env: Env, owner: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Nonce(owner))
+            .unwrap_or(0)
+    }
+}
+
+impl PermitToken {
+    /// Publishes `("approve", from, spender)` with the amount.
+    fn set_allowance(
+        env: &Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+    ) -> Result<(), PermitError> {
+        check_amount(amount)?;
+        env.storage().persistent().set(
+            &DataKey::Allowance(AllowanceKey {
+                from: from.clone(),
+                spender: spender.clone(),
+            }),
+            &amount,
+        );
+        env.events()
+            .publish((symbol_short!("approve"), from, spender), amount);
+        Ok(())
+    }
+
+    /// Publishes `("transfer", from, to)` with the amount.
+    fn move_balance(
+        env: &Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), PermitError> {
+        check_amount(amount)?;
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            return Err(PermitError::InsufficientBalance);
+        }
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::Balance(from.clone()), &(from_balance - amount));
+        // Read after the debit so a transfer to oneself is a no-op.
+        let to_balance = Self::balance(env.clone(), to.clone());
+        storage.set(&DataKey::Balance(to.clone()), &(to_balance + amount));
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+        Ok(())
+    }
+}
+
+fn check_amount(amount: i128) -> Result<(), PermitError> {
+    if amount < 0 {
+        return Err(PermitError::InvalidAmount);
+    }
+    Ok(())
+}
+
+mod test;
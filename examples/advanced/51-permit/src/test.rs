@@ -0,0 +1,248 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use recipes_errors::CommonError;
+use recipes_testutils::set_timestamp;
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    xdr::{AccountId, PublicKey, ScAddress, Uint256},
+    IntoVal, Symbol, TryFromVal,
+};
+
+const EXPIRY: u64 = 10_000;
+
+struct Setup {
+    env: Env,
+    token: PermitTokenClient<'static>,
+    spender: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_timestamp(&env, 1_000);
+    let token = PermitTokenClient::new(&env, &env.register_contract(None, PermitToken));
+    token.initialize(&Address::generate(&env));
+    let spender = Address::generate(&env);
+    Setup {
+        env,
+        token,
+        spender,
+    }
+}
+
+/// An off-chain owner: an ed25519 key, its public key and the account
+/// address the token derives from it.
+struct Owner {
+    key: SigningKey,
+    public: BytesN<32>,
+    account: Address,
+}
+
+impl Setup {
+    fn owner(&self, seed: u8) -> Owner {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let public = BytesN::from_array(&self.env, &key.verifying_key().to_bytes());
+        let account = self.token.account_of(&public);
+        Owner {
+            key,
+            public,
+            account,
+        }
+    }
+
+    fn permit(&self, amount: i128, nonce: u64) -> Permit {
+        Permit {
+            spender: self.spender.clone(),
+            amount,
+            nonce,
+            expiry: EXPIRY,
+        }
+    }
+
+    fn sign(&self, owner: &Owner, details: &Permit) -> BytesN<64> {
+        sign(&self.env, &self.token.address, owner, details)
+    }
+}
+
+/// Sign `details` for the token at `contract` the way a wallet would,
+/// building the payload without asking the contract.
+fn sign(env: &Env, contract: &Address, owner: &Owner, details: &Permit) -> BytesN<64> {
+    let payload = (contract.clone(), details.clone()).to_xdr(env);
+    let mut msg = std::vec![0u8; payload.len() as usize];
+    payload.copy_into_slice(&mut msg);
+    BytesN::from_array(env, &owner.key.sign(&msg).to_bytes())
+}
+
+#[test]
+fn test_permit_then_transfer_from() {
+    let s = setup();
+    let owner = s.owner(1);
+    let bob = Address::generate(&s.env);
+    s.token.mint(&owner.account, &100);
+
+    // The derived address is the owner's Stellar account.
+    let account = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+        owner.key.verifying_key().to_bytes(),
+    ))));
+    assert_eq!(
+        owner.account,
+        Address::try_from_val(&s.env, &account).unwrap()
+    );
+
+    let details = s.permit(40, 0);
+    s.token
+        .permit(&owner.public, &details, &s.sign(&owner, &details));
+    // Nobody had to authorize the permit: the signature is the authority.
+    assert_eq!(s.env.auths(), std::vec![]);
+    assert_eq!(s.token.nonce(&owner.account), 1);
+
+    s.token.transfer_from(&s.spender, &owner.account, &bob, &25);
+    assert_eq!(
+        s.env.auths(),
+        std::vec![(
+            s.spender.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    s.token.address.clone(),
+                    Symbol::new(&s.env, "transfer_from"),
+                    (
+                        s.spender.clone(),
+                        owner.account.clone(),
+                        bob.clone(),
+                        25_i128
+                    )
+                        .into_val(&s.env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(s.token.balance(&owner.account), 75);
+    assert_eq!(s.token.balance(&bob), 25);
+    assert_eq!(s.token.allowance(&owner.account, &s.spender), 15);
+    assert_eq!(
+        s.token
+            .try_transfer_from(&s.spender, &owner.account, &bob, &16),
+        Err(Ok(PermitError::InsufficientAllowance))
+    );
+}
+
+#[test]
+fn test_replayed_permit_rejected() {
+    let s = setup();
+    let owner = s.owner(1);
+    s.token.mint(&owner.account, &100);
+    let details = s.permit(40, 0);
+    let sig = s.sign(&owner, &details);
+    s.token.permit(&owner.public, &details, &sig);
+    s.token
+        .transfer_from(&s.spender, &owner.account, &s.spender, &40);
+
+    // Replaying would top the spent allowance back up.
+    assert_eq!(
+        s.token.try_permit(&owner.public, &details, &sig),
+        Err(Ok(PermitError::BadNonce))
+    );
+    assert_eq!(s.token.allowance(&owner.account, &s.spender), 0);
+
+    // A permit signed ahead of its turn is refused too.
+    let early = s.permit(10, 2);
+    assert_eq!(
+        s.token
+            .try_permit(&owner.public, &early, &s.sign(&owner, &early)),
+        Err(Ok(PermitError::BadNonce))
+    );
+}
+
+#[test]
+fn test_expired_permit_rejected() {
+    let s = setup();
+    let owner = s.owner(1);
+    let details = s.permit(40, 0);
+    let sig = s.sign(&owner, &details);
+
+    set_timestamp(&s.env, EXPIRY + 1);
+    assert_eq!(
+        s.token.try_permit(&owner.public, &details, &sig),
+        Err(Ok(PermitError::Expired))
+    );
+    assert_eq!(s.token.nonce(&owner.account), 0);
+
+    // `expiry` itself is still in time.
+    set_timestamp(&s.env, EXPIRY);
+    s.token.permit(&owner.public, &details, &sig);
+    assert_eq!(s.token.allowance(&owner.account, &s.spender), 40);
+}
+
+#[test]
+fn test_tampered_permit_rejected() {
+    let s = setup();
+    let (owner, mallory) = (s.owner(1), s.owner(2));
+    let details = s.permit(40, 0);
+    let sig = s.sign(&owner, &details);
+
+    let raised = s.permit(4_000, 0);
+    assert!(s.token.try_permit(&owner.public, &raised, &sig).is_err());
+    let redirected = Permit {
+        spender: mallory.account.clone(),
+        ..details.clone()
+    };
+    assert!(s
+        .token
+        .try_permit(&owner.public, &redirected, &sig)
+        .is_err());
+    // Mallory's own signature does not speak for the owner.
+    let forged = s.sign(&mallory, &raised);
+    assert!(s.token.try_permit(&owner.public, &raised, &forged).is_err());
+    // Nor does a signature meant for another token.
+    let elsewhere = sign(&s.env, &Address::generate(&s.env), &owner, &details);
+    assert!(s
+        .token
+        .try_permit(&owner.public, &details, &elsewhere)
+        .is_err());
+
+    assert_eq!(s.token.allowance(&owner.account, &s.spender), 0);
+    assert_eq!(s.token.allowance(&owner.account, &mallory.account), 0);
+    assert_eq!(s.token.nonce(&owner.account), 0);
+}
+
+#[test]
+fn test_allowance_is_exactly_the_signed_amount() {
+    let s = setup();
+    let owner = s.owner(1);
+
+    // A permit replaces an allowance set on-chain, rather than adding.
+    s.token.approve(&owner.account, &s.spender, &100);
+    let details = s.permit(30, 0);
+    s.token
+        .permit(&owner.public, &details, &s.sign(&owner, &details));
+    assert_eq!(s.token.allowance(&owner.account, &s.spender), 30);
+
+    // The next permit, with the next nonce, replaces it again.
+    let details = s.permit(7, 1);
+    s.token
+        .permit(&owner.public, &details, &s.sign(&owner, &details));
+    assert_eq!(s.token.allowance(&owner.account, &s.spender), 7);
+    assert_eq!(s.token.nonce(&owner.account), 2);
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (PermitError::NotInitialized, CommonError::NotInitialized),
+        (
+            PermitError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (PermitError::Expired, CommonError::Expired),
+        (PermitError::InvalidAmount, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(PermitError::BadNonce as u32 >= recipes_errors::CONTRACT_MIN);
+}
@@ -0,0 +1,285 @@
+//! # Multi-Token
+//!
+//! Many fungible tokens in one contract, in the style of ERC-1155. A token
+//! is a `u64` id, and every balance is keyed by `(holder, id)`, so ids are
+//! created by minting them and cost nothing up front.
+//!
+//! - `batch_transfer` moves several ids in one call. It is all or nothing:
+//!   if any leg fails, the contract returns the error and the host rolls
+//!   back every leg already written.
+//! - An operator approved with `set_approval_for_all` may move any id the
+//!   owner holds, through `transfer_from` and `batch_transfer_from`.
+//! - Each id has its own total supply, raised by `mint` and lowered by
+//!   `burn`.
+//!
+//! Events carry the id as a `u64` topic, so an indexer can filter one id
+//! without decoding the data.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Vec,
+};
+
+/// Most legs one batch may carry.
+pub const MAX_BATCH: u32 = 20;
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MultiTokenError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// `mint` by someone other than the admin, or a transfer by someone
+    /// who is neither the owner nor an approved operator.
+    /// `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// A supply or balance would pass `i128::MAX`. `CommonError::Overflow`.
+    Overflow = 5,
+    /// A non-positive amount, or an empty batch or one over `MAX_BATCH`.
+    /// `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    InsufficientBalance = 100,
+    /// `ids` and `amounts` have different lengths.
+    LengthMismatch = 101,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Balance of `(holder, id)`.
+    Balance(Address, u64),
+    /// Total supply of an id.
+    Supply(u64),
+    /// Present while `(owner, operator)` is approved for all ids.
+    Operator(Address, Address),
+}
+
+#[contract]
+pub struct MultiToken;
+
+#[contractimpl]
+impl MultiToken {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), MultiTokenError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(MultiTokenError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Create `amount` of token `id` in `to`'s balance. Admin only.
+    pub fn mint(
+        env: Env,
+        admin: Address,
+        to: Address,
+        id: u64,
+        amount: i128,
+    ) -> Result<(), MultiTokenError> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(MultiTokenError::NotInitialized)?;
+        if admin != stored {
+            return Err(MultiTokenError::Unauthorized);
+        }
+        check_amount(amount)?;
+        let supply = Self::total_supply(env.clone(), id)
+            .checked_add(amount)
+            .ok_or(MultiTokenError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Supply(id), &supply);
+        credit(&env, &to, id, amount)?;
+        env.events()
+            .publish((symbol_short!("mint"), to, id), amount);
+        Ok(())
+    }
+
+    /// Destroy `amount` of token `id` from `from`'s balance.
+    pub fn burn(env: Env, from: Address, id: u64, amount: i128) -> Result<(), MultiTokenError> {
+        from.require_auth();
+        check_amount(amount)?;
+        debit(&env, &from, id, amount)?;
+        let supply = Self::total_supply(env.clone(), id) - amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Supply(id), &supply);
+        env.events()
+            .publish((symbol_short!("burn"), from, id), amount);
+        Ok(())
+    }
+
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        id: u64,
+        amount: i128,
+    ) -> Result<(), MultiTokenError> {
+        from.require_auth();
+        move_balance(&env, &from, &to, id, amount)
+    }
+
+    /// Move several ids from `from` to `to`, `amounts[i]` of `ids[i]`.
+    /// Nothing moves unless every leg can.
+    pub fn batch_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        ids: Vec<u64>,
+        amounts: Vec<i128>,
+    ) -> Result<(), MultiTokenError> {
+        from.require_auth();
+        move_batch(&env, &from, &to, &ids, &amounts)
+    }
+
+    /// `transfer` by `spender`, who must be `from` or one of its operators.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        id: u64,
+        amount: i128,
+    ) -> Result<(), MultiTokenError> {
+        spender.require_auth();
+        Self::check_spender(&env, &spender, &from)?;
+        move_balance(&env, &from, &to, id, amount)
+    }
+
+    /// `batch_transfer` by `spender`, who must be `from` or one of its
+    /// operators.
+    pub fn batch_transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        ids: Vec<u64>,
+        amounts: Vec<i128>,
+    ) -> Result<(), MultiTokenError> {
+        spender.require_auth();
+        Self::check_spender(&env, &spender, &from)?;
+        move_batch(&env, &from, &to, &ids, &amounts)
+    }
+
+    /// Let `operator` move any of `owner`'s ids, or stop it.
+    pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        env.events()
+            .publish((symbol_short!("approval"), owner, operator), approved);
+    }
+
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Operator(owner, operator))
+    }
+
+    pub fn balance_of(env: Env, who: Address, id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(who, id))
+            .unwrap_or(0)
+    }
+
+    pub fn total_supply(env: Env, id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Supply(id))
+            .unwrap_or(0)
+    }
+}
+
+impl MultiToken {
+    fn check_spender(env: &Env, spender: &Address, from: &Address) -> Result<(), MultiTokenError> {
+        if spender == from || Self::is_approved_for_all(env.clone(), from.clone(), spender.clone())
+        {
+            Ok(())
+        } else {
+            Err(MultiTokenError::Unauthorized)
+        }
+    }
+}
+
+fn check_amount(amount: i128) -> Result<(), MultiTokenError> {
+    if amount <= 0 {
+        return Err(MultiTokenError::InvalidInput);
+    }
+    Ok(())
+}
+
+fn credit(env: &Env, to: &Address, id: u64, amount: i128) -> Result<(), MultiTokenError> {
+    let balance = MultiToken::balance_of(env.clone(), to.clone(), id)
+        .checked_add(amount)
+        .ok_or(MultiTokenError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(to.clone(), id), &balance);
+    Ok(())
+}
+
+fn debit(env: &Env, from: &Address, id: u64, amount: i128) -> Result<(), MultiTokenError> {
+    let balance = MultiToken::balance_of(env.clone(), from.clone(), id);
+    if balance < amount {
+        return Err(MultiTokenError::InsufficientBalance);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(from.clone(), id), &(balance - amount));
+    Ok(())
+}
+
+/// Publishes `("transfer", from, to, id)` with the amount.
+fn move_balance(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    id: u64,
+    amount: i128,
+) -> Result<(), MultiTokenError> {
+    check_amount(amount)?;
+    debit(env, from, id, amount)?;
+    credit(env, to, id, amount)?;
+    env.events().publish(
+        (symbol_short!("transfer"), from.clone(), to.clone(), id),
+        amount,
+    );
+    Ok(())
+}
+
+/// One `move_balance` per leg. A failing leg returns its error, which
+/// rolls back the legs before it along with the rest of the invocation.
+fn move_batch(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    ids: &Vec<u64>,
+    amounts: &Vec<i128>,
+) -> Result<(), MultiTokenError> {
+    if ids.len() != amounts.len() {
+        return Err(MultiTokenError::LengthMismatch);
+    }
+    if ids.is_empty() || ids.len() > MAX_BATCH {
+        return Err(MultiTokenError::InvalidInput);
+    }
+    for (id, amount) in ids.iter().zip(amounts.iter()) {
+        move_balance(env, from, to, id, amount)?;
+    }
+    Ok(())
+}
+
+mod test;
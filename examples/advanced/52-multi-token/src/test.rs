@@ -0,0 +1,247 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{testutils::Address as _, vec, IntoVal, Val};
+
+const SWORD: u64 = 1;
+const GOLD: u64 = 2;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+    token: MultiTokenClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let token = MultiTokenClient::new(&env, &env.register_contract(None, MultiToken));
+    let admin = Address::generate(&env);
+    token.initialize(&admin);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token.mint(&admin, &alice, &SWORD, &3);
+    token.mint(&admin, &alice, &GOLD, &100);
+    Setup {
+        env,
+        admin,
+        alice,
+        bob,
+        token,
+    }
+}
+
+#[test]
+fn test_balances_are_independent_per_id() {
+    let s = setup();
+    s.token.transfer(&s.alice, &s.bob, &GOLD, &40);
+
+    assert_eq!(s.token.balance_of(&s.alice, &GOLD), 60);
+    assert_eq!(s.token.balance_of(&s.bob, &GOLD), 40);
+    assert_eq!(s.token.balance_of(&s.alice, &SWORD), 3);
+    assert_eq!(s.token.balance_of(&s.bob, &SWORD), 0);
+
+    // The id is a plain `u64` topic.
+    let (topics, data) = all_events_for(&s.env, &s.token.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("transfer"),
+            s.alice.clone(),
+            s.bob.clone(),
+            GOLD
+        )
+            .into_val(&s.env)
+    );
+    let amount: i128 = data.into_val(&s.env);
+    assert_eq!(amount, 40);
+
+    assert_eq!(
+        s.token.try_transfer(&s.alice, &s.bob, &SWORD, &4),
+        Err(Ok(MultiTokenError::InsufficientBalance))
+    );
+    assert_eq!(
+        s.token.try_transfer(&s.alice, &s.bob, &SWORD, &0),
+        Err(Ok(MultiTokenError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_batch_transfer_is_atomic() {
+    let s = setup();
+    // The first leg fits, the second does not: neither happens.
+    assert_eq!(
+        s.token.try_batch_transfer(
+            &s.alice,
+            &s.bob,
+            &vec![&s.env, GOLD, SWORD],
+            &vec![&s.env, 10, 4],
+        ),
+        Err(Ok(MultiTokenError::InsufficientBalance))
+    );
+    assert_eq!(s.token.balance_of(&s.alice, &GOLD), 100);
+    assert_eq!(s.token.balance_of(&s.bob, &GOLD), 0);
+
+    s.token.batch_transfer(
+        &s.alice,
+        &s.bob,
+        &vec![&s.env, GOLD, SWORD],
+        &vec![&s.env, 10, 3],
+    );
+    assert_eq!(s.token.balance_of(&s.alice, &GOLD), 90);
+    assert_eq!(s.token.balance_of(&s.bob, &GOLD), 10);
+    assert_eq!(s.token.balance_of(&s.alice, &SWORD), 0);
+    assert_eq!(s.token.balance_of(&s.bob, &SWORD), 3);
+    // One transfer event per leg, in order.
+    let events = all_events_for(&s.env, &s.token.address);
+    let legs: std::vec::Vec<(Vec<Val>, i128)> = events[events.len() - 2..]
+        .iter()
+        .map(|(topics, data)| (topics.clone(), data.into_val(&s.env)))
+        .collect();
+    assert_eq!(
+        legs,
+        [
+            (
+                (
+                    symbol_short!("transfer"),
+                    s.alice.clone(),
+                    s.bob.clone(),
+                    GOLD
+                )
+                    .into_val(&s.env),
+                10
+            ),
+            (
+                (
+                    symbol_short!("transfer"),
+                    s.alice.clone(),
+                    s.bob.clone(),
+                    SWORD
+                )
+                    .into_val(&s.env),
+                3
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_operator_moves_any_id() {
+    let s = setup();
+    let market = Address::generate(&s.env);
+    assert_eq!(
+        s.token
+            .try_transfer_from(&market, &s.alice, &s.bob, &GOLD, &1),
+        Err(Ok(MultiTokenError::Unauthorized))
+    );
+
+    s.token.set_approval_for_all(&s.alice, &market, &true);
+    assert!(s.token.is_approved_for_all(&s.alice, &market));
+    s.token.transfer_from(&market, &s.alice, &s.bob, &GOLD, &25);
+    s.token.batch_transfer_from(
+        &market,
+        &s.alice,
+        &s.bob,
+        &vec![&s.env, SWORD, GOLD],
+        &vec![&s.env, 1, 5],
+    );
+    assert_eq!(s.token.balance_of(&s.bob, &GOLD), 30);
+    assert_eq!(s.token.balance_of(&s.bob, &SWORD), 1);
+
+    // Approval is per owner: the operator cannot touch Bob's tokens.
+    assert_eq!(
+        s.token
+            .try_transfer_from(&market, &s.bob, &market, &GOLD, &1),
+        Err(Ok(MultiTokenError::Unauthorized))
+    );
+
+    s.token.set_approval_for_all(&s.alice, &market, &false);
+    assert_eq!(
+        s.token
+            .try_transfer_from(&market, &s.alice, &s.bob, &GOLD, &1),
+        Err(Ok(MultiTokenError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_supply_tracked_per_id() {
+    let s = setup();
+    s.token.mint(&s.admin, &s.bob, &GOLD, &50);
+    assert_eq!(s.token.total_supply(&GOLD), 150);
+    assert_eq!(s.token.total_supply(&SWORD), 3);
+    assert_eq!(s.token.total_supply(&99), 0);
+
+    // Transfers leave supply alone; burns lower only their own id.
+    s.token.transfer(&s.alice, &s.bob, &GOLD, &20);
+    s.token.burn(&s.bob, &GOLD, &70);
+    assert_eq!(s.token.total_supply(&GOLD), 80);
+    assert_eq!(s.token.total_supply(&SWORD), 3);
+    assert_eq!(
+        s.token.try_burn(&s.bob, &GOLD, &1),
+        Err(Ok(MultiTokenError::InsufficientBalance))
+    );
+
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.token.try_mint(&other, &other, &GOLD, &1),
+        Err(Ok(MultiTokenError::Unauthorized))
+    );
+    assert_eq!(
+        s.token.try_mint(&s.admin, &s.bob, &GOLD, &i128::MAX),
+        Err(Ok(MultiTokenError::Overflow))
+    );
+}
+
+#[test]
+fn test_mismatched_lengths_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.token.try_batch_transfer(
+            &s.alice,
+            &s.bob,
+            &vec![&s.env, GOLD, SWORD],
+            &vec![&s.env, 10],
+        ),
+        Err(Ok(MultiTokenError::LengthMismatch))
+    );
+    assert_eq!(
+        s.token
+            .try_batch_transfer(&s.alice, &s.bob, &Vec::new(&s.env), &Vec::new(&s.env)),
+        Err(Ok(MultiTokenError::InvalidInput))
+    );
+    let mut ids = Vec::new(&s.env);
+    let mut amounts = Vec::new(&s.env);
+    for _ in 0..=MAX_BATCH {
+        ids.push_back(GOLD);
+        amounts.push_back(1_i128);
+    }
+    assert_eq!(
+        s.token.try_batch_transfer(&s.alice, &s.bob, &ids, &amounts),
+        Err(Ok(MultiTokenError::InvalidInput))
+    );
+    assert_eq!(s.token.balance_of(&s.alice, &GOLD), 100);
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (MultiTokenError::NotInitialized, CommonError::NotInitialized),
+        (
+            MultiTokenError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (MultiTokenError::Unauthorized, CommonError::Unauthorized),
+        (MultiTokenError::Overflow, CommonError::Overflow),
+        (MultiTokenError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(MultiTokenError::InsufficientBalance as u32 >= recipes_errors::CONTRACT_MIN);
+}
@@ -0,0 +1,235 @@
+//! # Sponsored Claim
+//!
+//! A user claims tokens without paying the protocol fee: a sponsor pays it
+//! for them, in the same invocation.
+//!
+//! This is the on-chain half of a relayer flow. A wallet or dApp backend
+//! acts as the sponsor. The user signs their leg and hands it to the
+//! relayer, the sponsor signs theirs, and the relayer submits one
+//! transaction carrying both authorizations (and pays the network fee as
+//! the source account). Each party signs only what concerns them, with
+//! `require_auth_for_args`:
+//!
+//! - the sponsor signs `(user, fee)`: "I pay `fee` to the protocol for this
+//!   user's claim". The amount is not in it, so whatever the user claims,
+//!   the sponsor pays exactly the fee it signed. If the admin raises the
+//!   fee, the old signature no longer matches.
+//! - the user signs `(amount,)`: "I take `amount` of my entitlement".
+//!
+//! Both legs are plain token transfers in one invocation. If either fails,
+//! the host rolls back the other, so the protocol is never paid for a claim
+//! that did not happen, and no claim goes through unpaid.
+//!
+//! ## Authorizing in tests
+//!
+//! `mock_all_auths` would pass even if the contract checked the wrong
+//! arguments. The tests instead build each party's authorization tree with
+//! `mock_auths`, as a wallet would sign it. The sponsor's tree includes the
+//! token transfer its fee makes:
+//!
+//! ```ignore
+//! env.mock_auths(&[
+//!     MockAuth {
+//!         address: &sponsor,
+//!         invoke: &MockAuthInvoke {
+//!             contract: &claim_contract,
+//!             fn_name: "sponsored_claim",
+//!             args: (user.clone(), FEE).into_val(&env),
+//!             sub_invokes: &[MockAuthInvoke {
+//!                 contract: &token,
+//!                 fn_name: "transfer",
+//!                 args: (sponsor.clone(), protocol.clone(), FEE).into_val(&env),
+//!                 sub_invokes: &[],
+//!             }],
+//!         },
+//!     },
+//!     MockAuth {
+//!         address: &user,
+//!         invoke: &MockAuthInvoke {
+//!             contract: &claim_contract,
+//!             fn_name: "sponsored_claim",
+//!             args: (amount,).into_val(&env),
+//!             sub_invokes: &[],
+//!         },
+//!     },
+//! ]);
+//! ```
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, IntoVal,
+};
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ClaimError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// `fund` or `set_fee` by someone other than the admin.
+    /// `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// An entitlement would pass `i128::MAX`. `CommonError::Overflow`.
+    Overflow = 5,
+    /// A non-positive amount or a negative fee. `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// The user is entitled to less than the amount claimed.
+    ExceedsEntitlement = 100,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub token: Address,
+    /// Receives the fee for every claim.
+    pub protocol: Address,
+    pub fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    /// What a user may still claim.
+    Entitlement(Address),
+}
+
+#[contract]
+pub struct SponsoredClaim;
+
+#[contractimpl]
+impl SponsoredClaim {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        protocol: Address,
+        fee: i128,
+    ) -> Result<(), ClaimError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(ClaimError::AlreadyInitialized);
+        }
+        if fee < 0 {
+            return Err(ClaimError::InvalidInput);
+        }
+        let config = Config {
+            admin,
+            token,
+            protocol,
+            fee,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Change the fee. Sponsor signatures over the old fee stop matching.
+    pub fn set_fee(env: Env, admin: Address, fee: i128) -> Result<(), ClaimError> {
+        let mut config = Self::admin_config(&env, &admin)?;
+        if fee < 0 {
+            return Err(ClaimError::InvalidInput);
+        }
+        config.fee = fee;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Pull `amount` from the admin and add it to `user`'s entitlement.
+    pub fn fund(env: Env, admin: Address, user: Address, amount: i128) -> Result<(), ClaimError> {
+        let config = Self::admin_config(&env, &admin)?;
+        if amount <= 0 {
+            return Err(ClaimError::InvalidInput);
+        }
+        let entitlement = Self::entitlement(env.clone(), user.clone())
+            .checked_add(amount)
+            .ok_or(ClaimError::Overflow)?;
+        token::Client::new(&env, &config.token).transfer(
+            &admin,
+            &env.current_contract_address(),
+            &amount,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::Entitlement(user), &entitlement);
+        Ok(())
+    }
+
+    /// Pay `amount` of `user`'s entitlement to `user`, with `sponsor`
+    /// paying the protocol fee.
+    ///
+    /// `sponsor` authorizes `(user, fee)` and `user` authorizes
+    /// `(amount,)`.
+    ///
+    /// Emits `("claim", "sponsor", user)` with `(sponsor, amount, fee)`.
+    pub fn sponsored_claim(
+        env: Env,
+        sponsor: Address,
+        user: Address,
+        amount: i128,
+    ) -> Result<(), ClaimError> {
+        let config = Self::config(&env)?;
+        if amount <= 0 {
+            return Err(ClaimError::InvalidInput);
+        }
+        sponsor.require_auth_for_args((user.clone(), config.fee).into_val(&env));
+        user.require_auth_for_args((amount,).into_val(&env));
+
+        let entitlement = Self::entitlement(env.clone(), user.clone());
+        if amount > entitlement {
+            return Err(ClaimError::ExceedsEntitlement);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Entitlement(user.clone()), &(entitlement - amount));
+
+        // Fee leg, then claim leg. A failure in either aborts both.
+        let token = token::Client::new(&env, &config.token);
+        if config.fee > 0 {
+            token.transfer(&sponsor, &config.protocol, &config.fee);
+        }
+        token.transfer(&env.current_contract_address(), &user, &amount);
+
+        env.events().publish(
+            (symbol_short!("claim"), symbol_short!("sponsor"), user),
+            (sponsor, amount, config.fee),
+        );
+        Ok(())
+    }
+
+    /// What `user` may still claim.
+    pub fn entitlement(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Entitlement(user))
+            .unwrap_or(0)
+    }
+
+    pub fn get_config(env: Env) -> Result<Config, ClaimError> {
+        Self::config(&env)
+    }
+}
+
+impl SponsoredClaim {
+    fn config(env: &Env) -> Result<Config, ClaimError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(ClaimError::NotInitialized)
+    }
+
+    fn admin_config(env: &Env, admin: &Address) -> Result<Config, ClaimError> {
+        admin.require_auth();
+        let config = Self::config(env)?;
+        if *admin != config.admin {
+            return Err(ClaimError::Unauthorized);
+        }
+        Ok(config)
+    }
+}
+
+mod test;
@@ -0,0 +1,217 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    token::{StellarAssetClient, TokenClient},
+};
+
+const FEE: i128 = 5;
+const FUNDED: i128 = 500;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    sponsor: Address,
+    user: Address,
+    protocol: Address,
+    token: TokenClient<'static>,
+    claim: SponsoredClaimClient<'static>,
+}
+
+/// Funds `user` with an entitlement of `FUNDED`. Setup runs under
+/// `mock_all_auths`; each test then authorizes its claim explicitly.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let token = TokenClient::new(&env, &asset.address());
+    let admin = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+    let user = Address::generate(&env);
+    let protocol = Address::generate(&env);
+    let minter = StellarAssetClient::new(&env, &asset.address());
+    minter.mint(&admin, &FUNDED);
+    minter.mint(&sponsor, &100);
+
+    let claim = SponsoredClaimClient::new(&env, &env.register_contract(None, SponsoredClaim));
+    claim.initialize(&admin, &token.address, &protocol, &FEE);
+    claim.fund(&admin, &user, &FUNDED);
+    Setup {
+        env,
+        admin,
+        sponsor,
+        user,
+        protocol,
+        token,
+        claim,
+    }
+}
+
+/// Authorize the sponsor's leg for `fee` and the user's leg for `amount`,
+/// leaving out whichever is `None`.
+fn authorize(s: &Setup, fee: Option<i128>, amount: Option<i128>) {
+    let fee_transfer = [MockAuthInvoke {
+        contract: &s.token.address,
+        fn_name: "transfer",
+        args: (s.sponsor.clone(), s.protocol.clone(), fee.unwrap_or(0)).into_val(&s.env),
+        sub_invokes: &[],
+    }];
+    let sponsor_leg = MockAuthInvoke {
+        contract: &s.claim.address,
+        fn_name: "sponsored_claim",
+        args: (s.user.clone(), fee.unwrap_or(0)).into_val(&s.env),
+        sub_invokes: &fee_transfer,
+    };
+    let user_leg = MockAuthInvoke {
+        contract: &s.claim.address,
+        fn_name: "sponsored_claim",
+        args: (amount.unwrap_or(0),).into_val(&s.env),
+        sub_invokes: &[],
+    };
+    let mut auths = std::vec::Vec::new();
+    if fee.is_some() {
+        auths.push(MockAuth {
+            address: &s.sponsor,
+            invoke: &sponsor_leg,
+        });
+    }
+    if amount.is_some() {
+        auths.push(MockAuth {
+            address: &s.user,
+            invoke: &user_leg,
+        });
+    }
+    s.env.mock_auths(&auths);
+}
+
+fn balances(s: &Setup) -> [i128; 4] {
+    [
+        s.token.balance(&s.sponsor),
+        s.token.balance(&s.user),
+        s.token.balance(&s.protocol),
+        s.token.balance(&s.claim.address),
+    ]
+}
+
+#[test]
+fn test_sponsored_claim_settles_both_legs() {
+    let s = setup();
+    authorize(&s, Some(FEE), Some(200));
+    s.claim.sponsored_claim(&s.sponsor, &s.user, &200);
+
+    // Sponsor, user, protocol, contract.
+    assert_eq!(balances(&s), [100 - FEE, 200, FEE, FUNDED - 200]);
+    assert_eq!(s.claim.entitlement(&s.user), FUNDED - 200);
+
+    let (topics, data) = all_events_for(&s.env, &s.claim.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("claim"),
+            symbol_short!("sponsor"),
+            s.user.clone()
+        )
+            .into_val(&s.env)
+    );
+    let data: (Address, i128, i128) = data.into_val(&s.env);
+    assert_eq!(data, (s.sponsor.clone(), 200, FEE));
+}
+
+#[test]
+fn test_missing_sponsor_auth_fails() {
+    let s = setup();
+    let before = balances(&s);
+    authorize(&s, None, Some(200));
+    assert!(s
+        .claim
+        .try_sponsored_claim(&s.sponsor, &s.user, &200)
+        .is_err());
+    assert_eq!(balances(&s), before);
+    assert_eq!(s.claim.entitlement(&s.user), FUNDED);
+}
+
+#[test]
+fn test_missing_user_auth_fails() {
+    let s = setup();
+    let before = balances(&s);
+    authorize(&s, Some(FEE), None);
+    assert!(s
+        .claim
+        .try_sponsored_claim(&s.sponsor, &s.user, &200)
+        .is_err());
+    assert_eq!(balances(&s), before);
+    assert_eq!(s.claim.entitlement(&s.user), FUNDED);
+}
+
+#[test]
+fn test_sponsor_pays_only_the_signed_fee() {
+    let s = setup();
+    // The same sponsor authorization covers claims of any size.
+    for amount in [10, 300] {
+        authorize(&s, Some(FEE), Some(amount));
+        let sponsor_before = s.token.balance(&s.sponsor);
+        s.claim.sponsored_claim(&s.sponsor, &s.user, &amount);
+        assert_eq!(s.token.balance(&s.sponsor), sponsor_before - FEE);
+    }
+    assert_eq!(s.token.balance(&s.protocol), 2 * FEE);
+
+    // Raising the fee invalidates signatures over the old one.
+    s.env.mock_all_auths();
+    s.claim.set_fee(&s.admin, &50);
+    let before = balances(&s);
+    authorize(&s, Some(FEE), Some(10));
+    assert!(s
+        .claim
+        .try_sponsored_claim(&s.sponsor, &s.user, &10)
+        .is_err());
+    assert_eq!(balances(&s), before);
+}
+
+#[test]
+fn test_claim_limits() {
+    let s = setup();
+    authorize(&s, Some(FEE), Some(FUNDED + 1));
+    assert_eq!(
+        s.claim
+            .try_sponsored_claim(&s.sponsor, &s.user, &(FUNDED + 1)),
+        Err(Ok(ClaimError::ExceedsEntitlement))
+    );
+    assert_eq!(
+        s.claim.try_sponsored_claim(&s.sponsor, &s.user, &0),
+        Err(Ok(ClaimError::InvalidInput))
+    );
+
+    s.env.mock_all_auths();
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.claim.try_fund(&stranger, &s.user, &1),
+        Err(Ok(ClaimError::Unauthorized))
+    );
+    assert_eq!(
+        s.claim.try_set_fee(&s.admin, &-1),
+        Err(Ok(ClaimError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (ClaimError::NotInitialized, CommonError::NotInitialized),
+        (
+            ClaimError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (ClaimError::Unauthorized, CommonError::Unauthorized),
+        (ClaimError::Overflow, CommonError::Overflow),
+        (ClaimError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(ClaimError::ExceedsEntitlement as u32 >= recipes_errors::CONTRACT_MIN);
+}
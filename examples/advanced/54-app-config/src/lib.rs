@@ -0,0 +1,172 @@
+//! # App Config
+//!
+//! The "config object" pattern: every setting a contract needs lives in one
+//! `#[contracttype]` struct, stored under a single instance key next to a
+//! version number.
+//!
+//! - One read loads every setting, and one write replaces them together, so
+//!   a reader never sees half of an update.
+//! - `set_config` validates every field before anything is written, then
+//!   bumps the version. Off-chain caches and other contracts can compare
+//!   versions to tell whether their copy is stale.
+//! - Each change emits the old and the new struct, so an indexer can diff
+//!   them without keeping its own history.
+//! - Granular getters (`fee_bps`, `treasury`, ...) let another contract read
+//!   just the field it needs through the generated client.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+};
+
+/// Highest fee: 10_000 basis points is 100%.
+pub const MAX_FEE_BPS: u32 = 10_000;
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones
+/// and codes from 100 up for this contract's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ConfigError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// `set_config` by someone other than the admin.
+    /// `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// `fee_bps` is over `MAX_FEE_BPS`.
+    FeeTooHigh = 100,
+    /// `min_amount` is not positive.
+    MinAmountNotPositive = 101,
+    /// `treasury` is this contract, which would keep fees where nobody can
+    /// spend them.
+    InvalidTreasury = 102,
+}
+
+/// Every setting, replaced as a whole.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppConfig {
+    /// Fee in basis points, at most `MAX_FEE_BPS`.
+    pub fee_bps: u32,
+    /// Smallest amount the application accepts. Positive.
+    pub min_amount: i128,
+    /// Receives the fees. Not this contract.
+    pub treasury: Address,
+    pub paused: bool,
+}
+
+/// What is stored: the settings and how many times they have been set.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionedConfig {
+    /// 1 after `initialize`, then one more per `set_config`.
+    pub version: u32,
+    pub config: AppConfig,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Config,
+}
+
+#[contract]
+pub struct AppConfigContract;
+
+#[contractimpl]
+impl AppConfigContract {
+    /// Store the first config as version 1.
+    pub fn initialize(env: Env, admin: Address, config: AppConfig) -> Result<(), ConfigError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ConfigError::AlreadyInitialized);
+        }
+        validate(&env, &config)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Config, &VersionedConfig { version: 1, config });
+        Ok(())
+    }
+
+    /// Replace the config and return the new version.
+    ///
+    /// Emits `("config", "changed")` with `(version, old, new)`.
+    pub fn set_config(env: Env, admin: Address, new: AppConfig) -> Result<u32, ConfigError> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ConfigError::NotInitialized)?;
+        if admin != stored {
+            return Err(ConfigError::Unauthorized);
+        }
+        validate(&env, &new)?;
+
+        let old = load(&env)?;
+        let version = old.version + 1;
+        env.storage().instance().set(
+            &DataKey::Config,
+            &VersionedConfig {
+                version,
+                config: new.clone(),
+            },
+        );
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("changed")),
+            (version, old.config, new),
+        );
+        Ok(version)
+    }
+
+    pub fn get_config(env: Env) -> Result<AppConfig, ConfigError> {
+        Ok(load(&env)?.config)
+    }
+
+    pub fn config_version(env: Env) -> Result<u32, ConfigError> {
+        Ok(load(&env)?.version)
+    }
+
+    pub fn fee_bps(env: Env) -> Result<u32, ConfigError> {
+        Ok(load(&env)?.config.fee_bps)
+    }
+
+    pub fn min_amount(env: Env) -> Result<i128, ConfigError> {
+        Ok(load(&env)?.config.min_amount)
+    }
+
+    pub fn treasury(env: Env) -> Result<Address, ConfigError> {
+        Ok(load(&env)?.config.treasury)
+    }
+
+    pub fn is_paused(env: Env) -> Result<bool, ConfigError> {
+        Ok(load(&env)?.config.paused)
+    }
+}
+
+fn load(env: &Env) -> Result<VersionedConfig, ConfigError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(ConfigError::NotInitialized)
+}
+
+/// Check every field, in declaration order.
+fn validate(env: &Env, config: &AppConfig) -> Result<(), ConfigError> {
+    if config.fee_bps > MAX_FEE_BPS {
+        return Err(ConfigError::FeeTooHigh);
+    }
+    if config.min_amount <= 0 {
+        return Err(ConfigError::MinAmountNotPositive);
+    }
+    if config.treasury == env.current_contract_address() {
+        return Err(ConfigError::InvalidTreasury);
+    }
+    Ok(())
+}
+
+mod test;
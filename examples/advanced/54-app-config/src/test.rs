@@ -0,0 +1,211 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{testutils::Address as _, IntoVal};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CheckoutError {
+    Paused = 4,
+    BelowMinimum = 100,
+}
+
+/// A second contract that reads its settings from the config contract.
+#[contract]
+pub struct Checkout;
+
+#[contractimpl]
+impl Checkout {
+    /// The fee owed on `amount` under `config`'s current settings.
+    pub fn fee_for(env: Env, config: Address, amount: i128) -> Result<i128, CheckoutError> {
+        let settings = AppConfigContractClient::new(&env, &config);
+        if settings.is_paused() {
+            return Err(CheckoutError::Paused);
+        }
+        if amount < settings.min_amount() {
+            return Err(CheckoutError::BelowMinimum);
+        }
+        Ok(amount * settings.fee_bps() as i128 / MAX_FEE_BPS as i128)
+    }
+}
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    treasury: Address,
+    contract: AppConfigContractClient<'static>,
+}
+
+fn config(treasury: &Address) -> AppConfig {
+    AppConfig {
+        fee_bps: 30,
+        min_amount: 100,
+        treasury: treasury.clone(),
+        paused: false,
+    }
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract =
+        AppConfigContractClient::new(&env, &env.register_contract(None, AppConfigContract));
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    contract.initialize(&admin, &config(&treasury));
+    Setup {
+        env,
+        admin,
+        treasury,
+        contract,
+    }
+}
+
+#[test]
+fn test_getters_read_single_fields() {
+    let s = setup();
+    assert_eq!(s.contract.get_config(), config(&s.treasury));
+    assert_eq!(s.contract.config_version(), 1);
+    assert_eq!(s.contract.fee_bps(), 30);
+    assert_eq!(s.contract.min_amount(), 100);
+    assert_eq!(s.contract.treasury(), s.treasury);
+    assert!(!s.contract.is_paused());
+
+    let empty =
+        AppConfigContractClient::new(&s.env, &s.env.register_contract(None, AppConfigContract));
+    assert_eq!(empty.try_get_config(), Err(Ok(ConfigError::NotInitialized)));
+    assert_eq!(
+        s.contract.try_initialize(&s.admin, &config(&s.treasury)),
+        Err(Ok(ConfigError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_each_field_is_validated() {
+    let s = setup();
+    let mut bad = config(&s.treasury);
+    bad.fee_bps = MAX_FEE_BPS + 1;
+    assert_eq!(
+        s.contract.try_set_config(&s.admin, &bad),
+        Err(Ok(ConfigError::FeeTooHigh))
+    );
+
+    let mut bad = config(&s.treasury);
+    bad.min_amount = 0;
+    assert_eq!(
+        s.contract.try_set_config(&s.admin, &bad),
+        Err(Ok(ConfigError::MinAmountNotPositive))
+    );
+
+    let mut bad = config(&s.treasury);
+    bad.treasury = s.contract.address.clone();
+    assert_eq!(
+        s.contract.try_set_config(&s.admin, &bad),
+        Err(Ok(ConfigError::InvalidTreasury))
+    );
+
+    // Both bounds are inclusive.
+    let mut edge = config(&s.treasury);
+    edge.fee_bps = MAX_FEE_BPS;
+    edge.min_amount = 1;
+    s.contract.set_config(&s.admin, &edge);
+    assert_eq!(s.contract.get_config(), edge);
+
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.contract.try_set_config(&stranger, &config(&s.treasury)),
+        Err(Ok(ConfigError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_version_only_grows() {
+    let s = setup();
+    let mut next = config(&s.treasury);
+    for expected in 2..=4 {
+        next.fee_bps += 10;
+        assert_eq!(s.contract.set_config(&s.admin, &next), expected);
+        assert_eq!(s.contract.config_version(), expected);
+    }
+
+    // A rejected update leaves the version and the config alone.
+    let mut bad = next.clone();
+    bad.min_amount = -5;
+    assert!(s.contract.try_set_config(&s.admin, &bad).is_err());
+    assert_eq!(s.contract.config_version(), 4);
+    assert_eq!(s.contract.get_config(), next);
+
+    // Setting identical values is still a new version.
+    assert_eq!(s.contract.set_config(&s.admin, &next), 5);
+}
+
+#[test]
+fn test_change_event_carries_old_and_new() {
+    let s = setup();
+    let old = config(&s.treasury);
+    let new = AppConfig {
+        fee_bps: 50,
+        min_amount: 1_000,
+        treasury: Address::generate(&s.env),
+        paused: true,
+    };
+    s.contract.set_config(&s.admin, &new);
+
+    let (topics, data) = all_events_for(&s.env, &s.contract.address).pop().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("config"), symbol_short!("changed")).into_val(&s.env)
+    );
+    let (version, before, after): (u32, AppConfig, AppConfig) = data.into_val(&s.env);
+    assert_eq!(version, 2);
+    assert_eq!(before, old);
+    assert_eq!(after, new);
+}
+
+#[test]
+fn test_other_contract_reads_config() {
+    let s = setup();
+    let checkout = CheckoutClient::new(&s.env, &s.env.register_contract(None, Checkout));
+    let config_id = s.contract.address.clone();
+
+    // 30 bps of 10_000.
+    assert_eq!(checkout.fee_for(&config_id, &10_000), 30);
+    assert_eq!(
+        checkout.try_fee_for(&config_id, &99),
+        Err(Ok(CheckoutError::BelowMinimum))
+    );
+
+    // The next read sees the update.
+    let mut next = config(&s.treasury);
+    next.fee_bps = 100;
+    s.contract.set_config(&s.admin, &next);
+    assert_eq!(checkout.fee_for(&config_id, &10_000), 100);
+
+    next.paused = true;
+    s.contract.set_config(&s.admin, &next);
+    assert_eq!(
+        checkout.try_fee_for(&config_id, &10_000),
+        Err(Ok(CheckoutError::Paused))
+    );
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (ConfigError::NotInitialized, CommonError::NotInitialized),
+        (
+            ConfigError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (ConfigError::Unauthorized, CommonError::Unauthorized),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+    assert!(ConfigError::FeeTooHigh as u32 >= recipes_errors::CONTRACT_MIN);
+}
@@ -0,0 +1,147 @@
+//! # Event-Sourced Counter
+//!
+//! A counter whose history is its event stream. Every change is published
+//! as a signed delta, and the value at any point is the fold of the deltas
+//! up to it.
+//!
+//! Replaying every delta since deployment gets slower as the history grows,
+//! so the contract keeps a minimal read model:
+//!
+//! - `snapshot` stores the folded value and publishes it as a `("snap",)`
+//!   event, which also lets a replayer check its fold at that point.
+//! - Between snapshots it keeps only the sum and the number of deltas
+//!   applied since the last one.
+//!
+//! `current` is the snapshot plus that sum. Replaying the event stream from
+//! the start, or from the last snapshot, must give the same number; the
+//! tests check exactly that.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+};
+
+/// Largest delta, up or down, that `apply` accepts.
+pub const MAX_DELTA: i64 = 1 << 62;
+
+/// Failures, using the `recipes-errors` catalogue codes for the common ones.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CounterError {
+    /// `CommonError::NotInitialized`.
+    NotInitialized = 1,
+    /// `CommonError::AlreadyInitialized`.
+    AlreadyInitialized = 2,
+    /// `snapshot` by someone other than the admin.
+    /// `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// The value would leave the `i64` range. `CommonError::Overflow`.
+    Overflow = 5,
+    /// A zero delta, or one larger than `MAX_DELTA`.
+    /// `CommonError::InvalidInput`.
+    InvalidInput = 8,
+}
+
+/// Deltas applied since the last snapshot.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Pending {
+    pub count: u32,
+    /// Kept as `i128` so it cannot overflow while the value itself stays
+    /// within `i64`.
+    pub sum: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Value at the last snapshot.
+    Snapshot,
+    Pending,
+}
+
+#[contract]
+pub struct EventSourcedCounter;
+
+#[contractimpl]
+impl EventSourcedCounter {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CounterError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(CounterError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Add `delta` to the counter.
+    ///
+    /// Emits `("delta",)` with `delta`. A rejected delta emits nothing, so
+    /// the stream holds only deltas that were applied.
+    pub fn apply(env: Env, delta: i64) -> Result<i64, CounterError> {
+        if delta == 0 || delta.unsigned_abs() > MAX_DELTA as u64 {
+            return Err(CounterError::InvalidInput);
+        }
+        let value = Self::current(env.clone())?
+            .checked_add(delta)
+            .ok_or(CounterError::Overflow)?;
+        let mut pending = Self::pending(&env);
+        pending.count += 1;
+        pending.sum += delta as i128;
+        env.storage().instance().set(&DataKey::Pending, &pending);
+        env.events().publish((symbol_short!("delta"),), delta);
+        Ok(value)
+    }
+
+    /// Fold the pending deltas into a new snapshot.
+    ///
+    /// Emits `("snap",)` with the folded value.
+    pub fn snapshot(env: Env, admin: Address) -> Result<i64, CounterError> {
+        admin.require_auth();
+        if admin != Self::admin(&env)? {
+            return Err(CounterError::Unauthorized);
+        }
+        let value = Self::current(env.clone())?;
+        env.storage().instance().set(&DataKey::Snapshot, &value);
+        env.storage().instance().remove(&DataKey::Pending);
+        env.events().publish((symbol_short!("snap"),), value);
+        Ok(value)
+    }
+
+    /// The last snapshot plus every delta since.
+    pub fn current(env: Env) -> Result<i64, CounterError> {
+        Self::admin(&env)?;
+        let snapshot: i64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Snapshot)
+            .unwrap_or(0);
+        // `apply` never lets the total leave the `i64` range.
+        Ok((snapshot as i128 + Self::pending(&env).sum) as i64)
+    }
+
+    /// How many deltas `current` adds on top of the last snapshot.
+    pub fn deltas_since_snapshot(env: Env) -> u32 {
+        Self::pending(&env).count
+    }
+}
+
+impl EventSourcedCounter {
+    fn admin(env: &Env) -> Result<Address, CounterError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CounterError::NotInitialized)
+    }
+
+    fn pending(env: &Env) -> Pending {
+        env.storage()
+            .instance()
+            .get(&DataKey::Pending)
+            .unwrap_or_default()
+    }
+}
+
+mod test;
@@ -0,0 +1,171 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_errors::CommonError;
+use recipes_testutils::all_events_for;
+use soroban_sdk::{testutils::Address as _, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+fn setup() -> (Env, Address, EventSourcedCounterClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let counter =
+        EventSourcedCounterClient::new(&env, &env.register_contract(None, EventSourcedCounter));
+    let admin = Address::generate(&env);
+    counter.initialize(&admin);
+    (env, admin, counter)
+}
+
+/// The counter's events as `(kind, value)`: `"delta"` with a delta, or
+/// `"snap"` with a snapshot.
+fn stream(env: &Env, counter: &Address) -> std::vec::Vec<(Symbol, i64)> {
+    all_events_for(env, counter)
+        .into_iter()
+        .map(|(topics, data): (Vec<Val>, Val)| {
+            let kind = Symbol::try_from_val(env, &topics.get(0).unwrap()).unwrap();
+            (kind, data.into_val(env))
+        })
+        .collect()
+}
+
+/// Fold the whole stream from zero, checking each snapshot against the fold
+/// so far. `None` if the fold leaves the `i64` range.
+fn replay(env: &Env, counter: &Address) -> Option<i64> {
+    let mut value: i64 = 0;
+    for (kind, data) in stream(env, counter) {
+        if kind == symbol_short!("delta") {
+            value = value.checked_add(data)?;
+        } else {
+            assert_eq!(kind, symbol_short!("snap"));
+            assert_eq!(data, value, "snapshot disagrees with the replayed deltas");
+        }
+    }
+    Some(value)
+}
+
+/// Start from the last snapshot and fold only the deltas after it.
+fn replay_from_last_snapshot(env: &Env, counter: &Address) -> i64 {
+    let events = stream(env, counter);
+    let start = events
+        .iter()
+        .rposition(|(kind, _)| *kind == symbol_short!("snap"));
+    let (mut value, rest) = match start {
+        Some(i) => (events[i].1, &events[i + 1..]),
+        None => (0, &events[..]),
+    };
+    for (_, delta) in rest {
+        value += delta;
+    }
+    value
+}
+
+#[test]
+fn test_replay_matches_current() {
+    let (env, admin, counter) = setup();
+    // A scripted, seeded sequence of deltas with a snapshot every so often.
+    let mut seed: u64 = 0x5eed;
+    for step in 0..60 {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let delta = (seed >> 40) as i64 % 1_000 - 500;
+        if delta == 0 {
+            assert_eq!(counter.try_apply(&0), Err(Ok(CounterError::InvalidInput)));
+        } else {
+            counter.apply(&delta);
+        }
+        if step % 17 == 16 {
+            counter.snapshot(&admin);
+        }
+        assert_eq!(replay(&env, &counter.address), Some(counter.current()));
+    }
+}
+
+#[test]
+fn test_snapshots_agree_with_replay() {
+    let (env, admin, counter) = setup();
+    for delta in [40, -15, 7] {
+        counter.apply(&delta);
+    }
+    assert_eq!(counter.snapshot(&admin), 32);
+    assert_eq!(counter.deltas_since_snapshot(), 0);
+
+    counter.apply(&-100);
+    counter.apply(&3);
+    assert_eq!(counter.deltas_since_snapshot(), 2);
+    assert_eq!(counter.snapshot(&admin), -65);
+    // A snapshot with nothing pending repeats the value.
+    assert_eq!(counter.snapshot(&admin), -65);
+    counter.apply(&5);
+
+    let full = replay(&env, &counter.address);
+    let partial = replay_from_last_snapshot(&env, &counter.address);
+    assert_eq!(full, Some(-60));
+    assert_eq!(partial, -60);
+    assert_eq!(counter.current(), -60);
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        counter.try_snapshot(&other),
+        Err(Ok(CounterError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_fold_stays_within_i64() {
+    let (env, admin, counter) = setup();
+    counter.apply(&MAX_DELTA);
+    counter.snapshot(&admin);
+    counter.apply(&(MAX_DELTA - 1));
+    assert_eq!(counter.current(), i64::MAX);
+    assert_eq!(counter.try_apply(&1), Err(Ok(CounterError::Overflow)));
+
+    // Oversized deltas are rejected before they reach the fold.
+    assert_eq!(
+        counter.try_apply(&(MAX_DELTA + 1)),
+        Err(Ok(CounterError::InvalidInput))
+    );
+    assert_eq!(
+        counter.try_apply(&i64::MIN),
+        Err(Ok(CounterError::InvalidInput))
+    );
+
+    // Down to the bottom of the range.
+    for _ in 0..3 {
+        counter.apply(&-MAX_DELTA);
+    }
+    counter.apply(&-(MAX_DELTA - 1));
+    assert_eq!(counter.current(), i64::MIN);
+    assert_eq!(counter.try_apply(&-1), Err(Ok(CounterError::Overflow)));
+
+    // Rejected deltas left no events, so the replay still agrees.
+    assert_eq!(replay(&env, &counter.address), Some(i64::MIN));
+    assert_eq!(replay_from_last_snapshot(&env, &counter.address), i64::MIN);
+}
+
+#[test]
+fn test_not_initialized() {
+    let env = Env::default();
+    let counter =
+        EventSourcedCounterClient::new(&env, &env.register_contract(None, EventSourcedCounter));
+    assert_eq!(counter.try_current(), Err(Ok(CounterError::NotInitialized)));
+    assert_eq!(counter.try_apply(&1), Err(Ok(CounterError::NotInitialized)));
+}
+
+#[test]
+fn test_common_errors_use_catalogue_codes() {
+    let pairs = [
+        (CounterError::NotInitialized, CommonError::NotInitialized),
+        (
+            CounterError::AlreadyInitialized,
+            CommonError::AlreadyInitialized,
+        ),
+        (CounterError::Unauthorized, CommonError::Unauthorized),
+        (CounterError::Overflow, CommonError::Overflow),
+        (CounterError::InvalidInput, CommonError::InvalidInput),
+    ];
+    for (ours, common) in pairs {
+        assert_eq!(ours as u32, common as u32);
+    }
+}
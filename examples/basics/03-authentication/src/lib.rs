@@ -1,6 +1,9 @@
 #![no_std]
 
-use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, vec,
+    Address, Bytes, Env, Map, Symbol, Vec,
+};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -31,11 +34,83 @@ pub enum ContractState {
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
-    Role(Address),
     State,
     TimeLock,
     CooldownPeriod,
     LastAction(Address),
+    Balance(Address),
+    Allowance(Address, Address),
+    MinSigners,
+    PendingRevoke(Address),
+    ActionStats,
+    Decommissioned,
+    Frozen(Address),
+    AuditLog,
+    Operator(Address, Address),
+    UnlockAt(Address),
+}
+
+/// One entry of the compliance audit log returned by `get_audit_log`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditEntry {
+    pub action: Symbol,
+    pub admin: Address,
+    pub account: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Default number of distinct signers `multi_sig_action` requires when the
+/// admin has not configured a value.
+const DEFAULT_MIN_SIGNERS: u32 = 2;
+
+/// Action names used as keys in the `get_action_stats` map.
+const STAT_ADMIN: Symbol = symbol_short!("admin");
+const STAT_MODERATOR: Symbol = symbol_short!("moderator");
+const STAT_TIME_LOCKED: Symbol = symbol_short!("timelock");
+const STAT_COOLDOWN: Symbol = symbol_short!("cooldown");
+
+/// Allowance value treated as unlimited: `transfer_from` never decrements it.
+pub const UNLIMITED_ALLOWANCE: i128 = i128::MAX;
+
+/// Longest memo accepted by `transfer_with_memo`, in bytes.
+const MAX_MEMO_LEN: u32 = 64;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AuthError {
+    Unauthorized = 1,
+    NotAdmin = 2,
+    AlreadyInitialized = 3,
+    DuplicateSigner = 4,
+    InsufficientSigners = 5,
+    MemoTooLong = 6,
+    Decommissioned = 7,
+    InvalidConfirmation = 8,
+    AccountFrozen = 9,
+    AccountNotFrozen = 10,
+    InsufficientBalance = 11,
+    MathOverflow = 12,
+    FundsLocked = 13,
+    LockShortened = 14,
+    /// A negative balance or transfer amount.
+    InvalidAmount = 15,
+    /// A transfer or `active_only_action` while the admin has paused the
+    /// contract.
+    Paused = 16,
+    /// `set_min_signers` below 2, which would let one key, or nobody,
+    /// satisfy `multi_sig_action`.
+    InvalidThreshold = 17,
+}
+
+impl From<recipes_pausable::PausableError> for AuthError {
+    fn from(err: recipes_pausable::PausableError) -> Self {
+        match err {
+            recipes_pausable::PausableError::Paused => AuthError::Paused,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -43,9 +118,9 @@ pub enum DataKey {
 // ---------------------------------------------------------------------------
 
 /// Authentication Patterns Contract
-/// 
+///
 /// This contract demonstrates various address authentication patterns using Soroban's require_auth() function.
-/// 
+///
 /// # Context
 /// Address authentication is the foundation of authorization in Soroban. The require_auth() function:
 /// - Verifies that the caller has authorized the transaction
@@ -55,23 +130,6 @@ pub enum DataKey {
 #[contract]
 pub struct AuthContract;
 
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-#[repr(u32)]
-pub enum AuthError {
-    Unauthorized = 1,
-    NotAdmin = 2,
-    AlreadyInitialized = 3,
-}
-
-#[contracttype]
-#[derive(Clone)]
-pub enum DataKey {
-    Admin,
-    Balance(Address),
-    Allowance(Address, Address),
-}
-
 #[contractimpl]
 impl AuthContract {
     /// Basic authentication check
@@ -80,95 +138,108 @@ impl AuthContract {
         true
     }
 
-    /// Initialize contract with admin
-    pub fn initialize(env: Env, admin: Address) -> Result<(), AuthError> {
-    /// Single-address authorization pattern
-    /// 
-    /// Demonstrates how to require authentication from a specific address for operations
-    /// like transferring assets or modifying user-specific data.
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `from` - The address initiating the transfer
-    /// * `to` - The destination address
-    /// * `amount` - The amount to transfer
-    /// 
-    /// # How authorization is verified:
-    /// The `from.require_auth()` call ensures that the `from` address has authorized this transaction.
-    /// This prevents someone else from initiating a transfer from another person's account.
-    pub fn transfer(_env: Env, from: Address, _to: Address, amount: i128) -> bool {
-        // Require authorization from the 'from' address
-        // This prevents unauthorized transfers from someone else's account
-        from.require_auth();
-    
-        // Validate inputs
-        if amount <= 0 {
-            panic!("Amount must be positive");
-        }
-    
-        // Perform the transfer logic here (in a real contract, this would update balances)
-        // For demonstration purposes, we just return true
+    /// Basic authentication pattern.
+    ///
+    /// Only succeeds when `user` has authorized the invocation.
+    pub fn basic_auth(_env: Env, user: Address) -> bool {
+        user.require_auth();
         true
     }
 
+    // ==================== INITIALIZATION ====================
+
+    /// Initializes the contract with the given admin address.
+    ///
+    /// Must be called exactly once. Returns `AlreadyInitialized` on repeated
+    /// calls to prevent admin hijacking after deployment. The admin is also
+    /// granted the `Admin` role so role-based checks recognise it.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), AuthError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(AuthError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Self::store_role(&env, &admin, Role::Admin);
+        let now = env.ledger().timestamp();
+        Self::publish_role_event(
+            &env,
+            &admin,
+            Role::Admin,
+            symbol_short!("grant"),
+            &admin,
+            now,
+        );
+        Ok(())
+    }
+
     /// Admin-only function pattern
-    /// 
+    ///
     /// Demonstrates how to restrict function access to a specific admin address.
-    /// 
+    ///
     /// # Parameters
     /// * `env` - The Soroban environment
     /// * `admin` - The address claiming to be admin
     /// * `new_admin` - The address to set as new admin
-    /// 
+    ///
     /// # Security considerations:
-    /// - Store the admin address in persistent storage
+    /// - Store the admin address in instance storage
     /// - Only allow the current admin to change the admin
     /// - Always verify admin permissions before critical operations
     pub fn set_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), AuthError> {
-        // First, check if there's already an admin stored
-        if let Some(stored_admin) = env.storage().instance().get::<Symbol, Address>(&ADMIN_KEY) {
-            // If there's a stored admin, verify that the caller is that admin
-            if admin != stored_admin {
-                return Err(AuthError::AdminOnly);
-            }
-            // Require authorization from the current admin
-            admin.require_auth();
-        } else {
-            // If no admin is set yet, anyone can become the initial admin
-            // In a real deployment, this would typically be the contract deployer
-            admin.require_auth();
-        }
-
-        // Set the new admin
-        env.storage().instance().set(&ADMIN_KEY, &new_admin);
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Ok(())
+    }
 
+    /// Permanently disable every state-changing function. Admin only.
+    ///
+    /// `confirm` must be the literal symbol `DECOMMISSION` so the kill switch
+    /// cannot be tripped by a mistyped call. Afterwards all mutating entry
+    /// points fail with `Decommissioned`, while getters keep working so
+    /// balances and roles stay auditable.
+    ///
+    /// The flag is irreversible by construction: this is the only function
+    /// that writes `DataKey::Decommissioned`, and nothing ever removes it.
+    pub fn decommission(env: Env, admin: Address, confirm: Symbol) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        if confirm != Symbol::new(&env, "DECOMMISSION") {
+            return Err(AuthError::InvalidConfirmation);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Decommissioned, &true);
+        env.events().publish(
+            (Symbol::new(&env, "decommissioned"), admin),
+            env.ledger().timestamp(),
+        );
         Ok(())
     }
 
+    /// Whether [`Self::decommission`] has been called.
+    pub fn is_decommissioned(env: Env) -> bool {
+        env.storage().instance().has(&DataKey::Decommissioned)
+    }
+
     /// Get the current admin address
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// 
-    /// # Returns
-    /// The current admin address, if set
     pub fn get_admin(env: Env) -> Option<Address> {
-        env.storage().instance().get::<Symbol, Address>(&ADMIN_KEY)
+        env.storage().instance().get(&DataKey::Admin)
     }
 
+    // ==================== ADDRESS AUTH PATTERNS ====================
+
     /// User-specific operations pattern
-    /// 
+    ///
     /// Demonstrates how to perform operations that affect only the authenticated user.
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `user` - The user whose data will be modified
-    /// * `data` - The data to store for the user
-    /// 
+    ///
     /// # Pattern:
     /// 1. Require auth from the user who owns the data
     /// 2. Use the authenticated address as a key for user-specific storage
     pub fn update_user_data(env: Env, user: Address, data: Symbol) -> bool {
+        Self::require_live(&env);
         // Require authentication from the user
         // This ensures that only the data owner can update their own data
         user.require_auth();
@@ -181,27 +252,12 @@ impl AuthContract {
     }
 
     /// Retrieve user-specific data
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `user` - The user whose data to retrieve
-    /// 
-    /// # Returns
-    /// The data stored for the user, if any
     pub fn get_user_data(env: Env, user: Address) -> Option<Symbol> {
         env.storage().persistent().get(&user)
     }
 
     /// Function demonstrating proper error handling for auth failures
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `user` - The address that should authorize the transaction
-    /// * `operation` - The operation identifier
-    /// 
-    /// # Returns
-    /// Result indicating success or specific error type
-    /// 
+    ///
     /// # Proper error handling:
     /// - Clear error messages when auth fails
     /// - Meaningful error codes for different failure types
@@ -220,154 +276,863 @@ impl AuthContract {
             return Err(AuthError::Unauthorized);
         }
 
-        // Perform the secure operation
-        let result = vec![&env, symbol_short!("success"), operation];
-
-        Ok(result)
+        Ok(vec![&env, symbol_short!("success"), operation])
     }
 
     /// Demonstration of self-authorization pattern
-    /// 
+    ///
     /// Shows how a contract can authenticate itself when calling other contracts
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `self_address` - The address of this contract
-    /// 
-    /// # Self-authorization use case:
-    /// When a contract needs to authenticate itself to call other contracts
-    /// or when implementing contract-to-contract authorization
     pub fn self_authenticate(_env: Env, self_address: Address) -> bool {
         // The contract authenticates itself
         // This is useful when the contract needs to prove its identity to other contracts
         self_address.require_auth();
-    
-        // In a real scenario, this would be used to call other contracts
-        // or to prove the contract's identity for cross-contract operations
         true
     }
 
     /// Demonstrates basic address-based authentication.
     /// Only the 'user' can successfully call this function.
     pub fn secure_action(_env: Env, user: Address) {
-        // 1. The magic line: checks signature and protects against replays.
+        // The magic line: checks signature and protects against replays.
         user.require_auth();
     }
 
-    // ==================== INITIALIZATION ====================
+    // ==================== ROLE-BASED ACCESS CONTROL ====================
 
-    /// Initializes the contract with the given admin address.
+    /// Assign `role` to `account`, replacing any previous role. Admin only.
     ///
-    /// Must be called exactly once. Panics on repeated calls to prevent
-    /// admin hijacking after deployment.
-    pub fn initialize(env: Env, admin: Address) {
-        if env.storage().instance().has(&DataKey::Admin) {
-            return Err(AuthError::AlreadyInitialized);
+    /// Granting also clears any revocation scheduled for the account.
+    /// Emits `("role", account, role, "grant")` with `(admin, timestamp)`.
+    pub fn grant_role(env: Env, admin: Address, account: Address, role: Role) {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRevoke(account.clone()));
+        Self::store_role(&env, &account, role);
+        let now = env.ledger().timestamp();
+        Self::publish_role_event(&env, &account, role, symbol_short!("grant"), &admin, now);
+    }
+
+    /// Remove any role held by `account` immediately. Admin only.
+    ///
+    /// This is the emergency path; use [`Self::schedule_revoke`] when
+    /// in-flight workflows need time to wind down. Revoking an account
+    /// without a role is a no-op and emits nothing; otherwise emits
+    /// `("role", account, role, "revoke")` with `(admin, timestamp)`.
+    pub fn revoke_role(env: Env, admin: Address, account: Address) {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRevoke(account.clone()));
+        let Some(role) = Self::stored_role(&env, &account) else {
+            return;
+        };
+        recipes_access_control::revoke(&env, &account, &Self::role_symbol(role));
+        let now = env.ledger().timestamp();
+        Self::publish_role_event(&env, &account, role, symbol_short!("revoke"), &admin, now);
+    }
+
+    /// Schedule the revocation of `account`'s role at `effective_at`. Admin only.
+    ///
+    /// Until the deadline the role keeps working and
+    /// [`Self::is_role_expiring`] exposes the deadline. Enforcement is lazy:
+    /// once the ledger timestamp reaches `effective_at` every role check
+    /// treats the account as revoked without any further call. Emits
+    /// `("role", account, role, "rev_sched")` with `(admin, effective_at)`.
+    pub fn schedule_revoke(env: Env, admin: Address, account: Address, effective_at: u64) {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+        let Some(role) = Self::load_role(&env, &account) else {
+            panic!("No role assigned");
+        };
+        if effective_at <= env.ledger().timestamp() {
+            panic!("Revocation must be in the future");
         }
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingRevoke(account.clone()), &effective_at);
+        Self::publish_role_event(
+            &env,
+            &account,
+            role,
+            symbol_short!("rev_sched"),
+            &admin,
+            effective_at,
+        );
+    }
+
+    /// Cancel a scheduled revocation that has not taken effect yet. Admin only.
+    ///
+    /// Emits `("role", account, role, "rev_cncl")` with `(admin, timestamp)`.
+    pub fn cancel_revoke(env: Env, admin: Address, account: Address) {
+        Self::require_live(&env);
         admin.require_auth();
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        Ok(())
+        Self::require_admin(&env, &admin);
+        if Self::is_role_expiring(env.clone(), account.clone()).is_none() {
+            panic!("No pending revocation");
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRevoke(account.clone()));
+        // A pending revocation implies a live role.
+        let role = Self::load_role(&env, &account).expect("No role assigned");
+        let now = env.ledger().timestamp();
+        Self::publish_role_event(&env, &account, role, symbol_short!("rev_cncl"), &admin, now);
     }
 
-    /// Get admin address
-    pub fn get_admin(env: Env) -> Option<Address> {
-        env.storage().instance().get(&DataKey::Admin)
+    /// Deadline of a pending revocation for `account`, or `None` when no
+    /// revocation is scheduled or it has already taken effect.
+    pub fn is_role_expiring(env: Env, account: Address) -> Option<u64> {
+        let effective_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingRevoke(account))?;
+        if env.ledger().timestamp() >= effective_at {
+            return None;
+        }
+        Some(effective_at)
+    }
+
+    /// Return the role of `account` as its numeric discriminant.
+    ///
+    /// Panics if the account has no role.
+    pub fn get_role(env: Env, account: Address) -> u32 {
+        let role = Self::load_role(&env, &account).expect("No role assigned");
+        role as u32
+    }
+
+    /// Check whether `account` currently holds exactly `role`.
+    pub fn has_role(env: Env, account: Address, role: Role) -> bool {
+        Self::load_role(&env, &account) == Some(role)
     }
 
     /// Admin-only function
     pub fn admin_action(env: Env, admin: Address, value: u32) -> Result<u32, AuthError> {
+        Self::require_live(&env);
         admin.require_auth();
-        let stored_admin: Address = env
+        Self::ensure_admin(&env, &admin)?;
+        Self::record_action(&env, STAT_ADMIN);
+        Ok(value * 2)
+    }
+
+    /// Moderator-level action. Admins implicitly satisfy moderator checks.
+    pub fn moderator_action(env: Env, caller: Address, value: u32) -> u32 {
+        Self::require_live(&env);
+        caller.require_auth();
+        Self::require_role(&env, &caller, &[Role::Admin, Role::Moderator]);
+        Self::record_action(&env, STAT_MODERATOR);
+        value + 100
+    }
+
+    /// Call counts of the gated actions, keyed by action name
+    /// (`admin`, `moderator`, `timelock`, `cooldown`).
+    ///
+    /// Counters live in a single instance-storage map so dashboards can read
+    /// cheap on-chain metrics without replaying events. Only successful calls
+    /// are counted; failed invocations roll back along with their increment.
+    pub fn get_action_stats(env: Env) -> Map<Symbol, u64> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ActionStats)
+            .unwrap_or(Map::new(&env))
+    }
+
+    // ==================== TIME-BASED RESTRICTIONS ====================
+
+    /// Set the global unlock timestamp for `time_locked_action`. Admin only.
+    pub fn set_time_lock(env: Env, admin: Address, unlock_time: u64) {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::TimeLock, &unlock_time);
+    }
+
+    /// Action that is only allowed once the ledger time reaches the unlock time.
+    pub fn time_locked_action(env: Env, caller: Address) -> u64 {
+        Self::require_live(&env);
+        caller.require_auth();
+
+        let unlock_time: u64 = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthError::NotAdmin)?;
-        
-        if admin != stored_admin {
-            return Err(AuthError::NotAdmin);
+            .get(&DataKey::TimeLock)
+            .unwrap_or(0);
+        let now = env.ledger().timestamp();
+        if now < unlock_time {
+            panic!("Action is time-locked");
         }
-        
-        Ok(value * 2)
+
+        Self::record_action(&env, STAT_TIME_LOCKED);
+        now
+    }
+
+    /// Set the minimum interval between `cooldown_action` calls. Admin only.
+    pub fn set_cooldown(env: Env, admin: Address, period: u64) {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::CooldownPeriod, &period);
+    }
+
+    /// Rate-limited action: each caller must wait out the cooldown period
+    /// between successive calls. Cooldowns are tracked per address.
+    pub fn cooldown_action(env: Env, caller: Address) -> u64 {
+        Self::require_live(&env);
+        caller.require_auth();
+
+        let period: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CooldownPeriod)
+            .unwrap_or(0);
+        let last_action: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastAction(caller.clone()));
+
+        let now = env.ledger().timestamp();
+        if let Some(last) = last_action {
+            if now < last + period {
+                panic!("Cooldown period not elapsed");
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastAction(caller), &now);
+        Self::record_action(&env, STAT_COOLDOWN);
+        now
+    }
+
+    /// Lock `account`'s outgoing transfers until `unlock_at`. Admin only.
+    ///
+    /// Unlike the global [`Self::set_time_lock`], locks are per address. A
+    /// lock may only be extended: an `unlock_at` earlier than the account's
+    /// current unlock time fails with `LockShortened`, so a lock placed for a
+    /// dispute or vesting schedule cannot be quietly cut short.
+    pub fn lock_until(
+        env: Env,
+        admin: Address,
+        account: Address,
+        unlock_at: u64,
+    ) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        if unlock_at < Self::get_unlock_time(env.clone(), account.clone()) {
+            return Err(AuthError::LockShortened);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::UnlockAt(account), &unlock_at);
+        Ok(())
     }
 
+    /// Time at which `account` may send funds again, or 0 when unlocked.
+    pub fn get_unlock_time(env: Env, account: Address) -> u64 {
+        let unlock_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UnlockAt(account))
+            .unwrap_or(0);
+        if env.ledger().timestamp() >= unlock_at {
+            return 0;
+        }
+        unlock_at
+    }
+
+    // ==================== STATE-BASED AUTHORIZATION ====================
+
+    /// Transition the contract-wide state. Admin only.
+    ///
+    /// `Paused` is the shared `recipes-pausable` flag, so entering or
+    /// leaving it publishes the standard `("pausable", "paused" |
+    /// "unpaused")` event. It blocks `transfer`, `transfer_with_memo` and
+    /// `transfer_from`. `Frozen` is stored under `DataKey::State`.
+    pub fn set_state(env: Env, admin: Address, state: ContractState) {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+        let paused = state == ContractState::Paused;
+        if paused != recipes_pausable::is_paused(&env) {
+            recipes_pausable::set_paused(&env, paused);
+            recipes_pausable::emit_pause_changed(&env, &admin, paused);
+        }
+        if state == ContractState::Frozen {
+            env.storage().instance().set(&DataKey::State, &state);
+        } else {
+            env.storage().instance().remove(&DataKey::State);
+        }
+    }
+
+    /// Return the contract state as its numeric discriminant.
+    pub fn get_state(env: Env) -> u32 {
+        Self::load_state(&env) as u32
+    }
+
+    /// Action that only succeeds while the contract is `Active`.
+    ///
+    /// Fails with `Paused` while paused, and panics while `Frozen`.
+    pub fn active_only_action(env: Env, caller: Address) -> u64 {
+        caller.require_auth();
+        if let Err(err) = recipes_pausable::require_not_paused(&env) {
+            panic_with_error!(&env, AuthError::from(err));
+        }
+        if Self::load_state(&env) != ContractState::Active {
+            panic!("Contract is not active");
+        }
+        env.ledger().timestamp()
+    }
+
+    // ==================== BALANCES & ALLOWANCES ====================
+
     /// Transfer with authentication
+    ///
+    /// Fails with `Paused` while the contract is paused.
     pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        recipes_pausable::require_not_paused(&env)?;
         from.require_auth();
-        
-        let from_balance: i128 = env.storage().persistent().get(&DataKey::Balance(from.clone())).unwrap_or(0);
-        let to_balance: i128 = env.storage().persistent().get(&DataKey::Balance(to.clone())).unwrap_or(0);
-        
-        env.storage().persistent().set(&DataKey::Balance(from), &(from_balance - amount));
-        env.storage().persistent().set(&DataKey::Balance(to), &(to_balance + amount));
-        
+        Self::ensure_can_send(&env, &from)?;
+        Self::move_balance(&env, &from, &to, amount)
+    }
+
+    /// Transfer carrying a reconciliation memo.
+    ///
+    /// Balance accounting is identical to [`Self::transfer`]. The memo is
+    /// capped at 64 bytes and published with the amount as the event data so
+    /// payment integrations can match deposits to invoices:
+    ///
+    /// * topics: `("transfer", from, to)`
+    /// * data:   `(amount, memo)`
+    pub fn transfer_with_memo(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        memo: Bytes,
+    ) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        recipes_pausable::require_not_paused(&env)?;
+        from.require_auth();
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(AuthError::MemoTooLong);
+        }
+        Self::ensure_can_send(&env, &from)?;
+        Self::move_balance(&env, &from, &to, amount)?;
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), (amount, memo));
         Ok(())
     }
 
     /// Set balance (admin only)
-    pub fn set_balance(env: Env, admin: Address, user: Address, amount: i128) -> Result<(), AuthError> {
+    ///
+    /// Fails with `InvalidAmount` for a negative balance.
+    pub fn set_balance(
+        env: Env,
+        admin: Address,
+        user: Address,
+        amount: i128,
+    ) -> Result<(), AuthError> {
+        Self::require_live(&env);
         admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthError::NotAdmin)?;
-        
-        if admin != stored_admin {
-            return Err(AuthError::NotAdmin);
+        Self::ensure_admin(&env, &admin)?;
+        if amount < 0 {
+            return Err(AuthError::InvalidAmount);
         }
-        
-        env.storage().persistent().set(&DataKey::Balance(user), &amount);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user), &amount);
         Ok(())
     }
 
     /// Get balance
     pub fn get_balance(env: Env, user: Address) -> i128 {
-        env.storage().persistent().get(&DataKey::Balance(user)).unwrap_or(0)
+        Self::balance_of(&env, &user)
     }
 
     /// Approve allowance
-    pub fn approve(env: Env, from: Address, spender: Address, amount: i128) -> Result<(), AuthError> {
+    ///
+    /// Approving [`UNLIMITED_ALLOWANCE`] (`i128::MAX`) grants an allowance
+    /// that `transfer_from` never decrements. Approving zero removes the
+    /// entry rather than storing a dead value.
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+    ) -> Result<(), AuthError> {
+        Self::require_live(&env);
         from.require_auth();
-        env.storage().persistent().set(&DataKey::Allowance(from, spender), &amount);
+        Self::write_allowance(&env, &from, &spender, amount);
+        Ok(())
+    }
+
+    /// Remaining allowance `spender` may move out of `from`'s balance.
+    pub fn get_allowance(env: Env, from: Address, spender: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allowance(from, spender))
+            .unwrap_or(0)
+    }
+
+    /// Raise the allowance `spender` has over `from`'s balance by `delta`.
+    ///
+    /// Fails with `MathOverflow` instead of wrapping. Because
+    /// [`UNLIMITED_ALLOWANCE`] is `i128::MAX`, an increase that lands exactly
+    /// on it makes the allowance unlimited, and increasing an unlimited
+    /// allowance any further is an overflow.
+    pub fn increase_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        delta: i128,
+    ) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        from.require_auth();
+        let allowance = Self::get_allowance(env.clone(), from.clone(), spender.clone())
+            .checked_add(delta)
+            .ok_or(AuthError::MathOverflow)?;
+        Self::write_allowance(&env, &from, &spender, allowance);
         Ok(())
     }
 
     /// Transfer from allowance
-    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) -> Result<(), AuthError> {
+    ///
+    /// Finite allowances are decremented by exactly `amount`, and the entry
+    /// is removed once it reaches zero. An unlimited allowance is left
+    /// untouched. An approved operator (see [`Self::set_operator`]) needs no
+    /// allowance at all, and its transfers never consume one.
+    ///
+    /// Both paths fail with `Paused` while the contract is paused.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        recipes_pausable::require_not_paused(&env)?;
         spender.require_auth();
-        
-        let allowance: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Allowance(from.clone(), spender.clone()))
-            .unwrap_or(0);
-        
+        Self::ensure_can_send(&env, &from)?;
+
+        if Self::is_operator(env.clone(), from.clone(), spender.clone()) {
+            return Self::move_balance(&env, &from, &to, amount);
+        }
+
+        let allowance = Self::get_allowance(env.clone(), from.clone(), spender.clone());
         if allowance < amount {
             return Err(AuthError::Unauthorized);
         }
-        
-        let from_balance: i128 = env.storage().persistent().get(&DataKey::Balance(from.clone())).unwrap_or(0);
-        let to_balance: i128 = env.storage().persistent().get(&DataKey::Balance(to.clone())).unwrap_or(0);
-        
-        env.storage().persistent().set(&DataKey::Balance(from.clone()), &(from_balance - amount));
-        env.storage().persistent().set(&DataKey::Balance(to), &(to_balance + amount));
-        env.storage().persistent().set(&DataKey::Allowance(from, spender), &(allowance - amount));
-        
+
+        let remaining = allowance
+            .checked_sub(amount)
+            .ok_or(AuthError::MathOverflow)?;
+        Self::move_balance(&env, &from, &to, amount)?;
+        if allowance != UNLIMITED_ALLOWANCE {
+            Self::write_allowance(&env, &from, &spender, remaining);
+        }
+
+        Ok(())
+    }
+
+    /// Approve or revoke `operator` for all of `owner`'s balance.
+    ///
+    /// An operator may call `transfer_from` on the owner's behalf for any
+    /// amount up to the owner's balance, without a per-amount allowance.
+    /// Publishes `("operator", owner, operator)` with `approved` as data.
+    pub fn set_operator(env: Env, owner: Address, operator: Address, approved: bool) {
+        Self::require_live(&env);
+        owner.require_auth();
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        env.events()
+            .publish((symbol_short!("operator"), owner, operator), approved);
+    }
+
+    /// Whether `operator` is currently approved for all of `owner`'s balance.
+    pub fn is_operator(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Operator(owner, operator))
+    }
+
+    // ==================== COMPLIANCE FREEZE & SWEEP ====================
+
+    /// Freeze `account` so it can no longer move funds out. Admin only.
+    pub fn freeze_account(env: Env, admin: Address, account: Address) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Frozen(account.clone()), &true);
+        Self::append_audit(&env, symbol_short!("freeze"), &admin, &account, 0);
+        Ok(())
+    }
+
+    /// Lift a freeze placed by [`Self::freeze_account`]. Admin only.
+    pub fn unfreeze_account(env: Env, admin: Address, account: Address) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Frozen(account.clone()));
+        Self::append_audit(&env, symbol_short!("unfreeze"), &admin, &account, 0);
+        Ok(())
+    }
+
+    /// Whether `account` is currently frozen.
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Frozen(account))
+    }
+
+    /// Move the entire balance of a frozen account to `custody`. Admin only.
+    ///
+    /// Fails with `AccountNotFrozen` unless the source is frozen. On success
+    /// the swept amount is returned and the contract publishes:
+    ///
+    /// * `("transfer", frozen_account, custody)` with the amount, so balance
+    ///   indexers stay consistent, and
+    /// * `("sweep", frozen_account, custody)` with `(admin, amount)`,
+    ///
+    /// and appends a `"sweep"` entry to the audit log. Sweeping an empty
+    /// balance is a no-op that returns 0.
+    ///
+    /// Returns `i128` rather than `u64`: balances are `i128`, so the swept
+    /// amount is reported in the same type with no conversion to fail.
+    pub fn sweep(
+        env: Env,
+        admin: Address,
+        frozen_account: Address,
+        custody: Address,
+    ) -> Result<i128, AuthError> {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        if !Self::is_frozen(env.clone(), frozen_account.clone()) {
+            return Err(AuthError::AccountNotFrozen);
+        }
+
+        let amount = Self::balance_of(&env, &frozen_account);
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        Self::move_balance(&env, &frozen_account, &custody, amount)?;
+        env.events().publish(
+            (
+                symbol_short!("transfer"),
+                frozen_account.clone(),
+                custody.clone(),
+            ),
+            amount,
+        );
+        env.events().publish(
+            (symbol_short!("sweep"), frozen_account.clone(), custody),
+            (admin.clone(), amount),
+        );
+        Self::append_audit(
+            &env,
+            symbol_short!("sweep"),
+            &admin,
+            &frozen_account,
+            amount,
+        );
+        Ok(amount)
+    }
+
+    /// Every compliance action recorded so far, oldest first.
+    pub fn get_audit_log(env: Env) -> Vec<AuditEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AuditLog)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ==================== MULTI-PARTY AUTHORIZATION ====================
+
+    /// Set how many distinct signers `multi_sig_action` requires. Admin only.
+    ///
+    /// Fails with `InvalidThreshold` below 2: a threshold of 1 is a single
+    /// key, and 0 would let an empty signer list through with no auth at all.
+    pub fn set_min_signers(env: Env, admin: Address, min_signers: u32) -> Result<(), AuthError> {
+        Self::require_live(&env);
+        admin.require_auth();
+        Self::ensure_admin(&env, &admin)?;
+        if min_signers < DEFAULT_MIN_SIGNERS {
+            return Err(AuthError::InvalidThreshold);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MinSigners, &min_signers);
         Ok(())
     }
 
+    /// Current minimum signer count (defaults to 2).
+    pub fn get_min_signers(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinSigners)
+            .unwrap_or(DEFAULT_MIN_SIGNERS)
+    }
+
     /// Multi-signature operation
-    pub fn multi_sig_action(env: Env, signers: Vec<Address>, value: u32) -> u32 {
+    ///
+    /// Every address in `signers` must authorize the call. The vector is
+    /// validated *before* any auth check runs: it must contain at least
+    /// `min_signers` entries and no address may appear twice, otherwise a
+    /// single key could satisfy the quorum by repeating itself.
+    pub fn multi_sig_action(env: Env, signers: Vec<Address>, value: u32) -> Result<u32, AuthError> {
+        if signers.len() < Self::get_min_signers(env.clone()) {
+            return Err(AuthError::InsufficientSigners);
+        }
+        for i in 0..signers.len() {
+            let signer = signers.get_unchecked(i);
+            for j in (i + 1)..signers.len() {
+                if signers.get_unchecked(j) == signer {
+                    return Err(AuthError::DuplicateSigner);
+                }
+            }
+        }
+
         for signer in signers.iter() {
             signer.require_auth();
         }
-        value + signers.len()
+        Ok(value + signers.len())
     }
 
     /// Emit event with authentication
     pub fn emit_event(env: Env, user: Address, message: Symbol) {
         user.require_auth();
-        env.events().publish((symbol_short!("event"), user), message);
+        env.events()
+            .publish((symbol_short!("event"), user), message);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+impl AuthContract {
+    /// Returns `NotAdmin` unless `caller` is the stored admin.
+    fn ensure_admin(env: &Env, caller: &Address) -> Result<(), AuthError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuthError::NotAdmin)?;
+        if *caller != stored_admin {
+            return Err(AuthError::NotAdmin);
+        }
+        Ok(())
+    }
+
+    /// Aborts with the typed `Decommissioned` error once the kill switch has
+    /// been tripped. Every mutating entry point calls this first.
+    fn require_live(env: &Env) {
+        if env.storage().instance().has(&DataKey::Decommissioned) {
+            panic_with_error!(env, AuthError::Decommissioned);
+        }
+    }
+
+    /// Panicking variant of [`Self::ensure_admin`] used by the role and
+    /// configuration setters.
+    fn require_admin(env: &Env, caller: &Address) {
+        if Self::ensure_admin(env, caller).is_err() {
+            panic!("Not admin");
+        }
+    }
+
+    /// Panics unless `caller` holds one of `allowed`.
+    fn require_role(env: &Env, caller: &Address, allowed: &[Role]) {
+        match Self::load_role(env, caller) {
+            Some(role) if allowed.contains(&role) => {}
+            _ => panic!("Insufficient role"),
+        }
+    }
+
+    /// Short symbol used for `role` in event topics and as its
+    /// `recipes-access-control` role key.
+    fn role_symbol(role: Role) -> Symbol {
+        match role {
+            Role::Admin => symbol_short!("admin"),
+            Role::Moderator => symbol_short!("moderator"),
+            Role::User => symbol_short!("user"),
+        }
+    }
+
+    /// Publishes `("role", account, role, action)` with `(admin, value)`.
+    ///
+    /// Every role change goes through here so that the full assignment
+    /// history can be replayed from events alone.
+    fn publish_role_event(
+        env: &Env,
+        account: &Address,
+        role: Role,
+        action: Symbol,
+        admin: &Address,
+        value: u64,
+    ) {
+        env.events().publish(
+            (
+                symbol_short!("role"),
+                account.clone(),
+                Self::role_symbol(role),
+                action,
+            ),
+            (admin.clone(), value),
+        );
+    }
+
+    /// Loads the role of `account`, treating a revocation whose deadline
+    /// has passed as already applied.
+    fn load_role(env: &Env, account: &Address) -> Option<Role> {
+        let pending: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingRevoke(account.clone()));
+        if let Some(effective_at) = pending {
+            if env.ledger().timestamp() >= effective_at {
+                return None;
+            }
+        }
+        Self::stored_role(env, account)
+    }
+
+    /// The role recorded for `account` in `recipes-access-control`,
+    /// ignoring any scheduled revocation. This contract keeps at most one
+    /// role per account.
+    fn stored_role(env: &Env, account: &Address) -> Option<Role> {
+        [Role::Admin, Role::Moderator, Role::User]
+            .into_iter()
+            .find(|role| recipes_access_control::has(env, account, &Self::role_symbol(*role)))
+    }
+
+    /// Replaces whatever role `account` held with `role`.
+    fn store_role(env: &Env, account: &Address, role: Role) {
+        if let Some(previous) = Self::stored_role(env, account) {
+            recipes_access_control::revoke(env, account, &Self::role_symbol(previous));
+        }
+        recipes_access_control::grant(env, account, &Self::role_symbol(role));
+    }
+
+    /// Increments the call counter for `action` using checked arithmetic.
+    fn record_action(env: &Env, action: Symbol) {
+        let mut stats = Self::get_action_stats(env.clone());
+        let count = stats
+            .get(action.clone())
+            .unwrap_or(0)
+            .checked_add(1)
+            .expect("Action counter overflow");
+        stats.set(action, count);
+        env.storage().instance().set(&DataKey::ActionStats, &stats);
+    }
+
+    fn load_state(env: &Env) -> ContractState {
+        if recipes_pausable::is_paused(env) {
+            return ContractState::Paused;
+        }
+        env.storage()
+            .instance()
+            .get(&DataKey::State)
+            .unwrap_or(ContractState::Active)
+    }
+
+    /// Returns `AccountFrozen` or `FundsLocked` if `account` may not move
+    /// funds out right now. Receiving is never restricted.
+    fn ensure_can_send(env: &Env, account: &Address) -> Result<(), AuthError> {
+        if Self::is_frozen(env.clone(), account.clone()) {
+            return Err(AuthError::AccountFrozen);
+        }
+        if Self::get_unlock_time(env.clone(), account.clone()) != 0 {
+            return Err(AuthError::FundsLocked);
+        }
+        Ok(())
+    }
+
+    /// Appends an entry to the compliance audit log.
+    fn append_audit(env: &Env, action: Symbol, admin: &Address, account: &Address, amount: i128) {
+        let mut log = Self::get_audit_log(env.clone());
+        log.push_back(AuditEntry {
+            action,
+            admin: admin.clone(),
+            account: account.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&DataKey::AuditLog, &log);
+    }
+
+    /// Moves `amount` from `from` to `to`, failing with `InsufficientBalance`
+    /// rather than letting the sender go negative and with `MathOverflow`
+    /// rather than wrapping either balance.
+    ///
+    /// A negative `amount` fails with `InvalidAmount`: it would pass the
+    /// balance check and move funds from `to` to `from`.
+    fn move_balance(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), AuthError> {
+        if amount < 0 {
+            return Err(AuthError::InvalidAmount);
+        }
+        let from_balance = Self::balance_of(env, from);
+        if from_balance < amount {
+            return Err(AuthError::InsufficientBalance);
+        }
+        if from == to {
+            return Ok(());
+        }
+        // Compute both sides before writing either so an overflow on the
+        // credit side cannot leave the debit behind.
+        let new_from = from_balance
+            .checked_sub(amount)
+            .ok_or(AuthError::MathOverflow)?;
+        let new_to = Self::balance_of(env, to)
+            .checked_add(amount)
+            .ok_or(AuthError::MathOverflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(from.clone()), &new_from);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to.clone()), &new_to);
+        Ok(())
+    }
+
+    /// Stores an allowance, removing the entry when it drops to zero so
+    /// spent allowances do not keep paying rent.
+    fn write_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        if amount == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &amount);
+        }
+    }
+
+    fn balance_of(env: &Env, account: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(account.clone()))
+            .unwrap_or(0)
     }
 }
 
@@ -1,6 +1,29 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, vec, Env};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Bytes, Env, IntoVal, TryFromVal, Val,
+};
+
+fn setup_initialized_contract() -> (Env, Address, Address, AuthContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AuthContract);
+    let client = AuthContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    (env, contract_id, admin, client)
+}
+
+/// Decodes the `("role", account, role, action)` topics of a role event.
+fn role_topics(env: &Env, topics: &Vec<Val>) -> (Symbol, Address, Symbol, Symbol) {
+    (
+        Symbol::try_from_val(env, &topics.get(0).unwrap()).unwrap(),
+        Address::try_from_val(env, &topics.get(1).unwrap()).unwrap(),
+        Symbol::try_from_val(env, &topics.get(2).unwrap()).unwrap(),
+        Symbol::try_from_val(env, &topics.get(3).unwrap()).unwrap(),
+    )
+}
 
 #[test]
 fn test_check_auth() {
@@ -148,6 +171,78 @@ fn test_multi_sig_action() {
     assert_eq!(result, 13);
 }
 
+#[test]
+fn test_multi_sig_action_rejects_duplicate_signers() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+    let signer = Address::generate(&env);
+
+    let signers = vec![&env, signer.clone(), signer.clone(), signer];
+    let result = client.try_multi_sig_action(&signers, &10);
+    assert_eq!(result, Err(Ok(AuthError::DuplicateSigner)));
+}
+
+#[test]
+fn test_multi_sig_action_rejects_below_minimum() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+    let signer = Address::generate(&env);
+
+    let result = client.try_multi_sig_action(&vec![&env, signer], &10);
+    assert_eq!(result, Err(Ok(AuthError::InsufficientSigners)));
+}
+
+#[test]
+fn test_multi_sig_action_exactly_minimum_succeeds() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+    assert_eq!(client.get_min_signers(), 2);
+
+    let signers = vec![&env, Address::generate(&env), Address::generate(&env)];
+    assert_eq!(client.multi_sig_action(&signers, &10), 12);
+}
+
+#[test]
+fn test_raising_min_signers_invalidates_previous_vectors() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let signers = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    assert_eq!(client.multi_sig_action(&signers, &10), 13);
+
+    client.set_min_signers(&admin, &4);
+    let result = client.try_multi_sig_action(&signers, &10);
+    assert_eq!(result, Err(Ok(AuthError::InsufficientSigners)));
+}
+
+#[test]
+fn test_set_min_signers_non_admin_fails() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+    let outsider = Address::generate(&env);
+    let result = client.try_set_min_signers(&outsider, &1);
+    assert_eq!(result, Err(Ok(AuthError::NotAdmin)));
+}
+
+#[test]
+fn test_set_min_signers_rejects_thresholds_below_two() {
+    let (_env, _contract_id, admin, client) = setup_initialized_contract();
+
+    let result = client.try_set_min_signers(&admin, &0);
+    assert_eq!(result, Err(Ok(AuthError::InvalidThreshold)));
+    let result = client.try_set_min_signers(&admin, &1);
+    assert_eq!(result, Err(Ok(AuthError::InvalidThreshold)));
+    assert_eq!(client.get_min_signers(), 2);
+}
+
+#[test]
+fn test_multi_sig_action_rejects_empty_signers() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+
+    let result = client.try_multi_sig_action(&Vec::new(&env), &10);
+    assert_eq!(result, Err(Ok(AuthError::InsufficientSigners)));
+    assert!(env.auths().is_empty());
+}
+
 #[test]
 fn test_emit_event() {
     let env = Env::default();
@@ -202,9 +297,28 @@ fn test_multi_party_role_hierarchy() {
     let moderator = Address::generate(&env);
     let user = Address::generate(&env);
 
+    env.ledger().with_mut(|li| li.timestamp = 42);
     client.grant_role(&admin, &moderator, &Role::Moderator);
     client.grant_role(&admin, &user, &Role::User);
 
+    let events = env.events().all();
+    let (_, topics, data) = events.get(events.len() - 2).unwrap();
+    assert_eq!(
+        role_topics(&env, &topics),
+        (
+            symbol_short!("role"),
+            moderator.clone(),
+            symbol_short!("moderator"),
+            symbol_short!("grant")
+        )
+    );
+    assert_eq!(
+        <(Address, u64)>::try_from_val(&env, &data).unwrap(),
+        (admin.clone(), 42)
+    );
+    let (_, topics, _) = events.get(events.len() - 1).unwrap();
+    assert_eq!(role_topics(&env, &topics).2, symbol_short!("user"));
+
     assert!(client.has_role(&admin, &Role::Admin));
     assert!(client.has_role(&moderator, &Role::Moderator));
     assert!(client.has_role(&user, &Role::User));
@@ -299,11 +413,132 @@ fn test_state_default_is_active() {
     assert_eq!(client.get_state(), 0);
 }
 
+#[test]
+fn test_paused_state_uses_pausable_flag() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+
+    client.set_state(&admin, &ContractState::Paused);
+    assert_eq!(client.get_state(), 1);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (recipes_pausable::NAMESPACE, symbol_short!("paused")).into_val(&env)
+    );
+    assert_eq!(Address::try_from_val(&env, &data).unwrap(), admin);
+    env.as_contract(&contract_id, || assert!(recipes_pausable::is_paused(&env)));
+
+    let result = client.try_active_only_action(&user);
+    assert_eq!(result, Err(Ok(AuthError::Paused.into())));
+
+    client.set_balance(&admin, &user, &100);
+    let result = client.try_transfer(&user, &admin, &40);
+    assert_eq!(result, Err(Ok(AuthError::Paused)));
+    let memo = Bytes::from_slice(&env, b"inv-1");
+    let result = client.try_transfer_with_memo(&user, &admin, &40, &memo);
+    assert_eq!(result, Err(Ok(AuthError::Paused)));
+    assert_eq!(client.get_balance(&user), 100);
+
+    client.set_state(&admin, &ContractState::Active);
+    assert_eq!(client.get_state(), 0);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (recipes_pausable::NAMESPACE, symbol_short!("unpaused")).into_val(&env)
+    );
+    client.active_only_action(&user);
+    client.transfer(&user, &admin, &40);
+    assert_eq!(client.get_balance(&user), 60);
+}
+
+#[test]
+#[should_panic(expected = "Contract is not active")]
+fn test_frozen_state_blocks_active_only_action() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+    client.set_state(&admin, &ContractState::Frozen);
+    assert_eq!(client.get_state(), 2);
+    client.active_only_action(&user);
+}
+
 #[test]
 fn test_revoke_nonexistent_role() {
     let (env, _contract_id, admin, client) = setup_initialized_contract();
     let user = Address::generate(&env);
+    let before = env.events().all().len();
+    client.revoke_role(&admin, &user);
+    assert_eq!(env.events().all().len(), before);
+}
+
+#[test]
+fn test_revoke_role_emits_event_with_revoked_role() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+    client.grant_role(&admin, &user, &Role::Moderator);
+
+    env.ledger().with_mut(|li| li.timestamp = 77);
     client.revoke_role(&admin, &user);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        role_topics(&env, &topics),
+        (
+            symbol_short!("role"),
+            user,
+            symbol_short!("moderator"),
+            symbol_short!("revoke")
+        )
+    );
+    assert_eq!(
+        <(Address, u64)>::try_from_val(&env, &data).unwrap(),
+        (admin, 77)
+    );
+}
+
+#[test]
+fn test_role_assignments_replay_from_events() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.grant_role(&admin, &alice, &Role::User);
+    client.grant_role(&admin, &bob, &Role::Moderator);
+    client.grant_role(&admin, &carol, &Role::User);
+    client.grant_role(&admin, &alice, &Role::Moderator);
+    client.revoke_role(&admin, &bob);
+    client.grant_role(&admin, &carol, &Role::Admin);
+    client.grant_role(&admin, &bob, &Role::User);
+    client.revoke_role(&admin, &carol);
+
+    // Rebuild the assignments from the event stream alone.
+    let mut replayed: Map<Address, Symbol> = Map::new(&env);
+    for (id, topics, _) in env.events().all().iter() {
+        if id != contract_id || topics.len() != 4 {
+            continue;
+        }
+        let (ns, account, role, action) = role_topics(&env, &topics);
+        if ns != symbol_short!("role") {
+            continue;
+        }
+        if action == symbol_short!("grant") {
+            replayed.set(account, role);
+        } else if action == symbol_short!("revoke") {
+            replayed.remove(account);
+        }
+    }
+
+    assert_eq!(
+        replayed.get(alice.clone()),
+        Some(symbol_short!("moderator"))
+    );
+    assert_eq!(replayed.get(bob.clone()), Some(symbol_short!("user")));
+    assert_eq!(replayed.get(carol.clone()), None);
+    assert_eq!(replayed.get(admin), Some(symbol_short!("admin")));
+
+    assert!(client.has_role(&alice, &Role::Moderator));
+    assert!(client.has_role(&bob, &Role::User));
+    assert!(client.try_get_role(&carol).is_err());
 }
 
 #[test]
@@ -332,3 +567,829 @@ fn test_non_admin_cannot_set_cooldown() {
     client.grant_role(&admin, &user, &Role::User);
     client.set_cooldown(&user, &100);
 }
+
+// ---------------------------------------------------------------------------
+// 10. Time-delayed revocation tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_scheduled_revoke_keeps_role_until_deadline() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::Moderator);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.schedule_revoke(&admin, &operator, &1_500);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_499);
+    assert!(client.has_role(&operator, &Role::Moderator));
+    assert_eq!(client.is_role_expiring(&operator), Some(1_500));
+    assert_eq!(client.moderator_action(&operator, &1), 101);
+}
+
+#[test]
+fn test_scheduled_revoke_applies_after_deadline() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::Moderator);
+    client.schedule_revoke(&admin, &operator, &500);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert!(!client.has_role(&operator, &Role::Moderator));
+    assert_eq!(client.is_role_expiring(&operator), None);
+    assert!(client.try_get_role(&operator).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Insufficient role")]
+fn test_scheduled_revoke_blocks_moderator_action_after_deadline() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::Moderator);
+    client.schedule_revoke(&admin, &operator, &500);
+
+    env.ledger().with_mut(|li| li.timestamp = 501);
+    client.moderator_action(&operator, &1);
+}
+
+#[test]
+fn test_cancel_revoke_restores_role() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::Moderator);
+    client.schedule_revoke(&admin, &operator, &500);
+    client.cancel_revoke(&admin, &operator);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+    assert!(client.has_role(&operator, &Role::Moderator));
+    assert_eq!(client.is_role_expiring(&operator), None);
+}
+
+#[test]
+fn test_immediate_revoke_still_works_with_pending_schedule() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::Moderator);
+    client.schedule_revoke(&admin, &operator, &500);
+
+    client.revoke_role(&admin, &operator);
+    assert!(!client.has_role(&operator, &Role::Moderator));
+    assert_eq!(client.is_role_expiring(&operator), None);
+}
+
+#[test]
+#[should_panic(expected = "Revocation must be in the future")]
+fn test_schedule_revoke_in_the_past_panics() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::User);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.schedule_revoke(&admin, &operator, &100);
+}
+
+#[test]
+fn test_revocation_events_distinguish_scheduled_and_immediate() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let scheduled = Address::generate(&env);
+    let immediate = Address::generate(&env);
+    client.grant_role(&admin, &scheduled, &Role::User);
+    client.grant_role(&admin, &immediate, &Role::User);
+
+    client.schedule_revoke(&admin, &scheduled, &700);
+    client.revoke_role(&admin, &immediate);
+
+    let events = env.events().all();
+    let (id, topics, data) = events.get(events.len() - 2).unwrap();
+    assert_eq!(id, contract_id);
+    let (_, who, role, kind) = role_topics(&env, &topics);
+    assert_eq!(kind, symbol_short!("rev_sched"));
+    assert_eq!(who, scheduled);
+    assert_eq!(role, symbol_short!("user"));
+    assert_eq!(
+        <(Address, u64)>::try_from_val(&env, &data).unwrap(),
+        (admin.clone(), 700)
+    );
+
+    let (_, topics, _) = events.get(events.len() - 1).unwrap();
+    let (_, who, _, kind) = role_topics(&env, &topics);
+    assert_eq!(kind, symbol_short!("revoke"));
+    assert_eq!(who, immediate);
+}
+
+// ---------------------------------------------------------------------------
+// 11. Action statistics tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_action_stats_empty_by_default() {
+    let (_env, _contract_id, _admin, client) = setup_initialized_contract();
+    assert!(client.get_action_stats().is_empty());
+}
+
+#[test]
+fn test_action_stats_reflect_scripted_calls() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let moderator = Address::generate(&env);
+    client.grant_role(&admin, &moderator, &Role::Moderator);
+
+    client.admin_action(&admin, &1);
+    client.admin_action(&admin, &2);
+    client.moderator_action(&moderator, &1);
+    client.moderator_action(&admin, &1);
+    client.moderator_action(&moderator, &1);
+    client.time_locked_action(&moderator);
+    client.cooldown_action(&moderator);
+
+    let stats = client.get_action_stats();
+    assert_eq!(stats.len(), 4);
+    assert_eq!(stats.get(symbol_short!("admin")), Some(2));
+    assert_eq!(stats.get(symbol_short!("moderator")), Some(3));
+    assert_eq!(stats.get(symbol_short!("timelock")), Some(1));
+    assert_eq!(stats.get(symbol_short!("cooldown")), Some(1));
+}
+
+#[test]
+fn test_failed_calls_do_not_increment_stats() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+    client.grant_role(&admin, &user, &Role::User);
+    client.set_time_lock(&admin, &1_000);
+
+    assert!(client.try_admin_action(&user, &1).is_err());
+    assert!(client.try_moderator_action(&user, &1).is_err());
+    assert!(client.try_time_locked_action(&user).is_err());
+
+    client.admin_action(&admin, &1);
+
+    let stats = client.get_action_stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats.get(symbol_short!("admin")), Some(1));
+    assert_eq!(stats.get(symbol_short!("moderator")), None);
+}
+
+// ---------------------------------------------------------------------------
+// 12. Transfer memo tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_transfer_with_memo_round_trips_through_event() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    client.set_balance(&admin, &from, &1_000);
+
+    let memo = Bytes::from_slice(&env, b"INV-2024-0042");
+    client.transfer_with_memo(&from, &to, &250, &memo);
+
+    let (id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(id, contract_id);
+    assert_eq!(topics.len(), 3);
+    let name = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+    let t_from = Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
+    let t_to = Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(name, symbol_short!("transfer"));
+    assert_eq!(t_from, from);
+    assert_eq!(t_to, to);
+
+    let (amount, event_memo) = <(i128, Bytes)>::try_from_val(&env, &data).unwrap();
+    assert_eq!(amount, 250);
+    assert_eq!(event_memo, memo);
+}
+
+#[test]
+fn test_transfer_with_memo_rejects_long_memo() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    client.set_balance(&admin, &from, &1_000);
+
+    let at_cap = Bytes::from_slice(&env, &[7u8; 64]);
+    client.transfer_with_memo(&from, &to, &1, &at_cap);
+
+    let too_long = Bytes::from_slice(&env, &[7u8; 65]);
+    let result = client.try_transfer_with_memo(&from, &to, &1, &too_long);
+    assert_eq!(result, Err(Ok(AuthError::MemoTooLong)));
+    assert_eq!(client.get_balance(&from), 999);
+}
+
+#[test]
+fn test_transfer_with_empty_memo_allowed() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    client.set_balance(&admin, &from, &10);
+
+    client.transfer_with_memo(&from, &to, &10, &Bytes::new(&env));
+
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (_, memo) = <(i128, Bytes)>::try_from_val(&env, &data).unwrap();
+    assert!(memo.is_empty());
+    assert_eq!(client.get_balance(&to), 10);
+}
+
+#[test]
+fn test_transfer_with_memo_matches_plain_transfer_accounting() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    let d = Address::generate(&env);
+    client.set_balance(&admin, &a, &1_000);
+    client.set_balance(&admin, &c, &1_000);
+
+    client.transfer(&a, &b, &300);
+    client.transfer_with_memo(&c, &d, &300, &Bytes::from_slice(&env, b"memo"));
+
+    assert_eq!(client.get_balance(&a), client.get_balance(&c));
+    assert_eq!(client.get_balance(&b), client.get_balance(&d));
+}
+
+// ---------------------------------------------------------------------------
+// 13. Allowance semantics tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_unlimited_allowance_is_never_decremented() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+    client.approve(&owner, &spender, &UNLIMITED_ALLOWANCE);
+
+    client.transfer_from(&spender, &owner, &recipient, &100);
+    client.transfer_from(&spender, &owner, &recipient, &250);
+    client.transfer_from(&spender, &owner, &recipient, &1);
+
+    assert_eq!(client.get_allowance(&owner, &spender), UNLIMITED_ALLOWANCE);
+    assert_eq!(client.get_balance(&owner), 649);
+    assert_eq!(client.get_balance(&recipient), 351);
+}
+
+#[test]
+fn test_finite_allowance_decrements_by_spent_amount() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+    client.approve(&owner, &spender, &500);
+
+    client.transfer_from(&spender, &owner, &recipient, &120);
+    assert_eq!(client.get_allowance(&owner, &spender), 380);
+}
+
+#[test]
+fn test_spending_allowance_to_zero_clears_entry() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+    client.approve(&owner, &spender, &300);
+
+    client.transfer_from(&spender, &owner, &recipient, &100);
+    client.transfer_from(&spender, &owner, &recipient, &200);
+
+    assert_eq!(client.get_allowance(&owner, &spender), 0);
+    let has_entry = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Allowance(owner.clone(), spender.clone()))
+    });
+    assert!(!has_entry);
+}
+
+// ---------------------------------------------------------------------------
+// 14. Decommission (kill switch) tests
+// ---------------------------------------------------------------------------
+
+fn decommission_confirmation(env: &Env) -> Symbol {
+    Symbol::new(env, "DECOMMISSION")
+}
+
+#[test]
+fn test_decommission_rejects_wrong_confirmation() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let result = client.try_decommission(&admin, &symbol_short!("yes"));
+    assert_eq!(result, Err(Ok(AuthError::InvalidConfirmation)));
+    assert!(!client.is_decommissioned());
+
+    let outsider = Address::generate(&env);
+    let result = client.try_decommission(&outsider, &decommission_confirmation(&env));
+    assert_eq!(result, Err(Ok(AuthError::NotAdmin)));
+}
+
+/// Asserts that a `try_` call failed with `Decommissioned`, whether or not the
+/// function returns `Result` (panicking functions surface `soroban_sdk::Error`).
+fn assert_decommissioned<T, C, E>(result: Result<Result<T, C>, Result<E, soroban_sdk::InvokeError>>)
+where
+    T: core::fmt::Debug,
+    C: core::fmt::Debug,
+    E: Into<soroban_sdk::Error> + core::fmt::Debug,
+{
+    match result {
+        Err(Ok(err)) => assert_eq!(err.into(), AuthError::Decommissioned.into()),
+        other => panic!("expected Decommissioned, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_decommission_blocks_every_mutation() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.set_balance(&admin, &user, &500);
+    client.grant_role(&admin, &user, &Role::Moderator);
+
+    client.decommission(&admin, &decommission_confirmation(&env));
+    assert!(client.is_decommissioned());
+
+    assert_decommissioned(client.try_transfer(&user, &other, &1));
+    assert_decommissioned(client.try_transfer_with_memo(&user, &other, &1, &Bytes::new(&env)));
+    assert_decommissioned(client.try_transfer_from(&other, &user, &other, &1));
+    assert_decommissioned(client.try_approve(&user, &other, &1));
+    assert_decommissioned(client.try_set_balance(&admin, &user, &1));
+    assert_decommissioned(client.try_set_admin(&admin, &other));
+    assert_decommissioned(client.try_set_min_signers(&admin, &3));
+    assert_decommissioned(client.try_admin_action(&admin, &1));
+    assert_decommissioned(client.try_moderator_action(&user, &1));
+    assert_decommissioned(client.try_time_locked_action(&user));
+    assert_decommissioned(client.try_cooldown_action(&user));
+    assert_decommissioned(client.try_grant_role(&admin, &other, &Role::User));
+    assert_decommissioned(client.try_revoke_role(&admin, &user));
+    assert_decommissioned(client.try_schedule_revoke(&admin, &user, &1_000));
+    assert_decommissioned(client.try_cancel_revoke(&admin, &user));
+    assert_decommissioned(client.try_set_state(&admin, &ContractState::Active));
+    assert_decommissioned(client.try_set_time_lock(&admin, &0));
+    assert_decommissioned(client.try_set_cooldown(&admin, &0));
+    assert_decommissioned(client.try_update_user_data(&user, &symbol_short!("data")));
+}
+
+#[test]
+fn test_getters_work_after_decommission() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.set_balance(&admin, &user, &500);
+    client.approve(&user, &spender, &50);
+    client.grant_role(&admin, &user, &Role::User);
+
+    client.decommission(&admin, &decommission_confirmation(&env));
+
+    assert_eq!(client.get_balance(&user), 500);
+    assert_eq!(client.get_allowance(&user, &spender), 50);
+    assert!(client.has_role(&user, &Role::User));
+    assert_eq!(client.get_admin(), Some(admin));
+    assert_eq!(client.get_state(), 0);
+}
+
+#[test]
+fn test_second_decommission_is_harmless_error() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    client.decommission(&admin, &decommission_confirmation(&env));
+
+    let result = client.try_decommission(&admin, &decommission_confirmation(&env));
+    assert_eq!(result, Err(Ok(AuthError::Decommissioned)));
+    assert!(client.is_decommissioned());
+}
+
+// ---------------------------------------------------------------------------
+// 15. Compliance freeze & sweep tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_sweep_moves_entire_frozen_balance_to_custody() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let suspect = Address::generate(&env);
+    let custody = Address::generate(&env);
+    client.set_balance(&admin, &suspect, &750);
+    client.set_balance(&admin, &custody, &100);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    client.freeze_account(&admin, &suspect);
+    let swept = client.sweep(&admin, &suspect, &custody);
+
+    assert_eq!(swept, 750);
+    assert_eq!(client.get_balance(&suspect), 0);
+    assert_eq!(client.get_balance(&custody), 850);
+
+    let events = env.events().all();
+    let (id, topics, data) = events.get(events.len() - 2).unwrap();
+    assert_eq!(id, contract_id);
+    assert_eq!(
+        topics,
+        vec![
+            &env,
+            symbol_short!("transfer").into_val(&env),
+            suspect.into_val(&env),
+            custody.into_val(&env),
+        ]
+    );
+    assert_eq!(i128::try_from_val(&env, &data).unwrap(), 750);
+
+    let (_, topics, data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(
+        topics,
+        vec![
+            &env,
+            symbol_short!("sweep").into_val(&env),
+            suspect.into_val(&env),
+            custody.into_val(&env),
+        ]
+    );
+    assert_eq!(
+        <(Address, i128)>::try_from_val(&env, &data).unwrap(),
+        (admin.clone(), 750)
+    );
+
+    let log = client.get_audit_log();
+    assert_eq!(log.len(), 2);
+    assert_eq!(
+        log.get(1).unwrap(),
+        AuditEntry {
+            action: symbol_short!("sweep"),
+            admin,
+            account: suspect,
+            amount: 750,
+            timestamp: 3_000,
+        }
+    );
+}
+
+#[test]
+fn test_sweep_unfrozen_account_rejected() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let holder = Address::generate(&env);
+    let custody = Address::generate(&env);
+    client.set_balance(&admin, &holder, &300);
+
+    let result = client.try_sweep(&admin, &holder, &custody);
+    assert_eq!(result, Err(Ok(AuthError::AccountNotFrozen)));
+    assert_eq!(client.get_balance(&holder), 300);
+    assert_eq!(client.get_audit_log().len(), 0);
+}
+
+#[test]
+fn test_sweep_zero_balance_is_noop() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let suspect = Address::generate(&env);
+    let custody = Address::generate(&env);
+    client.freeze_account(&admin, &suspect);
+    let events_before = env.events().all().len();
+
+    assert_eq!(client.sweep(&admin, &suspect, &custody), 0);
+    assert_eq!(env.events().all().len(), events_before);
+    assert_eq!(client.get_audit_log().len(), 1);
+}
+
+#[test]
+fn test_sweep_requires_admin() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let suspect = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    client.set_balance(&admin, &suspect, &10);
+    client.freeze_account(&admin, &suspect);
+
+    let result = client.try_sweep(&attacker, &suspect, &attacker);
+    assert_eq!(result, Err(Ok(AuthError::NotAdmin)));
+    assert_eq!(client.get_balance(&suspect), 10);
+}
+
+#[test]
+fn test_frozen_account_cannot_transfer_out() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let suspect = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.set_balance(&admin, &suspect, &100);
+    client.approve(&suspect, &other, &100);
+    client.freeze_account(&admin, &suspect);
+
+    assert_eq!(
+        client.try_transfer(&suspect, &other, &10),
+        Err(Ok(AuthError::AccountFrozen))
+    );
+    assert_eq!(
+        client.try_transfer_from(&other, &suspect, &other, &10),
+        Err(Ok(AuthError::AccountFrozen))
+    );
+
+    client.unfreeze_account(&admin, &suspect);
+    client.transfer(&suspect, &other, &10);
+    assert_eq!(client.get_balance(&other), 10);
+
+    let log = client.get_audit_log();
+    assert_eq!(log.get(0).unwrap().action, symbol_short!("freeze"));
+    assert_eq!(log.get(1).unwrap().action, symbol_short!("unfreeze"));
+}
+
+// ---------------------------------------------------------------------------
+// 16. Operator approval tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_operator_transfers_without_allowance() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+
+    client.set_operator(&owner, &operator, &true);
+    assert!(client.is_operator(&owner, &operator));
+    let (id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(id, contract_id);
+    assert_eq!(
+        topics,
+        vec![
+            &env,
+            symbol_short!("operator").into_val(&env),
+            owner.into_val(&env),
+            operator.into_val(&env),
+        ]
+    );
+    assert!(bool::try_from_val(&env, &data).unwrap());
+
+    client.transfer_from(&operator, &owner, &recipient, &400);
+    assert_eq!(client.get_balance(&owner), 600);
+    assert_eq!(client.get_balance(&recipient), 400);
+    assert_eq!(client.get_allowance(&owner, &operator), 0);
+}
+
+#[test]
+fn test_operator_bounded_by_owner_balance() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.set_balance(&admin, &owner, &100);
+    client.set_operator(&owner, &operator, &true);
+
+    let result = client.try_transfer_from(&operator, &owner, &operator, &101);
+    assert_eq!(result, Err(Ok(AuthError::InsufficientBalance)));
+    assert_eq!(client.get_balance(&owner), 100);
+}
+
+#[test]
+fn test_operator_blocked_while_paused() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+    client.set_operator(&owner, &operator, &true);
+    client.approve(&owner, &admin, &500);
+
+    client.set_state(&admin, &ContractState::Paused);
+    let result = client.try_transfer_from(&operator, &owner, &operator, &1_000);
+    assert_eq!(result, Err(Ok(AuthError::Paused)));
+    let result = client.try_transfer_from(&admin, &owner, &admin, &500);
+    assert_eq!(result, Err(Ok(AuthError::Paused)));
+    assert_eq!(client.get_balance(&owner), 1_000);
+    assert_eq!(client.get_allowance(&owner, &admin), 500);
+
+    client.set_state(&admin, &ContractState::Active);
+    client.transfer_from(&operator, &owner, &operator, &1_000);
+    assert_eq!(client.get_balance(&owner), 0);
+}
+
+#[test]
+fn test_operator_revocation_blocks_immediately() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+    client.set_operator(&owner, &operator, &true);
+    client.transfer_from(&operator, &owner, &operator, &10);
+
+    client.set_operator(&owner, &operator, &false);
+    assert!(!client.is_operator(&owner, &operator));
+    let (_, _, data) = env.events().all().last().unwrap();
+    assert!(!bool::try_from_val(&env, &data).unwrap());
+
+    let result = client.try_transfer_from(&operator, &owner, &operator, &10);
+    assert_eq!(result, Err(Ok(AuthError::Unauthorized)));
+    assert_eq!(client.get_balance(&owner), 990);
+}
+
+#[test]
+fn test_allowances_independent_of_operators() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+    client.set_operator(&owner, &operator, &true);
+    client.approve(&owner, &spender, &50);
+
+    client.transfer_from(&spender, &owner, &spender, &50);
+    assert_eq!(client.get_allowance(&owner, &spender), 0);
+    assert!(!client.is_operator(&owner, &spender));
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &spender, &1),
+        Err(Ok(AuthError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_operator_path_does_not_consume_allowance() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+    client.approve(&owner, &operator, &100);
+    client.set_operator(&owner, &operator, &true);
+
+    client.transfer_from(&operator, &owner, &operator, &300);
+    assert_eq!(client.get_allowance(&owner, &operator), 100);
+
+    // Once the operator approval goes away the numeric allowance applies.
+    client.set_operator(&owner, &operator, &false);
+    client.transfer_from(&operator, &owner, &operator, &100);
+    assert_eq!(client.get_allowance(&owner, &operator), 0);
+    assert_eq!(client.get_balance(&owner), 600);
+}
+
+#[test]
+fn test_frozen_owner_blocks_operator() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.set_balance(&admin, &owner, &1_000);
+    client.set_operator(&owner, &operator, &true);
+    client.freeze_account(&admin, &owner);
+
+    let result = client.try_transfer_from(&operator, &owner, &operator, &1);
+    assert_eq!(result, Err(Ok(AuthError::AccountFrozen)));
+}
+
+// ---------------------------------------------------------------------------
+// 17. Checked arithmetic tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_credit_recipient_at_max_overflows() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let sender = Address::generate(&env);
+    let whale = Address::generate(&env);
+    client.set_balance(&admin, &sender, &10);
+    client.set_balance(&admin, &whale, &i128::MAX);
+
+    let result = client.try_transfer(&sender, &whale, &1);
+    assert_eq!(result, Err(Ok(AuthError::MathOverflow)));
+}
+
+#[test]
+fn test_overflow_on_credit_leaves_debit_untouched() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let whale = Address::generate(&env);
+    client.set_balance(&admin, &owner, &500);
+    client.set_balance(&admin, &whale, &(i128::MAX - 1));
+    client.approve(&owner, &spender, &200);
+
+    // The debit of `owner` is computed first; the credit of `whale` overflows.
+    let result = client.try_transfer_from(&spender, &owner, &whale, &2);
+    assert_eq!(result, Err(Ok(AuthError::MathOverflow)));
+    assert_eq!(client.get_balance(&owner), 500);
+    assert_eq!(client.get_balance(&whale), i128::MAX - 1);
+    assert_eq!(client.get_allowance(&owner, &spender), 200);
+}
+
+#[test]
+fn test_increase_allowance_overflow_rejected() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.approve(&owner, &spender, &(i128::MAX - 5));
+
+    let result = client.try_increase_allowance(&owner, &spender, &6);
+    assert_eq!(result, Err(Ok(AuthError::MathOverflow)));
+    assert_eq!(client.get_allowance(&owner, &spender), i128::MAX - 5);
+
+    client.increase_allowance(&owner, &spender, &5);
+    assert_eq!(client.get_allowance(&owner, &spender), UNLIMITED_ALLOWANCE);
+}
+
+#[test]
+fn test_self_transfer_keeps_balance() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+    client.set_balance(&admin, &user, &100);
+
+    client.transfer(&user, &user, &40);
+    assert_eq!(client.get_balance(&user), 100);
+}
+
+// ---------------------------------------------------------------------------
+// 18. Per-account lock tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_locked_sender_blocked() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let holder = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.set_balance(&admin, &holder, &1_000);
+    client.approve(&holder, &spender, &100);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.lock_until(&admin, &holder, &500);
+    assert_eq!(client.get_unlock_time(&holder), 500);
+
+    assert_eq!(
+        client.try_transfer(&holder, &spender, &1),
+        Err(Ok(AuthError::FundsLocked))
+    );
+    assert_eq!(
+        client.try_transfer_from(&spender, &holder, &spender, &1),
+        Err(Ok(AuthError::FundsLocked))
+    );
+    assert_eq!(client.get_balance(&holder), 1_000);
+}
+
+#[test]
+fn test_locked_account_can_receive() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let holder = Address::generate(&env);
+    let payer = Address::generate(&env);
+    client.set_balance(&admin, &payer, &50);
+    client.lock_until(&admin, &holder, &500);
+
+    client.transfer(&payer, &holder, &50);
+    assert_eq!(client.get_balance(&holder), 50);
+}
+
+#[test]
+fn test_lock_expires_after_unlock_time() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.set_balance(&admin, &holder, &100);
+    client.lock_until(&admin, &holder, &500);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.get_unlock_time(&holder), 0);
+    client.transfer(&holder, &other, &100);
+    assert_eq!(client.get_balance(&other), 100);
+}
+
+#[test]
+fn test_admin_can_extend_lock() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.set_balance(&admin, &holder, &100);
+    client.lock_until(&admin, &holder, &500);
+    client.lock_until(&admin, &holder, &900);
+    assert_eq!(client.get_unlock_time(&holder), 900);
+
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(
+        client.try_transfer(&holder, &other, &1),
+        Err(Ok(AuthError::FundsLocked))
+    );
+}
+
+#[test]
+fn test_shortening_lock_rejected() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let holder = Address::generate(&env);
+    client.lock_until(&admin, &holder, &900);
+
+    let result = client.try_lock_until(&admin, &holder, &400);
+    assert_eq!(result, Err(Ok(AuthError::LockShortened)));
+    assert_eq!(client.get_unlock_time(&holder), 900);
+}
+
+#[test]
+fn test_lock_until_requires_admin() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+    let holder = Address::generate(&env);
+    let result = client.try_lock_until(&holder, &holder, &900);
+    assert_eq!(result, Err(Ok(AuthError::NotAdmin)));
+}
+
+#[test]
+fn test_negative_amounts_rejected() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.set_balance(&admin, &alice, &100);
+
+    assert_eq!(
+        client.try_set_balance(&admin, &bob, &-1),
+        Err(Ok(AuthError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &-1),
+        Err(Ok(AuthError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &bob, &-1),
+        Err(Ok(AuthError::InvalidAmount))
+    );
+    assert_eq!(client.get_balance(&alice), 100);
+    assert_eq!(client.get_balance(&bob), 0);
+    assert_eq!(client.get_allowance(&alice, &bob), 0);
+}
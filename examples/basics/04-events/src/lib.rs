@@ -69,42 +69,54 @@
 
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN,
+    Env, Symbol, Vec,
+};
 
-/// Event-emitting contract demonstrating both basic emission and
-/// query-friendly topic design.
+/// Version of the transfer event schema this contract emits, carried in
+/// topic[3] of [`EventsContract::emit_transfer_v2`].
 ///
-/// Demonstrates how to emit well-structured events in Soroban contracts using:
-///
-/// - **Custom event types** – `#[contracttype]` enums/structs as event data
-/// - **Multiple topics** – up to 4 topic slots (contract address consumes none)
-/// - **Indexed parameters** – placing searchable fields in topics, payload in data
-/// - **Naming conventions** – `(contract_name, action)` as the first two topics
-///
-/// ## Soroban Event Anatomy
-///
-/// ```text
-/// env.events().publish(
-///     (topic_1, topic_2, topic_3, topic_4),  // up to 4 topics; indexed for off-chain search
-///     data_payload,                           // arbitrary SCVal; not indexed
-/// );
-/// ```
-///
-/// **Topics** should contain discrete, filterable identifiers (contract name,
-/// action type, primary key, secondary key).  **Data** holds the rich payload
-/// that off-chain consumers decode after matching on topics.
-///
-/// ## Event Naming Convention
-///
-/// Adopt a consistent `(contract, action, [key...])` topic layout so that
-/// indexers and monitoring tools can build efficient filters:
+/// Bump it whenever the data layout changes, so an indexer can pick the
+/// decoder by version instead of failing on a payload it does not expect.
+pub const EVENT_SCHEMA_VERSION: u32 = 2;
+
+/// Largest `count` [`EventsContract::emit_multiple`] accepts.
+pub const MAX_EVENTS: u32 = 100;
+
+/// Largest batch [`EventsContract::emit_transfer_batch`] accepts.
 ///
-/// | Topic slot | Purpose            | Example              |
-/// |------------|--------------------|----------------------|
-/// | 0          | Contract namespace | `"events"`           |
-/// | 1          | Action name        | `"transfer"`         |
-/// | 2          | Primary index      | `sender: Address`    |
-/// | 3          | Secondary index    | `recipient: Address` |
+/// Every event adds to the invocation's resource usage, so an unbounded loop
+/// lets one caller push a transaction past its limits.
+pub const MAX_BATCH: u32 = 25;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    /// `emit_transfer_batch` was given more than `MAX_BATCH` entries.
+    BatchTooLarge = 1,
+    /// `initialize` was called a second time.
+    AlreadyInitialized = 2,
+    /// `set_dual_emit` or `set_paused` before `initialize`.
+    NotInitialized = 3,
+    /// `set_dual_emit` or `set_paused` by someone other than the admin.
+    Unauthorized = 4,
+    /// `emit_multiple` was asked for more than `MAX_EVENTS` events.
+    TooManyEvents = 5,
+    /// `guarded_emit` was given 0.
+    ZeroValue = 6,
+    /// An emit function was called while the admin has paused emission.
+    Paused = 7,
+}
+
+impl From<recipes_pausable::PausableError> for Error {
+    fn from(err: recipes_pausable::PausableError) -> Self {
+        match err {
+            recipes_pausable::PausableError::Paused => Error::Paused,
+        }
+    }
+}
 
 // ---------------------------------------------------------------------------
 // Custom event payload types
@@ -153,6 +165,142 @@ pub struct AuditTrailEventData {
     pub sequence: u32,
 }
 
+/// Payload for [`EventsContract::emit_transfer_detailed`].
+///
+/// Consumers decode it from the event data with
+/// `TransferDetails::try_from_val(&env, &data)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferDetails {
+    /// Number of units moved.
+    pub amount: i128,
+    /// Short reference chosen by the sender.
+    pub memo: Symbol,
+    /// Ledger timestamp the sender attached.
+    pub timestamp: u64,
+}
+
+/// A transfer as a typed event, with the same layout as
+/// [`EventsContract::emit_transfer`]: `("transfer", from, to)` topics and the
+/// amount as data.
+///
+/// soroban-sdk 23 generates the topic layout and `publish` from the struct
+/// with its `#[contractevent]` macro:
+///
+/// ```ignore
+/// #[contractevent(topics = ["transfer"], data_format = "single-value")]
+/// pub struct TransferEvent {
+///     #[topic]
+///     pub from: Address,
+///     #[topic]
+///     pub to: Address,
+///     pub amount: i128,
+/// }
+/// ```
+///
+/// This workspace is on soroban-sdk 21, which has no such macro, so the
+/// `publish` it would generate is written out by hand.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+impl TransferEvent {
+    /// Publish with the fields marked `#[topic]` above, in order, after the
+    /// fixed `"transfer"` topic.
+    pub fn publish(&self, env: &Env) {
+        env.events().publish(
+            (
+                symbol_short!("transfer"),
+                self.from.clone(),
+                self.to.clone(),
+            ),
+            self.amount,
+        );
+    }
+}
+
+/// Payload for [`EventsContract::emit_transfer_v2`], schema version 2.
+///
+/// Version 1 carried a bare amount; version 2 adds where in the ledger the
+/// transfer happened.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferEventV2 {
+    /// Number of units moved.
+    pub amount: i128,
+    /// Ledger timestamp at emission time.
+    pub timestamp: u64,
+    /// Ledger sequence number at emission time.
+    pub sequence: u32,
+}
+
+/// Where in the ledger an event was emitted.
+///
+/// Attach it to any payload that off-chain consumers need to order or
+/// deduplicate, rather than adding the two fields ad hoc per event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventMeta {
+    /// Ledger timestamp at emission time.
+    pub timestamp: u64,
+    /// Ledger sequence number at emission time.
+    pub sequence: u32,
+}
+
+impl EventMeta {
+    /// Read both fields from the current ledger.
+    pub fn now(env: &Env) -> Self {
+        Self {
+            timestamp: env.ledger().timestamp(),
+            sequence: env.ledger().sequence(),
+        }
+    }
+}
+
+/// The most recent event in a category, cached on chain so other contracts
+/// can read it without an indexer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LastEvent {
+    pub category: Symbol,
+    /// The event's headline number: the amount, value, id or nonce it
+    /// carries, or its timestamp or sequence when it has none. Signed
+    /// values are clamped into the `u64` range.
+    pub value: u64,
+    /// Ledger timestamp at emission time.
+    pub at: u64,
+}
+
+/// Storage keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Who may toggle dual emission.
+    Admin,
+    /// Contract version set by `initialize`.
+    Version,
+    /// Whether `emit_transfer` also emits the v2 event.
+    DualEmit,
+    /// Number of events emitted in a category. See
+    /// [`EventsContract::get_emit_count`].
+    EmitCount(Symbol),
+    /// Every entity id that has had a status change, in first-seen order.
+    Entities,
+    /// Value last stored by `set_and_emit` under a key.
+    Value(Symbol),
+    /// The most recent event in a category, kept in temporary storage. See
+    /// [`EventsContract::get_last`].
+    Last(Symbol),
+    /// Last nonce `emit_nonced` handed out to an address.
+    Nonce(Address),
+    /// Transfer event schema version stored by `initialize`. See
+    /// [`EventsContract::get_event_version`].
+    EventVersion,
+}
+
 // ---------------------------------------------------------------------------
 // Contract
 // ---------------------------------------------------------------------------
@@ -170,6 +318,13 @@ const ACTION_TRANSFER: Symbol = symbol_short!("transfer");
 const ACTION_CONFIG_UPDATE: Symbol = symbol_short!("cfg_upd");
 const ACTION_ADMIN: Symbol = symbol_short!("admin");
 const ACTION_AUDIT: Symbol = symbol_short!("audit");
+/// `get_emit_count` category of `emit_namespaced` and
+/// `emit_namespaced_addr`, whose topic[0] the caller chooses. Counting them
+/// under that topic would let anyone inflate another category, such as
+/// `"transfer"`.
+const COUNT_NAMESPACED: Symbol = symbol_short!("namespace");
+/// Same, for `emit_with_meta`.
+const COUNT_META: Symbol = symbol_short!("meta");
 
 /// Contract demonstrating structured, multi-topic event patterns.
 #[contract]
@@ -196,11 +351,20 @@ impl EventsContract {
     ///
     /// Placing both addresses in topics means an off-chain indexer can
     /// efficiently retrieve all transfers _to_ or _from_ a given address.
-    pub fn transfer(env: Env, sender: Address, recipient: Address, amount: i128, memo: u64) {
+    pub fn transfer(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        memo: u64,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
         env.events().publish(
             (CONTRACT_NS, ACTION_TRANSFER, sender, recipient),
             TransferEventData { amount, memo },
         );
+        record(&env, ACTION_TRANSFER, 1, clamp(amount));
+        Ok(())
     }
 
     // -----------------------------------------------------------------------
@@ -221,7 +385,13 @@ impl EventsContract {
     ///
     /// The config `key` is in the topics so consumers can subscribe to changes
     /// for a specific parameter (e.g. only `"max_supply"` updates).
-    pub fn update_config(env: Env, key: Symbol, old_value: u64, new_value: u64) {
+    pub fn update_config(
+        env: Env,
+        key: Symbol,
+        old_value: u64,
+        new_value: u64,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
         env.events().publish(
             (CONTRACT_NS, ACTION_CONFIG_UPDATE, key),
             ConfigUpdateEventData {
@@ -229,6 +399,8 @@ impl EventsContract {
                 new_value,
             },
         );
+        record(&env, ACTION_CONFIG_UPDATE, 1, new_value);
+        Ok(())
     }
 
     // -----------------------------------------------------------------------
@@ -245,12 +417,15 @@ impl EventsContract {
     /// | 2     | `admin: Address` | Indexed admin    |
     ///
     /// **Data:** `AdminActionEventData { action, timestamp }`
-    pub fn admin_action(env: Env, admin: Address, action: Symbol) {
+    pub fn admin_action(env: Env, admin: Address, action: Symbol) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
         let timestamp = env.ledger().timestamp();
         env.events().publish(
             (CONTRACT_NS, ACTION_ADMIN, admin),
             AdminActionEventData { action, timestamp },
         );
+        record(&env, ACTION_ADMIN, 1, timestamp);
+        Ok(())
     }
 
     // -----------------------------------------------------------------------
@@ -272,7 +447,13 @@ impl EventsContract {
     /// This pattern provides a complete audit trail: who did what, when,
     /// with additional context in the data payload. Off-chain indexers can
     /// filter by actor (topic[2]) or action type (topic[3]).
-    pub fn audit_trail(env: Env, actor: Address, action: Symbol, details: Symbol) {
+    pub fn audit_trail(
+        env: Env,
+        actor: Address,
+        action: Symbol,
+        details: Symbol,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
         let timestamp = env.ledger().timestamp();
         let sequence = env.ledger().sequence();
         env.events().publish(
@@ -283,6 +464,8 @@ impl EventsContract {
                 sequence,
             },
         );
+        record(&env, ACTION_AUDIT, 1, sequence as u64);
+        Ok(())
     }
 
     // -----------------------------------------------------------------------
@@ -292,24 +475,39 @@ impl EventsContract {
     /// Emit a simple one-topic event – demonstrates the minimal event form.
     ///
     /// Off-chain query: filter topic[0] == "simple"
-    pub fn emit_simple(env: Env, value: u64) {
+    pub fn emit_simple(env: Env, value: u64) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
         env.events().publish((symbol_short!("simple"),), value);
+        record(&env, symbol_short!("simple"), 1, value);
+        Ok(())
     }
 
     /// Emit a tagged two-topic event.
     ///
     /// Off-chain query: filter topic[0] == "tagged" AND topic[1] == <tag>
-    pub fn emit_tagged(env: Env, tag: Symbol, value: u64) {
+    pub fn emit_tagged(env: Env, tag: Symbol, value: u64) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
         env.events().publish((symbol_short!("tagged"), tag), value);
+        record(&env, symbol_short!("tagged"), 1, value);
+        Ok(())
     }
 
     /// Emit `count` indexed events – demonstrates a loop emission pattern.
     ///
     /// Demonstrates sequential event emission within a single invocation.
-    pub fn emit_multiple(env: Env, count: u32) {
+    /// The loop is bounded: a `count` over `MAX_EVENTS` fails with
+    /// [`Error::TooManyEvents`] before anything is emitted, instead of
+    /// running out of budget halfway. Returns the number of events emitted.
+    pub fn emit_multiple(env: Env, count: u32) -> Result<u32, Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        if count > MAX_EVENTS {
+            return Err(Error::TooManyEvents);
+        }
         for i in 0..count {
             env.events().publish((symbol_short!("multi"), i), i as u64);
         }
+        record(&env, symbol_short!("multi"), count as u64, count as u64);
+        Ok(count)
     }
 
     // -----------------------------------------------------------------------
@@ -327,9 +525,193 @@ impl EventsContract {
     ///   • All sends by Alice:           topic[0] == "transfer" AND topic[1] == Alice
     ///   • All receives by Bob:          topic[0] == "transfer" AND topic[2] == Bob
     ///   • Alice → Bob transfers only:   topic[0] == "transfer" AND topic[1] == Alice AND topic[2] == Bob
-    pub fn emit_transfer(env: Env, from: Address, to: Address, amount: u64) {
+    ///
+    /// While dual emission is on (see [`set_dual_emit`](Self::set_dual_emit))
+    /// the v1 event is followed by the same transfer as
+    /// [`emit_transfer_v2`](Self::emit_transfer_v2).
+    pub fn emit_transfer(env: Env, from: Address, to: Address, amount: u64) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events().publish(
+            (symbol_short!("transfer"), from.clone(), to.clone()),
+            amount,
+        );
+        record(&env, ACTION_TRANSFER, 1, amount);
+        if Self::is_dual_emit(env.clone()) {
+            Self::emit_transfer_v2(env, from, to, amount as i128)?;
+        }
+        Ok(())
+    }
+
+    /// Emits a [`TransferEvent`], the typed-event form of
+    /// [`emit_transfer`](Self::emit_transfer). The struct names each field
+    /// once and owns the topic order, so call sites cannot get it wrong.
+    pub fn emit_transfer_typed(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        TransferEvent { from, to, amount }.publish(&env);
+        record(&env, ACTION_TRANSFER, 1, clamp(amount));
+        Ok(())
+    }
+
+    /// Emits a version 2 transfer event:
+    ///   topic[0] = "transfer"
+    ///   topic[1] = from
+    ///   topic[2] = to
+    ///   topic[3] = EVENT_SCHEMA_VERSION
+    ///   data     = TransferEventV2 { amount, timestamp, sequence }
+    ///
+    /// v1 events have three topics, so an indexer tells the two apart by
+    /// topic count and version before decoding the data. Filters on the
+    /// first three topics match both.
+    pub fn emit_transfer_v2(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events().publish(
+            (symbol_short!("transfer"), from, to, EVENT_SCHEMA_VERSION),
+            TransferEventV2 {
+                amount,
+                timestamp: env.ledger().timestamp(),
+                sequence: env.ledger().sequence(),
+            },
+        );
+        record(&env, ACTION_TRANSFER, 1, clamp(amount));
+        Ok(())
+    }
+
+    /// Same 3-topic layout as [`emit_transfer`](Self::emit_transfer), with a
+    /// `#[contracttype]` struct in the data slot instead of a scalar:
+    ///   topic[0] = "transfer"
+    ///   topic[1] = from
+    ///   topic[2] = to
+    ///   data     = TransferDetails { amount, memo, timestamp }
+    ///
+    /// Filters written for `emit_transfer` match these events too; only the
+    /// decoding of the data differs.
+    pub fn emit_transfer_detailed(
+        env: Env,
+        from: Address,
+        to: Address,
+        details: TransferDetails,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), details.clone());
+        record(&env, ACTION_TRANSFER, 1, clamp(details.amount));
+        Ok(())
+    }
+
+    /// Emits one [`emit_transfer`](Self::emit_transfer)-style event per
+    /// `(from, to, amount)` entry, in order, and returns how many it emitted.
+    ///
+    /// Like `emit_multiple`, the loop is bounded: a batch over `MAX_BATCH`
+    /// is rejected with [`Error::BatchTooLarge`] before anything is emitted.
+    pub fn emit_transfer_batch(
+        env: Env,
+        transfers: Vec<(Address, Address, i128)>,
+    ) -> Result<u32, Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        if transfers.len() > MAX_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+        let mut last = 0;
+        for (from, to, amount) in transfers.iter() {
+            env.events()
+                .publish((symbol_short!("transfer"), from, to), amount);
+            last = amount;
+        }
+        record(&env, ACTION_TRANSFER, transfers.len() as u64, clamp(last));
+        Ok(transfers.len())
+    }
+
+    /// Emits a transfer event with hashed addresses in the topics:
+    ///   topic[0] = "xfer_hash"
+    ///   topic[1] = hash_address(from)
+    ///   topic[2] = hash_address(to)
+    ///   data     = amount
+    ///
+    /// The raw addresses never appear in the indexed topics. Someone who
+    /// already knows an address can still filter on its digest, computed
+    /// with [`hash_address`](Self::hash_address) or the same SHA-256 off
+    /// chain. The digest is not secret: anyone can hash a candidate address
+    /// and compare.
+    pub fn emit_transfer_hashed(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: u64,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        let from = Self::hash_address(env.clone(), from);
+        let to = Self::hash_address(env.clone(), to);
+        env.events()
+            .publish((symbol_short!("xfer_hash"), from, to), amount);
+        record(&env, symbol_short!("xfer_hash"), 1, amount);
+        Ok(())
+    }
+
+    /// SHA-256 of `addr`'s XDR encoding (`ScVal::Address`), as used in
+    /// [`emit_transfer_hashed`](Self::emit_transfer_hashed) topics.
+    pub fn hash_address(env: Env, addr: Address) -> BytesN<32> {
+        env.crypto().sha256(&addr.to_xdr(&env)).into()
+    }
+
+    /// Emits a SEP-41 mint event:
+    ///   topic[0] = "mint"
+    ///   topic[1] = admin  — who minted
+    ///   topic[2] = to     — who received the new units
+    ///   data     = amount (i128)
+    ///
+    /// Together with `emit_transfer` and `emit_burn` this is the event trio
+    /// token indexers follow to rebuild balances and total supply.
+    pub fn emit_mint(env: Env, admin: Address, to: Address, amount: i128) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
         env.events()
-            .publish((symbol_short!("transfer"), from, to), amount);
+            .publish((symbol_short!("mint"), admin, to), amount);
+        record(&env, symbol_short!("mint"), 1, clamp(amount));
+        Ok(())
+    }
+
+    /// Emits a SEP-41 burn event:
+    ///   topic[0] = "burn"
+    ///   topic[1] = from   — whose units were destroyed
+    ///   data     = amount (i128)
+    pub fn emit_burn(env: Env, from: Address, amount: i128) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events().publish((symbol_short!("burn"), from), amount);
+        record(&env, symbol_short!("burn"), 1, clamp(amount));
+        Ok(())
+    }
+
+    /// Emits a SEP-41 approval event:
+    ///   topic[0] = "approve"
+    ///   topic[1] = owner
+    ///   topic[2] = spender
+    ///   data     = (amount, expiration_ledger)
+    ///
+    /// The allowance lapses after `expiration_ledger`, so an indexer needs it
+    /// to know whether an allowance is still live without asking the token.
+    pub fn emit_approval(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events().publish(
+            (symbol_short!("approve"), owner, spender),
+            (amount, expiration_ledger),
+        );
+        record(&env, symbol_short!("approve"), 1, clamp(amount));
+        Ok(())
     }
 
     /// Emits a namespaced event using a 3-topic hierarchy:
@@ -345,14 +727,41 @@ impl EventsContract {
     ///   • Catch swaps on one pool   → all three topics fixed
     ///
     /// Keep category and action as short Symbols (≤ 9 chars, symbol_short!).
+    ///
+    /// Counted under `"namespace"` whatever the category.
     pub fn emit_namespaced(
         env: Env,
         category: Symbol,
         action: Symbol,
         pool_id: Symbol,
         amount: u64,
-    ) {
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
         env.events().publish((category, action, pool_id), amount);
+        record(&env, COUNT_NAMESPACED, 1, amount);
+        Ok(())
+    }
+
+    /// Same 3-topic hierarchy as [`emit_namespaced`](Self::emit_namespaced),
+    /// for pools identified by their contract address:
+    ///   topic[0] = category
+    ///   topic[1] = action
+    ///   topic[2] = pool (Address)
+    ///   data     = amount (i128)
+    ///
+    /// Indexers match an Address topic exactly as they match a Symbol one:
+    /// by comparing the encoded `ScVal`. Counted under `"namespace"`.
+    pub fn emit_namespaced_addr(
+        env: Env,
+        category: Symbol,
+        action: Symbol,
+        pool: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events().publish((category, action, pool), amount);
+        record(&env, COUNT_NAMESPACED, 1, clamp(amount));
+        Ok(())
     }
 
     /// Emits a status-change event with a 4-topic layout:
@@ -366,13 +775,311 @@ impl EventsContract {
     ///   • Any status change for entity X
     ///   • Any transition *from* a specific state (e.g. "pending" → anything)
     ///   • Specific old → new transitions for audit trails
-    pub fn emit_status_change(env: Env, entity_id: Symbol, old_status: Symbol, new_status: Symbol) {
+    ///
+    /// The first change for an entity also adds it to the registry behind
+    /// [`get_entities`](Self::get_entities).
+    pub fn emit_status_change(
+        env: Env,
+        entity_id: Symbol,
+        old_status: Symbol,
+        new_status: Symbol,
+    ) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        register_entity(&env, &entity_id);
         let ledger = env.ledger().sequence();
         env.events().publish(
             (symbol_short!("status"), entity_id, old_status, new_status),
             ledger,
         );
+        record(&env, symbol_short!("status"), 1, ledger as u64);
+        Ok(())
+    }
+
+    /// Emits an event whose data is a tuple of mixed types:
+    ///   topic[0] = "composite"
+    ///   data     = (id, kind, who)
+    ///
+    /// A tuple travels as an `ScVal::Vec`. Consumers decode it in one go
+    /// with the same element types in the same order:
+    /// `<(u64, Symbol, Address)>::try_from_val(&env, &data)`.
+    pub fn emit_composite(env: Env, id: u64, kind: Symbol, who: Address) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events()
+            .publish((symbol_short!("composite"),), (id, kind, who));
+        record(&env, symbol_short!("composite"), 1, id);
+        Ok(())
     }
+
+    /// Emits a gauge/metric event for monitoring:
+    ///   topic[0] = "metric"
+    ///   topic[1] = name  (e.g. "tvl", "net_pos")
+    ///   topic[2] = unit  (e.g. "usdc", "bps")
+    ///   data     = value (i128)
+    ///
+    /// The value is signed, so net changes such as a position going down can
+    /// be reported directly. A dashboard filters on name, and on unit when
+    /// one metric is reported in several.
+    pub fn emit_metric(env: Env, name: Symbol, value: i128, unit: Symbol) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events()
+            .publish((symbol_short!("metric"), name, unit), value);
+        record(&env, symbol_short!("metric"), 1, clamp(value));
+        Ok(())
+    }
+
+    /// Emits an authenticated event carrying the caller's next nonce:
+    ///   topic[0] = "nonced"
+    ///   topic[1] = caller
+    ///   data     = nonce (u64)
+    ///
+    /// Each address has its own sequence starting at 1 and growing by one
+    /// per call, so an indexer can dedupe on `(caller, nonce)` and spot a
+    /// gap. Requiring the caller's auth stops anyone else advancing it.
+    pub fn emit_nonced(env: Env, caller: Address) -> Result<u64, Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        caller.require_auth();
+        let key = DataKey::Nonce(caller.clone());
+        let nonce = env.storage().persistent().get::<_, u64>(&key).unwrap_or(0) + 1;
+        env.storage().persistent().set(&key, &nonce);
+        env.events()
+            .publish((symbol_short!("nonced"), caller), nonce);
+        record(&env, symbol_short!("nonced"), 1, nonce);
+        Ok(nonce)
+    }
+
+    /// Emits an event with the ledger metadata next to its value:
+    ///   topic[0] = action
+    ///   data     = (value, EventMeta { timestamp, sequence })
+    ///
+    /// Both metadata fields come from `env.ledger()`, so callers cannot
+    /// backdate an event. Counted under `"meta"` whatever the action.
+    pub fn emit_with_meta(env: Env, action: Symbol, value: u64) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events()
+            .publish((action,), (value, EventMeta::now(&env)));
+        record(&env, COUNT_META, 1, value);
+        Ok(())
+    }
+
+    /// Store `value` under `key`, emitting only if it changed:
+    ///   topic[0] = "changed"
+    ///   topic[1] = key
+    ///   data     = (old, new)
+    ///
+    /// Returns whether an event was emitted. Writing the same value again is
+    /// silent, so indexers see one event per real change instead of one per
+    /// call. The first write for a key always emits, with `old` as 0.
+    pub fn set_and_emit(env: Env, key: Symbol, value: u64) -> Result<bool, Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        let storage_key = DataKey::Value(key.clone());
+        let old: Option<u64> = env.storage().persistent().get(&storage_key);
+        if old == Some(value) {
+            return Ok(false);
+        }
+        env.storage().persistent().set(&storage_key, &value);
+        env.events()
+            .publish((symbol_short!("changed"), key), (old.unwrap_or(0), value));
+        record(&env, symbol_short!("changed"), 1, value);
+        Ok(true)
+    }
+
+    /// Emits a `("diag",)` event with `value`, then fails with
+    /// [`Error::ZeroValue`] if `value` is 0.
+    ///
+    /// Demonstrates that a failed invocation rolls back everything it did,
+    /// events included: the "diag" event only survives when the call
+    /// succeeds. To report why something failed, return a descriptive error
+    /// rather than emitting an event first.
+    ///
+    /// The test host still lists events from a failed call in
+    /// `env.events().all()`, flagged `failed_call` in
+    /// `env.host().get_events()`; they never reach the ledger.
+    pub fn guarded_emit(env: Env, value: u64) -> Result<(), Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        env.events().publish((symbol_short!("diag"),), value);
+        record(&env, symbol_short!("diag"), 1, value);
+        if value == 0 {
+            return Err(Error::ZeroValue);
+        }
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Lifecycle
+    // -----------------------------------------------------------------------
+
+    /// Set the admin, who may pause emission and toggle dual emission, and
+    /// the contract version.
+    ///
+    /// Emits `("init", admin)` with `version`, so an indexer learns when the
+    /// contract came up and which version it is running from the first event.
+    ///
+    /// `admin` must authorize the call, so nobody can front-run deployment
+    /// and name themselves admin.
+    pub fn initialize(env: Env, admin: Address, version: u32) -> Result<(), Error> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Version, &version);
+        env.storage()
+            .instance()
+            .set(&DataKey::EventVersion, &EVENT_SCHEMA_VERSION);
+        env.events()
+            .publish((symbol_short!("init"), admin), version);
+        record(&env, symbol_short!("init"), 1, version as u64);
+        Ok(())
+    }
+
+    /// The version passed to `initialize`, or 0 before it.
+    pub fn get_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Version).unwrap_or(0)
+    }
+
+    /// Pause or resume every emit function. While paused they fail with
+    /// [`Error::Paused`] and emit nothing.
+    ///
+    /// Emits the standard `("pausable", "paused" | "unpaused")` event with
+    /// `caller` as data.
+    pub fn set_paused(env: Env, caller: Address, paused: bool) -> Result<(), Error> {
+        require_admin(&env, &caller)?;
+        recipes_pausable::set_paused(&env, paused);
+        recipes_pausable::emit_pause_changed(&env, &caller, paused);
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        recipes_pausable::is_paused(&env)
+    }
+
+    // -----------------------------------------------------------------------
+    // Schema migration
+    // -----------------------------------------------------------------------
+
+    /// Turn dual emission on for a migration window, or off once indexers
+    /// have moved to v2.
+    pub fn set_dual_emit(env: Env, admin: Address, enabled: bool) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::DualEmit, &enabled);
+        Ok(())
+    }
+
+    pub fn is_dual_emit(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::DualEmit)
+            .unwrap_or(false)
+    }
+
+    /// The transfer event schema version `initialize` stored, which is
+    /// [`EVENT_SCHEMA_VERSION`] at the time, or 0 before it.
+    pub fn get_event_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EventVersion)
+            .unwrap_or(0)
+    }
+
+    // -----------------------------------------------------------------------
+    // On-chain accounting
+    // -----------------------------------------------------------------------
+
+    /// Number of events this contract has emitted in `category`.
+    ///
+    /// The category is the action name: topic[1] for events under the
+    /// `"events"` namespace (`"transfer"`, `"cfg_upd"`, `"admin"`,
+    /// `"audit"`), topic[0] for every other event. Transfers are counted
+    /// under `"transfer"` whichever layout they use. Events whose topic[0]
+    /// the caller chooses are counted under a fixed category instead:
+    /// `"namespace"` for `emit_namespaced` and `emit_namespaced_addr`,
+    /// `"meta"` for `emit_with_meta`.
+    ///
+    /// An indexer that has seen fewer events in a category than this count
+    /// knows it missed some and should backfill.
+    pub fn get_emit_count(env: Env, category: Symbol) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EmitCount(category))
+            .unwrap_or(0)
+    }
+
+    /// Every entity id that has had a status change, each once, in the
+    /// order they first appeared.
+    ///
+    /// Lets a consumer discover which entities exist before filtering
+    /// `"status"` events by topic[1].
+    pub fn get_entities(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Entities)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_entity_count(env: Env) -> u32 {
+        Self::get_entities(env).len()
+    }
+
+    /// The most recent event in `category`, or `None` if there has not been
+    /// one recently.
+    ///
+    /// The cache lives in temporary storage: it is cheap to write on every
+    /// emit, but it expires once its TTL runs out, so `None` means "nothing
+    /// recent", not "never".
+    pub fn get_last(env: Env, category: Symbol) -> Option<LastEvent> {
+        env.storage().temporary().get(&DataKey::Last(category))
+    }
+}
+
+/// Require `caller`'s auth and that it is the admin set by `initialize`.
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    if *caller != admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Add `entity_id` to the registry unless it is already there.
+fn register_entity(env: &Env, entity_id: &Symbol) {
+    let mut entities = EventsContract::get_entities(env.clone());
+    if entities.contains(entity_id) {
+        return;
+    }
+    entities.push_back(entity_id.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Entities, &entities);
+}
+
+/// Add `n` to the emission counter of `category` and cache the last of
+/// those events, whose headline number is `value`.
+fn record(env: &Env, category: Symbol, n: u64, value: u64) {
+    if n == 0 {
+        return;
+    }
+    let key = DataKey::EmitCount(category.clone());
+    let count: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(count + n));
+
+    let last = LastEvent {
+        category: category.clone(),
+        value,
+        at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .temporary()
+        .set(&DataKey::Last(category), &last);
+}
+
+/// `value` clamped into the `u64` range, for [`LastEvent::value`].
+fn clamp(value: i128) -> u64 {
+    value.clamp(0, u64::MAX as i128) as u64
 }
 
 mod test;
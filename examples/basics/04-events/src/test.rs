@@ -4,30 +4,42 @@
 //! - Correct number of events emitted
 //! - Correct topic count and topic values (including indexed parameters)
 //! - Correct data payload deserialization for custom types
+//!
+//! `test_structured_events_golden` compares the full event stream of one
+//! call to each structured API with `golden/structured_events.txt`. After an
+//! intentional change to a topic layout or payload, regenerate the file and
+//! review it alongside the code:
+//!
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test -p events test_structured_events_golden
+//! git diff examples/basics/04-events/golden/
+//! ```
+//!
+//! `test_indexer_fixtures` does the same for `testdata/events.fixtures`,
+//! the XDR event fixtures published for indexer projects.
 
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
+use recipes_testutils::{
+    all_events_for, assert_golden, assert_topic_address, assert_topic_symbol, Snapshot, TestEnv,
+};
 use soroban_sdk::{
     symbol_short,
-    testutils::{Address as _, Events as _},
-    Address, Env, Symbol, TryFromVal,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events as _, Ledger as _},
+    vec,
+    xdr::{
+        AccountId, ContractEventBody, ContractEventType, Hash, Limits, PublicKey, ReadXdr,
+        ScAddress, ScVal, Uint256, WriteXdr,
+    },
+    Address, Bytes, BytesN, IntoVal, Symbol, TryFromVal, Vec,
 };
 
-// ---------------------------------------------------------------------------
-// Helpers
-// ---------------------------------------------------------------------------
-
-fn make_env_and_client() -> (Env, Address, EventsContractClient<'static>) {
-    let env = Env::default();
-    let contract_id = env.register_contract(None, EventsContract);
-    let client = EventsContractClient::new(&env, &contract_id);
-    (env, contract_id, client)
-}
-
 #[test]
 fn test_naming_convention_namespace_and_action_slots_are_stable() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let a1 = Address::generate(&env);
     let a2 = Address::generate(&env);
@@ -53,11 +65,8 @@ fn test_naming_convention_namespace_and_action_slots_are_stable() {
     {
         let (_id, topics, _data) = events.get(index as u32).unwrap();
 
-        let namespace: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-        let action: Symbol = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-
-        assert_eq!(namespace, CONTRACT_NS);
-        assert_eq!(action, expected_action);
+        assert_topic_symbol(&env, &topics, 0, &CONTRACT_NS);
+        assert_topic_symbol(&env, &topics, 1, &expected_action);
     }
 }
 
@@ -67,7 +76,7 @@ fn test_naming_convention_namespace_and_action_slots_are_stable() {
 
 #[test]
 fn test_transfer_emits_one_event() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -80,7 +89,7 @@ fn test_transfer_emits_one_event() {
 
 #[test]
 fn test_transfer_event_has_four_topics() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -94,7 +103,7 @@ fn test_transfer_event_has_four_topics() {
 
 #[test]
 fn test_transfer_topic_namespace_and_action() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -103,17 +112,15 @@ fn test_transfer_topic_namespace_and_action() {
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
 
     // Topic 0: contract namespace
-    let ns: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    assert_eq!(ns, symbol_short!("events"));
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("events"));
 
     // Topic 1: action name
-    let action: Symbol = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-    assert_eq!(action, symbol_short!("transfer"));
+    assert_topic_symbol(&env, &topics, 1, &symbol_short!("transfer"));
 }
 
 #[test]
 fn test_transfer_indexed_addresses_in_topics() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -122,17 +129,15 @@ fn test_transfer_indexed_addresses_in_topics() {
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
 
     // Topic 2: sender (indexed for off-chain search)
-    let t_sender = Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
-    assert_eq!(t_sender, sender);
+    assert_topic_address(&env, &topics, 2, &sender);
 
     // Topic 3: recipient (indexed for off-chain search)
-    let t_recipient = Address::try_from_val(&env, &topics.get(3).unwrap()).unwrap();
-    assert_eq!(t_recipient, recipient);
+    assert_topic_address(&env, &topics, 3, &recipient);
 }
 
 #[test]
 fn test_transfer_structured_data_payload() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -154,7 +159,7 @@ fn test_transfer_structured_data_payload() {
 
 #[test]
 fn test_config_update_emits_one_event() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     client.update_config(&symbol_short!("max_sup"), &100, &200);
 
@@ -164,7 +169,7 @@ fn test_config_update_emits_one_event() {
 
 #[test]
 fn test_config_update_event_has_three_topics() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     client.update_config(&symbol_short!("fee"), &5, &10);
 
@@ -174,22 +179,20 @@ fn test_config_update_event_has_three_topics() {
 
 #[test]
 fn test_config_update_topic_namespace_and_action() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     client.update_config(&symbol_short!("fee"), &5, &10);
 
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
 
-    let ns: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    assert_eq!(ns, symbol_short!("events"));
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("events"));
 
-    let action: Symbol = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-    assert_eq!(action, symbol_short!("cfg_upd"));
+    assert_topic_symbol(&env, &topics, 1, &symbol_short!("cfg_upd"));
 }
 
 #[test]
 fn test_config_update_indexed_key_in_topic() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let key = symbol_short!("max_sup");
     client.update_config(&key, &50, &100);
@@ -197,13 +200,12 @@ fn test_config_update_indexed_key_in_topic() {
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
 
     // Topic 2: the config key — indexed so consumers can filter by key name
-    let t_key: Symbol = Symbol::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
-    assert_eq!(t_key, key);
+    assert_topic_symbol(&env, &topics, 2, &key);
 }
 
 #[test]
 fn test_config_update_structured_data_payload() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     client.update_config(&symbol_short!("rate"), &10, &20);
 
@@ -220,51 +222,70 @@ fn test_config_update_structured_data_payload() {
 
 #[test]
 fn test_event_emission_exists() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
     client.emit_simple(&100);
     assert!(!env.events().all().is_empty());
 }
 
 #[test]
 fn test_event_count_single() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
     client.emit_simple(&42);
     assert_eq!(env.events().all().len(), 1);
 }
 
 #[test]
 fn test_event_count_multiple() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
     client.emit_multiple(&3);
     assert_eq!(env.events().all().len(), 3);
 }
 
+#[test]
+fn test_emit_multiple_returns_count() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    assert_eq!(client.emit_multiple(&MAX_EVENTS), MAX_EVENTS);
+    assert_eq!(env.events().all().len(), MAX_EVENTS);
+}
+
+#[test]
+fn test_emit_multiple_rejects_too_many_events() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    assert_eq!(
+        client.try_emit_multiple(&(MAX_EVENTS + 1)),
+        Err(Ok(Error::TooManyEvents))
+    );
+    assert_eq!(
+        client.try_emit_multiple(&1_000_000),
+        Err(Ok(Error::TooManyEvents))
+    );
+    assert_eq!(env.events().all().len(), 0);
+    assert_eq!(client.get_emit_count(&symbol_short!("multi")), 0);
+}
+
 #[test]
 fn test_topic_structure_simple() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
     client.emit_simple(&99);
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
     assert_eq!(topics.len(), 1);
-    let t0: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    assert_eq!(t0, symbol_short!("simple"));
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("simple"));
 }
 
 #[test]
 fn test_topic_structure_tagged() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
     let tag = symbol_short!("mytag");
     client.emit_tagged(&tag, &50);
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
     assert_eq!(topics.len(), 2);
-    let t0: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    let t1: Symbol = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-    assert_eq!(t0, symbol_short!("tagged"));
-    assert_eq!(t1, tag);
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("tagged"));
+    assert_topic_symbol(&env, &topics, 1, &tag);
 }
 
 #[test]
 fn test_payload_values() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
     let value = 12345u64;
     client.emit_simple(&value);
     let (_id, _topics, data) = env.events().all().get(0).unwrap();
@@ -274,7 +295,7 @@ fn test_payload_values() {
 
 #[test]
 fn test_zero_events_on_empty_emit() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
     client.emit_multiple(&0);
     assert_eq!(env.events().all().len(), 0);
 }
@@ -283,33 +304,29 @@ fn test_zero_events_on_empty_emit() {
 
 #[test]
 fn test_emit_transfer_topic_layout() {
-    let env = Env::default();
-    let contract_id = env.register_contract(None, EventsContract);
-    let client = EventsContractClient::new(&env, &contract_id);
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let from = Address::generate(&env);
     let to = Address::generate(&env);
 
     client.emit_transfer(&from, &to, &500);
 
-    let events = env.events().all();
+    let events = all_events_for(&env, &contract_id);
     assert_eq!(events.len(), 1);
 
-    let (_id, topics, data) = events.get(0).unwrap();
+    let (topics, data) = &events[0];
 
     // topic[0] must always be the action name for event-type filtering
-    let action: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    assert_eq!(action, symbol_short!("transfer"));
+    assert_topic_symbol(&env, topics, 0, &symbol_short!("transfer"));
 
     // topic[1] = from-address; topic[2] = to-address
     // These positions enable per-address history queries off-chain.
-    let t_from: Address = Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-    let t_to: Address = Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
-    assert_eq!(t_from, from);
-    assert_eq!(t_to, to);
+    assert_topic_address(&env, topics, 1, &from);
+    assert_topic_address(&env, topics, 2, &to);
 
     // amount lives in data — readable after filtering, but not a filter key
-    let amount: u64 = u64::try_from_val(&env, &data).unwrap();
+    let amount: u64 = u64::try_from_val(&env, data).unwrap();
     assert_eq!(amount, 500);
 }
 
@@ -317,9 +334,8 @@ fn test_emit_transfer_topic_layout() {
 fn test_emit_transfer_independent_senders_queryable() {
     // Verifies that multiple transfers can be distinguished by topic[1] (sender).
     // An off-chain indexer watching topic[1] == alice sees only one event.
-    let env = Env::default();
-    let contract_id = env.register_contract(None, EventsContract);
-    let client = EventsContractClient::new(&env, &contract_id);
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let alice = Address::generate(&env);
     let bob = Address::generate(&env);
@@ -328,19 +344,17 @@ fn test_emit_transfer_independent_senders_queryable() {
     client.emit_transfer(&alice, &carol, &100);
     client.emit_transfer(&bob, &carol, &200);
 
-    let events = env.events().all();
+    let events = all_events_for(&env, &contract_id);
     assert_eq!(events.len(), 2);
 
     // Both events share the same action topic, so a "get all transfers" query works.
-    for i in 0..2u32 {
-        let (_id, topics, _data) = events.get(i).unwrap();
-        let action: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-        assert_eq!(action, symbol_short!("transfer"));
+    for (topics, _data) in &events {
+        assert_topic_symbol(&env, topics, 0, &symbol_short!("transfer"));
     }
 
     // Sender is distinguishable via topic[1].
-    let (_id0, topics0, _) = events.get(0).unwrap();
-    let (_id1, topics1, _) = events.get(1).unwrap();
+    let (topics0, _) = &events[0];
+    let (topics1, _) = &events[1];
     let sender0: Address = Address::try_from_val(&env, &topics0.get(1).unwrap()).unwrap();
     let sender1: Address = Address::try_from_val(&env, &topics1.get(1).unwrap()).unwrap();
     assert_ne!(
@@ -349,11 +363,501 @@ fn test_emit_transfer_independent_senders_queryable() {
     );
 }
 
+#[test]
+fn test_emit_transfer_typed_matches_manual_tuple() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    client.emit_transfer(&from, &to, &300);
+    client.emit_transfer_typed(&from, &to, &300);
+
+    // The typed event and the hand-built tuple publish identical topics, so
+    // one indexer filter matches both. Only the amount's type differs.
+    let events = all_events_for(&env, &contract_id);
+    let (manual_topics, manual_data) = &events[0];
+    let (typed_topics, typed_data) = &events[1];
+    assert_eq!(typed_topics.len(), 3);
+    assert_topic_symbol(&env, typed_topics, 0, &symbol_short!("transfer"));
+    assert_topic_address(&env, typed_topics, 1, &from);
+    assert_topic_address(&env, typed_topics, 2, &to);
+    for i in 0..3 {
+        assert_eq!(
+            ScVal::try_from_val(&env, &typed_topics.get(i).unwrap()).unwrap(),
+            ScVal::try_from_val(&env, &manual_topics.get(i).unwrap()).unwrap(),
+            "topic {i}"
+        );
+    }
+    assert_eq!(u64::try_from_val(&env, manual_data).unwrap(), 300);
+    assert_eq!(i128::try_from_val(&env, typed_data).unwrap(), 300);
+}
+
+#[test]
+fn test_emit_transfer_v2_carries_schema_version() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1_700_000_000;
+        l.sequence_number = 77;
+    });
+
+    client.emit_transfer_v2(&from, &to, &900);
+    let (_id, topics, data) = env.events().all().get(0).unwrap();
+    assert_eq!(topics.len(), 4);
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("transfer"));
+    assert_topic_address(&env, &topics, 1, &from);
+    assert_topic_address(&env, &topics, 2, &to);
+    let version = u32::try_from_val(&env, &topics.get(3).unwrap()).unwrap();
+    assert_eq!(version, EVENT_SCHEMA_VERSION);
+    assert_eq!(
+        TransferEventV2::try_from_val(&env, &data).unwrap(),
+        TransferEventV2 {
+            amount: 900,
+            timestamp: 1_700_000_000,
+            sequence: 77,
+        }
+    );
+}
+
+#[test]
+fn test_initialize_emits_init_event_once() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let admin = Address::generate(&env);
+    assert_eq!(client.get_version(), 0);
+
+    client.initialize(&admin, &3);
+    let events = all_events_for(&env, &contract_id);
+    assert_eq!(events.len(), 1);
+    let (topics, data) = &events[0];
+    assert_eq!(topics.len(), 2);
+    assert_topic_symbol(&env, topics, 0, &symbol_short!("init"));
+    assert_topic_address(&env, topics, 1, &admin);
+    assert_eq!(u32::try_from_val(&env, data).unwrap(), 3);
+    assert_eq!(client.get_version(), 3);
+
+    // A second call fails and changes nothing.
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_initialize(&other, &4),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.get_version(), 3);
+    assert_eq!(all_events_for(&env, &contract_id).len(), 1);
+}
+
+#[test]
+fn test_initialize_stores_event_version() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    assert_eq!(client.get_event_version(), 0);
+
+    client.initialize(&Address::generate(&env), &3);
+    assert_eq!(client.get_event_version(), EVENT_SCHEMA_VERSION);
+    let stored: Option<u32> = env.as_contract(&contract_id, || {
+        env.storage().instance().get(&DataKey::EventVersion)
+    });
+    assert_eq!(stored, Some(EVENT_SCHEMA_VERSION));
+}
+
+#[test]
+fn test_initialize_requires_admin_auth() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let admin = Address::generate(&env);
+
+    // Without the admin's signature nobody can claim the contract for them.
+    env.set_auths(&[]);
+    assert!(matches!(client.try_initialize(&admin, &1), Err(Err(_))));
+    assert_eq!(client.get_version(), 0);
+
+    env.mock_all_auths();
+    client.initialize(&admin, &1);
+    assert_eq!(
+        env.auths(),
+        [(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "initialize"),
+                    (admin.clone(), 1u32).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+}
+
+#[test]
+fn test_paused_emission_fails_and_emits_nothing() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    client.initialize(&admin, &1);
+
+    client.set_paused(&admin, &true);
+    assert!(client.is_paused());
+    let before = all_events_for(&env, &contract_id).len();
+    assert_eq!(client.try_emit_simple(&1), Err(Ok(Error::Paused)));
+    assert_eq!(client.try_emit_multiple(&3), Err(Ok(Error::Paused)));
+    assert_eq!(client.try_emit_nonced(&alice), Err(Ok(Error::Paused)));
+    assert_eq!(client.get_emit_count(&symbol_short!("simple")), 0);
+    // Only the events of successful calls count; the failed ones were
+    // rolled back.
+    let committed = host_events_with_status(&env)
+        .iter()
+        .filter(|(failed, _)| !failed)
+        .count();
+    assert_eq!(committed, before);
+
+    client.set_paused(&admin, &false);
+    assert!(!client.is_paused());
+    client.emit_simple(&2);
+    let (topics, data) = all_events_for(&env, &contract_id).pop().unwrap();
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("simple"));
+    assert_eq!(u64::try_from_val(&env, &data).unwrap(), 2);
+    assert_eq!(client.get_emit_count(&symbol_short!("simple")), 1);
+    assert_eq!(client.emit_nonced(&alice), 1);
+}
+
+#[test]
+fn test_set_paused_admin_only() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_paused(&admin, &true),
+        Err(Ok(Error::NotInitialized))
+    );
+
+    client.initialize(&admin, &1);
+    assert_eq!(
+        client.try_set_paused(&stranger, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert!(!client.is_paused());
+
+    // The admin's change publishes the standard pausable event.
+    client.set_paused(&admin, &true);
+    let (topics, data) = all_events_for(&env, &contract_id).pop().unwrap();
+    assert_topic_symbol(&env, &topics, 0, &recipes_pausable::NAMESPACE);
+    assert_topic_symbol(&env, &topics, 1, &symbol_short!("paused"));
+    assert_eq!(Address::try_from_val(&env, &data).unwrap(), admin);
+}
+
+#[test]
+fn test_emit_transfer_dual_emits_during_migration() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    // The test host's event log accumulates across calls, starting with
+    // the init event.
+    client.initialize(&admin, &1);
+
+    // Off by default: v1 only.
+    client.emit_transfer(&from, &to, &10);
+    assert_eq!(env.events().all().len(), 2);
+
+    client.set_dual_emit(&admin, &true);
+    client.emit_transfer(&from, &to, &10);
+    let events = env.events().all();
+    assert_eq!(events.len(), 4);
+    let (_id, v1_topics, v1_data) = events.get(2).unwrap();
+    let (_id, v2_topics, v2_data) = events.get(3).unwrap();
+    assert_eq!(v1_topics.len(), 3);
+    assert_eq!(u64::try_from_val(&env, &v1_data).unwrap(), 10);
+    assert_eq!(v2_topics.len(), 4);
+    assert_eq!(
+        TransferEventV2::try_from_val(&env, &v2_data)
+            .unwrap()
+            .amount,
+        10
+    );
+
+    client.set_dual_emit(&admin, &false);
+    client.emit_transfer(&from, &to, &10);
+    assert_eq!(env.events().all().len(), 5);
+    assert_eq!(client.get_emit_count(&symbol_short!("transfer")), 4);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_dual_emit(&stranger, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_initialize(&stranger, &2),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_emit_transfer_detailed_struct_round_trips() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    let details = TransferDetails {
+        amount: 7_500,
+        memo: symbol_short!("invoice9"),
+        timestamp: 1_700_000_000,
+    };
+
+    client.emit_transfer_detailed(&from, &to, &details);
+
+    // Read straight from `env.events().all()`: (contract, topics, data).
+    let (emitter, _topics, data) = env.events().all().last().unwrap();
+    assert_eq!(emitter, contract_id);
+
+    // The data slot holds the struct as an `ScMap`; `TryFromVal` rebuilds it.
+    let decoded = TransferDetails::try_from_val(&env, &data).unwrap();
+    assert_eq!(decoded, details);
+
+    // Decoding as the scalar `emit_transfer` uses fails, so a consumer must
+    // know which payload to expect.
+    assert!(u64::try_from_val(&env, &data).is_err());
+}
+
+#[test]
+fn test_emit_transfer_detailed_matches_transfer_topic_layout() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    client.emit_transfer(&from, &to, &500);
+    client.emit_transfer_detailed(
+        &from,
+        &to,
+        &TransferDetails {
+            amount: 500,
+            memo: symbol_short!("memo"),
+            timestamp: 0,
+        },
+    );
+
+    let events = all_events_for(&env, &contract_id);
+    assert_eq!(events.len(), 2);
+    let (plain, _) = &events[0];
+    let (detailed, _) = &events[1];
+
+    // Identical topics: every `emit_transfer` filter matches both events.
+    assert_eq!(detailed.len(), 3);
+    assert_eq!(plain, detailed);
+    assert_topic_symbol(&env, detailed, 0, &symbol_short!("transfer"));
+    assert_topic_address(&env, detailed, 1, &from);
+    assert_topic_address(&env, detailed, 2, &to);
+}
+
+#[test]
+fn test_emit_transfer_batch_emits_one_event_per_entry() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let batch = vec![
+        &env,
+        (alice.clone(), bob.clone(), 100i128),
+        (bob.clone(), alice.clone(), 40i128),
+        (alice.clone(), alice.clone(), 1i128),
+    ];
+
+    assert_eq!(client.emit_transfer_batch(&batch), 3);
+    let events = env.events().all();
+    assert_eq!(events.len(), 3);
+    for (i, (from, to, amount)) in batch.iter().enumerate() {
+        let (_id, topics, data) = events.get(i as u32).unwrap();
+        assert_eq!(topics.len(), 3);
+        assert_topic_symbol(&env, &topics, 0, &symbol_short!("transfer"));
+        assert_topic_address(&env, &topics, 1, &from);
+        assert_topic_address(&env, &topics, 2, &to);
+        assert_eq!(i128::try_from_val(&env, &data).unwrap(), amount);
+    }
+    assert_eq!(client.get_emit_count(&symbol_short!("transfer")), 3);
+}
+
+#[test]
+fn test_emit_transfer_batch_rejects_oversized_batch() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    let mut batch = Vec::new(&env);
+    for _ in 0..MAX_BATCH {
+        batch.push_back((from.clone(), to.clone(), 1i128));
+    }
+    assert_eq!(client.emit_transfer_batch(&batch), MAX_BATCH);
+
+    batch.push_back((from, to, 1i128));
+    assert_eq!(
+        client.try_emit_transfer_batch(&batch),
+        Err(Ok(Error::BatchTooLarge))
+    );
+    // Only the accepted batch's events remain.
+    assert_eq!(env.events().all().len(), MAX_BATCH);
+    assert_eq!(
+        client.get_emit_count(&symbol_short!("transfer")),
+        MAX_BATCH as u64
+    );
+}
+
+#[test]
+fn test_emit_transfer_batch_empty() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    assert_eq!(client.emit_transfer_batch(&Vec::new(&env)), 0);
+    assert_eq!(env.events().all().len(), 0);
+}
+
+#[test]
+fn test_emit_transfer_hashed_topics_are_stable_digests() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.emit_transfer_hashed(&alice, &bob, &10);
+    client.emit_transfer_hashed(&alice, &bob, &20);
+    let events = env.events().all();
+    let topic_hash = |i: u32, slot: u32| {
+        let (_id, topics, _data) = events.get(i).unwrap();
+        assert_topic_symbol(&env, &topics, 0, &symbol_short!("xfer_hash"));
+        BytesN::<32>::try_from_val(&env, &topics.get(slot).unwrap()).unwrap()
+    };
+
+    // Same address, same digest, and it matches the helper.
+    assert_eq!(topic_hash(0, 1), topic_hash(1, 1));
+    assert_eq!(topic_hash(0, 2), topic_hash(1, 2));
+    assert_eq!(topic_hash(0, 1), client.hash_address(&alice));
+    assert_eq!(topic_hash(0, 2), client.hash_address(&bob));
+    // Different addresses, different digests.
+    assert_ne!(topic_hash(0, 1), topic_hash(0, 2));
+
+    // The helper is the SHA-256 of the address's `ScVal` XDR.
+    let scval = ScVal::from(&alice);
+    let xdr = WriteXdr::to_xdr(&scval, Limits::none()).unwrap();
+    let expected = env.crypto().sha256(&Bytes::from_slice(&env, &xdr));
+    assert_eq!(client.hash_address(&alice), BytesN::from(expected));
+
+    let (_id, _topics, data) = events.get(1).unwrap();
+    assert_eq!(u64::try_from_val(&env, &data).unwrap(), 20);
+}
+
+#[test]
+fn test_emit_mint_sep41_layout() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+
+    let admin = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    client.emit_mint(&admin, &to, &1_000);
+
+    let events = all_events_for(&env, &contract_id);
+    assert_eq!(events.len(), 1, "One mint call must emit exactly one event");
+
+    let (topics, data) = &events[0];
+    assert_eq!(topics.len(), 3);
+    assert_topic_symbol(&env, topics, 0, &symbol_short!("mint"));
+    assert_topic_address(&env, topics, 1, &admin);
+    assert_topic_address(&env, topics, 2, &to);
+
+    // SEP-41 amounts are i128; a u64 decode must not be relied on.
+    let amount = i128::try_from_val(&env, data).unwrap();
+    assert_eq!(amount, 1_000);
+    assert!(u64::try_from_val(&env, data).is_err());
+}
+
+#[test]
+fn test_emit_burn_sep41_layout() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+
+    let from = Address::generate(&env);
+
+    client.emit_burn(&from, &250);
+
+    let events = all_events_for(&env, &contract_id);
+    assert_eq!(events.len(), 1, "One burn call must emit exactly one event");
+
+    let (topics, data) = &events[0];
+    assert_eq!(topics.len(), 2);
+    assert_topic_symbol(&env, topics, 0, &symbol_short!("burn"));
+    assert_topic_address(&env, topics, 1, &from);
+
+    let amount = i128::try_from_val(&env, data).unwrap();
+    assert_eq!(amount, 250);
+}
+
+#[test]
+fn test_emit_approval_data_tuple_order() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    client.emit_approval(&owner, &spender, &5_000, &123_456);
+
+    let events = all_events_for(&env, &contract_id);
+    assert_eq!(events.len(), 1);
+
+    let (topics, data) = &events[0];
+    assert_eq!(topics.len(), 3);
+    assert_topic_symbol(&env, topics, 0, &symbol_short!("approve"));
+    assert_topic_address(&env, topics, 1, &owner);
+    assert_topic_address(&env, topics, 2, &spender);
+
+    // Data is `(amount, expiration_ledger)`, in that order.
+    let (amount, expiration_ledger) = <(i128, u32)>::try_from_val(&env, data).unwrap();
+    assert_eq!(amount, 5_000);
+    assert_eq!(expiration_ledger, 123_456);
+    assert!(<(u32, i128)>::try_from_val(&env, data).is_err());
+}
+
+#[test]
+fn test_emit_approval_owners_distinguishable() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    client.emit_approval(&alice, &spender, &100, &1_000);
+    client.emit_approval(&bob, &spender, &200, &2_000);
+
+    let events = all_events_for(&env, &contract_id);
+    assert_eq!(events.len(), 2);
+
+    // Same action and spender; only topic[1] tells the owners apart.
+    let (topics0, _) = &events[0];
+    let (topics1, _) = &events[1];
+    assert_topic_address(&env, topics0, 1, &alice);
+    assert_topic_address(&env, topics1, 1, &bob);
+    assert_topic_address(&env, topics0, 2, &spender);
+    assert_topic_address(&env, topics1, 2, &spender);
+
+    let from_alice: std::vec::Vec<_> = events
+        .iter()
+        .filter(|(topics, _)| {
+            Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap() == alice
+        })
+        .collect();
+    assert_eq!(from_alice.len(), 1);
+    let (amount, _) = <(i128, u32)>::try_from_val(&env, &from_alice[0].1).unwrap();
+    assert_eq!(amount, 100);
+}
+
 #[test]
 fn test_emit_namespaced_three_topic_hierarchy() {
-    let env = Env::default();
-    let contract_id = env.register_contract(None, EventsContract);
-    let client = EventsContractClient::new(&env, &contract_id);
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let category = symbol_short!("defi");
     let action = symbol_short!("swap");
@@ -361,28 +865,57 @@ fn test_emit_namespaced_three_topic_hierarchy() {
 
     client.emit_namespaced(&category, &action, &pool, &1000);
 
-    let events = env.events().all();
+    let events = all_events_for(&env, &contract_id);
     assert_eq!(events.len(), 1);
 
-    let (_id, topics, data) = events.get(0).unwrap();
+    let (topics, data) = &events[0];
     assert_eq!(topics.len(), 3, "Namespaced event must carry 3 topics");
 
-    let t0: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    let t1: Symbol = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-    let t2: Symbol = Symbol::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
-    assert_eq!(t0, category);
-    assert_eq!(t1, action);
-    assert_eq!(t2, pool);
+    assert_topic_symbol(&env, topics, 0, &category);
+    assert_topic_symbol(&env, topics, 1, &action);
+    assert_topic_symbol(&env, topics, 2, &pool);
 
-    let amount: u64 = u64::try_from_val(&env, &data).unwrap();
+    let amount: u64 = u64::try_from_val(&env, data).unwrap();
     assert_eq!(amount, 1000);
 }
 
+#[test]
+fn test_emit_namespaced_addr_pools_distinguishable() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let (defi, swap) = (symbol_short!("defi"), symbol_short!("swap"));
+    let pool_a = Address::generate(&env);
+    let pool_b = Address::generate(&env);
+
+    client.emit_namespaced_addr(&defi, &swap, &pool_a, &100);
+    client.emit_namespaced_addr(&defi, &swap, &pool_b, &-40);
+    client.emit_namespaced_addr(&defi, &swap, &pool_a, &7);
+
+    // An indexer filters on an Address topic exactly like a Symbol one:
+    // compare topic[2] with the pool it wants.
+    let events = all_events_for(&env, &contract_id);
+    let amounts_for = |pool: &Address| -> std::vec::Vec<i128> {
+        events
+            .iter()
+            .filter(|(topics, _)| {
+                Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap() == *pool
+            })
+            .map(|(topics, data)| {
+                assert_eq!(topics.len(), 3);
+                assert_topic_symbol(&env, topics, 0, &defi);
+                assert_topic_symbol(&env, topics, 1, &swap);
+                i128::try_from_val(&env, data).unwrap()
+            })
+            .collect()
+    };
+    assert_eq!(amounts_for(&pool_a), [100, 7]);
+    assert_eq!(amounts_for(&pool_b), [-40]);
+}
+
 #[test]
 fn test_emit_status_change_four_topics() {
-    let env = Env::default();
-    let contract_id = env.register_contract(None, EventsContract);
-    let client = EventsContractClient::new(&env, &contract_id);
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let entity = symbol_short!("order42");
     let old_s = symbol_short!("pending");
@@ -390,27 +923,224 @@ fn test_emit_status_change_four_topics() {
 
     client.emit_status_change(&entity, &old_s, &new_s);
 
-    let events = env.events().all();
+    let events = all_events_for(&env, &contract_id);
     assert_eq!(events.len(), 1);
 
-    let (_id, topics, data) = events.get(0).unwrap();
+    let (topics, data) = &events[0];
     assert_eq!(
         topics.len(),
         4,
         "Status-change event must use all 4 topic slots"
     );
 
-    let t0: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    let t1: Symbol = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-    let t2: Symbol = Symbol::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
-    let t3: Symbol = Symbol::try_from_val(&env, &topics.get(3).unwrap()).unwrap();
-    assert_eq!(t0, symbol_short!("status"));
-    assert_eq!(t1, entity);
-    assert_eq!(t2, old_s);
-    assert_eq!(t3, new_s);
+    assert_topic_symbol(&env, topics, 0, &symbol_short!("status"));
+    assert_topic_symbol(&env, topics, 1, &entity);
+    assert_topic_symbol(&env, topics, 2, &old_s);
+    assert_topic_symbol(&env, topics, 3, &new_s);
 
     // data holds the ledger sequence for off-chain ordering / deduplication
-    let _ledger: u32 = u32::try_from_val(&env, &data).unwrap();
+    let _ledger: u32 = u32::try_from_val(&env, data).unwrap();
+}
+
+#[test]
+fn test_emit_composite_tuple_decodes() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let who = Address::generate(&env);
+    client.emit_composite(&7, &symbol_short!("order"), &who);
+
+    let (_id, topics, data) = env.events().all().get(0).unwrap();
+    assert_eq!(topics.len(), 1);
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("composite"));
+
+    // Decode the whole tuple at once, with the element types in order.
+    let (id, kind, decoded_who) = <(u64, Symbol, Address)>::try_from_val(&env, &data).unwrap();
+    assert_eq!(id, 7);
+    assert_eq!(kind, symbol_short!("order"));
+    assert_eq!(decoded_who, who);
+}
+
+#[test]
+fn test_emit_metric_signed_values_round_trip() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let name = symbol_short!("net_pos");
+    let unit = symbol_short!("usdc");
+    let values = [-42i128, 0, i128::MAX, i128::MIN];
+    for value in values {
+        client.emit_metric(&name, &value, &unit);
+    }
+
+    let events = env.events().all();
+    assert_eq!(events.len(), values.len() as u32);
+    for (i, value) in values.iter().enumerate() {
+        let (_id, topics, data) = events.get(i as u32).unwrap();
+        assert_eq!(topics.len(), 3);
+        assert_topic_symbol(&env, &topics, 0, &symbol_short!("metric"));
+        assert_topic_symbol(&env, &topics, 1, &name);
+        assert_topic_symbol(&env, &topics, 2, &unit);
+        assert_eq!(i128::try_from_val(&env, &data).unwrap(), *value);
+    }
+}
+
+#[test]
+fn test_emit_nonced_sequences_are_per_caller() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    env.mock_all_auths();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let callers = [&alice, &bob, &alice, &alice, &bob, &bob];
+    let returned: std::vec::Vec<u64> = callers.iter().map(|c| client.emit_nonced(c)).collect();
+    assert_eq!(returned, [1, 1, 2, 3, 2, 3]);
+
+    let events = all_events_for(&env, &contract_id);
+    for (who, expected) in [(&alice, [1u64, 2, 3]), (&bob, [1, 2, 3])] {
+        let nonces: std::vec::Vec<u64> = events
+            .iter()
+            .filter(|(topics, _)| {
+                Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap() == *who
+            })
+            .map(|(topics, data)| {
+                assert_topic_symbol(&env, topics, 0, &symbol_short!("nonced"));
+                u64::try_from_val(&env, data).unwrap()
+            })
+            .collect();
+        assert_eq!(nonces, expected);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_emit_nonced_requires_auth() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    env.mock_all_auths();
+    let caller = Address::generate(&env);
+    client.emit_nonced(&caller);
+
+    env.set_auths(&[]);
+    client.emit_nonced(&caller);
+}
+
+#[test]
+fn test_set_and_emit_only_emits_on_change() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let key = symbol_short!("fee");
+
+    assert!(client.set_and_emit(&key, &30));
+    assert!(!client.set_and_emit(&key, &30));
+    assert_eq!(all_events_for(&env, &contract_id).len(), 1);
+
+    assert!(client.set_and_emit(&key, &45));
+    let events = all_events_for(&env, &contract_id);
+    assert_eq!(events.len(), 2);
+    for ((topics, data), (old, new)) in events.iter().zip([(0u64, 30u64), (30, 45)]) {
+        assert_eq!(topics.len(), 2);
+        assert_topic_symbol(&env, topics, 0, &symbol_short!("changed"));
+        assert_topic_symbol(&env, topics, 1, &key);
+        assert_eq!(<(u64, u64)>::try_from_val(&env, data).unwrap(), (old, new));
+    }
+}
+
+/// `(failed_call, data)` for each contract event the host recorded, in
+/// order. `env.events().all()` does not say which invocation failed.
+fn host_events_with_status(env: &Env) -> std::vec::Vec<(bool, ScVal)> {
+    env.host()
+        .get_events()
+        .unwrap()
+        .0
+        .into_iter()
+        .filter(|e| e.event.type_ == ContractEventType::Contract)
+        .map(|e| {
+            let ContractEventBody::V0(body) = e.event.body;
+            (e.failed_call, body.data)
+        })
+        .collect()
+}
+
+#[test]
+fn test_guarded_emit_failure_rolls_back_event() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+
+    // The "diag" event was published before the error. The failed
+    // invocation is rolled back, storage writes included...
+    assert_eq!(client.try_guarded_emit(&0), Err(Ok(Error::ZeroValue)));
+    assert_eq!(client.get_emit_count(&symbol_short!("diag")), 0);
+    // ...and its event is marked as coming from a failed call. The test
+    // host keeps such events so they can be inspected (`env.events().all()`
+    // still lists them), but they never reach the ledger or an indexer.
+    assert_eq!(host_events_with_status(&env), [(true, ScVal::U64(0))]);
+
+    client.guarded_emit(&5);
+    assert_eq!(
+        host_events_with_status(&env),
+        [(true, ScVal::U64(0)), (false, ScVal::U64(5))]
+    );
+    let (_id, topics, data) = env.events().all().last().unwrap();
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("diag"));
+    assert_eq!(u64::try_from_val(&env, &data).unwrap(), 5);
+    assert_eq!(client.get_emit_count(&symbol_short!("diag")), 1);
+}
+
+#[test]
+fn test_emit_with_meta_reads_ledger() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let action = symbol_short!("heartbeat");
+
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1_700_000_000;
+        l.sequence_number = 321;
+    });
+    client.emit_with_meta(&action, &7);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1_700_000_005;
+        l.sequence_number = 322;
+    });
+    client.emit_with_meta(&action, &8);
+
+    let events = all_events_for(&env, &contract_id);
+    assert_eq!(events.len(), 2);
+    let expected = [(7u64, 1_700_000_000u64, 321u32), (8, 1_700_000_005, 322)];
+    for ((topics, data), (value, timestamp, sequence)) in events.iter().zip(expected) {
+        assert_eq!(topics.len(), 1);
+        assert_topic_symbol(&env, topics, 0, &action);
+        let (emitted, meta) = <(u64, EventMeta)>::try_from_val(&env, data).unwrap();
+        assert_eq!(emitted, value);
+        assert_eq!(
+            meta,
+            EventMeta {
+                timestamp,
+                sequence
+            }
+        );
+    }
+}
+
+#[test]
+fn test_status_change_registers_unique_entities() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    assert_eq!(client.get_entity_count(), 0);
+
+    let (pending, filled) = (symbol_short!("pending"), symbol_short!("filled"));
+    client.emit_status_change(&symbol_short!("order_1"), &pending, &filled);
+    client.emit_status_change(&symbol_short!("order_2"), &pending, &filled);
+    client.emit_status_change(&symbol_short!("order_1"), &filled, &pending);
+    client.emit_status_change(&symbol_short!("order_3"), &pending, &filled);
+
+    assert_eq!(client.get_entity_count(), 3);
+    assert_eq!(
+        client.get_entities(),
+        vec![
+            &env,
+            symbol_short!("order_1"),
+            symbol_short!("order_2"),
+            symbol_short!("order_3"),
+        ]
+    );
+    // Every change still emits, including the repeat.
+    assert_eq!(client.get_emit_count(&symbol_short!("status")), 4);
 }
 
 // ---------------------------------------------------------------------------
@@ -419,7 +1149,7 @@ fn test_emit_status_change_four_topics() {
 
 #[test]
 fn test_admin_action_emits_one_event() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let admin = Address::generate(&env);
     client.admin_action(&admin, &symbol_short!("pause"));
@@ -430,7 +1160,7 @@ fn test_admin_action_emits_one_event() {
 
 #[test]
 fn test_admin_action_event_has_three_topics() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let admin = Address::generate(&env);
     client.admin_action(&admin, &symbol_short!("pause"));
@@ -441,36 +1171,33 @@ fn test_admin_action_event_has_three_topics() {
 
 #[test]
 fn test_admin_action_topic_namespace_and_category() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let admin = Address::generate(&env);
     client.admin_action(&admin, &symbol_short!("upgrade"));
 
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
 
-    let ns: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    assert_eq!(ns, symbol_short!("events"));
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("events"));
 
-    let category: Symbol = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-    assert_eq!(category, symbol_short!("admin"));
+    assert_topic_symbol(&env, &topics, 1, &symbol_short!("admin"));
 }
 
 #[test]
 fn test_admin_action_indexed_admin_address() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let admin = Address::generate(&env);
     client.admin_action(&admin, &symbol_short!("pause"));
 
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
 
-    let t_admin = Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
-    assert_eq!(t_admin, admin);
+    assert_topic_address(&env, &topics, 2, &admin);
 }
 
 #[test]
 fn test_admin_action_structured_data_payload() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let admin = Address::generate(&env);
     let action = symbol_short!("pause");
@@ -488,7 +1215,7 @@ fn test_admin_action_structured_data_payload() {
 
 #[test]
 fn test_audit_trail_emits_one_event() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let actor = Address::generate(&env);
     client.audit_trail(&actor, &symbol_short!("delete"), &symbol_short!("rec_42"));
@@ -499,7 +1226,7 @@ fn test_audit_trail_emits_one_event() {
 
 #[test]
 fn test_audit_trail_event_has_four_topics() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let actor = Address::generate(&env);
     client.audit_trail(&actor, &symbol_short!("create"), &symbol_short!("item_1"));
@@ -510,23 +1237,21 @@ fn test_audit_trail_event_has_four_topics() {
 
 #[test]
 fn test_audit_trail_topic_namespace_and_category() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let actor = Address::generate(&env);
     client.audit_trail(&actor, &symbol_short!("update"), &symbol_short!("cfg_x"));
 
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
 
-    let ns: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
-    assert_eq!(ns, symbol_short!("events"));
+    assert_topic_symbol(&env, &topics, 0, &symbol_short!("events"));
 
-    let category: Symbol = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
-    assert_eq!(category, symbol_short!("audit"));
+    assert_topic_symbol(&env, &topics, 1, &symbol_short!("audit"));
 }
 
 #[test]
 fn test_audit_trail_indexed_actor_and_action() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let actor = Address::generate(&env);
     let action = symbol_short!("delete");
@@ -534,16 +1259,14 @@ fn test_audit_trail_indexed_actor_and_action() {
 
     let (_id, topics, _data) = env.events().all().get(0).unwrap();
 
-    let t_actor = Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
-    assert_eq!(t_actor, actor);
+    assert_topic_address(&env, &topics, 2, &actor);
 
-    let t_action: Symbol = Symbol::try_from_val(&env, &topics.get(3).unwrap()).unwrap();
-    assert_eq!(t_action, action);
+    assert_topic_symbol(&env, &topics, 3, &action);
 }
 
 #[test]
 fn test_audit_trail_structured_data_payload() {
-    let (env, _, client) = make_env_and_client();
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
 
     let actor = Address::generate(&env);
     let action = symbol_short!("create");
@@ -557,3 +1280,308 @@ fn test_audit_trail_structured_data_payload() {
     assert_eq!(payload.timestamp, env.ledger().timestamp());
     assert_eq!(payload.sequence, env.ledger().sequence());
 }
+
+// ---------------------------------------------------------------------------
+// Per-category emission counters
+// ---------------------------------------------------------------------------
+
+/// The counter category of an event: topic[1] under the `"events"`
+/// namespace, topic[0] otherwise.
+fn category_of(env: &Env, topics: &soroban_sdk::Vec<soroban_sdk::Val>) -> Symbol {
+    let first = Symbol::try_from_val(env, &topics.get(0).unwrap()).unwrap();
+    if first == symbol_short!("events") {
+        Symbol::try_from_val(env, &topics.get(1).unwrap()).unwrap()
+    } else if first == symbol_short!("defi") {
+        // The category `emit_namespaced` is called with below.
+        symbol_short!("namespace")
+    } else {
+        first
+    }
+}
+
+#[test]
+fn test_emit_counts_match_event_stream() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.emit_simple(&1);
+    client.emit_simple(&2);
+    client.emit_multiple(&3);
+    client.transfer(&alice, &bob, &10, &0);
+    client.emit_transfer(&alice, &bob, &20);
+    client.emit_mint(&alice, &bob, &30);
+    client.update_config(&symbol_short!("fee"), &1, &2);
+    client.emit_namespaced(
+        &symbol_short!("defi"),
+        &symbol_short!("swap"),
+        &symbol_short!("pool_1"),
+        &40,
+    );
+    client.emit_multiple(&0);
+
+    let events = all_events_for(&env, &contract_id);
+    let categories = [
+        symbol_short!("simple"),
+        symbol_short!("multi"),
+        symbol_short!("transfer"),
+        symbol_short!("mint"),
+        symbol_short!("cfg_upd"),
+        symbol_short!("namespace"),
+        symbol_short!("burn"),
+    ];
+    for category in &categories {
+        let seen = events
+            .iter()
+            .filter(|(topics, _)| category_of(&env, topics) == *category)
+            .count() as u64;
+        assert_eq!(client.get_emit_count(category), seen, "{category:?}");
+    }
+    assert_eq!(client.get_emit_count(&symbol_short!("transfer")), 2);
+    assert_eq!(client.get_emit_count(&symbol_short!("multi")), 3);
+    assert_eq!(client.get_emit_count(&symbol_short!("burn")), 0);
+    assert_eq!(client.get_emit_count(&symbol_short!("defi")), 0);
+
+    // The counters add up to every event the contract emitted.
+    let total: u64 = categories.iter().map(|c| client.get_emit_count(c)).sum();
+    assert_eq!(total, events.len() as u64);
+}
+
+#[test]
+fn test_caller_chosen_topics_cannot_inflate_counts() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let transfer = symbol_short!("transfer");
+    client.emit_transfer(&Address::generate(&env), &Address::generate(&env), &5);
+
+    client.emit_with_meta(&transfer, &1_000);
+    client.emit_namespaced(&transfer, &transfer, &symbol_short!("pool_1"), &1_000);
+    client.emit_namespaced_addr(&transfer, &transfer, &Address::generate(&env), &1_000);
+
+    assert_eq!(client.get_emit_count(&transfer), 1);
+    assert_eq!(client.get_last(&transfer).unwrap().value, 5);
+    assert_eq!(client.get_emit_count(&symbol_short!("meta")), 1);
+    assert_eq!(client.get_emit_count(&symbol_short!("namespace")), 2);
+}
+
+#[test]
+fn test_last_event_cache_follows_emits() {
+    let (env, _, client) = TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let simple = symbol_short!("simple");
+    assert_eq!(client.get_last(&simple), None);
+
+    recipes_testutils::set_timestamp(&env, 1_000);
+    client.emit_simple(&5);
+    assert_eq!(
+        client.get_last(&simple),
+        Some(LastEvent {
+            category: simple.clone(),
+            value: 5,
+            at: 1_000,
+        })
+    );
+
+    recipes_testutils::set_timestamp(&env, 1_060);
+    client.emit_simple(&9);
+    let last = client.get_last(&simple).unwrap();
+    assert_eq!((last.value, last.at), (9, 1_060));
+
+    // Each category has its own entry; signed values are clamped.
+    let alice = Address::generate(&env);
+    client.emit_burn(&alice, &-3);
+    client.emit_multiple(&4);
+    assert_eq!(client.get_last(&symbol_short!("burn")).unwrap().value, 0);
+    assert_eq!(client.get_last(&symbol_short!("multi")).unwrap().value, 4);
+    assert_eq!(client.get_last(&simple).unwrap().value, 9);
+
+    // Never emitted, or emitted nothing.
+    assert_eq!(client.get_last(&symbol_short!("mint")), None);
+    client.emit_transfer_batch(&Vec::new(&env));
+    assert_eq!(client.get_last(&symbol_short!("transfer")), None);
+}
+
+#[test]
+fn test_emit_count_reveals_missed_events() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+
+    client.emit_simple(&1);
+    // An indexer that stopped listening here saw one "simple" event.
+    let seen = all_events_for(&env, &contract_id).len() as u64;
+    client.emit_simple(&2);
+    client.emit_simple(&3);
+
+    let missed = client.get_emit_count(&symbol_short!("simple")) - seen;
+    assert_eq!(missed, 2);
+}
+
+#[test]
+fn test_structured_events_golden() {
+    let (env, contract_id, client) =
+        TestEnv::with_contract(EventsContract, EventsContractClient::new);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    recipes_testutils::set_timestamp(&env, 1_700_000_000);
+    client.transfer(&alice, &bob, &250, &42);
+    client.update_config(&symbol_short!("fee"), &30, &25);
+    client.admin_action(&alice, &symbol_short!("pause"));
+    client.audit_trail(&bob, &symbol_short!("resume"), &symbol_short!("ok"));
+    client.emit_status_change(
+        &symbol_short!("order_7"),
+        &symbol_short!("pending"),
+        &symbol_short!("filled"),
+    );
+
+    let mut snapshot = Snapshot::new(&env);
+    snapshot
+        .name(&contract_id, "events")
+        .name(&alice, "alice")
+        .name(&bob, "bob")
+        .section("events")
+        .events();
+    assert_golden("golden/structured_events.txt", &snapshot.render());
+}
+
+// ---------------------------------------------------------------------------
+// Indexer fixtures
+// ---------------------------------------------------------------------------
+
+/// Build an address from fixed bytes, so fixtures do not depend on the
+/// order in which a test generates addresses.
+fn pinned_address(env: &Env, contract: bool, byte: u8) -> Address {
+    let address = if contract {
+        ScAddress::Contract(Hash([byte; 32]))
+    } else {
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+            [byte; 32],
+        ))))
+    };
+    Address::try_from_val(env, &address).unwrap()
+}
+
+/// Render every event of `env` as `contract_id topics data`: the contract
+/// strkey, then the topics as a base64 XDR `ScVal::Vec` and the data as a
+/// base64 XDR `ScVal`.
+fn render_fixtures(env: &Env) -> std::string::String {
+    let mut out = std::string::String::from(
+        "# contract_id topics(base64 XDR ScVal) data(base64 XDR ScVal)\n",
+    );
+    for (contract_id, topics, data) in env.events().all().iter() {
+        let topics = ScVal::try_from_val(env, &topics.to_val()).unwrap();
+        let data = ScVal::try_from_val(env, &data).unwrap();
+        out.push_str(&std::format!(
+            "{} {} {}\n",
+            ScAddress::from(&contract_id),
+            topics.to_xdr_base64(Limits::none()).unwrap(),
+            data.to_xdr_base64(Limits::none()).unwrap(),
+        ));
+    }
+    out
+}
+
+/// Runs every emit function once against pinned addresses, timestamp and
+/// sequence, and compares the events with `testdata/events.fixtures`.
+///
+/// The file is meant for indexer projects that want to test their decoding
+/// without a network. Each non-comment line is one event, in emission
+/// order, as three space-separated fields: the emitting contract's strkey,
+/// the topics and the data. Topics and data are base64 `ScVal` XDR, the
+/// same encoding RPC `getEvents` returns, so they can be fed to any XDR
+/// decoder (`stellar xdr decode --type ScVal`, `stellar-xdr`, js-stellar-base).
+/// Regenerate with `UPDATE_GOLDEN=1` after an intentional change.
+#[test]
+fn test_indexer_fixtures() {
+    let env = Env::default();
+    let contract_id = pinned_address(&env, true, 0xc0);
+    env.register_contract(&contract_id, EventsContract);
+    let client = EventsContractClient::new(&env, &contract_id);
+    let alice = pinned_address(&env, false, 0xa1);
+    let bob = pinned_address(&env, false, 0xb0);
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1_700_000_000;
+        l.sequence_number = 1_000;
+    });
+
+    env.mock_all_auths();
+    client.initialize(&alice, &1);
+    client.transfer(&alice, &bob, &250, &42);
+    client.update_config(&symbol_short!("fee"), &30, &25);
+    client.admin_action(&alice, &symbol_short!("pause"));
+    client.audit_trail(&bob, &symbol_short!("resume"), &symbol_short!("ok"));
+    client.emit_simple(&7);
+    client.emit_tagged(&symbol_short!("price"), &101);
+    client.emit_multiple(&3);
+    client.emit_transfer(&alice, &bob, &500);
+    client.emit_transfer_v2(&alice, &bob, &500);
+    client.emit_transfer_typed(&alice, &bob, &500);
+    client.emit_transfer_detailed(
+        &alice,
+        &bob,
+        &TransferDetails {
+            amount: 500,
+            memo: symbol_short!("invoice9"),
+            timestamp: 1_700_000_000,
+        },
+    );
+    client.emit_transfer_batch(&vec![
+        &env,
+        (alice.clone(), bob.clone(), 10i128),
+        (bob.clone(), alice.clone(), 4i128),
+    ]);
+    client.emit_transfer_hashed(&alice, &bob, &75);
+    client.emit_mint(&alice, &bob, &1_000);
+    client.emit_burn(&bob, &250);
+    client.emit_approval(&alice, &bob, &5_000, &2_000);
+    client.emit_namespaced(
+        &symbol_short!("defi"),
+        &symbol_short!("swap"),
+        &symbol_short!("pool_1"),
+        &1_000,
+    );
+    client.emit_composite(&9, &symbol_short!("order"), &bob);
+    client.emit_namespaced_addr(
+        &symbol_short!("defi"),
+        &symbol_short!("deposit"),
+        &contract_id,
+        &2_000,
+    );
+    client.emit_metric(&symbol_short!("net_pos"), &-1_250, &symbol_short!("usdc"));
+    client.emit_nonced(&alice);
+    client.set_and_emit(&symbol_short!("fee"), &30);
+    client.guarded_emit(&1);
+    client.emit_with_meta(&symbol_short!("heartbeat"), &3);
+    client.emit_status_change(
+        &symbol_short!("order_7"),
+        &symbol_short!("pending"),
+        &symbol_short!("filled"),
+    );
+
+    assert_golden("testdata/events.fixtures", &render_fixtures(&env));
+}
+
+/// Decode the committed fixtures the way an indexer would, to check the
+/// file stays readable without the contract's types.
+#[test]
+fn test_indexer_fixtures_decode() {
+    let path = std::concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/events.fixtures");
+    let fixtures = std::fs::read_to_string(path).unwrap();
+    let lines: std::vec::Vec<&str> = fixtures
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    assert_eq!(lines.len(), 29);
+
+    for line in lines {
+        let fields: std::vec::Vec<&str> = line.split(' ').collect();
+        assert_eq!(fields.len(), 3, "bad fixture line: {line}");
+        let contract_id: ScAddress = fields[0].parse().unwrap();
+        assert!(matches!(contract_id, ScAddress::Contract(_)));
+        let topics = ScVal::from_xdr_base64(fields[1], Limits::none()).unwrap();
+        let ScVal::Vec(Some(topics)) = topics else {
+            panic!("topics are not a vector: {line}");
+        };
+        assert!((1..=4).contains(&topics.len()));
+        ScVal::from_xdr_base64(fields[2], Limits::none()).unwrap();
+    }
+}
@@ -16,11 +16,20 @@
 //! - Critical internal errors
 //! - Development/debugging assertions
 
+//!
+//! ## Shared Codes
+//!
+//! Conditions that every contract can hit (unauthorized caller, overflow,
+//! bad input, division by zero) use the numbers from the `recipes-errors`
+//! catalogue, so a client sees the same code here as in any other example.
+//! Codes from 100 up are specific to this contract.
+
 #![no_std]
-use soroban_sdk::{contract, contracterror, contractimpl, Env};
 
 use soroban_sdk::{contract, contracterror, contractimpl, symbol_short, Address, Env, Symbol};
 
+pub use recipes_errors::CommonError;
+
 /// Public contract error type returned to clients.
 ///
 /// These are the only errors that cross the contract boundary, so we keep them
@@ -29,16 +38,21 @@ use soroban_sdk::{contract, contracterror, contractimpl, symbol_short, Address,
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
 pub enum Error {
+    /// Caller failed authorization. `CommonError::Unauthorized`.
+    Unauthorized = 3,
+    /// Arithmetic failed due to overflow/underflow. `CommonError::Overflow`.
+    Overflow = 5,
     /// Input failed domain validation (for example: zero count).
-    InvalidInput = 1,
+    /// `CommonError::InvalidInput`.
+    InvalidInput = 8,
+    /// Division by zero was attempted. `CommonError::DivisionByZero`.
+    DivisionByZero = 9,
     /// Input exceeded a business limit enforced by the contract.
-    LimitExceeded = 2,
-    /// Arithmetic failed due to overflow/underflow.
-    MathOverflow = 3,
-    /// Division by zero was attempted.
-    DivisionByZero = 4,
-    /// Caller failed authorization.
-    Unauthorized = 5,
+    LimitExceeded = 100,
+    /// A transfer amount of zero.
+    InvalidAmount = 101,
+    /// A transfer larger than the balance.
+    InsufficientBalance = 102,
 }
 
 /// Internal validation errors used only inside this module.
@@ -74,13 +88,30 @@ impl From<ValidationError> for Error {
 impl From<MathError> for Error {
     fn from(value: MathError) -> Self {
         match value {
-            MathError::Overflow => Error::MathOverflow,
+            MathError::Overflow => Error::Overflow,
             MathError::ZeroDivisor => Error::DivisionByZero,
         }
     }
-    InvalidAmount = 1,
-    InsufficientBalance = 2,
-    Unauthorized = 3,
+}
+
+/// Error conversion pattern #3:
+/// Accept the shared catalogue's errors wherever this contract uses the
+/// same condition.
+impl From<CommonError> for Error {
+    fn from(value: CommonError) -> Self {
+        match value {
+            CommonError::Unauthorized => Error::Unauthorized,
+            CommonError::Overflow => Error::Overflow,
+            CommonError::InvalidInput => Error::InvalidInput,
+            CommonError::DivisionByZero => Error::DivisionByZero,
+            // This contract has no state to initialize, pause or look up.
+            CommonError::NotInitialized
+            | CommonError::AlreadyInitialized
+            | CommonError::Paused
+            | CommonError::NotFound
+            | CommonError::Expired => unreachable!(),
+        }
+    }
 }
 
 #[contract]
@@ -148,10 +179,11 @@ impl ErrorHandlingContract {
         Ok(numerator / denominator)
     }
 
-    /// Authorization helper that returns a contract-level error directly.
-    fn ensure_admin(caller: &Address, admin: &Address) -> Result<(), Error> {
+    /// Authorization helper that returns a shared catalogue error, converted
+    /// by `?` at the call site.
+    fn ensure_admin(caller: &Address, admin: &Address) -> Result<(), CommonError> {
         if caller != admin {
-            return Err(Error::Unauthorized);
+            return Err(CommonError::Unauthorized);
         }
         Ok(())
     }
@@ -161,6 +193,8 @@ impl ErrorHandlingContract {
         let validated = Self::validate_limit(count)?;
         let doubled = Self::scale_by_two(validated)?;
         Ok(doubled)
+    }
+
     /// ✅ GOOD: Use Result for expected validation failures
     /// Returns error for invalid input - caller can handle gracefully
     pub fn transfer(amount: u64, balance: u64) -> Result<u64, Error> {
@@ -0,0 +1,116 @@
+//! # Storage Durability Contract
+//!
+//! Side-by-side recipe for Soroban's three storage durability classes:
+//!
+//! - **Instance**: one ledger entry shared by the contract instance and all
+//!   of its instance keys. Loaded on every invocation, so keep it small.
+//! - **Persistent**: one ledger entry per key. Archived (not deleted) when its
+//!   TTL runs out and can be restored later.
+//! - **Temporary**: one ledger entry per key. Deleted for good when its TTL
+//!   runs out; the cheapest class.
+//!
+//! Every class has a setter, a getter, and an `extend_*_ttl` wrapper so the
+//! archival behaviour can be observed from tests. The same key may be used
+//! in each class; the three namespaces never collide.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, Env, Symbol};
+
+#[contract]
+pub struct StorageDurabilityContract;
+
+#[contractimpl]
+impl StorageDurabilityContract {
+    // ==================== INSTANCE STORAGE ====================
+
+    /// Store `value` under `key` in instance storage.
+    ///
+    /// # Archival
+    /// Instance keys live inside the contract instance entry, so they share
+    /// its TTL: if the instance is archived, every instance key (and the
+    /// contract itself) becomes unusable until restored.
+    ///
+    /// # Cost
+    /// The whole instance entry is read and written on every call that
+    /// touches it. Cheap for a handful of config values, expensive for
+    /// anything that grows with the number of users: with 100 instance keys,
+    /// reading one costs about 5x a persistent read
+    /// (`bench_instance_cost_grows_with_its_keys`).
+    pub fn set_instance(env: Env, key: Symbol, value: u64) {
+        env.storage().instance().set(&key, &value);
+    }
+
+    /// Read `key` from instance storage, or `None` if it was never set.
+    pub fn get_instance(env: Env, key: Symbol) -> Option<u64> {
+        env.storage().instance().get(&key)
+    }
+
+    /// Extend the TTL of the contract instance (and with it every instance
+    /// key) to `extend_to` ledgers once it falls below `threshold`.
+    pub fn extend_instance_ttl(env: Env, threshold: u32, extend_to: u32) {
+        env.storage().instance().extend_ttl(threshold, extend_to);
+    }
+
+    // ==================== PERSISTENT STORAGE ====================
+
+    /// Store `value` under `key` in persistent storage.
+    ///
+    /// # Archival
+    /// Each key has its own TTL. When it expires the entry is archived, not
+    /// deleted: reads fail until the entry is restored, after which the old
+    /// value is available again.
+    ///
+    /// # Cost
+    /// The most expensive class in fees to write and to keep alive, but the
+    /// only one whose data is never lost. Use it for balances and ownership
+    /// records. In host CPU it costs the same as temporary storage.
+    pub fn set_persistent(env: Env, key: Symbol, value: u64) {
+        env.storage().persistent().set(&key, &value);
+    }
+
+    /// Read `key` from persistent storage, or `None` if it was never set.
+    pub fn get_persistent(env: Env, key: Symbol) -> Option<u64> {
+        env.storage().persistent().get(&key)
+    }
+
+    /// Extend the TTL of the persistent entry `key` to `extend_to` ledgers
+    /// once it falls below `threshold`. Panics if the entry does not exist.
+    pub fn extend_persistent_ttl(env: Env, key: Symbol, threshold: u32, extend_to: u32) {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, threshold, extend_to);
+    }
+
+    // ==================== TEMPORARY STORAGE ====================
+
+    /// Store `value` under `key` in temporary storage.
+    ///
+    /// # Archival
+    /// Temporary entries are never archived. Once the TTL runs out the entry
+    /// is gone for good and reads behave as if it had never been written.
+    ///
+    /// # Cost
+    /// The cheapest class in rent; the host work per read or write is the
+    /// same as persistent storage. Use it for data that is safe to lose:
+    /// price quotes, nonces with a short validity window, rate-limit buckets.
+    pub fn set_temporary(env: Env, key: Symbol, value: u64) {
+        env.storage().temporary().set(&key, &value);
+    }
+
+    /// Read `key` from temporary storage, or `None` if it was never set or
+    /// has expired.
+    pub fn get_temporary(env: Env, key: Symbol) -> Option<u64> {
+        env.storage().temporary().get(&key)
+    }
+
+    /// Extend the TTL of the temporary entry `key` to `extend_to` ledgers
+    /// once it falls below `threshold`. Panics if the entry does not exist.
+    pub fn extend_temporary_ttl(env: Env, key: Symbol, threshold: u32, extend_to: u32) {
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, threshold, extend_to);
+    }
+}
+
+mod test;
@@ -0,0 +1,352 @@
+//! Unit tests for the storage durability contract
+
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use recipes_testutils::advance_ledgers;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{
+        storage::{Instance as _, Persistent as _, Temporary as _},
+        EnvTestConfig, Ledger,
+    },
+    Address, Env, Map,
+};
+
+const MIN_TEMP_TTL: u32 = 16;
+const MIN_PERSISTENT_TTL: u32 = 100;
+
+fn setup() -> (Env, Address, StorageDurabilityContractClient<'static>) {
+    let env = Env::default();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 1_000;
+        li.min_temp_entry_ttl = MIN_TEMP_TTL;
+        li.min_persistent_entry_ttl = MIN_PERSISTENT_TTL;
+        li.max_entry_ttl = 100_000;
+    });
+    let contract_id = env.register_contract(None, StorageDurabilityContract);
+    let client = StorageDurabilityContractClient::new(&env, &contract_id);
+    (env, contract_id, client)
+}
+
+#[test]
+fn test_each_class_reads_back_independently() {
+    let (_env, _contract_id, client) = setup();
+    let key = symbol_short!("counter");
+
+    client.set_instance(&key, &1);
+    client.set_persistent(&key, &2);
+    client.set_temporary(&key, &3);
+
+    assert_eq!(client.get_instance(&key), Some(1));
+    assert_eq!(client.get_persistent(&key), Some(2));
+    assert_eq!(client.get_temporary(&key), Some(3));
+}
+
+#[test]
+fn test_unset_keys_read_as_none() {
+    let (_env, _contract_id, client) = setup();
+    let key = symbol_short!("missing");
+
+    client.set_persistent(&key, &7);
+
+    assert_eq!(client.get_instance(&key), None);
+    assert_eq!(client.get_temporary(&key), None);
+}
+
+#[test]
+fn test_extend_persistent_ttl() {
+    let (env, contract_id, client) = setup();
+    let key = symbol_short!("balance");
+    client.set_persistent(&key, &500);
+
+    let initial = env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&key));
+    assert_eq!(initial, MIN_PERSISTENT_TTL - 1);
+
+    client.extend_persistent_ttl(&key, &1_000, &5_000);
+    let extended = env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&key));
+    assert_eq!(extended, 5_000);
+
+    // A threshold the current TTL is already above leaves it untouched.
+    client.extend_persistent_ttl(&key, &10, &20_000);
+    let unchanged = env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&key));
+    assert_eq!(unchanged, 5_000);
+}
+
+#[test]
+fn test_extend_instance_ttl() {
+    let (env, contract_id, client) = setup();
+    client.set_instance(&symbol_short!("admin"), &1);
+
+    client.extend_instance_ttl(&1_000, &2_000);
+    let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+    assert_eq!(ttl, 2_000);
+}
+
+#[test]
+fn test_temporary_entry_disappears_after_ttl() {
+    let (env, contract_id, client) = setup();
+    let key = symbol_short!("quote");
+    client.set_temporary(&key, &42);
+
+    let ttl = env.as_contract(&contract_id, || env.storage().temporary().get_ttl(&key));
+    assert_eq!(ttl, MIN_TEMP_TTL - 1);
+
+    advance_ledgers(&env, ttl);
+    assert_eq!(client.get_temporary(&key), Some(42));
+
+    advance_ledgers(&env, 1);
+    assert_eq!(client.get_temporary(&key), None);
+}
+
+#[test]
+fn test_extended_temporary_entry_outlives_default_ttl() {
+    let (env, _contract_id, client) = setup();
+    let key = symbol_short!("quote");
+    client.set_temporary(&key, &42);
+    client.extend_temporary_ttl(&key, &100, &200);
+    // The instance entry has its own TTL; without this the contract itself
+    // would be archived before the temporary entry expires.
+    client.extend_instance_ttl(&1_000, &1_000);
+
+    advance_ledgers(&env, 150);
+    assert_eq!(client.get_temporary(&key), Some(42));
+
+    advance_ledgers(&env, 51);
+    assert_eq!(client.get_temporary(&key), None);
+}
+
+#[test]
+fn test_persistent_survives_where_temporary_expires() {
+    let (env, _contract_id, client) = setup();
+    let key = symbol_short!("data");
+    client.set_persistent(&key, &9);
+    client.set_temporary(&key, &9);
+    client.extend_persistent_ttl(&key, &1_000, &1_000);
+
+    advance_ledgers(&env, MIN_TEMP_TTL * 2);
+    assert_eq!(client.get_persistent(&key), Some(9));
+    assert_eq!(client.get_temporary(&key), None);
+}
+
+// ── cost benchmarks ──────────────────────────────────────────────────────────
+//
+// Recorded costs, from `cargo test -p storage-durability bench -- --nocapture`.
+//
+// Each benchmark asserts that every measurement stays within
+// `TOLERANCE_PCT` of its recorded CPU cost, plus the ordering the recipe's
+// doc comments rely on. After an SDK upgrade, rerun with `--nocapture` and
+// copy the printed numbers here once the new ordering has been checked.
+//
+// The budget measures host work: CPU instructions and memory. It does not
+// include fees, so it cannot show that temporary entries pay less rent than
+// persistent ones; for host work the two classes cost the same.
+
+/// Entries written to each class before measuring.
+const ENTRIES: u32 = 100;
+/// The expensive variant must cost at least this many times the cheap one.
+const MARGIN: u64 = 2;
+/// How far a measurement may drift from its recorded number.
+const TOLERANCE_PCT: u64 = 10;
+
+const INSTANCE_READ_CPU: u64 = 117_664;
+const INSTANCE_WRITE_CPU: u64 = 177_563;
+const PERSISTENT_READ_CPU: u64 = 23_619;
+const PERSISTENT_WRITE_CPU: u64 = 38_384;
+const PERSISTENT_WRITE_ALL_CPU: u64 = 4_719_754;
+const TEMPORARY_WRITE_ALL_CPU: u64 = 4_719_754;
+
+const MAP_WRITE_ALL_CPU: u64 = 189_454;
+const ENTRIES_WRITE_ALL_CPU: u64 = 3_139_577;
+const MAP_READ_ONE_CPU: u64 = 14_592;
+const ENTRIES_READ_ONE_CPU: u64 = 13_979;
+const MAP_UPDATE_ONE_CPU: u64 = 43_836;
+const ENTRIES_UPDATE_ONE_CPU: u64 = 28_336;
+
+#[derive(Debug, Clone, Copy)]
+struct Cost {
+    cpu: u64,
+    mem: u64,
+}
+
+/// Run `f` against a freshly reset budget and return what it consumed.
+fn reset_and_measure<R>(env: &Env, f: impl FnOnce() -> R) -> Cost {
+    env.budget().reset_default();
+    f();
+    Cost {
+        cpu: env.budget().cpu_instruction_cost(),
+        mem: env.budget().memory_bytes_cost(),
+    }
+}
+
+#[track_caller]
+fn assert_recorded(name: &str, measured: Cost, recorded: u64) {
+    std::println!("{name}: {measured:?}");
+    let low = recorded * (100 - TOLERANCE_PCT) / 100;
+    let high = recorded * (100 + TOLERANCE_PCT) / 100;
+    assert!(
+        (low..=high).contains(&measured.cpu),
+        "{name}: {} cpu is more than {TOLERANCE_PCT}% from recorded {recorded}",
+        measured.cpu
+    );
+}
+
+#[track_caller]
+fn assert_cheaper(name: &str, cheap: Cost, expensive: Cost) {
+    assert!(
+        expensive.cpu >= cheap.cpu * MARGIN,
+        "{name}: expected at least {MARGIN}x, got {} vs {}",
+        expensive.cpu,
+        cheap.cpu
+    );
+    assert!(expensive.mem > cheap.mem, "{name}: memory did not drop");
+}
+
+/// A contract for benchmarks: snapshots of hundreds of entries would only
+/// bloat `test_snapshots/`.
+fn bench_setup() -> (Env, Address, StorageDurabilityContractClient<'static>) {
+    let (mut env, contract_id, client) = setup();
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    (env, contract_id, client)
+}
+
+fn bench_key(env: &Env, index: u32) -> Symbol {
+    Symbol::new(env, &std::format!("key_{index}"))
+}
+
+#[test]
+fn bench_instance_cost_grows_with_its_keys() {
+    // Separate contracts, so each measurement sees only its own entries.
+    let (instance_env, _, instance) = bench_setup();
+    let (persistent_env, _, persistent) = bench_setup();
+    instance_env.budget().reset_unlimited();
+    persistent_env.budget().reset_unlimited();
+    for index in 0..ENTRIES {
+        instance.set_instance(&bench_key(&instance_env, index), &1);
+        persistent.set_persistent(&bench_key(&persistent_env, index), &1);
+    }
+    let key = bench_key(&instance_env, 7);
+
+    // Every call loads the instance entry, and with it all instance keys.
+    let instance_read = reset_and_measure(&instance_env, || instance.get_instance(&key));
+    let persistent_read = reset_and_measure(&persistent_env, || persistent.get_persistent(&key));
+    let instance_write = reset_and_measure(&instance_env, || instance.set_instance(&key, &2));
+    let persistent_write =
+        reset_and_measure(&persistent_env, || persistent.set_persistent(&key, &2));
+
+    assert_recorded("instance read", instance_read, INSTANCE_READ_CPU);
+    assert_recorded("persistent read", persistent_read, PERSISTENT_READ_CPU);
+    assert_recorded("instance write", instance_write, INSTANCE_WRITE_CPU);
+    assert_recorded("persistent write", persistent_write, PERSISTENT_WRITE_CPU);
+    assert_cheaper("read one key", persistent_read, instance_read);
+    assert_cheaper("write one key", persistent_write, instance_write);
+}
+
+#[test]
+fn bench_persistent_and_temporary_cost_the_same_host_work() {
+    let (persistent_env, _, persistent) = bench_setup();
+    let (temporary_env, _, temporary) = bench_setup();
+    let keys: std::vec::Vec<Symbol> = (0..ENTRIES)
+        .map(|i| bench_key(&persistent_env, i))
+        .collect();
+
+    let persistent_all = reset_and_measure(&persistent_env, || {
+        for key in &keys {
+            persistent.set_persistent(key, &1);
+        }
+    });
+    let temporary_all = reset_and_measure(&temporary_env, || {
+        for key in &keys {
+            temporary.set_temporary(key, &1);
+        }
+    });
+
+    assert_recorded(
+        "persistent write all",
+        persistent_all,
+        PERSISTENT_WRITE_ALL_CPU,
+    );
+    assert_recorded(
+        "temporary write all",
+        temporary_all,
+        TEMPORARY_WRITE_ALL_CPU,
+    );
+    // Temporary storage is cheaper in rent, not in host work.
+    assert_eq!(persistent_all.cpu, temporary_all.cpu);
+}
+
+#[test]
+fn bench_one_map_entry_vs_many_entries() {
+    // Both layouts live in one contract: the test host's per-call work
+    // grows with the number of entries in the ledger, so separate
+    // contracts would favour whichever holds fewer.
+    let (env, contract_id, _) = bench_setup();
+    let map_key = symbol_short!("map");
+    let storage = || env.storage().persistent();
+    let in_contract = |f: &dyn Fn()| env.as_contract(&contract_id, f);
+
+    let map_write_all = reset_and_measure(&env, || {
+        in_contract(&|| {
+            let mut map = Map::<u32, u64>::new(&env);
+            for index in 0..ENTRIES {
+                map.set(index, 1);
+            }
+            storage().set(&map_key, &map);
+        })
+    });
+    let entries_write_all = reset_and_measure(&env, || {
+        in_contract(&|| {
+            for index in 0..ENTRIES {
+                storage().set(&index, &1u64);
+            }
+        })
+    });
+
+    // Reading one value decodes the whole map. For a map this small the
+    // CPU stays close to one small read; the size shows in memory, and on
+    // chain in read fees.
+    let map_read_one = reset_and_measure(&env, || {
+        in_contract(&|| {
+            let map: Map<u32, u64> = storage().get(&map_key).unwrap();
+            assert_eq!(map.get_unchecked(7), 1);
+        })
+    });
+    let entries_read_one = reset_and_measure(&env, || {
+        in_contract(&|| assert_eq!(storage().get::<u32, u64>(&7), Some(1)))
+    });
+
+    let map_update_one = reset_and_measure(&env, || {
+        in_contract(&|| {
+            let mut map: Map<u32, u64> = storage().get(&map_key).unwrap();
+            map.set(7, 2);
+            storage().set(&map_key, &map);
+        })
+    });
+    let entries_update_one =
+        reset_and_measure(&env, || in_contract(&|| storage().set(&7u32, &2u64)));
+
+    assert_recorded("map write all", map_write_all, MAP_WRITE_ALL_CPU);
+    assert_recorded(
+        "entries write all",
+        entries_write_all,
+        ENTRIES_WRITE_ALL_CPU,
+    );
+    assert_recorded("map read one", map_read_one, MAP_READ_ONE_CPU);
+    assert_recorded("entries read one", entries_read_one, ENTRIES_READ_ONE_CPU);
+    assert_recorded("map update one", map_update_one, MAP_UPDATE_ONE_CPU);
+    assert_recorded(
+        "entries update one",
+        entries_update_one,
+        ENTRIES_UPDATE_ONE_CPU,
+    );
+    // Writing everything at once favours the map; touching one value
+    // favours separate entries.
+    assert_cheaper("write all", map_write_all, entries_write_all);
+    assert!(map_read_one.mem > entries_read_one.mem);
+    assert!(map_update_one.cpu > entries_update_one.cpu);
+    assert!(map_update_one.mem > entries_update_one.mem);
+}
@@ -0,0 +1,155 @@
+//! # Counter Contract
+//!
+//! The smallest useful stateful contract, written with the habits the rest
+//! of the cookbook builds on:
+//!
+//! - checked arithmetic instead of wrapping or unhelpful panics,
+//! - a `#[contracterror]` enum for every expected failure,
+//! - an event for every state change, with the new value as data,
+//! - an admin pause switch from the shared `recipes-pausable` crate.
+//!
+//! Events:
+//!
+//! | Operation   | Topics              | Data      |
+//! |-------------|---------------------|-----------|
+//! | `increment` | `("count", "inc")`  | new count |
+//! | `decrement` | `("count", "dec")`  | new count |
+//! | `reset`     | `("count", "reset")`| `0`       |
+//!
+//! `pause`/`unpause` publish the standard `("pausable", "paused" |
+//! "unpaused")` events with the admin as data.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address,
+    Env, Symbol,
+};
+
+/// Topic shared by every counter event.
+const COUNT: Symbol = symbol_short!("count");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Count,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    /// `initialize` was called a second time.
+    AlreadyInitialized = 1,
+    /// The caller is not the configured admin.
+    NotAdmin = 2,
+    /// `decrement` would take the count below zero.
+    Underflow = 3,
+    /// `increment` would take the count past `u32::MAX`.
+    MathOverflow = 4,
+    /// `increment`/`decrement` while the admin has paused the counter.
+    Paused = 5,
+}
+
+impl From<recipes_pausable::PausableError> for Error {
+    fn from(err: recipes_pausable::PausableError) -> Self {
+        match err {
+            recipes_pausable::PausableError::Paused => Error::Paused,
+        }
+    }
+}
+
+#[contract]
+pub struct CounterContract;
+
+#[contractimpl]
+impl CounterContract {
+    /// Store the admin allowed to `reset` the counter. Callable once.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Add one to the count and return the new value.
+    ///
+    /// Overflow is reported as the typed `MathOverflow` error rather than
+    /// wrapping back to zero.
+    pub fn increment(env: Env) -> u32 {
+        if let Err(err) = recipes_pausable::require_not_paused(&env) {
+            panic_with_error!(&env, Error::from(err));
+        }
+        let count = Self::get(env.clone())
+            .checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::MathOverflow));
+        Self::store(&env, count, symbol_short!("inc"));
+        count
+    }
+
+    /// Subtract one from the count and return the new value.
+    ///
+    /// The count never goes below zero; at zero this returns `Underflow`.
+    pub fn decrement(env: Env) -> Result<u32, Error> {
+        recipes_pausable::require_not_paused(&env)?;
+        let count = Self::get(env.clone())
+            .checked_sub(1)
+            .ok_or(Error::Underflow)?;
+        Self::store(&env, count, symbol_short!("dec"));
+        Ok(count)
+    }
+
+    /// Set the count back to zero. Admin only.
+    pub fn reset(env: Env, admin: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::store(&env, 0, symbol_short!("reset"));
+        Ok(())
+    }
+
+    /// Block `increment` and `decrement` until `unpause`. Admin only.
+    pub fn pause(env: Env, admin: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        recipes_pausable::set_paused(&env, true);
+        recipes_pausable::emit_pause_changed(&env, &admin, true);
+        Ok(())
+    }
+
+    /// Lift a previous `pause`. Admin only.
+    pub fn unpause(env: Env, admin: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        recipes_pausable::set_paused(&env, false);
+        recipes_pausable::emit_pause_changed(&env, &admin, false);
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        recipes_pausable::is_paused(&env)
+    }
+
+    /// Current count; zero before the first increment.
+    pub fn get(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Count).unwrap_or(0)
+    }
+}
+
+impl CounterContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if stored.as_ref() != Some(admin) {
+            return Err(Error::NotAdmin);
+        }
+        Ok(())
+    }
+
+    /// Persists `count` and publishes `("count", action)` with it as data.
+    fn store(env: &Env, count: u32, action: Symbol) {
+        env.storage().instance().set(&DataKey::Count, &count);
+        env.events().publish((COUNT, action), count);
+    }
+}
+
+mod test;
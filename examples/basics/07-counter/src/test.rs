@@ -0,0 +1,177 @@
+//! Unit tests for the counter contract
+
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events as _},
+    vec, Env, IntoVal, TryFromVal,
+};
+
+fn setup() -> (Env, Address, Address, CounterContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, CounterContract);
+    let client = CounterContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    (env, contract_id, admin, client)
+}
+
+/// Asserts that the most recent event is `("count", action)` carrying `count`.
+fn assert_last_event(env: &Env, contract_id: &Address, action: Symbol, count: u32) {
+    let (id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(&id, contract_id);
+    assert_eq!(topics, (COUNT, action).into_val(env));
+    assert_eq!(u32::try_from_val(env, &data).unwrap(), count);
+}
+
+#[test]
+fn test_count_starts_at_zero() {
+    let (_env, _contract_id, _admin, client) = setup();
+    assert_eq!(client.get(), 0);
+}
+
+#[test]
+fn test_count_persists_across_invocations() {
+    let (_env, _contract_id, _admin, client) = setup();
+    assert_eq!(client.increment(), 1);
+    assert_eq!(client.increment(), 2);
+    assert_eq!(client.increment(), 3);
+    assert_eq!(client.decrement(), 2);
+    assert_eq!(client.get(), 2);
+}
+
+#[test]
+fn test_decrement_at_zero_is_rejected() {
+    let (env, _contract_id, _admin, client) = setup();
+    let events_before = env.events().all().len();
+
+    assert_eq!(client.try_decrement(), Err(Ok(Error::Underflow)));
+    assert_eq!(client.get(), 0);
+    assert_eq!(env.events().all().len(), events_before);
+}
+
+#[test]
+fn test_decrement_stops_at_zero_floor() {
+    let (_env, _contract_id, _admin, client) = setup();
+    client.increment();
+    assert_eq!(client.decrement(), 0);
+    assert_eq!(client.try_decrement(), Err(Ok(Error::Underflow)));
+}
+
+#[test]
+fn test_increment_overflow_is_typed_error() {
+    let (env, contract_id, _admin, client) = setup();
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::Count, &u32::MAX);
+    });
+
+    let result = client.try_increment();
+    assert_eq!(result, Err(Ok(Error::MathOverflow.into())));
+    assert_eq!(client.get(), u32::MAX);
+}
+
+#[test]
+fn test_increment_and_decrement_events() {
+    let (env, contract_id, _admin, client) = setup();
+
+    client.increment();
+    assert_last_event(&env, &contract_id, symbol_short!("inc"), 1);
+    client.increment();
+    assert_last_event(&env, &contract_id, symbol_short!("inc"), 2);
+    client.decrement();
+    assert_last_event(&env, &contract_id, symbol_short!("dec"), 1);
+}
+
+#[test]
+fn test_admin_can_reset() {
+    let (env, contract_id, admin, client) = setup();
+    client.increment();
+    client.increment();
+
+    client.reset(&admin);
+    assert_eq!(client.get(), 0);
+    assert_last_event(&env, &contract_id, symbol_short!("reset"), 0);
+}
+
+#[test]
+fn test_non_admin_cannot_reset() {
+    let (env, _contract_id, _admin, client) = setup();
+    let stranger = Address::generate(&env);
+    client.increment();
+
+    assert_eq!(client.try_reset(&stranger), Err(Ok(Error::NotAdmin)));
+    assert_eq!(client.get(), 1);
+}
+
+#[test]
+fn test_initialize_twice_fails() {
+    let (env, _contract_id, _admin, client) = setup();
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_initialize(&other),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_event_sequence_matches_operations() {
+    let (env, contract_id, admin, client) = setup();
+    client.increment();
+    client.decrement();
+    client.reset(&admin);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 3..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (COUNT, symbol_short!("inc")).into_val(&env),
+                1u32.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (COUNT, symbol_short!("dec")).into_val(&env),
+                0u32.into_val(&env),
+            ),
+            (
+                contract_id,
+                (COUNT, symbol_short!("reset")).into_val(&env),
+                0u32.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_pause_blocks_increment_and_decrement() {
+    let (env, contract_id, admin, client) = setup();
+    client.increment();
+    client.pause(&admin);
+    assert!(client.is_paused());
+
+    let (id, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(id, contract_id);
+    assert_eq!(
+        topics,
+        (recipes_pausable::NAMESPACE, symbol_short!("paused")).into_val(&env)
+    );
+
+    assert_eq!(client.try_increment(), Err(Ok(Error::Paused.into())));
+    assert_eq!(client.try_decrement(), Err(Ok(Error::Paused)));
+    assert_eq!(client.get(), 1);
+
+    client.unpause(&admin);
+    assert_eq!(client.increment(), 2);
+}
+
+#[test]
+fn test_non_admin_cannot_pause() {
+    let (env, _contract_id, _admin, client) = setup();
+    let stranger = Address::generate(&env);
+    assert_eq!(client.try_pause(&stranger), Err(Ok(Error::NotAdmin)));
+    assert!(!client.is_paused());
+}
@@ -0,0 +1,122 @@
+//! # Custom Types and Collections
+//!
+//! A tiny address book showing how compound values live in storage:
+//!
+//! - a `#[contracttype]` struct (`Profile`) with a `String` field,
+//! - a `Map<Symbol, Address>` per owner,
+//! - a nested enum key (`DataKey::Book(BookKey)`) to namespace entries.
+//!
+//! Compound values are stored as a single ledger entry, so every update is a
+//! read-modify-write: load the whole value, change it in memory, write the
+//! whole value back. Soroban executes a contract invocation atomically, so
+//! there is no window for another call to interleave between the read and
+//! the write.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, String, Symbol};
+
+/// Per-owner keys of the address book.
+#[contracttype]
+#[derive(Clone)]
+pub enum BookKey {
+    Contacts(Address),
+    Profile(Address),
+}
+
+/// Top-level storage keys. Wrapping `BookKey` in a variant keeps every
+/// address-book entry in its own namespace, separate from any contract-wide
+/// keys added later.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Book(BookKey),
+}
+
+/// Public profile attached to an owner's address book.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Profile {
+    pub display: String,
+    pub created_at: u64,
+}
+
+#[contract]
+pub struct AddressBookContract;
+
+#[contractimpl]
+impl AddressBookContract {
+    // ==================== CONTACTS (Map) ====================
+
+    /// Add or overwrite the contact `name` in `owner`'s address book.
+    pub fn add_contact(env: Env, owner: Address, name: Symbol, addr: Address) {
+        owner.require_auth();
+        let mut contacts = Self::get_contacts(env.clone(), owner.clone());
+        contacts.set(name, addr);
+        Self::save_contacts(&env, &owner, &contacts);
+    }
+
+    /// Remove the contact `name`. Returns whether it existed.
+    pub fn remove_contact(env: Env, owner: Address, name: Symbol) -> bool {
+        owner.require_auth();
+        let mut contacts = Self::get_contacts(env.clone(), owner.clone());
+        if contacts.remove(name).is_none() {
+            return false;
+        }
+        Self::save_contacts(&env, &owner, &contacts);
+        true
+    }
+
+    /// All contacts of `owner`, or an empty map if none were ever added.
+    pub fn get_contacts(env: Env, owner: Address) -> Map<Symbol, Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Book(BookKey::Contacts(owner)))
+            .unwrap_or(Map::new(&env))
+    }
+
+    // ==================== PROFILE (struct) ====================
+
+    /// Set the display name of `owner`'s profile.
+    ///
+    /// `created_at` is stamped on the first call and kept on later updates,
+    /// which is why this reads the existing struct before writing it back.
+    pub fn set_profile(env: Env, owner: Address, display: String) -> Profile {
+        owner.require_auth();
+        let key = DataKey::Book(BookKey::Profile(owner));
+        let profile = match env.storage().persistent().get::<_, Profile>(&key) {
+            Some(mut existing) => {
+                existing.display = display;
+                existing
+            }
+            None => Profile {
+                display,
+                created_at: env.ledger().timestamp(),
+            },
+        };
+        env.storage().persistent().set(&key, &profile);
+        profile
+    }
+
+    /// Profile of `owner`, if one was set.
+    pub fn get_profile(env: Env, owner: Address) -> Option<Profile> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Book(BookKey::Profile(owner)))
+    }
+}
+
+impl AddressBookContract {
+    /// Writes the contact map back, dropping the entry once it is empty so
+    /// an emptied address book does not keep paying rent.
+    fn save_contacts(env: &Env, owner: &Address, contacts: &Map<Symbol, Address>) {
+        let key = DataKey::Book(BookKey::Contacts(owner.clone()));
+        if contacts.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, contacts);
+        }
+    }
+}
+
+mod test;
@@ -0,0 +1,126 @@
+//! Unit tests for the address book contract
+
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Env,
+};
+
+fn setup() -> (Env, Address, AddressBookContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AddressBookContract);
+    let client = AddressBookContractClient::new(&env, &contract_id);
+    (env, contract_id, client)
+}
+
+#[test]
+fn test_empty_book_defaults_to_empty_map() {
+    let (env, _contract_id, client) = setup();
+    let owner = Address::generate(&env);
+
+    let contacts = client.get_contacts(&owner);
+    assert!(contacts.is_empty());
+    assert_eq!(client.get_profile(&owner), None);
+}
+
+#[test]
+fn test_map_grows_with_each_contact() {
+    let (env, _contract_id, client) = setup();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.add_contact(&owner, &symbol_short!("alice"), &alice);
+    assert_eq!(client.get_contacts(&owner).len(), 1);
+
+    client.add_contact(&owner, &symbol_short!("bob"), &bob);
+    let contacts = client.get_contacts(&owner);
+    assert_eq!(contacts.len(), 2);
+    assert_eq!(contacts.get(symbol_short!("alice")), Some(alice));
+    assert_eq!(contacts.get(symbol_short!("bob")), Some(bob));
+}
+
+#[test]
+fn test_overwriting_a_name_replaces_the_address() {
+    let (env, _contract_id, client) = setup();
+    let owner = Address::generate(&env);
+    let old = Address::generate(&env);
+    let new = Address::generate(&env);
+
+    client.add_contact(&owner, &symbol_short!("alice"), &old);
+    client.add_contact(&owner, &symbol_short!("alice"), &new);
+
+    let contacts = client.get_contacts(&owner);
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts.get(symbol_short!("alice")), Some(new));
+}
+
+#[test]
+fn test_remove_contact() {
+    let (env, contract_id, client) = setup();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.add_contact(&owner, &symbol_short!("alice"), &alice);
+    client.add_contact(&owner, &symbol_short!("bob"), &bob);
+
+    assert!(client.remove_contact(&owner, &symbol_short!("alice")));
+    assert!(!client.remove_contact(&owner, &symbol_short!("alice")));
+    let contacts = client.get_contacts(&owner);
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts.get(symbol_short!("bob")), Some(bob));
+
+    // Removing the last contact drops the storage entry entirely.
+    assert!(client.remove_contact(&owner, &symbol_short!("bob")));
+    let key = DataKey::Book(BookKey::Contacts(owner.clone()));
+    let stored = env.as_contract(&contract_id, || env.storage().persistent().has(&key));
+    assert!(!stored);
+    assert!(client.get_contacts(&owner).is_empty());
+}
+
+#[test]
+fn test_books_are_isolated_per_owner() {
+    let (env, _contract_id, client) = setup();
+    let owner_a = Address::generate(&env);
+    let owner_b = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    client.add_contact(&owner_a, &symbol_short!("alice"), &alice);
+
+    assert_eq!(client.get_contacts(&owner_a).len(), 1);
+    assert!(client.get_contacts(&owner_b).is_empty());
+}
+
+#[test]
+fn test_profile_round_trips_with_string_field() {
+    let (env, _contract_id, client) = setup();
+    let owner = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_700);
+
+    let display = String::from_str(&env, "Ada Lovelace 🚀");
+    let written = client.set_profile(&owner, &display);
+
+    let read = client.get_profile(&owner).unwrap();
+    assert_eq!(read, written);
+    assert_eq!(read.display, display);
+    assert_eq!(read.created_at, 1_700);
+}
+
+#[test]
+fn test_profile_update_keeps_created_at() {
+    let (env, _contract_id, client) = setup();
+    let owner = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.set_profile(&owner, &String::from_str(&env, "first"));
+
+    env.ledger().with_mut(|li| li.timestamp = 900);
+    client.set_profile(&owner, &String::from_str(&env, "second"));
+
+    let profile = client.get_profile(&owner).unwrap();
+    assert_eq!(profile.display, String::from_str(&env, "second"));
+    assert_eq!(profile.created_at, 100);
+}
@@ -0,0 +1,247 @@
+//! # Basic NFT
+//!
+//! A minimal non-fungible token with:
+//!
+//! - sequential ids minted by an admin,
+//! - owner transfers plus per-token approvals and operators (approval for
+//!   all of an owner's tokens),
+//! - admin-set metadata URIs returned as `String`,
+//! - per-owner enumeration with `tokens_of(owner, start, limit)`.
+//!
+//! Events follow the `(namespace, action, [indexes...])` layout from the
+//! events example, with the token id as a numeric topic so indexers can
+//! follow one token's history directly.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String,
+    Symbol, Vec,
+};
+
+const NAMESPACE: Symbol = symbol_short!("nft");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    NextId,
+    Owner(u64),
+    Approved(u64),
+    /// `(owner, operator)`: operator may move all of owner's tokens.
+    Operator(Address, Address),
+    Uri(u64),
+    /// Ids held by an owner, in acquisition order.
+    Owned(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum NftError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAdmin = 3,
+    TokenNotFound = 4,
+    NotOwner = 5,
+    /// The spender is neither approved for the token nor an operator.
+    NotApproved = 6,
+}
+
+#[contract]
+pub struct NftContract;
+
+#[contractimpl]
+impl NftContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), NftError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(NftError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::NextId, &1_u64);
+        Ok(())
+    }
+
+    /// Mint the next id to `to`. Ids start at 1.
+    pub fn mint(env: Env, admin: Address, to: Address) -> Result<u64, NftError> {
+        Self::require_admin(&env, &admin)?;
+        let id: u64 = env.storage().instance().get(&DataKey::NextId).unwrap_or(1);
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        env.storage().persistent().set(&DataKey::Owner(id), &to);
+        Self::add_owned(&env, &to, id);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("mint"), id), to);
+        Ok(id)
+    }
+
+    pub fn owner_of(env: Env, id: u64) -> Result<Address, NftError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Owner(id))
+            .ok_or(NftError::TokenNotFound)
+    }
+
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        Self::owned(&env, &owner).len()
+    }
+
+    /// Move `id` from its owner `from` to `to`.
+    pub fn transfer(env: Env, from: Address, to: Address, id: u64) -> Result<(), NftError> {
+        from.require_auth();
+        if Self::owner_of(env.clone(), id)? != from {
+            return Err(NftError::NotOwner);
+        }
+        Self::move_token(&env, &from, &to, id);
+        Ok(())
+    }
+
+    /// Move `id` on behalf of `from`, as its approved spender or one of
+    /// `from`'s operators.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        id: u64,
+    ) -> Result<(), NftError> {
+        spender.require_auth();
+        if Self::owner_of(env.clone(), id)? != from {
+            return Err(NftError::NotOwner);
+        }
+        let approved = Self::get_approved(env.clone(), id) == Some(spender.clone());
+        if !approved && !Self::is_operator(env.clone(), from.clone(), spender) {
+            return Err(NftError::NotApproved);
+        }
+        Self::move_token(&env, &from, &to, id);
+        Ok(())
+    }
+
+    /// Let `spender` move token `id` once. Cleared on transfer.
+    pub fn approve_nft(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        id: u64,
+    ) -> Result<(), NftError> {
+        owner.require_auth();
+        if Self::owner_of(env.clone(), id)? != owner {
+            return Err(NftError::NotOwner);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Approved(id), &spender);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("approve"), id), (owner, spender));
+        Ok(())
+    }
+
+    pub fn get_approved(env: Env, id: u64) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Approved(id))
+    }
+
+    /// Grant or revoke `operator`'s right to move every token `owner` holds.
+    pub fn set_operator(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        env.events().publish(
+            (NAMESPACE, symbol_short!("operator"), owner, operator),
+            approved,
+        );
+    }
+
+    pub fn is_operator(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Operator(owner, operator))
+    }
+
+    pub fn set_token_uri(env: Env, admin: Address, id: u64, uri: String) -> Result<(), NftError> {
+        Self::require_admin(&env, &admin)?;
+        Self::owner_of(env.clone(), id)?;
+        env.storage().persistent().set(&DataKey::Uri(id), &uri);
+        env.events()
+            .publish((NAMESPACE, symbol_short!("meta"), id), uri);
+        Ok(())
+    }
+
+    /// The token's metadata URI, or an empty string if none was set.
+    pub fn token_uri(env: Env, id: u64) -> Result<String, NftError> {
+        Self::owner_of(env.clone(), id)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Uri(id))
+            .unwrap_or_else(|| String::from_str(&env, "")))
+    }
+
+    /// Up to `limit` of `owner`'s token ids, starting at position `start`.
+    pub fn tokens_of(env: Env, owner: Address, start: u32, limit: u32) -> Vec<u64> {
+        let owned = Self::owned(&env, &owner);
+        let end = start.saturating_add(limit).min(owned.len());
+        if start >= end {
+            return Vec::new(&env);
+        }
+        owned.slice(start..end)
+    }
+}
+
+impl NftContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), NftError> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(NftError::NotInitialized)?;
+        if stored != *admin {
+            return Err(NftError::NotAdmin);
+        }
+        Ok(())
+    }
+
+    fn move_token(env: &Env, from: &Address, to: &Address, id: u64) {
+        env.storage().persistent().remove(&DataKey::Approved(id));
+        env.storage().persistent().set(&DataKey::Owner(id), to);
+        Self::remove_owned(env, from, id);
+        Self::add_owned(env, to, id);
+        env.events().publish(
+            (NAMESPACE, symbol_short!("transfer"), id),
+            (from.clone(), to.clone()),
+        );
+    }
+
+    fn owned(env: &Env, owner: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Owned(owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn add_owned(env: &Env, owner: &Address, id: u64) {
+        let mut owned = Self::owned(env, owner);
+        owned.push_back(id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owned(owner.clone()), &owned);
+    }
+
+    fn remove_owned(env: &Env, owner: &Address, id: u64) {
+        let mut owned = Self::owned(env, owner);
+        if let Some(index) = owned.first_index_of(id) {
+            owned.remove(index);
+        }
+        let key = DataKey::Owned(owner.clone());
+        if owned.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &owned);
+        }
+    }
+}
+
+mod test;
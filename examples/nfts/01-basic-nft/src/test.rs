@@ -0,0 +1,160 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    vec, Env, IntoVal, String,
+};
+
+fn setup() -> (Env, Address, NftContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let client = NftContractClient::new(&env, &env.register_contract(None, NftContract));
+    client.initialize(&admin);
+    (env, admin, client)
+}
+
+#[test]
+fn test_mint_transfer_and_ownership() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    assert_eq!(client.mint(&admin, &alice), 1);
+    assert_eq!(client.mint(&admin, &alice), 2);
+    assert_eq!(client.owner_of(&1), alice);
+    assert_eq!(client.balance_of(&alice), 2);
+
+    client.transfer(&alice, &bob, &1);
+    assert_eq!(client.owner_of(&1), bob);
+    assert_eq!(client.balance_of(&alice), 1);
+    assert_eq!(client.balance_of(&bob), 1);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (NAMESPACE, symbol_short!("transfer"), 1_u64).into_val(&env)
+    );
+    let data: (Address, Address) = event.2.into_val(&env);
+    assert_eq!(data, (alice.clone(), bob));
+
+    assert_eq!(
+        client.try_transfer(&alice, &alice, &1),
+        Err(Ok(NftError::NotOwner))
+    );
+}
+
+#[test]
+fn test_only_admin_mints() {
+    let (env, _, client) = setup();
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_mint(&stranger, &stranger),
+        Err(Ok(NftError::NotAdmin))
+    );
+}
+
+#[test]
+fn test_approved_spender_transfer() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let id = client.mint(&admin, &alice);
+
+    assert_eq!(
+        client.try_transfer_from(&spender, &alice, &carol, &id),
+        Err(Ok(NftError::NotApproved))
+    );
+    client.approve_nft(&alice, &spender, &id);
+    assert_eq!(client.get_approved(&id), Some(spender.clone()));
+
+    client.transfer_from(&spender, &alice, &carol, &id);
+    assert_eq!(client.owner_of(&id), carol);
+    // The approval does not survive the transfer.
+    assert_eq!(client.get_approved(&id), None);
+    assert_eq!(
+        client.try_transfer_from(&spender, &carol, &alice, &id),
+        Err(Ok(NftError::NotApproved))
+    );
+}
+
+#[test]
+fn test_operator_moves_any_owned_token() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let first = client.mint(&admin, &alice);
+    let second = client.mint(&admin, &alice);
+
+    client.set_operator(&alice, &operator, &true);
+    client.transfer_from(&operator, &alice, &operator, &first);
+    client.transfer_from(&operator, &alice, &operator, &second);
+    assert_eq!(client.balance_of(&operator), 2);
+
+    client.set_operator(&alice, &operator, &false);
+    assert!(!client.is_operator(&alice, &operator));
+}
+
+#[test]
+fn test_nonexistent_token() {
+    let (env, _, client) = setup();
+    let alice = Address::generate(&env);
+    assert_eq!(client.try_owner_of(&99), Err(Ok(NftError::TokenNotFound)));
+    assert_eq!(
+        client.try_transfer(&alice, &alice, &99),
+        Err(Ok(NftError::TokenNotFound))
+    );
+    assert_eq!(client.try_token_uri(&99), Err(Ok(NftError::TokenNotFound)));
+}
+
+#[test]
+fn test_enumeration_consistent_after_transfers() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    for _ in 0..5 {
+        client.mint(&admin, &alice);
+    }
+
+    client.transfer(&alice, &bob, &2);
+    client.transfer(&alice, &bob, &4);
+
+    assert_eq!(client.tokens_of(&alice, &0, &10), vec![&env, 1, 3, 5]);
+    assert_eq!(client.tokens_of(&bob, &0, &10), vec![&env, 2, 4]);
+    // Paging.
+    assert_eq!(client.tokens_of(&alice, &1, &1), vec![&env, 3]);
+    assert_eq!(client.tokens_of(&alice, &3, &5), vec![&env]);
+
+    // Every enumerated id agrees with owner_of.
+    for id in client.tokens_of(&bob, &0, &10).iter() {
+        assert_eq!(client.owner_of(&id), bob);
+    }
+    assert_eq!(
+        client.balance_of(&alice) + client.balance_of(&bob),
+        5,
+        "no token lost or duplicated"
+    );
+}
+
+#[test]
+fn test_metadata_round_trip() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let id = client.mint(&admin, &alice);
+    assert_eq!(client.token_uri(&id), String::from_str(&env, ""));
+
+    let uri = String::from_str(&env, "ipfs://bafybeigdyrzt/1.json");
+    client.set_token_uri(&admin, &id, &uri);
+    assert_eq!(client.token_uri(&id), uri);
+
+    // Metadata follows the token, not the owner.
+    client.transfer(&alice, &admin, &id);
+    assert_eq!(client.token_uri(&id), uri);
+
+    assert_eq!(
+        client.try_set_token_uri(&alice, &id, &uri),
+        Err(Ok(NftError::NotAdmin))
+    );
+}
@@ -0,0 +1,367 @@
+//! Governance Scenarios: Timelock + Authentication + Events
+//!
+//! The timelock is the admin of the authentication contract, so every
+//! privileged change goes through a delayed, cancellable operation. The
+//! events contract acts as an observer: operations include a call to it, so
+//! the audit record is written in the same transaction as the change.
+//!
+//! Unlike `integration_tests.rs`, these scenarios register the contracts
+//! natively and need no prebuilt WASM. Each test asserts the final state of
+//! all three contracts and the full event stream, in order, across all
+//! three contract ids.
+
+#![cfg(test)]
+
+use authentication::{AuthContract, AuthContractClient, Role};
+use events::{AuditTrailEventData, EventsContract, EventsContractClient, TransferEventData};
+use recipes_testutils::advance_time;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    vec, Address, Bytes, ConversionError, Env, Error, IntoVal, InvokeError, Symbol, Val, Vec,
+};
+use timelock::{
+    Call, OperationState, TimelockContract, TimelockContractClient, TimelockError, MIN_DELAY,
+};
+
+struct Governance {
+    env: Env,
+    /// Signs for the timelock: queues, executes, cancels and pauses.
+    guardian: Address,
+    timelock: TimelockContractClient<'static>,
+    auth: AuthContractClient<'static>,
+    observer: EventsContractClient<'static>,
+}
+
+impl Governance {
+    /// Deploy all three contracts and hand the auth contract's admin role to
+    /// the timelock.
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        let timelock =
+            TimelockContractClient::new(&env, &env.register_contract(None, TimelockContract));
+        let auth = AuthContractClient::new(&env, &env.register_contract(None, AuthContract));
+        let observer =
+            EventsContractClient::new(&env, &env.register_contract(None, EventsContract));
+
+        let guardian = Address::generate(&env);
+        timelock.initialize(&guardian);
+        auth.initialize(&timelock.address);
+        assert_eq!(auth.get_admin(), Some(timelock.address.clone()));
+
+        Governance {
+            env,
+            guardian,
+            timelock,
+            auth,
+            observer,
+        }
+    }
+
+    fn call(&self, contract: &Address, function: &str, args: Vec<Val>) -> Call {
+        Call {
+            contract: contract.clone(),
+            function: Symbol::new(&self.env, function),
+            args,
+        }
+    }
+
+    /// `grant_role` on the auth contract, signed by the timelock.
+    fn grant(&self, account: &Address, role: Role) -> Call {
+        let args = (self.timelock.address.clone(), account.clone(), role).into_val(&self.env);
+        self.call(&self.auth.address, "grant_role", args)
+    }
+
+    /// `set_balance` on the auth contract, signed by the timelock.
+    fn seed(&self, account: &Address, amount: i128) -> Call {
+        let args = (self.timelock.address.clone(), account.clone(), amount).into_val(&self.env);
+        self.call(&self.auth.address, "set_balance", args)
+    }
+
+    /// An audit record on the observer, attributed to the timelock.
+    fn audit(&self, action: Symbol, details: Symbol) -> Call {
+        let args = (self.timelock.address.clone(), action, details).into_val(&self.env);
+        self.call(&self.observer.address, "audit_trail", args)
+    }
+
+    /// The event the auth contract publishes when the timelock becomes its
+    /// admin during `new`.
+    fn admin_granted(&self) -> (Address, Vec<Val>, Val) {
+        let topics = (
+            symbol_short!("role"),
+            self.timelock.address.clone(),
+            symbol_short!("admin"),
+            symbol_short!("grant"),
+        );
+        (
+            self.auth.address.clone(),
+            topics.into_val(&self.env),
+            (self.timelock.address.clone(), 0u64).into_val(&self.env),
+        )
+    }
+
+    fn timelock_event(&self, name: &str, data: impl IntoVal<Env, Val>) -> (Address, Vec<Val>, Val) {
+        (
+            self.timelock.address.clone(),
+            (Symbol::new(&self.env, name),).into_val(&self.env),
+            data.into_val(&self.env),
+        )
+    }
+}
+
+/// What a `try_` call into the timelock returns when it fails with `err`.
+fn rejected(err: TimelockError) -> Result<Result<(), ConversionError>, Result<Error, InvokeError>> {
+    Err(Ok(err.into()))
+}
+
+/// Scenario 1: a role grant proposed, delayed, executed and audited.
+#[test]
+fn test_governance_grant_through_timelock() {
+    let gov = Governance::new();
+    let env = &gov.env;
+    let alice = Address::generate(env);
+    let id = Bytes::from_slice(env, b"grant-alice-moderator");
+
+    // Queue: the grant and its audit record travel together.
+    let calls = vec![
+        env,
+        gov.grant(&alice, Role::Moderator),
+        gov.audit(symbol_short!("grant"), symbol_short!("moderator")),
+    ];
+    gov.timelock.queue_calls(&id, &calls, &MIN_DELAY);
+    assert_eq!(gov.timelock.get_calls(&id), calls);
+    assert!(!gov.auth.has_role(&alice, &Role::Moderator));
+
+    // Nothing happens before the delay, not even the audit record.
+    advance_time(env, MIN_DELAY - 1);
+    assert_eq!(
+        gov.timelock.try_execute(&id),
+        rejected(TimelockError::TooEarly)
+    );
+    assert!(!gov.auth.has_role(&alice, &Role::Moderator));
+
+    advance_time(env, 1);
+    gov.timelock.execute(&id);
+
+    // State across all three contracts.
+    assert!(gov.auth.has_role(&alice, &Role::Moderator));
+    assert_eq!(gov.auth.get_role(&alice), Role::Moderator as u32);
+    assert_eq!(gov.timelock.get_state(&id), OperationState::Unknown);
+    assert_eq!(gov.timelock.get_calls(&id), Vec::new(env));
+
+    // The combined stream reads as the scenario: admin hand-over, proposal,
+    // then grant, audit and execution in one transaction.
+    let now = MIN_DELAY;
+    assert_eq!(
+        env.events().all(),
+        vec![
+            env,
+            gov.admin_granted(),
+            gov.timelock_event("queued", (id.clone(), now)),
+            (
+                gov.auth.address.clone(),
+                (
+                    symbol_short!("role"),
+                    alice.clone(),
+                    symbol_short!("moderator"),
+                    symbol_short!("grant"),
+                )
+                    .into_val(env),
+                (gov.timelock.address.clone(), now).into_val(env),
+            ),
+            (
+                gov.observer.address.clone(),
+                (
+                    symbol_short!("events"),
+                    symbol_short!("audit"),
+                    gov.timelock.address.clone(),
+                    symbol_short!("grant"),
+                )
+                    .into_val(env),
+                AuditTrailEventData {
+                    details: symbol_short!("moderator"),
+                    timestamp: now,
+                    sequence: 0,
+                }
+                .into_val(env),
+            ),
+            gov.timelock_event("executed", (id.clone(), now)),
+        ]
+    );
+
+    // Executed operations cannot be replayed.
+    assert_eq!(
+        gov.timelock.try_execute(&id),
+        rejected(TimelockError::OperationNotFound)
+    );
+}
+
+/// Scenario 2: a suspicious grant is caught while pending. The guardian
+/// pauses the timelock, cancels the operation and resumes.
+#[test]
+fn test_pause_during_incident() {
+    let gov = Governance::new();
+    let env = &gov.env;
+    let mallory = Address::generate(env);
+    let id = Bytes::from_slice(env, b"grant-mallory-admin");
+
+    gov.timelock.queue_calls(
+        &id,
+        &vec![env, gov.grant(&mallory, Role::Admin)],
+        &MIN_DELAY,
+    );
+
+    // Incident: freeze the timelock and note it with the observer.
+    gov.timelock.pause();
+    gov.observer.audit_trail(
+        &gov.guardian,
+        &symbol_short!("incident"),
+        &symbol_short!("paused"),
+    );
+    assert!(gov.timelock.is_paused());
+
+    // Even after the delay, paused operations cannot run, and new ones
+    // cannot be queued.
+    advance_time(env, MIN_DELAY);
+    assert_eq!(
+        gov.timelock.try_execute(&id),
+        rejected(TimelockError::Paused)
+    );
+    let other = Bytes::from_slice(env, b"other");
+    assert_eq!(
+        gov.timelock.try_queue(&other, &MIN_DELAY),
+        rejected(TimelockError::Paused)
+    );
+
+    // Cancelling stays available while paused.
+    assert_eq!(gov.timelock.get_state(&id), OperationState::Ready);
+    gov.timelock.cancel(&id);
+    gov.timelock.unpause();
+
+    // The grant is gone for good.
+    assert_eq!(
+        gov.timelock.try_execute(&id),
+        rejected(TimelockError::OperationNotFound)
+    );
+    assert!(!gov.auth.has_role(&mallory, &Role::Admin));
+    assert!(gov.auth.try_get_role(&mallory).is_err());
+    assert_eq!(gov.timelock.get_state(&id), OperationState::Unknown);
+    assert_eq!(gov.timelock.get_calls(&id), Vec::new(env));
+    assert!(!gov.timelock.is_paused());
+
+    // Failed attempts leave no trace; the auth contract never saw the grant.
+    assert_eq!(
+        env.events().all(),
+        vec![
+            env,
+            gov.admin_granted(),
+            gov.timelock_event("queued", (id.clone(), MIN_DELAY)),
+            (
+                gov.timelock.address.clone(),
+                (symbol_short!("pausable"), symbol_short!("paused")).into_val(env),
+                gov.guardian.into_val(env),
+            ),
+            (
+                gov.observer.address.clone(),
+                (
+                    symbol_short!("events"),
+                    symbol_short!("audit"),
+                    gov.guardian.clone(),
+                    symbol_short!("incident"),
+                )
+                    .into_val(env),
+                AuditTrailEventData {
+                    details: symbol_short!("paused"),
+                    timestamp: 0,
+                    sequence: 0,
+                }
+                .into_val(env),
+            ),
+            gov.timelock_event("cancelled", id.clone()),
+            (
+                gov.timelock.address.clone(),
+                (symbol_short!("pausable"), symbol_short!("unpaused")).into_val(env),
+                gov.guardian.into_val(env),
+            ),
+        ]
+    );
+}
+
+/// Scenario 3: initial balances are seeded by a single timelocked
+/// operation, each credit mirrored as an observer transfer record, and the
+/// seeded funds are then spendable.
+#[test]
+fn test_balance_seeding_via_timelock() {
+    let gov = Governance::new();
+    let env = &gov.env;
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+    let id = Bytes::from_slice(env, b"seed-genesis");
+
+    let record = |to: &Address, amount: i128| {
+        let args = (gov.timelock.address.clone(), to.clone(), amount, 1u64).into_val(env);
+        gov.call(&gov.observer.address, "transfer", args)
+    };
+    let calls = vec![
+        env,
+        gov.seed(&alice, 1_000),
+        record(&alice, 1_000),
+        gov.seed(&bob, 500),
+        record(&bob, 500),
+    ];
+    gov.timelock.queue_calls(&id, &calls, &(2 * MIN_DELAY));
+
+    advance_time(env, MIN_DELAY);
+    assert_eq!(
+        gov.timelock.try_execute(&id),
+        rejected(TimelockError::TooEarly)
+    );
+    assert_eq!(gov.auth.get_balance(&alice), 0);
+
+    advance_time(env, MIN_DELAY);
+    gov.timelock.execute(&id);
+    assert_eq!(gov.auth.get_balance(&alice), 1_000);
+    assert_eq!(gov.auth.get_balance(&bob), 500);
+
+    // Seeded funds move like any others, and the total is conserved.
+    let memo = Bytes::from_slice(env, b"inv-1");
+    gov.auth.transfer_with_memo(&alice, &bob, &250, &memo);
+    assert_eq!(gov.auth.get_balance(&alice), 750);
+    assert_eq!(gov.auth.get_balance(&bob), 750);
+    assert_eq!(
+        gov.auth.get_balance(&alice) + gov.auth.get_balance(&bob),
+        1_500
+    );
+
+    let now = 2 * MIN_DELAY;
+    let seeded = |to: &Address, amount: i128| {
+        (
+            gov.observer.address.clone(),
+            (
+                symbol_short!("events"),
+                symbol_short!("transfer"),
+                gov.timelock.address.clone(),
+                to.clone(),
+            )
+                .into_val(env),
+            TransferEventData { amount, memo: 1 }.into_val(env),
+        )
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            env,
+            gov.admin_granted(),
+            gov.timelock_event("queued", (id.clone(), now)),
+            seeded(&alice, 1_000),
+            seeded(&bob, 500),
+            gov.timelock_event("executed", (id.clone(), now)),
+            (
+                gov.auth.address.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(env),
+                (250i128, memo).into_val(env),
+            ),
+        ]
+    );
+}